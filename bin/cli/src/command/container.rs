@@ -181,6 +181,7 @@ pub async fn inspect_container(
         .read(InspectDockerContainer {
           container: c.name,
           server: c.server_id.context("No server...")?,
+          fields: Vec::new(),
         })
         .await
     })
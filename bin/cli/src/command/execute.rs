@@ -80,6 +80,9 @@ pub async fn handle(
     Execution::DestroyDeployment(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
+    Execution::RollbackDeployment(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
     Execution::BatchDestroyDeployment(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
@@ -122,6 +125,9 @@ pub async fn handle(
     Execution::DestroyContainer(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
+    Execution::CommitContainer(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
     Execution::StartAllContainers(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
@@ -152,6 +158,9 @@ pub async fn handle(
     Execution::PruneImages(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
+    Execution::TransferImage(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
     Execution::DeleteVolume(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
@@ -164,9 +173,36 @@ pub async fn handle(
     Execution::PruneBuildx(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
+    Execution::PruneBuildCache(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
     Execution::PruneSystem(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
+    Execution::RunServerExtension(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
+    Execution::KillSystemProcess(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
+    Execution::SetSystemProcessPriority(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
+    Execution::StartSystemdUnit(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
+    Execution::StopSystemdUnit(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
+    Execution::RestartSystemdUnit(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
+    Execution::RebootServer(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
+    Execution::ShutdownServer(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
     Execution::RunSync(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
@@ -215,6 +251,15 @@ pub async fn handle(
     Execution::RunStackService(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
+    Execution::ScaleStack(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
+    Execution::PromoteStack(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
+    Execution::MigrateStack(data) => {
+      println!("{}: {data:?}", "Data".dimmed())
+    }
     Execution::TestAlerter(data) => {
       println!("{}: {data:?}", "Data".dimmed())
     }
@@ -308,6 +353,10 @@ pub async fn handle(
       .execute(request)
       .await
       .map(|u| ExecutionResult::Single(u.into())),
+    Execution::RollbackDeployment(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
     Execution::BatchDestroyDeployment(request) => {
       client.execute(request).await.map(ExecutionResult::Batch)
     }
@@ -360,6 +409,10 @@ pub async fn handle(
       .execute(request)
       .await
       .map(|u| ExecutionResult::Single(u.into())),
+    Execution::CommitContainer(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
     Execution::StartAllContainers(request) => client
       .execute(request)
       .await
@@ -400,6 +453,10 @@ pub async fn handle(
       .execute(request)
       .await
       .map(|u| ExecutionResult::Single(u.into())),
+    Execution::TransferImage(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
     Execution::DeleteVolume(request) => client
       .execute(request)
       .await
@@ -416,10 +473,46 @@ pub async fn handle(
       .execute(request)
       .await
       .map(|u| ExecutionResult::Single(u.into())),
+    Execution::PruneBuildCache(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
     Execution::PruneSystem(request) => client
       .execute(request)
       .await
       .map(|u| ExecutionResult::Single(u.into())),
+    Execution::RunServerExtension(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
+    Execution::KillSystemProcess(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
+    Execution::SetSystemProcessPriority(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
+    Execution::StartSystemdUnit(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
+    Execution::StopSystemdUnit(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
+    Execution::RestartSystemdUnit(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
+    Execution::RebootServer(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
+    Execution::ShutdownServer(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
     Execution::RunSync(request) => client
       .execute(request)
       .await
@@ -480,6 +573,18 @@ pub async fn handle(
       .execute(request)
       .await
       .map(|u| ExecutionResult::Single(u.into())),
+    Execution::ScaleStack(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
+    Execution::PromoteStack(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
+    Execution::MigrateStack(request) => client
+      .execute(request)
+      .await
+      .map(|u| ExecutionResult::Single(u.into())),
     Execution::TestAlerter(request) => client
       .execute(request)
       .await
@@ -105,6 +105,91 @@ pub async fn send_alert(
         "{level} | **{name}**{region} disk usage at **{percentage:.1}%** 💿\nmount point: `{path:?}`\nusing **{used_gb:.1} GiB** / **{total_gb:.1} GiB**\n{link}"
       )
     }
+    AlertData::ServerDiskHealth {
+      id,
+      name,
+      region,
+      device,
+      model,
+      serial,
+      problems,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | **{name}**{region} disk `{device}` is **healthy** 🩺\nmodel: `{model}` | serial: `{serial}`\n{link}"
+        ),
+        _ => {
+          let problems = problems.join(", ");
+          format!(
+            "{level} | **{name}**{region} disk `{device}` is **failing** 🩺\nmodel: `{model}` | serial: `{serial}`\nproblems: **{problems}**\n{link}"
+          )
+        }
+      }
+    }
+    AlertData::ServerZfsPool {
+      id,
+      name,
+      region,
+      pool,
+      health,
+      scan,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      let scan = scan
+        .as_ref()
+        .map(|scan| format!("\nscan: `{scan}`"))
+        .unwrap_or_default();
+      format!(
+        "{level} | **{name}**{region} ZFS pool **{pool}** is **{health}** 🗄️{scan}\n{link}"
+      )
+    }
+    AlertData::ServerOsSecurityUpdates {
+      id,
+      name,
+      region,
+      package_manager,
+      packages,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | **{name}**{region} has **no pending security updates** 🛡️\n{link}"
+        ),
+        _ => {
+          let count = packages.len();
+          let packages = packages.join(", ");
+          format!(
+            "{level} | **{name}**{region} has **{count}** pending security updates (`{package_manager}`) 🛡️\npackages: `{packages}`\n{link}"
+          )
+        }
+      }
+    }
+    AlertData::ServerGpu {
+      id,
+      name,
+      region,
+      vendor,
+      index,
+      gpu,
+      mem_used_gb,
+      mem_total_gb,
+      temperature,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      let percentage = fmt_gpu_mem_perc(*mem_used_gb, *mem_total_gb);
+      let temperature = fmt_gpu_temperature(temperature);
+      format!(
+        "{level} | **{name}**{region} GPU {index} memory usage at **{percentage:.1}%**{temperature} 🎮
+gpu: `{vendor} {gpu}`
+using **{mem_used_gb:.1} GiB** / **{mem_total_gb:.1} GiB**
+{link}"
+      )
+    }
     AlertData::ContainerStateChange {
       id,
       name,
@@ -143,6 +228,23 @@ pub async fn send_alert(
         "⬆ Deployment **{name}** was updated automatically ⏫\nserver: **{server_name}**\nimage: **{image}**\n{link}"
       )
     }
+    AlertData::DeploymentUnhealthy {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      message,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Deployment, id);
+      match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | Deployment **{name}** is **healthy** again\nserver: **{server_name}**\n{link}"
+        ),
+        _ => format!(
+          "{level} | Deployment **{name}** is **unhealthy** ❌\nserver: **{server_name}**\n{link}\n**error**: {message}"
+        ),
+      }
+    }
     AlertData::StackStateChange {
       id,
       name,
@@ -238,6 +340,21 @@ pub async fn send_alert(
         }
       )
     }
+    AlertData::Alertmanager {
+      name, summary, url, ..
+    } => {
+      let mut content =
+        format!("{level} | **{name}** (Alertmanager)");
+      if !summary.is_empty() {
+        content.push('\n');
+        content.push_str(summary);
+      }
+      if !url.is_empty() {
+        content.push('\n');
+        content.push_str(url);
+      }
+      content
+    }
     AlertData::None {} => Default::default(),
   };
 
@@ -9,12 +9,16 @@ use komodo_client::entities::{
   alerter::*,
   deployment::DeploymentState,
   komodo_timestamp,
+  notification::{Notification, NotificationKind},
+  permission::PermissionLevel,
   stack::StackState,
 };
 
 use crate::helpers::query::get_variables_and_secrets;
 use crate::helpers::{
-  maintenance::is_in_maintenance, query::VariablesAndSecrets,
+  maintenance::is_in_maintenance,
+  notification::send_notifications,
+  query::{VariablesAndSecrets, get_user_permission_on_target},
 };
 use crate::{config::core_config, state::db_client};
 
@@ -28,6 +32,10 @@ pub async fn send_alerts(alerts: &[Alert]) {
     return;
   }
 
+  if let Err(e) = notify_users(alerts).await {
+    error!("ERROR sending alert notifications | {e:#}");
+  }
+
   let Ok(alerters) = find_collect(
     &db_client().alerters,
     doc! { "config.enabled": true },
@@ -49,6 +57,54 @@ pub async fn send_alerts(alerts: &[Alert]) {
   join_all(handles).await;
 }
 
+/// Adds the alerts to the notification inbox
+/// of every user able to see the alert target.
+async fn notify_users(alerts: &[Alert]) -> anyhow::Result<()> {
+  let users = find_collect(
+    &db_client().users,
+    doc! { "enabled": true, "config.type": { "$ne": "Service" } },
+    None,
+  )
+  .await
+  .context("failed to get users from db")?;
+  let ts = komodo_timestamp();
+  let mut notifications = Vec::new();
+  for alert in alerts {
+    if let AlertData::Test { .. } = alert.data {
+      continue;
+    }
+    let title = format!(
+      "{} | {:?}",
+      fmt_level(alert.level),
+      alert.data.extract_variant()
+    );
+    let body = standard_alert_content(alert);
+    for user in &users {
+      if !user.admin
+        && get_user_permission_on_target(user, &alert.target)
+          .await
+          .map(|permission| permission.level == PermissionLevel::None)
+          .unwrap_or(true)
+      {
+        continue;
+      }
+      notifications.push(Notification {
+        id: Default::default(),
+        user: user.id.clone(),
+        ts,
+        kind: NotificationKind::Alert,
+        title: title.clone(),
+        body: body.clone(),
+        target: alert.target.clone(),
+        source: alert.id.clone(),
+        read: false,
+        read_ts: 0,
+      });
+    }
+  }
+  send_notifications(notifications).await
+}
+
 async fn send_alert_to_alerters(alerters: &[Alerter], alert: &Alert) {
   if alerters.is_empty() {
     return;
@@ -202,6 +258,21 @@ fn fmt_region(region: &Option<String>) -> String {
   }
 }
 
+fn fmt_gpu_mem_perc(used_gb: f64, total_gb: f64) -> f64 {
+  if total_gb > 0.0 {
+    100.0 * used_gb / total_gb
+  } else {
+    0.0
+  }
+}
+
+fn fmt_gpu_temperature(temperature: &Option<f64>) -> String {
+  match temperature {
+    Some(temperature) => format!(" at {temperature:.0}°C"),
+    None => String::new(),
+  }
+}
+
 fn fmt_docker_container_state(state: &DeploymentState) -> String {
   match state {
     DeploymentState::Running => String::from("Running ▶️"),
@@ -339,6 +410,91 @@ fn standard_alert_content(alert: &Alert) -> String {
         "{level} | {name}{region} disk usage at {percentage:.1}%💿\nmount point: {path:?}\nusing {used_gb:.1} GiB / {total_gb:.1} GiB\n{link}",
       )
     }
+    AlertData::ServerDiskHealth {
+      id,
+      name,
+      region,
+      device,
+      model,
+      serial,
+      problems,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | {name}{region} disk {device} is healthy🩺\nmodel: {model} | serial: {serial}\n{link}",
+        ),
+        _ => {
+          let problems = problems.join(", ");
+          format!(
+            "{level} | {name}{region} disk {device} is failing🩺\nmodel: {model} | serial: {serial}\nproblems: {problems}\n{link}",
+          )
+        }
+      }
+    }
+    AlertData::ServerZfsPool {
+      id,
+      name,
+      region,
+      pool,
+      health,
+      scan,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      let scan = scan
+        .as_ref()
+        .map(|scan| format!("\nscan: {scan}"))
+        .unwrap_or_default();
+      format!(
+        "{level} | {name}{region} ZFS pool {pool} is {health}🗄️{scan}\n{link}",
+      )
+    }
+    AlertData::ServerOsSecurityUpdates {
+      id,
+      name,
+      region,
+      package_manager,
+      packages,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | {name}{region} has no pending security updates🛡️\n{link}",
+        ),
+        _ => {
+          let count = packages.len();
+          let packages = packages.join(", ");
+          format!(
+            "{level} | {name}{region} has {count} pending security updates ({package_manager})🛡️\npackages: {packages}\n{link}",
+          )
+        }
+      }
+    }
+    AlertData::ServerGpu {
+      id,
+      name,
+      region,
+      vendor,
+      index,
+      gpu,
+      mem_used_gb,
+      mem_total_gb,
+      temperature,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      let percentage = fmt_gpu_mem_perc(*mem_used_gb, *mem_total_gb);
+      let temperature = fmt_gpu_temperature(temperature);
+      format!(
+        "{level} | {name}{region} GPU {index} memory usage at {percentage:.1}%{temperature}🎮
+gpu: {vendor} {gpu}
+using {mem_used_gb:.1} GiB / {mem_total_gb:.1} GiB
+{link}",
+      )
+    }
     AlertData::ContainerStateChange {
       id,
       name,
@@ -377,6 +533,23 @@ fn standard_alert_content(alert: &Alert) -> String {
         "⬆ Deployment {name} was updated automatically\nserver: {server_name}\nimage: {image}\n{link}",
       )
     }
+    AlertData::DeploymentUnhealthy {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      message,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Deployment, id);
+      match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | Deployment {name} is healthy again\nserver: {server_name}\n{link}",
+        ),
+        _ => format!(
+          "{level} | Deployment {name} is unhealthy ❌\nserver: {server_name}\n{link}\nerror: {message}",
+        ),
+      }
+    }
     AlertData::StackStateChange {
       id,
       name,
@@ -472,6 +645,20 @@ fn standard_alert_content(alert: &Alert) -> String {
         }
       )
     }
+    AlertData::Alertmanager {
+      name, summary, url, ..
+    } => {
+      let mut content = format!("{level} | {name} (Alertmanager)");
+      if !summary.is_empty() {
+        content.push('\n');
+        content.push_str(summary);
+      }
+      if !url.is_empty() {
+        content.push('\n');
+        content.push_str(url);
+      }
+      content
+    }
     AlertData::None {} => Default::default(),
   }
 }
@@ -237,6 +237,143 @@ pub async fn send_alert(
         }
       }
     }
+    AlertData::ServerDiskHealth {
+      id,
+      name,
+      region,
+      device,
+      model,
+      serial,
+      problems,
+    } => {
+      let region = fmt_region(region);
+      let status = match alert.level {
+        SeverityLevel::Ok => String::from("is *healthy*"),
+        _ => format!("is *failing* | {}", problems.join(", ")),
+      };
+      let text = format!(
+        "{level} | *{name}*{region} disk *{device}* {status} 🩺"
+      );
+      let blocks = vec![
+        Block::header(level),
+        Block::section(format!(
+          "*{name}*{region} disk *{device}* {status} 🩺"
+        )),
+        Block::section(format!("model: {model} | serial: {serial}")),
+        Block::section(resource_link(
+          ResourceTargetVariant::Server,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ServerZfsPool {
+      id,
+      name,
+      region,
+      pool,
+      health,
+      scan,
+    } => {
+      let region = fmt_region(region);
+      let text = format!(
+        "{level} | *{name}*{region} ZFS pool *{pool}* is *{health}* 🗄️"
+      );
+      let mut blocks = vec![
+        Block::header(level),
+        Block::section(format!(
+          "*{name}*{region} ZFS pool *{pool}* is *{health}* 🗄️"
+        )),
+      ];
+      if let Some(scan) = scan {
+        blocks.push(Block::section(format!("scan: {scan}")));
+      }
+      blocks.push(Block::section(resource_link(
+        ResourceTargetVariant::Server,
+        id,
+      )));
+      (text, blocks.into())
+    }
+    AlertData::ServerOsSecurityUpdates {
+      id,
+      name,
+      region,
+      package_manager,
+      packages,
+    } => {
+      let region = fmt_region(region);
+      match alert.level {
+        SeverityLevel::Ok => {
+          let text = format!(
+            "{level} | *{name}*{region} has no pending security updates 🛡️"
+          );
+          let blocks = vec![
+            Block::header(level),
+            Block::section(format!(
+              "*{name}*{region} has no pending security updates 🛡️"
+            )),
+            Block::section(resource_link(
+              ResourceTargetVariant::Server,
+              id,
+            )),
+          ];
+          (text, blocks.into())
+        }
+        _ => {
+          let count = packages.len();
+          let text = format!(
+            "{level} | *{name}*{region} has *{count}* pending security updates 🛡️"
+          );
+          let blocks = vec![
+            Block::header(level),
+            Block::section(format!(
+              "*{name}*{region} has *{count}* pending security updates ({package_manager}) 🛡️"
+            )),
+            Block::section(format!(
+              "packages: {}",
+              packages.join(", ")
+            )),
+            Block::section(resource_link(
+              ResourceTargetVariant::Server,
+              id,
+            )),
+          ];
+          (text, blocks.into())
+        }
+      }
+    }
+    AlertData::ServerGpu {
+      id,
+      name,
+      region,
+      vendor,
+      index,
+      gpu,
+      mem_used_gb,
+      mem_total_gb,
+      temperature,
+    } => {
+      let region = fmt_region(region);
+      let percentage = fmt_gpu_mem_perc(*mem_used_gb, *mem_total_gb);
+      let temperature = fmt_gpu_temperature(temperature);
+      let text = format!(
+        "{level} | *{name}*{region} GPU {index} memory usage at *{percentage:.1}%*{temperature} | gpu: *{vendor} {gpu}* 🎮"
+      );
+      let blocks = vec![
+        Block::header(level),
+        Block::section(format!(
+          "*{name}*{region} GPU {index} memory usage at *{percentage:.1}%*{temperature} 🎮"
+        )),
+        Block::section(format!(
+          "gpu: {vendor} {gpu} | using *{mem_used_gb:.1} GiB* / *{mem_total_gb:.1} GiB*"
+        )),
+        Block::section(resource_link(
+          ResourceTargetVariant::Server,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
     AlertData::ContainerStateChange {
       name,
       server_name,
@@ -301,6 +438,33 @@ pub async fn send_alert(
       ];
       (text, blocks.into())
     }
+    AlertData::DeploymentUnhealthy {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      message,
+    } => {
+      let (text, details) = match alert.level {
+        SeverityLevel::Ok => (
+          format!("{level} | Deployment *{name}* is *healthy* again"),
+          format!("server: *{server_name}*"),
+        ),
+        _ => (
+          format!("{level} | Deployment *{name}* is *unhealthy* ❌"),
+          format!("server: *{server_name}*\nerror: {message}"),
+        ),
+      };
+      let blocks = vec![
+        Block::header(text.clone()),
+        Block::section(details),
+        Block::section(resource_link(
+          ResourceTargetVariant::Deployment,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
     AlertData::StackStateChange {
       name,
       server_name,
@@ -465,6 +629,19 @@ pub async fn send_alert(
         vec![Block::header(text.clone()), Block::section(details)];
       (text, blocks.into())
     }
+    AlertData::Alertmanager {
+      name, summary, url, ..
+    } => {
+      let text = format!("{level} | *{name}* (Alertmanager)");
+      let mut blocks = vec![Block::header(text.clone())];
+      if !summary.is_empty() {
+        blocks.push(Block::section(summary));
+      }
+      if !url.is_empty() {
+        blocks.push(Block::section(url));
+      }
+      (text, blocks.into())
+    }
     AlertData::None {} => Default::default(),
   };
   if text.is_empty() {
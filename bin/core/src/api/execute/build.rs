@@ -35,7 +35,9 @@ use komodo_client::{
   },
 };
 use periphery_client::api;
+use reqwest::StatusCode;
 use resolver_api::Resolve;
+use serror::AddStatusCode;
 use tokio_util::sync::CancellationToken;
 
 use crate::{
@@ -48,8 +50,12 @@ use crate::{
       VariablesAndSecrets, get_deployment_state,
       get_variables_and_secrets,
     },
+    quota::check_build_quota,
     registry_token,
-    update::{init_execution_update, update_update},
+    update::{
+      init_execution_update, request_streamed_to_update,
+      update_update,
+    },
   },
   permission::get_check_permissions,
   resource::{self, refresh_build_state_cache},
@@ -91,6 +97,10 @@ impl Resolve<ExecuteArgs> for RunBuild {
     )
     .await?;
 
+    check_build_quota(user, &update.id)
+      .await
+      .status_code(StatusCode::FORBIDDEN)?;
+
     let mut repo = if !build.config.files_on_host
       && !build.config.linked_repo.is_empty()
     {
@@ -284,8 +294,9 @@ impl Resolve<ExecuteArgs> for RunBuild {
     if all_logs_success(&update.logs) {
       // RUN BUILD
       let res = tokio::select! {
-        res = periphery
-          .request(api::build::Build {
+        res = request_streamed_to_update(
+          &periphery,
+          api::build::Build {
             build: build.clone(),
             repo,
             registry_tokens,
@@ -294,7 +305,9 @@ impl Resolve<ExecuteArgs> for RunBuild {
             commit_hash: optional_string(&update.commit_hash),
             // Unused for now
             additional_tags: Default::default(),
-          }) => res.context("failed at call to periphery to build"),
+          },
+          update.clone(),
+        ) => res.context("failed at call to periphery to build"),
         _ = cancel.cancelled() => {
           info!("build cancelled during build, cleaning up builder");
           update.push_error_log("build cancelled", String::from("user cancelled build during docker build"));
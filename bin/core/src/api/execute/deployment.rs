@@ -24,10 +24,12 @@ use resolver_api::Resolve;
 
 use crate::{
   helpers::{
+    health_probe::wait_for_healthy,
+    inherit::inherit_deployment_config,
     periphery_client,
     query::{VariablesAndSecrets, get_variables_and_secrets},
     registry_token,
-    update::update_update,
+    update::{request_streamed_to_update, update_update},
   },
   monitor::update_cache_for_server,
   permission::get_check_permissions,
@@ -182,47 +184,71 @@ impl Resolve<ExecuteArgs> for Deploy {
       }
     };
 
-    // interpolate variables / secrets, returning the sanitizing replacers to send to
-    // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
-    let secret_replacers = if !deployment.config.skip_secret_interp {
-      let VariablesAndSecrets { variables, secrets } =
-        get_variables_and_secrets().await?;
+    let replacers =
+      prepare_deployment_config(&mut deployment, &mut update).await?;
 
-      let mut interpolator =
-        Interpolator::new(Some(&variables), &secrets);
+    update.version = version;
+    update_update(update.clone()).await?;
 
-      interpolator
-        .interpolate_deployment(&mut deployment)?
-        .push_logs(&mut update.logs);
+    let periphery = periphery_client(&server).await?;
 
-      interpolator.secret_replacers
+    let request = api::container::Deploy {
+      deployment: deployment.clone(),
+      stop_signal: self.stop_signal,
+      stop_time: self.stop_time,
+      registry_token,
+      replacers: replacers.clone(),
+    };
+    // Streams the container status while waiting for the healthcheck.
+    let res = if deployment.config.wait_for_healthcheck {
+      request_streamed_to_update(&periphery, request, update.clone())
+        .await
     } else {
-      Default::default()
+      periphery.request(request).await
     };
 
-    update.version = version;
-    update_update(update.clone()).await?;
-
-    match periphery_client(&server)
-      .await?
-      .request(api::container::Deploy {
-        deployment,
-        stop_signal: self.stop_signal,
-        stop_time: self.stop_time,
-        registry_token,
-        replacers: secret_replacers.into_iter().collect(),
-      })
-      .await
-    {
-      Ok(log) => update.logs.push(log),
+    let deployed = match res {
+      Ok(log) => {
+        let deployed = log.success;
+        update.logs.push(log);
+        if deployed && !deployment.config.health_probe.is_none() {
+          update_update(update.clone()).await?;
+          let log = wait_for_healthy(&periphery, &deployment).await;
+          let healthy = log.success;
+          update.logs.push(log);
+          healthy
+        } else {
+          deployed
+        }
+      }
       Err(e) => {
         update.push_error_log(
           "Deploy Container",
           format_serror(&e.into()),
         );
+        false
       }
     };
 
+    if !deployed && deployment.config.rollback_on_failure {
+      update_update(update.clone()).await?;
+      let log = periphery
+        .request(api::container::RollbackDeploy {
+          deployment: deployment.clone(),
+          stop_signal: self.stop_signal,
+          stop_time: self.stop_time,
+          replacers,
+        })
+        .await
+        .unwrap_or_else(|e| {
+          Log::error(
+            "Rollback",
+            format_serror(&e.context("Failed to roll back").into()),
+          )
+        });
+      update.logs.push(log);
+    }
+
     update_cache_for_server(&server, true).await;
 
     update.finalize();
@@ -232,6 +258,90 @@ impl Resolve<ExecuteArgs> for Deploy {
   }
 }
 
+/// Applies the inherited config and interpolates variables / secrets,
+/// returning the sanitizing replacers to send to periphery so it may
+/// sanitize the final command for safe logging (avoids exposing secret values).
+async fn prepare_deployment_config(
+  deployment: &mut Deployment,
+  update: &mut Update,
+) -> anyhow::Result<Vec<(String, String)>> {
+  if let Some(log) = inherit_deployment_config(deployment).await? {
+    update.logs.push(log);
+  }
+
+  if deployment.config.skip_secret_interp {
+    return Ok(Vec::new());
+  }
+
+  let VariablesAndSecrets { variables, secrets } =
+    get_variables_and_secrets().await?;
+
+  let mut interpolator =
+    Interpolator::new(Some(&variables), &secrets);
+
+  interpolator
+    .interpolate_deployment(deployment)?
+    .push_logs(&mut update.logs);
+
+  Ok(interpolator.secret_replacers.into_iter().collect())
+}
+
+impl Resolve<ExecuteArgs> for RollbackDeployment {
+  #[instrument("RollbackDeployment", skip(user, update), fields(user_id = user.id, update_id = update.id))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let (mut deployment, server) =
+      setup_deployment_execution(&self.deployment, user).await?;
+
+    let action_state = action_states()
+      .deployment
+      .get_or_insert_default(&deployment.id)
+      .await;
+
+    let _action_guard =
+      action_state.update(|state| state.rolling_back = true)?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let replacers =
+      prepare_deployment_config(&mut deployment, &mut update).await?;
+
+    let periphery = periphery_client(&server).await?;
+    let wait_for_healthcheck = deployment.config.wait_for_healthcheck;
+    let request = api::container::RollbackDeploy {
+      deployment,
+      stop_signal: self.stop_signal,
+      stop_time: self.stop_time,
+      replacers,
+    };
+    let res = if wait_for_healthcheck {
+      request_streamed_to_update(&periphery, request, update.clone())
+        .await
+    } else {
+      periphery.request(request).await
+    };
+
+    let log = match res {
+      Ok(log) => log,
+      Err(e) => Log::error(
+        "Rollback",
+        format_serror(&e.context("Failed to roll back").into()),
+      ),
+    };
+
+    update.logs.push(log);
+    update_cache_for_server(&server, true).await;
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
 /// Wait this long after a pull to allow another pull through
 const PULL_TIMEOUT: i64 = 5_000;
 type ServerId = String;
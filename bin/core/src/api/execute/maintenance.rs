@@ -14,8 +14,10 @@ use komodo_client::{
     RotateAllServerKeys, RotateCoreKeys,
   },
   entities::{
-    deployment::DeploymentState, server::ServerState,
+    deployment::DeploymentState,
+    server::{Server, ServerState},
     stack::StackState,
+    update::Update,
   },
 };
 use periphery_client::api;
@@ -328,7 +330,7 @@ impl Resolve<ExecuteArgs> for GlobalAutoUpdate {
 //
 
 /// Makes sure the method can only be called once at a time
-fn global_rotate_lock() -> &'static Mutex<()> {
+pub fn global_rotate_lock() -> &'static Mutex<()> {
   static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
   LOCK.get_or_init(Default::default)
 }
@@ -358,89 +360,97 @@ impl Resolve<ExecuteArgs> for RotateAllServerKeys {
 
     update_update(update.clone()).await?;
 
-    let mut servers = db_client()
-      .servers
-      .find(Document::new())
-      .await
-      .context("Failed to query servers from database")?;
+    let servers =
+      find_collect(&db_client().servers, Document::new(), None)
+        .await
+        .context("Failed to query servers from database")?;
 
-    let server_status_cache = server_status_cache();
+    rotate_keys_for_servers(servers, &mut update).await;
 
-    let mut log = String::new();
+    update.finalize();
+    update_update(update.clone()).await?;
 
-    while let Some(server) = servers.next().await {
-      let server = match server {
-        Ok(server) => server,
-        Err(e) => {
-          warn!("Failed to parse Server | {e:#}");
-          continue;
-        }
-      };
-      if !server.config.auto_rotate_keys {
+    Ok(update)
+  }
+}
+
+/// Rotates the keys of the Servers with `auto_rotate_keys`,
+/// logging the results on the update.
+/// Returns the names of the Servers skipped as unreachable.
+pub async fn rotate_keys_for_servers(
+  servers: Vec<Server>,
+  update: &mut Update,
+) -> Vec<String> {
+  let server_status_cache = server_status_cache();
+
+  let mut log = String::new();
+  let mut unreachable = Vec::new();
+
+  for server in servers {
+    if !server.config.auto_rotate_keys {
+      let _ = write!(
+        &mut log,
+        "\nSkipping {}: Key Rotation Disabled ⚙️",
+        bold(&server.name)
+      );
+      continue;
+    }
+    let Some(status) = server_status_cache.get(&server.id).await
+    else {
+      let _ = write!(
+        &mut log,
+        "\nSkipping {}: No Status ⚠️",
+        bold(&server.name)
+      );
+      unreachable.push(server.name);
+      continue;
+    };
+    match status.state {
+      ServerState::Disabled => {
         let _ = write!(
           &mut log,
-          "\nSkipping {}: Key Rotation Disabled ⚙️",
+          "\nSkipping {}: Server Disabled ⚙️",
           bold(&server.name)
         );
         continue;
       }
-      let Some(status) = server_status_cache.get(&server.id).await
-      else {
+      ServerState::NotOk => {
         let _ = write!(
           &mut log,
-          "\nSkipping {}: No Status ⚠️",
+          "\nSkipping {}: Server Not Ok ⚠️",
           bold(&server.name)
         );
+        unreachable.push(server.name);
         continue;
-      };
-      match status.state {
-        ServerState::Disabled => {
-          let _ = write!(
-            &mut log,
-            "\nSkipping {}: Server Disabled ⚙️",
-            bold(&server.name)
-          );
-          continue;
-        }
-        ServerState::NotOk => {
-          let _ = write!(
-            &mut log,
-            "\nSkipping {}: Server Not Ok ⚠️",
-            bold(&server.name)
-          );
-          continue;
-        }
-        _ => {}
       }
-      match rotate_server_keys(&server).await {
-        Ok(_) => {
-          let _ = write!(
-            &mut log,
-            "\nRotated keys for {} ✅",
-            bold(&server.name)
-          );
-        }
-        Err(e) => {
-          update.push_error_log(
-            "Key Rotation Failure",
-            format_serror(
-              &e.context(format!(
-                "Failed to rotate {} keys",
-                bold(&server.name)
-              ))
-              .into(),
-            ),
-          );
-        }
+      _ => {}
+    }
+    match rotate_server_keys(&server).await {
+      Ok(_) => {
+        let _ = write!(
+          &mut log,
+          "\nRotated keys for {} ✅",
+          bold(&server.name)
+        );
+      }
+      Err(e) => {
+        update.push_error_log(
+          "Key Rotation Failure",
+          format_serror(
+            &e.context(format!(
+              "Failed to rotate {} keys",
+              bold(&server.name)
+            ))
+            .into(),
+          ),
+        );
       }
     }
+  }
 
-    update.push_simple_log("Rotate Server Keys", log);
-    update.finalize();
-    update_update(update.clone()).await?;
+  update.push_simple_log("Rotate Server Keys", log);
 
-    Ok(update)
-  }
+  unreachable
 }
 
 impl Resolve<ExecuteArgs> for RotateCoreKeys {
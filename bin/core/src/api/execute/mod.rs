@@ -2,10 +2,14 @@ use std::pin::Pin;
 
 use anyhow::Context;
 use axum::{
-  Extension, Router, extract::Path, middleware, routing::post,
+  Extension, Router,
+  extract::Path,
+  http::{HeaderMap, StatusCode},
+  middleware,
+  routing::post,
 };
 use axum_extra::{TypedHeader, headers::ContentType};
-use database::mungos::by_id::find_one_by_id;
+use database::mungos::{by_id::find_one_by_id, mongodb::bson::doc};
 use derive_variants::{EnumVariants, ExtractVariant};
 use formatting::format_serror;
 use futures::{
@@ -14,7 +18,7 @@ use futures::{
 use komodo_client::{
   api::execute::*,
   entities::{
-    Operation,
+    Operation, komodo_timestamp,
     permission::PermissionLevel,
     update::{Log, Update},
     user::User,
@@ -24,14 +28,17 @@ use resolver_api::Resolve;
 use response::JsonString;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use serror::Json;
+use serror::{AddStatusCode, Json};
 use strum::Display;
 use typeshare::typeshare;
 use uuid::Uuid;
 
 use crate::{
   auth::auth_request,
-  helpers::update::{init_execution_update, update_update},
+  helpers::{
+    maintenance::check_read_only,
+    update::{init_idempotent_execution_update, update_update},
+  },
   permission::get_check_permissions,
   resource::{KomodoResource, list_full_for_user_using_pattern},
   state::db_client,
@@ -51,7 +58,9 @@ mod sync;
 use super::Variant;
 
 pub use {
-  deployment::pull_deployment_inner, stack::pull_stack_inner,
+  deployment::pull_deployment_inner,
+  maintenance::{global_rotate_lock, rotate_keys_for_servers},
+  stack::pull_stack_inner,
 };
 
 pub struct ExecuteArgs {
@@ -76,6 +85,7 @@ pub enum ExecuteRequest {
   UnpauseContainer(UnpauseContainer),
   StopContainer(StopContainer),
   DestroyContainer(DestroyContainer),
+  CommitContainer(CommitContainer),
   StartAllContainers(StartAllContainers),
   RestartAllContainers(RestartAllContainers),
   PauseAllContainers(PauseAllContainers),
@@ -86,11 +96,21 @@ pub enum ExecuteRequest {
   PruneNetworks(PruneNetworks),
   DeleteImage(DeleteImage),
   PruneImages(PruneImages),
+  TransferImage(TransferImage),
   DeleteVolume(DeleteVolume),
   PruneVolumes(PruneVolumes),
   PruneDockerBuilders(PruneDockerBuilders),
   PruneBuildx(PruneBuildx),
+  PruneBuildCache(PruneBuildCache),
   PruneSystem(PruneSystem),
+  RunServerExtension(RunServerExtension),
+  KillSystemProcess(KillSystemProcess),
+  SetSystemProcessPriority(SetSystemProcessPriority),
+  StartSystemdUnit(StartSystemdUnit),
+  StopSystemdUnit(StopSystemdUnit),
+  RestartSystemdUnit(RestartSystemdUnit),
+  RebootServer(RebootServer),
+  ShutdownServer(ShutdownServer),
 
   // ==== STACK ====
   DeployStack(DeployStack),
@@ -107,6 +127,9 @@ pub enum ExecuteRequest {
   DestroyStack(DestroyStack),
   BatchDestroyStack(BatchDestroyStack),
   RunStackService(RunStackService),
+  ScaleStack(ScaleStack),
+  PromoteStack(PromoteStack),
+  MigrateStack(MigrateStack),
 
   // ==== DEPLOYMENT ====
   Deploy(Deploy),
@@ -118,6 +141,7 @@ pub enum ExecuteRequest {
   UnpauseDeployment(UnpauseDeployment),
   StopDeployment(StopDeployment),
   DestroyDeployment(DestroyDeployment),
+  RollbackDeployment(RollbackDeployment),
   BatchDestroyDeployment(BatchDestroyDeployment),
 
   // ==== BUILD ====
@@ -166,6 +190,7 @@ pub fn router() -> Router {
 
 async fn variant_handler(
   user: Extension<User>,
+  headers: HeaderMap,
   Path(Variant { variant }): Path<Variant>,
   Json(params): Json<serde_json::Value>,
 ) -> serror::Result<(TypedHeader<ContentType>, String)> {
@@ -173,21 +198,81 @@ async fn variant_handler(
     "type": variant,
     "params": params,
   }))?;
-  handler(user, Json(req)).await
+  handler(user, headers, Json(req)).await
 }
 
+/// Clients may pass this header with execute requests
+/// to make retrying them safe. If an Update was already created
+/// by the same user with the same key, it is returned
+/// instead of running the execution again.
+///
+/// Batch executions don't create their own Update,
+/// so the key has no effect on them.
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// How long an idempotency key is remembered for.
+const IDEMPOTENCY_KEY_TTL_MS: i64 = 24 * 60 * 60 * 1000;
+
 async fn handler(
   Extension(user): Extension<User>,
+  headers: HeaderMap,
   Json(request): Json<ExecuteRequest>,
 ) -> serror::Result<(TypedHeader<ContentType>, String)> {
-  let res = match inner_handler(request, user).await? {
-    ExecutionResult::Single(update) => serde_json::to_string(&update)
-      .context("Failed to serialize Update")?,
-    ExecutionResult::Batch(res) => res,
+  check_read_only().status_code(StatusCode::SERVICE_UNAVAILABLE)?;
+
+  let idempotency_key = headers
+    .get(IDEMPOTENCY_KEY_HEADER)
+    .map(|key| key.to_str().map(str::trim))
+    .transpose()
+    .context("Invalid idempotency key header")?
+    .filter(|key| !key.is_empty())
+    .map(str::to_string);
+
+  // Ensure concurrent retries with the same key
+  // can't both pass the check before either creates the Update.
+  static IDEMPOTENCY_LOCK: tokio::sync::Mutex<()> =
+    tokio::sync::Mutex::const_new(());
+  let _lock = match &idempotency_key {
+    Some(_) => Some(IDEMPOTENCY_LOCK.lock().await),
+    None => None,
   };
+
+  if let Some(key) = &idempotency_key
+    && let Some(update) = find_idempotent_update(key, &user).await?
+  {
+    let res = serde_json::to_string(&update)
+      .context("Failed to serialize Update")?;
+    return Ok((TypedHeader(ContentType::json()), res));
+  }
+
+  let res =
+    match idempotent_inner_handler(request, user, idempotency_key)
+      .await?
+    {
+      ExecutionResult::Single(update) => {
+        serde_json::to_string(&update)
+          .context("Failed to serialize Update")?
+      }
+      ExecutionResult::Batch(res) => res,
+    };
   Ok((TypedHeader(ContentType::json()), res))
 }
 
+async fn find_idempotent_update(
+  idempotency_key: &str,
+  user: &User,
+) -> anyhow::Result<Option<Update>> {
+  db_client()
+    .updates
+    .find_one(doc! {
+      "idempotency_key": idempotency_key,
+      "operator": &user.id,
+      "start_ts": { "$gte": komodo_timestamp() - IDEMPOTENCY_KEY_TTL_MS },
+    })
+    .await
+    .context("Failed to query for update with idempotency key")
+}
+
 #[typeshare(serialized_as = "Update")]
 type BoxUpdate = Box<Update>;
 
@@ -205,16 +290,37 @@ pub fn inner_handler(
     dyn std::future::Future<Output = anyhow::Result<ExecutionResult>>
       + Send,
   >,
+> {
+  idempotent_inner_handler(request, user, None)
+}
+
+fn idempotent_inner_handler(
+  request: ExecuteRequest,
+  user: User,
+  idempotency_key: Option<String>,
+) -> Pin<
+  Box<
+    dyn std::future::Future<Output = anyhow::Result<ExecutionResult>>
+      + Send,
+  >,
 > {
   Box::pin(async move {
     let req_id = Uuid::new_v4();
 
+    // Also covers internal executions, like auto update.
+    check_read_only()?;
+
     // Need to validate no cancel is active before any update is created.
     // This ensures no double update created if Cancel is called more than once for the same request.
     build::validate_cancel_build(&request).await?;
     repo::validate_cancel_repo_build(&request).await?;
 
-    let update = init_execution_update(&request, &user).await?;
+    let update = init_idempotent_execution_update(
+      &request,
+      &user,
+      idempotency_key,
+    )
+    .await?;
 
     // This will be the case for the Batch exections,
     // they don't have their own updates.
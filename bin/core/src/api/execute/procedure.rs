@@ -1,5 +1,6 @@
 use std::pin::Pin;
 
+use anyhow::anyhow;
 use database::mungos::{
   by_id::update_one_by_id, mongodb::bson::to_document,
 };
@@ -19,13 +20,14 @@ use komodo_client::{
 };
 use resolver_api::Resolve;
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 
 use crate::{
   alert::send_alerts,
   helpers::{procedure::execute_procedure, update::update_update},
   permission::get_check_permissions,
   resource::refresh_procedure_state_cache,
-  state::{action_states, db_client},
+  state::{action_states, db_client, procedure_cancels},
 };
 
 use super::{ExecuteArgs, ExecuteRequest};
@@ -119,7 +121,20 @@ fn resolve_inner(
 
     let update = Mutex::new(update);
 
-    let res = execute_procedure(&procedure, &update).await;
+    // Allows the schedule overlap policy to cancel this run.
+    let cancel = CancellationToken::new();
+    procedure_cancels()
+      .insert(procedure.id.clone(), cancel.clone())
+      .await;
+
+    let res = tokio::select! {
+      res = execute_procedure(&procedure, &update) => res,
+      _ = cancel.cancelled() => Err(anyhow!(
+        "The procedure run was cancelled by a newer scheduled run"
+      )),
+    };
+
+    procedure_cancels().remove(&procedure.id).await;
 
     let mut update = update.into_inner();
 
@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use formatting::format_serror;
 use komodo_client::{
   api::execute::*,
@@ -359,6 +359,74 @@ impl Resolve<ExecuteArgs> for DestroyContainer {
   }
 }
 
+impl Resolve<ExecuteArgs> for CommitContainer {
+  #[instrument("CommitContainer", skip(user, update), fields(user_id = user.id, update_id = update.id))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let CommitContainer {
+      server,
+      container,
+      image,
+      author,
+      message,
+      pause,
+    } = self;
+    let server = get_check_permissions::<Server>(
+      &server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    // get the action state for the server (or insert default).
+    let action_state = action_states()
+      .server
+      .get_or_insert_default(&server.id)
+      .await;
+
+    // Will check to ensure server not already busy before updating, and return Err if so.
+    // The returned guard will set the action state back to default when dropped.
+    let _action_guard = action_state
+      .update(|state| state.committing_container = true)?;
+
+    let mut update = update.clone();
+
+    // Send update after setting action state, this way frontend gets correct state.
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    let log = match periphery
+      .request(api::container::CommitContainer {
+        name: container,
+        image,
+        author: author.or_else(|| Some(user.username.clone())),
+        message,
+        pause,
+      })
+      .await
+    {
+      Ok(log) => log,
+      Err(e) => Log::error(
+        "commit container",
+        format_serror(
+          &e.context("failed to commit container").into(),
+        ),
+      ),
+    };
+
+    update.logs.push(log);
+    update_cache_for_server(&server, true).await;
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
 impl Resolve<ExecuteArgs> for StartAllContainers {
   #[instrument("StartAllContainers", skip(user, update), fields(user_id = user.id, update_id = update.id))]
   async fn resolve(
@@ -879,6 +947,70 @@ impl Resolve<ExecuteArgs> for PruneImages {
   }
 }
 
+impl Resolve<ExecuteArgs> for TransferImage {
+  #[instrument("TransferImage", skip(user, update), fields(user_id = user.id, update_id = update.id))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let from_server = get_check_permissions::<Server>(
+      &self.from_server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+    let to_server = get_check_permissions::<Server>(
+      &self.to_server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    // get the action state for the target server (or insert default).
+    let action_state = action_states()
+      .server
+      .get_or_insert_default(&to_server.id)
+      .await;
+
+    // Will check to ensure server not already busy before updating, and return Err if so.
+    // The returned guard will set the action state back to default when dropped.
+    let _action_guard =
+      action_state.update(|state| state.transferring_image = true)?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let res = async {
+      let from = periphery_client(&from_server).await?;
+      let to = periphery_client(&to_server).await?;
+      from.transfer_image(&to, &self.image).await
+    }
+    .await;
+
+    match res {
+      Ok(logs) => update.logs.extend(logs),
+      Err(e) => update.push_error_log(
+        "transfer image",
+        format_serror(
+          &e.context(format!(
+            "failed to transfer image {} from {} to {}",
+            self.image, from_server.name, to_server.name
+          ))
+          .into(),
+        ),
+      ),
+    };
+
+    update_cache_for_server(&to_server, true).await;
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
 impl Resolve<ExecuteArgs> for DeleteVolume {
   #[instrument("DeleteVolume", skip(user, update), fields(user_id = user.id, update_id = update.id))]
   async fn resolve(
@@ -1086,6 +1218,61 @@ impl Resolve<ExecuteArgs> for PruneBuildx {
   }
 }
 
+impl Resolve<ExecuteArgs> for PruneBuildCache {
+  #[instrument("PruneBuildCache", skip(user, update), fields(user_id = user.id, update_id = update.id))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    // get the action state for the server (or insert default).
+    let action_state = action_states()
+      .server
+      .get_or_insert_default(&server.id)
+      .await;
+
+    // Will check to ensure server not already busy before updating, and return Err if so.
+    // The returned guard will set the action state back to default when dropped.
+    let _action_guard =
+      action_state.update(|state| state.pruning_buildx = true)?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    match periphery
+      .request(api::build::PruneBuildCache {
+        keep_last: self.keep_last,
+      })
+      .await
+    {
+      Ok(logs) => update.logs.extend(logs),
+      Err(e) => update.push_error_log(
+        "prune build cache",
+        format!(
+          "failed to prune build cache on server {} | {e:#?}",
+          server.name
+        ),
+      ),
+    };
+
+    update_cache_for_server(&server, true).await;
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
 impl Resolve<ExecuteArgs> for PruneSystem {
   #[instrument("PruneSystem", skip(user, update), fields(user_id = user.id, update_id = update.id))]
   async fn resolve(
@@ -1136,3 +1323,354 @@ impl Resolve<ExecuteArgs> for PruneSystem {
     Ok(update)
   }
 }
+
+impl Resolve<ExecuteArgs> for RunServerExtension {
+  #[instrument("RunServerExtension", skip(self, user, update), fields(user_id = user.id, update_id = update.id, extension = self.extension, command = self.command))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    let log = match periphery
+      .request(api::extension::RunExtension {
+        extension: self.extension.clone(),
+        command: self.command.clone(),
+        params: self.params.unwrap_or_default(),
+      })
+      .await
+    {
+      Ok(log) => log,
+      Err(e) => Log::error(
+        &format!("{}.{}", self.extension, self.command),
+        format!(
+          "Failed to run extension {} on server {} | {e:#?}",
+          self.extension, server.name
+        ),
+      ),
+    };
+
+    update.logs.push(log);
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for KillSystemProcess {
+  #[instrument("KillSystemProcess", skip(self, user, update), fields(user_id = user.id, update_id = update.id, pid = self.pid))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Execute.processes(),
+    )
+    .await?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    let log = match periphery
+      .request(api::stats::KillSystemProcess {
+        pid: self.pid,
+        signal: self.signal.unwrap_or_default(),
+      })
+      .await
+    {
+      Ok(log) => log,
+      Err(e) => Log::error(
+        "kill process",
+        format_serror(&e.context("failed to kill process").into()),
+      ),
+    };
+
+    update.logs.push(log);
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for SetSystemProcessPriority {
+  #[instrument("SetSystemProcessPriority", skip(self, user, update), fields(user_id = user.id, update_id = update.id, pid = self.pid))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Execute.processes(),
+    )
+    .await?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    let log = match periphery
+      .request(api::stats::SetProcessPriority {
+        pid: self.pid,
+        nice: self.nice,
+      })
+      .await
+    {
+      Ok(log) => log,
+      Err(e) => Log::error(
+        "set process priority",
+        format_serror(
+          &e.context("failed to set process priority").into(),
+        ),
+      ),
+    };
+
+    update.logs.push(log);
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for StartSystemdUnit {
+  #[instrument("StartSystemdUnit", skip(self, user, update), fields(user_id = user.id, update_id = update.id, unit = self.unit))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    let log = match periphery
+      .request(api::systemd::StartUnit { unit: self.unit })
+      .await
+    {
+      Ok(log) => log,
+      Err(e) => Log::error(
+        "start unit",
+        format_serror(&e.context("failed to start unit").into()),
+      ),
+    };
+
+    update.logs.push(log);
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for StopSystemdUnit {
+  #[instrument("StopSystemdUnit", skip(self, user, update), fields(user_id = user.id, update_id = update.id, unit = self.unit))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    let log = match periphery
+      .request(api::systemd::StopUnit { unit: self.unit })
+      .await
+    {
+      Ok(log) => log,
+      Err(e) => Log::error(
+        "stop unit",
+        format_serror(&e.context("failed to stop unit").into()),
+      ),
+    };
+
+    update.logs.push(log);
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for RestartSystemdUnit {
+  #[instrument("RestartSystemdUnit", skip(self, user, update), fields(user_id = user.id, update_id = update.id, unit = self.unit))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    let log = match periphery
+      .request(api::systemd::RestartUnit { unit: self.unit })
+      .await
+    {
+      Ok(log) => log,
+      Err(e) => Log::error(
+        "restart unit",
+        format_serror(&e.context("failed to restart unit").into()),
+      ),
+    };
+
+    update.logs.push(log);
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for RebootServer {
+  #[instrument("RebootServer", skip(self, user, update), fields(user_id = user.id, update_id = update.id))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    check_power_action_confirmed(&server, &self.confirm)?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let logs = periphery_client(&server)
+      .await?
+      .request(api::host::RebootHost {})
+      .await
+      .context("failed to reboot host")?;
+
+    update.logs.extend(logs);
+
+    if all_logs_success(&update.logs) {
+      update.push_simple_log(
+        "reboot host",
+        String::from(
+          "The host is rebooting. The server will reconnect once it is back up.",
+        ),
+      );
+    }
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for ShutdownServer {
+  #[instrument("ShutdownServer", skip(self, user, update), fields(user_id = user.id, update_id = update.id))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Execute.into(),
+    )
+    .await?;
+
+    check_power_action_confirmed(&server, &self.confirm)?;
+
+    let mut update = update.clone();
+
+    update_update(update.clone()).await?;
+
+    let logs = periphery_client(&server)
+      .await?
+      .request(api::host::ShutdownHost {})
+      .await
+      .context("failed to shut down host")?;
+
+    update.logs.extend(logs);
+
+    if all_logs_success(&update.logs) {
+      update.push_simple_log(
+        "shutdown host",
+        String::from(
+          "The host is powering off. The server will be unreachable until it is powered back on.",
+        ),
+      );
+    }
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+/// Host power actions take down everything on the server,
+/// so the caller must pass the server name to confirm the target.
+fn check_power_action_confirmed(
+  server: &Server,
+  confirm: &str,
+) -> anyhow::Result<()> {
+  if confirm == server.name {
+    Ok(())
+  } else {
+    Err(anyhow!(
+      "Must pass the server name '{}' as 'confirm'",
+      server.name
+    ))
+  }
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashSet, str::FromStr};
 
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use database::mungos::mongodb::bson::{
   doc, oid::ObjectId, to_bson, to_document,
 };
@@ -14,30 +14,38 @@ use komodo_client::{
     repo::Repo,
     server::Server,
     stack::{
-      Stack, StackFileRequires, StackInfo, StackRemoteFileContents,
+      PartialStackConfig, Stack, StackFileRequires, StackInfo,
+      StackRemoteFileContents,
     },
     update::{Log, Update},
     user::User,
   },
 };
-use periphery_client::api::compose::*;
+use partial_derive2::{MaybeNone, PartialDiff};
+use periphery_client::api::{compose::*, swarm::DeploySwarmStack};
 use resolver_api::Resolve;
 
 use crate::{
   api::write::WriteArgs,
   helpers::{
+    inherit::inherit_stack_config,
     periphery_client,
     query::{VariablesAndSecrets, get_variables_and_secrets},
     stack_git_token,
     update::{
-      add_update_without_send, init_execution_update, update_update,
+      add_update_without_send, init_execution_update,
+      request_streamed_to_update, update_update,
     },
   },
   monitor::update_cache_for_server,
   permission::get_check_permissions,
   resource,
-  stack::{execute::execute_compose, get_stack_and_server},
+  stack::{
+    execute::execute_compose, get_stack_and_server,
+    migrate::migrate_stack,
+  },
   state::{action_states, db_client},
+  sync::toml::TOML_PRETTY_OPTIONS,
 };
 
 use super::{ExecuteArgs, ExecuteRequest};
@@ -80,6 +88,13 @@ impl Resolve<ExecuteArgs> for DeployStack {
     )
     .await?;
 
+    if stack.config.swarm && !self.services.is_empty() {
+      return Err(
+        anyhow!("Swarm stacks can only be deployed as a whole")
+          .into(),
+      );
+    }
+
     let mut repo = if !stack.config.files_on_host
       && !stack.config.linked_repo.is_empty()
     {
@@ -113,6 +128,10 @@ impl Resolve<ExecuteArgs> for DeployStack {
       ))
     }
 
+    if let Some(log) = inherit_stack_config(&mut stack).await? {
+      update.logs.push(log);
+    }
+
     let git_token =
       stack_git_token(&mut stack, repo.as_mut()).await?;
 
@@ -155,17 +174,39 @@ impl Resolve<ExecuteArgs> for DeployStack {
       compose_config,
       commit_hash,
       commit_message,
-    } = periphery_client(&server)
-      .await?
-      .request(ComposeUp {
+    } = if stack.config.swarm {
+      periphery_client(&server)
+        .await?
+        .request(DeploySwarmStack {
+          stack: stack.clone(),
+          repo,
+          git_token,
+          registry_token,
+          replacers: secret_replacers.into_iter().collect(),
+        })
+        .await?
+    } else {
+      let periphery = periphery_client(&server).await?;
+      let request = ComposeUp {
         stack: stack.clone(),
         services: self.services,
         repo,
         git_token,
         registry_token,
         replacers: secret_replacers.into_iter().collect(),
-      })
-      .await?;
+      };
+      // Streams the container status while waiting for the healthchecks.
+      if stack.config.wait_for_healthcheck {
+        request_streamed_to_update(
+          &periphery,
+          request,
+          update.clone(),
+        )
+        .await?
+      } else {
+        periphery.request(request).await?
+      }
+    };
 
     update.logs.extend(logs);
 
@@ -234,6 +275,7 @@ impl Resolve<ExecuteArgs> for DeployStack {
           .then_some(remote_errors),
         latest_hash: commit_hash,
         latest_message: commit_message,
+        migration: stack.info.migration,
       };
 
       let info = to_document(&info)
@@ -719,6 +761,13 @@ pub async fn pull_stack_inner(
     ))
   }
 
+  let inherit_log = inherit_stack_config(&mut stack).await?;
+  if let Some(update) = update.as_mut()
+    && let Some(log) = inherit_log
+  {
+    update.logs.push(log);
+  }
+
   let git_token = stack_git_token(&mut stack, repo.as_mut()).await?;
 
   let registry_token = crate::helpers::registry_token(
@@ -994,6 +1043,10 @@ impl Resolve<ExecuteArgs> for RunStackService {
     let mut update = update.clone();
     update_update(update.clone()).await?;
 
+    if let Some(log) = inherit_stack_config(&mut stack).await? {
+      update.logs.push(log);
+    }
+
     let git_token =
       stack_git_token(&mut stack, repo.as_mut()).await?;
 
@@ -1053,3 +1106,206 @@ impl Resolve<ExecuteArgs> for RunStackService {
     Ok(update)
   }
 }
+
+impl Resolve<ExecuteArgs> for ScaleStack {
+  #[instrument("ScaleStack", skip(user, update), fields(user_id = user.id, update_id = update.id))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let ScaleStack {
+      stack,
+      service,
+      replicas,
+    } = self;
+
+    if replicas < 0 {
+      return Err(anyhow!("Replicas cannot be negative").into());
+    }
+
+    let (stack, server) = get_stack_and_server(
+      &stack,
+      user,
+      PermissionLevel::Execute.into(),
+      true,
+    )
+    .await?;
+
+    let action_state =
+      action_states().stack.get_or_insert_default(&stack.id).await;
+
+    let _action_guard =
+      action_state.update(|state| state.scaling = true)?;
+
+    let mut update = update.clone();
+    update_update(update.clone()).await?;
+
+    let log = periphery_client(&server)
+      .await?
+      .request(ComposeScale {
+        project: stack.project_name(false),
+        service,
+        replicas,
+      })
+      .await
+      .context("Failed to scale service on Periphery")?;
+
+    update.logs.push(log);
+
+    update_cache_for_server(&server, true).await;
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for PromoteStack {
+  #[instrument("PromoteStack", skip(user, update), fields(user_id = user.id, update_id = update.id))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let from = get_check_permissions::<Stack>(
+      &self.from,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await?;
+    let to = get_check_permissions::<Stack>(
+      &self.to,
+      user,
+      PermissionLevel::Write.into(),
+    )
+    .await?;
+
+    if from.id == to.id {
+      return Err(anyhow!("Cannot promote a Stack to itself").into());
+    }
+
+    let mut update = update.clone();
+    update_update(update.clone()).await?;
+
+    // Minimize against the target config so only the changes remain.
+    let changes: PartialStackConfig =
+      to.config.partial_diff(promotion_config(&from)).into();
+
+    if changes.is_none() {
+      update.push_simple_log(
+        "Promote Stack",
+        format!(
+          "Stack {} is already up to date with {}",
+          to.name, from.name
+        ),
+      );
+    } else {
+      let changes_toml =
+        toml_pretty::to_string(&changes, TOML_PRETTY_OPTIONS)
+          .context("Failed to serialize promotion changes to toml")?;
+      update.push_simple_log("Promotion Changes", changes_toml);
+      if self.dry_run {
+        update.push_simple_log(
+          "Dry Run",
+          "Changes were not applied. Run again without dry run to promote.",
+        );
+      } else {
+        resource::update::<Stack>(&to.id, changes, user).await?;
+        update.push_simple_log(
+          "Promote Stack",
+          format!("Promoted Stack {} to {}", from.name, to.name),
+        );
+      }
+    }
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+impl Resolve<ExecuteArgs> for MigrateStack {
+  #[instrument("MigrateStack", skip(user, update), fields(user_id = user.id, update_id = update.id, to_server = self.to_server))]
+  async fn resolve(
+    self,
+    ExecuteArgs { user, update }: &ExecuteArgs,
+  ) -> serror::Result<Update> {
+    let stack = get_check_permissions::<Stack>(
+      &self.stack,
+      user,
+      PermissionLevel::Write.into(),
+    )
+    .await?;
+    let to_server = get_check_permissions::<Server>(
+      &self.to_server,
+      user,
+      PermissionLevel::Read.attach(),
+    )
+    .await?;
+
+    if action_states()
+      .stack
+      .get_or_insert_default(&stack.id)
+      .await
+      .busy()?
+    {
+      return Err(anyhow!("Stack is busy").into());
+    }
+
+    let mut update = update.clone();
+    update_update(update.clone()).await?;
+
+    if let Err(e) =
+      migrate_stack(stack, to_server, user, &mut update).await
+    {
+      update
+        .push_error_log("Migrate Stack", format_serror(&e.into()));
+    }
+
+    update.finalize();
+    update_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+/// The release config of the Stack being promoted.
+/// Excludes the Server, environment and deploy behavior,
+/// which are specific to each Stack.
+fn promotion_config(from: &Stack) -> PartialStackConfig {
+  let config = &from.config;
+  // Pin the commit currently deployed on the source,
+  // so later pushes to the branch aren't promoted with it.
+  let commit = if config.commit.is_empty() {
+    from
+      .info
+      .deployed_hash
+      .clone()
+      .or_else(|| from.info.latest_hash.clone())
+      .unwrap_or_default()
+  } else {
+    config.commit.clone()
+  };
+  PartialStackConfig {
+    linked_repo: Some(config.linked_repo.clone()),
+    git_provider: Some(config.git_provider.clone()),
+    git_https: Some(config.git_https),
+    git_account: Some(config.git_account.clone()),
+    repo: Some(config.repo.clone()),
+    branch: Some(config.branch.clone()),
+    commit: Some(commit),
+    files_on_host: Some(config.files_on_host),
+    run_directory: Some(config.run_directory.clone()),
+    file_paths: Some(config.file_paths.clone()),
+    env_file_path: Some(config.env_file_path.clone()),
+    additional_env_files: Some(config.additional_env_files.clone()),
+    config_files: Some(config.config_files.clone()),
+    file_contents: Some(config.file_contents.clone()),
+    run_build: Some(config.run_build),
+    build_extra_args: Some(config.build_extra_args.clone()),
+    extra_args: Some(config.extra_args.clone()),
+    ignore_services: Some(config.ignore_services.clone()),
+    ..Default::default()
+  }
+}
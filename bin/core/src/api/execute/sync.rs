@@ -380,6 +380,23 @@ impl Resolve<ExecuteArgs> for RunSync {
       Default::default()
     };
 
+    let (
+      onboarding_keys_to_update,
+      onboarding_keys_to_delete,
+      onboarding_keys_missing,
+    ) = if match_resource_type.is_none()
+      && match_resources.is_none()
+      && sync.config.include_onboarding_keys
+    {
+      crate::sync::onboarding_keys::get_updates_for_execution(
+        resources.onboarding_keys,
+        delete,
+      )
+      .await?
+    } else {
+      Default::default()
+    };
+
     if deploy_cache.is_empty()
       && resource_sync_deltas.no_changes()
       && server_deltas.no_changes()
@@ -397,6 +414,8 @@ impl Resolve<ExecuteArgs> for RunSync {
       && variables_to_create.is_empty()
       && variables_to_update.is_empty()
       && variables_to_delete.is_empty()
+      && onboarding_keys_to_update.is_empty()
+      && onboarding_keys_to_delete.is_empty()
     {
       update.push_simple_log(
         "No Changes",
@@ -431,6 +450,15 @@ impl Resolve<ExecuteArgs> for RunSync {
       )
       .await,
     );
+    maybe_extend(
+      &mut update.logs,
+      crate::sync::onboarding_keys::run_updates(
+        onboarding_keys_to_update,
+        onboarding_keys_to_delete,
+        onboarding_keys_missing,
+      )
+      .await,
+    );
     maybe_extend(
       &mut update.logs,
       ResourceSync::execute_sync_updates(resource_sync_deltas).await,
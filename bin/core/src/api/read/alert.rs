@@ -30,7 +30,10 @@ impl Resolve<ReadArgs> for ListAlerts {
     ReadArgs { user }: &ReadArgs,
   ) -> serror::Result<ListAlertsResponse> {
     let mut query = self.query.unwrap_or_default();
-    if !user.admin && !core_config().transparent_mode {
+    if !user.admin
+      && (!core_config().transparent_mode
+        || !user.organization.is_empty())
+    {
       let server_ids =
         get_resource_ids_for_user::<Server>(user).await?;
       let stack_ids =
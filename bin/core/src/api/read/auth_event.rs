@@ -0,0 +1,85 @@
+use anyhow::{Context, anyhow};
+use database::mungos::{
+  find::find_collect,
+  mongodb::{
+    bson::{Document, doc},
+    options::FindOptions,
+  },
+};
+use komodo_client::api::read::{
+  ListAuthEvents, ListAuthEventsResponse,
+};
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::AddStatusCodeError;
+
+use crate::{helpers::query::id_or_name_filter, state::db_client};
+
+use super::ReadArgs;
+
+const NUM_AUTH_EVENTS_PER_PAGE: u64 = 100;
+
+impl Resolve<ReadArgs> for ListAuthEvents {
+  async fn resolve(
+    self,
+    ReadArgs { user: admin }: &ReadArgs,
+  ) -> serror::Result<ListAuthEventsResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let mut query = Document::new();
+
+    if let Some(server) = self.server {
+      // Also match the query of connections made before
+      // the Server existed, or with its name.
+      let server_id = db_client()
+        .servers
+        .find_one(id_or_name_filter(&server))
+        .await
+        .context("Failed to query database for Server")?
+        .map(|server| server.id);
+      let mut or = vec![doc! { "server_query": &server }];
+      if let Some(server_id) = server_id {
+        or.push(doc! { "server_id": server_id });
+      }
+      query.insert("$or", or);
+    }
+    if let Some(public_key) = self.public_key {
+      query.insert("public_key", public_key);
+    }
+    if let Some(success) = self.success {
+      query.insert("success", success);
+    }
+    if let Some(direction) = self.direction {
+      query.insert("direction", direction.to_string());
+    }
+    if let Some(since) = self.since {
+      query.insert("ts", doc! { "$gte": since });
+    }
+
+    let events = find_collect(
+      &db_client().auth_events,
+      query,
+      FindOptions::builder()
+        .sort(doc! { "ts": -1 })
+        .limit(NUM_AUTH_EVENTS_PER_PAGE as i64)
+        .skip(self.page * NUM_AUTH_EVENTS_PER_PAGE)
+        .build(),
+    )
+    .await
+    .context("Failed to query database for auth events")?;
+
+    let next_page =
+      if events.len() < NUM_AUTH_EVENTS_PER_PAGE as usize {
+        None
+      } else {
+        Some((self.page + 1) as i64)
+      };
+
+    Ok(ListAuthEventsResponse { events, next_page })
+  }
+}
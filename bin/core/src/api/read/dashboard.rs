@@ -0,0 +1,44 @@
+use anyhow::Context;
+use database::mungos::{
+  find::find_collect,
+  mongodb::{bson::doc, options::FindOptions},
+};
+use komodo_client::api::read::*;
+use resolver_api::Resolve;
+
+use crate::{
+  helpers::query::get_dashboard_check_access, state::db_client,
+};
+
+use super::ReadArgs;
+
+impl Resolve<ReadArgs> for GetDashboard {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<GetDashboardResponse> {
+    Ok(get_dashboard_check_access(&self.id, user).await?)
+  }
+}
+
+impl Resolve<ReadArgs> for ListDashboards {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListDashboardsResponse> {
+    let filter = doc! {
+      "$or": [
+        { "owner": &user.id },
+        { "shared": true },
+      ]
+    };
+    let dashboards = find_collect(
+      &db_client().dashboards,
+      filter,
+      FindOptions::builder().sort(doc! { "name": 1 }).build(),
+    )
+    .await
+    .context("failed to query db for dashboards")?;
+    Ok(dashboards)
+  }
+}
@@ -18,7 +18,10 @@ use periphery_client::api::{self, container::InspectContainer};
 use resolver_api::Resolve;
 
 use crate::{
-  helpers::{periphery_client, query::get_all_tags},
+  helpers::{
+    inherit::deployment_inheritance, periphery_client,
+    query::get_all_tags,
+  },
   permission::get_check_permissions,
   resource,
   state::{
@@ -238,6 +241,30 @@ impl Resolve<ReadArgs> for InspectDeploymentContainer {
   }
 }
 
+impl Resolve<ReadArgs> for GetDeploymentInheritance {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<GetDeploymentInheritanceResponse> {
+    let deployment = get_check_permissions::<Deployment>(
+      &self.deployment,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await?;
+    if !deployment.config.inherit.is_empty() {
+      // The template config is also exposed
+      get_check_permissions::<Deployment>(
+        &deployment.config.inherit,
+        user,
+        PermissionLevel::Read.into(),
+      )
+      .await?;
+    }
+    Ok(deployment_inheritance(&deployment).await?)
+  }
+}
+
 impl Resolve<ReadArgs> for GetDeploymentStats {
   async fn resolve(
     self,
@@ -17,18 +17,19 @@ use komodo_client::{
     user::User,
   },
 };
-use resolver_api::Resolve;
+use reqwest::StatusCode;
+use resolver_api::{HasResponse, Resolve};
 use response::Response;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use serror::Json;
+use serror::{AddStatusCode, Json};
 use typeshare::typeshare;
 use uuid::Uuid;
 
 use crate::{
   auth::auth_request,
   config::{core_config, core_keys},
-  helpers::periphery_client,
+  helpers::{periphery_client, projection::projected_response},
   resource,
 };
 
@@ -37,19 +38,26 @@ use super::Variant;
 mod action;
 mod alert;
 mod alerter;
+mod auth_event;
 mod build;
 mod builder;
+mod dashboard;
 mod deployment;
+mod notification;
 mod onboarding_key;
+mod organization;
+mod pending_server;
 mod permission;
 mod procedure;
 mod provider;
 mod repo;
+mod revoked_key;
 mod schedule;
 mod server;
 mod stack;
 mod sync;
 mod tag;
+mod template;
 mod toml;
 mod update;
 mod user;
@@ -87,6 +95,13 @@ enum ReadRequest {
   GetUserGroup(GetUserGroup),
   ListUserGroups(ListUserGroups),
 
+  // ==== NOTIFICATION ====
+  ListNotifications(ListNotifications),
+
+  // ==== ORGANIZATION ====
+  GetOrganization(GetOrganization),
+  ListOrganizations(ListOrganizations),
+
   // ==== PROCEDURE ====
   GetProceduresSummary(GetProceduresSummary),
   GetProcedure(GetProcedure),
@@ -109,11 +124,18 @@ enum ReadRequest {
   GetServer(GetServer),
   GetServerState(GetServerState),
   GetPeripheryInformation(GetPeripheryInformation),
+  ListConnectionShards(ListConnectionShards),
+  GetServerConnectionMetrics(GetServerConnectionMetrics),
+  ListTransportTrace(ListTransportTrace),
   GetServerActionState(GetServerActionState),
   GetHistoricalServerStats(GetHistoricalServerStats),
   ListServers(ListServers),
   ListFullServers(ListFullServers),
   ListTerminals(ListTerminals),
+  ListTerminalRecordings(ListTerminalRecordings),
+  GetTerminalRecording(GetTerminalRecording),
+  ListActiveTerminalSessions(ListActiveTerminalSessions),
+  ListServerSchedules(ListServerSchedules),
 
   // ==== DOCKER ====
   GetDockerContainersSummary(GetDockerContainersSummary),
@@ -132,14 +154,23 @@ enum ReadRequest {
   ListDockerVolumes(ListDockerVolumes),
   InspectDockerVolume(InspectDockerVolume),
 
+  // ==== SYSTEMD ====
+  ListSystemdUnits(ListSystemdUnits),
+  GetSystemdUnitStatus(GetSystemdUnitStatus),
+  GetSystemdUnitJournal(GetSystemdUnitJournal),
+
   // ==== SERVER STATS ====
   GetSystemInformation(GetSystemInformation),
   GetSystemStats(GetSystemStats),
   ListSystemProcesses(ListSystemProcesses),
+  ListListeningSockets(ListListeningSockets),
+  SuggestServerPlacement(SuggestServerPlacement),
+  CheckServerPlacement(CheckServerPlacement),
 
   // ==== STACK ====
   GetStacksSummary(GetStacksSummary),
   GetStack(GetStack),
+  GetStackInheritance(GetStackInheritance),
   GetStackActionState(GetStackActionState),
   GetStackLog(GetStackLog),
   SearchStackLog(SearchStackLog),
@@ -159,6 +190,7 @@ enum ReadRequest {
   GetDeploymentLog(GetDeploymentLog),
   SearchDeploymentLog(SearchDeploymentLog),
   InspectDeploymentContainer(InspectDeploymentContainer),
+  GetDeploymentInheritance(GetDeploymentInheritance),
   ListDeployments(ListDeployments),
   ListFullDeployments(ListFullDeployments),
   ListCommonDeploymentExtraArgs(ListCommonDeploymentExtraArgs),
@@ -206,6 +238,8 @@ enum ReadRequest {
   // ==== TAG ====
   GetTag(GetTag),
   ListTags(ListTags),
+  GetTagUsageReport(GetTagUsageReport),
+  ListTagUsageReports(ListTagUsageReports),
 
   // ==== UPDATE ====
   GetUpdate(GetUpdate),
@@ -219,6 +253,14 @@ enum ReadRequest {
   GetVariable(GetVariable),
   ListVariables(ListVariables),
 
+  // ==== TEMPLATE ====
+  GetTemplate(GetTemplate),
+  ListTemplates(ListTemplates),
+
+  // ==== DASHBOARD ====
+  GetDashboard(GetDashboard),
+  ListDashboards(ListDashboards),
+
   // ==== PROVIDER ====
   GetGitProviderAccount(GetGitProviderAccount),
   ListGitProviderAccounts(ListGitProviderAccounts),
@@ -227,6 +269,15 @@ enum ReadRequest {
 
   // ==== ONBOARDING KEY ====
   ListOnboardingKeys(ListOnboardingKeys),
+
+  // ==== PENDING SERVER ====
+  ListPendingServers(ListPendingServers),
+
+  // ==== REVOKED KEY ====
+  ListRevokedKeys(ListRevokedKeys),
+
+  // ==== AUTH EVENT ====
+  ListAuthEvents(ListAuthEvents),
 }
 
 pub fn router() -> Router {
@@ -255,7 +306,25 @@ async fn handler(
   let timer = Instant::now();
   let req_id = Uuid::new_v4();
   debug!("/read request | user: {}", user.username);
-  let res = request.resolve(&ReadArgs { user }).await;
+  let args = ReadArgs { user };
+  let res = match request {
+    ReadRequest::ListFullDeployments(req) => {
+      resolve_fields(req, &args).await
+    }
+    ReadRequest::InspectDockerContainer(req) => {
+      resolve_fields(req, &args).await
+    }
+    ReadRequest::GetPeripheryInformation(req) => {
+      resolve_fields(req, &args).await
+    }
+    ReadRequest::GetSystemInformation(req) => {
+      resolve_fields(req, &args).await
+    }
+    ReadRequest::GetSystemStats(req) => {
+      resolve_fields(req, &args).await
+    }
+    request => request.resolve(&args).await,
+  };
   if let Err(e) = &res {
     debug!("/read request {req_id} error: {:#}", e.error);
   }
@@ -264,6 +333,63 @@ async fn handler(
   res.map(|res| res.0)
 }
 
+/// Heavy reads can request only some `fields`,
+/// keeping the payload small for frequent polling.
+trait WithFields {
+  fn fields(&mut self) -> &mut Vec<String>;
+}
+
+impl WithFields for ListFullDeployments {
+  fn fields(&mut self) -> &mut Vec<String> {
+    &mut self.fields
+  }
+}
+
+impl WithFields for InspectDockerContainer {
+  fn fields(&mut self) -> &mut Vec<String> {
+    &mut self.fields
+  }
+}
+
+impl WithFields for GetPeripheryInformation {
+  fn fields(&mut self) -> &mut Vec<String> {
+    &mut self.fields
+  }
+}
+
+impl WithFields for GetSystemInformation {
+  fn fields(&mut self) -> &mut Vec<String> {
+    &mut self.fields
+  }
+}
+
+impl WithFields for GetSystemStats {
+  fn fields(&mut self) -> &mut Vec<String> {
+    &mut self.fields
+  }
+}
+
+/// Resolves the request, responding with only
+/// the requested fields if there are any.
+async fn resolve_fields<T>(
+  mut req: T,
+  args: &ReadArgs,
+) -> serror::Result<Response>
+where
+  T: Resolve<ReadArgs>
+    + HasResponse<Error = serror::Error>
+    + WithFields,
+  T::Response: Serialize,
+{
+  let fields = std::mem::take(req.fields());
+  let res = req.resolve(args).await?;
+  if fields.is_empty() {
+    return Ok(res.into());
+  }
+  projected_response(&res, &fields)
+    .status_code(StatusCode::BAD_REQUEST)
+}
+
 impl Resolve<ReadArgs> for GetVersion {
   async fn resolve(
     self,
@@ -297,6 +423,7 @@ impl Resolve<ReadArgs> for GetCoreInfo {
       enable_fancy_toml: config.enable_fancy_toml,
       timezone: config.timezone.clone(),
       public_key: core_keys().load().public.to_string(),
+      read_only: read_only_mode().load().as_ref().clone(),
     };
     Ok(info)
   }
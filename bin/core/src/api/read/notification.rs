@@ -0,0 +1,64 @@
+use anyhow::Context;
+use database::mungos::{
+  find::find_collect,
+  mongodb::{bson::doc, options::FindOptions},
+};
+use komodo_client::api::read::{
+  ListNotifications, ListNotificationsResponse,
+};
+use resolver_api::Resolve;
+
+use crate::state::db_client;
+
+use super::ReadArgs;
+
+const NUM_NOTIFICATIONS_PER_PAGE: u64 = 100;
+
+impl Resolve<ReadArgs> for ListNotifications {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListNotificationsResponse> {
+    let mut query = doc! { "user": &user.id };
+    if self.unread_only {
+      query.insert("read", false);
+    }
+
+    let db = db_client();
+
+    let (notifications, unread) = tokio::try_join!(
+      async {
+        find_collect(
+          &db.notifications,
+          query,
+          FindOptions::builder()
+            .sort(doc! { "ts": -1 })
+            .limit(NUM_NOTIFICATIONS_PER_PAGE as i64)
+            .skip(self.page * NUM_NOTIFICATIONS_PER_PAGE)
+            .build(),
+        )
+        .await
+        .context("failed to get notifications from db")
+      },
+      async {
+        db.notifications
+          .count_documents(doc! { "user": &user.id, "read": false })
+          .await
+          .context("failed to count unread notifications on db")
+      }
+    )?;
+
+    let next_page =
+      if notifications.len() < NUM_NOTIFICATIONS_PER_PAGE as usize {
+        None
+      } else {
+        Some((self.page + 1) as i64)
+      };
+
+    Ok(ListNotificationsResponse {
+      notifications,
+      unread,
+      next_page,
+    })
+  }
+}
@@ -2,8 +2,9 @@ use std::cmp::Ordering;
 
 use anyhow::{Context, anyhow};
 use database::mungos::find::find_collect;
-use komodo_client::api::read::{
-  ListOnboardingKeys, ListOnboardingKeysResponse,
+use komodo_client::{
+  api::read::{ListOnboardingKeys, ListOnboardingKeysResponse},
+  entities::onboarding_key::OnboardingKey,
 };
 use reqwest::StatusCode;
 use resolver_api::Resolve;
@@ -46,6 +47,11 @@ impl Resolve<ReadArgs> for ListOnboardingKeys {
       }
     });
 
-    Ok(keys)
+    Ok(
+      keys
+        .into_iter()
+        .map(OnboardingKey::with_remaining_uses)
+        .collect(),
+    )
   }
 }
@@ -0,0 +1,56 @@
+use anyhow::{Context, anyhow};
+use database::mungos::{
+  by_id::find_one_by_id,
+  find::find_collect,
+  mongodb::{bson::doc, options::FindOptions},
+};
+use komodo_client::api::read::*;
+use resolver_api::Resolve;
+
+use crate::{helpers::query::get_organization, state::db_client};
+
+use super::ReadArgs;
+
+impl Resolve<ReadArgs> for GetOrganization {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<GetOrganizationResponse> {
+    let organization = get_organization(&self.organization).await?;
+    // Don't allow non admin users to get Organizations they aren't a part of.
+    if !user.admin && organization.id != user.organization {
+      return Err(
+        anyhow!("User is not a part of this Organization").into(),
+      );
+    }
+    Ok(organization)
+  }
+}
+
+impl Resolve<ReadArgs> for ListOrganizations {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListOrganizationsResponse> {
+    if !user.admin {
+      if user.organization.is_empty() {
+        return Ok(Vec::new());
+      }
+      let organization = find_one_by_id(
+        &db_client().organizations,
+        &user.organization,
+      )
+      .await
+      .context("failed to query db for Organizations")?;
+      return Ok(organization.into_iter().collect());
+    }
+    let res = find_collect(
+      &db_client().organizations,
+      None,
+      FindOptions::builder().sort(doc! { "name": 1 }).build(),
+    )
+    .await
+    .context("failed to query db for Organizations")?;
+    Ok(res)
+  }
+}
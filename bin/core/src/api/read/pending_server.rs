@@ -0,0 +1,39 @@
+use anyhow::{Context, anyhow};
+use database::mungos::{
+  find::find_collect,
+  mongodb::{bson::doc, options::FindOptions},
+};
+use komodo_client::api::read::{
+  ListPendingServers, ListPendingServersResponse,
+};
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::AddStatusCodeError;
+
+use crate::{api::read::ReadArgs, state::db_client};
+
+//
+
+impl Resolve<ReadArgs> for ListPendingServers {
+  async fn resolve(
+    self,
+    ReadArgs { user: admin }: &ReadArgs,
+  ) -> serror::Result<ListPendingServersResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    let pending = find_collect(
+      &db_client().pending_servers,
+      None,
+      FindOptions::builder()
+        .sort(doc! { "requested_at": -1 })
+        .build(),
+    )
+    .await
+    .context("Failed to query database for pending servers")?;
+    Ok(pending)
+  }
+}
@@ -0,0 +1,39 @@
+use anyhow::{Context, anyhow};
+use database::mungos::{
+  find::find_collect,
+  mongodb::{bson::doc, options::FindOptions},
+};
+use komodo_client::api::read::{
+  ListRevokedKeys, ListRevokedKeysResponse,
+};
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::AddStatusCodeError;
+
+use crate::{api::read::ReadArgs, state::db_client};
+
+//
+
+impl Resolve<ReadArgs> for ListRevokedKeys {
+  async fn resolve(
+    self,
+    ReadArgs { user: admin }: &ReadArgs,
+  ) -> serror::Result<ListRevokedKeysResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    let keys = find_collect(
+      &db_client().revoked_keys,
+      None,
+      FindOptions::builder()
+        .sort(doc! { "revoked_at": -1 })
+        .build(),
+    )
+    .await
+    .context("Failed to query database for revoked keys")?;
+    Ok(keys)
+  }
+}
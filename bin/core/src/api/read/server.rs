@@ -5,9 +5,7 @@ use std::{
 };
 
 use anyhow::{Context, anyhow};
-use async_timing_util::{
-  FIFTEEN_SECONDS_MS, get_timelength_in_ms, unix_timestamp_ms,
-};
+use async_timing_util::{get_timelength_in_ms, unix_timestamp_ms};
 use database::mungos::{
   find::find_collect,
   mongodb::{bson::doc, options::FindOptions},
@@ -32,7 +30,7 @@ use komodo_client::{
       TerminalInfo,
     },
     stack::{Stack, StackServiceNames},
-    stats::{SystemInformation, SystemProcess},
+    stats::SystemInformation,
     update::Log,
   },
 };
@@ -45,15 +43,26 @@ use periphery_client::api::{
 };
 use reqwest::StatusCode;
 use resolver_api::Resolve;
-use serror::AddStatusCode;
-use tokio::sync::Mutex;
+use serror::{AddStatusCode, AddStatusCodeError};
 
 use crate::{
-  helpers::{periphery_client, query::get_all_tags},
+  config::core_config,
+  helpers::{
+    periphery_client,
+    query::get_all_tags,
+    read_cache::{
+      cached_read, image_history_cache, inspect_image_cache,
+      system_processes_cache,
+    },
+    terminal_session::list_terminal_sessions,
+  },
   permission::get_check_permissions,
   resource,
   stack::compose_container_match_regex,
-  state::{action_states, db_client, server_status_cache},
+  state::{
+    action_states, connection_shards, db_client,
+    periphery_connections, server_status_cache, transport_trace,
+  },
 };
 
 use super::ReadArgs;
@@ -217,6 +226,83 @@ impl Resolve<ReadArgs> for GetPeripheryInformation {
   }
 }
 
+impl Resolve<ReadArgs> for ListConnectionShards {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListConnectionShardsResponse> {
+    if !user.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    Ok(connection_shards().stats())
+  }
+}
+
+impl Resolve<ReadArgs> for GetServerConnectionMetrics {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<GetServerConnectionMetricsResponse> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await?;
+    let Some(connection) =
+      periphery_connections().get(&server.id).await
+    else {
+      // Never connected since Core started
+      return Ok(Default::default());
+    };
+    Ok(
+      connection
+        .metrics
+        .get(connection.connected(), connection.latency_ms()),
+    )
+  }
+}
+
+impl Resolve<ReadArgs> for ListTransportTrace {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListTransportTraceResponse> {
+    if !user.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    let trace = transport_trace();
+    if !trace.enabled() {
+      return Err(
+        anyhow!(
+          "Transport trace is disabled. Enable with KOMODO_TRANSPORT_TRACE=1"
+        )
+        .status_code(StatusCode::BAD_REQUEST),
+      );
+    }
+    let server = match &self.server {
+      Some(server) => Some(
+        get_check_permissions::<Server>(
+          server,
+          user,
+          PermissionLevel::Read.into(),
+        )
+        .await?
+        .id,
+      ),
+      None => None,
+    };
+    let limit = self.limit.unwrap_or(1000) as usize;
+    Ok(trace.list(server.as_deref(), limit))
+  }
+}
+
 impl Resolve<ReadArgs> for GetSystemInformation {
   async fn resolve(
     self,
@@ -264,15 +350,121 @@ impl Resolve<ReadArgs> for GetSystemStats {
   }
 }
 
-// This protects the peripheries from spam requests
-const PROCESSES_EXPIRY: u128 = FIFTEEN_SECONDS_MS;
-type ProcessesCache =
-  Mutex<HashMap<String, Arc<(Vec<SystemProcess>, u128)>>>;
-fn processes_cache() -> &'static ProcessesCache {
-  static PROCESSES_CACHE: OnceLock<ProcessesCache> = OnceLock::new();
-  PROCESSES_CACHE.get_or_init(Default::default)
+impl Resolve<ReadArgs> for SuggestServerPlacement {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<SuggestServerPlacementResponse> {
+    let servers = resource::list_full_for_user::<Server>(
+      Default::default(),
+      user,
+      &[],
+    )
+    .await?
+    .into_iter()
+    .filter(|server| {
+      self.servers.is_empty()
+        || self.servers.iter().any(|target| {
+          target == &server.id || target == &server.name
+        })
+    });
+
+    let mut placements = Vec::new();
+    for server in servers {
+      placements
+        .push(server_placement(&server, self.cpu, self.mem_gb).await);
+    }
+
+    placements.sort_by(|a, b| {
+      b.fits
+        .cmp(&a.fits)
+        .then(b.available_mem_gb.total_cmp(&a.available_mem_gb))
+        .then(b.available_cpu.total_cmp(&a.available_cpu))
+    });
+
+    Ok(placements)
+  }
+}
+
+impl Resolve<ReadArgs> for CheckServerPlacement {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ServerPlacement> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await?;
+    Ok(server_placement(&server, self.cpu, self.mem_gb).await)
+  }
+}
+
+async fn server_placement(
+  server: &Server,
+  cpu: f32,
+  mem_gb: f64,
+) -> ServerPlacement {
+  let mut placement = ServerPlacement {
+    server_id: server.id.clone(),
+    server_name: server.name.clone(),
+    max_containers: server.config.max_containers,
+    ..Default::default()
+  };
+
+  let status = server_status_cache().get(&server.id).await;
+  let (Some(status), Some(stats)) = (
+    status
+      .as_ref()
+      .filter(|status| status.state == ServerState::Ok),
+    status
+      .as_ref()
+      .and_then(|status| status.system_stats.as_ref()),
+  ) else {
+    placement
+      .reasons
+      .push(String::from("Server is not connected"));
+    return placement;
+  };
+
+  placement.available_cpu =
+    100.0 - server.config.reserved_cpu - stats.cpu_perc;
+  placement.available_mem_gb = stats.mem_total_gb
+    - server.config.reserved_mem_gb
+    - stats.mem_used_gb;
+  placement.containers = status
+    .containers
+    .as_ref()
+    .map(|containers| containers.len() as i64)
+    .unwrap_or_default();
+
+  if placement.available_cpu < cpu {
+    placement.reasons.push(format!(
+      "Not enough CPU available: {:.1}% < {cpu:.1}%",
+      placement.available_cpu
+    ));
+  }
+  if placement.available_mem_gb < mem_gb {
+    placement.reasons.push(format!(
+      "Not enough memory available: {:.2} GB < {mem_gb:.2} GB",
+      placement.available_mem_gb
+    ));
+  }
+  if placement.max_containers > 0
+    && placement.containers >= placement.max_containers
+  {
+    placement.reasons.push(format!(
+      "Server is at its max containers: {}",
+      placement.max_containers
+    ));
+  }
+
+  placement.fits = placement.reasons.is_empty();
+  placement
 }
 
+// This protects the peripheries from spam requests
 impl Resolve<ReadArgs> for ListSystemProcesses {
   async fn resolve(
     self,
@@ -284,24 +476,39 @@ impl Resolve<ReadArgs> for ListSystemProcesses {
       PermissionLevel::Read.processes(),
     )
     .await?;
-    let mut lock = processes_cache().lock().await;
-    let res = match lock.get(&server.id) {
-      Some(cached) if cached.1 > unix_timestamp_ms() => {
-        cached.0.clone()
-      }
-      _ => {
-        let stats = periphery_client(&server)
+    let res = cached_read(
+      system_processes_cache(),
+      (server.id.clone(), String::new()),
+      core_config().system_processes_cache_ms,
+      self.skip_cache,
+      async {
+        periphery_client(&server)
           .await?
           .request(periphery::stats::GetSystemProcesses {})
-          .await?;
-        lock.insert(
-          server.id,
-          (stats.clone(), unix_timestamp_ms() + PROCESSES_EXPIRY)
-            .into(),
-        );
-        stats
-      }
-    };
+          .await
+      },
+    )
+    .await?;
+    Ok(res)
+  }
+}
+
+impl Resolve<ReadArgs> for ListListeningSockets {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListListeningSocketsResponse> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Read.processes(),
+    )
+    .await?;
+    let res = periphery_client(&server)
+      .await?
+      .request(periphery::stats::GetListeningSockets {})
+      .await
+      .context("Failed to list listening sockets")?;
     Ok(res)
   }
 }
@@ -710,10 +917,19 @@ impl Resolve<ReadArgs> for InspectDockerImage {
           .into(),
       );
     }
-    let res = periphery_client(&server)
-      .await?
-      .request(InspectImage { name: self.image })
-      .await?;
+    let res = cached_read(
+      inspect_image_cache(),
+      (server.id.clone(), self.image.clone()),
+      core_config().inspect_image_cache_ms,
+      self.skip_cache,
+      async {
+        periphery_client(&server)
+          .await?
+          .request(InspectImage { name: self.image })
+          .await
+      },
+    )
+    .await?;
     Ok(res)
   }
 }
@@ -741,10 +957,19 @@ impl Resolve<ReadArgs> for ListDockerImageHistory {
         .into(),
       );
     }
-    let res = periphery_client(&server)
-      .await?
-      .request(ImageHistory { name: self.image })
-      .await?;
+    let res = cached_read(
+      image_history_cache(),
+      (server.id.clone(), self.image.clone()),
+      core_config().image_history_cache_ms,
+      self.skip_cache,
+      async {
+        periphery_client(&server)
+          .await?
+          .request(ImageHistory { name: self.image })
+          .await
+      },
+    )
+    .await?;
     Ok(res)
   }
 }
@@ -886,3 +1111,154 @@ impl Resolve<ReadArgs> for ListTerminals {
     }
   }
 }
+
+impl Resolve<ReadArgs> for ListTerminalRecordings {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListTerminalRecordingsResponse> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Read.terminal(),
+    )
+    .await?;
+    let recordings = periphery_client(&server)
+      .await?
+      .request(
+        periphery_client::api::terminal::ListTerminalRecordings {},
+      )
+      .await
+      .context("Failed to list terminal recordings")?;
+    Ok(recordings)
+  }
+}
+
+impl Resolve<ReadArgs> for GetTerminalRecording {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<GetTerminalRecordingResponse> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Read.terminal(),
+    )
+    .await?;
+    let recording = periphery_client(&server)
+      .await?
+      .request(
+        periphery_client::api::terminal::GetTerminalRecording {
+          name: self.recording,
+        },
+      )
+      .await
+      .context("Failed to get terminal recording")?;
+    Ok(recording)
+  }
+}
+
+impl Resolve<ReadArgs> for ListActiveTerminalSessions {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListActiveTerminalSessionsResponse> {
+    if !user.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    Ok(list_terminal_sessions())
+  }
+}
+
+impl Resolve<ReadArgs> for ListServerSchedules {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListServerSchedulesResponse> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await?;
+    let res = periphery_client(&server)
+      .await?
+      .request(periphery::schedule::ListSchedules {})
+      .await
+      .context("Failed to list server schedules")?;
+    Ok(res)
+  }
+}
+
+impl Resolve<ReadArgs> for ListSystemdUnits {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<ListSystemdUnitsResponse> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await?;
+    let res = periphery_client(&server)
+      .await?
+      .request(periphery::systemd::ListSystemdUnits {})
+      .await
+      .context("Failed to list systemd units")?;
+    Ok(res)
+  }
+}
+
+impl Resolve<ReadArgs> for GetSystemdUnitStatus {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<GetSystemdUnitStatusResponse> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await?;
+    let res = periphery_client(&server)
+      .await?
+      .request(periphery::systemd::GetUnitStatus { unit: self.unit })
+      .await
+      .context("Failed to get systemd unit status")?;
+    Ok(res)
+  }
+}
+
+impl Resolve<ReadArgs> for GetSystemdUnitJournal {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<Log> {
+    let GetSystemdUnitJournal {
+      server,
+      unit,
+      tail,
+      timestamps,
+    } = self;
+    let server = get_check_permissions::<Server>(
+      &server,
+      user,
+      PermissionLevel::Read.logs(),
+    )
+    .await?;
+    let res = periphery_client(&server)
+      .await?
+      .request(periphery::systemd::GetUnitJournal {
+        unit,
+        tail: cmp::min(tail, MAX_LOG_LENGTH),
+        timestamps,
+      })
+      .await
+      .context("Failed to get systemd unit journal")?;
+    Ok(res)
+  }
+}
@@ -17,7 +17,9 @@ use periphery_client::api::{
 use resolver_api::Resolve;
 
 use crate::{
-  helpers::{periphery_client, query::get_all_tags},
+  helpers::{
+    inherit::stack_inheritance, periphery_client, query::get_all_tags,
+  },
   permission::get_check_permissions,
   resource,
   stack::get_stack_and_server,
@@ -42,6 +44,30 @@ impl Resolve<ReadArgs> for GetStack {
   }
 }
 
+impl Resolve<ReadArgs> for GetStackInheritance {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<GetStackInheritanceResponse> {
+    let stack = get_check_permissions::<Stack>(
+      &self.stack,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await?;
+    if !stack.config.inherit.is_empty() {
+      // The template config is also exposed
+      get_check_permissions::<Stack>(
+        &stack.config.inherit,
+        user,
+        PermissionLevel::Read.into(),
+      )
+      .await?;
+    }
+    Ok(stack_inheritance(&stack).await?)
+  }
+}
+
 impl Resolve<ReadArgs> for ListStackServices {
   async fn resolve(
     self,
@@ -116,7 +116,8 @@ impl Resolve<ReadArgs> for GetResourceSyncsSummary {
       if !(resource_sync.info.pending_deploy.to_deploy == 0
         && resource_sync.info.resource_updates.is_empty()
         && resource_sync.info.variable_updates.is_empty()
-        && resource_sync.info.user_group_updates.is_empty())
+        && resource_sync.info.user_group_updates.is_empty()
+        && resource_sync.info.onboarding_key_updates.is_empty())
       {
         res.pending += 1;
         continue;
@@ -1,29 +1,55 @@
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use database::mongo_indexed::doc;
 use database::mungos::{
   find::find_collect, mongodb::options::FindOptions,
 };
 use komodo_client::{
-  api::read::{GetTag, ListTags},
+  api::read::{
+    GetTag, GetTagUsageReport, GetTagUsageReportResponse,
+    ListTagUsageReports, ListTagUsageReportsResponse, ListTags,
+  },
   entities::tag::Tag,
 };
 use resolver_api::Resolve;
 
-use crate::{helpers::query::get_tag, state::db_client};
+use crate::{
+  helpers::{
+    query::get_tag,
+    tag_report::{get_tag_usage_report, report_window},
+  },
+  permission::in_user_organization,
+  state::db_client,
+};
 
 use super::ReadArgs;
 
 impl Resolve<ReadArgs> for GetTag {
-  async fn resolve(self, _: &ReadArgs) -> serror::Result<Tag> {
-    Ok(get_tag(&self.tag).await?)
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<Tag> {
+    let tag = get_tag(&self.tag).await?;
+    if !in_user_organization(user, &tag.organization) {
+      return Err(
+        anyhow!("Tag is not in the user's Organization").into(),
+      );
+    }
+    Ok(tag)
   }
 }
 
 impl Resolve<ReadArgs> for ListTags {
-  async fn resolve(self, _: &ReadArgs) -> serror::Result<Vec<Tag>> {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<Vec<Tag>> {
+    let mut query = self.query.unwrap_or_default();
+    if !user.admin && !user.organization.is_empty() {
+      query.insert("organization", &user.organization);
+    }
     let res = find_collect(
       &db_client().tags,
-      self.query,
+      query,
       FindOptions::builder().sort(doc! { "name": 1 }).build(),
     )
     .await
@@ -31,3 +57,36 @@ impl Resolve<ReadArgs> for ListTags {
     Ok(res)
   }
 }
+
+impl Resolve<ReadArgs> for GetTagUsageReport {
+  async fn resolve(
+    self,
+    ReadArgs { user }: &ReadArgs,
+  ) -> serror::Result<GetTagUsageReportResponse> {
+    let tag = get_tag(&self.tag).await?;
+    if !in_user_organization(user, &tag.organization) {
+      return Err(
+        anyhow!("Tag is not in the user's Organization").into(),
+      );
+    }
+    let (since, until) = report_window(self.since, self.until);
+    Ok(get_tag_usage_report(&tag, since, until, user).await?)
+  }
+}
+
+impl Resolve<ReadArgs> for ListTagUsageReports {
+  async fn resolve(
+    self,
+    args: &ReadArgs,
+  ) -> serror::Result<ListTagUsageReportsResponse> {
+    let (since, until) = report_window(self.since, self.until);
+    let tags = ListTags::default().resolve(args).await?;
+    let mut reports = Vec::with_capacity(tags.len());
+    for tag in &tags {
+      reports.push(
+        get_tag_usage_report(tag, since, until, &args.user).await?,
+      );
+    }
+    Ok(reports)
+  }
+}
@@ -0,0 +1,36 @@
+use anyhow::Context;
+use database::mongo_indexed::doc;
+use database::mungos::{
+  find::find_collect, mongodb::options::FindOptions,
+};
+use komodo_client::api::read::*;
+use resolver_api::Resolve;
+
+use crate::{helpers::query::get_template, state::db_client};
+
+use super::ReadArgs;
+
+impl Resolve<ReadArgs> for GetTemplate {
+  async fn resolve(
+    self,
+    _: &ReadArgs,
+  ) -> serror::Result<GetTemplateResponse> {
+    Ok(get_template(&self.name).await?)
+  }
+}
+
+impl Resolve<ReadArgs> for ListTemplates {
+  async fn resolve(
+    self,
+    _: &ReadArgs,
+  ) -> serror::Result<ListTemplatesResponse> {
+    let templates = find_collect(
+      &db_client().templates,
+      None,
+      FindOptions::builder().sort(doc! { "name": 1 }).build(),
+    )
+    .await
+    .context("failed to query db for templates")?;
+    Ok(templates)
+  }
+}
@@ -24,6 +24,9 @@ use crate::{
   resource,
   state::db_client,
   sync::{
+    onboarding_keys::{
+      get_current_onboarding_keys, onboarding_key_to_toml,
+    },
     toml::{ToToml, convert_resource},
     user_groups::{convert_user_groups, user_group_to_toml},
     variables::variable_to_toml,
@@ -176,6 +179,7 @@ impl Resolve<ReadArgs> for ExportAllResourcesToToml {
       targets,
       user_groups,
       include_variables: self.include_variables,
+      include_onboarding_keys: self.include_onboarding_keys,
     }
     .resolve(args)
     .await
@@ -191,6 +195,7 @@ impl Resolve<ReadArgs> for ExportResourcesToToml {
       targets,
       user_groups,
       include_variables,
+      include_onboarding_keys,
     } = self;
     let mut res = ResourcesToml::default();
     let id_to_tags = get_id_to_tags(None).await?;
@@ -376,6 +381,12 @@ impl Resolve<ReadArgs> for ExportResourcesToToml {
           .collect();
     }
 
+    if include_onboarding_keys && user.admin {
+      res.onboarding_keys =
+        get_current_onboarding_keys().await?.into_values().collect();
+      res.onboarding_keys.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
     let toml = serialize_resources_toml(res)
       .context("failed to serialize resources to toml")?;
 
@@ -502,5 +513,12 @@ fn serialize_resources_toml(
     toml.push_str(&user_group_to_toml(user_group)?);
   }
 
+  for onboarding_key in &resources.onboarding_keys {
+    if !toml.is_empty() {
+      toml.push_str("\n\n##\n\n");
+    }
+    toml.push_str(&onboarding_key_to_toml(onboarding_key)?);
+  }
+
   Ok(toml)
 }
@@ -42,7 +42,10 @@ impl Resolve<ReadArgs> for ListUpdates {
     self,
     ReadArgs { user }: &ReadArgs,
   ) -> serror::Result<ListUpdatesResponse> {
-    let query = if user.admin || core_config().transparent_mode {
+    let query = if user.admin
+      || (core_config().transparent_mode
+        && user.organization.is_empty())
+    {
       self.query
     } else {
       let server_query = get_resource_ids_for_user::<Server>(user)
@@ -219,7 +222,10 @@ impl Resolve<ReadArgs> for GetUpdate {
       .await
       .context("failed to query to db")?
       .context("no update exists with given id")?;
-    if user.admin || core_config().transparent_mode {
+    if user.admin
+      || (core_config().transparent_mode
+        && user.organization.is_empty())
+    {
       return Ok(update);
     }
     match &update.target {
@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use database::mongo_indexed::doc;
 use database::mungos::{
   find::find_collect, mongodb::options::FindOptions,
@@ -6,7 +6,10 @@ use database::mungos::{
 use komodo_client::api::read::*;
 use resolver_api::Resolve;
 
-use crate::{helpers::query::get_variable, state::db_client};
+use crate::{
+  helpers::query::get_variable, permission::in_user_organization,
+  state::db_client,
+};
 
 use super::ReadArgs;
 
@@ -16,6 +19,14 @@ impl Resolve<ReadArgs> for GetVariable {
     ReadArgs { user }: &ReadArgs,
   ) -> serror::Result<GetVariableResponse> {
     let mut variable = get_variable(&self.name).await?;
+    // Variables without an Organization are shared
+    if !variable.organization.is_empty()
+      && !in_user_organization(user, &variable.organization)
+    {
+      return Err(
+        anyhow!("Variable is not in the user's Organization").into(),
+      );
+    }
     if !variable.is_secret || user.admin {
       return Ok(variable);
     }
@@ -29,9 +40,13 @@ impl Resolve<ReadArgs> for ListVariables {
     self,
     ReadArgs { user }: &ReadArgs,
   ) -> serror::Result<ListVariablesResponse> {
+    let filter = (!user.admin && !user.organization.is_empty())
+      .then(|| {
+        doc! { "organization": { "$in": [null, "", &user.organization] } }
+      });
     let variables = find_collect(
       &db_client().variables,
-      None,
+      filter,
       FindOptions::builder().sort(doc! { "name": 1 }).build(),
     )
     .await
@@ -1,3 +1,5 @@
+use std::cmp;
+
 use anyhow::Context;
 use axum::{Extension, Router, middleware, routing::post};
 use komodo_client::{
@@ -22,6 +24,7 @@ pub fn router() -> Router {
     .route("/execute/container", post(execute_container_exec))
     .route("/execute/deployment", post(execute_deployment_exec))
     .route("/execute/stack", post(execute_stack_exec))
+    .route("/tail/host_file", post(tail_host_file))
     .layer(middleware::from_fn(auth_request))
 }
 
@@ -237,3 +240,51 @@ async fn execute_stack_exec_inner(
 
   Ok(axum::body::Body::from_stream(stream))
 }
+
+// ==============
+//  TailHostFile
+// ==============
+
+const MAX_TAIL_LINES: u64 = 5000;
+
+async fn tail_host_file(
+  Extension(user): Extension<User>,
+  Json(request): Json<TailHostFileBody>,
+) -> serror::Result<axum::body::Body> {
+  tail_host_file_inner(Uuid::new_v4(), request, user).await
+}
+
+#[instrument(
+  name = "TailHostFile",
+  skip(user),
+  fields(
+    user_id = user.id,
+  )
+)]
+async fn tail_host_file_inner(
+  req_id: Uuid,
+  TailHostFileBody {
+    server,
+    path,
+    lines,
+    follow,
+  }: TailHostFileBody,
+  user: User,
+) -> serror::Result<axum::body::Body> {
+  info!("TailHostFile request | user: {}", user.username);
+
+  let server = get_check_permissions::<Server>(
+    &server,
+    &user,
+    PermissionLevel::Read.logs(),
+  )
+  .await?;
+
+  let stream = periphery_client(&server)
+    .await?
+    .tail_host_file(path, cmp::min(lines, MAX_TAIL_LINES), follow)
+    .await
+    .context("Failed to tail host file on periphery")?;
+
+  Ok(axum::body::Body::from_stream(stream))
+}
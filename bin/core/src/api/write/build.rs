@@ -433,7 +433,6 @@ async fn get_on_host_periphery(
           &ObjectId::new().to_hex(),
           &config,
         ),
-        config.insecure_tls,
       )
       .await?;
       // Poll for connection to be estalished
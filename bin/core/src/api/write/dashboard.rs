@@ -0,0 +1,135 @@
+use anyhow::{Context, anyhow};
+use database::mungos::{
+  by_id::{delete_one_by_id, find_one_by_id},
+  mongodb::bson::{doc, oid::ObjectId},
+};
+use komodo_client::{
+  api::write::*,
+  entities::{dashboard::Dashboard, komodo_timestamp, user::User},
+};
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::AddStatusCodeError;
+
+use crate::{
+  helpers::query::get_dashboard_check_access, state::db_client,
+};
+
+use super::WriteArgs;
+
+impl Resolve<WriteArgs> for CreateDashboard {
+  #[instrument("CreateDashboard", skip(user, self), fields(user = user.username, name = &self.name))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<CreateDashboardResponse> {
+    if name_is_empty(&self.name) {
+      return Err(anyhow!("Dashboard name cannot be empty").into());
+    }
+    let ts = komodo_timestamp();
+    let dashboard = Dashboard {
+      id: Default::default(),
+      owner: user.id.clone(),
+      name: self.name,
+      description: self.description,
+      shared: self.shared,
+      widgets: self.widgets,
+      created_at: ts,
+      updated_at: ts,
+    };
+    let db = db_client();
+    let id = db
+      .dashboards
+      .insert_one(dashboard)
+      .await
+      .context("failed to create Dashboard on db")?
+      .inserted_id
+      .as_object_id()
+      .context("inserted id is not ObjectId")?
+      .to_string();
+    let res = find_one_by_id(&db.dashboards, &id)
+      .await
+      .context("failed to query db for dashboards")?
+      .context("dashboard at id not found")?;
+    Ok(res)
+  }
+}
+
+impl Resolve<WriteArgs> for UpdateDashboard {
+  #[instrument("UpdateDashboard", skip(user, self), fields(user = user.username, id = &self.id))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<UpdateDashboardResponse> {
+    let UpdateDashboard {
+      id,
+      name,
+      description,
+      shared,
+      widgets,
+    } = self;
+
+    let mut dashboard = get_dashboard_check_edit(&id, user).await?;
+
+    if let Some(name) = name {
+      if name_is_empty(&name) {
+        return Err(anyhow!("Dashboard name cannot be empty").into());
+      }
+      dashboard.name = name;
+    }
+    if let Some(description) = description {
+      dashboard.description = description;
+    }
+    if let Some(shared) = shared {
+      dashboard.shared = shared;
+    }
+    if let Some(widgets) = widgets {
+      dashboard.widgets = widgets;
+    }
+    dashboard.updated_at = komodo_timestamp();
+
+    let object_id = ObjectId::parse_str(&id)
+      .context("Dashboard id is not valid ObjectId")?;
+    db_client()
+      .dashboards
+      .replace_one(doc! { "_id": object_id }, &dashboard)
+      .await
+      .context("failed to update Dashboard on db")?;
+
+    Ok(dashboard)
+  }
+}
+
+impl Resolve<WriteArgs> for DeleteDashboard {
+  #[instrument("DeleteDashboard", skip(user), fields(user = user.username))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<DeleteDashboardResponse> {
+    let dashboard = get_dashboard_check_edit(&self.id, user).await?;
+    delete_one_by_id(&db_client().dashboards, &self.id, None)
+      .await
+      .context("failed to delete Dashboard from db")?;
+    Ok(dashboard)
+  }
+}
+
+/// Only the owner or an admin can edit a dashboard,
+/// even if it is shared.
+async fn get_dashboard_check_edit(
+  id: &str,
+  user: &User,
+) -> serror::Result<Dashboard> {
+  let dashboard = get_dashboard_check_access(id, user).await?;
+  if !user.admin && dashboard.owner != user.id {
+    return Err(
+      anyhow!("Only the dashboard owner or an admin can edit it")
+        .status_code(StatusCode::FORBIDDEN),
+    );
+  }
+  Ok(dashboard)
+}
+
+fn name_is_empty(name: &str) -> bool {
+  name.trim().is_empty()
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use komodo_client::{
+  api::write::{SetReadOnlyMode, SetReadOnlyModeResponse},
+  entities::{ReadOnlyMode, komodo_timestamp},
+};
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::AddStatusCodeError;
+
+use crate::state::read_only_mode;
+
+use super::WriteArgs;
+
+impl Resolve<WriteArgs> for SetReadOnlyMode {
+  #[instrument(
+    "SetReadOnlyMode",
+    skip(admin),
+    fields(admin = admin.username)
+  )]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<SetReadOnlyModeResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    let mode = ReadOnlyMode {
+      enabled: self.enabled,
+      reason: self.reason,
+      since: komodo_timestamp(),
+      set_by: admin.username.clone(),
+    };
+    if mode.enabled {
+      warn!(
+        "Core read-only mode enabled by {} | {}",
+        admin.username, mode.reason
+      );
+    } else {
+      info!("Core read-only mode disabled by {}", admin.username);
+    }
+    read_only_mode().store(Arc::new(mode.clone()));
+    Ok(mode)
+  }
+}
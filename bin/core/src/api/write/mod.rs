@@ -1,6 +1,7 @@
 use anyhow::Context;
 use axum::{
-  Extension, Router, extract::Path, middleware, routing::post,
+  Extension, Router, extract::Path, http::StatusCode, middleware,
+  routing::post,
 };
 use derive_variants::{EnumVariants, ExtractVariant};
 use komodo_client::{api::write::*, entities::user::User};
@@ -8,12 +9,14 @@ use resolver_api::Resolve;
 use response::Response;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use serror::Json;
+use serror::{AddStatusCode, Json};
 use strum::Display;
 use typeshare::typeshare;
 use uuid::Uuid;
 
-use crate::auth::auth_request;
+use crate::{
+  auth::auth_request, helpers::maintenance::check_read_only,
+};
 
 use super::Variant;
 
@@ -22,18 +25,25 @@ mod alert;
 mod alerter;
 mod build;
 mod builder;
+mod dashboard;
 mod deployment;
+mod maintenance;
+mod notification;
 mod onboarding_key;
+mod organization;
+mod pending_server;
 mod permissions;
 mod procedure;
 mod provider;
 mod repo;
 mod resource;
+mod revoked_key;
 mod server;
 mod service_user;
 mod stack;
 mod sync;
 mod tag;
+mod template;
 mod user;
 mod user_group;
 mod variable;
@@ -72,6 +82,18 @@ pub enum WriteRequest {
   RemoveUserFromUserGroup(RemoveUserFromUserGroup),
   SetUsersInUserGroup(SetUsersInUserGroup),
   SetEveryoneUserGroup(SetEveryoneUserGroup),
+  UpdateUserGroupQuotas(UpdateUserGroupQuotas),
+
+  // ==== NOTIFICATION ====
+  AckNotifications(AckNotifications),
+  SendNotification(SendNotification),
+
+  // ==== ORGANIZATION ====
+  CreateOrganization(CreateOrganization),
+  UpdateOrganization(UpdateOrganization),
+  DeleteOrganization(DeleteOrganization),
+  SetUserOrganization(SetUserOrganization),
+  SetResourceOrganization(SetResourceOrganization),
 
   // ==== PERMISSIONS ====
   UpdateUserAdmin(UpdateUserAdmin),
@@ -92,8 +114,13 @@ pub enum WriteRequest {
   CreateTerminal(CreateTerminal),
   DeleteTerminal(DeleteTerminal),
   DeleteAllTerminals(DeleteAllTerminals),
+  ClearTerminalHistory(ClearTerminalHistory),
+  DisconnectTerminalSession(DisconnectTerminalSession),
   UpdateServerPublicKey(UpdateServerPublicKey),
+  AcceptServerTlsPin(AcceptServerTlsPin),
   RotateServerKeys(RotateServerKeys),
+  UpsertServerSchedule(UpsertServerSchedule),
+  DeleteServerSchedule(DeleteServerSchedule),
 
   // ==== STACK ====
   CreateStack(CreateStack),
@@ -180,6 +207,17 @@ pub enum WriteRequest {
   UpdateVariableIsSecret(UpdateVariableIsSecret),
   DeleteVariable(DeleteVariable),
 
+  // ==== TEMPLATE ====
+  CreateTemplate(CreateTemplate),
+  UpdateTemplate(UpdateTemplate),
+  DeleteTemplate(DeleteTemplate),
+  InstantiateTemplate(InstantiateTemplate),
+
+  // ==== DASHBOARD ====
+  CreateDashboard(CreateDashboard),
+  UpdateDashboard(UpdateDashboard),
+  DeleteDashboard(DeleteDashboard),
+
   // ==== PROVIDER ====
   CreateGitProviderAccount(CreateGitProviderAccount),
   UpdateGitProviderAccount(UpdateGitProviderAccount),
@@ -193,8 +231,19 @@ pub enum WriteRequest {
   UpdateOnboardingKey(UpdateOnboardingKey),
   DeleteOnboardingKey(DeleteOnboardingKey),
 
+  // ==== PENDING SERVER ====
+  ApprovePendingServer(ApprovePendingServer),
+  DeletePendingServer(DeletePendingServer),
+
+  // ==== REVOKED KEY ====
+  RevokePublicKey(RevokePublicKey),
+  UnrevokePublicKey(UnrevokePublicKey),
+
   // ==== ALERT ====
   CloseAlert(CloseAlert),
+
+  // ==== MAINTENANCE ====
+  SetReadOnlyMode(SetReadOnlyMode),
 }
 
 pub fn router() -> Router {
@@ -237,6 +286,11 @@ async fn task(
   let variant = request.extract_variant();
   info!("/write request | {variant} | user: {}", user.username);
 
+  // Read-only mode can always be turned off again.
+  if !matches!(request, WriteRequest::SetReadOnlyMode(_)) {
+    check_read_only().status_code(StatusCode::SERVICE_UNAVAILABLE)?;
+  }
+
   let res = request.resolve(&WriteArgs { user }).await;
 
   if let Err(e) = &res {
@@ -0,0 +1,115 @@
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use database::mungos::mongodb::bson::{doc, oid::ObjectId};
+use komodo_client::{
+  api::write::*,
+  entities::{
+    NoData, ResourceTarget, komodo_timestamp,
+    notification::{Notification, NotificationKind},
+    permission::PermissionLevel,
+  },
+};
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::AddStatusCodeError;
+
+use crate::{
+  helpers::{
+    notification::send_notifications,
+    query::{get_user, get_user_permission_on_target},
+  },
+  state::db_client,
+};
+
+use super::WriteArgs;
+
+impl Resolve<WriteArgs> for AckNotifications {
+  #[instrument("AckNotifications", skip(user))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<AckNotificationsResponse> {
+    let mut filter = doc! { "user": &user.id, "read": false };
+    if !self.ids.is_empty() {
+      let ids = self
+        .ids
+        .iter()
+        .map(|id| ObjectId::from_str(id))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Notification ids must be valid ObjectIds")
+        .status_code(StatusCode::BAD_REQUEST)?;
+      filter.insert("_id", doc! { "$in": ids });
+    }
+    db_client()
+      .notifications
+      .update_many(
+        filter,
+        doc! { "$set": { "read": true, "read_ts": komodo_timestamp() } },
+      )
+      .await
+      .context("failed to mark notifications as read on db")?;
+    Ok(NoData {})
+  }
+}
+
+impl Resolve<WriteArgs> for SendNotification {
+  #[instrument("SendNotification", skip(user))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<SendNotificationResponse> {
+    if self.users.is_empty() {
+      return Err(
+        anyhow!("Must provide at least one user to notify")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    }
+    if self.message.is_empty() {
+      return Err(
+        anyhow!("Must provide non-empty message")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    }
+
+    let target = self.target.unwrap_or_default();
+    let ts = komodo_timestamp();
+
+    let mut notifications = Vec::with_capacity(self.users.len());
+    for recipient in &self.users {
+      let recipient = get_user(recipient).await?;
+      // Don't leak resources to users who can't see them
+      if !matches!(target, ResourceTarget::System(_))
+        && !recipient.admin
+        && get_user_permission_on_target(&recipient, &target)
+          .await?
+          .level
+          == PermissionLevel::None
+      {
+        return Err(
+          anyhow!(
+            "User {} does not have permission to see the target",
+            recipient.username
+          )
+          .status_code(StatusCode::FORBIDDEN),
+        );
+      }
+      notifications.push(Notification {
+        id: Default::default(),
+        user: recipient.id,
+        ts,
+        kind: NotificationKind::Mention,
+        title: format!("{} mentioned you", user.username),
+        body: self.message.clone(),
+        target: target.clone(),
+        source: user.id.clone(),
+        read: false,
+        read_ts: 0,
+      });
+    }
+
+    send_notifications(notifications).await?;
+
+    Ok(NoData {})
+  }
+}
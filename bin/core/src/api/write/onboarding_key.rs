@@ -49,6 +49,10 @@ impl Resolve<WriteArgs> for CreateOnboardingKey {
       tags: self.tags,
       copy_server: self.copy_server,
       create_builder: self.create_builder,
+      max_uses: self.max_uses,
+      single_use: self.single_use,
+      uses: 0,
+      remaining_uses: None,
     };
     let db = db_client();
     // Create the key
@@ -68,7 +72,7 @@ impl Resolve<WriteArgs> for CreateOnboardingKey {
       )?;
     Ok(CreateOnboardingKeyResponse {
       private_key,
-      created,
+      created: created.with_remaining_uses(),
     })
   }
 }
@@ -97,6 +101,7 @@ impl Resolve<WriteArgs> for UpdateOnboardingKey {
         .await
         .context("Failed to query database for onboarding key")?
         .context("No matching onboarding key found")
+        .map(OnboardingKey::with_remaining_uses)
         .status_code(StatusCode::NOT_FOUND);
     }
 
@@ -126,6 +131,14 @@ impl Resolve<WriteArgs> for UpdateOnboardingKey {
       update.insert("create_builder", create_builder);
     }
 
+    if let Some(max_uses) = self.max_uses {
+      update.insert("max_uses", max_uses);
+    }
+
+    if let Some(single_use) = self.single_use {
+      update.insert("single_use", single_use);
+    }
+
     db_client()
       .onboarding_keys
       .update_one(query.clone(), doc! { "$set": update })
@@ -138,6 +151,7 @@ impl Resolve<WriteArgs> for UpdateOnboardingKey {
       .await
       .context("Failed to query database for onboarding key")?
       .context("No matching onboarding key found")
+      .map(OnboardingKey::with_remaining_uses)
       .status_code(StatusCode::NOT_FOUND)
   }
 }
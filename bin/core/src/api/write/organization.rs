@@ -0,0 +1,293 @@
+use anyhow::{Context, anyhow};
+use database::mongo_indexed::Indexed;
+use database::mungos::{
+  by_id::{delete_one_by_id, find_one_by_id, update_one_by_id},
+  mongodb::bson::{Document, doc},
+};
+use komodo_client::{
+  api::write::*,
+  entities::{
+    NoData, ResourceTarget, action::Action, alerter::Alerter,
+    build::Build, builder::Builder, deployment::Deployment,
+    komodo_timestamp, organization::Organization,
+    procedure::Procedure, repo::Repo, server::Server, stack::Stack,
+    sync::ResourceSync, tag::Tag, user::User, variable::Variable,
+  },
+};
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::AddStatusCodeError;
+
+use crate::{
+  helpers::query::{get_organization, get_user},
+  resource,
+  state::db_client,
+};
+
+use super::WriteArgs;
+
+impl Resolve<WriteArgs> for CreateOrganization {
+  #[instrument("CreateOrganization", skip(admin), fields(admin = admin.username))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<Organization> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    if self.name.is_empty() {
+      return Err(
+        anyhow!("Must provide non-empty name for Organization")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    }
+    let ts = komodo_timestamp();
+    let organization = Organization {
+      id: Default::default(),
+      name: self.name,
+      description: self.description,
+      created_at: ts,
+      updated_at: ts,
+    };
+    let db = db_client();
+    let id = db
+      .organizations
+      .insert_one(organization)
+      .await
+      .context("failed to create Organization on db")?
+      .inserted_id
+      .as_object_id()
+      .context("inserted id is not ObjectId")?
+      .to_string();
+    let res = find_one_by_id(&db.organizations, &id)
+      .await
+      .context("failed to query db for Organizations")?
+      .context("Organization at id not found")?;
+    Ok(res)
+  }
+}
+
+impl Resolve<WriteArgs> for UpdateOrganization {
+  #[instrument("UpdateOrganization", skip(admin), fields(admin = admin.username))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<Organization> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    if self.name.is_empty() {
+      return Err(
+        anyhow!("Must provide non-empty name for Organization")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    }
+    let db = db_client();
+    update_one_by_id(
+      &db.organizations,
+      &self.id,
+      doc! { "$set": {
+        "name": self.name,
+        "description": self.description,
+        "updated_at": komodo_timestamp(),
+      } },
+      None,
+    )
+    .await
+    .context("failed to update Organization on db")?;
+    let res = find_one_by_id(&db.organizations, &self.id)
+      .await
+      .context("failed to query db for Organizations")?
+      .context("no Organization with given id")?;
+    Ok(res)
+  }
+}
+
+/// The resource collections, which are named after the resource type.
+const RESOURCE_COLLECTIONS: &[&str] = &[
+  "Server",
+  "Stack",
+  "Deployment",
+  "Build",
+  "Repo",
+  "Procedure",
+  "Action",
+  "Builder",
+  "Alerter",
+  "ResourceSync",
+];
+
+impl Resolve<WriteArgs> for DeleteOrganization {
+  #[instrument("DeleteOrganization", skip(admin), fields(admin = admin.username))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<Organization> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let db = db_client();
+
+    let organization = find_one_by_id(&db.organizations, &self.id)
+      .await
+      .context("failed to query db for Organizations")?
+      .context("no Organization found with given id")?;
+
+    let collections = [
+      User::default_collection_name(),
+      Tag::default_collection_name(),
+      Variable::default_collection_name(),
+    ]
+    .into_iter()
+    .chain(RESOURCE_COLLECTIONS.iter().copied());
+
+    for collection in collections {
+      let count = db
+        .db
+        .collection::<Document>(collection)
+        .count_documents(doc! { "organization": &organization.id })
+        .await
+        .with_context(|| {
+          format!("failed to count {collection} in Organization")
+        })?;
+      if count > 0 {
+        return Err(
+          anyhow!(
+            "Organization still has {count} {collection}(s). Reassign them before deleting."
+          )
+          .status_code(StatusCode::CONFLICT),
+        );
+      }
+    }
+
+    delete_one_by_id(&db.organizations, &self.id, None)
+      .await
+      .context("failed to delete Organization from db")?;
+
+    Ok(organization)
+  }
+}
+
+impl Resolve<WriteArgs> for SetUserOrganization {
+  #[instrument("SetUserOrganization", skip(admin), fields(admin = admin.username))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<SetUserOrganizationResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let user = get_user(&self.user).await?;
+
+    let organization = if self.organization.is_empty() {
+      String::new()
+    } else {
+      get_organization(&self.organization).await?.id
+    };
+
+    update_one_by_id(
+      &db_client().users,
+      &user.id,
+      doc! { "$set": {
+        "organization": organization,
+        "updated_at": komodo_timestamp(),
+      } },
+      None,
+    )
+    .await
+    .context("failed to set user Organization on db")?;
+
+    Ok(NoData {})
+  }
+}
+
+impl Resolve<WriteArgs> for SetResourceOrganization {
+  #[instrument("SetResourceOrganization", skip(admin), fields(admin = admin.username))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<SetResourceOrganizationResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let organization = if self.organization.is_empty() {
+      String::new()
+    } else {
+      get_organization(&self.organization).await?.id
+    };
+
+    match self.target {
+      ResourceTarget::System(_) => {
+        return Err(
+          anyhow!(
+            "cannot set Organization of System resource target"
+          )
+          .status_code(StatusCode::BAD_REQUEST),
+        );
+      }
+      ResourceTarget::Server(id) => {
+        resource::set_organization::<Server>(&id, &organization)
+          .await?;
+      }
+      ResourceTarget::Stack(id) => {
+        resource::set_organization::<Stack>(&id, &organization)
+          .await?;
+      }
+      ResourceTarget::Deployment(id) => {
+        resource::set_organization::<Deployment>(&id, &organization)
+          .await?;
+      }
+      ResourceTarget::Build(id) => {
+        resource::set_organization::<Build>(&id, &organization)
+          .await?;
+      }
+      ResourceTarget::Repo(id) => {
+        resource::set_organization::<Repo>(&id, &organization)
+          .await?;
+      }
+      ResourceTarget::Procedure(id) => {
+        resource::set_organization::<Procedure>(&id, &organization)
+          .await?;
+      }
+      ResourceTarget::Action(id) => {
+        resource::set_organization::<Action>(&id, &organization)
+          .await?;
+      }
+      ResourceTarget::Builder(id) => {
+        resource::set_organization::<Builder>(&id, &organization)
+          .await?;
+      }
+      ResourceTarget::Alerter(id) => {
+        resource::set_organization::<Alerter>(&id, &organization)
+          .await?;
+      }
+      ResourceTarget::ResourceSync(id) => {
+        resource::set_organization::<ResourceSync>(
+          &id,
+          &organization,
+        )
+        .await?;
+      }
+    }
+
+    Ok(NoData {})
+  }
+}
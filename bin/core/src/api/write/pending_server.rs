@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use database::mungos::mongodb::bson::{doc, oid::ObjectId};
+use komodo_client::{
+  api::write::{
+    ApprovePendingServer, ApprovePendingServerResponse,
+    DeletePendingServer, DeletePendingServerResponse,
+  },
+  entities::server::Server,
+};
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::{AddStatusCode, AddStatusCodeError};
+
+use crate::{
+  api::write::WriteArgs, connection::server::create_approved_server,
+  resource, state::db_client,
+};
+
+//
+
+impl Resolve<WriteArgs> for ApprovePendingServer {
+  #[instrument("ApprovePendingServer", skip(admin))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<ApprovePendingServerResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    let db = db_client();
+    let id = ObjectId::from_str(&self.id)
+      .context("Invalid pending server id")
+      .status_code(StatusCode::BAD_REQUEST)?;
+    let query = doc! { "_id": id };
+    let pending = db
+      .pending_servers
+      .find_one(query.clone())
+      .await
+      .context("Failed to query database for pending servers")?
+      .context("No pending server found with given id")
+      .status_code(StatusCode::NOT_FOUND)?;
+    let name = self
+      .name
+      .filter(|name| !name.is_empty())
+      .unwrap_or(pending.name);
+    let server_id = create_approved_server(
+      name,
+      pending.public_key,
+      &pending.onboarding_key,
+    )
+    .await?;
+    db.pending_servers
+      .delete_one(query)
+      .await
+      .context("Failed to delete pending server from database")?;
+    Ok(resource::get::<Server>(&server_id).await?)
+  }
+}
+
+impl Resolve<WriteArgs> for DeletePendingServer {
+  #[instrument("DeletePendingServer", skip(admin))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<DeletePendingServerResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    let db = db_client();
+    let id = ObjectId::from_str(&self.id)
+      .context("Invalid pending server id")
+      .status_code(StatusCode::BAD_REQUEST)?;
+    let query = doc! { "_id": id };
+    let pending = db
+      .pending_servers
+      .find_one(query.clone())
+      .await
+      .context("Failed to query database for pending servers")?
+      .context("No pending server found with given id")
+      .status_code(StatusCode::NOT_FOUND)?;
+    db.pending_servers
+      .delete_one(query)
+      .await
+      .context("Failed to delete pending server from database")?;
+    Ok(pending)
+  }
+}
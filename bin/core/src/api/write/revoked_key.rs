@@ -0,0 +1,123 @@
+use anyhow::{Context, anyhow};
+use database::mungos::{find::find_collect, mongodb::bson::doc};
+use komodo_client::{
+  api::write::{
+    RevokePublicKey, RevokePublicKeyResponse, UnrevokePublicKey,
+    UnrevokePublicKeyResponse,
+  },
+  entities::{komodo_timestamp, revoked_key::RevokedKey},
+};
+use noise::key::SpkiPublicKey;
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::AddStatusCodeError;
+
+use crate::{
+  api::write::WriteArgs,
+  state::{db_client, periphery_connections},
+};
+
+//
+
+impl Resolve<WriteArgs> for RevokePublicKey {
+  #[instrument("RevokePublicKey", skip(admin))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<RevokePublicKeyResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    let public_key = normalize_public_key(&self.public_key)?;
+    let db = db_client();
+    let query = doc! { "public_key": &public_key };
+
+    let existing = db
+      .revoked_keys
+      .find_one(query.clone())
+      .await
+      .context("Failed to query database for revoked keys")?;
+
+    let revoked = match existing {
+      Some(revoked) => revoked,
+      None => {
+        let revoked = RevokedKey {
+          public_key,
+          reason: self.reason,
+          revoked_by: admin.id.clone(),
+          revoked_at: komodo_timestamp(),
+        };
+        db.revoked_keys
+          .insert_one(&revoked)
+          .await
+          .context("Failed to create revoked key on database")?;
+        revoked
+      }
+    };
+
+    // Disconnect Servers currently authenticated with the key.
+    // They will fail the handshake on reconnect.
+    let servers = find_collect(
+      &db.servers,
+      doc! { "info.public_key": &revoked.public_key },
+      None,
+    )
+    .await
+    .context("Failed to query database for servers")?;
+    for server in servers {
+      periphery_connections().remove(&server.id).await;
+    }
+
+    Ok(revoked)
+  }
+}
+
+impl Resolve<WriteArgs> for UnrevokePublicKey {
+  #[instrument("UnrevokePublicKey", skip(admin))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<UnrevokePublicKeyResponse> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    let public_key = normalize_public_key(&self.public_key)?;
+    let db = db_client();
+    let query = doc! { "public_key": &public_key };
+    let revoked = db
+      .revoked_keys
+      .find_one(query.clone())
+      .await
+      .context("Failed to query database for revoked keys")?
+      .context("Revoked key matching provided public key not found")
+      .status_code(StatusCode::NOT_FOUND)?;
+    db.revoked_keys
+      .delete_one(query)
+      .await
+      .context("Failed to delete revoked key from database")?;
+    Ok(revoked)
+  }
+}
+
+/// Revoked keys are stored in the base64 der form
+/// Periphery authenticates with, so pem or base64 der
+/// inputs of the same key match.
+fn normalize_public_key(public_key: &str) -> serror::Result<String> {
+  let public_key = public_key.trim();
+  if public_key.is_empty() {
+    return Err(
+      anyhow!("Must provide the public key")
+        .status_code(StatusCode::BAD_REQUEST),
+    );
+  }
+  SpkiPublicKey::from_maybe_pem(public_key)
+    .map(SpkiPublicKey::into_inner)
+    .context("Invalid public key")
+    .status_code(StatusCode::BAD_REQUEST)
+}
@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use formatting::{bold, format_serror};
 use komodo_client::{
   api::write::*,
@@ -6,20 +6,24 @@ use komodo_client::{
     NoData, Operation,
     permission::PermissionLevel,
     server::{Server, ServerInfo},
+    server_schedule::ServerScheduleStatus,
     to_docker_compatible_name,
     update::{Update, UpdateStatus},
   },
 };
 use periphery_client::api;
+use reqwest::StatusCode;
 use resolver_api::Resolve;
+use serror::AddStatusCodeError;
 
 use crate::{
   helpers::{
     periphery_client,
+    terminal_session::disconnect_terminal_session,
     update::{add_update, make_update, update_update},
   },
   permission::get_check_permissions,
-  resource::{self, update_server_public_key},
+  resource::{self, accept_server_tls_pin, update_server_public_key},
 };
 
 use super::WriteArgs;
@@ -160,6 +164,8 @@ impl Resolve<WriteArgs> for CreateTerminal {
         name: self.name,
         command: self.command,
         recreate: self.recreate,
+        record: self.record,
+        history_kb: self.history_kb,
       })
       .await
       .context("Failed to create terminal on Periphery")?;
@@ -218,6 +224,56 @@ impl Resolve<WriteArgs> for DeleteAllTerminals {
   }
 }
 
+impl Resolve<WriteArgs> for ClearTerminalHistory {
+  #[instrument("ClearTerminalHistory", skip(user))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<NoData> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Write.terminal(),
+    )
+    .await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    periphery
+      .request(api::terminal::ClearTerminalHistory {
+        terminal: self.terminal,
+      })
+      .await
+      .context("Failed to clear terminal history on Periphery")?;
+
+    Ok(NoData {})
+  }
+}
+
+//
+
+impl Resolve<WriteArgs> for DisconnectTerminalSession {
+  #[instrument("DisconnectTerminalSession", skip(admin), fields(admin = admin.username))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<NoData> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    if !disconnect_terminal_session(&self.id) {
+      return Err(
+        anyhow!("No active terminal session with id {}", self.id)
+          .status_code(StatusCode::NOT_FOUND),
+      );
+    }
+    Ok(NoData {})
+  }
+}
+
 //
 
 impl Resolve<WriteArgs> for UpdateServerPublicKey {
@@ -251,6 +307,45 @@ impl Resolve<WriteArgs> for UpdateServerPublicKey {
 
 //
 
+impl Resolve<WriteArgs> for AcceptServerTlsPin {
+  #[instrument("AcceptServerTlsPin", skip(args))]
+  async fn resolve(
+    self,
+    args: &WriteArgs,
+  ) -> Result<Self::Response, Self::Error> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      &args.user,
+      PermissionLevel::Write.into(),
+    )
+    .await?;
+
+    let pin = &server.info.attempted_tls_pin;
+    if pin.is_empty() {
+      return Err(
+        anyhow!("Server has no attempted tls pin to accept")
+          .status_code(StatusCode::BAD_REQUEST),
+      );
+    }
+
+    accept_server_tls_pin(&server.id, pin).await?;
+
+    let mut update =
+      make_update(&server, Operation::UpdateServer, &args.user);
+
+    update.push_simple_log(
+      "Accept Server TLS Pin",
+      format!("Accepted tls certificate pin {}", bold(pin)),
+    );
+    update.finalize();
+    update.id = add_update(update.clone()).await?;
+
+    Ok(update)
+  }
+}
+
+//
+
 impl Resolve<WriteArgs> for RotateServerKeys {
   #[instrument("RotateServerPrivateKey", skip(args))]
   async fn resolve(
@@ -280,3 +375,55 @@ impl Resolve<WriteArgs> for RotateServerKeys {
     .await
   }
 }
+
+impl Resolve<WriteArgs> for UpsertServerSchedule {
+  #[instrument("UpsertServerSchedule", skip(user))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<ServerScheduleStatus> {
+    // Schedules run arbitrary commands on the host,
+    // so they need the same permission as terminals.
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Write.terminal(),
+    )
+    .await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    let status = periphery
+      .request(api::schedule::UpsertSchedule {
+        schedule: self.schedule,
+      })
+      .await
+      .context("Failed to upsert schedule on Periphery")?;
+
+    Ok(status)
+  }
+}
+
+impl Resolve<WriteArgs> for DeleteServerSchedule {
+  #[instrument("DeleteServerSchedule", skip(user))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<NoData> {
+    let server = get_check_permissions::<Server>(
+      &self.server,
+      user,
+      PermissionLevel::Write.terminal(),
+    )
+    .await?;
+
+    let periphery = periphery_client(&server).await?;
+
+    periphery
+      .request(api::schedule::DeleteSchedule { name: self.name })
+      .await
+      .context("Failed to delete schedule on Periphery")?;
+
+    Ok(NoData {})
+  }
+}
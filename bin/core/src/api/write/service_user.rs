@@ -47,6 +47,7 @@ impl Resolve<WriteArgs> for CreateServiceUser {
       last_update_view: 0,
       recents: Default::default(),
       all: Default::default(),
+      organization: Default::default(),
       updated_at: komodo_timestamp(),
     };
     user.id = db_client()
@@ -544,6 +544,7 @@ impl Resolve<WriteArgs> for RefreshStackCache {
       remote_errors,
       latest_hash,
       latest_message,
+      migration: stack.info.migration.clone(),
     };
 
     let info = to_document(&info)
@@ -456,6 +456,7 @@ impl Resolve<WriteArgs> for CommitSync {
       tags: sync.config.match_tags.clone(),
       include_variables: sync.config.include_variables,
       include_user_groups: sync.config.include_user_groups,
+      include_onboarding_keys: sync.config.include_onboarding_keys,
     }
     .resolve(&ReadArgs {
       user: sync_user().to_owned(),
@@ -833,11 +834,23 @@ impl Resolve<WriteArgs> for RefreshResourceSyncPending {
         Default::default()
       };
 
+      let onboarding_key_updates =
+        if sync.config.include_onboarding_keys {
+          crate::sync::onboarding_keys::get_updates_for_view(
+            &resources.onboarding_keys,
+            delete,
+          )
+          .await?
+        } else {
+          Default::default()
+        };
+
       anyhow::Ok((
         resource_updates,
         deploy_updates,
         variable_updates,
         user_group_updates,
+        onboarding_key_updates,
       ))
     }
     .await;
@@ -847,14 +860,16 @@ impl Resolve<WriteArgs> for RefreshResourceSyncPending {
       deploy_updates,
       variable_updates,
       user_group_updates,
+      onboarding_key_updates,
       pending_error,
     ) = match res {
-      Ok(res) => (res.0, res.1, res.2, res.3, None),
+      Ok(res) => (res.0, res.1, res.2, res.3, res.4, None),
       Err(e) => (
         Default::default(),
         Default::default(),
         Default::default(),
         Default::default(),
+        Default::default(),
         Some(format_serror(&e.into())),
       ),
     };
@@ -862,7 +877,8 @@ impl Resolve<WriteArgs> for RefreshResourceSyncPending {
     let has_updates = !resource_updates.is_empty()
       || !deploy_updates.to_deploy == 0
       || !variable_updates.is_empty()
-      || !user_group_updates.is_empty();
+      || !user_group_updates.is_empty()
+      || !onboarding_key_updates.is_empty();
 
     let info = ResourceSyncInfo {
       last_sync_ts: sync.info.last_sync_ts,
@@ -876,6 +892,7 @@ impl Resolve<WriteArgs> for RefreshResourceSyncPending {
       resource_updates,
       variable_updates,
       user_group_updates,
+      onboarding_key_updates,
       pending_error,
     };
 
@@ -51,6 +51,7 @@ impl Resolve<WriteArgs> for CreateTag {
       name: self.name,
       color: self.color.unwrap_or_default(),
       owner: user.id.clone(),
+      organization: user.organization.clone(),
     };
 
     tag.id = db_client()
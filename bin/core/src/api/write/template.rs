@@ -0,0 +1,235 @@
+use anyhow::{Context, anyhow};
+use database::mungos::mongodb::bson::doc;
+use komodo_client::{
+  api::write::*,
+  entities::{
+    Operation, ResourceTarget, komodo_timestamp,
+    stack::PartialStackConfig, template::Template,
+  },
+};
+use reqwest::StatusCode;
+use resolver_api::Resolve;
+use serror::AddStatusCodeError;
+
+use crate::{
+  helpers::{
+    query::get_template,
+    update::{add_update, make_update},
+  },
+  state::db_client,
+};
+
+use super::WriteArgs;
+
+impl Resolve<WriteArgs> for CreateTemplate {
+  #[instrument("CreateTemplate", skip(user, self), fields(name = &self.name))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<CreateTemplateResponse> {
+    if !user.admin {
+      return Err(
+        anyhow!("Only admins can create templates")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let CreateTemplate {
+      name,
+      description,
+      file_contents,
+      environment,
+      parameters,
+    } = self;
+
+    if name.is_empty() {
+      return Err(anyhow!("Template name cannot be empty").into());
+    }
+
+    let ts = komodo_timestamp();
+    let template = Template {
+      name,
+      description,
+      file_contents,
+      environment,
+      parameters,
+      created_at: ts,
+      updated_at: ts,
+    };
+
+    db_client()
+      .templates
+      .insert_one(&template)
+      .await
+      .context("Failed to create template on db")?;
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::CreateTemplate,
+      user,
+    );
+
+    update
+      .push_simple_log("Create Template", format!("{template:#?}"));
+    update.finalize();
+
+    add_update(update).await?;
+
+    Ok(get_template(&template.name).await?)
+  }
+}
+
+impl Resolve<WriteArgs> for UpdateTemplate {
+  #[instrument("UpdateTemplate", skip(user, self), fields(name = &self.name))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<UpdateTemplateResponse> {
+    if !user.admin {
+      return Err(
+        anyhow!("Only admins can update templates")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let UpdateTemplate {
+      name,
+      description,
+      file_contents,
+      environment,
+      parameters,
+    } = self;
+
+    let mut template = get_template(&name).await?;
+
+    if let Some(description) = description {
+      template.description = description;
+    }
+    if let Some(file_contents) = file_contents {
+      template.file_contents = file_contents;
+    }
+    if let Some(environment) = environment {
+      template.environment = environment;
+    }
+    if let Some(parameters) = parameters {
+      template.parameters = parameters;
+    }
+    template.updated_at = komodo_timestamp();
+
+    db_client()
+      .templates
+      .replace_one(doc! { "name": &name }, &template)
+      .await
+      .context("Failed to update template on db")?;
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::UpdateTemplate,
+      user,
+    );
+
+    update
+      .push_simple_log("Update Template", format!("{template:#?}"));
+    update.finalize();
+
+    add_update(update).await?;
+
+    Ok(template)
+  }
+}
+
+impl Resolve<WriteArgs> for DeleteTemplate {
+  #[instrument("DeleteTemplate", skip(user))]
+  async fn resolve(
+    self,
+    WriteArgs { user }: &WriteArgs,
+  ) -> serror::Result<DeleteTemplateResponse> {
+    if !user.admin {
+      return Err(
+        anyhow!("Only admins can delete templates")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+    let template = get_template(&self.name).await?;
+    db_client()
+      .templates
+      .delete_one(doc! { "name": &self.name })
+      .await
+      .context("Failed to delete template on db")?;
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::DeleteTemplate,
+      user,
+    );
+
+    update
+      .push_simple_log("Delete Template", format!("{template:#?}"));
+    update.finalize();
+
+    add_update(update).await?;
+
+    Ok(template)
+  }
+}
+
+impl Resolve<WriteArgs> for InstantiateTemplate {
+  #[instrument("InstantiateTemplate", skip(user, self), fields(template = &self.template, name = &self.name))]
+  async fn resolve(
+    self,
+    args: &WriteArgs,
+  ) -> serror::Result<InstantiateTemplateResponse> {
+    let InstantiateTemplate {
+      template,
+      name,
+      server,
+      parameters,
+    } = self;
+
+    if server.is_empty() {
+      return Err(
+        anyhow!("Must specify a Server to create the Stack on")
+          .into(),
+      );
+    }
+
+    let template = get_template(&template).await?;
+
+    let file_contents = template
+      .interpolate(&template.file_contents, &parameters)
+      .context("Failed to interpolate template file contents")?;
+    let environment = template
+      .interpolate(&template.environment, &parameters)
+      .context("Failed to interpolate template environment")?;
+
+    // Goes through CreateStack so the usual create
+    // and Server attach permissions are enforced.
+    let stack = CreateStack {
+      name,
+      config: PartialStackConfig {
+        server_id: Some(server),
+        file_contents: Some(file_contents),
+        environment: Some(environment),
+        ..Default::default()
+      },
+    }
+    .resolve(args)
+    .await?;
+
+    let mut update =
+      make_update(&stack, Operation::InstantiateTemplate, &args.user);
+
+    update.push_simple_log(
+      "Instantiate Template",
+      format!(
+        "Created Stack '{}' from template '{}'",
+        stack.name, template.name
+      ),
+    );
+    update.finalize();
+
+    add_update(update).await?;
+
+    Ok(stack)
+  }
+}
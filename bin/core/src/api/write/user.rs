@@ -77,6 +77,7 @@ impl Resolve<WriteArgs> for CreateLocalUser {
       last_update_view: 0,
       recents: Default::default(),
       all: Default::default(),
+      organization: Default::default(),
       config: UserConfig::Local {
         password: hashed_password,
       },
@@ -4,7 +4,7 @@ use anyhow::{Context, anyhow};
 use database::mungos::{
   by_id::{delete_one_by_id, find_one_by_id, update_one_by_id},
   find::find_collect,
-  mongodb::bson::{doc, oid::ObjectId},
+  mongodb::bson::{doc, oid::ObjectId, to_document},
 };
 use komodo_client::{
   api::write::*,
@@ -36,6 +36,7 @@ impl Resolve<WriteArgs> for CreateUserGroup {
       everyone: Default::default(),
       users: Default::default(),
       all: Default::default(),
+      quotas: Default::default(),
       updated_at: komodo_timestamp(),
     };
     let db = db_client();
@@ -300,3 +301,44 @@ impl Resolve<WriteArgs> for SetEveryoneUserGroup {
     Ok(res)
   }
 }
+
+impl Resolve<WriteArgs> for UpdateUserGroupQuotas {
+  #[instrument("UpdateUserGroupQuotas", skip(admin), fields(admin = admin.username))]
+  async fn resolve(
+    self,
+    WriteArgs { user: admin }: &WriteArgs,
+  ) -> serror::Result<UserGroup> {
+    if !admin.admin {
+      return Err(
+        anyhow!("This call is admin only")
+          .status_code(StatusCode::FORBIDDEN),
+      );
+    }
+
+    let db = db_client();
+
+    let filter = match ObjectId::from_str(&self.user_group) {
+      Ok(id) => doc! { "_id": id },
+      Err(_) => doc! { "name": &self.user_group },
+    };
+    let quotas = to_document(&self.quotas)
+      .context("failed to serialize user group quotas")?;
+    db.user_groups
+      .update_one(
+        filter.clone(),
+        doc! { "$set": {
+          "quotas": quotas,
+          "updated_at": komodo_timestamp(),
+        } },
+      )
+      .await
+      .context("failed to set quotas on user group")?;
+    let res = db
+      .user_groups
+      .find_one(filter)
+      .await
+      .context("failed to query db for UserGroups")?
+      .context("no user group with given id")?;
+    Ok(res)
+  }
+}
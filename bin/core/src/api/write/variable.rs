@@ -43,6 +43,7 @@ impl Resolve<WriteArgs> for CreateVariable {
       value,
       description,
       is_secret,
+      organization: user.organization.clone(),
     };
 
     db_client()
@@ -81,6 +81,7 @@ impl Resolve<AuthArgs> for SignUpLocalUser {
       last_update_view: 0,
       recents: Default::default(),
       all: Default::default(),
+      organization: Default::default(),
       config: UserConfig::Local {
         password: hashed_password,
       },
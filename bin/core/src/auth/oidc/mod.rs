@@ -289,6 +289,7 @@ async fn callback(
         last_update_view: 0,
         recents: Default::default(),
         all: Default::default(),
+        organization: Default::default(),
         config: UserConfig::Oidc {
           provider: core_config.oidc_provider.clone(),
           user_id: user_id.to_string(),
@@ -146,6 +146,11 @@ pub fn core_config() -> &'static CoreConfig {
         env.komodo_passkey,
       )
       .or(config.passkey),
+      transport_psk: maybe_read_item_from_file(
+        env.komodo_transport_psk_file,
+        env.komodo_transport_psk,
+      )
+      .or(config.transport_psk),
       jwt_secret: maybe_read_item_from_file(
         env.komodo_jwt_secret_file,
         env.komodo_jwt_secret,
@@ -156,6 +161,11 @@ pub fn core_config() -> &'static CoreConfig {
         env.komodo_webhook_secret,
       )
       .unwrap_or(config.webhook_secret),
+      onboarding_validator_secret: maybe_read_item_from_file(
+        env.komodo_onboarding_validator_secret_file,
+        env.komodo_onboarding_validator_secret,
+      )
+      .unwrap_or(config.onboarding_validator_secret),
       database: DatabaseConfig {
         uri: maybe_read_item_from_file(
           env.komodo_database_uri_file,
@@ -271,6 +281,90 @@ pub fn core_config() -> &'static CoreConfig {
       periphery_public_keys: env
         .komodo_periphery_public_keys
         .or(config.periphery_public_keys),
+      key_rotation_interval_days: env
+        .komodo_key_rotation_interval_days
+        .unwrap_or(config.key_rotation_interval_days),
+      auth_timeout_ms: env
+        .komodo_auth_timeout_ms
+        .unwrap_or(config.auth_timeout_ms),
+      connection_retry_seconds: env
+        .komodo_connection_retry_seconds
+        .unwrap_or(config.connection_retry_seconds),
+      connection_poll_times: env
+        .komodo_connection_poll_times
+        .unwrap_or(config.connection_poll_times),
+      connection_poll_interval_ms: env
+        .komodo_connection_poll_interval_ms
+        .unwrap_or(config.connection_poll_interval_ms),
+      shutdown_retry_after_seconds: env
+        .komodo_shutdown_retry_after_seconds
+        .unwrap_or(config.shutdown_retry_after_seconds),
+      connection_shards: env
+        .komodo_connection_shards
+        .unwrap_or(config.connection_shards),
+      connection_shard_max_logins: env
+        .komodo_connection_shard_max_logins
+        .unwrap_or(config.connection_shard_max_logins),
+      connection_accept_rate: env
+        .komodo_connection_accept_rate
+        .unwrap_or(config.connection_accept_rate),
+      connection_accept_burst: env
+        .komodo_connection_accept_burst
+        .unwrap_or(config.connection_accept_burst),
+      transport_compression: env
+        .komodo_transport_compression
+        .unwrap_or(config.transport_compression),
+      transport_encoding: env
+        .komodo_transport_encoding
+        .unwrap_or(config.transport_encoding),
+      transport_max_chunk_bytes: env
+        .komodo_transport_max_chunk_bytes
+        .unwrap_or(config.transport_max_chunk_bytes),
+      transport_max_message_bytes: env
+        .komodo_transport_max_message_bytes
+        .unwrap_or(config.transport_max_message_bytes),
+      transport_max_body_bytes: env
+        .komodo_transport_max_body_bytes
+        .unwrap_or(config.transport_max_body_bytes),
+      transport_max_body_depth: env
+        .komodo_transport_max_body_depth
+        .unwrap_or(config.transport_max_body_depth),
+      transport_max_bytes_per_sec: env
+        .komodo_transport_max_bytes_per_sec
+        .unwrap_or(config.transport_max_bytes_per_sec),
+      transport_heartbeat_interval_ms: env
+        .komodo_transport_heartbeat_interval_ms
+        .unwrap_or(config.transport_heartbeat_interval_ms),
+      transport_heartbeat_max_missed: env
+        .komodo_transport_heartbeat_max_missed
+        .unwrap_or(config.transport_heartbeat_max_missed),
+      transport_trace: env
+        .komodo_transport_trace
+        .unwrap_or(config.transport_trace),
+      transport_spillover_directory: env
+        .komodo_transport_spillover_directory
+        .or(config.transport_spillover_directory),
+      transport_spillover_max_bytes: env
+        .komodo_transport_spillover_max_bytes
+        .unwrap_or(config.transport_spillover_max_bytes),
+      inspect_image_cache_ms: env
+        .komodo_inspect_image_cache_ms
+        .unwrap_or(config.inspect_image_cache_ms),
+      image_history_cache_ms: env
+        .komodo_image_history_cache_ms
+        .unwrap_or(config.image_history_cache_ms),
+      system_processes_cache_ms: env
+        .komodo_system_processes_cache_ms
+        .unwrap_or(config.system_processes_cache_ms),
+      onboarding_validator_url: env
+        .komodo_onboarding_validator_url
+        .unwrap_or(config.onboarding_validator_url),
+      onboarding_validator_procedure: env
+        .komodo_onboarding_validator_procedure
+        .unwrap_or(config.onboarding_validator_procedure),
+      onboarding_require_approval: env
+        .komodo_onboarding_require_approval
+        .unwrap_or(config.onboarding_require_approval),
       first_server_address: env
         .komodo_first_server_address
         .or(config.first_server_address),
@@ -296,6 +390,12 @@ pub fn core_config() -> &'static CoreConfig {
       monitoring_interval: env
         .komodo_monitoring_interval
         .unwrap_or(config.monitoring_interval),
+      tag_usage_report_interval: env
+        .komodo_tag_usage_report_interval
+        .unwrap_or(config.tag_usage_report_interval),
+      tag_usage_report_alerters: env
+        .komodo_tag_usage_report_alerters
+        .unwrap_or(config.tag_usage_report_alerters),
       keep_stats_for_days: env
         .komodo_keep_stats_for_days
         .unwrap_or(config.keep_stats_for_days),
@@ -317,6 +417,12 @@ pub fn core_config() -> &'static CoreConfig {
       disable_websocket_reconnect: env
         .komodo_disable_websocket_reconnect
         .unwrap_or(config.disable_websocket_reconnect),
+      max_terminal_sessions: env
+        .komodo_max_terminal_sessions
+        .unwrap_or(config.max_terminal_sessions),
+      max_terminal_sessions_per_user: env
+        .komodo_max_terminal_sessions_per_user
+        .unwrap_or(config.max_terminal_sessions_per_user),
       enable_new_users: env
         .komodo_enable_new_users
         .unwrap_or(config.enable_new_users),
@@ -0,0 +1,49 @@
+//! Records Core <-> Periphery login attempts
+//! to the `auth_events` collection.
+
+use std::sync::OnceLock;
+
+use komodo_client::entities::{
+  auth_event::AuthEvent, komodo_timestamp,
+};
+use transport::auth::PublicKeyValidator;
+
+use crate::state::db_client;
+
+/// Wraps a validator to keep the public key Periphery presented,
+/// so it can be recorded whether or not it is accepted.
+pub struct RecordPublicKey<'a, V> {
+  pub validator: V,
+  pub public_key: &'a OnceLock<String>,
+}
+
+impl<V: PublicKeyValidator> PublicKeyValidator
+  for RecordPublicKey<'_, V>
+{
+  type ValidationResult = V::ValidationResult;
+  async fn validate(
+    &self,
+    public_key: String,
+  ) -> anyhow::Result<Self::ValidationResult> {
+    let _ = self.public_key.set(public_key.clone());
+    self.validator.validate(public_key).await
+  }
+}
+
+/// Sets the timestamp and result on the event,
+/// and spawns a task to insert it.
+pub fn spawn_record_auth_event<T>(
+  mut event: AuthEvent,
+  res: &anyhow::Result<T>,
+) {
+  event.ts = komodo_timestamp();
+  match res {
+    Ok(_) => event.success = true,
+    Err(e) => event.error = format!("{e:#}"),
+  }
+  tokio::spawn(async move {
+    if let Err(e) = db_client().auth_events.insert_one(&event).await {
+      warn!("Failed to record auth event | {e:?}");
+    }
+  });
+}
@@ -1,34 +1,42 @@
 use std::time::Duration;
 
 use anyhow::{Context, anyhow};
-use periphery_client::{
-  CONNECTION_RETRY_SECONDS, transport::LoginMessage,
+use axum::http::HeaderValue;
+use komodo_client::entities::auth_event::{
+  AuthEvent, AuthEventDirection, AuthEventFlow,
+};
+use periphery_client::transport::{
+  EncodedTransportMessage, LoginMessage, TransportOptions,
 };
 use transport::{
   auth::{
     AddressConnectionIdentifiers, ClientLoginFlow,
-    ConnectionIdentifiers,
+    ConnectionIdentifiers, advertise_options_query,
   },
+  backoff::{Backoff, DEFAULT_MAX_RETRY_DELAY},
+  channel::BufferedReceiver,
   fix_ws_address,
   websocket::{
     Websocket, WebsocketExt as _, login::LoginWebsocketExt,
-    tungstenite::TungsteniteWebsocket,
+    quic::QuicWebsocket, tungstenite::TungsteniteWebsocket,
   },
 };
 
 use crate::{
   config::{core_config, core_connection_query},
   periphery::PeripheryClient,
-  state::periphery_connections,
+  state::{connection_shards, periphery_connections},
 };
 
-use super::{PeripheryConnection, PeripheryConnectionArgs};
+use super::{
+  PeripheryConnection, PeripheryConnectionArgs,
+  audit::spawn_record_auth_event, spawn_update_attempted_tls_pin,
+};
 
 impl PeripheryConnectionArgs<'_> {
   pub async fn spawn_client_connection(
     self,
     id: String,
-    insecure: bool,
   ) -> anyhow::Result<PeripheryClient> {
     let Some(address) = self.address else {
       return Err(anyhow!(
@@ -39,7 +47,16 @@ impl PeripheryConnectionArgs<'_> {
     let address = fix_ws_address(address);
     let identifiers =
       AddressConnectionIdentifiers::extract(&address)?;
-    let endpoint = format!("{address}/?{}", core_connection_query());
+    let endpoint = format!(
+      "{address}/?{}&{}",
+      core_connection_query(),
+      advertise_options_query(
+        core_config().transport_compression,
+        core_config().transport_encoding
+      )
+    );
+
+    let tls = self.tls_verify();
 
     let (connection, mut receiver) =
       periphery_connections().insert(id.clone(), self).await;
@@ -47,47 +64,51 @@ impl PeripheryConnectionArgs<'_> {
     let responses = connection.responses.clone();
     let terminals = connection.terminals.clone();
 
+    let quic = endpoint.starts_with("quic://");
+
     tokio::spawn(async move {
+      let mut backoff = Backoff::new(
+        Duration::from_secs(core_config().connection_retry_seconds),
+        DEFAULT_MAX_RETRY_DELAY,
+      );
       loop {
-        let ws = tokio::select! {
-          ws = TungsteniteWebsocket::connect_maybe_tls_insecure(
-            &endpoint,
-            insecure && endpoint.starts_with("wss"),
-          ) => ws,
-          _ = connection.cancel.cancelled() => {
-            break
+        if quic {
+          let ws = tokio::select! {
+            ws = QuicWebsocket::connect_with_tls(&endpoint, &tls) => ws,
+            _ = connection.cancel.cancelled() => {
+              break
+            }
+          };
+          if let Some(pin) = tls.take_rejected_pin() {
+            spawn_update_attempted_tls_pin(id.clone(), pin);
           }
-        };
-
-        let (mut socket, accept) = match ws {
-          Ok(res) => res,
-          Err(e) => {
-            connection.set_error(e.error).await;
-            tokio::time::sleep(Duration::from_secs(
-              CONNECTION_RETRY_SECONDS,
-            ))
+          connection
+            .handle_connect_result(
+              ws,
+              &identifiers,
+              &mut receiver,
+              &mut backoff,
+            )
             .await;
-            continue;
+        } else {
+          let ws = tokio::select! {
+            ws = TungsteniteWebsocket::connect_with_tls(&endpoint, &tls) => ws,
+            _ = connection.cancel.cancelled() => {
+              break
+            }
+          };
+          if let Some(pin) = tls.take_rejected_pin() {
+            spawn_update_attempted_tls_pin(id.clone(), pin);
           }
-        };
-
-        let identifiers = identifiers.build(
-          accept.as_bytes(),
-          core_connection_query().as_bytes(),
-        );
-
-        if let Err(e) =
-          connection.client_login(&mut socket, identifiers).await
-        {
-          connection.set_error(e).await;
-          tokio::time::sleep(Duration::from_secs(
-            CONNECTION_RETRY_SECONDS,
-          ))
-          .await;
-          continue;
-        };
-
-        connection.handle_socket(socket, &mut receiver).await
+          connection
+            .handle_connect_result(
+              ws,
+              &identifiers,
+              &mut receiver,
+              &mut backoff,
+            )
+            .await;
+        }
       }
     });
 
@@ -100,6 +121,46 @@ impl PeripheryConnectionArgs<'_> {
 }
 
 impl PeripheryConnection {
+  /// Logs in and handles the socket until the connection ends,
+  /// or records the error if the connection failed.
+  async fn handle_connect_result<W: Websocket>(
+    &self,
+    ws: serror::Result<(W, HeaderValue)>,
+    identifiers: &AddressConnectionIdentifiers,
+    receiver: &mut BufferedReceiver<EncodedTransportMessage>,
+    backoff: &mut Backoff,
+  ) {
+    let (mut socket, accept) = match ws {
+      Ok(res) => res,
+      Err(e) => {
+        self.set_error(e.error).await;
+        backoff.wait().await;
+        return;
+      }
+    };
+
+    let identifiers = identifiers
+      .build(accept.as_bytes(), core_connection_query().as_bytes());
+
+    let login_permit =
+      connection_shards().shard(&self.args.id).login().await;
+    let login = self.client_login(&mut socket, identifiers).await;
+    drop(login_permit);
+
+    let options = match login {
+      Ok(options) => options,
+      Err(e) => {
+        self.set_error(e).await;
+        backoff.wait().await;
+        return;
+      }
+    };
+
+    backoff.reset();
+
+    self.handle_socket(socket, receiver, options).await
+  }
+
   /// Custom Core -> Periphery side only login wrapper
   /// to implement passkey support for backward compatibility
   #[instrument(
@@ -111,11 +172,11 @@ impl PeripheryConnection {
       direction = "CoreToPeriphery"
     )
   )]
-  async fn client_login(
+  async fn client_login<W: Websocket>(
     &self,
-    socket: &mut TungsteniteWebsocket,
+    socket: &mut W,
     identifiers: ConnectionIdentifiers<'_>,
-  ) -> anyhow::Result<()> {
+  ) -> anyhow::Result<TransportOptions> {
     // Get the required auth type
     let v1_passkey_flow =
       socket
@@ -123,22 +184,46 @@ impl PeripheryConnection {
         .await
         .context("Failed to receive Login V1PasskeyFlow message")?;
 
+    let address = self.args.address.as_deref().unwrap_or_default();
+
     if v1_passkey_flow {
-      handle_passkey_login(socket, self.args.passkey.as_deref()).await
+      // No handshake to get session keys from.
+      self.set_session_keys(None);
+      let res =
+        handle_passkey_login(socket, self.args.passkey.as_deref())
+          .await;
+      spawn_record_auth_event(
+        AuthEvent {
+          server_id: self.args.id.clone(),
+          direction: AuthEventDirection::CoreToPeriphery,
+          flow: AuthEventFlow::V1Passkey,
+          remote: address.to_string(),
+          ..Default::default()
+        },
+        &res,
+      );
+      res
     } else {
       self
-        .handle_login::<_, ClientLoginFlow>(socket, identifiers)
+        .handle_login::<_, ClientLoginFlow>(
+          socket,
+          identifiers,
+          // Periphery decides the options on client side.
+          TransportOptions::default(),
+          "",
+          address,
+        )
         .await
     }
   }
 }
 
 #[instrument("V1PasskeyPeripheryLoginFlow", skip(socket, passkey))]
-async fn handle_passkey_login(
-  socket: &mut TungsteniteWebsocket,
+async fn handle_passkey_login<W: Websocket>(
+  socket: &mut W,
   // for legacy auth
   passkey: Option<&str>,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<TransportOptions> {
   let res = async {
     let passkey = if let Some(passkey) = passkey {
       passkey.as_bytes().to_vec()
@@ -160,25 +245,24 @@ async fn handle_passkey_login(
     socket
       .recv_login_success()
       .await
-      .context("Failed to receive Login Success message")?;
-
-    anyhow::Ok(())
+      .context("Failed to receive Login Success message")
   }
   .await;
-  if let Err(e) = res {
-    if let Err(e) = socket
-      .send_login_error(&e)
-      .await
-      .context("Failed to send login failed to client")
-    {
-      // Log additional error
-      warn!("{e:#}");
+  match res {
+    Ok(options) => Ok(options),
+    Err(e) => {
+      if let Err(e) = socket
+        .send_login_error(&e)
+        .await
+        .context("Failed to send login failed to client")
+      {
+        // Log additional error
+        warn!("{e:#}");
+      }
+      // Close socket
+      let _ = socket.close().await;
+      // Return the original error
+      Err(e)
     }
-    // Close socket
-    let _ = socket.close().await;
-    // Return the original error
-    Err(e)
-  } else {
-    Ok(())
   }
 }
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use komodo_client::entities::{
+  Operation, ResourceTarget, deployment::DeploymentState,
+  docker::container::ContainerStateStatusEnum, event::CoreEventData,
+  update::Log, user::system_user,
+};
+use periphery_client::{
+  api::container::SubscribeDockerEvents, transport::PeripheryEvent,
+};
+
+use crate::{
+  connection::PeripheryConnection,
+  helpers::update::{add_update, make_update},
+  monitor::History,
+  periphery::PeripheryClient,
+  state::{all_resources_cache, deployment_status_cache},
+};
+
+impl PeripheryConnection {
+  /// Periphery only pushes docker events to subscribed Cores.
+  /// Older Peripheries don't have the API, and are just polled.
+  pub fn spawn_subscribe_docker_events(&self) {
+    if !all_resources_cache()
+      .load()
+      .servers
+      .contains_key(&self.args.id)
+    {
+      return;
+    }
+    let client = PeripheryClient {
+      id: self.args.id.clone(),
+      responses: self.responses.clone(),
+      terminals: self.terminals.clone(),
+    };
+    tokio::spawn(async move {
+      if let Err(e) = client.request(SubscribeDockerEvents {}).await {
+        debug!(
+          server_id = client.id,
+          "Failed to subscribe to docker events | {e:#}"
+        );
+      }
+    });
+  }
+
+  /// Routes the events pushed by Periphery to the Core event bus,
+  /// rather than the request / response channels.
+  pub async fn dispatch_event(&self, event: PeripheryEvent) {
+    let data = match event {
+      PeripheryEvent::ContainerStateChanged { container, state } => {
+        self.update_deployment_status(&container, state).await;
+        CoreEventData::ContainerStateChanged { container, state }
+      }
+      PeripheryEvent::ContainerOomKilled { container } => {
+        CoreEventData::ContainerOomKilled { container }
+      }
+      PeripheryEvent::ScheduleRun { name, log } => {
+        self.record_schedule_run(&name, log).await;
+        return;
+      }
+    };
+    self.emit_server_event(data).await;
+  }
+
+  /// Records the Periphery schedule run as an Update on the Server,
+  /// timed by the run rather than when it was received.
+  async fn record_schedule_run(&self, name: &str, log: Log) {
+    if !all_resources_cache()
+      .load()
+      .servers
+      .contains_key(&self.args.id)
+    {
+      return;
+    }
+    let mut update = make_update(
+      ResourceTarget::Server(self.args.id.clone()),
+      Operation::RunServerSchedule,
+      system_user(),
+    );
+    update.start_ts = log.start_ts;
+    let end_ts = log.end_ts;
+    update.logs.push(log);
+    update.finalize();
+    update.end_ts = Some(end_ts);
+    if let Err(e) = add_update(update).await {
+      warn!(
+        server_id = self.args.id,
+        "Failed to record run of schedule {name} | {e:#}"
+      );
+    }
+  }
+
+  /// Applies the state change to the cached Deployment status
+  /// right away, rather than waiting for the next poll.
+  async fn update_deployment_status(
+    &self,
+    container: &str,
+    state: ContainerStateStatusEnum,
+  ) {
+    let Some(id) = all_resources_cache()
+      .load()
+      .deployments
+      .values()
+      .find(|deployment| {
+        deployment.config.server_id == self.args.id
+          && deployment.name == container
+      })
+      .map(|deployment| deployment.id.clone())
+    else {
+      return;
+    };
+    let cache = deployment_status_cache();
+    let Some(status) = cache.get(&id).await else {
+      return;
+    };
+    let mut curr = status.curr.clone();
+    curr.state = DeploymentState::from(state);
+    if let Some(container) = &mut curr.container {
+      container.state = state;
+    }
+    let prev = Some(status.curr.state);
+    cache.insert(id, Arc::new(History { curr, prev })).await;
+  }
+}
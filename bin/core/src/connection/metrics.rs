@@ -0,0 +1,106 @@
+use std::{
+  collections::HashMap,
+  sync::{
+    Mutex,
+    atomic::{AtomicU64, Ordering},
+  },
+};
+
+use komodo_client::entities::{
+  komodo_timestamp, server::ServerConnectionMetrics,
+};
+use periphery_client::transport::{
+  EncodedTransportMessage, TransportMessageVariant,
+};
+
+/// Transport metrics for a Periphery connection.
+/// Shared by the connections which replace it at the same id,
+/// so they accumulate over reconnects.
+#[derive(Debug, Default)]
+pub struct ConnectionMetrics {
+  connects: AtomicU64,
+  bytes_sent: AtomicU64,
+  bytes_received: AtomicU64,
+  sent: VariantCounts,
+  received: VariantCounts,
+  /// (timestamp, error)
+  last_error: Mutex<Option<(i64, serror::Serror)>>,
+}
+
+impl ConnectionMetrics {
+  pub fn record_connected(&self) {
+    self.connects.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn record_sent(&self, message: &EncodedTransportMessage) {
+    self
+      .bytes_sent
+      .fetch_add(message.len() as u64, Ordering::Relaxed);
+    self.sent.record(message);
+  }
+
+  pub fn record_received(&self, message: &EncodedTransportMessage) {
+    self
+      .bytes_received
+      .fetch_add(message.len() as u64, Ordering::Relaxed);
+    self.received.record(message);
+  }
+
+  pub fn record_error(&self, error: serror::Serror) {
+    *self.last_error.lock().unwrap() =
+      Some((komodo_timestamp(), error));
+  }
+
+  pub fn get(
+    &self,
+    connected: bool,
+    latency_ms: Option<u64>,
+  ) -> ServerConnectionMetrics {
+    let (last_error_at, last_error) =
+      self.last_error.lock().unwrap().clone().unzip();
+    ServerConnectionMetrics {
+      connected,
+      latency_ms,
+      reconnects: self
+        .connects
+        .load(Ordering::Relaxed)
+        .saturating_sub(1),
+      bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+      bytes_received: self.bytes_received.load(Ordering::Relaxed),
+      messages_sent: self.sent.get(),
+      messages_received: self.received.get(),
+      last_error,
+      last_error_at,
+    }
+  }
+}
+
+/// Message counts indexed by the variant byte.
+#[derive(Debug, Default)]
+struct VariantCounts([AtomicU64; TransportMessageVariant::COUNT]);
+
+impl VariantCounts {
+  fn record(&self, message: &EncodedTransportMessage) {
+    if let Ok(variant) = message.variant() {
+      self.0[variant.as_byte() as usize]
+        .fetch_add(1, Ordering::Relaxed);
+    }
+  }
+
+  fn get(&self) -> HashMap<String, u64> {
+    self
+      .0
+      .iter()
+      .enumerate()
+      .filter_map(|(byte, count)| {
+        let count = count.load(Ordering::Relaxed);
+        if count == 0 {
+          return None;
+        }
+        let variant =
+          TransportMessageVariant::from_byte(byte as u8).ok()?;
+        Some((format!("{variant:?}"), count))
+      })
+      .collect()
+  }
+}
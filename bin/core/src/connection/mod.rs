@@ -1,25 +1,33 @@
 use std::{
+  collections::HashSet,
   sync::{
-    Arc,
-    atomic::{self, AtomicBool},
+    Arc, Mutex, OnceLock,
+    atomic::{self, AtomicBool, AtomicI64},
   },
   time::Duration,
 };
 
-use anyhow::anyhow;
+use anyhow::{Context as _, anyhow};
+use bytes::Bytes;
 use cache::CloneCache;
 use database::mungos::{by_id::update_one_by_id, mongodb::bson::doc};
 use encoding::{
-  CastBytes as _, Decode as _, EncodedJsonMessage, EncodedResponse,
-  WithChannel,
+  CastBytes as _, Decode as _, Encode as _, EncodedBodyMessage,
+  EncodedResponse, WithChannel,
 };
 use komodo_client::entities::{
+  ResourceTarget,
+  auth_event::{AuthEvent, AuthEventDirection, AuthEventFlow},
   builder::{AwsBuilderConfig, UrlBuilderConfig},
-  optional_str,
-  server::Server,
+  event::CoreEventData,
+  komodo_timestamp, optional_str,
+  server::{Server, TlsVerification, TransportDirection},
 };
+use noise::SessionKeys;
 use periphery_client::transport::{
-  EncodedTransportMessage, ResponseMessage, TransportMessage,
+  EncodedFileMessage, EncodedTransportMessage, PingMessage,
+  RequestMessage, ResponseMessage, TransportMessage, TransportNotice,
+  TransportOptions,
 };
 use serror::serror_into_anyhow_error;
 use tokio::sync::RwLock;
@@ -29,21 +37,38 @@ use transport::{
     ConnectionIdentifiers, LoginFlow, LoginFlowArgs,
     PublicKeyValidator,
   },
-  channel::{BufferedReceiver, Sender, buffered_channel},
+  channel::{BufferedReceiver, Priority, Sender},
+  chunk::ChunkAssembler,
+  rate_limit::RateLimiter,
+  spillover::Spillover,
+  tls::TlsVerify,
   websocket::{
     Websocket, WebsocketMessage, WebsocketReceiver as _,
-    WebsocketSender as _,
+    WebsocketSender as _, WebsocketSenderExt as _,
   },
 };
 use uuid::Uuid;
 
 use crate::{
-  config::{core_keys, periphery_public_keys},
-  state::db_client,
+  config::{core_config, core_keys, periphery_public_keys},
+  connection::{
+    audit::{RecordPublicKey, spawn_record_auth_event},
+    metrics::ConnectionMetrics,
+  },
+  helpers::event::emit_event,
+  state::{
+    all_resources_cache, connection_shards, db_client,
+    transport_trace,
+  },
 };
 
+pub mod audit;
 pub mod client;
+pub mod event;
+pub mod metrics;
 pub mod server;
+pub mod shard;
+pub mod trace;
 
 #[derive(Default)]
 pub struct PeripheryConnections(
@@ -105,6 +130,11 @@ pub struct PeripheryConnectionArgs<'a> {
   /// V1 legacy support.
   /// Only possible for Core -> Periphery.
   passkey: Option<&'a str>,
+  /// 0 uses the Core default, negative disables the limit.
+  max_bytes_per_sec: i64,
+  /// Only relevant for Core -> Periphery.
+  tls_verification: TlsVerification,
+  tls_pins: &'a [String],
 }
 
 impl PublicKeyValidator for PeripheryConnectionArgs<'_> {
@@ -125,6 +155,29 @@ impl PublicKeyValidator for PeripheryConnectionArgs<'_> {
         )
         .context("Core failed to validate Periphery public key")
     };
+    // Revoked keys are refused even if the Server still expects them.
+    if let Some(revoked) = db_client()
+      .revoked_keys
+      .find_one(doc! { "public_key": &public_key })
+      .await
+      .context("Failed to query database for revoked keys")?
+    {
+      spawn_update_attempted_public_key(
+        self.id.to_string(),
+        Some(public_key.clone()),
+      );
+      return Err(
+        anyhow!(
+          "{public_key} was revoked{}",
+          if revoked.reason.is_empty() {
+            String::new()
+          } else {
+            format!(" | {}", revoked.reason)
+          }
+        )
+        .context("Core failed to validate Periphery public key"),
+      );
+    }
     let core_to_periphery = self.address.is_some();
     match (self.periphery_public_key, core_to_periphery) {
       // The key matches expected.
@@ -159,6 +212,9 @@ impl<'a> PeripheryConnectionArgs<'a> {
       address: optional_str(&server.config.address),
       periphery_public_key: optional_str(&server.info.public_key),
       passkey: optional_str(&server.config.passkey),
+      max_bytes_per_sec: server.config.transport_max_bytes_per_sec,
+      tls_verification: server.config.tls_verification,
+      tls_pins: &server.config.tls_pins,
     }
   }
 
@@ -173,6 +229,11 @@ impl<'a> PeripheryConnectionArgs<'a> {
         &config.periphery_public_key,
       ),
       passkey: optional_str(&config.passkey),
+      max_bytes_per_sec: 0,
+      tls_verification: insecure_tls_verification(
+        config.insecure_tls,
+      ),
+      tls_pins: &[],
     }
   }
 
@@ -188,6 +249,11 @@ impl<'a> PeripheryConnectionArgs<'a> {
         &config.periphery_public_key,
       ),
       passkey: None,
+      max_bytes_per_sec: 0,
+      tls_verification: insecure_tls_verification(
+        config.insecure_tls,
+      ),
+      tls_pins: &[],
     }
   }
 
@@ -199,6 +265,9 @@ impl<'a> PeripheryConnectionArgs<'a> {
         .periphery_public_key
         .map(str::to_string),
       passkey: self.passkey.map(str::to_string),
+      max_bytes_per_sec: self.max_bytes_per_sec,
+      tls_verification: self.tls_verification,
+      tls_pins: self.tls_pins.to_vec(),
     }
   }
 
@@ -208,6 +277,25 @@ impl<'a> PeripheryConnectionArgs<'a> {
   ) -> bool {
     self == args.into()
   }
+
+  /// How to verify the Periphery tls certificate
+  /// for Core -> Periphery connections.
+  pub fn tls_verify(&self) -> TlsVerify {
+    match self.tls_verification {
+      TlsVerification::Insecure => TlsVerify::Insecure,
+      TlsVerification::System => TlsVerify::System,
+      TlsVerification::Pinned => TlsVerify::pinned(self.tls_pins),
+    }
+  }
+}
+
+/// Builders only support skipping tls verification or not.
+fn insecure_tls_verification(insecure_tls: bool) -> TlsVerification {
+  if insecure_tls {
+    TlsVerification::Insecure
+  } else {
+    TlsVerification::System
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -224,6 +312,14 @@ pub struct OwnedPeripheryConnectionArgs {
   /// V1 legacy support.
   /// Only possible for Core -> Periphery connection.
   pub passkey: Option<String>,
+  /// Limit on bytes per second sent and received.
+  /// 0 uses the Core default, negative disables the limit.
+  pub max_bytes_per_sec: i64,
+  /// How to verify the Periphery tls certificate.
+  /// Only relevant for Core -> Periphery connection.
+  pub tls_verification: TlsVerification,
+  /// The accepted certificate pins with `Pinned` verification.
+  pub tls_pins: Vec<String>,
 }
 
 impl OwnedPeripheryConnectionArgs {
@@ -233,6 +329,17 @@ impl OwnedPeripheryConnectionArgs {
       address: self.address.as_deref(),
       periphery_public_key: self.periphery_public_key.as_deref(),
       passkey: self.passkey.as_deref(),
+      max_bytes_per_sec: self.max_bytes_per_sec,
+      tls_verification: self.tls_verification,
+      tls_pins: &self.tls_pins,
+    }
+  }
+
+  /// The effective bytes per second limit, 0 for no limit.
+  fn bytes_per_sec_limit(&self) -> u64 {
+    match self.max_bytes_per_sec {
+      0 => core_config().transport_max_bytes_per_sec,
+      limit => u64::try_from(limit).unwrap_or_default(),
     }
   }
 }
@@ -253,11 +360,48 @@ impl<'a> From<&'a OwnedPeripheryConnectionArgs>
   }
 }
 
+/// Creates the channel for messages to Periphery,
+/// with spillover to disk if configured.
+fn buffered_channel(
+  args: &OwnedPeripheryConnectionArgs,
+) -> (
+  Sender<EncodedTransportMessage>,
+  BufferedReceiver<EncodedTransportMessage>,
+) {
+  let (sender, mut receiver) = transport::channel::buffered_channel();
+  let config = core_config();
+  if let Some(directory) = &config.transport_spillover_directory {
+    receiver.set_spillover(Spillover::new(
+      directory.join(&args.id),
+      config.transport_spillover_max_bytes,
+    ));
+  }
+  (sender, receiver)
+}
+
 /// Sends None as InProgress ping.
 pub type ResponseChannels =
-  CloneCache<Uuid, Sender<EncodedResponse<EncodedJsonMessage>>>;
+  CloneCache<Uuid, Sender<EncodedResponse<EncodedBodyMessage>>>;
 
-pub type TerminalChannels = CloneCache<Uuid, Sender<Vec<u8>>>;
+pub type TerminalChannels = CloneCache<Uuid, Sender<Bytes>>;
+
+pub type FileChannels = CloneCache<Uuid, Sender<EncodedFileMessage>>;
+
+/// Sent requests by channel, retransmitted after a reconnect
+/// while the response channel is still open.
+pub type RequestRetransmits = CloneCache<Uuid, PendingRequest>;
+
+/// A sent request, kept unencrypted so sensitive requests
+/// can be encrypted again with the keys of a new login.
+#[derive(Debug, Clone)]
+pub struct PendingRequest {
+  pub message: RequestMessage,
+  /// See [periphery_client::api::SENSITIVE_REQUESTS].
+  pub sensitive: bool,
+  /// Whether it was already sent again for a response
+  /// sealed with the keys of a previous login.
+  pub resent: bool,
+}
 
 #[derive(Debug)]
 pub struct PeripheryConnection {
@@ -269,6 +413,14 @@ pub struct PeripheryConnection {
   pub cancel: CancellationToken,
   /// Whether Periphery is currently connected.
   pub connected: AtomicBool,
+  /// Round trip latency of the latest Ping in ms,
+  /// or -1 if unknown.
+  latency_ms: AtomicI64,
+  /// Timestamp of the latest Pong.
+  last_pong: AtomicI64,
+  /// Keys of the current login to encrypt sensitive
+  /// requests with, if encryption was negotiated.
+  session_keys: Mutex<Option<SessionKeys>>,
   // These fields must be maintained if new connection replaces old
   // at the same server id.
   /// Stores latest connection error
@@ -277,6 +429,12 @@ pub struct PeripheryConnection {
   pub responses: Arc<ResponseChannels>,
   /// Forward bytes from Periphery to terminal channel handlers.
   pub terminals: Arc<TerminalChannels>,
+  /// Forward pulled file contents from Periphery.
+  pub files: Arc<FileChannels>,
+  /// Requests to retransmit after a reconnect.
+  pub requests: Arc<RequestRetransmits>,
+  /// Transport metrics, see GetServerConnectionMetrics.
+  pub metrics: Arc<ConnectionMetrics>,
 }
 
 impl PeripheryConnection {
@@ -286,16 +444,23 @@ impl PeripheryConnection {
     Arc<PeripheryConnection>,
     BufferedReceiver<EncodedTransportMessage>,
   ) {
-    let (sender, receiever) = buffered_channel();
+    let args = args.into();
+    let (sender, receiever) = buffered_channel(&args);
     (
       PeripheryConnection {
         sender,
-        args: args.into(),
+        args,
         cancel: CancellationToken::new(),
         connected: AtomicBool::new(false),
+        latency_ms: AtomicI64::new(-1),
+        last_pong: AtomicI64::new(0),
+        session_keys: Default::default(),
         error: Default::default(),
         responses: Default::default(),
         terminals: Default::default(),
+        files: Default::default(),
+        requests: Default::default(),
+        metrics: Default::default(),
       }
       .into(),
       receiever,
@@ -311,16 +476,23 @@ impl PeripheryConnection {
   ) {
     // Ensure this connection is cancelled.
     self.cancel();
-    let (sender, receiever) = buffered_channel();
+    let args = args.into();
+    let (sender, receiever) = buffered_channel(&args);
     (
       PeripheryConnection {
         sender,
-        args: args.into(),
+        args,
         cancel: CancellationToken::new(),
         connected: AtomicBool::new(false),
+        latency_ms: AtomicI64::new(-1),
+        last_pong: AtomicI64::new(0),
+        session_keys: Default::default(),
         error: self.error.clone(),
         responses: self.responses.clone(),
         terminals: self.terminals.clone(),
+        files: self.files.clone(),
+        requests: self.requests.clone(),
+        metrics: self.metrics.clone(),
       }
       .into(),
       receiever,
@@ -336,41 +508,114 @@ impl PeripheryConnection {
     &self,
     socket: &mut W,
     identifiers: ConnectionIdentifiers<'_>,
-  ) -> anyhow::Result<()> {
-    L::login(LoginFlowArgs {
+    options: TransportOptions,
+    server_query: &str,
+    remote: &str,
+  ) -> anyhow::Result<TransportOptions> {
+    let public_key = OnceLock::new();
+    let res = L::login(LoginFlowArgs {
       socket,
       identifiers,
       private_key: core_keys().load().private.as_str(),
-      public_key_validator: self.args.borrow(),
+      public_key_validator: RecordPublicKey {
+        validator: self.args.borrow(),
+        public_key: &public_key,
+      },
+      options,
     })
-    .await?;
-    // Clear attempted public key after successful login
-    spawn_update_attempted_public_key(self.args.id.clone(), None);
-    Ok(())
+    .await
+    .map(|success| {
+      self.set_session_keys(success.payload_keys());
+      success.options
+    });
+    spawn_record_auth_event(
+      AuthEvent {
+        server_id: self.args.id.clone(),
+        server_query: server_query.to_string(),
+        direction: self.auth_event_direction(),
+        flow: AuthEventFlow::Standard,
+        public_key: public_key.into_inner().unwrap_or_default(),
+        remote: remote.to_string(),
+        ..Default::default()
+      },
+      &res,
+    );
+    res
+  }
+
+  pub fn auth_event_direction(&self) -> AuthEventDirection {
+    if self.args.address.is_some() {
+      AuthEventDirection::CoreToPeriphery
+    } else {
+      AuthEventDirection::PeripheryToCore
+    }
   }
 
   pub async fn handle_socket<W: Websocket>(
     &self,
     socket: W,
     receiver: &mut BufferedReceiver<EncodedTransportMessage>,
+    options: TransportOptions,
   ) {
     let cancel = self.cancel.child_token();
+    let shard = connection_shards().shard(&self.args.id);
+    let _active = shard.track_active();
 
+    self.metrics.record_connected();
     self.set_connected(true);
     self.clear_error().await;
+    self
+      .emit_server_event(CoreEventData::ServerConnected {})
+      .await;
 
     let (mut ws_write, mut ws_read) = socket.split();
 
     ws_read.set_cancel(cancel.clone());
     receiver.set_cancel(cancel.clone());
+    // Requests spilled before a restart lost their caller,
+    // but are still replayed so what they started gets finished.
+    // Periphery runs each idempotency key once.
+    let spillable = self.spillable_requests().await;
+    let replayed = receiver
+      .replay_spillover(|message, previous_run| {
+        message.channel().filter(|channel| {
+          previous_run || spillable.contains(channel)
+        })
+      })
+      .await;
+    self.retransmit_requests(&replayed).await;
+    self.spawn_subscribe_docker_events();
+
+    let max_chunk_bytes = if options.chunking {
+      core_config().transport_max_chunk_bytes
+    } else {
+      0
+    };
+
+    let max_bytes_per_sec = self.args.bytes_per_sec_limit();
+    let send_limit = RateLimiter::new(max_bytes_per_sec);
+    let receive_limit = RateLimiter::new(max_bytes_per_sec);
 
     let forward_writes = async {
       loop {
         let Ok(message) = receiver.recv().await else {
           break;
         };
-        match ws_write.send(message.into_bytes()).await {
-          Ok(_) => receiver.clear_buffer(),
+        self.metrics.record_sent(&message);
+        transport_trace().record(
+          &self.args.id,
+          TransportDirection::Sent,
+          &message,
+        );
+        send_limit.acquire(message.len()).await;
+        match ws_write
+          .send_framed(message, options, max_chunk_bytes)
+          .await
+        {
+          Ok(_) => {
+            shard.record_sent();
+            receiver.clear_buffer()
+          }
           Err(e) => {
             self.set_error(e).await;
             break;
@@ -382,11 +627,72 @@ impl PeripheryConnection {
       cancel.cancel();
     };
 
+    let keepalive = async {
+      if !options.keepalive {
+        return;
+      }
+      let config = core_config();
+      let interval_ms = config.transport_heartbeat_interval_ms.max(1);
+      let max_missed = config.transport_heartbeat_max_missed.max(1);
+      // Missed heartbeats are only counted once a full
+      // interval passes without a Pong.
+      let timeout_ms = (interval_ms * max_missed) as i64;
+      self
+        .last_pong
+        .store(komodo_timestamp(), atomic::Ordering::Relaxed);
+      let mut interval =
+        tokio::time::interval(Duration::from_millis(interval_ms));
+      loop {
+        tokio::select! {
+          _ = interval.tick() => {}
+          _ = cancel.cancelled() => return,
+        }
+        let last_pong =
+          self.last_pong.load(atomic::Ordering::Relaxed);
+        if komodo_timestamp() - last_pong > timeout_ms {
+          // The socket may never close, so mark disconnected now
+          // rather than leave new requests waiting on it.
+          self.set_connected(false);
+          let e = anyhow!(
+            "Missed {max_missed} heartbeats, no response for over {timeout_ms}ms"
+          );
+          self.cancel_channels().await;
+          self.set_error(e).await;
+          break;
+        }
+        let ping = PingMessage {
+          sent_at: komodo_timestamp(),
+        };
+        if self.sender.send_message(ping).await.is_err() {
+          break;
+        }
+      }
+      cancel.cancel();
+    };
+
     let handle_reads = async {
+      let mut chunks = ChunkAssembler::new(options);
       loop {
         match ws_read.recv().await {
           Ok(WebsocketMessage::Message(message)) => {
-            self.handle_incoming_message(message).await
+            shard.record_received();
+            let message =
+              match message.normalize_framing(options.header) {
+                Ok(message) => message,
+                Err(e) => {
+                  warn!("Failed to parse Message bytes | {e:#}");
+                  continue;
+                }
+              };
+            self.metrics.record_received(&message);
+            transport_trace().record(
+              &self.args.id,
+              TransportDirection::Received,
+              &message,
+            );
+            // Slowing down reads pushes back on Periphery.
+            receive_limit.acquire(message.len()).await;
+            self.handle_incoming_message(message, &mut chunks).await
           }
           Ok(WebsocketMessage::Close(_))
           | Ok(WebsocketMessage::Closed) => {
@@ -394,7 +700,11 @@ impl PeripheryConnection {
             break;
           }
           Err(e) => {
-            self.set_error(e).await;
+            // Keepalive already recorded why it cancelled.
+            if !cancel.is_cancelled() {
+              self.set_error(e).await;
+            }
+            break;
           }
         };
       }
@@ -402,14 +712,41 @@ impl PeripheryConnection {
       cancel.cancel();
     };
 
-    tokio::join!(forward_writes, handle_reads);
+    tokio::join!(forward_writes, handle_reads, keepalive);
+
+    // Persist the requests not yet sent before reporting disconnected.
+    let spillable = self.spillable_requests().await;
+    receiver
+      .spill(|message| {
+        message
+          .channel()
+          .is_some_and(|channel| spillable.contains(&channel))
+      })
+      .await;
 
     self.set_connected(false);
+    self.latency_ms.store(-1, atomic::Ordering::Relaxed);
+    self
+      .emit_server_event(CoreEventData::ServerDisconnected {})
+      .await;
+  }
+
+  /// Builder connections aren't Servers, so don't emit for them.
+  async fn emit_server_event(&self, data: CoreEventData) {
+    if all_resources_cache()
+      .load()
+      .servers
+      .contains_key(&self.args.id)
+    {
+      emit_event(ResourceTarget::Server(self.args.id.clone()), data)
+        .await;
+    }
   }
 
   pub async fn handle_incoming_message(
     &self,
     message: EncodedTransportMessage,
+    chunks: &mut ChunkAssembler,
   ) {
     let message: TransportMessage = match message.decode() {
       Ok(res) => res,
@@ -418,10 +755,36 @@ impl PeripheryConnection {
         return;
       }
     };
+    let message = match chunks.reassemble(message) {
+      Ok(Some(message)) => message,
+      // Waiting on more chunks
+      Ok(None) => return,
+      Err(e) => {
+        warn!("Failed to reassemble chunked message | {e:#}");
+        return;
+      }
+    };
     match message {
       TransportMessage::Response(data) => {
         match data.decode().map(ResponseMessage::into_inner) {
           Ok(WithChannel { channel, data }) => {
+            let data = match self.session_keys() {
+              Some(keys) => {
+                match data.decrypt_body(&keys.receive, channel) {
+                  Ok(data) => data,
+                  Err(_) if self.resend_request(channel).await => {
+                    return;
+                  }
+                  Err(e) => {
+                    let e = e.context(
+                      "Response is sealed with the keys of a previous login. Retry the request",
+                    );
+                    (&e).encode()
+                  }
+                }
+              }
+              None => data,
+            };
             let Some(response_channel) =
               self.responses.get(&channel).await
             else {
@@ -463,6 +826,59 @@ impl PeripheryConnection {
           warn!("Failed to read Terminal message | {e:#}");
         }
       },
+      // Decoded by the transfer, so a corrupt piece fails
+      // the attempt and it resumes from the last good offset.
+      TransportMessage::File(data) => {
+        let channel_id = data.channel();
+        let Some(channel) = self.files.get(&channel_id).await else {
+          warn!(
+            "Failed to forward File message | No file channel found at {channel_id}"
+          );
+          return;
+        };
+        if let Err(e) = channel.send(data).await {
+          warn!(
+            "Failed to forward File message | Channel failure at {channel_id} | {e:#}"
+          );
+        }
+      }
+      TransportMessage::Event(data) => match data.decode() {
+        Ok(event) => self.dispatch_event(event).await,
+        Err(e) => {
+          warn!("Failed to read Event message | {e:#}");
+        }
+      },
+      TransportMessage::Notice(data) => match data.decode() {
+        Ok(TransportNotice::PeripheryDraining { action }) => {
+          info!(
+            server_id = self.args.id,
+            "Periphery is draining before host {action}"
+          );
+          self
+            .emit_server_event(CoreEventData::ServerDraining {
+              action,
+            })
+            .await;
+        }
+        Ok(notice) => {
+          warn!("Received unexpected Notice message | {notice:?}");
+        }
+        Err(e) => {
+          warn!("Failed to read Notice message | {e:#}");
+        }
+      },
+      TransportMessage::Pong(data) => match data.decode() {
+        Ok(PingMessage { sent_at }) => {
+          let now = komodo_timestamp();
+          self.last_pong.store(now, atomic::Ordering::Relaxed);
+          self
+            .latency_ms
+            .store((now - sent_at).max(0), atomic::Ordering::Relaxed);
+        }
+        Err(e) => {
+          warn!("Failed to read Pong message | {e:#}");
+        }
+      },
       //
       other => {
         warn!("Received unexpected transport message | {other:?}");
@@ -470,6 +886,133 @@ impl PeripheryConnection {
     }
   }
 
+  /// Requests sent before a reconnect may never have reached
+  /// Periphery, or their responses may have been lost.
+  /// Periphery runs each idempotency key once, so it is safe
+  /// to send them again. The `replayed` channels were already
+  /// queued again from the spillover.
+  async fn retransmit_requests(&self, replayed: &[Uuid]) {
+    for (channel, request) in self.requests.get_entries().await {
+      if replayed.contains(&channel) {
+        continue;
+      }
+      let waiting = self
+        .responses
+        .get(&channel)
+        .await
+        .is_some_and(|sender| !sender.is_closed());
+      if !waiting {
+        self.requests.remove(&channel).await;
+        self.responses.remove(&channel).await;
+        continue;
+      }
+      let message = match self.encode_request(request) {
+        Ok(message) => message,
+        Err(e) => {
+          warn!("Failed to encode request at {channel} | {e:#}");
+          continue;
+        }
+      };
+      if let Err(e) = self
+        .sender
+        .send_with_priority(message, Priority::Request)
+        .await
+      {
+        warn!("Failed to retransmit request at {channel} | {e:#}");
+      }
+    }
+  }
+
+  /// The channels of the requests which can be spilled to disk:
+  /// those still waiting on a response, and without secrets
+  /// in the params. The others are dropped on disconnect,
+  /// sensitive ones are still retransmitted from memory.
+  async fn spillable_requests(&self) -> HashSet<Uuid> {
+    let mut channels = HashSet::new();
+    for (channel, request) in self.requests.get_entries().await {
+      if request.sensitive {
+        continue;
+      }
+      if self
+        .responses
+        .get(&channel)
+        .await
+        .is_some_and(|sender| !sender.is_closed())
+      {
+        channels.insert(channel);
+      }
+    }
+    channels
+  }
+
+  /// Closes the terminal and file channels when the connection
+  /// is lost in a way they wouldn't otherwise notice.
+  /// Requests waiting on a response are kept, they are
+  /// retransmitted after a reconnect, or fail on their own timeout.
+  async fn cancel_channels(&self) {
+    for channel in self.terminals.get_keys().await {
+      self.terminals.remove(&channel).await;
+    }
+    for channel in self.files.get_keys().await {
+      self.files.remove(&channel).await;
+    }
+  }
+
+  /// Sends the request again when its response couldn't be
+  /// decrypted, as Periphery sealed it with the keys of a previous
+  /// login. Periphery responds from its idempotency cache, sealed
+  /// with the current keys. Only done once per request,
+  /// returns whether it was sent.
+  async fn resend_request(&self, channel: Uuid) -> bool {
+    let Some(request) = self.requests.get(&channel).await else {
+      return false;
+    };
+    if request.resent || request.message.idempotency_key().is_none() {
+      return false;
+    }
+    let request = PendingRequest {
+      resent: true,
+      ..request
+    };
+    self.requests.insert(channel, request.clone()).await;
+    let res = async {
+      let message = self.encode_request(request)?;
+      self
+        .sender
+        .send_with_priority(message, Priority::Request)
+        .await
+    }
+    .await;
+    if let Err(e) = &res {
+      warn!("Failed to resend request at {channel} | {e:#}");
+    }
+    res.is_ok()
+  }
+
+  /// Sensitive requests are encrypted with the keys
+  /// of the current login, if encryption was negotiated.
+  pub fn encode_request(
+    &self,
+    PendingRequest {
+      message, sensitive, ..
+    }: PendingRequest,
+  ) -> anyhow::Result<EncodedTransportMessage> {
+    match self.session_keys().filter(|_| sensitive) {
+      Some(keys) => {
+        message.encrypt(&keys.send).map(|message| message.encode())
+      }
+      None => Ok(message.encode()),
+    }
+  }
+
+  pub fn set_session_keys(&self, keys: Option<SessionKeys>) {
+    *self.session_keys.lock().unwrap() = keys;
+  }
+
+  fn session_keys(&self) -> Option<SessionKeys> {
+    *self.session_keys.lock().unwrap()
+  }
+
   pub fn set_connected(&self, connected: bool) {
     self.connected.store(connected, atomic::Ordering::Relaxed);
   }
@@ -478,15 +1021,27 @@ impl PeripheryConnection {
     self.connected.load(atomic::Ordering::Relaxed)
   }
 
-  /// Polls connected 3 times (500ms in between) before bailing.
+  /// The round trip latency of the latest Ping,
+  /// if the connection uses keepalive.
+  pub fn latency_ms(&self) -> Option<u64> {
+    u64::try_from(self.latency_ms.load(atomic::Ordering::Relaxed))
+      .ok()
+  }
+
+  /// Polls connected `connection_poll_times`
+  /// (`connection_poll_interval_ms` in between) before bailing.
   pub async fn bail_if_not_connected(&self) -> anyhow::Result<()> {
-    const POLL_TIMES: usize = 3;
-    for i in 0..POLL_TIMES {
+    let config = core_config();
+    let poll_times = config.connection_poll_times.max(1);
+    for i in 0..poll_times {
       if self.connected() {
         return Ok(());
       }
-      if i < POLL_TIMES - 1 {
-        tokio::time::sleep(Duration::from_millis(500)).await;
+      if i < poll_times - 1 {
+        tokio::time::sleep(Duration::from_millis(
+          config.connection_poll_interval_ms,
+        ))
+        .await;
       }
     }
     if let Some(e) = self.error().await {
@@ -501,8 +1056,10 @@ impl PeripheryConnection {
   }
 
   pub async fn set_error(&self, e: anyhow::Error) {
+    let e = serror::Serror::from(e);
+    self.metrics.record_error(e.clone());
     let mut error = self.error.write().await;
-    *error = Some(e.into());
+    *error = Some(e);
   }
 
   pub async fn clear_error(&self) {
@@ -515,9 +1072,32 @@ impl PeripheryConnection {
   }
 }
 
+/// Spawn task to set the 'attempted_tls_pin'
+/// for easy manual connection acceptance later on.
+pub fn spawn_update_attempted_tls_pin(id: String, pin: String) {
+  tokio::spawn(async move {
+    if let Err(e) = update_one_by_id(
+      &db_client().servers,
+      &id,
+      doc! {
+        "$set": {
+          "info.attempted_tls_pin": &pin,
+        }
+      },
+      None,
+    )
+    .await
+    {
+      warn!(
+        "Failed to update attempted tls pin for Server {id} | {e:?}"
+      );
+    };
+  });
+}
+
 /// Spawn task to set the 'attempted_public_key'
 /// for easy manual connection acceptance later on.
-fn spawn_update_attempted_public_key(
+pub fn spawn_update_attempted_public_key(
   id: String,
   public_key: impl Into<Option<String>>,
 ) {
@@ -1,15 +1,19 @@
-use std::str::FromStr;
+use std::{net::SocketAddr, str::FromStr, sync::OnceLock};
 
 use anyhow::{Context, anyhow};
 use axum::{
-  extract::{Query, WebSocketUpgrade},
+  extract::{ConnectInfo, Query, WebSocketUpgrade},
   http::{HeaderMap, StatusCode},
   response::Response,
 };
 use database::mungos::mongodb::bson::{doc, oid::ObjectId};
 use komodo_client::{
-  api::write::{CreateBuilder, CreateServer, UpdateResourceMeta},
+  api::{
+    execute::RunProcedure,
+    write::{CreateBuilder, CreateServer, UpdateResourceMeta},
+  },
   entities::{
+    auth_event::{AuthEvent, AuthEventDirection, AuthEventFlow},
     builder::{PartialBuilderConfig, PartialServerBuilderConfig},
     komodo_timestamp,
     onboarding_key::OnboardingKey,
@@ -18,15 +22,17 @@ use komodo_client::{
   },
 };
 use periphery_client::{
-  api::PeripheryConnectionQuery, transport::LoginMessage,
+  api::PeripheryConnectionQuery,
+  transport::{LoginMessage, TransportOptions},
 };
 use resolver_api::Resolve;
+use serde::Serialize;
 use serror::{AddStatusCode, AddStatusCodeError};
 use tracing::Instrument;
 use transport::{
   auth::{
     HeaderConnectionIdentifiers, LoginFlow, LoginFlowArgs,
-    PublicKeyValidator, ServerLoginFlow,
+    PublicKeyValidator, ServerLoginFlow, negotiate_options,
   },
   websocket::{
     Websocket, WebsocketExt as _, axum::AxumWebsocket,
@@ -35,22 +41,38 @@ use transport::{
 };
 
 use crate::{
-  api::write::WriteArgs,
-  config::core_keys,
-  helpers::query::id_or_name_filter,
+  api::{
+    execute::{ExecuteArgs, ExecuteRequest},
+    write::WriteArgs,
+  },
+  config::{core_config, core_keys},
+  helpers::{
+    query::id_or_name_filter, update::init_execution_update,
+  },
   resource::KomodoResource,
-  state::{db_client, periphery_connections},
+  state::{connection_shards, db_client, periphery_connections},
 };
 
-use super::PeripheryConnectionArgs;
+use super::{
+  PeripheryConnectionArgs,
+  audit::{RecordPublicKey, spawn_record_auth_event},
+};
 
 pub async fn handler(
   Query(PeripheryConnectionQuery {
     server: server_query,
+    compression,
+    chunking,
+    keepalive,
+    encoding,
+    encryption,
+    header,
   }): Query<PeripheryConnectionQuery>,
+  ConnectInfo(socket_addr): ConnectInfo<SocketAddr>,
   mut headers: HeaderMap,
   ws: WebSocketUpgrade,
 ) -> serror::Result<Response> {
+  let remote = socket_addr.ip().to_string();
   let identifiers =
     HeaderConnectionIdentifiers::extract(&mut headers)
       .status_code(StatusCode::UNAUTHORIZED)?;
@@ -62,6 +84,13 @@ pub async fn handler(
     );
   }
 
+  if !connection_shards().try_accept() {
+    return Err(
+      anyhow!("Core is accepting too many connections, retry later")
+        .status_code(StatusCode::TOO_MANY_REQUESTS),
+    );
+  }
+
   // Handle connection vs. onboarding flow.
   match Server::coll()
     .find_one(id_or_name_filter(&server_query))
@@ -69,11 +98,29 @@ pub async fn handler(
     .context("Failed to query database for Server")?
   {
     Some(server) => {
-      existing_server_handler(server_query, server, identifiers, ws)
-        .await
+      let options = negotiate_options(
+        core_config().transport_compression,
+        core_config().transport_encoding,
+        compression,
+        encoding,
+        chunking,
+        keepalive,
+        encryption,
+        header,
+      );
+      existing_server_handler(
+        server_query,
+        server,
+        identifiers,
+        options,
+        remote,
+        ws,
+      )
+      .await
     }
     None if ObjectId::from_str(&server_query).is_err() => {
-      onboard_server_handler(server_query, identifiers, ws).await
+      onboard_server_handler(server_query, identifiers, remote, ws)
+        .await
     }
     None => Err(
       anyhow!("Must provide name based Server specifier for onboarding flow, name cannot be valid ObjectId (hex)")
@@ -86,6 +133,8 @@ async fn existing_server_handler(
   server_query: String,
   server: Server,
   identifiers: HeaderConnectionIdentifiers,
+  options: TransportOptions,
+  remote: String,
   ws: WebSocketUpgrade,
 ) -> serror::Result<Response> {
   if !server.config.enabled {
@@ -113,6 +162,12 @@ async fn existing_server_handler(
     );
   }
 
+  let login_permit = connection_shards()
+    .shard(&server.id)
+    .try_login()
+    .context("Core connection shard is busy, retry later")
+    .status_code(StatusCode::SERVICE_UNAVAILABLE)?;
+
   let (connection, mut receiver) = periphery_connections()
     .insert(
       server.id.clone(),
@@ -144,29 +199,45 @@ async fn existing_server_handler(
         .handle_login::<_, ServerLoginFlow>(
           &mut socket,
           identifiers.build(query.as_bytes()),
+          options,
+          &server_query,
+          &remote,
         )
         .await
     }
     .instrument(span)
     .await;
+    drop(login_permit);
 
-    if let Err(e) = login {
-      connection.set_error(e).await;
-      return;
-    }
+    let options = match login {
+      Ok(options) => options,
+      Err(e) => {
+        connection.set_error(e).await;
+        return;
+      }
+    };
 
-    connection.handle_socket(socket, &mut receiver).await
+    connection
+      .handle_socket(socket, &mut receiver, options)
+      .await
   }))
 }
 
 async fn onboard_server_handler(
   server_query: String,
   identifiers: HeaderConnectionIdentifiers,
+  remote: String,
   ws: WebSocketUpgrade,
 ) -> serror::Result<Response> {
+  let login_permit = connection_shards()
+    .shard(&server_query)
+    .try_login()
+    .context("Core connection shard is busy, retry later")
+    .status_code(StatusCode::SERVICE_UNAVAILABLE)?;
   Ok(ws.on_upgrade(|socket| async move {
     let query =
       format!("server={}", urlencoding::encode(&server_query));
+    let host = identifiers.host().unwrap_or_default();
     let mut socket = AxumWebsocket(socket);
 
     if let Err(e) = socket.send_message(LoginMessage::OnboardingFlow(true)).await.context(
@@ -176,20 +247,38 @@ async fn onboard_server_handler(
       return;
     };
 
-    let onboarding_key = match ServerLoginFlow::login(LoginFlowArgs {
+    let attempted_public_key = OnceLock::new();
+    let login = ServerLoginFlow::login(LoginFlowArgs {
       socket: &mut socket,
       identifiers: identifiers.build(query.as_bytes()),
       private_key: core_keys().load().private.as_str(),
-      public_key_validator: CreationKeyValidator,
+      public_key_validator: RecordPublicKey {
+        validator: CreationKeyValidator,
+        public_key: &attempted_public_key,
+      },
+      // The onboarding connection is closed after the Server is created.
+      options: TransportOptions::default(),
     })
-    .await
-    {
-      Ok(onboarding_key) => onboarding_key,
+    .await;
+    spawn_record_auth_event(
+      AuthEvent {
+        server_query: server_query.clone(),
+        direction: AuthEventDirection::PeripheryToCore,
+        flow: AuthEventFlow::Onboarding,
+        public_key: attempted_public_key.into_inner().unwrap_or_default(),
+        remote: remote.clone(),
+        ..Default::default()
+      },
+      &login,
+    );
+    let onboarding_key = match login {
+      Ok(success) => success.validation,
       Err(e) => {
         debug!("Server {server_query} failed to onboard | {e:#}");
         return;
       }
     };
+    drop(login_permit);
 
     // Post onboarding login 1: Receive public key
     let public_key = match socket
@@ -199,20 +288,61 @@ async fn onboard_server_handler(
       Ok(public_key) => public_key,
       Err(e) => {
         warn!("Server {server_query} failed to onboard | failed to receive Server public key | {e:#}");
+        release_onboarding_key_use(&onboarding_key.public_key).await;
         return;
       }
     };
 
-    let server_id = match create_server_maybe_builder(
-      server_query,
-      public_key.into_inner(),
-      onboarding_key.copy_server,
-      onboarding_key.tags,
-      onboarding_key.create_builder
-    ).await {
-      Ok(server_id) => server_id,
+    let public_key = public_key.into_inner();
+
+    let res = async {
+      ExternalOnboardingValidator {
+        server: &server_query,
+        host: &host,
+        onboarding_key: &onboarding_key.name,
+      }
+      .validate(public_key.clone())
+      .await
+      .with_context(|| format!("Server {server_query} was rejected by onboarding validator"))?;
+      if core_config().onboarding_require_approval {
+        queue_pending_server(
+          &server_query,
+          &public_key,
+          &remote,
+          &host,
+          &onboarding_key,
+        ).await?;
+        return Ok(None);
+      }
+      create_server_maybe_builder(
+        server_query.clone(),
+        public_key,
+        onboarding_key.copy_server.clone(),
+        onboarding_key.tags.clone(),
+        onboarding_key.create_builder
+      ).await.map(Some)
+    }.await;
+
+    let server_id = match res {
+      Ok(Some(server_id)) => server_id,
+      // The use is reserved again when the
+      // Server is approved and created.
+      Ok(None) => {
+        debug!("Server {server_query} is pending onboarding approval");
+        release_onboarding_key_use(&onboarding_key.public_key).await;
+        let e = anyhow!("Server {server_query} is pending approval by a Komodo admin");
+        if let Err(e) = socket
+          .send_login_error(&e)
+          .await
+          .context("Failed to send Server pending approval to client")
+        {
+          warn!("{e:#}");
+        }
+        return;
+      }
       Err(e) => {
         warn!("{e:#}");
+        release_onboarding_key_use(&onboarding_key.public_key).await;
         if let Err(e) = socket
           .send_login_error(&e)
           .await
@@ -226,7 +356,7 @@ async fn onboard_server_handler(
     };
 
     if let Err(e) = socket
-      .send_message(LoginMessage::Success)
+      .send_message(LoginMessage::Success(TransportOptions::default()))
       .await
       .context("Failed to send Login Onboarding Successful message")
     {
@@ -251,6 +381,100 @@ async fn onboard_server_handler(
   }))
 }
 
+/// Adds the Server to the onboarding approval queue,
+/// or refreshes the existing entry for the public key.
+async fn queue_pending_server(
+  server_query: &str,
+  public_key: &str,
+  remote: &str,
+  host: &str,
+  onboarding_key: &OnboardingKey,
+) -> anyhow::Result<()> {
+  let now = komodo_timestamp();
+  db_client()
+    .pending_servers
+    .update_one(
+      doc! { "public_key": public_key },
+      doc! {
+        "$set": {
+          "name": server_query,
+          "remote": remote,
+          "host": host,
+          "onboarding_key": &onboarding_key.public_key,
+          "onboarding_key_name": &onboarding_key.name,
+          "last_attempt_at": now,
+        },
+        "$setOnInsert": { "requested_at": now },
+      },
+    )
+    .upsert(true)
+    .await
+    .context("Failed to add Server to onboarding approval queue")?;
+  Ok(())
+}
+
+/// Creates an approved [PendingServer][komodo_client::entities::pending_server::PendingServer],
+/// applying the onboarding key settings if it still exists.
+/// Returns the Server id.
+pub async fn create_approved_server(
+  name: String,
+  public_key: String,
+  onboarding_key: &str,
+) -> anyhow::Result<String> {
+  let onboarding_key = db_client()
+    .onboarding_keys
+    .find_one(doc! { "public_key": onboarding_key })
+    .await
+    .context("Failed to query database for Server onboarding keys")?;
+  let Some(onboarding_key) = onboarding_key else {
+    return create_server_maybe_builder(
+      name,
+      public_key,
+      String::new(),
+      Vec::new(),
+      false,
+    )
+    .await;
+  };
+  let server_id = create_server_maybe_builder(
+    name,
+    public_key,
+    onboarding_key.copy_server,
+    onboarding_key.tags,
+    onboarding_key.create_builder,
+  )
+  .await?;
+  if let Err(e) = db_client()
+    .onboarding_keys
+    .update_one(
+      doc! { "public_key": &onboarding_key.public_key },
+      doc! {
+        "$push": { "onboarded": &server_id },
+        "$inc": { "uses": 1 },
+      },
+    )
+    .await
+  {
+    warn!("Failed to update onboarding key 'onboarded' | {e:?}");
+  }
+  Ok(server_id)
+}
+
+/// Gives back the use reserved by [CreationKeyValidator]
+/// when the onboarding fails after login.
+async fn release_onboarding_key_use(public_key: &str) {
+  if let Err(e) = db_client()
+    .onboarding_keys
+    .update_one(
+      doc! { "public_key": public_key },
+      doc! { "$inc": { "uses": -1 } },
+    )
+    .await
+  {
+    warn!("Failed to release onboarding key use | {e:?}");
+  }
+}
+
 async fn create_server_maybe_builder(
   server_query: String,
   public_key: String,
@@ -356,14 +580,125 @@ impl PublicKeyValidator for CreationKeyValidator {
       .await
       .context("Failed to query database for Server onboarding keys")?
       .context("Matching Server onboarding key not found")?;
-    // Check enabled and not expired.
-    if onboarding_key.enabled
-      && (onboarding_key.expires == 0
-        || onboarding_key.expires > komodo_timestamp())
-    {
-      Ok(onboarding_key)
-    } else {
-      Err(anyhow!("Onboarding key is invalid"))
+    // Check enabled, not expired, and uses remaining.
+    if !onboarding_key.is_valid() {
+      return Err(anyhow!("Onboarding key is invalid"));
+    }
+    // Reserve a use. The filter makes concurrent
+    // onboardings unable to exceed the limit.
+    let mut filter = doc! { "public_key": &public_key };
+    if let Some(limit) = onboarding_key.use_limit() {
+      filter.insert(
+        "$or",
+        vec![
+          doc! { "uses": { "$exists": false } },
+          doc! { "uses": { "$lt": limit } },
+        ],
+      );
     }
+    let reserved = db_client()
+      .onboarding_keys
+      .update_one(filter, doc! { "$inc": { "uses": 1 } })
+      .await
+      .context("Failed to reserve Server onboarding key use")?;
+    if reserved.modified_count == 0 {
+      return Err(anyhow!("Onboarding key has no uses remaining"));
+    }
+    Ok(onboarding_key)
+  }
+}
+
+/// Optional external approval of Servers during onboarding,
+/// using the Core `onboarding_validator_url` and / or
+/// `onboarding_validator_procedure`.
+struct ExternalOnboardingValidator<'a> {
+  server: &'a str,
+  host: &'a str,
+  /// The onboarding key name
+  onboarding_key: &'a str,
+}
+
+#[derive(Serialize)]
+struct OnboardingValidationBody<'a> {
+  server: &'a str,
+  public_key: &'a str,
+  host: &'a str,
+  onboarding_key: &'a str,
+}
+
+impl PublicKeyValidator for ExternalOnboardingValidator<'_> {
+  type ValidationResult = ();
+  async fn validate(&self, public_key: String) -> anyhow::Result<()> {
+    let config = core_config();
+    if !config.onboarding_validator_url.is_empty() {
+      self.validate_with_url(&public_key).await?;
+    }
+    if !config.onboarding_validator_procedure.is_empty() {
+      validate_with_procedure(&config.onboarding_validator_procedure)
+        .await?;
+    }
+    Ok(())
+  }
+}
+
+impl ExternalOnboardingValidator<'_> {
+  async fn validate_with_url(
+    &self,
+    public_key: &str,
+  ) -> anyhow::Result<()> {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    let config = core_config();
+    let mut req = CLIENT
+      .get_or_init(reqwest::Client::new)
+      .post(&config.onboarding_validator_url)
+      .json(&OnboardingValidationBody {
+        server: self.server,
+        public_key,
+        host: self.host,
+        onboarding_key: self.onboarding_key,
+      });
+    if !config.onboarding_validator_secret.is_empty() {
+      req = req.bearer_auth(&config.onboarding_validator_secret);
+    }
+    let res = req
+      .send()
+      .await
+      .context("Failed to send request to onboarding validator")?;
+    let status = res.status();
+    if status.is_success() {
+      return Ok(());
+    }
+    let text = res.text().await.unwrap_or_default();
+    Err(anyhow!("Onboarding validator denied | {status} | {text}"))
+  }
+}
+
+/// Runs the Procedure, failing if the Procedure is unsuccessful.
+async fn validate_with_procedure(
+  procedure: &str,
+) -> anyhow::Result<()> {
+  let request = ExecuteRequest::RunProcedure(RunProcedure {
+    procedure: procedure.to_string(),
+  });
+  let update = init_execution_update(&request, system_user())
+    .await
+    .context(
+    "Failed to init onboarding validator Procedure update",
+  )?;
+  let ExecuteRequest::RunProcedure(request) = request else {
+    unreachable!()
+  };
+  let update = request
+    .resolve(&ExecuteArgs {
+      user: system_user().to_owned(),
+      update,
+    })
+    .await
+    .map_err(|e| e.error)
+    .context("Failed to run onboarding validator Procedure")?;
+  if update.success {
+    Ok(())
+  } else {
+    Err(anyhow!("Onboarding validator Procedure was unsuccessful"))
   }
 }
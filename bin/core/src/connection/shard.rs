@@ -0,0 +1,192 @@
+use std::{
+  hash::{DefaultHasher, Hash, Hasher},
+  sync::{
+    Mutex,
+    atomic::{AtomicU64, Ordering},
+  },
+  time::Instant,
+};
+
+use komodo_client::{
+  api::read::ListConnectionShardsResponse,
+  entities::server::ConnectionShardStats,
+};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::config::core_config;
+
+/// Periphery connections are split between shards by Server id,
+/// so login load and metrics can be tracked per shard.
+pub struct ConnectionShards {
+  shards: Vec<ConnectionShard>,
+  accept: AcceptRateLimiter,
+}
+
+impl Default for ConnectionShards {
+  fn default() -> Self {
+    let config = core_config();
+    let max_logins = config.connection_shard_max_logins.max(1);
+    Self {
+      shards: (0..config.connection_shards.max(1))
+        .map(|index| ConnectionShard::new(index, max_logins))
+        .collect(),
+      accept: AcceptRateLimiter::new(
+        config.connection_accept_rate,
+        config.connection_accept_burst,
+      ),
+    }
+  }
+}
+
+impl ConnectionShards {
+  pub fn shard(&self, id: &str) -> &ConnectionShard {
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    &self.shards[hasher.finish() as usize % self.shards.len()]
+  }
+
+  /// Returns false if the inbound connection
+  /// is over `connection_accept_rate`.
+  pub fn try_accept(&self) -> bool {
+    self.accept.try_acquire()
+  }
+
+  pub fn stats(&self) -> ListConnectionShardsResponse {
+    ListConnectionShardsResponse {
+      rate_limited: self.accept.rejected.load(Ordering::Relaxed),
+      shards: self
+        .shards
+        .iter()
+        .map(ConnectionShard::stats)
+        .collect(),
+    }
+  }
+}
+
+pub struct ConnectionShard {
+  index: usize,
+  max_logins: usize,
+  /// Bounds the concurrent logins on the shard.
+  logins: Semaphore,
+  active: AtomicU64,
+  accepted: AtomicU64,
+  rejected_busy: AtomicU64,
+  messages_received: AtomicU64,
+  messages_sent: AtomicU64,
+}
+
+impl ConnectionShard {
+  fn new(index: usize, max_logins: usize) -> ConnectionShard {
+    ConnectionShard {
+      index,
+      max_logins,
+      logins: Semaphore::new(max_logins),
+      active: Default::default(),
+      accepted: Default::default(),
+      rejected_busy: Default::default(),
+      messages_received: Default::default(),
+      messages_sent: Default::default(),
+    }
+  }
+
+  /// Reserve a login slot for an inbound connection,
+  /// or None if the shard login pool is full.
+  pub fn try_login(&self) -> Option<SemaphorePermit<'_>> {
+    match self.logins.try_acquire() {
+      Ok(permit) => {
+        self.accepted.fetch_add(1, Ordering::Relaxed);
+        Some(permit)
+      }
+      Err(_) => {
+        self.rejected_busy.fetch_add(1, Ordering::Relaxed);
+        None
+      }
+    }
+  }
+
+  /// Wait for a login slot for an outbound connection.
+  pub async fn login(&self) -> Option<SemaphorePermit<'_>> {
+    // Only errors if the semaphore is closed, which it never is.
+    self.logins.acquire().await.ok()
+  }
+
+  /// Counts the connection as active until the guard is dropped.
+  pub fn track_active(&self) -> ActiveConnection<'_> {
+    self.active.fetch_add(1, Ordering::Relaxed);
+    ActiveConnection(self)
+  }
+
+  pub fn record_received(&self) {
+    self.messages_received.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn record_sent(&self) {
+    self.messages_sent.fetch_add(1, Ordering::Relaxed);
+  }
+
+  fn stats(&self) -> ConnectionShardStats {
+    ConnectionShardStats {
+      index: self.index,
+      active: self.active.load(Ordering::Relaxed),
+      logging_in: (self.max_logins - self.logins.available_permits())
+        as u64,
+      max_logins: self.max_logins as u64,
+      accepted: self.accepted.load(Ordering::Relaxed),
+      rejected_busy: self.rejected_busy.load(Ordering::Relaxed),
+      messages_received: self
+        .messages_received
+        .load(Ordering::Relaxed),
+      messages_sent: self.messages_sent.load(Ordering::Relaxed),
+    }
+  }
+}
+
+pub struct ActiveConnection<'a>(&'a ConnectionShard);
+
+impl Drop for ActiveConnection<'_> {
+  fn drop(&mut self) {
+    self.0.active.fetch_sub(1, Ordering::Relaxed);
+  }
+}
+
+/// Token bucket limiting the inbound connections accepted per second.
+struct AcceptRateLimiter {
+  /// Tokens added per second. 0 disables the limit.
+  rate: f64,
+  burst: f64,
+  /// (available tokens, last refill)
+  bucket: Mutex<(f64, Instant)>,
+  rejected: AtomicU64,
+}
+
+impl AcceptRateLimiter {
+  fn new(rate: u32, burst: u32) -> AcceptRateLimiter {
+    let burst = burst.max(1) as f64;
+    AcceptRateLimiter {
+      rate: rate as f64,
+      burst,
+      bucket: Mutex::new((burst, Instant::now())),
+      rejected: Default::default(),
+    }
+  }
+
+  fn try_acquire(&self) -> bool {
+    if self.rate == 0.0 {
+      return true;
+    }
+    let mut bucket = self.bucket.lock().unwrap();
+    let (tokens, last) = &mut *bucket;
+    let now = Instant::now();
+    *tokens = (*tokens
+      + now.duration_since(*last).as_secs_f64() * self.rate)
+      .min(self.burst);
+    *last = now;
+    if *tokens >= 1.0 {
+      *tokens -= 1.0;
+      true
+    } else {
+      self.rejected.fetch_add(1, Ordering::Relaxed);
+      false
+    }
+  }
+}
@@ -0,0 +1,72 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use komodo_client::entities::{
+  komodo_timestamp,
+  server::{TransportDirection, TransportTraceEntry},
+};
+use periphery_client::transport::EncodedTransportMessage;
+
+use crate::config::core_config;
+
+/// The max entries kept, the oldest are dropped first.
+const TRANSPORT_TRACE_CAPACITY: usize = 10_000;
+
+/// Ring buffer of transport message headers,
+/// recorded when `transport_trace` is enabled.
+#[derive(Default)]
+pub struct TransportTrace(Mutex<VecDeque<TransportTraceEntry>>);
+
+impl TransportTrace {
+  pub fn enabled(&self) -> bool {
+    core_config().transport_trace
+  }
+
+  /// No-op unless `transport_trace` is enabled.
+  pub fn record(
+    &self,
+    server: &str,
+    direction: TransportDirection,
+    message: &EncodedTransportMessage,
+  ) {
+    if !self.enabled() {
+      return;
+    }
+    let entry = TransportTraceEntry {
+      ts: komodo_timestamp(),
+      server: server.to_string(),
+      direction,
+      variant: message
+        .variant()
+        .map(|variant| format!("{variant:?}"))
+        .unwrap_or_default(),
+      channel: message.channel().map(|channel| channel.to_string()),
+      size: message.len() as u64,
+      compressed: message.is_compressed(),
+    };
+    let mut entries = self.0.lock().unwrap();
+    if entries.len() >= TRANSPORT_TRACE_CAPACITY {
+      entries.pop_front();
+    }
+    entries.push_back(entry);
+  }
+
+  /// Newest first.
+  pub fn list(
+    &self,
+    server: Option<&str>,
+    limit: usize,
+  ) -> Vec<TransportTraceEntry> {
+    self
+      .0
+      .lock()
+      .unwrap()
+      .iter()
+      .rev()
+      .filter(|entry| {
+        server.is_none_or(|server| entry.server == server)
+      })
+      .take(limit)
+      .cloned()
+      .collect()
+  }
+}
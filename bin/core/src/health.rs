@@ -0,0 +1,26 @@
+use axum::{Json, Router, routing::get};
+use komodo_client::entities::ReadOnlyMode;
+use serde::Serialize;
+
+use crate::state::read_only_mode;
+
+pub fn router() -> Router {
+  Router::new().route("/", get(|| async { Json(health()) }))
+}
+
+#[derive(Serialize)]
+struct Health {
+  /// `Ok`, or `ReadOnly` when Core is in read-only mode.
+  status: &'static str,
+  version: &'static str,
+  read_only: ReadOnlyMode,
+}
+
+fn health() -> Health {
+  let read_only = read_only_mode().load().as_ref().clone();
+  Health {
+    status: if read_only.enabled { "ReadOnly" } else { "Ok" },
+    version: env!("CARGO_PKG_VERSION"),
+    read_only,
+  }
+}
@@ -53,7 +53,6 @@ pub async fn get_builder_periphery(
           &ObjectId::new().to_hex(),
           &config,
         ),
-        config.insecure_tls,
       )
       .await?;
       periphery
@@ -108,15 +107,13 @@ async fn get_aws_builder(
   // TODO: Handle ad-hoc (non server) periphery connections. These don't have ids.
   let periphery_address =
     format!("{protocol}://{ip}:{}", config.port);
-  let periphery = PeripheryClient::new(
-    PeripheryConnectionArgs::from_aws_builder(
+  let periphery =
+    PeripheryClient::new(PeripheryConnectionArgs::from_aws_builder(
       &ObjectId::new().to_hex(),
       &periphery_address,
       &config,
-    ),
-    config.insecure_tls,
-  )
-  .await?;
+    ))
+    .await?;
 
   let start_connect_ts = komodo_timestamp();
   let mut res = Ok(GetVersionResponse {
@@ -1,6 +1,10 @@
 use std::sync::OnceLock;
 
-use komodo_client::entities::update::{Update, UpdateListItem};
+use komodo_client::entities::{
+  event::CoreEvent,
+  notification::Notification,
+  update::{Update, UpdateListItem},
+};
 use tokio::sync::{Mutex, broadcast};
 
 /// A channel sending (build_id, update_id)
@@ -27,6 +31,23 @@ pub fn update_channel() -> &'static BroadcastChannel<UpdateListItem> {
   UPDATE_CHANNEL.get_or_init(|| BroadcastChannel::new(100))
 }
 
+/// A channel sending newly created notifications,
+/// forwarded to the recipient over the update websocket.
+pub fn notification_channel()
+-> &'static BroadcastChannel<Notification> {
+  static NOTIFICATION_CHANNEL: OnceLock<
+    BroadcastChannel<Notification>,
+  > = OnceLock::new();
+  NOTIFICATION_CHANNEL.get_or_init(|| BroadcastChannel::new(100))
+}
+
+/// The Core event bus. Use [emit_event][super::event::emit_event] to send.
+pub fn event_channel() -> &'static BroadcastChannel<CoreEvent> {
+  static EVENT_CHANNEL: OnceLock<BroadcastChannel<CoreEvent>> =
+    OnceLock::new();
+  EVENT_CHANNEL.get_or_init(|| BroadcastChannel::new(500))
+}
+
 pub struct BroadcastChannel<T> {
   pub sender: Mutex<broadcast::Sender<T>>,
   pub receiver: broadcast::Receiver<T>,
@@ -0,0 +1,53 @@
+use komodo_client::entities::{
+  ResourceTarget,
+  event::{CoreEvent, CoreEventData},
+  komodo_timestamp,
+};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::connection::spawn_update_attempted_public_key;
+
+use super::channel::event_channel;
+
+/// Emits the event on the Core event bus.
+pub async fn emit_event(
+  target: impl Into<ResourceTarget>,
+  data: CoreEventData,
+) {
+  let event = CoreEvent {
+    ts: komodo_timestamp(),
+    target: target.into(),
+    data,
+  };
+  // Only errors if there are no subscribers.
+  let _ = event_channel().sender.lock().await.send(event);
+}
+
+/// Spawns the Core subsystems which react to events on the event bus.
+pub fn spawn_event_handlers() {
+  let mut receiver = event_channel().receiver.resubscribe();
+  tokio::spawn(async move {
+    loop {
+      let event = match receiver.recv().await {
+        Ok(event) => event,
+        Err(RecvError::Lagged(skipped)) => {
+          warn!("Event handler lagged, skipped {skipped} events");
+          continue;
+        }
+        Err(RecvError::Closed) => break,
+      };
+      handle_event(event);
+    }
+  });
+}
+
+fn handle_event(event: CoreEvent) {
+  if let (
+    ResourceTarget::Server(id),
+    CoreEventData::ServerConnected {},
+  ) = (event.target, event.data)
+  {
+    // Clear attempted public key after successful login
+    spawn_update_attempted_public_key(id, None);
+  }
+}
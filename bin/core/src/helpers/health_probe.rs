@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use formatting::format_serror;
+use komodo_client::entities::{
+  deployment::{Deployment, DeploymentHealth},
+  komodo_timestamp,
+  update::Log,
+};
+use periphery_client::api::container::RunHealthProbe;
+
+use crate::periphery::PeripheryClient;
+
+const RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Runs the Deployment health probe on Periphery.
+/// Failure to reach Periphery is reported as unhealthy.
+pub async fn run_health_probe(
+  periphery: &PeripheryClient,
+  deployment: &Deployment,
+) -> DeploymentHealth {
+  periphery
+    .request(RunHealthProbe {
+      name: deployment.name.clone(),
+      probe: deployment.config.health_probe.clone(),
+      timeout: deployment.config.health_probe_timeout,
+    })
+    .await
+    .unwrap_or_else(|e| DeploymentHealth {
+      healthy: false,
+      message: format_serror(&e.into()),
+      ts: komodo_timestamp(),
+    })
+}
+
+/// Retries the health probe after deploy until it succeeds,
+/// or `health_probe_start_period` elapses.
+pub async fn wait_for_healthy(
+  periphery: &PeripheryClient,
+  deployment: &Deployment,
+) -> Log {
+  let start = komodo_timestamp();
+  let deadline = start
+    + deployment.config.health_probe_start_period as i64 * 1_000;
+  let mut attempts = 0;
+  let health = loop {
+    attempts += 1;
+    let health = run_health_probe(periphery, deployment).await;
+    if health.healthy || komodo_timestamp() >= deadline {
+      break health;
+    }
+    tokio::time::sleep(RETRY_INTERVAL).await;
+  };
+  let stage = "Health Probe";
+  let end = komodo_timestamp();
+  if health.healthy {
+    Log {
+      stage: stage.to_string(),
+      stdout: format!(
+        "Healthy after {attempts} attempt(s)\n{}",
+        health.message
+      ),
+      success: true,
+      start_ts: start,
+      end_ts: end,
+      ..Default::default()
+    }
+  } else {
+    Log {
+      stage: stage.to_string(),
+      stderr: format!(
+        "Unhealthy after {attempts} attempt(s)\n{}",
+        health.message
+      ),
+      success: false,
+      start_ts: start,
+      end_ts: end,
+      ..Default::default()
+    }
+  }
+}
@@ -0,0 +1,208 @@
+use anyhow::{Context, anyhow};
+use komodo_client::{
+  entities::{
+    deployment::{Deployment, RestartMode},
+    resource::{
+      ConfigInheritance, InheritedSource, InheritedValue, Resource,
+    },
+    stack::Stack,
+    update::Log,
+  },
+  parsers::parse_key_value_list,
+};
+
+use crate::resource::{self, KomodoResource};
+
+/// Applies the inherited template config to the Deployment
+/// before it is deployed. Returns a log if anything was inherited.
+pub async fn inherit_deployment_config(
+  deployment: &mut Deployment,
+) -> anyhow::Result<Option<Log>> {
+  let inheritance = deployment_inheritance(deployment).await?;
+  if inheritance.template.is_empty() {
+    return Ok(None);
+  }
+  deployment.config.environment =
+    key_value_string(&inheritance.environment);
+  deployment.config.labels = key_value_string(&inheritance.labels);
+  if let Some(restart) = &inheritance.restart {
+    deployment.config.restart = restart
+      .value
+      .parse()
+      .context("Invalid inherited restart mode")?;
+  }
+  Ok(Some(inheritance_log(&inheritance)))
+}
+
+/// Applies the inherited template config to the Stack
+/// before it is deployed. Returns a log if anything was inherited.
+pub async fn inherit_stack_config(
+  stack: &mut Stack,
+) -> anyhow::Result<Option<Log>> {
+  let inheritance = stack_inheritance(stack).await?;
+  if inheritance.template.is_empty() {
+    return Ok(None);
+  }
+  stack.config.environment =
+    key_value_string(&inheritance.environment);
+  Ok(Some(inheritance_log(&inheritance)))
+}
+
+pub async fn deployment_inheritance(
+  deployment: &Deployment,
+) -> anyhow::Result<ConfigInheritance> {
+  let Some(template) = get_template::<Deployment>(
+    &deployment.id,
+    &deployment.config.inherit,
+  )
+  .await?
+  else {
+    return Ok(ConfigInheritance::default());
+  };
+  let restart = if deployment.config.restart == RestartMode::default()
+  {
+    InheritedValue {
+      key: String::from("restart"),
+      value: template.config.restart.to_string(),
+      template_value: Some(template.config.restart.to_string()),
+      source: InheritedSource::Template,
+    }
+  } else {
+    InheritedValue {
+      key: String::from("restart"),
+      value: deployment.config.restart.to_string(),
+      template_value: Some(template.config.restart.to_string()),
+      source: InheritedSource::Override,
+    }
+  };
+  Ok(ConfigInheritance {
+    environment: merge_key_values(
+      &template.config.environment,
+      &deployment.config.environment,
+    )
+    .context("Failed to merge environment")?,
+    labels: merge_key_values(
+      &template.config.labels,
+      &deployment.config.labels,
+    )
+    .context("Failed to merge labels")?,
+    restart: Some(restart),
+    template: template.id,
+  })
+}
+
+pub async fn stack_inheritance(
+  stack: &Stack,
+) -> anyhow::Result<ConfigInheritance> {
+  let Some(template) =
+    get_template::<Stack>(&stack.id, &stack.config.inherit).await?
+  else {
+    return Ok(ConfigInheritance::default());
+  };
+  Ok(ConfigInheritance {
+    environment: merge_key_values(
+      &template.config.environment,
+      &stack.config.environment,
+    )
+    .context("Failed to merge environment")?,
+    labels: Vec::new(),
+    restart: None,
+    template: template.id,
+  })
+}
+
+/// Only a single level is inherited,
+/// the template's own `inherit` is ignored.
+async fn get_template<T: KomodoResource>(
+  id: &str,
+  inherit: &str,
+) -> anyhow::Result<Option<Resource<T::Config, T::Info>>> {
+  if inherit.is_empty() {
+    return Ok(None);
+  }
+  let template =
+    resource::get::<T>(inherit).await.with_context(|| {
+      format!(
+        "Failed to get inherited {} {inherit}",
+        T::resource_type()
+      )
+    })?;
+  if template.id == id {
+    return Ok(None);
+  }
+  if !template.template {
+    return Err(anyhow!(
+      "Inherited {} {} is not a template",
+      T::resource_type(),
+      template.name
+    ));
+  }
+  Ok(Some(template))
+}
+
+/// Template values come first, with resource values
+/// overriding them by key or appended after.
+fn merge_key_values(
+  template: &str,
+  resource: &str,
+) -> anyhow::Result<Vec<InheritedValue>> {
+  let mut values = parse_key_value_list(template)?
+    .into_iter()
+    .map(|(key, value)| InheritedValue {
+      key,
+      template_value: Some(value.clone()),
+      value,
+      source: InheritedSource::Template,
+    })
+    .collect::<Vec<_>>();
+  for (key, value) in parse_key_value_list(resource)? {
+    if let Some(existing) = values.iter_mut().find(|v| v.key == key) {
+      existing.value = value;
+      existing.source = InheritedSource::Override;
+    } else {
+      values.push(InheritedValue {
+        key,
+        value,
+        template_value: None,
+        source: InheritedSource::Resource,
+      });
+    }
+  }
+  Ok(values)
+}
+
+fn key_value_string(values: &[InheritedValue]) -> String {
+  values
+    .iter()
+    .map(|InheritedValue { key, value, .. }| format!("{key}={value}"))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn inheritance_log(inheritance: &ConfigInheritance) -> Log {
+  let mut inherited = inheritance
+    .environment
+    .iter()
+    .chain(&inheritance.labels)
+    .chain(&inheritance.restart)
+    .filter(|value| value.source != InheritedSource::Resource)
+    .map(|value| {
+      let source = match value.source {
+        InheritedSource::Template => "inherited",
+        _ => "overridden",
+      };
+      format!("{}: {source}", value.key)
+    })
+    .collect::<Vec<_>>();
+  if inherited.is_empty() {
+    inherited.push(String::from("Template defines no values"));
+  }
+  Log::simple(
+    "Inherit Config",
+    format!(
+      "Inherited from template {}\n\n{}",
+      inheritance.template,
+      inherited.join("\n")
+    ),
+  )
+}
@@ -0,0 +1,219 @@
+use anyhow::Context;
+use async_timing_util::{
+  ONE_DAY_MS, Timelength, wait_until_timelength,
+};
+use database::mungos::{
+  find::find_collect,
+  mongodb::{
+    bson::{Document, doc},
+    options::{FindOneOptions, FindOptions},
+  },
+};
+use komodo_client::{
+  api::execute::{RotateAllServerKeys, RotateCoreKeys},
+  entities::{
+    I64, ResourceTarget,
+    alert::{Alert, AlertData, SeverityLevel},
+    komodo_timestamp,
+    server::ServerState,
+    user::system_user,
+  },
+};
+use resolver_api::Resolve;
+
+use crate::{
+  alert::send_alerts,
+  api::execute::{
+    ExecuteArgs, ExecuteRequest, global_rotate_lock,
+    rotate_keys_for_servers,
+  },
+  config::{core_config, core_keys},
+  helpers::{
+    procedure::handle_resolve_result,
+    update::{init_execution_update, update_update},
+  },
+  state::{db_client, server_status_cache},
+};
+
+/// Rotates keys once their last rotation is older than
+/// `key_rotation_interval_days`, checking daily.
+pub fn spawn_key_rotation_loop() {
+  let interval_days = core_config().key_rotation_interval_days;
+  if interval_days == 0 {
+    return;
+  }
+  let interval_ms = (interval_days as u128 * ONE_DAY_MS) as I64;
+  tokio::spawn(async move {
+    loop {
+      wait_until_timelength(Timelength::OneDay, 0).await;
+      if let Err(e) = rotate_due_server_keys(interval_ms).await {
+        error!("Failed scheduled Server key rotation | {e:#}");
+      }
+      if let Err(e) = rotate_core_keys_if_due(interval_ms).await {
+        error!("Failed scheduled Core key rotation | {e:#}");
+      }
+    }
+  });
+}
+
+/// Servers are rotated individually based on their own last
+/// rotation, so the rotations roll across the fleet.
+async fn rotate_due_server_keys(
+  interval_ms: I64,
+) -> anyhow::Result<()> {
+  let rotated_before = komodo_timestamp() - interval_ms;
+  let servers = find_collect(
+    &db_client().servers,
+    doc! {
+      "config.enabled": true,
+      "config.auto_rotate_keys": true,
+      // Also matches Servers which were never rotated.
+      "info.keys_rotated_at": { "$not": { "$gt": rotated_before } },
+    },
+    FindOptions::builder()
+      .sort(doc! { "info.keys_rotated_at": 1 })
+      .build(),
+  )
+  .await
+  .context("Failed to query servers from database")?;
+
+  if servers.is_empty() {
+    return Ok(());
+  }
+
+  let Ok(_lock) = global_rotate_lock().try_lock() else {
+    warn!(
+      "Key rotation already in progress, skipping scheduled rotation"
+    );
+    return Ok(());
+  };
+
+  let request =
+    ExecuteRequest::RotateAllServerKeys(RotateAllServerKeys {});
+  let mut update = init_execution_update(&request, system_user())
+    .await
+    .context("Failed to init scheduled key rotation update")?;
+  update_update(update.clone()).await?;
+
+  let unreachable =
+    rotate_keys_for_servers(servers, &mut update).await;
+
+  update.finalize();
+  update_update(update).await?;
+
+  if !unreachable.is_empty() {
+    alert_key_rotation_skipped(
+      "Scheduled key rotation skipped unreachable Servers",
+      unreachable.join("\n"),
+    )
+    .await;
+  }
+
+  Ok(())
+}
+
+/// The Core keys are due when the latest successful
+/// RotateCoreKeys is older than the interval.
+async fn rotate_core_keys_if_due(
+  interval_ms: I64,
+) -> anyhow::Result<()> {
+  if !core_keys().rotatable() {
+    return Ok(());
+  }
+
+  let last_rotation = db_client()
+    .updates
+    .find_one(doc! { "operation": "RotateCoreKeys", "success": true })
+    .with_options(
+      FindOneOptions::builder()
+        .sort(doc! { "start_ts": -1 })
+        .build(),
+    )
+    .await
+    .context(
+      "Failed to query database for latest Core key rotation",
+    )?;
+
+  if last_rotation.is_some_and(|update| {
+    update.start_ts > komodo_timestamp() - interval_ms
+  }) {
+    return Ok(());
+  }
+
+  // RotateCoreKeys refuses to run with NotOk Servers,
+  // as they would be left with the old Core public key.
+  let server_status_cache = server_status_cache();
+  let mut unreachable = Vec::new();
+  for server in
+    find_collect(&db_client().servers, Document::new(), None)
+      .await
+      .context("Failed to query servers from database")?
+  {
+    let state = server_status_cache
+      .get(&server.id)
+      .await
+      .map(|status| status.state)
+      .unwrap_or(ServerState::NotOk);
+    if matches!(state, ServerState::NotOk) {
+      unreachable.push(server.name);
+    }
+  }
+  if !unreachable.is_empty() {
+    alert_key_rotation_skipped(
+      "Scheduled Core key rotation skipped due to unreachable Servers",
+      unreachable.join("\n"),
+    )
+    .await;
+    return Ok(());
+  }
+
+  let request =
+    ExecuteRequest::RotateCoreKeys(RotateCoreKeys { force: false });
+  let update =
+    init_execution_update(&request, system_user())
+      .await
+      .context("Failed to init scheduled Core key rotation update")?;
+  let ExecuteRequest::RotateCoreKeys(request) = request else {
+    unreachable!()
+  };
+  let update_id = update.id.clone();
+  let update = handle_resolve_result(
+    request
+      .resolve(&ExecuteArgs {
+        user: system_user().to_owned(),
+        update,
+      })
+      .await
+      .map_err(|e| e.error)
+      .context("Failed at RotateCoreKeys"),
+    &update_id,
+  )
+  .await?;
+
+  if !update.success {
+    alert_key_rotation_skipped(
+      "Scheduled Core key rotation failed",
+      String::from("See the RotateCoreKeys update for details."),
+    )
+    .await;
+  }
+
+  Ok(())
+}
+
+async fn alert_key_rotation_skipped(message: &str, details: String) {
+  let ts = komodo_timestamp();
+  let alert = Alert {
+    id: Default::default(),
+    ts,
+    resolved: true,
+    level: SeverityLevel::Warning,
+    target: ResourceTarget::system(),
+    data: AlertData::Custom {
+      message: message.to_string(),
+      details,
+    },
+    resolved_ts: Some(ts),
+  };
+  send_alerts(&[alert]).await;
+}
@@ -1,12 +1,26 @@
 use std::str::FromStr;
 
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use chrono::{Datelike, Local};
 use komodo_client::entities::{
   DayOfWeek, MaintenanceScheduleType, MaintenanceWindow,
 };
 
-use crate::config::core_config;
+use crate::{config::core_config, state::read_only_mode};
+
+/// Errors if Core is in read-only mode.
+/// Used to reject writes / executions during maintenance.
+pub fn check_read_only() -> anyhow::Result<()> {
+  let mode = read_only_mode().load();
+  if !mode.enabled {
+    return Ok(());
+  }
+  if mode.reason.is_empty() {
+    Err(anyhow!("Core is in read-only mode"))
+  } else {
+    Err(anyhow!("Core is in read-only mode | {}", mode.reason))
+  }
+}
 
 /// Check if a timestamp is currently in a maintenance window, given a list of windows.
 pub fn is_in_maintenance(
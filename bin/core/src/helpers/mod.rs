@@ -25,11 +25,21 @@ pub mod action_state;
 pub mod all_resources;
 pub mod builder;
 pub mod channel;
+pub mod event;
+pub mod health_probe;
+pub mod inherit;
+pub mod key_rotation;
 pub mod maintenance;
 pub mod matcher;
+pub mod notification;
 pub mod procedure;
+pub mod projection;
 pub mod prune;
 pub mod query;
+pub mod quota;
+pub mod read_cache;
+pub mod tag_report;
+pub mod terminal_session;
 pub mod update;
 
 // pub mod resource;
@@ -183,11 +193,8 @@ pub async fn periphery_client(
   if !server.config.enabled {
     return Err(anyhow!("server not enabled"));
   }
-  PeripheryClient::new(
-    PeripheryConnectionArgs::from_server(server),
-    server.config.insecure_tls,
-  )
-  .await
+  PeripheryClient::new(PeripheryConnectionArgs::from_server(server))
+    .await
 }
 
 #[instrument(
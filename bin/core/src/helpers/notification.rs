@@ -0,0 +1,31 @@
+use anyhow::Context;
+use komodo_client::entities::notification::Notification;
+
+use crate::{
+  helpers::channel::notification_channel, state::db_client,
+};
+
+/// Stores the notifications in the recipients' inboxes,
+/// and forwards them to any connected update websockets.
+pub async fn send_notifications(
+  mut notifications: Vec<Notification>,
+) -> anyhow::Result<()> {
+  if notifications.is_empty() {
+    return Ok(());
+  }
+  let res = db_client()
+    .notifications
+    .insert_many(&notifications)
+    .await
+    .context("failed to add notifications to db")?;
+  let sender = notification_channel().sender.lock().await;
+  for (i, notification) in notifications.iter_mut().enumerate() {
+    if let Some(id) =
+      res.inserted_ids.get(&i).and_then(|id| id.as_object_id())
+    {
+      notification.id = id.to_string();
+    }
+    let _ = sender.send(notification.clone());
+  }
+  Ok(())
+}
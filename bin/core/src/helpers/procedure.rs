@@ -459,6 +459,23 @@ async fn execute_execution(
       )
       .await?
     }
+    Execution::RollbackDeployment(req) => {
+      let req = ExecuteRequest::RollbackDeployment(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::RollbackDeployment(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at RollbackDeployment"),
+        &update_id,
+      )
+      .await?
+    }
     Execution::BatchDestroyDeployment(_) => {
       // All batch executions must be expanded in `execute_stage`
       return Err(anyhow!(
@@ -653,6 +670,23 @@ async fn execute_execution(
       )
       .await?
     }
+    Execution::CommitContainer(req) => {
+      let req = ExecuteRequest::CommitContainer(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::CommitContainer(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at CommitContainer"),
+        &update_id,
+      )
+      .await?
+    }
     Execution::StartAllContainers(req) => {
       let req = ExecuteRequest::StartAllContainers(req);
       let update = init_execution_update(&req, &user).await?;
@@ -823,6 +857,23 @@ async fn execute_execution(
       )
       .await?
     }
+    Execution::TransferImage(req) => {
+      let req = ExecuteRequest::TransferImage(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::TransferImage(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at TransferImage"),
+        &update_id,
+      )
+      .await?
+    }
     Execution::DeleteVolume(req) => {
       let req = ExecuteRequest::DeleteVolume(req);
       let update = init_execution_update(&req, &user).await?;
@@ -891,6 +942,23 @@ async fn execute_execution(
       )
       .await?
     }
+    Execution::PruneBuildCache(req) => {
+      let req = ExecuteRequest::PruneBuildCache(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::PruneBuildCache(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at PruneBuildCache"),
+        &update_id,
+      )
+      .await?
+    }
     Execution::PruneSystem(req) => {
       let req = ExecuteRequest::PruneSystem(req);
       let update = init_execution_update(&req, &user).await?;
@@ -908,6 +976,142 @@ async fn execute_execution(
       )
       .await?
     }
+    Execution::RunServerExtension(req) => {
+      let req = ExecuteRequest::RunServerExtension(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::RunServerExtension(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at RunServerExtension"),
+        &update_id,
+      )
+      .await?
+    }
+    Execution::KillSystemProcess(req) => {
+      let req = ExecuteRequest::KillSystemProcess(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::KillSystemProcess(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at KillSystemProcess"),
+        &update_id,
+      )
+      .await?
+    }
+    Execution::SetSystemProcessPriority(req) => {
+      let req = ExecuteRequest::SetSystemProcessPriority(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::SetSystemProcessPriority(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at SetSystemProcessPriority"),
+        &update_id,
+      )
+      .await?
+    }
+    Execution::StartSystemdUnit(req) => {
+      let req = ExecuteRequest::StartSystemdUnit(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::StartSystemdUnit(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at StartSystemdUnit"),
+        &update_id,
+      )
+      .await?
+    }
+    Execution::StopSystemdUnit(req) => {
+      let req = ExecuteRequest::StopSystemdUnit(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::StopSystemdUnit(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at StopSystemdUnit"),
+        &update_id,
+      )
+      .await?
+    }
+    Execution::RestartSystemdUnit(req) => {
+      let req = ExecuteRequest::RestartSystemdUnit(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::RestartSystemdUnit(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at RestartSystemdUnit"),
+        &update_id,
+      )
+      .await?
+    }
+    Execution::RebootServer(req) => {
+      let req = ExecuteRequest::RebootServer(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::RebootServer(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at RebootServer"),
+        &update_id,
+      )
+      .await?
+    }
+    Execution::ShutdownServer(req) => {
+      let req = ExecuteRequest::ShutdownServer(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::ShutdownServer(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at ShutdownServer"),
+        &update_id,
+      )
+      .await?
+    }
     Execution::RunSync(req) => {
       let req = ExecuteRequest::RunSync(req);
       let update = init_execution_update(&req, &user).await?;
@@ -1119,6 +1323,57 @@ async fn execute_execution(
       )
       .await?
     }
+    Execution::ScaleStack(req) => {
+      let req = ExecuteRequest::ScaleStack(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::ScaleStack(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at ScaleStack"),
+        &update_id,
+      )
+      .await?
+    }
+    Execution::PromoteStack(req) => {
+      let req = ExecuteRequest::PromoteStack(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::PromoteStack(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at PromoteStack"),
+        &update_id,
+      )
+      .await?
+    }
+    Execution::MigrateStack(req) => {
+      let req = ExecuteRequest::MigrateStack(req);
+      let update = init_execution_update(&req, &user).await?;
+      let ExecuteRequest::MigrateStack(req) = req else {
+        unreachable!()
+      };
+      let update_id = update.id.clone();
+      handle_resolve_result(
+        req
+          .resolve(&ExecuteArgs { user, update })
+          .await
+          .map_err(|e| e.error)
+          .context("Failed at MigrateStack"),
+        &update_id,
+      )
+      .await?
+    }
     Execution::BatchDestroyStack(_) => {
       // All batch executions must be expanded in `execute_stage`
       return Err(anyhow!(
@@ -1266,7 +1521,7 @@ async fn execute_execution(
 
 /// If the call to .resolve returns Err, the update may not be closed.
 /// This will ensure it is closed with error log attached.
-async fn handle_resolve_result(
+pub async fn handle_resolve_result(
   res: anyhow::Result<Update>,
   update_id: &str,
 ) -> anyhow::Result<Update> {
@@ -0,0 +1,82 @@
+use anyhow::{Context, anyhow};
+use response::Response;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Serializes only the requested `fields` of the response.
+/// Fields are dot separated paths, eg. `config.image`.
+/// Arrays are projected item by item. Errors if a field
+/// doesn't match any field of the response, rather than
+/// silently leaving it out.
+pub fn projected_response<T: Serialize>(
+  res: &T,
+  fields: &[String],
+) -> anyhow::Result<Response> {
+  let value = serde_json::to_value(res)
+    .context("Failed to serialize response")?;
+  let paths = fields
+    .iter()
+    .map(|field| field.split('.').collect::<Vec<_>>())
+    .collect::<Vec<_>>();
+  let paths = paths.iter().map(Vec::as_slice).collect::<Vec<_>>();
+  for (field, path) in fields.iter().zip(&paths) {
+    if !path_matches(&value, path) {
+      return Err(anyhow!(
+        "Field '{field}' does not match any field of the response"
+      ));
+    }
+  }
+  Ok(project(value, &paths).into())
+}
+
+/// Arrays match if any item does, as items may differ in
+/// optional fields. Empty arrays have nothing to check.
+fn path_matches(value: &Value, path: &[&str]) -> bool {
+  let Some((first, rest)) = path.split_first() else {
+    return true;
+  };
+  match value {
+    Value::Array(items) => {
+      items.is_empty()
+        || items.iter().any(|item| path_matches(item, path))
+    }
+    Value::Object(object) => object
+      .get(*first)
+      .is_some_and(|value| path_matches(value, rest)),
+    // Paths past a scalar keep the scalar.
+    _ => true,
+  }
+}
+
+fn project(value: Value, paths: &[&[&str]]) -> Value {
+  match value {
+    Value::Array(items) => Value::Array(
+      items.into_iter().map(|item| project(item, paths)).collect(),
+    ),
+    Value::Object(object) => Value::Object(
+      object
+        .into_iter()
+        .filter_map(|(key, value)| {
+          let mut rest_paths = Vec::new();
+          for path in paths {
+            let Some((first, rest)) = path.split_first() else {
+              continue;
+            };
+            if *first != key {
+              continue;
+            }
+            // The whole field is requested.
+            if rest.is_empty() {
+              return Some((key, value));
+            }
+            rest_paths.push(rest);
+          }
+          (!rest_paths.is_empty())
+            .then(|| (key, project(value, &rest_paths)))
+        })
+        .collect(),
+    ),
+    // Paths past a scalar keep the scalar.
+    value => value,
+  }
+}
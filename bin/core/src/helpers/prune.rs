@@ -14,8 +14,19 @@ pub fn spawn_prune_loop() {
   tokio::spawn(async move {
     loop {
       wait_until_timelength(Timelength::OneDay, 5000).await;
-      let (images_res, stats_res, alerts_res) =
-        tokio::join!(prune_images(), prune_stats(), prune_alerts());
+      let (
+        images_res,
+        stats_res,
+        alerts_res,
+        notifications_res,
+        auth_events_res,
+      ) = tokio::join!(
+        prune_images(),
+        prune_stats(),
+        prune_alerts(),
+        prune_notifications(),
+        prune_auth_events()
+      );
       if let Err(e) = images_res {
         error!("error in pruning images | {e:#}");
       }
@@ -25,6 +36,12 @@ pub fn spawn_prune_loop() {
       if let Err(e) = alerts_res {
         error!("error in pruning alerts | {e:#}");
       }
+      if let Err(e) = notifications_res {
+        error!("error in pruning notifications | {e:#}");
+      }
+      if let Err(e) = auth_events_res {
+        error!("error in pruning auth events | {e:#}");
+      }
     }
   });
 }
@@ -101,3 +118,44 @@ async fn prune_alerts() -> anyhow::Result<()> {
   }
   Ok(())
 }
+
+/// Read notifications are kept as long as alerts.
+async fn prune_notifications() -> anyhow::Result<()> {
+  if core_config().keep_alerts_for_days == 0 {
+    return Ok(());
+  }
+  let delete_before_ts = (unix_timestamp_ms()
+    - core_config().keep_alerts_for_days as u128 * ONE_DAY_MS)
+    as i64;
+  let res = db_client()
+    .notifications
+    .delete_many(doc! {
+      "read": true,
+      "ts": { "$lt": delete_before_ts }
+    })
+    .await?;
+  if res.deleted_count > 0 {
+    info!("deleted {} notifications from db", res.deleted_count);
+  }
+  Ok(())
+}
+
+/// Auth events are kept as long as alerts.
+async fn prune_auth_events() -> anyhow::Result<()> {
+  if core_config().keep_alerts_for_days == 0 {
+    return Ok(());
+  }
+  let delete_before_ts = (unix_timestamp_ms()
+    - core_config().keep_alerts_for_days as u128 * ONE_DAY_MS)
+    as i64;
+  let res = db_client()
+    .auth_events
+    .delete_many(doc! {
+      "ts": { "$lt": delete_before_ts }
+    })
+    .await?;
+  if res.deleted_count > 0 {
+    info!("deleted {} auth events from db", res.deleted_count);
+  }
+  Ok(())
+}
@@ -2,6 +2,7 @@ use std::{collections::HashMap, str::FromStr};
 
 use anyhow::{Context, anyhow};
 use database::mungos::{
+  by_id::find_one_by_id,
   find::find_collect,
   mongodb::{
     bson::{Document, doc, oid::ObjectId},
@@ -16,10 +17,12 @@ use komodo_client::{
     alerter::Alerter,
     build::Build,
     builder::Builder,
+    dashboard::Dashboard,
     deployment::{Deployment, DeploymentState},
     docker::container::{
       ContainerListItem, ContainerStateStatusEnum,
     },
+    organization::Organization,
     permission::{PermissionLevel, PermissionLevelAndSpecifics},
     procedure::{Procedure, ProcedureState},
     repo::Repo,
@@ -27,6 +30,7 @@ use komodo_client::{
     stack::{Stack, StackServiceNames, StackState},
     sync::ResourceSync,
     tag::Tag,
+    template::Template,
     update::Update,
     user::{User, admin_service_user},
     user_group::UserGroup,
@@ -345,6 +349,47 @@ pub async fn get_variable(name: &str) -> anyhow::Result<Variable> {
     })
 }
 
+pub async fn get_organization(
+  id_or_name: &str,
+) -> anyhow::Result<Organization> {
+  db_client()
+    .organizations
+    .find_one(id_or_name_filter(id_or_name))
+    .await
+    .context("failed to query mongo for Organization")?
+    .with_context(|| {
+      format!("no Organization found matching {id_or_name}")
+    })
+}
+
+pub async fn get_template(name: &str) -> anyhow::Result<Template> {
+  db_client()
+    .templates
+    .find_one(doc! { "name": &name })
+    .await
+    .context("failed at call to db")?
+    .with_context(|| {
+      format!("no template found with given name: {name}")
+    })
+}
+
+/// Gets the dashboard, checking the user
+/// owns it, it is shared, or the user is admin.
+pub async fn get_dashboard_check_access(
+  id: &str,
+  user: &User,
+) -> anyhow::Result<Dashboard> {
+  let dashboard = find_one_by_id(&db_client().dashboards, id)
+    .await
+    .context("failed at call to db")?
+    .with_context(|| format!("no dashboard found with id {id}"))?;
+  if user.admin || dashboard.shared || dashboard.owner == user.id {
+    Ok(dashboard)
+  } else {
+    Err(anyhow!("User does not have access to this dashboard"))
+  }
+}
+
 pub async fn get_latest_update(
   resource_type: ResourceTargetVariant,
   id: &str,
@@ -0,0 +1,126 @@
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use database::mungos::mongodb::bson::{Document, doc, oid::ObjectId};
+use komodo_client::entities::{
+  Operation, ResourceTargetVariant, komodo_timestamp,
+  user::User,
+  user_group::{UserGroup, UserGroupQuotas},
+};
+
+use crate::{
+  helpers::query::get_user_user_groups, resource::KomodoResource,
+  state::db_client,
+};
+
+const ONE_DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Check that creating another resource of type T
+/// keeps the user within the quotas of all their user groups.
+pub async fn check_create_quota<T: KomodoResource>(
+  user: &User,
+) -> anyhow::Result<()> {
+  if user.admin {
+    return Ok(());
+  }
+  let max = |quotas: &UserGroupQuotas| match T::resource_type() {
+    ResourceTargetVariant::Server => quotas.max_servers,
+    ResourceTargetVariant::Deployment => quotas.max_deployments,
+    _ => 0,
+  };
+  for group in get_user_user_groups(&user.id).await? {
+    let max = max(&group.quotas);
+    if max <= 0 {
+      continue;
+    }
+    let existing = group_created_count::<T>(&group).await?;
+    if existing >= max as u64 {
+      return Err(anyhow!(
+        "Quota exceeded | User Group '{}' is limited to {max} {}s, and {existing} already exist",
+        group.name,
+        T::resource_type(),
+      ));
+    }
+  }
+  Ok(())
+}
+
+/// Check that running another build keeps the user
+/// within the daily build quotas of all their user groups.
+/// The Update for the build being checked is excluded from the count.
+pub async fn check_build_quota(
+  user: &User,
+  update_id: &str,
+) -> anyhow::Result<()> {
+  if user.admin {
+    return Ok(());
+  }
+  for group in get_user_user_groups(&user.id).await? {
+    let max = group.quotas.max_builds_per_day;
+    if max <= 0 {
+      continue;
+    }
+    let mut filter =
+      group_operation_filter(&group, Operation::RunBuild);
+    filter.insert(
+      "start_ts",
+      doc! { "$gte": komodo_timestamp() - ONE_DAY_MS },
+    );
+    if let Ok(id) = ObjectId::from_str(update_id) {
+      filter.insert("_id", doc! { "$ne": id });
+    }
+    let builds = db_client()
+      .updates
+      .count_documents(filter)
+      .await
+      .context("failed to count builds for user group quota")?;
+    if builds >= max as u64 {
+      return Err(anyhow!(
+        "Quota exceeded | User Group '{}' is limited to {max} builds per day, and {builds} have run in the last 24 hours",
+        group.name,
+      ));
+    }
+  }
+  Ok(())
+}
+
+/// Counts the resources of type T which were created
+/// by group members and still exist.
+async fn group_created_count<T: KomodoResource>(
+  group: &UserGroup,
+) -> anyhow::Result<u64> {
+  let ids = db_client()
+    .updates
+    .distinct(
+      "target.id",
+      group_operation_filter(group, T::create_operation()),
+    )
+    .await
+    .context("failed to query db for created resources")?
+    .into_iter()
+    .filter_map(|id| {
+      id.as_str().and_then(|id| ObjectId::from_str(id).ok())
+    })
+    .collect::<Vec<_>>();
+  if ids.is_empty() {
+    return Ok(0);
+  }
+  T::coll()
+    .count_documents(doc! { "_id": { "$in": ids } })
+    .await
+    .with_context(|| {
+      format!("failed to count {} on db", T::resource_type())
+    })
+}
+
+/// Filter for Updates of the operation run by any group member.
+fn group_operation_filter(
+  group: &UserGroup,
+  operation: Operation,
+) -> Document {
+  let mut filter = doc! { "operation": operation.as_ref() };
+  if !group.everyone {
+    filter.insert("operator", doc! { "$in": &group.users });
+  }
+  filter
+}
@@ -0,0 +1,64 @@
+use std::sync::OnceLock;
+
+use cache::TimeoutCache;
+use komodo_client::entities::{
+  docker::image::{Image, ImageHistoryResponseItem},
+  komodo_timestamp,
+  stats::SystemProcess,
+};
+
+/// (Server id, item). The item is empty
+/// for reads over the whole Server.
+type ReadCacheKey = (String, String);
+pub type ReadCache<Res> = TimeoutCache<ReadCacheKey, Res>;
+
+pub fn inspect_image_cache() -> &'static ReadCache<Image> {
+  static INSPECT_IMAGE_CACHE: OnceLock<ReadCache<Image>> =
+    OnceLock::new();
+  INSPECT_IMAGE_CACHE.get_or_init(Default::default)
+}
+
+pub fn image_history_cache()
+-> &'static ReadCache<Vec<ImageHistoryResponseItem>> {
+  static IMAGE_HISTORY_CACHE: OnceLock<
+    ReadCache<Vec<ImageHistoryResponseItem>>,
+  > = OnceLock::new();
+  IMAGE_HISTORY_CACHE.get_or_init(Default::default)
+}
+
+pub fn system_processes_cache()
+-> &'static ReadCache<Vec<SystemProcess>> {
+  static SYSTEM_PROCESSES_CACHE: OnceLock<
+    ReadCache<Vec<SystemProcess>>,
+  > = OnceLock::new();
+  SYSTEM_PROCESSES_CACHE.get_or_init(Default::default)
+}
+
+/// Returns the cached result if it is younger than `ttl_ms`,
+/// otherwise reads through and caches the result.
+/// Concurrent reads of the same key wait on the one in flight.
+/// Errors are not cached.
+pub async fn cached_read<Res: Clone + Default>(
+  cache: &ReadCache<Res>,
+  key: ReadCacheKey,
+  ttl_ms: i64,
+  skip_cache: bool,
+  read: impl Future<Output = anyhow::Result<Res>>,
+) -> anyhow::Result<Res> {
+  if ttl_ms <= 0 {
+    return read.await;
+  }
+  let lock = cache.get_lock(key).await;
+  let mut entry = lock.lock().await;
+  if !skip_cache
+    && entry.last_ts > 0
+    && komodo_timestamp() - entry.last_ts < ttl_ms
+  {
+    return entry.clone_res();
+  }
+  let res = read.await;
+  if res.is_ok() {
+    entry.set(&res, komodo_timestamp());
+  }
+  res
+}
@@ -0,0 +1,276 @@
+use std::fmt::Write;
+
+use anyhow::Context;
+use async_timing_util::{
+  get_timelength_in_ms, unix_timestamp_ms, wait_until_timelength,
+};
+use database::mungos::{
+  find::find_collect,
+  mongodb::bson::{Document, doc},
+};
+use futures::{StreamExt, TryStreamExt, stream::FuturesUnordered};
+use komodo_client::entities::{
+  I64, ResourceTarget,
+  alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
+  alerter::Alerter,
+  build::Build,
+  deployment::Deployment,
+  docker::container::ContainerStateStatusEnum,
+  komodo_timestamp,
+  server::Server,
+  stack::Stack,
+  tag::{Tag, TagUsageReport},
+  update::UpdateStatus,
+  user::{User, system_user},
+};
+
+use crate::{
+  alert::send_alert_to_alerter,
+  config::core_config,
+  resource::{self, list_full_for_user_using_document},
+  state::{db_client, server_status_cache},
+};
+
+/// The window used when `since` is not given.
+const DEFAULT_REPORT_WINDOW_MS: I64 = 7 * 24 * 60 * 60 * 1000;
+
+/// Resolves the report window, defaulting to the last 7 days.
+pub fn report_window(
+  since: Option<I64>,
+  until: Option<I64>,
+) -> (I64, I64) {
+  let until = until.unwrap_or_else(komodo_timestamp);
+  let since = since.unwrap_or(until - DEFAULT_REPORT_WINDOW_MS);
+  (since, until)
+}
+
+/// Aggregates the usage of the tagged resources the user has access to.
+pub async fn get_tag_usage_report(
+  tag: &Tag,
+  since: I64,
+  until: I64,
+  user: &User,
+) -> anyhow::Result<TagUsageReport> {
+  let filter = doc! { "tags": &tag.id };
+  let (servers, deployments, stacks, builds) = tokio::try_join!(
+    list_full_for_user_using_document::<Server>(filter.clone(), user),
+    list_full_for_user_using_document::<Deployment>(
+      filter.clone(),
+      user
+    ),
+    list_full_for_user_using_document::<Stack>(filter.clone(), user),
+    list_full_for_user_using_document::<Build>(filter, user),
+  )?;
+
+  let server_ids =
+    servers.iter().map(|server| &server.id).collect::<Vec<_>>();
+  let build_ids =
+    builds.iter().map(|build| &build.id).collect::<Vec<_>>();
+
+  let mut report = TagUsageReport {
+    tag: tag.id.clone(),
+    name: tag.name.clone(),
+    since,
+    until,
+    servers: servers.len() as I64,
+    deployments: deployments.len() as I64,
+    stacks: stacks.len() as I64,
+    builds: builds.len() as I64,
+    ..Default::default()
+  };
+
+  // Server stats, averaged per server first so
+  // servers with more samples don't dominate.
+  if !server_ids.is_empty() {
+    let per_server = db_client()
+      .stats
+      .aggregate([
+        doc! { "$match": {
+          "sid": { "$in": &server_ids },
+          "ts": { "$gte": since, "$lte": until },
+        } },
+        doc! { "$group": {
+          "_id": "$sid",
+          "cpu_perc": { "$avg": "$cpu_perc" },
+          "mem_used_gb": { "$avg": "$mem_used_gb" },
+          "mem_total_gb": { "$avg": "$mem_total_gb" },
+        } },
+      ])
+      .await
+      .context("Failed to aggregate server stats")?
+      .try_collect::<Vec<Document>>()
+      .await
+      .context("Failed to collect server stats")?;
+    for stats in &per_server {
+      report.cpu_perc_avg += stats.get_f64("cpu_perc").unwrap_or(0.0);
+      report.mem_used_gb_avg +=
+        stats.get_f64("mem_used_gb").unwrap_or(0.0);
+      report.mem_total_gb_avg +=
+        stats.get_f64("mem_total_gb").unwrap_or(0.0);
+    }
+    if !per_server.is_empty() {
+      report.cpu_perc_avg /= per_server.len() as f64;
+    }
+  }
+
+  // Container counts come from the latest server status.
+  for server in &servers {
+    let Some(containers) = server_status_cache()
+      .get(&server.id)
+      .await
+      .and_then(|status| status.containers.clone())
+    else {
+      continue;
+    };
+    report.containers += containers.len() as I64;
+    report.running_containers += containers
+      .iter()
+      .filter(|container| {
+        container.state == ContainerStateStatusEnum::Running
+      })
+      .count() as I64;
+  }
+
+  // Build minutes from the finished RunBuild Updates in the window.
+  if !build_ids.is_empty() {
+    let builds = db_client()
+      .updates
+      .aggregate([
+        doc! { "$match": {
+          "operation": "RunBuild",
+          "target.type": "Build",
+          "target.id": { "$in": &build_ids },
+          "status": UpdateStatus::Complete.to_string(),
+          "start_ts": { "$gte": since, "$lte": until },
+          "end_ts": { "$ne": null },
+        } },
+        doc! { "$group": {
+          "_id": null,
+          "runs": { "$sum": 1 },
+          "ms": { "$sum": { "$subtract": ["$end_ts", "$start_ts"] } },
+        } },
+      ])
+      .await
+      .context("Failed to aggregate build updates")?
+      .try_collect::<Vec<Document>>()
+      .await
+      .context("Failed to collect build updates")?;
+    if let Some(builds) = builds.first() {
+      report.build_runs = get_number(builds, "runs") as I64;
+      report.build_minutes = get_number(builds, "ms") / 60_000.0;
+    }
+  }
+
+  Ok(report)
+}
+
+/// Mongo may return sums as i32, i64 or f64.
+fn get_number(doc: &Document, key: &str) -> f64 {
+  doc
+    .get_i64(key)
+    .map(|n| n as f64)
+    .or_else(|_| doc.get_i32(key).map(|n| n as f64))
+    .or_else(|_| doc.get_f64(key))
+    .unwrap_or_default()
+}
+
+/// Sends the tag usage reports to the configured
+/// `tag_usage_report_alerters` every `tag_usage_report_interval`.
+pub fn spawn_tag_usage_report_loop() {
+  let config = core_config();
+  if config.tag_usage_report_alerters.is_empty() {
+    return;
+  }
+  let interval = config.tag_usage_report_interval;
+  tokio::spawn(async move {
+    loop {
+      wait_until_timelength(interval, 0).await;
+      let until = unix_timestamp_ms() as I64;
+      let since = until - get_timelength_in_ms(interval) as I64;
+      if let Err(e) =
+        send_tag_usage_reports(since, until, &interval.to_string())
+          .await
+      {
+        error!("Failed to send tag usage reports | {e:#}");
+      }
+    }
+  });
+}
+
+async fn send_tag_usage_reports(
+  since: I64,
+  until: I64,
+  window: &str,
+) -> anyhow::Result<()> {
+  let config = core_config();
+  let mut alerters = Vec::new();
+  for alerter in &config.tag_usage_report_alerters {
+    match resource::get::<Alerter>(alerter).await {
+      Ok(alerter) => alerters.push(alerter),
+      Err(e) => {
+        warn!("Tag usage report alerter {alerter} not found | {e:#}")
+      }
+    }
+  }
+  alerters.retain(|alerter| {
+    alerter.config.alert_types.is_empty()
+      || alerter
+        .config
+        .alert_types
+        .contains(&AlertDataVariant::Custom)
+  });
+  if alerters.is_empty() {
+    return Ok(());
+  }
+
+  let tags = find_collect(&db_client().tags, None, None)
+    .await
+    .context("Failed to get tags from db")?;
+
+  let mut details = String::new();
+  for tag in &tags {
+    let report =
+      get_tag_usage_report(tag, since, until, system_user()).await?;
+    let _ = writeln!(
+      &mut details,
+      "{}: {} servers (cpu {:.1}%, mem {:.1}/{:.1} GB) | {}/{} containers running | {} deployments | {} stacks | {} builds ({} runs, {:.1} min)",
+      report.name,
+      report.servers,
+      report.cpu_perc_avg,
+      report.mem_used_gb_avg,
+      report.mem_total_gb_avg,
+      report.running_containers,
+      report.containers,
+      report.deployments,
+      report.stacks,
+      report.builds,
+      report.build_runs,
+      report.build_minutes,
+    );
+  }
+
+  let ts = komodo_timestamp();
+  let alert = Alert {
+    id: Default::default(),
+    ts,
+    resolved: true,
+    level: SeverityLevel::Ok,
+    target: ResourceTarget::system(),
+    data: AlertData::Custom {
+      message: format!("Tag usage report | last {window}"),
+      details,
+    },
+    resolved_ts: Some(ts),
+  };
+
+  alerters
+    .iter()
+    .map(|alerter| send_alert_to_alerter(alerter, &alert))
+    .collect::<FuturesUnordered<_>>()
+    .collect::<Vec<_>>()
+    .await
+    .into_iter()
+    .collect::<anyhow::Result<Vec<_>>>()?;
+
+  Ok(())
+}
@@ -0,0 +1,115 @@
+use std::{
+  collections::HashMap,
+  sync::{Mutex, OnceLock},
+};
+
+use anyhow::anyhow;
+use komodo_client::entities::{
+  komodo_timestamp,
+  server::{ActiveTerminalSession, Server},
+  user::User,
+};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::config::core_config;
+
+struct TerminalSession {
+  info: ActiveTerminalSession,
+  cancel: CancellationToken,
+}
+
+fn terminal_sessions()
+-> &'static Mutex<HashMap<String, TerminalSession>> {
+  static TERMINAL_SESSIONS: OnceLock<
+    Mutex<HashMap<String, TerminalSession>>,
+  > = OnceLock::new();
+  TERMINAL_SESSIONS.get_or_init(Default::default)
+}
+
+/// Held for the lifetime of a user terminal connection.
+/// The session is removed from the active sessions when dropped.
+pub struct TerminalSessionGuard {
+  id: String,
+  /// Cancelled when the session is force disconnected.
+  pub cancel: CancellationToken,
+}
+
+impl Drop for TerminalSessionGuard {
+  fn drop(&mut self) {
+    terminal_sessions().lock().unwrap().remove(&self.id);
+  }
+}
+
+/// Registers a new terminal session for the user,
+/// enforcing the Core `max_terminal_sessions` and
+/// `max_terminal_sessions_per_user` limits.
+pub fn open_terminal_session(
+  user: &User,
+  server: &Server,
+  terminal: String,
+) -> anyhow::Result<TerminalSessionGuard> {
+  let config = core_config();
+  let mut sessions = terminal_sessions().lock().unwrap();
+  if config.max_terminal_sessions > 0
+    && sessions.len() >= config.max_terminal_sessions
+  {
+    return Err(anyhow!(
+      "Reached the maximum of {} concurrent terminal sessions",
+      config.max_terminal_sessions
+    ));
+  }
+  if config.max_terminal_sessions_per_user > 0
+    && sessions
+      .values()
+      .filter(|session| session.info.user_id == user.id)
+      .count()
+      >= config.max_terminal_sessions_per_user
+  {
+    return Err(anyhow!(
+      "Reached the maximum of {} concurrent terminal sessions for user",
+      config.max_terminal_sessions_per_user
+    ));
+  }
+  let id = Uuid::new_v4().to_string();
+  let cancel = CancellationToken::new();
+  sessions.insert(
+    id.clone(),
+    TerminalSession {
+      info: ActiveTerminalSession {
+        id: id.clone(),
+        user_id: user.id.clone(),
+        username: user.username.clone(),
+        server_id: server.id.clone(),
+        server_name: server.name.clone(),
+        terminal,
+        started_at: komodo_timestamp(),
+      },
+      cancel: cancel.clone(),
+    },
+  );
+  Ok(TerminalSessionGuard { id, cancel })
+}
+
+pub fn list_terminal_sessions() -> Vec<ActiveTerminalSession> {
+  let mut sessions = terminal_sessions()
+    .lock()
+    .unwrap()
+    .values()
+    .map(|session| session.info.clone())
+    .collect::<Vec<_>>();
+  sessions.sort_by_key(|session| session.started_at);
+  sessions
+}
+
+/// Force disconnects the session.
+/// Returns false if the session was not found.
+pub fn disconnect_terminal_session(id: &str) -> bool {
+  match terminal_sessions().lock().unwrap().get(id) {
+    Some(session) => {
+      session.cancel.cancel();
+      true
+    }
+    None => false,
+  }
+}
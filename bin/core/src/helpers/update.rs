@@ -1,29 +1,39 @@
-use anyhow::Context;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, anyhow};
 use database::mungos::{
   by_id::{find_one_by_id, update_one_by_id},
   mongodb::bson::to_document,
 };
+use futures::StreamExt;
 use komodo_client::entities::{
   Operation, ResourceTarget,
   action::Action,
   alerter::Alerter,
   build::Build,
   deployment::Deployment,
+  event::CoreEventData,
   komodo_timestamp,
   procedure::Procedure,
   repo::Repo,
   server::Server,
   stack::Stack,
   sync::ResourceSync,
-  update::{Update, UpdateListItem},
+  update::{Log, Update, UpdateListItem, UpdateStatus},
   user::User,
 };
+use periphery_client::api::OutputChunk;
+use resolver_api::HasResponse;
+use serde::{Serialize, de::DeserializeOwned};
 
 use crate::{
-  api::execute::ExecuteRequest, resource, state::db_client,
+  api::execute::ExecuteRequest,
+  periphery::{PeripheryClient, StreamResponse},
+  resource,
+  state::db_client,
 };
 
-use super::channel::update_channel;
+use super::{channel::update_channel, event::emit_event};
 
 pub fn make_update(
   target: impl Into<ResourceTarget>,
@@ -112,13 +122,93 @@ async fn update_list_item(
 }
 
 async fn send_update(update: UpdateListItem) -> anyhow::Result<()> {
+  if update.status == UpdateStatus::Complete {
+    emit_event(
+      update.target.clone(),
+      CoreEventData::UpdateFinalized(update.clone()),
+    )
+    .await;
+  }
   update_channel().sender.lock().await.send(update)?;
   Ok(())
 }
 
+/// How often streamed output is written to the update.
+const STREAM_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Runs the request on Periphery, writing the output to
+/// the update as it streams in. The caller adds the final
+/// logs, which replace the in progress ones.
+pub async fn request_streamed_to_update<T>(
+  periphery: &PeripheryClient,
+  request: T,
+  mut update: Update,
+) -> anyhow::Result<T::Response>
+where
+  T: std::fmt::Debug + Serialize + HasResponse,
+  T::Response: DeserializeOwned,
+{
+  let stream = periphery.request_stream(request).await?;
+  let mut stream = std::pin::pin!(stream);
+  let base_len = update.logs.len();
+  let mut last_progress = Instant::now();
+  while let Some(res) = stream.next().await {
+    let OutputChunk {
+      stage,
+      stdout,
+      stderr,
+    } = match res? {
+      StreamResponse::Chunk(chunk) => chunk,
+      StreamResponse::Done(response) => return Ok(response),
+    };
+    let index = match update.logs[base_len..]
+      .iter()
+      .position(|log| log.stage == stage)
+    {
+      Some(index) => base_len + index,
+      None => {
+        update.logs.push(Log {
+          stage,
+          success: true,
+          start_ts: komodo_timestamp(),
+          ..Default::default()
+        });
+        update.logs.len() - 1
+      }
+    };
+    let log = &mut update.logs[index];
+    if !stdout.is_empty() {
+      log.stdout.push_str(&stdout);
+      log.stdout.push('\n');
+    }
+    if !stderr.is_empty() {
+      log.stderr.push_str(&stderr);
+      log.stderr.push('\n');
+    }
+    log.end_ts = komodo_timestamp();
+    if last_progress.elapsed() >= STREAM_PROGRESS_INTERVAL {
+      last_progress = Instant::now();
+      if let Err(e) = update_update(update.clone()).await {
+        warn!("Failed to write progress to update | {e:#}");
+      }
+    }
+  }
+  Err(anyhow!("Periphery closed the stream without a response"))
+}
+
 pub async fn init_execution_update(
   request: &ExecuteRequest,
   user: &User,
+) -> anyhow::Result<Update> {
+  init_idempotent_execution_update(request, user, None).await
+}
+
+/// Same as [init_execution_update], additionally storing
+/// the idempotency key passed with the request on the Update.
+pub async fn init_idempotent_execution_update(
+  request: &ExecuteRequest,
+  user: &User,
+  idempotency_key: Option<String>,
 ) -> anyhow::Result<Update> {
   let (operation, target) = match &request {
     // Server
@@ -158,6 +248,12 @@ pub async fn init_execution_update(
         resource::get::<Server>(&data.server).await?.id,
       ),
     ),
+    ExecuteRequest::CommitContainer(data) => (
+      Operation::CommitContainer,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
     ExecuteRequest::StartAllContainers(data) => (
       Operation::StartAllContainers,
       ResourceTarget::Server(
@@ -218,6 +314,12 @@ pub async fn init_execution_update(
         resource::get::<Server>(&data.server).await?.id,
       ),
     ),
+    ExecuteRequest::TransferImage(data) => (
+      Operation::TransferImage,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.to_server).await?.id,
+      ),
+    ),
     ExecuteRequest::DeleteVolume(data) => (
       Operation::DeleteVolume,
       ResourceTarget::Server(
@@ -242,12 +344,66 @@ pub async fn init_execution_update(
         resource::get::<Server>(&data.server).await?.id,
       ),
     ),
+    ExecuteRequest::PruneBuildCache(data) => (
+      Operation::PruneBuildCache,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
     ExecuteRequest::PruneSystem(data) => (
       Operation::PruneSystem,
       ResourceTarget::Server(
         resource::get::<Server>(&data.server).await?.id,
       ),
     ),
+    ExecuteRequest::RunServerExtension(data) => (
+      Operation::RunServerExtension,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
+    ExecuteRequest::KillSystemProcess(data) => (
+      Operation::KillSystemProcess,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
+    ExecuteRequest::SetSystemProcessPriority(data) => (
+      Operation::SetSystemProcessPriority,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
+    ExecuteRequest::StartSystemdUnit(data) => (
+      Operation::StartSystemdUnit,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
+    ExecuteRequest::StopSystemdUnit(data) => (
+      Operation::StopSystemdUnit,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
+    ExecuteRequest::RestartSystemdUnit(data) => (
+      Operation::RestartSystemdUnit,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
+    ExecuteRequest::RebootServer(data) => (
+      Operation::RebootServer,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
+    ExecuteRequest::ShutdownServer(data) => (
+      Operation::ShutdownServer,
+      ResourceTarget::Server(
+        resource::get::<Server>(&data.server).await?.id,
+      ),
+    ),
 
     // Deployment
     ExecuteRequest::Deploy(data) => (
@@ -301,6 +457,12 @@ pub async fn init_execution_update(
         resource::get::<Deployment>(&data.deployment).await?.id,
       ),
     ),
+    ExecuteRequest::RollbackDeployment(data) => (
+      Operation::RollbackDeployment,
+      ResourceTarget::Deployment(
+        resource::get::<Deployment>(&data.deployment).await?.id,
+      ),
+    ),
     ExecuteRequest::BatchDestroyDeployment(_data) => {
       return Ok(Default::default());
     }
@@ -493,6 +655,24 @@ pub async fn init_execution_update(
         resource::get::<Stack>(&data.stack).await?.id,
       ),
     ),
+    ExecuteRequest::ScaleStack(data) => (
+      Operation::ScaleStack,
+      ResourceTarget::Stack(
+        resource::get::<Stack>(&data.stack).await?.id,
+      ),
+    ),
+    ExecuteRequest::PromoteStack(data) => (
+      Operation::PromoteStack,
+      ResourceTarget::Stack(
+        resource::get::<Stack>(&data.to).await?.id,
+      ),
+    ),
+    ExecuteRequest::MigrateStack(data) => (
+      Operation::MigrateStack,
+      ResourceTarget::Stack(
+        resource::get::<Stack>(&data.stack).await?.id,
+      ),
+    ),
 
     // Alerter
     ExecuteRequest::TestAlerter(data) => (
@@ -525,6 +705,7 @@ pub async fn init_execution_update(
 
   let mut update = make_update(target, operation, user);
   update.in_progress();
+  update.idempotency_key = idempotency_key.unwrap_or_default();
 
   // Hold off on even adding update for DeployStackIfChanged
   if !matches!(&request, ExecuteRequest::DeployStackIfChanged(_)) {
@@ -0,0 +1,300 @@
+use std::{
+  collections::HashMap,
+  sync::{Arc, OnceLock},
+};
+
+use anyhow::{Context, anyhow};
+use axum::http::HeaderMap;
+use database::mungos::{
+  find::find_collect,
+  mongodb::bson::{doc, oid::ObjectId},
+};
+use hmac::{Hmac, Mac};
+use komodo_client::entities::{
+  ResourceTarget,
+  alert::{Alert, AlertData, SeverityLevel},
+  deployment::Deployment,
+  komodo_timestamp,
+  server::Server,
+  stack::Stack,
+};
+use serde::Deserialize;
+use sha2::Sha256;
+use tokio::sync::Mutex;
+
+use crate::{
+  alert::send_alerts,
+  config::core_config,
+  resource::{self, KomodoResource},
+  state::db_client,
+};
+
+/// The Alertmanager webhook payload.
+/// https://prometheus.io/docs/alerting/latest/configuration/#webhook_config
+#[derive(Deserialize)]
+pub struct AlertmanagerWebhook {
+  alerts: Vec<AlertmanagerAlert>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlertmanagerAlert {
+  /// `firing` or `resolved`
+  status: String,
+  #[serde(default)]
+  labels: HashMap<String, String>,
+  #[serde(default)]
+  annotations: HashMap<String, String>,
+  #[serde(default, rename = "generatorURL")]
+  generator_url: String,
+  fingerprint: String,
+}
+
+/// Alertmanager is configured with the Core `webhook_secret`
+/// as the bearer token in the webhook `http_config.authorization`.
+pub fn verify_secret(headers: &HeaderMap) -> anyhow::Result<()> {
+  let secret = &core_config().webhook_secret;
+  if secret.is_empty() {
+    return Err(anyhow!(
+      "Core must be configured with 'webhook_secret' to receive Alertmanager alerts"
+    ));
+  }
+  let token = headers
+    .get("authorization")
+    .context("No authorization header")?
+    .to_str()
+    .context("Authorization header is not valid string")?
+    .strip_prefix("Bearer ")
+    .context("Authorization header is not bearer token")?;
+  // Compare the hmacs in constant time, so the timing
+  // doesn't reveal how much of the token matched.
+  let mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+    .context("Failed to create hmac sha256 from secret")?;
+  let expected =
+    mac.clone().chain_update(secret).finalize().into_bytes();
+  mac
+    .chain_update(token)
+    .verify_slice(&expected)
+    .map_err(|_| anyhow!("Invalid bearer token"))
+}
+
+/// Opens new Komodo alerts for firing alerts,
+/// and resolves the open alerts for resolved ones.
+/// Alerts are matched up using the Alertmanager fingerprint.
+pub async fn handle_alertmanager_webhook(
+  webhook: AlertmanagerWebhook,
+) -> anyhow::Result<()> {
+  // Alertmanager may retry / send overlapping groups,
+  // process one webhook at a time to avoid duplicate alerts.
+  static LOCK: OnceLock<Arc<Mutex<()>>> = OnceLock::new();
+  let _lock = LOCK.get_or_init(Default::default).lock().await;
+
+  let fingerprints = webhook
+    .alerts
+    .iter()
+    .map(|alert| alert.fingerprint.as_str())
+    .collect::<Vec<_>>();
+  let open = find_collect(
+    &db_client().alerts,
+    doc! {
+      "resolved": false,
+      "data.type": "Alertmanager",
+      "data.data.fingerprint": { "$in": fingerprints },
+    },
+    None,
+  )
+  .await
+  .context("Failed to query db for open alerts")?
+  .into_iter()
+  .filter_map(|alert| match &alert.data {
+    AlertData::Alertmanager { fingerprint, .. } => {
+      Some((fingerprint.clone(), alert))
+    }
+    _ => None,
+  })
+  .collect::<HashMap<_, _>>();
+
+  let ts = komodo_timestamp();
+  let mut opened = Vec::new();
+  let mut resolved = Vec::new();
+
+  for alert in webhook.alerts {
+    match (alert.status.as_str(), open.get(&alert.fingerprint)) {
+      // Already open, nothing to do
+      ("firing", Some(_)) => {}
+      ("firing", None) => {
+        let target = alert_target(&alert.labels).await;
+        let level = match alert
+          .labels
+          .get("severity")
+          .map(|severity| severity.to_lowercase())
+          .as_deref()
+        {
+          Some("critical" | "error" | "page") => {
+            SeverityLevel::Critical
+          }
+          _ => SeverityLevel::Warning,
+        };
+        let summary = alert
+          .annotations
+          .get("summary")
+          .or_else(|| alert.annotations.get("description"))
+          .cloned()
+          .unwrap_or_default();
+        opened.push(Alert {
+          id: Default::default(),
+          ts,
+          resolved: false,
+          level,
+          target,
+          data: AlertData::Alertmanager {
+            name: alert
+              .labels
+              .get("alertname")
+              .cloned()
+              .unwrap_or_else(|| alert.fingerprint.clone()),
+            fingerprint: alert.fingerprint,
+            summary,
+            url: alert.generator_url,
+            labels: alert.labels,
+          },
+          resolved_ts: None,
+        });
+      }
+      ("resolved", Some(existing)) => {
+        let mut existing = existing.clone();
+        existing.resolved = true;
+        existing.resolved_ts = Some(ts);
+        existing.level = SeverityLevel::Ok;
+        resolved.push(existing);
+      }
+      // Resolved but not open, or unknown status
+      _ => {}
+    }
+  }
+
+  if !opened.is_empty() {
+    let res = db_client()
+      .alerts
+      .insert_many(&opened)
+      .await
+      .context("Failed to record Alertmanager alerts to db")?;
+    for (index, id) in res.inserted_ids {
+      if let (Some(alert), Some(id)) =
+        (opened.get_mut(index), id.as_object_id())
+      {
+        alert.id = id.to_hex();
+      }
+    }
+  }
+
+  if !resolved.is_empty() {
+    let ids = resolved
+      .iter()
+      .filter_map(|alert| ObjectId::parse_str(&alert.id).ok())
+      .collect::<Vec<_>>();
+    db_client()
+      .alerts
+      .update_many(
+        doc! { "_id": { "$in": ids } },
+        doc! { "$set": { "resolved": true, "resolved_ts": ts } },
+      )
+      .await
+      .context("Failed to resolve Alertmanager alerts on db")?;
+  }
+
+  opened.extend(resolved);
+  send_alerts(&opened).await;
+
+  Ok(())
+}
+
+/// Maps the alert labels to a Komodo resource.
+///
+/// 1. Explicit `komodo_server`, `komodo_stack`, or `komodo_deployment`
+///    labels containing the resource id or name.
+/// 2. Servers, Stacks, or Deployments with a tag named `<label>=<value>`
+///    matching one of the alert labels, eg. `instance=node-1:9100`.
+///
+/// Falls back to the System target when nothing matches.
+async fn alert_target(
+  labels: &HashMap<String, String>,
+) -> ResourceTarget {
+  if let Some(target) = explicit_target(labels).await {
+    return target;
+  }
+  tagged_target(labels)
+    .await
+    .inspect_err(|e| {
+      warn!("Failed to match Alertmanager alert to tagged resource | {e:#}")
+    })
+    .ok()
+    .flatten()
+    .unwrap_or_default()
+}
+
+async fn explicit_target(
+  labels: &HashMap<String, String>,
+) -> Option<ResourceTarget> {
+  if let Some(server) = labels.get("komodo_server")
+    && let Ok(server) = resource::get::<Server>(server).await
+  {
+    return Some((&server).into());
+  }
+  if let Some(stack) = labels.get("komodo_stack")
+    && let Ok(stack) = resource::get::<Stack>(stack).await
+  {
+    return Some(ResourceTarget::Stack(stack.id));
+  }
+  if let Some(deployment) = labels.get("komodo_deployment")
+    && let Ok(deployment) =
+      resource::get::<Deployment>(deployment).await
+  {
+    return Some((&deployment).into());
+  }
+  None
+}
+
+async fn tagged_target(
+  labels: &HashMap<String, String>,
+) -> anyhow::Result<Option<ResourceTarget>> {
+  let names = labels
+    .iter()
+    .map(|(key, value)| format!("{key}={value}"))
+    .collect::<Vec<_>>();
+  let tags = find_collect(
+    &db_client().tags,
+    doc! { "name": { "$in": names } },
+    None,
+  )
+  .await
+  .context("Failed to query db for tags")?
+  .into_iter()
+  .map(|tag| tag.id)
+  .collect::<Vec<_>>();
+  if tags.is_empty() {
+    return Ok(None);
+  }
+  if let Some(id) = tagged_id::<Server>(&tags).await? {
+    return Ok(Some(ResourceTarget::Server(id)));
+  }
+  if let Some(id) = tagged_id::<Stack>(&tags).await? {
+    return Ok(Some(ResourceTarget::Stack(id)));
+  }
+  if let Some(id) = tagged_id::<Deployment>(&tags).await? {
+    return Ok(Some(ResourceTarget::Deployment(id)));
+  }
+  Ok(None)
+}
+
+async fn tagged_id<T: KomodoResource>(
+  tags: &[String],
+) -> anyhow::Result<Option<String>> {
+  T::coll()
+    .find_one(doc! { "tags": { "$in": tags } })
+    .await
+    .with_context(|| {
+      format!("Failed to query db for tagged {}", T::resource_type())
+    })
+    .map(|resource| resource.map(|resource| resource.id))
+}
@@ -1,13 +1,16 @@
 use std::sync::Arc;
 
 use anyhow::anyhow;
-use axum::{Router, http::HeaderMap};
+use axum::{Json, Router, http::HeaderMap, routing::post};
 use cache::CloneCache;
 use komodo_client::entities::resource::Resource;
+use reqwest::StatusCode;
+use serror::AddStatusCode;
 use tokio::sync::Mutex;
 
 use crate::resource::KomodoResource;
 
+mod alertmanager;
 mod integrations;
 mod resources;
 mod router;
@@ -18,6 +21,19 @@ pub fn router() -> Router {
   Router::new()
     .nest("/github", router::router::<github::Github>())
     .nest("/gitlab", router::router::<gitlab::Gitlab>())
+    .route(
+      "/alertmanager",
+      post(
+        |headers: HeaderMap,
+         Json(webhook): Json<alertmanager::AlertmanagerWebhook>| async move {
+          alertmanager::verify_secret(&headers)
+            .status_code(StatusCode::UNAUTHORIZED)?;
+          alertmanager::handle_alertmanager_webhook(webhook)
+            .await
+            .status_code(StatusCode::INTERNAL_SERVER_ERROR)
+        },
+      ),
+    )
 }
 
 type ListenerLockCache = CloneCache<String, Arc<Mutex<()>>>;
@@ -8,7 +8,9 @@ use serde::Deserialize;
 use serror::AddStatusCode;
 use tracing::Instrument;
 
-use crate::resource::KomodoResource;
+use crate::{
+  helpers::maintenance::check_read_only, resource::KomodoResource,
+};
 
 use super::{
   CustomSecret, ExtractBranch, VerifySecret,
@@ -216,5 +218,6 @@ where
     .status_code(StatusCode::BAD_REQUEST)?;
   P::verify_secret(headers, body, R::custom_secret(&resource))
     .status_code(StatusCode::UNAUTHORIZED)?;
+  check_read_only().status_code(StatusCode::SERVICE_UNAVAILABLE)?;
   Ok(resource)
 }
@@ -3,7 +3,7 @@
 #[macro_use]
 extern crate tracing;
 
-use std::{net::SocketAddr, str::FromStr};
+use std::{net::SocketAddr, str::FromStr, time::Duration};
 
 use anyhow::Context;
 use axum::{Router, routing::get};
@@ -22,14 +22,17 @@ mod auth;
 mod cloud;
 mod config;
 mod connection;
+mod health;
 mod helpers;
 mod listener;
 mod monitor;
 mod network;
+mod onboard;
 mod periphery;
 mod permission;
 mod resource;
 mod schedule;
+mod shutdown;
 mod stack;
 mod startup;
 mod state;
@@ -63,6 +66,18 @@ async fn app() -> anyhow::Result<()> {
       .install_default()
       .expect("Failed to install default crypto provider");
 
+    transport::auth::set_auth_timeout(Duration::from_millis(
+      config.auth_timeout_ms,
+    ));
+    if let Some(psk) = config.transport_psk.as_deref() {
+      transport::auth::set_transport_psk(psk);
+    }
+    encoding::set_decode_limits(encoding::DecodeLimits {
+      max_message_bytes: config.transport_max_message_bytes,
+      max_body_bytes: config.transport_max_body_bytes,
+      max_body_depth: config.transport_max_body_depth,
+    });
+
     // Init jwt client to crash on failure
     state::jwt_client();
     tokio::join!(
@@ -71,6 +86,8 @@ async fn app() -> anyhow::Result<()> {
       // Manage OIDC client (defined in config / env vars / compose secret file)
       auth::oidc::client::spawn_oidc_client_management()
     );
+    // Subscribe before startup connects to Periphery.
+    helpers::event::spawn_event_handlers();
     // Run after db connection.
     startup::on_startup().await;
 
@@ -84,6 +101,8 @@ async fn app() -> anyhow::Result<()> {
     resource::spawn_action_state_refresh_loop();
     schedule::spawn_schedule_executor();
     helpers::prune::spawn_prune_loop();
+    helpers::tag_report::spawn_tag_usage_report_loop();
+    helpers::key_rotation::spawn_key_rotation_loop();
   }
   .instrument(startup_span)
   .await;
@@ -97,6 +116,7 @@ async fn app() -> anyhow::Result<()> {
 
   let app = Router::new()
     .route("/version", get(|| async { env!("CARGO_PKG_VERSION") }))
+    .nest("/health", health::router())
     .nest("/auth", api::auth::router())
     .nest("/user", api::user::router())
     .nest("/read", api::read::router())
@@ -104,6 +124,7 @@ async fn app() -> anyhow::Result<()> {
     .nest("/execute", api::execute::router())
     .nest("/terminal", api::terminal::router())
     .nest("/listener", listener::router())
+    .nest("/onboard", onboard::router())
     .nest("/ws", ws::router())
     .nest("/client", ts_client::router())
     .fallback_service(serve_frontend)
@@ -113,7 +134,7 @@ async fn app() -> anyhow::Result<()> {
         .allow_methods(Any)
         .allow_headers(Any),
     )
-    .into_make_service();
+    .into_make_service_with_connect_info::<SocketAddr>();
 
   let addr =
     format!("{}:{}", core_config().bind_ip, core_config().port);
@@ -164,6 +185,9 @@ async fn main() -> anyhow::Result<()> {
   )?;
   tokio::select! {
     res = tokio::spawn(app()) => res?,
-    _ = term_signal.recv() => Ok(()),
+    _ = term_signal.recv() => {
+      shutdown::on_shutdown().await;
+      Ok(())
+    },
   }
 }
@@ -1,5 +1,9 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
+use database::mungos::{
+  find::find_collect,
+  mongodb::bson::{doc, oid::ObjectId},
+};
 use komodo_client::entities::{
   ResourceTarget,
   alert::{Alert, AlertData, SeverityLevel},
@@ -16,6 +20,16 @@ use crate::{
 pub async fn alert_deployments(
   ts: i64,
   server_names: &HashMap<String, String>,
+) {
+  tokio::join!(
+    alert_deployment_states(ts, server_names),
+    alert_deployment_health(ts, server_names),
+  );
+}
+
+async fn alert_deployment_states(
+  ts: i64,
+  server_names: &HashMap<String, String>,
 ) {
   let mut alerts = Vec::<Alert>::new();
   let action_states = action_states();
@@ -94,3 +108,117 @@ pub async fn alert_deployments(
     error!("failed to record deployment status alerts to db | {e:#}");
   }
 }
+
+/// Opens a DeploymentUnhealthy alert while the custom
+/// health probe fails, and resolves it once it passes again.
+async fn alert_deployment_health(
+  ts: i64,
+  server_names: &HashMap<String, String>,
+) {
+  let open_alerts = match find_collect(
+    &db_client().alerts,
+    doc! { "resolved": false, "data.type": "DeploymentUnhealthy" },
+    None,
+  )
+  .await
+  {
+    Ok(alerts) => alerts
+      .into_iter()
+      .filter_map(|alert| match &alert.data {
+        AlertData::DeploymentUnhealthy { id, .. } => {
+          Some((id.clone(), alert))
+        }
+        _ => None,
+      })
+      .collect::<HashMap<_, _>>(),
+    Err(e) => {
+      error!("failed to get open deployment health alerts | {e:#}");
+      return;
+    }
+  };
+
+  let mut to_open = Vec::<Alert>::new();
+  let mut to_resolve = Vec::<Alert>::new();
+
+  for status in deployment_status_cache().get_values().await {
+    // Server unreachable is alerted separately.
+    if status.curr.state == DeploymentState::Unknown {
+      continue;
+    }
+    let open = open_alerts.get(&status.curr.id);
+    match (&status.curr.health, open) {
+      (Some(health), None) if !health.healthy => {
+        let Ok(deployment) =
+          resource::get::<Deployment>(&status.curr.id)
+            .await
+            .inspect_err(|e| {
+              error!("failed to get deployment from db | {e:#?}")
+            })
+        else {
+          continue;
+        };
+        if !deployment.config.send_alerts {
+          continue;
+        }
+        to_open.push(Alert {
+          id: Default::default(),
+          ts,
+          resolved: false,
+          resolved_ts: None,
+          level: SeverityLevel::Warning,
+          target: (&deployment).into(),
+          data: AlertData::DeploymentUnhealthy {
+            id: deployment.id,
+            name: deployment.name,
+            server_name: server_names
+              .get(&deployment.config.server_id)
+              .cloned()
+              .unwrap_or(String::from("unknown")),
+            server_id: deployment.config.server_id,
+            message: health.message.clone(),
+          },
+        });
+      }
+      // Healthy again, or the probe no longer runs.
+      (health, Some(alert))
+        if health.as_ref().map(|h| h.healthy).unwrap_or(true) =>
+      {
+        let mut alert = alert.clone();
+        alert.resolved = true;
+        alert.resolved_ts = Some(ts);
+        alert.level = SeverityLevel::Ok;
+        to_resolve.push(alert);
+      }
+      _ => {}
+    }
+  }
+
+  if !to_open.is_empty() {
+    send_alerts(&to_open).await;
+    if let Err(e) = db_client().alerts.insert_many(&to_open).await {
+      error!(
+        "failed to record deployment health alerts to db | {e:#}"
+      );
+    }
+  }
+
+  if !to_resolve.is_empty() {
+    let ids = to_resolve
+      .iter()
+      .filter_map(|alert| ObjectId::from_str(&alert.id).ok())
+      .collect::<Vec<_>>();
+    if let Err(e) = db_client()
+      .alerts
+      .update_many(
+        doc! { "_id": { "$in": ids } },
+        doc! { "$set": { "resolved": true, "resolved_ts": ts } },
+      )
+      .await
+    {
+      error!(
+        "failed to resolve deployment health alerts on db | {e:#}"
+      );
+    }
+    send_alerts(&to_resolve).await;
+  }
+}
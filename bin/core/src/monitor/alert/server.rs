@@ -18,11 +18,16 @@ use komodo_client::entities::{
   alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
   komodo_timestamp, optional_string,
   server::{Server, ServerState},
+  stats::{DiskHealth, SingleGpuUsage, ZfsPool, gpu_id},
 };
 
 use crate::{
   alert::send_alerts,
   helpers::maintenance::is_in_maintenance,
+  monitor::{
+    disk_health::{disk_health_cache, disk_health_level},
+    os_updates::os_updates_cache,
+  },
   state::{db_client, server_status_cache},
 };
 
@@ -30,6 +35,9 @@ type SendAlerts = bool;
 type OpenAlertMap<T = AlertDataVariant> =
   HashMap<ResourceTarget, HashMap<T, Alert>>;
 type OpenDiskAlertMap = OpenAlertMap<PathBuf>;
+type OpenGpuAlertMap = OpenAlertMap<String>;
+type OpenDiskHealthAlertMap = OpenAlertMap<String>;
+type OpenZfsAlertMap = OpenAlertMap<String>;
 
 /// Alert buffer to prevent immediate alerts on transient issues
 struct AlertBuffer {
@@ -79,8 +87,13 @@ pub async fn alert_servers(
 ) {
   let server_statuses = server_status_cache().get_values().await;
 
-  let (open_alerts, open_disk_alerts) = match get_open_alerts().await
-  {
+  let (
+    open_alerts,
+    open_disk_alerts,
+    open_gpu_alerts,
+    open_disk_health_alerts,
+    open_zfs_alerts,
+  ) = match get_open_alerts().await {
     Ok(alerts) => alerts,
     Err(e) => {
       error!("{e:#}");
@@ -255,6 +268,76 @@ pub async fn alert_servers(
       }
     }
 
+    // ===================
+    // SERVER OS SECURITY UPDATES
+    // ===================
+
+    // Not cached if the server can't check for updates.
+    let os_updates = os_updates_cache().get(&server_status.id).await;
+    let security_packages = os_updates
+      .as_ref()
+      .map(|updates| {
+        updates
+          .security_updates()
+          .map(|update| update.name.clone())
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+    let os_updates_data = || AlertData::ServerOsSecurityUpdates {
+      id: server_status.id.clone(),
+      name: server.name.clone(),
+      region: optional_string(&server.config.region),
+      package_manager: os_updates
+        .as_ref()
+        .map(|updates| updates.package_manager.clone())
+        .unwrap_or_default(),
+      packages: security_packages.clone(),
+    };
+
+    let os_updates_alert =
+      server_alerts.as_ref().and_then(|alerts| {
+        alerts.get(&AlertDataVariant::ServerOsSecurityUpdates)
+      });
+
+    match (security_packages.is_empty(), os_updates_alert) {
+      (false, None) => {
+        // Only open os updates alert if not in maintenance and buffer is ready
+        if !in_maintenance
+          && buffer.ready_to_open(
+            server_status.id.clone(),
+            AlertDataVariant::ServerOsSecurityUpdates,
+          )
+        {
+          let alert = Alert {
+            id: Default::default(),
+            ts,
+            resolved: false,
+            resolved_ts: None,
+            level: SeverityLevel::Warning,
+            target: ResourceTarget::Server(server_status.id.clone()),
+            data: os_updates_data(),
+          };
+          alerts_to_open
+            .push((alert, server.config.send_os_update_alerts))
+        }
+      }
+      (false, Some(alert)) => {
+        // Update the pending packages, without notification
+        let mut alert = alert.clone();
+        alert.data = os_updates_data();
+        alerts_to_update.push((alert, false));
+      }
+      (true, Some(alert)) => {
+        // Security updates were installed, close the alert
+        alert_ids_to_close
+          .push((alert.clone(), server.config.send_os_update_alerts));
+      }
+      (true, None) => buffer.reset(
+        server_status.id.clone(),
+        AlertDataVariant::ServerOsSecurityUpdates,
+      ),
+    }
+
     let Some(health) = &server_status.health else {
       continue;
     };
@@ -545,6 +628,264 @@ pub async fn alert_servers(
         }
       }
     }
+
+    // ===================
+    // SERVER GPU
+    // ===================
+
+    let server_gpu_alerts = open_gpu_alerts
+      .get(&ResourceTarget::Server(server_status.id.clone()));
+
+    let gpus = server_status
+      .system_stats
+      .as_ref()
+      .map(|stats| stats.gpus.as_slice())
+      .unwrap_or_default();
+
+    for gpu in gpus {
+      let key = gpu_id(gpu.vendor, gpu.index);
+      let Some(health) = health.gpus.get(&key) else {
+        continue;
+      };
+      let gpu_alert = server_gpu_alerts
+        .as_ref()
+        .and_then(|alerts| alerts.get(&key))
+        .cloned();
+      let data = || gpu_alert_data(&server, gpu);
+      match (health.level, gpu_alert, health.should_close_alert) {
+        (
+          SeverityLevel::Warning | SeverityLevel::Critical,
+          None,
+          _,
+        ) => {
+          // Only open gpu alert if not in maintenance and buffer is ready
+          if !in_maintenance
+            && buffer.ready_to_open(
+              server_status.id.clone(),
+              AlertDataVariant::ServerGpu,
+            )
+          {
+            let alert = Alert {
+              id: Default::default(),
+              ts,
+              resolved: false,
+              resolved_ts: None,
+              level: health.level,
+              target: ResourceTarget::Server(
+                server_status.id.clone(),
+              ),
+              data: data(),
+            };
+            alerts_to_open
+              .push((alert, server.config.send_gpu_alerts));
+          }
+        }
+        (
+          SeverityLevel::Warning | SeverityLevel::Critical,
+          Some(mut alert),
+          _,
+        ) => {
+          // modify alert level only if it has increased and not in maintenance
+          if !in_maintenance && alert.level < health.level {
+            alert.level = health.level;
+            alert.data = data();
+            alerts_to_update
+              .push((alert, server.config.send_gpu_alerts));
+          }
+        }
+        (SeverityLevel::Ok, Some(mut alert), true) => {
+          alert.level = health.level;
+          alert.data = data();
+          alert_ids_to_close
+            .push((alert, server.config.send_gpu_alerts))
+        }
+        (SeverityLevel::Ok, _, _) => buffer.reset(
+          server_status.id.clone(),
+          AlertDataVariant::ServerGpu,
+        ),
+      }
+    }
+
+    // Need to close any open ones on gpus no longer reported
+    if let Some(gpu_alerts) = server_gpu_alerts {
+      for (key, alert) in gpu_alerts {
+        if !health.gpus.contains_key(key) {
+          let mut alert = alert.clone();
+          alert.level = SeverityLevel::Ok;
+          alert_ids_to_close
+            .push((alert, server.config.send_gpu_alerts));
+        }
+      }
+    }
+
+    // ===================
+    // SERVER DISK HEALTH
+    // ===================
+
+    let server_disk_health_alerts = open_disk_health_alerts
+      .get(&ResourceTarget::Server(server_status.id.clone()));
+
+    // Not cached if the server can't read disk health.
+    let disk_healths = disk_health_cache()
+      .get(&server_status.id)
+      .await
+      .unwrap_or_default();
+
+    for disk in &disk_healths {
+      let (level, problems) = disk_health_level(disk);
+      let disk_health_alert = server_disk_health_alerts
+        .as_ref()
+        .and_then(|alerts| alerts.get(&disk.device))
+        .cloned();
+      let data = || disk_health_alert_data(&server, disk, &problems);
+      match (level, disk_health_alert) {
+        (SeverityLevel::Warning | SeverityLevel::Critical, None) => {
+          // Only open disk health alert if not in maintenance and buffer is ready
+          if !in_maintenance
+            && buffer.ready_to_open(
+              server_status.id.clone(),
+              AlertDataVariant::ServerDiskHealth,
+            )
+          {
+            let alert = Alert {
+              id: Default::default(),
+              ts,
+              resolved: false,
+              resolved_ts: None,
+              level,
+              target: ResourceTarget::Server(
+                server_status.id.clone(),
+              ),
+              data: data(),
+            };
+            alerts_to_open
+              .push((alert, server.config.send_disk_health_alerts));
+          }
+        }
+        (
+          SeverityLevel::Warning | SeverityLevel::Critical,
+          Some(mut alert),
+        ) => {
+          // modify alert level only if it has increased and not in maintenance
+          if !in_maintenance && alert.level < level {
+            alert.level = level;
+            alert.data = data();
+            alerts_to_update
+              .push((alert, server.config.send_disk_health_alerts));
+          }
+        }
+        (SeverityLevel::Ok, Some(mut alert)) => {
+          alert.level = level;
+          alert.data = data();
+          alert_ids_to_close
+            .push((alert, server.config.send_disk_health_alerts))
+        }
+        (SeverityLevel::Ok, None) => buffer.reset(
+          server_status.id.clone(),
+          AlertDataVariant::ServerDiskHealth,
+        ),
+      }
+    }
+
+    // Need to close any open ones on disks no longer reported
+    if let Some(disk_health_alerts) = server_disk_health_alerts {
+      for (device, alert) in disk_health_alerts {
+        if !disk_healths.iter().any(|disk| disk.device == *device) {
+          let mut alert = alert.clone();
+          alert.level = SeverityLevel::Ok;
+          alert_ids_to_close
+            .push((alert, server.config.send_disk_health_alerts));
+        }
+      }
+    }
+
+    // ===================
+    // SERVER ZFS POOLS
+    // ===================
+
+    let server_zfs_alerts = open_zfs_alerts
+      .get(&ResourceTarget::Server(server_status.id.clone()));
+
+    let pools = server_status
+      .system_stats
+      .as_ref()
+      .map(|stats| stats.zfs_pools.as_slice())
+      .unwrap_or_default();
+
+    for pool in pools {
+      let Some(health) = health.zfs_pools.get(&pool.name) else {
+        continue;
+      };
+      let zfs_alert = server_zfs_alerts
+        .as_ref()
+        .and_then(|alerts| alerts.get(&pool.name))
+        .cloned();
+      let data = || zfs_alert_data(&server, pool);
+      match (health.level, zfs_alert, health.should_close_alert) {
+        (
+          SeverityLevel::Warning | SeverityLevel::Critical,
+          None,
+          _,
+        ) => {
+          // Only open zfs alert if not in maintenance and buffer is ready
+          if !in_maintenance
+            && buffer.ready_to_open(
+              server_status.id.clone(),
+              AlertDataVariant::ServerZfsPool,
+            )
+          {
+            let alert = Alert {
+              id: Default::default(),
+              ts,
+              resolved: false,
+              resolved_ts: None,
+              level: health.level,
+              target: ResourceTarget::Server(
+                server_status.id.clone(),
+              ),
+              data: data(),
+            };
+            alerts_to_open
+              .push((alert, server.config.send_zfs_alerts));
+          }
+        }
+        (
+          SeverityLevel::Warning | SeverityLevel::Critical,
+          Some(mut alert),
+          _,
+        ) => {
+          // modify alert level only if it has increased and not in maintenance
+          if !in_maintenance && alert.level < health.level {
+            alert.level = health.level;
+            alert.data = data();
+            alerts_to_update
+              .push((alert, server.config.send_zfs_alerts));
+          }
+        }
+        (SeverityLevel::Ok, Some(mut alert), true) => {
+          alert.level = health.level;
+          alert.data = data();
+          alert_ids_to_close
+            .push((alert, server.config.send_zfs_alerts))
+        }
+        (SeverityLevel::Ok, _, _) => buffer.reset(
+          server_status.id.clone(),
+          AlertDataVariant::ServerZfsPool,
+        ),
+      }
+    }
+
+    // Need to close any open ones on pools no longer reported
+    if let Some(zfs_alerts) = server_zfs_alerts {
+      for (pool, alert) in zfs_alerts {
+        if !health.zfs_pools.contains_key(pool) {
+          let mut alert = alert.clone();
+          alert.level = SeverityLevel::Ok;
+          alert_ids_to_close
+            .push((alert, server.config.send_zfs_alerts));
+        }
+      }
+    }
   }
 
   tokio::join!(
@@ -554,6 +895,50 @@ pub async fn alert_servers(
   );
 }
 
+fn gpu_alert_data(
+  server: &Server,
+  gpu: &SingleGpuUsage,
+) -> AlertData {
+  AlertData::ServerGpu {
+    id: server.id.clone(),
+    name: server.name.clone(),
+    region: optional_string(&server.config.region),
+    vendor: gpu.vendor,
+    index: gpu.index,
+    gpu: gpu.name.clone(),
+    mem_used_gb: gpu.mem_used_gb,
+    mem_total_gb: gpu.mem_total_gb,
+    temperature: gpu.temperature,
+  }
+}
+
+fn disk_health_alert_data(
+  server: &Server,
+  disk: &DiskHealth,
+  problems: &[String],
+) -> AlertData {
+  AlertData::ServerDiskHealth {
+    id: server.id.clone(),
+    name: server.name.clone(),
+    region: optional_string(&server.config.region),
+    device: disk.device.clone(),
+    model: disk.model.clone(),
+    serial: disk.serial.clone(),
+    problems: problems.to_vec(),
+  }
+}
+
+fn zfs_alert_data(server: &Server, pool: &ZfsPool) -> AlertData {
+  AlertData::ServerZfsPool {
+    id: server.id.clone(),
+    name: server.name.clone(),
+    region: optional_string(&server.config.region),
+    pool: pool.name.clone(),
+    health: pool.health.clone(),
+    scan: pool.scan.clone(),
+  }
+}
+
 async fn open_new_alerts(alerts: &[(Alert, SendAlerts)]) {
   if alerts.is_empty() {
     return;
@@ -704,8 +1089,13 @@ async fn resolve_alerts(alerts: &[(Alert, SendAlerts)]) {
   }
 }
 
-async fn get_open_alerts()
--> anyhow::Result<(OpenAlertMap, OpenDiskAlertMap)> {
+async fn get_open_alerts() -> anyhow::Result<(
+  OpenAlertMap,
+  OpenDiskAlertMap,
+  OpenGpuAlertMap,
+  OpenDiskHealthAlertMap,
+  OpenZfsAlertMap,
+)> {
   let alerts = find_collect(
     &db_client().alerts,
     doc! { "resolved": false },
@@ -716,6 +1106,9 @@ async fn get_open_alerts()
 
   let mut map = OpenAlertMap::new();
   let mut disk_map = OpenDiskAlertMap::new();
+  let mut gpu_map = OpenGpuAlertMap::new();
+  let mut disk_health_map = OpenDiskHealthAlertMap::new();
+  let mut zfs_map = OpenZfsAlertMap::new();
 
   for alert in alerts {
     match &alert.data {
@@ -723,6 +1116,19 @@ async fn get_open_alerts()
         let inner = disk_map.entry(alert.target.clone()).or_default();
         inner.insert(path.to_owned(), alert);
       }
+      AlertData::ServerGpu { vendor, index, .. } => {
+        let inner = gpu_map.entry(alert.target.clone()).or_default();
+        inner.insert(gpu_id(*vendor, *index), alert);
+      }
+      AlertData::ServerDiskHealth { device, .. } => {
+        let inner =
+          disk_health_map.entry(alert.target.clone()).or_default();
+        inner.insert(device.clone(), alert);
+      }
+      AlertData::ServerZfsPool { pool, .. } => {
+        let inner = zfs_map.entry(alert.target.clone()).or_default();
+        inner.insert(pool.clone(), alert);
+      }
       _ => {
         let inner = map.entry(alert.target.clone()).or_default();
         inner.insert(alert.data.extract_variant(), alert);
@@ -730,5 +1136,5 @@ async fn get_open_alerts()
     }
   }
 
-  Ok((map, disk_map))
+  Ok((map, disk_map, gpu_map, disk_health_map, zfs_map))
 }
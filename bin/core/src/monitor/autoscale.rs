@@ -0,0 +1,182 @@
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use cache::CloneCache;
+use formatting::format_serror;
+use komodo_client::entities::{
+  Operation,
+  docker::container::{ContainerListItem, ContainerStateStatusEnum},
+  komodo_timestamp,
+  server::Server,
+  stack::{Stack, StackServiceAutoscale},
+  user::autoscale_user,
+};
+use periphery_client::api::compose::ComposeScale;
+
+use crate::{
+  helpers::{
+    periphery_client,
+    update::{add_update, make_update, update_update},
+  },
+  stack::compose_container_match_regex,
+  state::{action_states, read_only_mode},
+};
+
+/// Unix timestamp (ms) of the last scale, by (stack id, service).
+fn last_scaled_cache() -> &'static CloneCache<(String, String), i64> {
+  static LAST_SCALED: OnceLock<CloneCache<(String, String), i64>> =
+    OnceLock::new();
+  LAST_SCALED.get_or_init(Default::default)
+}
+
+/// Evaluates the autoscale policies of the stacks on the server
+/// against the latest container stats, and scales the services
+/// which are outside their target.
+pub async fn autoscale_stacks(
+  server: &Server,
+  stacks: Vec<Stack>,
+  containers: &[ContainerListItem],
+) {
+  if read_only_mode().load().enabled {
+    return;
+  }
+  for stack in stacks {
+    if stack.config.autoscale.is_empty()
+      || action_states()
+        .stack
+        .get_or_insert_default(&stack.id)
+        .await
+        .busy()
+        .unwrap_or(true)
+    {
+      continue;
+    }
+    let project = stack.project_name(false);
+    for policy in &stack.config.autoscale {
+      let Some((replicas, cpu)) =
+        service_cpu(&project, &policy.service, containers)
+      else {
+        continue;
+      };
+      let desired = desired_replicas(policy, replicas, cpu);
+      if desired == replicas {
+        continue;
+      }
+      let key = (stack.id.clone(), policy.service.clone());
+      let now = komodo_timestamp();
+      if let Some(last) = last_scaled_cache().get(&key).await
+        && now - last < policy.cooldown_seconds * 1_000
+      {
+        continue;
+      }
+      last_scaled_cache().insert(key, now).await;
+      let server = server.clone();
+      let stack = stack.clone();
+      let service = policy.service.clone();
+      tokio::spawn(async move {
+        if let Err(e) = scale_service(
+          &server, &stack, service, replicas, desired, cpu,
+        )
+        .await
+        {
+          warn!("Failed to autoscale Stack {} | {e:#}", stack.name);
+        }
+      });
+    }
+  }
+}
+
+/// Returns the number of running replicas of the service,
+/// and their average cpu percent.
+/// Returns None if the service has no running replicas.
+fn service_cpu(
+  project: &str,
+  service: &str,
+  containers: &[ContainerListItem],
+) -> Option<(i64, f64)> {
+  let regex =
+    compose_container_match_regex(&format!("{project}-{service}"))
+      .inspect_err(|e| warn!("{e:#}"))
+      .ok()?;
+  let cpus = containers
+    .iter()
+    .filter(|container| {
+      container.state == ContainerStateStatusEnum::Running
+        && regex.is_match(&container.name)
+    })
+    .map(|container| {
+      container
+        .stats
+        .as_ref()
+        .and_then(|stats| {
+          stats.cpu_perc.trim_end_matches('%').parse::<f64>().ok()
+        })
+        .unwrap_or_default()
+    })
+    .collect::<Vec<_>>();
+  if cpus.is_empty() {
+    return None;
+  }
+  let replicas = cpus.len() as i64;
+  Some((replicas, cpus.into_iter().sum::<f64>() / replicas as f64))
+}
+
+/// Scales replicas proportionally to how far the average cpu
+/// is from the target, clamped to the policy min / max.
+fn desired_replicas(
+  policy: &StackServiceAutoscale,
+  replicas: i64,
+  cpu: f64,
+) -> i64 {
+  let min = policy.min_replicas.max(1);
+  let max = policy.max_replicas.max(min);
+  if policy.target_cpu_percent <= 0.0 {
+    return replicas.clamp(min, max);
+  }
+  let desired =
+    (replicas as f64 * cpu / policy.target_cpu_percent).ceil() as i64;
+  desired.clamp(min, max)
+}
+
+async fn scale_service(
+  server: &Server,
+  stack: &Stack,
+  service: String,
+  replicas: i64,
+  desired: i64,
+  cpu: f64,
+) -> anyhow::Result<()> {
+  let mut update =
+    make_update(stack, Operation::ScaleStack, autoscale_user());
+  update.in_progress();
+  update.push_simple_log(
+    "Autoscale",
+    format!(
+      "Scaling service '{service}' from {replicas} to {desired} replicas | average cpu: {cpu:.1}%"
+    ),
+  );
+  update.id = add_update(update.clone()).await?;
+
+  let res = async {
+    periphery_client(server)
+      .await?
+      .request(ComposeScale {
+        project: stack.project_name(false),
+        service,
+        replicas: desired,
+      })
+      .await
+      .context("Failed to scale service on Periphery")
+  }
+  .await;
+
+  match res {
+    Ok(log) => update.logs.push(log),
+    Err(e) => {
+      update.push_error_log("Scale", format_serror(&e.into()))
+    }
+  }
+
+  update.finalize();
+  update_update(update).await
+}
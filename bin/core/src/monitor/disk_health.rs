@@ -0,0 +1,117 @@
+use std::sync::OnceLock;
+
+use async_timing_util::{Timelength, wait_until_timelength};
+use cache::CloneCache;
+use database::mungos::find::find_collect;
+use futures::future::join_all;
+use komodo_client::entities::{
+  alert::SeverityLevel,
+  server::{Server, ServerState},
+  stats::DiskHealth,
+};
+use periphery_client::api::stats::GetDiskHealth;
+
+use crate::{
+  helpers::periphery_client,
+  state::{db_client, server_status_cache},
+};
+
+/// Server id -> The SMART health of its disks.
+/// Servers without `smartctl` aren't included.
+pub fn disk_health_cache()
+-> &'static CloneCache<String, Vec<DiskHealth>> {
+  static CACHE: OnceLock<CloneCache<String, Vec<DiskHealth>>> =
+    OnceLock::new();
+  CACHE.get_or_init(Default::default)
+}
+
+/// SMART attributes change slowly, and reading them
+/// wakes up sleeping disks, so they are polled much less
+/// often than the rest of the server status.
+pub fn spawn_disk_health_loop() {
+  tokio::spawn(async move {
+    loop {
+      wait_until_timelength(Timelength::TenMinutes, 0).await;
+      refresh_disk_health().await;
+    }
+  });
+}
+
+async fn refresh_disk_health() {
+  let servers =
+    match find_collect(&db_client().servers, None, None).await {
+      Ok(servers) => servers,
+      Err(e) => {
+        error!("Failed to get server list (disk health) | {e:#}");
+        return;
+      }
+    };
+  let futures = servers.into_iter().map(|server| async move {
+    refresh_server_disk_health(&server).await;
+  });
+  join_all(futures).await;
+}
+
+async fn refresh_server_disk_health(server: &Server) {
+  let cache = disk_health_cache();
+  let connected = server_status_cache()
+    .get(&server.id)
+    .await
+    .map(|status| status.state == ServerState::Ok)
+    .unwrap_or_default();
+  if !server.config.enabled || !connected {
+    cache.remove(&server.id).await;
+    return;
+  }
+  let res = match periphery_client(server).await {
+    Ok(periphery) => periphery.request(GetDiskHealth {}).await,
+    Err(e) => Err(e),
+  };
+  match res {
+    Ok(disks) => {
+      cache.insert(server.id.clone(), disks).await;
+    }
+    Err(e) => {
+      debug!(
+        "Failed to get disk health for server {} | {e:#}",
+        server.name
+      );
+      cache.remove(&server.id).await;
+    }
+  }
+}
+
+/// The severity of the disk health, and the problems causing it.
+/// Disks smartctl failed to read are reported Ok.
+pub fn disk_health_level(
+  disk: &DiskHealth,
+) -> (SeverityLevel, Vec<String>) {
+  let mut level = SeverityLevel::Ok;
+  let mut problems = Vec::new();
+  if disk.error.is_some() {
+    return (level, problems);
+  }
+  let mut warn = |problem: String| {
+    if level == SeverityLevel::Ok {
+      level = SeverityLevel::Warning;
+    }
+    problems.push(problem);
+  };
+  if let Some(count) = disk.reallocated_sectors.filter(|c| *c > 0) {
+    warn(format!("{count} reallocated sectors"));
+  }
+  if let Some(count) = disk.pending_sectors.filter(|c| *c > 0) {
+    warn(format!("{count} sectors pending reallocation"));
+  }
+  if let Some(count) = disk.media_errors.filter(|c| *c > 0) {
+    warn(format!("{count} media errors"));
+  }
+  if let Some(wear) = disk.wear_perc.filter(|w| *w >= 90.0) {
+    warn(format!("{wear:.0}% of rated endurance used"));
+  }
+  if !disk.passed {
+    level = SeverityLevel::Critical;
+    problems.insert(0, String::from("SMART self assessment failed"));
+  }
+  (level, problems)
+}
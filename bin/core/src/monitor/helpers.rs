@@ -11,13 +11,16 @@ use komodo_client::entities::{
     ServerHealthState, ServerState,
   },
   stack::{ComposeProject, Stack, StackState},
-  stats::{SingleDiskUsage, SystemInformation, SystemStats},
+  stats::{
+    SingleDiskUsage, SingleGpuUsage, SystemInformation, SystemStats,
+    gpu_id,
+  },
 };
 use serror::Serror;
 
 use crate::state::{
-  deployment_status_cache, repo_status_cache, server_status_cache,
-  stack_status_cache,
+  deployment_status_cache, periphery_connections, repo_status_cache,
+  server_status_cache, stack_status_cache,
 };
 
 use super::{
@@ -41,6 +44,7 @@ pub async fn insert_deployments_status_unknown(
             state: DeploymentState::Unknown,
             container: None,
             update_available: false,
+            health: None,
           },
           prev,
         }
@@ -107,6 +111,10 @@ pub async fn insert_server_status(
 ) {
   let health =
     system_stats.as_ref().map(|s| get_server_health(server, s));
+  let latency_ms = periphery_connections()
+    .get(&server.id)
+    .await
+    .and_then(|connection| connection.latency_ms());
   server_status_cache()
     .insert(
       server.id.clone(),
@@ -122,6 +130,7 @@ pub async fn insert_server_status(
         images,
         volumes,
         projects,
+        latency_ms,
         err: err.into(),
       }
       .into(),
@@ -138,6 +147,8 @@ fn get_server_health(
     mem_used_gb,
     mem_total_gb,
     disks,
+    gpus,
+    zfs_pools,
     ..
   }: &SystemStats,
 ) -> ServerHealth {
@@ -148,6 +159,10 @@ fn get_server_health(
     mem_critical,
     disk_warning,
     disk_critical,
+    gpu_mem_warning,
+    gpu_mem_critical,
+    gpu_temp_warning,
+    gpu_temp_critical,
     ..
   } = &server.config;
   let mut health = ServerHealth::default();
@@ -192,5 +207,52 @@ fn get_server_health(
     health.disks.insert(mount.clone(), state);
   }
 
+  for SingleGpuUsage {
+    index,
+    vendor,
+    mem_used_gb,
+    mem_total_gb,
+    temperature,
+    ..
+  } in gpus
+  {
+    let mem_perc = if *mem_total_gb > 0.0 {
+      100.0 * mem_used_gb / mem_total_gb
+    } else {
+      0.0
+    };
+    let temperature = temperature.unwrap_or_default();
+    // The GPU takes the worse level of its memory and temperature,
+    // and only closes the alert once both have recovered.
+    let mut state = ServerHealthState::default();
+    if mem_perc >= *gpu_mem_critical
+      || temperature >= *gpu_temp_critical
+    {
+      state.level = SeverityLevel::Critical;
+    } else if mem_perc >= *gpu_mem_warning
+      || temperature >= *gpu_temp_warning
+    {
+      state.level = SeverityLevel::Warning;
+    } else if mem_perc
+      < gpu_mem_warning - (ALERT_PERCENTAGE_THRESHOLD as f64)
+      && temperature
+        < gpu_temp_warning - (ALERT_PERCENTAGE_THRESHOLD as f64)
+    {
+      state.should_close_alert = true;
+    }
+    health.gpus.insert(gpu_id(*vendor, *index), state);
+  }
+
+  for pool in zfs_pools {
+    let mut state = ServerHealthState::default();
+    match pool.health.as_str() {
+      "ONLINE" => state.should_close_alert = true,
+      // Redundancy is lost, but the data is still available.
+      "DEGRADED" => state.level = SeverityLevel::Warning,
+      _ => state.level = SeverityLevel::Critical,
+    }
+    health.zfs_pools.insert(pool.name.clone(), state);
+  }
+
   health
 }
@@ -7,7 +7,7 @@ use futures::future::join_all;
 use helpers::insert_stacks_status_unknown;
 use komodo_client::entities::{
   build::Build,
-  deployment::{Deployment, DeploymentState},
+  deployment::{Deployment, DeploymentHealth, DeploymentState},
   docker::{
     container::ContainerListItem, image::ImageListItem,
     network::NetworkListItem, volume::VolumeListItem,
@@ -30,7 +30,7 @@ use crate::{
   monitor::{alert::check_alerts, record::record_server_stats},
   state::{
     db_client, deployment_status_cache, periphery_connections,
-    repo_status_cache,
+    repo_status_cache, server_status_cache,
   },
 };
 
@@ -40,7 +40,10 @@ use self::helpers::{
 };
 
 mod alert;
+mod autoscale;
+mod disk_health;
 mod helpers;
+mod os_updates;
 mod record;
 mod resources;
 
@@ -63,6 +66,9 @@ pub struct CachedServerStatus {
   pub images: Option<Vec<ImageListItem>>,
   pub volumes: Option<Vec<VolumeListItem>>,
   pub projects: Option<Vec<ComposeProject>>,
+  /// Round trip latency to Periphery in ms,
+  /// measured by the connection keepalive.
+  pub latency_ms: Option<u64>,
   /// Store the error in reaching periphery
   pub err: Option<serror::Serror>,
 }
@@ -74,6 +80,8 @@ pub struct CachedDeploymentStatus {
   pub state: DeploymentState,
   pub container: Option<ContainerListItem>,
   pub update_available: bool,
+  /// The latest custom health probe result, if configured.
+  pub health: Option<DeploymentHealth>,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -99,6 +107,8 @@ pub fn spawn_monitor_loop() {
     .monitoring_interval
     .try_into()
     .expect("Invalid monitoring interval");
+  disk_health::spawn_disk_health_loop();
+  os_updates::spawn_os_updates_loop();
   tokio::spawn(async move {
     refresh_server_cache(komodo_timestamp()).await;
     loop {
@@ -233,6 +243,18 @@ pub async fn update_cache_for_server(server: &Server, force: bool) {
   containers.iter_mut().for_each(|container| {
     container.server_id = Some(server.id.clone())
   });
+  let autoscale_stacks = resources
+    .stacks
+    .iter()
+    .filter(|stack| !stack.config.autoscale.is_empty())
+    .cloned()
+    .collect::<Vec<_>>();
+  let deployment_health = resources::run_deployment_health_probes(
+    &periphery,
+    &resources.deployments,
+    &containers,
+  )
+  .await;
   tokio::join!(
     resources::update_deployment_cache(
       server.name.clone(),
@@ -240,6 +262,7 @@ pub async fn update_cache_for_server(server: &Server, force: bool) {
       &containers,
       &images,
       &resources.builds,
+      deployment_health,
     ),
     resources::update_stack_cache(
       server.name.clone(),
@@ -247,7 +270,21 @@ pub async fn update_cache_for_server(server: &Server, force: bool) {
       &containers,
       &images
     ),
+    autoscale::autoscale_stacks(
+      server,
+      autoscale_stacks,
+      &containers
+    ),
   );
+  // Fill the stats records missed while Periphery
+  // was unreachable, or Core was down.
+  let reconnected = server_status_cache()
+    .get(&server.id)
+    .await
+    .is_none_or(|status| status.state != ServerState::Ok);
+  if reconnected && server.config.stats_monitoring {
+    tokio::spawn(record::backfill_server_stats(server.clone()));
+  }
   insert_server_status(
     server,
     ServerState::Ok,
@@ -0,0 +1,89 @@
+use std::sync::OnceLock;
+
+use async_timing_util::{Timelength, wait_until_timelength};
+use cache::CloneCache;
+use database::mungos::find::find_collect;
+use futures::future::join_all;
+use komodo_client::entities::{
+  server::{Server, ServerState},
+  stats::PendingOsUpdates,
+};
+use periphery_client::api::stats::GetPendingOsUpdates;
+
+use crate::{
+  helpers::periphery_client,
+  state::{db_client, server_status_cache},
+};
+
+/// Server id -> The OS package updates pending on the server.
+/// Servers which failed the check aren't included.
+pub fn os_updates_cache()
+-> &'static CloneCache<String, PendingOsUpdates> {
+  static CACHE: OnceLock<CloneCache<String, PendingOsUpdates>> =
+    OnceLock::new();
+  CACHE.get_or_init(Default::default)
+}
+
+/// Periphery caches the package manager results for
+/// `os_updates_refresh_interval`, so polling more often
+/// only picks up the refreshes sooner.
+pub fn spawn_os_updates_loop() {
+  tokio::spawn(async move {
+    loop {
+      wait_until_timelength(Timelength::FifteenMinutes, 0).await;
+      refresh_os_updates().await;
+    }
+  });
+}
+
+async fn refresh_os_updates() {
+  let servers =
+    match find_collect(&db_client().servers, None, None).await {
+      Ok(servers) => servers,
+      Err(e) => {
+        error!("Failed to get server list (os updates) | {e:#}");
+        return;
+      }
+    };
+  let futures = servers.into_iter().map(|server| async move {
+    refresh_server_os_updates(&server).await;
+  });
+  join_all(futures).await;
+}
+
+async fn refresh_server_os_updates(server: &Server) {
+  let cache = os_updates_cache();
+  let connected = server_status_cache()
+    .get(&server.id)
+    .await
+    .map(|status| status.state == ServerState::Ok)
+    .unwrap_or_default();
+  if !server.config.enabled || !connected {
+    cache.remove(&server.id).await;
+    return;
+  }
+  let res = match periphery_client(server).await {
+    Ok(periphery) => periphery.request(GetPendingOsUpdates {}).await,
+    Err(e) => Err(e),
+  };
+  match res {
+    Ok(updates) if updates.error.is_none() => {
+      cache.insert(server.id.clone(), updates).await;
+    }
+    Ok(updates) => {
+      debug!(
+        "Failed to check os updates for server {} | {}",
+        server.name,
+        updates.error.unwrap_or_default()
+      );
+      cache.remove(&server.id).await;
+    }
+    Err(e) => {
+      debug!(
+        "Failed to get os updates for server {} | {e:#}",
+        server.name
+      );
+      cache.remove(&server.id).await;
+    }
+  }
+}
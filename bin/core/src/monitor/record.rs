@@ -1,8 +1,28 @@
-use komodo_client::entities::stats::{
-  SystemStatsRecord, TotalDiskUsage, sum_disk_usage,
+use std::collections::HashSet;
+
+use anyhow::Context;
+use async_timing_util::{Timelength, get_timelength_in_ms};
+use database::mungos::{
+  find::find_collect,
+  mongodb::{bson::doc, options::FindOneOptions},
+};
+use komodo_client::entities::{
+  komodo_timestamp,
+  server::Server,
+  stats::{
+    ContainerIoStatsRecord, SystemStats, SystemStatsRecord,
+    TotalDiskUsage, sum_disk_usage,
+  },
+};
+use periphery_client::api::stats::GetStatsHistory;
+
+use crate::{
+  config::core_config,
+  helpers::periphery_client,
+  state::{db_client, server_status_cache},
 };
 
-use crate::state::{db_client, server_status_cache};
+use super::filter_volumes;
 
 pub async fn record_server_stats(ts: i64) {
   let status = server_status_cache().get_values().await;
@@ -11,24 +31,23 @@ pub async fn record_server_stats(ts: i64) {
     .filter_map(|status| {
       let stats = status.system_stats.as_ref()?;
 
-      let TotalDiskUsage {
-        used_gb: disk_used_gb,
-        total_gb: disk_total_gb,
-      } = sum_disk_usage(&stats.disks);
+      let containers = status
+        .containers
+        .iter()
+        .flatten()
+        .filter_map(|container| {
+          let rates = container.stats.as_ref()?.io_rates?;
+          Some(ContainerIoStatsRecord {
+            name: container.name.clone(),
+            net_rx: rates.net_rx,
+            net_tx: rates.net_tx,
+            block_read: rates.block_read,
+            block_write: rates.block_write,
+          })
+        })
+        .collect();
 
-      Some(SystemStatsRecord {
-        ts,
-        sid: status.id.clone(),
-        cpu_perc: stats.cpu_perc,
-        load_average: stats.load_average.clone(),
-        mem_total_gb: stats.mem_total_gb,
-        mem_used_gb: stats.mem_used_gb,
-        disk_total_gb,
-        disk_used_gb,
-        disks: stats.disks.clone(),
-        network_ingress_bytes: stats.network_ingress_bytes,
-        network_egress_bytes: stats.network_egress_bytes,
-      })
+      Some(stats_record(ts, status.id.clone(), stats, containers))
     })
     .collect::<Vec<_>>();
   if !records.is_empty() {
@@ -38,3 +57,131 @@ pub async fn record_server_stats(ts: i64) {
     }
   }
 }
+
+fn stats_record(
+  ts: i64,
+  sid: String,
+  stats: &SystemStats,
+  containers: Vec<ContainerIoStatsRecord>,
+) -> SystemStatsRecord {
+  let TotalDiskUsage {
+    used_gb: disk_used_gb,
+    total_gb: disk_total_gb,
+  } = sum_disk_usage(&stats.disks);
+  SystemStatsRecord {
+    ts,
+    sid,
+    cpu_perc: stats.cpu_perc,
+    cpu_cores: stats.cpu_cores.clone(),
+    cpu_steal_perc: stats.cpu_steal_perc,
+    load_average: stats.load_average.clone(),
+    mem_total_gb: stats.mem_total_gb,
+    mem_used_gb: stats.mem_used_gb,
+    disk_total_gb,
+    disk_used_gb,
+    disks: stats.disks.clone(),
+    network_ingress_bytes: stats.network_ingress_bytes,
+    network_egress_bytes: stats.network_egress_bytes,
+    containers,
+    gpus: stats.gpus.clone(),
+  }
+}
+
+/// Fills the gap in the stats records of the server left while
+/// it was unreachable, using the stats history kept by Periphery.
+pub async fn backfill_server_stats(server: Server) {
+  match backfill_server_stats_inner(&server).await {
+    Ok(0) => {}
+    Ok(count) => info!(
+      "Backfilled {count} stats records for server {}",
+      server.name
+    ),
+    Err(e) => warn!(
+      "Failed to backfill stats for server {} | {e:#}",
+      server.name
+    ),
+  }
+}
+
+async fn backfill_server_stats_inner(
+  server: &Server,
+) -> anyhow::Result<usize> {
+  let interval: Timelength = core_config()
+    .monitoring_interval
+    .try_into()
+    .context("Invalid monitoring interval")?;
+  let interval = get_timelength_in_ms(interval) as i64;
+
+  let Some(last) = db_client()
+    .stats
+    .find_one(doc! { "sid": &server.id })
+    .with_options(
+      FindOneOptions::builder().sort(doc! { "ts": -1 }).build(),
+    )
+    .await
+    .context("Failed to query database for latest stats record")?
+  else {
+    return Ok(0);
+  };
+
+  // The current slot is recorded by the monitor loop.
+  let now = komodo_timestamp();
+  let until = now - now % interval;
+  if last.ts + interval >= until {
+    return Ok(0);
+  }
+
+  let samples = periphery_client(server)
+    .await?
+    .request(GetStatsHistory { since: last.ts })
+    .await
+    .context("Failed to get stats history from Periphery")?;
+  if samples.is_empty() {
+    return Ok(0);
+  }
+
+  let existing = find_collect(
+    &db_client().stats,
+    doc! {
+      "sid": &server.id,
+      "ts": { "$gt": last.ts, "$lt": until },
+    },
+    None,
+  )
+  .await
+  .context("Failed to query database for existing stats records")?
+  .into_iter()
+  .map(|record| record.ts)
+  .collect::<HashSet<_>>();
+
+  // Each slot takes the latest sample polled within the interval
+  // before it. Slots where Periphery was also down stay empty.
+  let mut records = Vec::new();
+  let mut ts = last.ts + interval;
+  while ts < until {
+    if !existing.contains(&ts)
+      && let Some(stats) = samples.iter().rev().find(|stats| {
+        stats.refresh_ts <= ts && stats.refresh_ts > ts - interval
+      })
+    {
+      let stats = filter_volumes(server, stats.clone());
+      records.push(stats_record(
+        ts,
+        server.id.clone(),
+        &stats,
+        Vec::new(),
+      ));
+    }
+    ts += interval;
+  }
+
+  let count = records.len();
+  if count > 0 {
+    db_client()
+      .stats
+      .insert_many(records)
+      .await
+      .context("Failed to insert backfilled stats records")?;
+  }
+  Ok(count)
+}
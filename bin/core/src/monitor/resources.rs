@@ -1,16 +1,19 @@
 use std::{
-  collections::HashSet,
+  collections::{HashMap, HashSet},
   sync::{Mutex, OnceLock},
 };
 
 use anyhow::Context;
+use futures::future::join_all;
 use komodo_client::{
   api::execute::{Deploy, DeployStack},
   entities::{
     ResourceTarget,
     alert::{Alert, AlertData, SeverityLevel},
     build::Build,
-    deployment::{Deployment, DeploymentImage, DeploymentState},
+    deployment::{
+      Deployment, DeploymentHealth, DeploymentImage, DeploymentState,
+    },
     docker::{
       container::{ContainerListItem, ContainerStateStatusEnum},
       image::ImageListItem,
@@ -24,7 +27,11 @@ use komodo_client::{
 use crate::{
   alert::send_alerts,
   api::execute::{self, ExecuteRequest},
-  helpers::query::get_stack_state_from_containers,
+  helpers::{
+    health_probe::run_health_probe,
+    query::get_stack_state_from_containers,
+  },
+  periphery::PeripheryClient,
   stack::{
     compose_container_match_regex,
     services::extract_services_from_stack,
@@ -42,12 +49,37 @@ fn deployment_alert_sent_cache() -> &'static Mutex<HashSet<String>> {
   CACHE.get_or_init(Default::default)
 }
 
+/// Runs the custom health probes for the running Deployments which configure one.
+pub async fn run_deployment_health_probes(
+  periphery: &PeripheryClient,
+  deployments: &[Deployment],
+  containers: &[ContainerListItem],
+) -> HashMap<String, DeploymentHealth> {
+  let futures = deployments
+    .iter()
+    .filter(|deployment| {
+      !deployment.config.health_probe.is_none()
+        && containers.iter().any(|container| {
+          container.name == deployment.name
+            && container.state == ContainerStateStatusEnum::Running
+        })
+    })
+    .map(|deployment| async move {
+      (
+        deployment.id.clone(),
+        run_health_probe(periphery, deployment).await,
+      )
+    });
+  join_all(futures).await.into_iter().collect()
+}
+
 pub async fn update_deployment_cache(
   server_name: String,
   deployments: Vec<Deployment>,
   containers: &[ContainerListItem],
   images: &[ImageListItem],
   builds: &[Build],
+  mut health: HashMap<String, DeploymentHealth>,
 ) {
   let deployment_status_cache = deployment_status_cache();
   for deployment in deployments {
@@ -211,6 +243,7 @@ pub async fn update_deployment_cache(
         deployment.id.clone(),
         History {
           curr: CachedDeploymentStatus {
+            health: health.remove(&deployment.id),
             id: deployment.id,
             state,
             container,
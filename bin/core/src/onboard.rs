@@ -0,0 +1,150 @@
+use anyhow::{Context, anyhow};
+use axum::{
+  Router,
+  extract::Query,
+  http::{HeaderMap, HeaderValue, header::CONTENT_TYPE},
+  routing::get,
+};
+use database::mungos::mongodb::bson::doc;
+use noise::key::EncodedKeyPair;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serror::{AddStatusCode, AddStatusCodeError};
+
+use crate::{config::core_config, state::db_client};
+
+const SETUP_SCRIPT_URL: &str = "https://raw.githubusercontent.com/moghtech/komodo/main/scripts/setup-periphery.py";
+const PERIPHERY_IMAGE: &str = "ghcr.io/moghtech/komodo-periphery";
+
+pub fn router() -> Router {
+  Router::new().route("/install.sh", get(install_script))
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum InstallMode {
+  /// Installs the systemd managed binary using `setup-periphery.py`.
+  #[default]
+  Systemd,
+  /// Runs the Periphery container with `docker run`.
+  Docker,
+}
+
+#[derive(Deserialize)]
+struct InstallQuery {
+  /// The onboarding private key.
+  key: String,
+  #[serde(default)]
+  mode: InstallMode,
+  /// The Server name to connect as.
+  /// Defaults to the hostname of the host running the script.
+  #[serde(default)]
+  name: Option<String>,
+  /// Install a systemd '--user' service. Systemd mode only.
+  #[serde(default)]
+  user: bool,
+}
+
+/// Serves a shell installer for Periphery with the onboarding key
+/// and Core address baked in, while the onboarding key is valid.
+///
+/// `curl -fsSL "https://komodo.example.com/onboard/install.sh?key=O-..." | sh`
+async fn install_script(
+  Query(query): Query<InstallQuery>,
+) -> serror::Result<(HeaderMap, String)> {
+  let public_key = EncodedKeyPair::from_private_key(&query.key)
+    .context("Invalid onboarding key")
+    .status_code(StatusCode::BAD_REQUEST)?
+    .public
+    .into_inner();
+
+  let onboarding_key = db_client()
+    .onboarding_keys
+    .find_one(doc! { "public_key": &public_key })
+    .await
+    .context("Failed to query database for Server onboarding keys")?;
+
+  // Same response whether missing or invalid.
+  let Some(onboarding_key) =
+    onboarding_key.filter(|key| key.is_valid())
+  else {
+    return Err(
+      anyhow!("Onboarding key is invalid")
+        .status_code(StatusCode::UNAUTHORIZED),
+    );
+  };
+
+  let config = core_config();
+  if config.host.is_empty() {
+    return Err(
+      anyhow!(
+        "Core 'host' must be configured to generate the installer"
+      )
+      .into(),
+    );
+  }
+
+  let name = match &query.name {
+    Some(name) if !name.is_empty() => quote(name),
+    _ => String::from("\"$(hostname)\""),
+  };
+  let address = quote(&config.host);
+  let key = quote(&query.key);
+  let version = env!("CARGO_PKG_VERSION");
+
+  let install = match query.mode {
+    InstallMode::Systemd => {
+      let user = if query.user { " \\\n  --user" } else { "" };
+      format!(
+        "curl -fsSL {SETUP_SCRIPT_URL} \\
+  | python3 - --version v{version} \\
+  --core-address {address} \\
+  --connect-as \"$NAME\" \\
+  --onboarding-key {key}{user}"
+      )
+    }
+    InstallMode::Docker => format!(
+      "docker run -d --name komodo-periphery --restart unless-stopped \\
+  -e PERIPHERY_CORE_ADDRESS={address} \\
+  -e PERIPHERY_CONNECT_AS=\"$NAME\" \\
+  -e PERIPHERY_ONBOARDING_KEY={key} \\
+  -e PERIPHERY_CORE_PUBLIC_KEYS=file:/config/keys/core.pub \\
+  -e PERIPHERY_ROOT_DIRECTORY=/etc/komodo \\
+  -e PERIPHERY_INCLUDE_DISK_MOUNTS=/etc/hostname \\
+  -v komodo-periphery-keys:/config/keys \\
+  -v /var/run/docker.sock:/var/run/docker.sock \\
+  -v /proc:/proc \\
+  -v /etc/komodo:/etc/komodo \\
+  {PERIPHERY_IMAGE}:{version}"
+    ),
+  };
+
+  let script = format!(
+    "#!/bin/sh
+# Komodo Periphery installer generated by Komodo Core v{version}.
+# Onboarding key: {}
+set -e
+
+NAME={name}
+
+echo \"Installing Komodo Periphery as Server '$NAME'\"
+
+{install}
+",
+    // Keep the key name inside the comment.
+    onboarding_key.name.replace(['\n', '\r'], " ")
+  );
+
+  let mut headers = HeaderMap::new();
+  headers.insert(
+    CONTENT_TYPE,
+    HeaderValue::from_static("text/x-shellscript; charset=utf-8"),
+  );
+
+  Ok((headers, script))
+}
+
+/// Single quotes the value for use in the generated shell script.
+fn quote(value: &str) -> String {
+  format!("'{}'", value.replace('\'', "'\\''"))
+}
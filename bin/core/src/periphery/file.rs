@@ -0,0 +1,623 @@
+use std::{
+  path::{Path, PathBuf},
+  time::Duration,
+};
+
+use anyhow::{Context, anyhow};
+use bytes::Bytes;
+use encoding::{DecodeStream as _, EncodedStream};
+use futures::{StreamExt as _, stream};
+use komodo_client::entities::update::Log;
+use periphery_client::{
+  api::{
+    container::{
+      CheckpointContainer, CopyFromContainer, CopyIntoContainer,
+      RestoreContainer,
+    },
+    docker::{ExportImage, ImportImage},
+    file::{
+      BeginFilePush, CancelFilePush, FinishFilePush, PullFile,
+      PulledFile,
+    },
+  },
+  transport::{
+    EncodedTransportMessage, FILE_MESSAGE_BYTES, FileMessage,
+  },
+};
+use sha2::{Digest, Sha256};
+use transport::channel::{Sender, channel};
+use uuid::Uuid;
+
+use crate::{
+  periphery::PeripheryClient, state::periphery_connections,
+};
+
+/// Times an interrupted transfer is resumed before giving up.
+const FILE_TRANSFER_RETRIES: usize = 3;
+const FILE_TRANSFER_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+impl PeripheryClient {
+  /// Write the contents to the path on the host.
+  /// Relative paths are relative to the Periphery root directory.
+  ///
+  /// The contents are sent as File messages rather than in a JSON request,
+  /// and interrupted transfers resume where they left off.
+  #[instrument("PushFile", skip(self, path, contents), fields(server_id = self.id, size = contents.len()))]
+  pub async fn push_file(
+    &self,
+    path: impl Into<PathBuf>,
+    contents: &[u8],
+  ) -> anyhow::Result<Log> {
+    let path = path.into();
+    let channel = Uuid::new_v4();
+    let sha256 = hex::encode(Sha256::digest(contents));
+    let mut attempt = 0;
+    loop {
+      match self
+        .try_push_file(channel, &path, contents, &sha256)
+        .await
+      {
+        Ok(log) => return Ok(log),
+        Err(e) if attempt < FILE_TRANSFER_RETRIES => {
+          attempt += 1;
+          warn!(
+            "File push to {path:?} interrupted, resuming (attempt {attempt}) | {e:#}"
+          );
+          tokio::time::sleep(FILE_TRANSFER_RETRY_DELAY).await;
+        }
+        Err(e) => {
+          // Best effort cleanup of the partial file.
+          let _ = self.request(CancelFilePush { channel }).await;
+          return Err(e);
+        }
+      }
+    }
+  }
+
+  async fn try_push_file(
+    &self,
+    channel: Uuid,
+    path: &Path,
+    contents: &[u8],
+    sha256: &str,
+  ) -> anyhow::Result<Log> {
+    let connection =
+      periphery_connections().get(&self.id).await.with_context(
+        || format!("No connection found for server {}", self.id),
+      )?;
+
+    let state = self
+      .request(BeginFilePush {
+        channel,
+        path: path.to_path_buf(),
+        size: Some(contents.len() as u64),
+      })
+      .await
+      .context("Failed to begin file push")?;
+
+    let offset = usize::try_from(state.offset)
+      .ok()
+      .filter(|offset| *offset <= contents.len())
+      .context("Periphery returned invalid file offset")?;
+
+    for (i, data) in
+      contents[offset..].chunks(FILE_MESSAGE_BYTES).enumerate()
+    {
+      connection
+        .sender
+        .send_message(FileMessage {
+          channel,
+          offset: (offset + i * FILE_MESSAGE_BYTES) as u64,
+          data: Bytes::copy_from_slice(data),
+        })
+        .await
+        .context("Failed to send file contents")?;
+    }
+
+    self
+      .request(FinishFilePush {
+        channel,
+        sha256: sha256.to_string(),
+      })
+      .await
+      .context("Failed to finish file push")
+  }
+
+  /// Copy the path out of the container as a tar archive.
+  /// Interrupted copies resume where they left off.
+  #[instrument("CopyFromContainer", skip(self), fields(server_id = self.id))]
+  pub async fn copy_from_container(
+    &self,
+    container: &str,
+    path: &str,
+  ) -> anyhow::Result<Vec<u8>> {
+    self
+      .pull_with_retries(
+        &format!("{container}:{path}"),
+        |channel, offset| async move {
+          self
+            .request(CopyFromContainer {
+              container: container.to_string(),
+              path: path.to_string(),
+              channel,
+              offset,
+              dry_run: false,
+            })
+            .await
+            .map(|archive| PulledFile {
+              size: archive.size,
+              sha256: archive.sha256,
+            })
+        },
+      )
+      .await
+  }
+
+  /// List the entries which [Self::copy_from_container]
+  /// would copy, without transferring them.
+  pub async fn list_container_path(
+    &self,
+    container: &str,
+    path: &str,
+  ) -> anyhow::Result<Vec<String>> {
+    self
+      .request(CopyFromContainer {
+        container: container.to_string(),
+        path: path.to_string(),
+        channel: Uuid::new_v4(),
+        offset: 0,
+        dry_run: true,
+      })
+      .await
+      .map(|archive| archive.entries)
+  }
+
+  /// Extract the tar archive into the destination directory
+  /// in the container, optionally changing the owner and mode
+  /// of the copied entries. With `dry_run`, the returned log
+  /// lists the entries without copying them.
+  #[instrument(
+    "CopyIntoContainer",
+    skip(self, archive, owner, mode),
+    fields(server_id = self.id, size = archive.len())
+  )]
+  pub async fn copy_into_container(
+    &self,
+    container: &str,
+    destination: &str,
+    archive: &[u8],
+    owner: Option<String>,
+    mode: Option<String>,
+    dry_run: bool,
+  ) -> anyhow::Result<Vec<Log>> {
+    // Relative to the Periphery root directory.
+    let path =
+      PathBuf::from(format!("container-copy/{}.tar", Uuid::new_v4()));
+    self.push_file(&path, archive).await?;
+    self
+      .request(CopyIntoContainer {
+        container: container.to_string(),
+        archive: path,
+        destination: destination.to_string(),
+        owner,
+        mode,
+        dry_run,
+      })
+      .await
+  }
+
+  /// Checkpoint the container, and restore it from the
+  /// checkpoint on the `target` server. The container must
+  /// already be created (but not started) on the target.
+  ///
+  /// The checkpoint is transferred as File messages, staged at
+  /// the same path on both hosts. The source archive is kept,
+  /// and replaced by the next checkpoint with the same name.
+  #[instrument(
+    "MigrateCheckpoint",
+    skip(self, target),
+    fields(server_id = self.id, target_id = target.id)
+  )]
+  pub async fn migrate_checkpoint(
+    &self,
+    target: &PeripheryClient,
+    name: &str,
+    checkpoint: &str,
+  ) -> anyhow::Result<Vec<Log>> {
+    // Relative to the Periphery root directory.
+    let archive =
+      PathBuf::from(format!("checkpoints/{name}.{checkpoint}.tar"));
+    let mut logs = Vec::new();
+
+    let log = self
+      .request(CheckpointContainer {
+        name: name.to_string(),
+        checkpoint: checkpoint.to_string(),
+        leave_running: false,
+        archive: Some(archive.clone()),
+      })
+      .await
+      .context("Failed to checkpoint container")?;
+    let success = log.success;
+    logs.push(log);
+    if !success {
+      return Ok(logs);
+    }
+
+    logs.push(
+      self
+        .relay_file(target, &archive, |channel, offset| {
+          self.request(PullFile {
+            channel,
+            path: archive.clone(),
+            offset,
+          })
+        })
+        .await
+        .context("Failed to transfer checkpoint archive")?,
+    );
+
+    logs.push(
+      target
+        .request(RestoreContainer {
+          name: name.to_string(),
+          checkpoint: checkpoint.to_string(),
+          archive: Some(archive),
+        })
+        .await
+        .context("Failed to restore container")?,
+    );
+
+    Ok(logs)
+  }
+
+  /// Export the image from this server with `docker save`,
+  /// and load it on the `target` server with `docker load`.
+  /// No registry is involved, so this works for servers
+  /// without registry access.
+  #[instrument(
+    "TransferImage",
+    skip(self, target),
+    fields(server_id = self.id, target_id = target.id)
+  )]
+  pub async fn transfer_image(
+    &self,
+    target: &PeripheryClient,
+    image: &str,
+  ) -> anyhow::Result<Vec<Log>> {
+    // Relative to the Periphery root directory.
+    let archive =
+      PathBuf::from(format!("image-transfer/{}.tar", Uuid::new_v4()));
+    let mut logs = vec![
+      self
+        .relay_file(target, &archive, |channel, offset| {
+          self.request(ExportImage {
+            name: image.to_string(),
+            channel,
+            offset,
+          })
+        })
+        .await
+        .context("Failed to transfer image archive")?,
+    ];
+    logs.push(
+      target
+        .request(ImportImage { archive })
+        .await
+        .context("Failed to import image")?,
+    );
+
+    Ok(logs)
+  }
+
+  /// Transfer the volume archive made with `ArchiveVolume`
+  /// to the same path on the `target` server,
+  /// ready for `RestoreVolume` there.
+  #[instrument(
+    "TransferVolumeArchive",
+    skip(self, target),
+    fields(server_id = self.id, target_id = target.id)
+  )]
+  pub async fn transfer_volume_archive(
+    &self,
+    target: &PeripheryClient,
+    name: &str,
+  ) -> anyhow::Result<Log> {
+    // Relative to the Periphery root directory.
+    let archive = PathBuf::from(format!("volumes/{name}.tar.gz"));
+    self
+      .relay_file(target, &archive, |channel, offset| {
+        self.request(PullFile {
+          channel,
+          path: archive.clone(),
+          offset,
+        })
+      })
+      .await
+  }
+
+  /// Pulls from this server with the `request`, and pushes
+  /// to the `path` on the `target` server. Each File message is
+  /// forwarded as it arrives, so the contents are never buffered
+  /// in Core. The target verifies the sha256 of the source file.
+  ///
+  /// Interrupted transfers resume from the contents
+  /// the target has received so far.
+  async fn relay_file<F>(
+    &self,
+    target: &PeripheryClient,
+    path: &Path,
+    request: impl Fn(Uuid, u64) -> F,
+  ) -> anyhow::Result<Log>
+  where
+    F: Future<Output = anyhow::Result<PulledFile>>,
+  {
+    let pull_channel = Uuid::new_v4();
+    let push_channel = Uuid::new_v4();
+    let mut attempt = 0;
+    loop {
+      match self
+        .try_relay(target, pull_channel, push_channel, path, &request)
+        .await
+      {
+        Ok(log) => return Ok(log),
+        Err(e) if attempt < FILE_TRANSFER_RETRIES => {
+          attempt += 1;
+          warn!(
+            "File transfer to {path:?} interrupted, resuming (attempt {attempt}) | {e:#}"
+          );
+          tokio::time::sleep(FILE_TRANSFER_RETRY_DELAY).await;
+        }
+        Err(e) => {
+          // Best effort cleanup of the partial file.
+          let _ = target
+            .request(CancelFilePush {
+              channel: push_channel,
+            })
+            .await;
+          return Err(e);
+        }
+      }
+    }
+  }
+
+  async fn try_relay<F>(
+    &self,
+    target: &PeripheryClient,
+    pull_channel: Uuid,
+    push_channel: Uuid,
+    path: &Path,
+    request: impl Fn(Uuid, u64) -> F,
+  ) -> anyhow::Result<Log>
+  where
+    F: Future<Output = anyhow::Result<PulledFile>>,
+  {
+    let source_connection =
+      periphery_connections().get(&self.id).await.with_context(
+        || format!("No connection found for server {}", self.id),
+      )?;
+    let target_connection =
+      periphery_connections().get(&target.id).await.with_context(
+        || format!("No connection found for server {}", target.id),
+      )?;
+
+    let state = target
+      .request(BeginFilePush {
+        channel: push_channel,
+        path: path.to_path_buf(),
+        // Only known once the source has sent everything.
+        size: None,
+      })
+      .await
+      .context("Failed to begin file push")?;
+    let mut position = state.offset;
+
+    let (sender, mut receiver) = channel();
+    source_connection.files.insert(pull_channel, sender).await;
+    let messages =
+      EncodedStream(stream::poll_fn(|cx| receiver.poll_recv(cx)))
+        .decode_stream();
+    tokio::pin!(messages);
+
+    let request = request(pull_channel, position);
+    tokio::pin!(request);
+
+    // Forward the contents while waiting on the response,
+    // so a full channel doesn't block the connection.
+    let mut res = loop {
+      tokio::select! {
+        res = &mut request => break res,
+        Some(message) = messages.next() => {
+          if let Err(e) = forward_file_message(
+            &target_connection.sender,
+            push_channel,
+            &mut position,
+            message,
+          )
+          .await {
+            break Err(e);
+          }
+        }
+      }
+    };
+
+    // All contents are sent before the response.
+    // Closing the channel lets the rest be drained.
+    source_connection.files.remove(&pull_channel).await;
+    while let Some(message) = messages.next().await {
+      if res.is_ok()
+        && let Err(e) = forward_file_message(
+          &target_connection.sender,
+          push_channel,
+          &mut position,
+          message,
+        )
+        .await
+      {
+        res = Err(e);
+      }
+    }
+
+    let pulled = res.context("Failed to pull file")?;
+
+    if position != pulled.size {
+      return Err(anyhow!(
+        "Forwarded {position} of {} bytes",
+        pulled.size
+      ));
+    }
+
+    target
+      .request(FinishFilePush {
+        channel: push_channel,
+        sha256: pulled.sha256,
+      })
+      .await
+      .context("Failed to finish file push")
+  }
+
+  /// Repeats the pull `request` with the offset of the
+  /// contents received so far, until it completes.
+  async fn pull_with_retries<F>(
+    &self,
+    source: &str,
+    request: impl Fn(Uuid, u64) -> F,
+  ) -> anyhow::Result<Vec<u8>>
+  where
+    F: Future<Output = anyhow::Result<PulledFile>>,
+  {
+    let channel_id = Uuid::new_v4();
+    let mut contents = Vec::new();
+    let mut attempt = 0;
+    loop {
+      let request = request(channel_id, contents.len() as u64);
+      match self.try_pull(channel_id, &mut contents, request).await {
+        Ok(()) => return Ok(contents),
+        Err(e) if attempt < FILE_TRANSFER_RETRIES => {
+          attempt += 1;
+          warn!(
+            "File pull from {source} interrupted, resuming (attempt {attempt}) | {e:#}"
+          );
+          tokio::time::sleep(FILE_TRANSFER_RETRY_DELAY).await;
+        }
+        Err(e) => return Err(e),
+      }
+    }
+  }
+
+  /// Appends the received contents after `contents.len()`.
+  /// The `request` must not be polled before the channel
+  /// is registered, which futures guarantee by being lazy.
+  async fn try_pull(
+    &self,
+    channel_id: Uuid,
+    contents: &mut Vec<u8>,
+    request: impl Future<Output = anyhow::Result<PulledFile>>,
+  ) -> anyhow::Result<()> {
+    let connection =
+      periphery_connections().get(&self.id).await.with_context(
+        || format!("No connection found for server {}", self.id),
+      )?;
+
+    let (sender, mut receiver) = channel();
+    connection.files.insert(channel_id, sender).await;
+    let messages =
+      EncodedStream(stream::poll_fn(|cx| receiver.poll_recv(cx)))
+        .decode_stream();
+    tokio::pin!(messages);
+
+    tokio::pin!(request);
+
+    // Receive the contents while waiting on the response,
+    // so a full channel doesn't block the connection.
+    let mut res = loop {
+      tokio::select! {
+        res = &mut request => break res,
+        Some(message) = messages.next() => {
+          if let Err(e) = append_file_message(contents, message) {
+            break Err(e);
+          }
+        }
+      }
+    };
+
+    // All contents are sent before the response.
+    // Closing the channel lets the rest be drained.
+    connection.files.remove(&channel_id).await;
+    while let Some(message) = messages.next().await {
+      if res.is_ok()
+        && let Err(e) = append_file_message(contents, message)
+      {
+        res = Err(e);
+      }
+    }
+
+    let pulled = res.context("Failed to pull file")?;
+
+    if contents.len() as u64 != pulled.size {
+      return Err(anyhow!(
+        "Received {} of {} bytes",
+        contents.len(),
+        pulled.size
+      ));
+    }
+
+    let sha256 = hex::encode(Sha256::digest(&*contents));
+    if sha256 != pulled.sha256 {
+      // Corrupt, start over on retry.
+      contents.clear();
+      return Err(anyhow!(
+        "Checksum mismatch | expected: {} | got: {sha256}",
+        pulled.sha256
+      ));
+    }
+
+    Ok(())
+  }
+}
+
+/// Fails on a message which didn't decode, so the
+/// pull resumes from the last good offset.
+fn append_file_message(
+  contents: &mut Vec<u8>,
+  message: anyhow::Result<FileMessage>,
+) -> anyhow::Result<()> {
+  let FileMessage { offset, data, .. } = message?;
+  if offset != contents.len() as u64 {
+    return Err(anyhow!(
+      "Expected data at offset {}, got offset {offset}",
+      contents.len()
+    ));
+  }
+  contents.extend_from_slice(&data);
+  Ok(())
+}
+
+/// Forwards the pulled contents as a File message on the push
+/// `channel`, checking they continue from the `position`.
+/// Fails on a message which didn't decode, so the
+/// relay resumes from the last good offset.
+async fn forward_file_message(
+  sender: &Sender<EncodedTransportMessage>,
+  channel: Uuid,
+  position: &mut u64,
+  message: anyhow::Result<FileMessage>,
+) -> anyhow::Result<()> {
+  let FileMessage { offset, data, .. } = message?;
+  if offset != *position {
+    return Err(anyhow!(
+      "Expected data at offset {position}, got offset {offset}"
+    ));
+  }
+  let len = data.len() as u64;
+  sender
+    .send_message(FileMessage {
+      channel,
+      offset,
+      data,
+    })
+    .await
+    .context("Failed to send file contents")?;
+  *position += len;
+  Ok(())
+}
@@ -1,25 +1,33 @@
 use std::{sync::Arc, time::Duration};
 
 use anyhow::{Context, anyhow};
-use encoding::Decode as _;
-use periphery_client::api;
+use encoding::{
+  Decode as _, Encode as _, EncodedBodyMessage, EncodedResponse,
+  JsonMessage, Response,
+};
+use futures::Stream;
+use periphery_client::{
+  api::{self, OutputChunk, is_sensitive_request},
+  transport::RequestMessage,
+};
 use resolver_api::HasResponse;
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json::json;
-use transport::channel::channel;
+use transport::channel::{Priority, Receiver, channel};
 use uuid::Uuid;
 
 use crate::{
   connection::{
-    PeripheryConnection, PeripheryConnectionArgs, ResponseChannels,
-    TerminalChannels,
+    PendingRequest, PeripheryConnection, PeripheryConnectionArgs,
+    ResponseChannels, TerminalChannels,
   },
   state::periphery_connections,
 };
 
+pub mod file;
 pub mod terminal;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PeripheryClient {
   pub id: String,
   pub responses: Arc<ResponseChannels>,
@@ -29,7 +37,6 @@ pub struct PeripheryClient {
 impl PeripheryClient {
   pub async fn new(
     args: PeripheryConnectionArgs<'_>,
-    insecure_tls: bool,
   ) -> anyhow::Result<PeripheryClient> {
     let connections = periphery_connections();
 
@@ -40,9 +47,7 @@ impl PeripheryClient {
       if args.address.is_none() {
         return Err(anyhow!("Server {id} is not connected"));
       }
-      return args
-        .spawn_client_connection(id.clone(), insecure_tls)
-        .await;
+      return args.spawn_client_connection(id.clone()).await;
     };
 
     // Ensure the connection args are unchanged.
@@ -71,7 +76,7 @@ impl PeripheryClient {
       })
     } else {
       // Core -> Periphery connection
-      args.spawn_client_connection(id.clone(), insecure_tls).await
+      args.spawn_client_connection(id.clone()).await
     }
   }
 
@@ -91,6 +96,83 @@ impl PeripheryClient {
   where
     T: std::fmt::Debug + Serialize + HasResponse,
     T::Response: DeserializeOwned,
+  {
+    let mut response_receiever =
+      self.send_request(request, false).await?;
+
+    // Poll for the associated response
+    loop {
+      let message = response_receiever
+        .recv()
+        .with_timeout(Duration::from_secs(10))
+        .await?;
+
+      // Still in progress, sent to avoid timeout.
+      let Some(message) = message.decode()? else {
+        continue;
+      };
+
+      return message.decode();
+    }
+  }
+
+  /// Like [PeripheryClient::request], but Periphery also sends
+  /// the command output as it runs, for requests which support it.
+  /// The stream ends after the final response or an error.
+  pub async fn request_stream<T>(
+    &self,
+    request: T,
+  ) -> anyhow::Result<
+    impl Stream<Item = anyhow::Result<StreamResponse<T::Response>>>,
+  >
+  where
+    T: std::fmt::Debug + Serialize + HasResponse,
+    T::Response: DeserializeOwned,
+  {
+    let response_receiever = self.send_request(request, true).await?;
+    let stream = futures::stream::unfold(
+      Some(response_receiever),
+      |response_receiever| async move {
+        let mut response_receiever = response_receiever?;
+        loop {
+          let message = match response_receiever
+            .recv()
+            .with_timeout(Duration::from_secs(10))
+            .await
+          {
+            Ok(message) => message,
+            Err(e) => return Some((Err(e), None)),
+          };
+          let item = match message.decode_response() {
+            // Still in progress, sent to avoid timeout.
+            Ok(Response::Pending) => continue,
+            Ok(Response::StreamChunk(chunk)) => {
+              chunk.decode().map(StreamResponse::Chunk)
+            }
+            Ok(Response::Ok(response)) => {
+              let response =
+                response.decode().map(StreamResponse::Done);
+              return Some((response, None));
+            }
+            Ok(Response::Err(e)) | Err(e) => {
+              return Some((Err(e), None));
+            }
+          };
+          return Some((item, Some(response_receiever)));
+        }
+      },
+    );
+    Ok(stream)
+  }
+
+  /// Sends the request, returning the receiver for the responses.
+  async fn send_request<T>(
+    &self,
+    request: T,
+    stream: bool,
+  ) -> anyhow::Result<Receiver<EncodedResponse<EncodedBodyMessage>>>
+  where
+    T: std::fmt::Debug + Serialize + HasResponse,
   {
     let connection =
       periphery_connections().get(&self.id).await.with_context(
@@ -101,39 +183,63 @@ impl PeripheryClient {
     connection.bail_if_not_connected().await?;
 
     let channel_id = Uuid::new_v4();
-    let (response_sender, mut response_receiever) = channel();
+    let (response_sender, response_receiever) = channel();
     self.responses.insert(channel_id, response_sender).await;
 
-    if let Err(e) = connection
-      .sender
-      .send_request(
+    let request = if stream {
+      json!({
+        "type": T::req_type(),
+        "params": request,
+        "stream": true,
+      })
+    } else {
+      json!({
+        "type": T::req_type(),
+        "params": request
+      })
+    };
+
+    // Kept to retransmit after a reconnect, the idempotency key
+    // ensures Periphery only runs it once.
+    let request = PendingRequest {
+      message: RequestMessage::new(
         channel_id,
-        &json!({
-          "type": T::req_type(),
-          "params": request
-        }),
+        JsonMessage(&request).encode()?,
       )
-      .await
-      .context("Failed to send request over channel")
-    {
+      .with_idempotency_key(Uuid::new_v4()),
+      sensitive: is_sensitive_request(T::req_type()),
+      resent: false,
+    };
+    connection
+      .requests
+      .insert(channel_id, request.clone())
+      .await;
+
+    let res = async {
+      let message = connection.encode_request(request)?;
+      connection
+        .sender
+        .send_with_priority(message, Priority::Request)
+        .await
+        .context("Failed to send request over channel")
+    }
+    .await;
+
+    if let Err(e) = res {
+      connection.requests.remove(&channel_id).await;
       // cleanup
       self.terminals.remove(&channel_id).await;
       return Err(e);
     }
 
-    // Poll for the associated response
-    loop {
-      let message = response_receiever
-        .recv()
-        .with_timeout(Duration::from_secs(10))
-        .await?;
-
-      // Still in progress, sent to avoid timeout.
-      let Some(message) = message.decode()? else {
-        continue;
-      };
-
-      return message.decode();
-    }
+    Ok(response_receiever)
   }
 }
+
+/// An item of [PeripheryClient::request_stream].
+pub enum StreamResponse<T> {
+  /// Output sent while the request runs.
+  Chunk(OutputChunk),
+  /// The final response.
+  Done(T),
+}
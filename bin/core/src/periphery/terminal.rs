@@ -2,17 +2,19 @@ use std::{
   pin::Pin,
   sync::Arc,
   task::{self, Poll},
+  time::{Duration, Instant},
 };
 
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use bytes::Bytes;
-use cache::CloneCache;
 use futures::Stream;
 use komodo_client::api::write::TerminalRecreateMode;
 use periphery_client::{
   api::terminal::{
-    ConnectContainerAttach, ConnectContainerExec, ConnectTerminal,
-    END_OF_OUTPUT, ExecuteContainerExec, ExecuteTerminal,
+    AckExecute, ConnectContainerAttach, ConnectContainerExec,
+    ConnectTerminal, DisconnectTerminal, END_OF_OUTPUT,
+    ExecuteContainerExec, ExecuteTerminal, OUTPUT_DROPPED,
+    ResumeExecute, TailHostFile,
   },
   transport::EncodedTransportMessage,
 };
@@ -20,7 +22,8 @@ use transport::channel::{Receiver, Sender, channel};
 use uuid::Uuid;
 
 use crate::{
-  periphery::PeripheryClient, state::periphery_connections,
+  connection::PeripheryConnection, periphery::PeripheryClient,
+  state::periphery_connections,
 };
 
 impl PeripheryClient {
@@ -28,13 +31,14 @@ impl PeripheryClient {
   pub async fn connect_terminal(
     &self,
     terminal: String,
+    read_only: bool,
   ) -> anyhow::Result<(
     Uuid,
     Sender<EncodedTransportMessage>,
-    Receiver<Vec<u8>>,
+    Receiver<Bytes>,
   )> {
     tracing::trace!(
-      "request | type: ConnectTerminal | terminal name: {terminal}",
+      "request | type: ConnectTerminal | terminal name: {terminal} | read only: {read_only}",
     );
 
     let connection =
@@ -43,7 +47,10 @@ impl PeripheryClient {
       )?;
 
     let channel_id = self
-      .request(ConnectTerminal { terminal })
+      .request(ConnectTerminal {
+        terminal,
+        read_only,
+      })
       .await
       .context("Failed to create terminal connection")?;
 
@@ -70,7 +77,7 @@ impl PeripheryClient {
   ) -> anyhow::Result<(
     Uuid,
     Sender<EncodedTransportMessage>,
-    Receiver<Vec<u8>>,
+    Receiver<Bytes>,
   )> {
     tracing::trace!(
       "request | type: ConnectContainerExec | container name: {container} | shell: {shell}",
@@ -109,10 +116,11 @@ impl PeripheryClient {
     &self,
     container: String,
     recreate: TerminalRecreateMode,
+    detach_keys: Option<String>,
   ) -> anyhow::Result<(
     Uuid,
     Sender<EncodedTransportMessage>,
-    Receiver<Vec<u8>>,
+    Receiver<Bytes>,
   )> {
     tracing::trace!(
       "request | type: ConnectContainerAttach | container name: {container}",
@@ -127,6 +135,7 @@ impl PeripheryClient {
       .request(ConnectContainerAttach {
         container,
         recreate,
+        detach_keys,
       })
       .await
       .context("Failed to create container attach connection")?;
@@ -164,7 +173,7 @@ impl PeripheryClient {
     terminal: String,
     command: String,
   ) -> anyhow::Result<
-    impl Stream<Item = anyhow::Result<Vec<u8>>> + 'static,
+    impl Stream<Item = anyhow::Result<Bytes>> + 'static,
   > {
     trace!(
       "sending request | type: ExecuteTerminal | terminal name: {terminal} | command: {command}",
@@ -180,25 +189,14 @@ impl PeripheryClient {
       .await
       .context("Failed to create execute terminal connection")?;
 
-    let (terminal_sender, terminal_receiver) = channel();
-    connection
-      .terminals
-      .insert(channel_id, terminal_sender)
-      .await;
-
-    connection
-      .sender
-      .send_terminal(channel_id, Bytes::new())
-      .await
-      .context(
-        "Failed to send TerminalTrigger to begin forwarding.",
-      )?;
+    let terminal_receiver =
+      start_execute_forwarding(&connection, channel_id).await?;
 
-    Ok(ReceiverStream {
+    Ok(self.spawn_execute_receiver(
+      connection,
       channel_id,
-      receiver: terminal_receiver,
-      channels: connection.terminals.clone(),
-    })
+      terminal_receiver,
+    ))
   }
 
   /// Executes command on specified container,
@@ -241,35 +239,242 @@ impl PeripheryClient {
       .await
       .context("Failed to create execute terminal connection")?;
 
-    let (terminal_sender, terminal_receiver) = channel();
-    connection
-      .terminals
-      .insert(channel_id, terminal_sender)
-      .await;
+    let terminal_receiver =
+      start_execute_forwarding(&connection, channel_id).await?;
 
-    // Trigger forwarding to begin now that forwarding channel is ready.
-    // This is required to not miss messages.
-    connection
-      .sender
-      .send_terminal(channel_id, Bytes::new())
-      .await?;
-
-    Ok(ReceiverStream {
+    Ok(self.spawn_execute_receiver(
+      connection,
       channel_id,
-      receiver: terminal_receiver,
-      channels: connection.terminals.clone(),
-    })
+      terminal_receiver,
+    ))
+  }
+
+  /// Streams the lines of a host file in an `allowed_log_paths`
+  /// directory on the Server. With `follow`, the stream stays open
+  /// until the returned stream is dropped, which disconnects the tail.
+  #[instrument("TailHostFile", skip(self), fields(server_id = self.id))]
+  pub async fn tail_host_file(
+    &self,
+    path: String,
+    lines: u64,
+    follow: bool,
+  ) -> anyhow::Result<ReceiverStream> {
+    tracing::trace!(
+      "sending request | type: TailHostFile | path: {path} | lines: {lines} | follow: {follow}",
+    );
+
+    let connection =
+      periphery_connections().get(&self.id).await.with_context(
+        || format!("No connection found for server {}", self.id),
+      )?;
+
+    let channel_id = self
+      .request(TailHostFile {
+        path,
+        lines,
+        follow,
+      })
+      .await
+      .context("Failed to create tail host file connection")?;
+
+    let mut receiver =
+      start_execute_forwarding(&connection, channel_id).await?;
+
+    let client = self.clone();
+    let (sender, output) = channel();
+    tokio::spawn(async move {
+      let mut complete = false;
+      while let Ok(bytes) = receiver.recv().await {
+        complete = bytes == END_OF_OUTPUT.as_bytes();
+        if sender.send(bytes).await.is_err() || complete {
+          break;
+        }
+      }
+      connection.terminals.remove(&channel_id).await;
+      if !complete
+        && let Err(e) =
+          client.request(DisconnectTerminal { id: channel_id }).await
+      {
+        warn!(
+          "Failed to disconnect host file tail on server {} | {e:#}",
+          client.id
+        );
+      }
+    });
+
+    Ok(ReceiverStream { receiver: output })
+  }
+
+  /// Forwards the execution output to the returned stream.
+  /// If the connection drops mid execution, forwarding is resumed
+  /// from the last received line once the Server reconnects.
+  /// Periphery is told to release the buffered output on completion.
+  fn spawn_execute_receiver(
+    &self,
+    mut connection: Arc<PeripheryConnection>,
+    execution: Uuid,
+    mut receiver: Receiver<Bytes>,
+  ) -> ReceiverStream {
+    let client = self.clone();
+    let (sender, output) = channel();
+    tokio::spawn(async move {
+      let mut channel_id = execution;
+      let mut lines = 0;
+      let mut complete = false;
+      let mut interval = tokio::time::interval(RESUME_CHECK_INTERVAL);
+      loop {
+        let lost = tokio::select! {
+          bytes = receiver.recv() => match bytes {
+            Ok(bytes) => {
+              complete = bytes == END_OF_OUTPUT.as_bytes();
+              let bytes = match dropped_lines(&bytes) {
+                Some(dropped) => {
+                  lines += dropped;
+                  Bytes::from(format!(
+                    "[{dropped} lines of output dropped, exceeded the Periphery buffer]\n"
+                  ))
+                }
+                None if complete => bytes,
+                None => {
+                  lines += 1;
+                  bytes
+                }
+              };
+              if sender.send(bytes).await.is_err() || complete {
+                break;
+              }
+              false
+            }
+            // Closed when the connection is lost before
+            // the output completes.
+            Err(_) => true,
+          },
+          _ = interval.tick() => {
+            !connection_is_current(&client.id, &connection).await
+          }
+        };
+        if !lost {
+          continue;
+        }
+        match client
+          .resume_execute(&connection, channel_id, execution, lines)
+          .await
+        {
+          Ok((_connection, _channel_id, _receiver)) => {
+            connection = _connection;
+            channel_id = _channel_id;
+            receiver = _receiver;
+          }
+          Err(e) => {
+            warn!(
+              "Failed to resume execution {execution} on server {} | {e:#}",
+              client.id
+            );
+            break;
+          }
+        }
+      }
+      connection.terminals.remove(&channel_id).await;
+      if complete
+        && let Err(e) =
+          client.request(AckExecute { channel: execution }).await
+      {
+        warn!(
+          "Failed to acknowledge execution {execution} on server {} | {e:#}",
+          client.id
+        );
+      }
+    });
+    ReceiverStream { receiver: output }
+  }
+
+  /// Waits for the Server to reconnect, then resumes
+  /// forwarding the execution output on a new channel.
+  async fn resume_execute(
+    &self,
+    connection: &PeripheryConnection,
+    channel_id: Uuid,
+    execution: Uuid,
+    from_line: usize,
+  ) -> anyhow::Result<(Arc<PeripheryConnection>, Uuid, Receiver<Bytes>)>
+  {
+    connection.terminals.remove(&channel_id).await;
+
+    let start = Instant::now();
+    let connection = loop {
+      if let Some(connection) =
+        periphery_connections().get(&self.id).await
+        && connection.connected()
+      {
+        break connection;
+      }
+      if start.elapsed() > RESUME_TIMEOUT {
+        return Err(anyhow!(
+          "Server did not reconnect within {}s",
+          RESUME_TIMEOUT.as_secs()
+        ));
+      }
+      tokio::time::sleep(RESUME_CHECK_INTERVAL).await;
+    };
+
+    let channel_id = self
+      .request(ResumeExecute {
+        channel: execution,
+        from_line,
+      })
+      .await
+      .context("Failed to resume execution")?;
+
+    let receiver =
+      start_execute_forwarding(&connection, channel_id).await?;
+
+    Ok((connection, channel_id, receiver))
   }
 }
 
-pub struct ReceiverStream {
+const RESUME_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+const RESUME_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Whether the connection is still the active,
+/// connected connection for the server.
+async fn connection_is_current(
+  server_id: &String,
+  connection: &Arc<PeripheryConnection>,
+) -> bool {
+  connection.connected()
+    && periphery_connections()
+      .get(server_id)
+      .await
+      .is_some_and(|current| Arc::ptr_eq(&current, connection))
+}
+
+async fn start_execute_forwarding(
+  connection: &PeripheryConnection,
   channel_id: Uuid,
-  channels: Arc<CloneCache<Uuid, Sender<Vec<u8>>>>,
-  receiver: Receiver<Vec<u8>>,
+) -> anyhow::Result<Receiver<Bytes>> {
+  let (terminal_sender, terminal_receiver) = channel();
+  connection
+    .terminals
+    .insert(channel_id, terminal_sender)
+    .await;
+
+  // Trigger forwarding to begin now that forwarding channel is ready.
+  // This is required to not miss messages.
+  connection
+    .sender
+    .send_terminal(channel_id, Bytes::new())
+    .await
+    .context("Failed to send TerminalTrigger to begin forwarding.")?;
+
+  Ok(terminal_receiver)
+}
+
+pub struct ReceiverStream {
+  receiver: Receiver<Bytes>,
 }
 
 impl Stream for ReceiverStream {
-  type Item = anyhow::Result<Vec<u8>>;
+  type Item = anyhow::Result<Bytes>;
   fn poll_next(
     mut self: Pin<&mut Self>,
     cx: &mut task::Context<'_>,
@@ -278,27 +483,22 @@ impl Stream for ReceiverStream {
       Poll::Ready(Some(bytes))
         if bytes == END_OF_OUTPUT.as_bytes() =>
       {
-        self.cleanup();
         Poll::Ready(None)
       }
       Poll::Ready(Some(bytes)) => Poll::Ready(Some(Ok(bytes))),
-      // Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
-      Poll::Ready(None) => {
-        self.cleanup();
-        Poll::Ready(None)
-      }
+      Poll::Ready(None) => Poll::Ready(None),
       Poll::Pending => Poll::Pending,
     }
   }
 }
 
-impl ReceiverStream {
-  fn cleanup(&self) {
-    // Not the prettiest but it should be fine
-    let channels = self.channels.clone();
-    let id = self.channel_id;
-    tokio::spawn(async move {
-      channels.remove(&id).await;
-    });
-  }
+/// The number of lines Periphery dropped from the execution buffer,
+/// if this is an [OUTPUT_DROPPED] line.
+fn dropped_lines(bytes: &[u8]) -> Option<usize> {
+  std::str::from_utf8(bytes)
+    .ok()?
+    .strip_prefix(OUTPUT_DROPPED)?
+    .trim()
+    .parse()
+    .ok()
 }
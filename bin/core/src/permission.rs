@@ -35,6 +35,13 @@ pub async fn get_check_permissions<T: KomodoResource>(
     return Ok(resource);
   }
 
+  if !in_user_organization(user, &resource.organization) {
+    return Err(anyhow!(
+      "{} is not in the user's Organization",
+      T::resource_type()
+    ));
+  }
+
   let user_permissions =
     get_user_permission_on_resource::<T>(user, &resource.id).await?;
 
@@ -80,6 +87,11 @@ pub fn get_user_permission_on_resource<'a, T: KomodoResource>(
 
     let resource_type = T::resource_type();
     let resource = get::<T>(resource_id).await?;
+
+    // No permissions on resources outside the user's Organization
+    if !in_user_organization(user, &resource.organization) {
+      return Ok(PermissionLevel::None.into());
+    }
     let initial_specific = if let Some(additional_target) =
       T::inherit_specific_permissions_from(&resource)
       // Ensure target is actually assigned
@@ -162,9 +174,53 @@ pub fn get_user_permission_on_resource<'a, T: KomodoResource>(
   })
 }
 
+/// Whether an item with the given organization is visible to the user.
+/// Users without an Organization are not scoped.
+pub fn in_user_organization(user: &User, organization: &str) -> bool {
+  user.admin
+    || user.organization.is_empty()
+    || user.organization == organization
+}
+
 /// Returns None if still no need to filter by resource id (eg transparent mode, group membership with all access).
+/// Non admin users in an Organization are always filtered to resources in the Organization.
 pub async fn get_resource_ids_for_user<T: KomodoResource>(
   user: &User,
+) -> anyhow::Result<Option<Vec<String>>> {
+  let ids = get_unscoped_resource_ids_for_user::<T>(user).await?;
+
+  if user.admin || user.organization.is_empty() {
+    return Ok(ids);
+  }
+
+  let organization_ids = find_collect(
+    T::coll(),
+    doc! { "organization": &user.organization },
+    None,
+  )
+  .await
+  .with_context(|| {
+    format!(
+      "failed to query {} in Organization on db",
+      T::resource_type()
+    )
+  })?
+  .into_iter()
+  .map(|resource| resource.id);
+
+  let ids = match ids {
+    Some(ids) => {
+      let ids = ids.into_iter().collect::<HashSet<_>>();
+      organization_ids.filter(|id| ids.contains(id)).collect()
+    }
+    None => organization_ids.collect(),
+  };
+
+  Ok(Some(ids))
+}
+
+async fn get_unscoped_resource_ids_for_user<T: KomodoResource>(
+  user: &User,
 ) -> anyhow::Result<Option<Vec<String>>> {
   // Check admin or transparent mode
   if user.admin || core_config().transparent_mode {
@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use database::mungos::mongodb::Collection;
 use formatting::format_serror;
 use indexmap::IndexSet;
@@ -58,6 +58,7 @@ impl super::KomodoResource for Deployment {
       SpecificPermission::Inspect,
       SpecificPermission::Logs,
       SpecificPermission::Terminal,
+      SpecificPermission::ContainerAttach,
     ]
     .into_iter()
     .collect()
@@ -140,6 +141,7 @@ impl super::KomodoResource for Deployment {
         update_available,
         server_id: deployment.config.server_id,
         build_id,
+        health: status.as_ref().and_then(|s| s.curr.health.clone()),
       },
     }
   }
@@ -342,6 +344,25 @@ async fn validate_config(
       version: *version,
     });
   }
+  if let Some(inherit) = &config.inherit
+    && !inherit.is_empty()
+  {
+    let template = get_check_permissions::<Deployment>(
+      inherit,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await
+    .context("Cannot inherit from this Deployment")?;
+    if !template.template {
+      return Err(anyhow!(
+        "Cannot inherit from Deployment {}, it is not a template",
+        template.name
+      ));
+    }
+    // in case it comes in as name
+    config.inherit = Some(template.id);
+  }
   if let Some(volumes) = &config.volumes {
     conversions_from_str(volumes).context("Invalid volumes")?;
   }
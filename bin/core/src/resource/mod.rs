@@ -20,6 +20,7 @@ use komodo_client::{
   api::{read::ExportResourcesToToml, write::CreateTag},
   entities::{
     Operation, ResourceTarget, ResourceTargetVariant,
+    event::CoreEventData,
     komodo_timestamp,
     permission::{PermissionLevel, SpecificPermission},
     resource::{AddFilters, Resource, ResourceQuery},
@@ -34,13 +35,16 @@ use partial_derive2::{Diff, MaybeNone, PartialDiff};
 use reqwest::StatusCode;
 use resolver_api::Resolve;
 use serde::{Serialize, de::DeserializeOwned};
-use serror::AddStatusCodeError;
+use serror::{AddStatusCode, AddStatusCodeError};
 
 use crate::{
   api::{read::ReadArgs, write::WriteArgs},
   helpers::{
-    create_permission, flatten_document,
+    create_permission,
+    event::emit_event,
+    flatten_document,
     query::{get_tag, id_or_name_filter},
+    quota::check_create_quota,
     update::{add_update, make_update},
   },
   permission::{get_check_permissions, get_resource_ids_for_user},
@@ -76,7 +80,9 @@ pub use refresh::{
 pub use repo::{
   refresh_repo_state_cache, spawn_repo_state_refresh_loop,
 };
-pub use server::{rotate_server_keys, update_server_public_key};
+pub use server::{
+  accept_server_tls_pin, rotate_server_keys, update_server_public_key,
+};
 
 /// Implement on each Komodo resource for common methods
 pub trait KomodoResource {
@@ -466,6 +472,10 @@ pub async fn create<T: KomodoResource>(
     );
   }
 
+  check_create_quota::<T>(user)
+    .await
+    .status_code(StatusCode::FORBIDDEN)?;
+
   let name = T::validated_name(name);
 
   if ObjectId::from_str(&name).is_ok() {
@@ -499,6 +509,7 @@ pub async fn create<T: KomodoResource>(
     description: Default::default(),
     template: Default::default(),
     tags: Default::default(),
+    organization: user.organization.clone(),
     config: config.into(),
     info: if let Some(info) = info {
       info
@@ -552,6 +563,14 @@ pub async fn create<T: KomodoResource>(
 
   refresh_all_resources_cache().await;
 
+  emit_event(
+    update.target.clone(),
+    CoreEventData::ResourceCreated {
+      name: resource.name.clone(),
+    },
+  )
+  .await;
+
   update.finalize();
   add_update(update).await?;
 
@@ -649,6 +668,14 @@ pub async fn update<T: KomodoResource>(
 
   refresh_all_resources_cache().await;
 
+  emit_event(
+    update.target.clone(),
+    CoreEventData::ResourceUpdated {
+      name: updated.name.clone(),
+    },
+  )
+  .await;
+
   update.finalize();
   add_update(update).await?;
 
@@ -745,6 +772,25 @@ pub async fn remove_tag_from_all<T: KomodoResource>(
   Ok(())
 }
 
+pub async fn set_organization<T: KomodoResource>(
+  id_or_name: &str,
+  organization: &str,
+) -> anyhow::Result<()> {
+  let resource = get::<T>(id_or_name).await?;
+  update_one_by_id(
+    T::coll(),
+    &resource.id,
+    doc! { "$set": { "organization": organization } },
+    None,
+  )
+  .await
+  .with_context(|| {
+    format!("Failed to set {} Organization on db", T::resource_type())
+  })?;
+  refresh_all_resources_cache().await;
+  Ok(())
+}
+
 // =======
 // RENAME
 // =======
@@ -797,6 +843,15 @@ pub async fn rename<T: KomodoResource>(
 
   refresh_all_resources_cache().await;
 
+  emit_event(
+    update.target.clone(),
+    CoreEventData::ResourceRenamed {
+      from: resource.name,
+      to: name,
+    },
+  )
+  .await;
+
   update.finalize();
   update.id = add_update(update.clone()).await?;
 
@@ -864,6 +919,14 @@ pub async fn delete<T: KomodoResource>(
 
   refresh_all_resources_cache().await;
 
+  emit_event(
+    target,
+    CoreEventData::ResourceDeleted {
+      name: resource.name.clone(),
+    },
+  )
+  .await;
+
   update.finalize();
   add_update(update).await?;
 
@@ -336,6 +336,16 @@ async fn validate_config(
             .await?;
           params.deployment = deployment.id;
         }
+        Execution::RollbackDeployment(params) => {
+          let deployment =
+            super::get_check_permissions::<Deployment>(
+              &params.deployment,
+              user,
+              PermissionLevel::Execute.into(),
+            )
+            .await?;
+          params.deployment = deployment.id;
+        }
         Execution::BatchDestroyDeployment(_params) => {
           if !user.admin {
             return Err(anyhow!(
@@ -454,6 +464,15 @@ async fn validate_config(
           .await?;
           params.server = server.id;
         }
+        Execution::CommitContainer(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.server = server.id;
+        }
         Execution::StartAllContainers(params) => {
           let server = super::get_check_permissions::<Server>(
             &params.server,
@@ -544,6 +563,22 @@ async fn validate_config(
           .await?;
           params.server = server.id;
         }
+        Execution::TransferImage(params) => {
+          let from_server = super::get_check_permissions::<Server>(
+            &params.from_server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.from_server = from_server.id;
+          let to_server = super::get_check_permissions::<Server>(
+            &params.to_server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.to_server = to_server.id;
+        }
         Execution::DeleteVolume(params) => {
           let server = super::get_check_permissions::<Server>(
             &params.server,
@@ -580,6 +615,15 @@ async fn validate_config(
           .await?;
           params.server = server.id;
         }
+        Execution::PruneBuildCache(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.server = server.id;
+        }
         Execution::PruneSystem(params) => {
           let server = super::get_check_permissions::<Server>(
             &params.server,
@@ -589,6 +633,78 @@ async fn validate_config(
           .await?;
           params.server = server.id;
         }
+        Execution::RunServerExtension(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.server = server.id;
+        }
+        Execution::KillSystemProcess(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.processes(),
+          )
+          .await?;
+          params.server = server.id;
+        }
+        Execution::SetSystemProcessPriority(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.processes(),
+          )
+          .await?;
+          params.server = server.id;
+        }
+        Execution::StartSystemdUnit(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.server = server.id;
+        }
+        Execution::StopSystemdUnit(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.server = server.id;
+        }
+        Execution::RestartSystemdUnit(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.server = server.id;
+        }
+        Execution::RebootServer(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.server = server.id;
+        }
+        Execution::ShutdownServer(params) => {
+          let server = super::get_check_permissions::<Server>(
+            &params.server,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.server = server.id;
+        }
         Execution::RunSync(params) => {
           let sync = super::get_check_permissions::<ResourceSync>(
             &params.sync,
@@ -719,6 +835,47 @@ async fn validate_config(
           .await?;
           params.stack = stack.id;
         }
+        Execution::ScaleStack(params) => {
+          let stack = super::get_check_permissions::<Stack>(
+            &params.stack,
+            user,
+            PermissionLevel::Execute.into(),
+          )
+          .await?;
+          params.stack = stack.id;
+        }
+        Execution::PromoteStack(params) => {
+          let from = super::get_check_permissions::<Stack>(
+            &params.from,
+            user,
+            PermissionLevel::Read.into(),
+          )
+          .await?;
+          params.from = from.id;
+          let to = super::get_check_permissions::<Stack>(
+            &params.to,
+            user,
+            PermissionLevel::Write.into(),
+          )
+          .await?;
+          params.to = to.id;
+        }
+        Execution::MigrateStack(params) => {
+          let stack = super::get_check_permissions::<Stack>(
+            &params.stack,
+            user,
+            PermissionLevel::Write.into(),
+          )
+          .await?;
+          params.stack = stack.id;
+          let server = super::get_check_permissions::<Server>(
+            &params.to_server,
+            user,
+            PermissionLevel::Read.attach(),
+          )
+          .await?;
+          params.to_server = server.id;
+        }
         Execution::BatchDestroyStack(_params) => {
           if !user.admin {
             return Err(anyhow!(
@@ -54,6 +54,7 @@ impl super::KomodoResource for Server {
       SpecificPermission::Attach,
       SpecificPermission::Logs,
       SpecificPermission::Processes,
+      SpecificPermission::ContainerAttach,
     ]
     .into_iter()
     .collect()
@@ -73,6 +74,8 @@ impl super::KomodoResource for Server {
       public_key,
       terminals_disabled,
       container_terminals_disabled,
+      allowed_requests,
+      denied_requests,
     ) = match status.as_ref().and_then(|s| s.periphery_info.as_ref())
     {
       Some(info) => (
@@ -80,8 +83,10 @@ impl super::KomodoResource for Server {
         Some(info.public_key.clone()),
         info.terminals_disabled,
         info.container_terminals_disabled,
+        info.allowed_requests.clone(),
+        info.denied_requests.clone(),
       ),
-      None => (None, None, true, true),
+      None => (None, None, true, true, Vec::new(), Vec::new()),
     };
     ServerListItem {
       name: server.name,
@@ -102,16 +107,28 @@ impl super::KomodoResource for Server {
         send_cpu_alerts: server.config.send_cpu_alerts,
         send_mem_alerts: server.config.send_mem_alerts,
         send_disk_alerts: server.config.send_disk_alerts,
+        send_gpu_alerts: server.config.send_gpu_alerts,
+        send_disk_health_alerts: server
+          .config
+          .send_disk_health_alerts,
+        send_zfs_alerts: server.config.send_zfs_alerts,
+        send_os_update_alerts: server.config.send_os_update_alerts,
         send_version_mismatch_alerts: server
           .config
           .send_version_mismatch_alerts,
         version,
+        latency_ms: status.as_ref().and_then(|s| s.latency_ms),
         public_key,
         attempted_public_key: optional_string(
           server.info.attempted_public_key,
         ),
+        attempted_tls_pin: optional_string(
+          server.info.attempted_tls_pin,
+        ),
         terminals_disabled,
         container_terminals_disabled,
+        allowed_requests,
+        denied_requests,
       },
     }
   }
@@ -283,8 +300,29 @@ pub async fn update_server_public_key(
   Ok(())
 }
 
+/// Adds the pin to `server.config.tls_pins`,
+/// and clears `server.info.attempted_tls_pin`.
+pub async fn accept_server_tls_pin(
+  server_id: &str,
+  pin: &str,
+) -> anyhow::Result<()> {
+  db_client()
+    .servers
+    .update_one(
+      doc! { "_id": ObjectId::from_str(server_id)? },
+      doc! {
+        "$addToSet": { "config.tls_pins": pin },
+        "$set": { "info.attempted_tls_pin": "" },
+      },
+    )
+    .await
+    .context("Failed to accept Server tls pin on database")?;
+  Ok(())
+}
+
 /// Rotates Periphery keys and updates
-/// `server.info.public_key` to match new public key.
+/// `server.info.public_key` to match new public key,
+/// recording the time on `server.info.keys_rotated_at`.
 /// Does so without making a specific update.
 pub async fn rotate_server_keys(
   server: &Server,
@@ -295,6 +333,16 @@ pub async fn rotate_server_keys(
     .await
     .context("Failed to rotate Periphery private key")?
     .public_key;
-  update_server_public_key(&server.id, &public_key).await?;
+  db_client()
+    .servers
+    .update_one(
+      doc! { "_id": ObjectId::from_str(&server.id)? },
+      doc! { "$set": {
+        "info.public_key": public_key,
+        "info.keys_rotated_at": komodo_timestamp(),
+      } },
+    )
+    .await
+    .context("Failed to update Server public key on database")?;
   Ok(())
 }
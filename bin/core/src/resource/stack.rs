@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use database::mungos::mongodb::Collection;
 use formatting::format_serror;
 use indexmap::IndexSet;
@@ -61,6 +61,7 @@ impl super::KomodoResource for Stack {
       SpecificPermission::Inspect,
       SpecificPermission::Logs,
       SpecificPermission::Terminal,
+      SpecificPermission::ContainerAttach,
     ]
     .into_iter()
     .collect()
@@ -346,6 +347,7 @@ impl super::KomodoResource for Stack {
       .request(ComposeExecution {
         project: stack.project_name(false),
         command: String::from("down --remove-orphans"),
+        remove_secret_files: true,
       })
       .await
     {
@@ -404,5 +406,24 @@ async fn validate_config(
     // in case it comes in as name
     config.linked_repo = Some(repo.id);
   }
+  if let Some(inherit) = &config.inherit
+    && !inherit.is_empty()
+  {
+    let template = get_check_permissions::<Stack>(
+      inherit,
+      user,
+      PermissionLevel::Read.into(),
+    )
+    .await
+    .context("Cannot inherit from this Stack")?;
+    if !template.template {
+      return Err(anyhow!(
+        "Cannot inherit from Stack {}, it is not a template",
+        template.name
+      ));
+    }
+    // in case it comes in as name
+    config.inherit = Some(template.id);
+  }
   Ok(())
 }
@@ -265,6 +265,7 @@ async fn get_resource_sync_state(
   } else if !data.resource_updates.is_empty()
     || !data.variable_updates.is_empty()
     || !data.user_group_updates.is_empty()
+    || !data.onboarding_key_updates.is_empty()
     || data.pending_deploy.to_deploy > 0
   {
     ResourceSyncState::Pending
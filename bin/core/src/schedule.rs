@@ -1,12 +1,13 @@
 use std::{
-  collections::HashMap,
-  sync::{OnceLock, RwLock},
+  collections::{HashMap, HashSet},
+  sync::{Mutex, OnceLock, RwLock},
+  time::Duration,
 };
 
 use anyhow::{Context, anyhow};
 use async_timing_util::Timelength;
-use chrono::Local;
-use croner::parser::CronParser;
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone};
+use croner::{Cron, parser::CronParser};
 use database::mungos::find::find_collect;
 use formatting::format_serror;
 use komodo_client::{
@@ -16,7 +17,9 @@ use komodo_client::{
     action::Action,
     alert::{Alert, AlertData, SeverityLevel},
     komodo_timestamp,
-    procedure::Procedure,
+    procedure::{
+      Procedure, ProcedureActionState, ScheduleOverlapPolicy,
+    },
     user::{action_user, procedure_user},
   },
 };
@@ -26,8 +29,11 @@ use crate::{
   alert::send_alerts,
   api::execute::{ExecuteArgs, ExecuteRequest},
   config::core_config,
-  helpers::update::init_execution_update,
-  state::db_client,
+  helpers::{
+    action_state::ActionState, maintenance::check_read_only,
+    update::init_execution_update,
+  },
+  state::{action_states, db_client, procedure_cancels},
 };
 
 pub fn spawn_schedule_executor() {
@@ -62,6 +68,14 @@ pub fn spawn_schedule_executor() {
                     }
                   };
 
+                  if let Err(e) = check_read_only() {
+                    warn!(
+                      "Scheduled action run on {id} skipped | {e:#}"
+                    );
+                    update_schedule(&action);
+                    return;
+                  }
+
                   if action.config.schedule_alert {
                     let alert = Alert {
                       id: Default::default(),
@@ -133,6 +147,22 @@ pub fn spawn_schedule_executor() {
                     }
                   };
 
+                  // Schedule the next run straight away, so the cadence
+                  // doesn't drift with the run duration. Overlapping runs
+                  // are handled by the procedure overlap policy.
+                  update_schedule(&procedure);
+
+                  if let Err(e) = check_read_only() {
+                    warn!(
+                      "Scheduled procedure run on {id} skipped | {e:#}"
+                    );
+                    return;
+                  }
+
+                  if !apply_overlap_policy(&procedure).await {
+                    return;
+                  }
+
                   if procedure.config.schedule_alert {
                     let alert = Alert {
                       id: Default::default(),
@@ -185,8 +215,6 @@ pub fn spawn_schedule_executor() {
                       "Scheduled procedure run on {id} failed | {e:?}"
                     );
                   }
-
-                  update_schedule(&procedure);
                 }
                 _ => unreachable!(),
               }
@@ -202,6 +230,60 @@ pub fn spawn_schedule_executor() {
   });
 }
 
+/// Applies the procedure `schedule_overlap` policy when the previous
+/// run is still in progress. Returns false if the run should be skipped.
+async fn apply_overlap_policy(procedure: &Procedure) -> bool {
+  let action_state = action_states()
+    .procedure
+    .get_or_insert_default(&procedure.id)
+    .await;
+  if !action_state.busy().unwrap_or_default() {
+    return true;
+  }
+  let id = &procedure.id;
+  match procedure.config.schedule_overlap {
+    ScheduleOverlapPolicy::Skip => {
+      info!(
+        "Scheduled procedure run on {id} skipped | previous run still in progress"
+      );
+      false
+    }
+    ScheduleOverlapPolicy::Queue => {
+      if !queued_procedures().lock().unwrap().insert(id.clone()) {
+        info!(
+          "Scheduled procedure run on {id} skipped | a run is already queued"
+        );
+        return false;
+      }
+      wait_until_idle(&action_state).await;
+      queued_procedures().lock().unwrap().remove(id);
+      true
+    }
+    ScheduleOverlapPolicy::CancelPrevious => {
+      if let Some(cancel) = procedure_cancels().get(id).await {
+        cancel.cancel();
+      }
+      wait_until_idle(&action_state).await;
+      true
+    }
+  }
+}
+
+async fn wait_until_idle(
+  action_state: &ActionState<ProcedureActionState>,
+) {
+  while action_state.busy().unwrap_or_default() {
+    tokio::time::sleep(Duration::from_secs(1)).await;
+  }
+}
+
+/// Procedure ids with a scheduled run waiting on the previous run.
+fn queued_procedures() -> &'static Mutex<HashSet<String>> {
+  static QUEUED_PROCEDURES: OnceLock<Mutex<HashSet<String>>> =
+    OnceLock::new();
+  QUEUED_PROCEDURES.get_or_init(Default::default)
+}
+
 type UnixTimestampMs = i64;
 type Schedules =
   HashMap<ResourceTarget, Result<UnixTimestampMs, String>>;
@@ -318,26 +400,91 @@ fn find_next_occurrence(
         .with_context(|| format!("English expression produced invalid CRON schedule | produced: {cron}"))?
     }
   };
-  let next =
-    match (schedule.timezone(), core_config().timezone.as_str()) {
-      ("", "") => {
-        let tz_time = chrono::Local::now().with_timezone(&Local);
-        cron
-          .find_next_occurrence(&tz_time, false)
-          .context("Failed to find next run time")?
-          .timestamp_millis()
-      }
-      ("", timezone) | (timezone, _) => {
-        let tz: chrono_tz::Tz =
-          timezone.parse().context("Failed to parse timezone")?;
-        let tz_time = chrono::Local::now().with_timezone(&tz);
-        cron
-          .find_next_occurrence(&tz_time, false)
-          .context("Failed to find next run time")?
-          .timestamp_millis()
+  let exceptions = schedule
+    .exceptions()
+    .iter()
+    .map(|exception| ScheduleException::parse(exception))
+    .collect::<anyhow::Result<Vec<_>>>()?;
+  match (schedule.timezone(), core_config().timezone.as_str()) {
+    ("", "") => {
+      let tz_time = chrono::Local::now().with_timezone(&Local);
+      find_next_non_exception(&cron, tz_time, &exceptions)
+    }
+    ("", timezone) | (timezone, _) => {
+      let tz: chrono_tz::Tz =
+        timezone.parse().context("Failed to parse timezone")?;
+      let tz_time = chrono::Local::now().with_timezone(&tz);
+      find_next_non_exception(&cron, tz_time, &exceptions)
+    }
+  }
+}
+
+/// Exceptions may skip many runs in a row, eg. an hourly
+/// schedule over a holiday, but shouldn't skip forever.
+const MAX_EXCEPTION_SKIPS: usize = 10_000;
+
+fn find_next_non_exception<Tz: TimeZone>(
+  cron: &Cron,
+  from: DateTime<Tz>,
+  exceptions: &[ScheduleException],
+) -> anyhow::Result<i64> {
+  let mut next = cron
+    .find_next_occurrence(&from, false)
+    .context("Failed to find next run time")?;
+  for _ in 0..MAX_EXCEPTION_SKIPS {
+    let date = next.date_naive();
+    if !exceptions.iter().any(|exception| exception.matches(date)) {
+      return Ok(next.timestamp_millis());
+    }
+    next = cron
+      .find_next_occurrence(&next, false)
+      .context("Failed to find next run time")?;
+  }
+  Err(anyhow!(
+    "Schedule exceptions skip more than {MAX_EXCEPTION_SKIPS} runs in a row"
+  ))
+}
+
+/// A date on which scheduled runs are skipped.
+enum ScheduleException {
+  /// `YYYY-MM-DD`
+  Date(NaiveDate),
+  /// `MM-DD`, every year
+  Yearly { month: u32, day: u32 },
+}
+
+impl ScheduleException {
+  fn parse(exception: &str) -> anyhow::Result<ScheduleException> {
+    let exception = exception.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(exception, "%Y-%m-%d")
+    {
+      return Ok(ScheduleException::Date(date));
+    }
+    let (month, day) = exception
+      .split_once('-')
+      .and_then(|(month, day)| {
+        Some((month.parse::<u32>().ok()?, day.parse::<u32>().ok()?))
+      })
+      // Validate using a leap year, so 02-29 is allowed.
+      .filter(|(month, day)| {
+        NaiveDate::from_ymd_opt(2000, *month, *day).is_some()
+      })
+      .with_context(|| {
+        format!(
+          "Invalid schedule exception '{exception}'. Expected 'YYYY-MM-DD' or 'MM-DD'"
+        )
+      })?;
+    Ok(ScheduleException::Yearly { month, day })
+  }
+
+  fn matches(&self, date: NaiveDate) -> bool {
+    match self {
+      ScheduleException::Date(exception) => *exception == date,
+      ScheduleException::Yearly { month, day } => {
+        date.month() == *month && date.day() == *day
       }
-    };
-  Ok(next)
+    }
+  }
 }
 
 pub trait HasSchedule {
@@ -346,6 +493,7 @@ pub trait HasSchedule {
   fn format(&self) -> ScheduleFormat;
   fn schedule(&self) -> &str;
   fn timezone(&self) -> &str;
+  fn exceptions(&self) -> &[String];
 }
 
 impl HasSchedule for &Procedure {
@@ -364,6 +512,9 @@ impl HasSchedule for &Procedure {
   fn timezone(&self) -> &str {
     &self.config.schedule_timezone
   }
+  fn exceptions(&self) -> &[String] {
+    &self.config.schedule_exceptions
+  }
 }
 
 impl HasSchedule for &Action {
@@ -382,4 +533,7 @@ impl HasSchedule for &Action {
   fn timezone(&self) -> &str {
     &self.config.schedule_timezone
   }
+  fn exceptions(&self) -> &[String] {
+    &[]
+  }
 }
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use database::mungos::mongodb::bson::{doc, to_document};
+use futures::future::join_all;
+use komodo_client::entities::update::Log;
+use periphery_client::transport::TransportNotice;
+
+use crate::{
+  config::core_config,
+  state::{periphery_connections, try_db_client},
+};
+
+/// Gives the connection write loops time to flush
+/// the shutdown notice before the sockets are closed.
+const NOTICE_FLUSH_MS: u64 = 500;
+
+/// Max time to wait for the connections to spill
+/// their queued requests to disk.
+const SPILLOVER_WAIT_MS: u64 = 5_000;
+
+/// Run on SIGTERM, before the process exits.
+pub async fn on_shutdown() {
+  info!("Komodo Core shutting down...");
+  tokio::join!(
+    close_periphery_connections(),
+    interrupt_in_progress_updates(),
+  );
+  info!("Komodo Core shutdown complete");
+}
+
+/// Tells each connected Periphery when to retry,
+/// then closes the sockets cleanly.
+async fn close_periphery_connections() {
+  let notice = TransportNotice::CoreShutdown {
+    retry_after_seconds: core_config().shutdown_retry_after_seconds,
+  };
+  let connections = periphery_connections().get_values().await;
+  join_all(connections.iter().filter(|c| c.connected()).map(
+    |connection| async {
+      if let Err(e) = connection.sender.send_notice(&notice).await {
+        warn!(
+          "Failed to send shutdown notice to {} | {e:#}",
+          connection.args.id
+        );
+      }
+    },
+  ))
+  .await;
+  tokio::time::sleep(Duration::from_millis(NOTICE_FLUSH_MS)).await;
+  for connection in &connections {
+    connection.cancel();
+  }
+  if core_config().transport_spillover_directory.is_none() {
+    return;
+  }
+  // Connections report disconnected after spilling.
+  let spilled = async {
+    while connections.iter().any(|c| c.connected()) {
+      tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+  };
+  if tokio::time::timeout(
+    Duration::from_millis(SPILLOVER_WAIT_MS),
+    spilled,
+  )
+  .await
+  .is_err()
+  {
+    warn!(
+      "Timed out waiting for connections to spill queued requests"
+    );
+  }
+}
+
+/// Finalize the Updates which won't be able to complete.
+async fn interrupt_in_progress_updates() {
+  let Some(db) = try_db_client() else {
+    return;
+  };
+  let log = Log::error(
+    "Komodo shutdown",
+    String::from(
+      "Interrupted by Komodo Core shutdown. If this is a build, the builder may not have been terminated.",
+    ),
+  );
+  // This static log won't fail to serialize, unwrap ok.
+  let log = to_document(&log).unwrap();
+  if let Err(e) = db
+    .updates
+    .update_many(
+      doc! { "status": "InProgress" },
+      doc! {
+        "$set": {
+          "status": "Complete",
+          "success": false,
+        },
+        "$push": {
+          "logs": log
+        }
+      },
+    )
+    .await
+  {
+    error!(
+      "Failed to interrupt in progress updates on shutdown | {e:#}"
+    )
+  }
+}
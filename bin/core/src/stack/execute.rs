@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use komodo_client::{
   api::execute::*,
   entities::{
@@ -7,7 +8,7 @@ use komodo_client::{
     user::User,
   },
 };
-use periphery_client::api::compose::*;
+use periphery_client::api::{compose::*, swarm::RemoveSwarmStack};
 
 use crate::{
   helpers::{periphery_client, update::update_update},
@@ -102,6 +103,7 @@ impl ExecuteCompose for StartStack {
       .request(ComposeExecution {
         project: stack.project_name(false),
         command: format!("start{service_args}"),
+        remove_secret_files: false,
       })
       .await
   }
@@ -120,6 +122,7 @@ impl ExecuteCompose for RestartStack {
       .request(ComposeExecution {
         project: stack.project_name(false),
         command: format!("restart{service_args}"),
+        remove_secret_files: false,
       })
       .await
   }
@@ -138,6 +141,7 @@ impl ExecuteCompose for PauseStack {
       .request(ComposeExecution {
         project: stack.project_name(false),
         command: format!("pause{service_args}"),
+        remove_secret_files: false,
       })
       .await
   }
@@ -156,6 +160,7 @@ impl ExecuteCompose for UnpauseStack {
       .request(ComposeExecution {
         project: stack.project_name(false),
         command: format!("unpause{service_args}"),
+        remove_secret_files: false,
       })
       .await
   }
@@ -175,6 +180,7 @@ impl ExecuteCompose for StopStack {
       .request(ComposeExecution {
         project: stack.project_name(false),
         command: format!("stop{maybe_timeout}{service_args}"),
+        remove_secret_files: false,
       })
       .await
   }
@@ -188,6 +194,18 @@ impl ExecuteCompose for DestroyStack {
     services: Vec<String>,
     (timeout, remove_orphans): Self::Extras,
   ) -> anyhow::Result<Log> {
+    if stack.config.swarm {
+      if !services.is_empty() {
+        return Err(anyhow!(
+          "Swarm stacks can only be destroyed as a whole"
+        ));
+      }
+      return periphery
+        .request(RemoveSwarmStack {
+          project: stack.project_name(false),
+        })
+        .await;
+    }
     let service_args = service_args(&services);
     let maybe_timeout = maybe_timeout(timeout);
     let maybe_remove_orphans = if remove_orphans {
@@ -201,6 +219,8 @@ impl ExecuteCompose for DestroyStack {
         command: format!(
           "down{maybe_timeout}{maybe_remove_orphans}{service_args}"
         ),
+        // Keep secrets in place for any remaining services.
+        remove_secret_files: services.is_empty(),
       })
       .await
   }
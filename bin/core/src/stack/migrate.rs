@@ -0,0 +1,357 @@
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use database::mungos::{
+  by_id::update_one_by_id,
+  mongodb::bson::{doc, to_bson},
+};
+use komodo_client::{
+  api::execute::{DeployStack, DestroyStack},
+  entities::{
+    komodo_timestamp,
+    server::{Server, ServerState},
+    stack::{
+      PartialStackConfig, Stack, StackMigration, StackMigrationStep,
+      StackState,
+    },
+    update::{Log, Update},
+    user::User,
+  },
+};
+use periphery_client::api::docker::{
+  ArchiveVolume, DeleteVolume, DeleteVolumeArchive, InspectVolume,
+  ListComposeVolumes, RestoreVolume,
+};
+use resolver_api::Resolve;
+
+use crate::{
+  api::execute::{ExecuteArgs, ExecuteRequest},
+  helpers::{
+    periphery_client,
+    query::{get_server_with_state, get_stack_state},
+    update::{init_execution_update, update_update},
+  },
+  monitor::update_cache_for_server,
+  resource,
+  state::db_client,
+};
+
+const VERIFY_ATTEMPTS: usize = 20;
+const VERIFY_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Runs the migration steps which aren't completed yet,
+/// persisting the progress on the Stack info after each step.
+pub async fn migrate_stack(
+  stack: Stack,
+  to_server: Server,
+  user: &User,
+  update: &mut Update,
+) -> anyhow::Result<()> {
+  let mut migration = match stack.info.migration.clone() {
+    Some(migration) if migration.to_server == to_server.id => {
+      update.push_simple_log(
+        "Resume Migration",
+        format!(
+          "Resuming migration to Server {} | Completed steps: {}",
+          to_server.name,
+          migration
+            .completed
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+        ),
+      );
+      migration
+    }
+    Some(migration) => {
+      return Err(anyhow!(
+        "Stack has an unfinished migration to Server {}. Run MigrateStack to that Server to finish it first.",
+        migration.to_server
+      ));
+    }
+    None => {
+      if stack.config.server_id.is_empty() {
+        return Err(anyhow!("Stack has no server configured"));
+      }
+      if stack.config.server_id == to_server.id {
+        return Err(anyhow!(
+          "Stack is already on Server {}",
+          to_server.name
+        ));
+      }
+      let migration = StackMigration {
+        from_server: stack.config.server_id.clone(),
+        to_server: to_server.id.clone(),
+        volumes: Vec::new(),
+        completed: Vec::new(),
+        started_at: komodo_timestamp(),
+      };
+      save_migration(&stack.id, Some(&migration)).await?;
+      migration
+    }
+  };
+
+  let (from_server, from_state) =
+    get_server_with_state(&migration.from_server).await?;
+  let (_, to_state) = get_server_with_state(&to_server.id).await?;
+  if from_state != ServerState::Ok || to_state != ServerState::Ok {
+    return Err(anyhow!(
+      "Both Servers must be reachable to migrate | {}: {from_state} | {}: {to_state}",
+      from_server.name,
+      to_server.name
+    ));
+  }
+
+  let servers = MigrationServers {
+    from: from_server,
+    to: to_server,
+  };
+
+  for step in StackMigrationStep::ALL {
+    if migration.completed.contains(&step) {
+      continue;
+    }
+    run_step(step, &stack, &servers, &mut migration, user, update)
+      .await
+      .with_context(|| {
+        format!(
+          "Migration failed at step {step}. Run MigrateStack again to resume."
+        )
+      })?;
+    migration.completed.push(step);
+    if step == StackMigrationStep::Cleanup {
+      save_migration(&stack.id, None).await?;
+    } else {
+      save_migration(&stack.id, Some(&migration)).await?;
+    }
+    update_update(update.clone()).await?;
+  }
+
+  update.push_simple_log(
+    "Migrate Stack",
+    format!(
+      "Migrated Stack {} from {} to {}",
+      stack.name, servers.from.name, servers.to.name
+    ),
+  );
+
+  Ok(())
+}
+
+struct MigrationServers {
+  from: Server,
+  to: Server,
+}
+
+async fn run_step(
+  step: StackMigrationStep,
+  stack: &Stack,
+  servers: &MigrationServers,
+  migration: &mut StackMigration,
+  user: &User,
+  update: &mut Update,
+) -> anyhow::Result<()> {
+  match step {
+    StackMigrationStep::Destroy => {
+      let child = execute_child(
+        ExecuteRequest::DestroyStack(DestroyStack {
+          stack: stack.id.clone(),
+          services: Vec::new(),
+          remove_orphans: false,
+          stop_time: None,
+        }),
+        user,
+      )
+      .await?;
+      update.logs.extend(child.logs);
+      if !child.success {
+        return Err(anyhow!(
+          "Failed to destroy Stack on Server {}",
+          servers.from.name
+        ));
+      }
+    }
+    StackMigrationStep::Archive => {
+      let periphery = periphery_client(&servers.from).await?;
+      migration.volumes = periphery
+        .request(ListComposeVolumes {
+          project: stack.project_name(false),
+        })
+        .await?;
+      if migration.volumes.is_empty() {
+        update.push_simple_log(
+          "Archive Volumes",
+          String::from("Stack has no named volumes to migrate"),
+        );
+      }
+      for name in &migration.volumes {
+        let log = periphery
+          .request(ArchiveVolume { name: name.clone() })
+          .await?;
+        push_checked(update, log)?;
+      }
+    }
+    StackMigrationStep::Transfer => {
+      let from = periphery_client(&servers.from).await?;
+      let to = periphery_client(&servers.to).await?;
+      for name in &migration.volumes {
+        let log = from
+          .transfer_volume_archive(&to, name)
+          .await
+          .with_context(|| {
+            format!("Failed to transfer archive for volume {name}")
+          })?;
+        push_checked(update, log)?;
+      }
+    }
+    StackMigrationStep::Restore => {
+      let from = periphery_client(&servers.from).await?;
+      let to = periphery_client(&servers.to).await?;
+      for name in &migration.volumes {
+        // Keep the compose labels so compose adopts the volume.
+        let labels = from
+          .request(InspectVolume { name: name.clone() })
+          .await
+          .with_context(|| {
+            format!("Failed to inspect volume {name}")
+          })?
+          .labels
+          .into_iter()
+          .filter(|(key, _)| key.starts_with("com.docker.compose."))
+          .collect();
+        let log = to
+          .request(RestoreVolume {
+            name: name.clone(),
+            labels,
+          })
+          .await?;
+        push_checked(update, log)?;
+      }
+    }
+    StackMigrationStep::SwitchServer => {
+      resource::update::<Stack>(
+        &stack.id,
+        PartialStackConfig {
+          server_id: Some(servers.to.id.clone()),
+          ..Default::default()
+        },
+        user,
+      )
+      .await?;
+      update.push_simple_log(
+        "Switch Server",
+        format!(
+          "Stack Server changed from {} to {}",
+          servers.from.name, servers.to.name
+        ),
+      );
+    }
+    StackMigrationStep::Deploy => {
+      let child = execute_child(
+        ExecuteRequest::DeployStack(DeployStack {
+          stack: stack.id.clone(),
+          services: Vec::new(),
+          stop_time: None,
+        }),
+        user,
+      )
+      .await?;
+      update.logs.extend(child.logs);
+      if !child.success {
+        return Err(anyhow!(
+          "Failed to deploy Stack on Server {}",
+          servers.to.name
+        ));
+      }
+    }
+    StackMigrationStep::Verify => {
+      let mut state = StackState::Unknown;
+      for _ in 0..VERIFY_ATTEMPTS {
+        update_cache_for_server(&servers.to, true).await;
+        let stack = resource::get::<Stack>(&stack.id).await?;
+        state = get_stack_state(&stack).await?;
+        if state == StackState::Running {
+          update.push_simple_log(
+            "Verify",
+            format!("Stack is running on {}", servers.to.name),
+          );
+          return Ok(());
+        }
+        tokio::time::sleep(VERIFY_INTERVAL).await;
+      }
+      return Err(anyhow!(
+        "Stack is {state} on {} after deploy, expected running",
+        servers.to.name
+      ));
+    }
+    StackMigrationStep::Cleanup => {
+      let from = periphery_client(&servers.from).await?;
+      let to = periphery_client(&servers.to).await?;
+      for name in &migration.volumes {
+        let log =
+          from.request(DeleteVolume { name: name.clone() }).await?;
+        push_checked(update, log)?;
+        update.logs.push(
+          from
+            .request(DeleteVolumeArchive { name: name.clone() })
+            .await?,
+        );
+        update.logs.push(
+          to.request(DeleteVolumeArchive { name: name.clone() })
+            .await?,
+        );
+      }
+      update_cache_for_server(&servers.from, true).await;
+    }
+  }
+  Ok(())
+}
+
+/// Pushes the log to the update, erroring if it failed.
+fn push_checked(update: &mut Update, log: Log) -> anyhow::Result<()> {
+  let success = log.success;
+  let stage = log.stage.clone();
+  update.logs.push(log);
+  if success {
+    Ok(())
+  } else {
+    Err(anyhow!("Failed at {stage}"))
+  }
+}
+
+/// Runs the execution with its own Update,
+/// so it shows up in the Stack's history as usual.
+async fn execute_child(
+  request: ExecuteRequest,
+  user: &User,
+) -> anyhow::Result<Update> {
+  let update = init_execution_update(&request, user).await?;
+  let args = ExecuteArgs {
+    user: user.clone(),
+    update,
+  };
+  let res = match request {
+    ExecuteRequest::DestroyStack(req) => req.resolve(&args).await,
+    ExecuteRequest::DeployStack(req) => req.resolve(&args).await,
+    _ => unreachable!(),
+  };
+  res.map_err(|e| e.error)
+}
+
+async fn save_migration(
+  id: &str,
+  migration: Option<&StackMigration>,
+) -> anyhow::Result<()> {
+  let migration = to_bson(&migration)
+    .context("Failed to serialize stack migration to bson")?;
+  update_one_by_id(
+    &db_client().stacks,
+    id,
+    doc! { "$set": { "info.migration": migration } },
+    None,
+  )
+  .await
+  .context("Failed to update stack migration on db")?;
+  Ok(())
+}
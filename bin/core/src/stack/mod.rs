@@ -13,6 +13,7 @@ use crate::{
 };
 
 pub mod execute;
+pub mod migrate;
 pub mod remote;
 pub mod services;
 
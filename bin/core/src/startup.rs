@@ -113,6 +113,7 @@ pub async fn on_startup() {
     ensure_init_user_and_resources(),
     clean_up_server_templates(),
     init_server_info(),
+    migrate_server_tls_verification(),
   );
 }
 
@@ -542,3 +543,22 @@ async fn init_server_info() {
     error!("Failed to migrate ServerInfo to v2 | {e:?}");
   }
 }
+
+/// 'insecure_tls' is replaced by 'tls_verification'.
+/// Servers which verified certificates keep verifying
+/// against the system CA roots.
+async fn migrate_server_tls_verification() {
+  if let Err(e) = db_client()
+    .servers
+    .update_many(
+      doc! {
+        "config.tls_verification": { "$exists": false },
+        "config.insecure_tls": false,
+      },
+      doc! { "$set": { "config.tls_verification": "System" } },
+    )
+    .await
+  {
+    error!("Failed to migrate Server tls verification | {e:?}");
+  }
+}
@@ -4,15 +4,19 @@ use anyhow::Context;
 use arc_swap::ArcSwap;
 use cache::CloneCache;
 use komodo_client::entities::{
-  action::ActionState, build::BuildState,
+  ReadOnlyMode, action::ActionState, build::BuildState,
   deployment::DeploymentState, procedure::ProcedureState,
   repo::RepoState, stack::StackState,
 };
+use tokio_util::sync::CancellationToken;
 
 use crate::{
   auth::jwt::JwtClient,
   config::core_config,
-  connection::PeripheryConnections,
+  connection::{
+    PeripheryConnections, shard::ConnectionShards,
+    trace::TransportTrace,
+  },
   helpers::{
     action_state::ActionStates, all_resources::AllResourcesById,
   },
@@ -30,6 +34,11 @@ pub fn db_client() -> &'static database::Client {
     .expect("db_client accessed before initialized")
 }
 
+/// For code which may run before startup completes, like shutdown.
+pub fn try_db_client() -> Option<&'static database::Client> {
+  DB_CLIENT.get()
+}
+
 /// Must be called in app startup sequence.
 pub async fn init_db_client() {
   let client = database::Client::new(&core_config().database)
@@ -59,6 +68,25 @@ pub fn periphery_connections() -> &'static PeripheryConnections {
   CONNECTIONS.get_or_init(Default::default)
 }
 
+pub fn connection_shards() -> &'static ConnectionShards {
+  static SHARDS: OnceLock<ConnectionShards> = OnceLock::new();
+  SHARDS.get_or_init(Default::default)
+}
+
+pub fn transport_trace() -> &'static TransportTrace {
+  static TRANSPORT_TRACE: OnceLock<TransportTrace> = OnceLock::new();
+  TRANSPORT_TRACE.get_or_init(Default::default)
+}
+
+/// procedure id => cancel the in progress run
+pub fn procedure_cancels()
+-> &'static CloneCache<String, CancellationToken> {
+  static PROCEDURE_CANCELS: OnceLock<
+    CloneCache<String, CancellationToken>,
+  > = OnceLock::new();
+  PROCEDURE_CANCELS.get_or_init(Default::default)
+}
+
 pub fn action_states() -> &'static ActionStates {
   static ACTION_STATES: OnceLock<ActionStates> = OnceLock::new();
   ACTION_STATES.get_or_init(ActionStates::default)
@@ -139,3 +167,11 @@ pub fn all_resources_cache() -> &'static ArcSwap<AllResourcesById> {
     OnceLock::new();
   ALL_RESOURCES.get_or_init(Default::default)
 }
+
+/// Core global read-only mode. Not persisted,
+/// Core always starts with read-only mode disabled.
+pub fn read_only_mode() -> &'static ArcSwap<ReadOnlyMode> {
+  static READ_ONLY_MODE: OnceLock<ArcSwap<ReadOnlyMode>> =
+    OnceLock::new();
+  READ_ONLY_MODE.get_or_init(Default::default)
+}
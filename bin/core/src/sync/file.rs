@@ -275,6 +275,7 @@ pub fn extend_resources(
     .extend(filter_by_tag(more.resource_syncs, match_tags));
   resources.user_groups.extend(more.user_groups);
   resources.variables.extend(more.variables);
+  resources.onboarding_keys.extend(more.onboarding_keys);
 }
 
 fn filter_by_tag<T: Default>(
@@ -14,6 +14,7 @@ use crate::resource::KomodoResource;
 pub mod deploy;
 pub mod execute;
 pub mod file;
+pub mod onboarding_keys;
 pub mod remote;
 pub mod resources;
 pub mod toml;
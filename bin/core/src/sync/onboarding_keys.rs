@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use database::mungos::find::find_collect;
+use formatting::{Color, bold, colored, muted};
+use komodo_client::{
+  api::write::{DeleteOnboardingKey, UpdateOnboardingKey},
+  entities::{
+    onboarding_key::OnboardingKey, sync::DiffData, tag::Tag,
+    toml::OnboardingKeyToml, update::Log, user::sync_user,
+  },
+};
+use resolver_api::Resolve;
+
+use crate::{
+  api::write::WriteArgs, helpers::query::get_id_to_tags,
+  state::db_client,
+};
+
+use super::toml::TOML_PRETTY_OPTIONS;
+
+pub fn onboarding_key_to_toml(
+  onboarding_key: &OnboardingKeyToml,
+) -> anyhow::Result<String> {
+  let inner =
+    toml_pretty::to_string(onboarding_key, TOML_PRETTY_OPTIONS)
+      .context("failed to serialize onboarding key to toml")?;
+  Ok(format!("[[onboarding_key]]\n{inner}"))
+}
+
+/// Gets the current onboarding keys by public key,
+/// with tag and Server ids replaced by names.
+pub async fn get_current_onboarding_keys()
+-> anyhow::Result<HashMap<String, OnboardingKeyToml>> {
+  let keys = find_collect(&db_client().onboarding_keys, None, None)
+    .await
+    .context("failed to query db for onboarding keys")?;
+  let id_to_tags = get_id_to_tags(None).await?;
+  let server_names = find_collect(&db_client().servers, None, None)
+    .await
+    .context("failed to query db for servers")?
+    .into_iter()
+    .map(|server| (server.id, server.name))
+    .collect::<HashMap<_, _>>();
+  Ok(
+    keys
+      .into_iter()
+      .map(|key| {
+        let key =
+          convert_onboarding_key(key, &id_to_tags, &server_names);
+        (key.public_key.clone(), key)
+      })
+      .collect(),
+  )
+}
+
+fn convert_onboarding_key(
+  OnboardingKey {
+    public_key,
+    enabled,
+    expires,
+    name,
+    tags,
+    copy_server,
+    create_builder,
+    max_uses,
+    single_use,
+    ..
+  }: OnboardingKey,
+  id_to_tags: &HashMap<String, Tag>,
+  server_names: &HashMap<String, String>,
+) -> OnboardingKeyToml {
+  OnboardingKeyToml {
+    public_key,
+    name,
+    enabled,
+    expires,
+    tags: tags
+      .into_iter()
+      .map(|tag| {
+        id_to_tags
+          .get(&tag)
+          .map(|tag| tag.name.clone())
+          .unwrap_or(tag)
+      })
+      .collect(),
+    copy_server: server_names
+      .get(&copy_server)
+      .cloned()
+      .unwrap_or(copy_server),
+    create_builder,
+    max_uses,
+    single_use,
+  }
+}
+
+pub async fn get_updates_for_view(
+  onboarding_keys: &[OnboardingKeyToml],
+  delete: bool,
+) -> anyhow::Result<Vec<DiffData>> {
+  let map = get_current_onboarding_keys().await?;
+
+  let mut diffs = Vec::<DiffData>::new();
+
+  if delete {
+    for onboarding_key in map.values() {
+      if !onboarding_keys
+        .iter()
+        .any(|k| k.public_key == onboarding_key.public_key)
+      {
+        diffs.push(DiffData::Delete {
+          current: onboarding_key_to_toml(onboarding_key)?,
+        });
+      }
+    }
+  }
+
+  for onboarding_key in onboarding_keys {
+    // Keys can't be created from the sync,
+    // these are reported when the sync is executed.
+    let Some(original) = map.get(&onboarding_key.public_key) else {
+      continue;
+    };
+    if original == onboarding_key {
+      continue;
+    }
+    diffs.push(DiffData::Update {
+      proposed: onboarding_key_to_toml(onboarding_key)?,
+      current: onboarding_key_to_toml(original)?,
+    });
+  }
+
+  Ok(diffs)
+}
+
+pub async fn get_updates_for_execution(
+  onboarding_keys: Vec<OnboardingKeyToml>,
+  delete: bool,
+) -> anyhow::Result<(
+  Vec<UpdateOnboardingKey>,
+  Vec<String>,
+  Vec<String>,
+)> {
+  let map = get_current_onboarding_keys().await?;
+
+  let mut to_update = Vec::<UpdateOnboardingKey>::new();
+  let mut to_delete = Vec::<String>::new();
+  let mut missing = Vec::<String>::new();
+
+  if delete {
+    for onboarding_key in map.values() {
+      if !onboarding_keys
+        .iter()
+        .any(|k| k.public_key == onboarding_key.public_key)
+      {
+        to_delete.push(onboarding_key.public_key.clone());
+      }
+    }
+  }
+
+  for onboarding_key in onboarding_keys {
+    let Some(original) = map.get(&onboarding_key.public_key) else {
+      missing.push(onboarding_key.public_key);
+      continue;
+    };
+    let update = UpdateOnboardingKey {
+      enabled: (original.enabled != onboarding_key.enabled)
+        .then_some(onboarding_key.enabled),
+      name: (original.name != onboarding_key.name)
+        .then_some(onboarding_key.name),
+      expires: (original.expires != onboarding_key.expires)
+        .then_some(onboarding_key.expires),
+      tags: (original.tags != onboarding_key.tags)
+        .then_some(onboarding_key.tags),
+      copy_server: (original.copy_server
+        != onboarding_key.copy_server)
+        .then_some(onboarding_key.copy_server),
+      create_builder: (original.create_builder
+        != onboarding_key.create_builder)
+        .then_some(onboarding_key.create_builder),
+      max_uses: (original.max_uses != onboarding_key.max_uses)
+        .then_some(onboarding_key.max_uses),
+      single_use: (original.single_use != onboarding_key.single_use)
+        .then_some(onboarding_key.single_use),
+      public_key: onboarding_key.public_key,
+    };
+    if update.is_none() {
+      continue;
+    }
+    to_update.push(update);
+  }
+
+  Ok((to_update, to_delete, missing))
+}
+
+pub async fn run_updates(
+  to_update: Vec<UpdateOnboardingKey>,
+  to_delete: Vec<String>,
+  missing: Vec<String>,
+) -> Option<Log> {
+  if to_update.is_empty()
+    && to_delete.is_empty()
+    && missing.is_empty()
+  {
+    return None;
+  }
+
+  let mut has_error = false;
+  let mut log = String::from("running updates on Onboarding Keys");
+
+  for public_key in missing {
+    log.push_str(&format!(
+      "\n{}: onboarding key '{}' not found. Create it on Core first, the sync only manages existing keys.",
+      colored("WARN", Color::Red),
+      bold(&public_key),
+    ));
+  }
+
+  for update in to_update {
+    let public_key = update.public_key.clone();
+    if let Err(e) = update
+      .resolve(&WriteArgs {
+        user: sync_user().to_owned(),
+      })
+      .await
+    {
+      has_error = true;
+      log.push_str(&format!(
+        "\n{}: failed to update onboarding key '{}' | {:#}",
+        colored("ERROR", Color::Red),
+        bold(&public_key),
+        e.error
+      ))
+    } else {
+      log.push_str(&format!(
+        "\n{}: {} onboarding key '{}'",
+        muted("INFO"),
+        colored("updated", Color::Blue),
+        bold(&public_key)
+      ))
+    };
+  }
+
+  for public_key in to_delete {
+    if let Err(e) = (DeleteOnboardingKey {
+      public_key: public_key.clone(),
+    })
+    .resolve(&WriteArgs {
+      user: sync_user().to_owned(),
+    })
+    .await
+    {
+      has_error = true;
+      log.push_str(&format!(
+        "\n{}: failed to delete onboarding key '{}' | {:#}",
+        colored("ERROR", Color::Red),
+        bold(&public_key),
+        e.error
+      ))
+    } else {
+      log.push_str(&format!(
+        "\n{}: {} onboarding key '{}'",
+        muted("INFO"),
+        colored("deleted", Color::Red),
+        bold(&public_key)
+      ))
+    }
+  }
+
+  let stage = "Update Onboarding Keys";
+  Some(if has_error {
+    Log::error(stage, log)
+  } else {
+    Log::simple(stage, log)
+  })
+}
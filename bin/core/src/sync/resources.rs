@@ -72,6 +72,13 @@ impl ResourceSyncTrait for Deployment {
       };
     }
 
+    // need to replace the inherited template id with name
+    original.inherit = resources
+      .deployments
+      .get(&original.inherit)
+      .map(|d| d.name.clone())
+      .unwrap_or_default();
+
     Ok(original.partial_diff(update))
   }
 }
@@ -96,6 +103,12 @@ impl ResourceSyncTrait for Stack {
       .get(&original.linked_repo)
       .map(|r| r.name.clone())
       .unwrap_or_default();
+    // Replace inherited template with name
+    original.inherit = resources
+      .stacks
+      .get(&original.inherit)
+      .map(|s| s.name.clone())
+      .unwrap_or_default();
 
     Ok(original.partial_diff(update))
   }
@@ -401,6 +414,13 @@ impl ResourceSyncTrait for Procedure {
               .map(|d| d.name.clone())
               .unwrap_or_default();
           }
+          Execution::RollbackDeployment(config) => {
+            config.deployment = resources
+              .deployments
+              .get(&config.deployment)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
           Execution::BatchDestroyDeployment(_config) => {}
           Execution::CloneRepo(config) => {
             config.repo = resources
@@ -475,6 +495,13 @@ impl ResourceSyncTrait for Procedure {
               .map(|d| d.name.clone())
               .unwrap_or_default();
           }
+          Execution::CommitContainer(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
           Execution::StartAllContainers(config) => {
             config.server = resources
               .servers
@@ -545,6 +572,18 @@ impl ResourceSyncTrait for Procedure {
               .map(|d| d.name.clone())
               .unwrap_or_default();
           }
+          Execution::TransferImage(config) => {
+            config.from_server = resources
+              .servers
+              .get(&config.from_server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+            config.to_server = resources
+              .servers
+              .get(&config.to_server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
           Execution::DeleteVolume(config) => {
             config.server = resources
               .servers
@@ -573,6 +612,13 @@ impl ResourceSyncTrait for Procedure {
               .map(|d| d.name.clone())
               .unwrap_or_default();
           }
+          Execution::PruneBuildCache(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
           Execution::PruneSystem(config) => {
             config.server = resources
               .servers
@@ -580,6 +626,62 @@ impl ResourceSyncTrait for Procedure {
               .map(|d| d.name.clone())
               .unwrap_or_default();
           }
+          Execution::RunServerExtension(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
+          Execution::KillSystemProcess(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
+          Execution::SetSystemProcessPriority(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
+          Execution::StartSystemdUnit(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
+          Execution::StopSystemdUnit(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
+          Execution::RestartSystemdUnit(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
+          Execution::RebootServer(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
+          Execution::ShutdownServer(config) => {
+            config.server = resources
+              .servers
+              .get(&config.server)
+              .map(|d| d.name.clone())
+              .unwrap_or_default();
+          }
           Execution::RunSync(config) => {
             config.sync = resources
               .syncs
@@ -667,6 +769,37 @@ impl ResourceSyncTrait for Procedure {
               .map(|s| s.name.clone())
               .unwrap_or_default();
           }
+          Execution::ScaleStack(config) => {
+            config.stack = resources
+              .stacks
+              .get(&config.stack)
+              .map(|s| s.name.clone())
+              .unwrap_or_default();
+          }
+          Execution::PromoteStack(config) => {
+            config.from = resources
+              .stacks
+              .get(&config.from)
+              .map(|s| s.name.clone())
+              .unwrap_or_default();
+            config.to = resources
+              .stacks
+              .get(&config.to)
+              .map(|s| s.name.clone())
+              .unwrap_or_default();
+          }
+          Execution::MigrateStack(config) => {
+            config.stack = resources
+              .stacks
+              .get(&config.stack)
+              .map(|s| s.name.clone())
+              .unwrap_or_default();
+            config.to_server = resources
+              .servers
+              .get(&config.to_server)
+              .map(|s| s.name.clone())
+              .unwrap_or_default();
+          }
           Execution::BatchDestroyStack(_config) => {}
           Execution::TestAlerter(config) => {
             config.alerter = resources
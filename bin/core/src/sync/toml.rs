@@ -191,6 +191,13 @@ impl ToToml for Stack {
         .map(|r| &r.name)
         .unwrap_or(&String::new()),
     );
+    resource.config.inherit.clone_from(
+      all
+        .stacks
+        .get(&resource.config.inherit)
+        .map(|s| &s.name)
+        .unwrap_or(&String::new()),
+    );
   }
 
   fn edit_config_object(
@@ -232,6 +239,13 @@ impl ToToml for Deployment {
           .unwrap_or(&String::new()),
       );
     }
+    resource.config.inherit.clone_from(
+      all
+        .deployments
+        .get(&resource.config.inherit)
+        .map(|d| &d.name)
+        .unwrap_or(&String::new()),
+    );
   }
 
   fn edit_config_object(
@@ -501,6 +515,15 @@ impl ToToml for Procedure {
                 .unwrap_or(&String::new()),
             )
           }
+          Execution::RollbackDeployment(exec) => {
+            exec.deployment.clone_from(
+              all
+                .deployments
+                .get(&exec.deployment)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
           Execution::BatchDestroyDeployment(_exec) => {}
           Execution::CloneRepo(exec) => exec.repo.clone_from(
             all
@@ -581,6 +604,13 @@ impl ToToml for Procedure {
                 .unwrap_or(&String::new()),
             )
           }
+          Execution::CommitContainer(exec) => exec.server.clone_from(
+            all
+              .servers
+              .get(&exec.server)
+              .map(|r| &r.name)
+              .unwrap_or(&String::new()),
+          ),
           Execution::StartAllContainers(exec) => {
             exec.server.clone_from(
               all
@@ -661,6 +691,22 @@ impl ToToml for Procedure {
               .map(|r| &r.name)
               .unwrap_or(&String::new()),
           ),
+          Execution::TransferImage(exec) => {
+            exec.from_server.clone_from(
+              all
+                .servers
+                .get(&exec.from_server)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            );
+            exec.to_server.clone_from(
+              all
+                .servers
+                .get(&exec.to_server)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
           Execution::DeleteVolume(exec) => exec.server.clone_from(
             all
               .servers
@@ -691,6 +737,13 @@ impl ToToml for Procedure {
               .map(|r| &r.name)
               .unwrap_or(&String::new()),
           ),
+          Execution::PruneBuildCache(exec) => exec.server.clone_from(
+            all
+              .servers
+              .get(&exec.server)
+              .map(|r| &r.name)
+              .unwrap_or(&String::new()),
+          ),
           Execution::PruneSystem(exec) => exec.server.clone_from(
             all
               .servers
@@ -698,6 +751,72 @@ impl ToToml for Procedure {
               .map(|r| &r.name)
               .unwrap_or(&String::new()),
           ),
+          Execution::RunServerExtension(exec) => {
+            exec.server.clone_from(
+              all
+                .servers
+                .get(&exec.server)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
+          Execution::KillSystemProcess(exec) => {
+            exec.server.clone_from(
+              all
+                .servers
+                .get(&exec.server)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
+          Execution::SetSystemProcessPriority(exec) => {
+            exec.server.clone_from(
+              all
+                .servers
+                .get(&exec.server)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
+          Execution::StartSystemdUnit(exec) => {
+            exec.server.clone_from(
+              all
+                .servers
+                .get(&exec.server)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
+          Execution::StopSystemdUnit(exec) => exec.server.clone_from(
+            all
+              .servers
+              .get(&exec.server)
+              .map(|r| &r.name)
+              .unwrap_or(&String::new()),
+          ),
+          Execution::RestartSystemdUnit(exec) => {
+            exec.server.clone_from(
+              all
+                .servers
+                .get(&exec.server)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
+          Execution::RebootServer(exec) => exec.server.clone_from(
+            all
+              .servers
+              .get(&exec.server)
+              .map(|r| &r.name)
+              .unwrap_or(&String::new()),
+          ),
+          Execution::ShutdownServer(exec) => exec.server.clone_from(
+            all
+              .servers
+              .get(&exec.server)
+              .map(|r| &r.name)
+              .unwrap_or(&String::new()),
+          ),
           Execution::RunSync(exec) => exec.sync.clone_from(
             all
               .syncs
@@ -759,6 +878,45 @@ impl ToToml for Procedure {
               .map(|r| &r.name)
               .unwrap_or(&String::new()),
           ),
+          Execution::ScaleStack(exec) => exec.stack.clone_from(
+            all
+              .stacks
+              .get(&exec.stack)
+              .map(|r| &r.name)
+              .unwrap_or(&String::new()),
+          ),
+          Execution::PromoteStack(exec) => {
+            exec.from.clone_from(
+              all
+                .stacks
+                .get(&exec.from)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            );
+            exec.to.clone_from(
+              all
+                .stacks
+                .get(&exec.to)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
+          Execution::MigrateStack(exec) => {
+            exec.stack.clone_from(
+              all
+                .stacks
+                .get(&exec.stack)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            );
+            exec.to_server.clone_from(
+              all
+                .servers
+                .get(&exec.to_server)
+                .map(|r| &r.name)
+                .unwrap_or(&String::new()),
+            )
+          }
           Execution::PauseStack(exec) => exec.stack.clone_from(
             all
               .stacks
@@ -49,6 +49,7 @@ pub async fn exec(
 
     super::handle_container_exec_terminal(
       client_socket,
+      &user,
       &server,
       container,
       shell,
@@ -64,6 +65,7 @@ pub async fn attach(
     server,
     container,
     recreate,
+    detach_keys,
   }): Query<ConnectContainerAttachQuery>,
   ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
@@ -77,7 +79,7 @@ pub async fn attach(
     let server = match get_check_permissions::<Server>(
       &server,
       &user,
-      PermissionLevel::Read.terminal(),
+      PermissionLevel::Read.container_attach(),
     )
     .await
     {
@@ -94,9 +96,11 @@ pub async fn attach(
 
     super::handle_container_attach_terminal(
       client_socket,
+      &user,
       &server,
       container,
       recreate,
+      detach_keys,
     )
     .await
   })
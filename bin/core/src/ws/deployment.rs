@@ -64,6 +64,7 @@ pub async fn exec(
 
     super::handle_container_exec_terminal(
       client_socket,
+      &user,
       &server,
       deployment.name,
       shell,
@@ -78,6 +79,7 @@ pub async fn attach(
   Query(ConnectDeploymentAttachQuery {
     deployment,
     recreate,
+    detach_keys,
   }): Query<ConnectDeploymentAttachQuery>,
   ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
@@ -91,7 +93,7 @@ pub async fn attach(
     let deployment = match get_check_permissions::<Deployment>(
       &deployment,
       &user,
-      PermissionLevel::Read.terminal(),
+      PermissionLevel::Read.container_attach(),
     )
     .await
     {
@@ -121,9 +123,11 @@ pub async fn attach(
 
     super::handle_container_attach_terminal(
       client_socket,
+      &user,
       &server,
       deployment.name,
       recreate,
+      detach_keys,
     )
     .await
   })
@@ -0,0 +1,86 @@
+use axum::{
+  extract::{WebSocketUpgrade, ws::Message},
+  response::IntoResponse,
+};
+use futures::{SinkExt, StreamExt};
+use komodo_client::ws::WsEventSubscription;
+use serde_json::json;
+use serror::serialize_error;
+use tokio::{select, sync::broadcast::error::RecvError};
+
+use crate::helpers::channel::event_channel;
+
+pub async fn handler(ws: WebSocketUpgrade) -> impl IntoResponse {
+  // get a reveiver for internal event messages.
+  let mut receiver = event_channel().receiver.resubscribe();
+
+  // handle http -> ws updgrade
+  ws.on_upgrade(|socket| async move {
+    let Some((socket, user)) = super::user_ws_login(socket).await
+    else {
+      return;
+    };
+
+    let (mut ws_sender, mut ws_reciever) = socket.split();
+
+    // Receives all events until the client sends a subscription.
+    let mut subscription = WsEventSubscription::default();
+
+    loop {
+      select! {
+        event = receiver.recv() => {
+          let event = match event {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+              warn!("Event websocket lagged, skipped {skipped} events");
+              continue;
+            }
+            Err(RecvError::Closed) => return,
+          };
+
+          if !subscription.matches(&event) {
+            continue;
+          }
+
+          // before sending every event, verify user is still valid.
+          // kill the connection is user if found to be invalid.
+          let user = match super::check_user_valid(&user.id).await {
+            Ok(user) => user,
+            Err(e) => {
+              let _ = ws_sender
+                .send(Message::text(json!({ "type": "INVALID_USER", "msg": serialize_error(&e) }).to_string()))
+                .await;
+              let _ = ws_sender.close().await;
+              return;
+            }
+          };
+
+          // Only send if user has permission on the target.
+          if super::user_can_see_target(&user, &event.target).await.is_err() {
+            continue;
+          }
+
+          let _ = ws_sender
+            .send(Message::text(serde_json::to_string(&event).unwrap()))
+            .await;
+        }
+        msg = ws_reciever.next() => {
+          match msg {
+            Some(Ok(Message::Text(msg))) => {
+              match serde_json::from_str::<WsEventSubscription>(&msg) {
+                Ok(sub) => subscription = sub,
+                Err(e) => {
+                  let _ = ws_sender
+                    .send(Message::text(json!({ "type": "INVALID_SUBSCRIPTION", "msg": format!("{e:#}") }).to_string()))
+                    .await;
+                }
+              }
+            }
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+            Some(Ok(_)) => {}
+          }
+        }
+      }
+    }
+  })
+}
@@ -1,10 +1,13 @@
 use crate::{
   auth::{auth_api_key_check_enabled, auth_jwt_check_enabled},
-  helpers::query::get_user,
+  helpers::{
+    query::{get_user, get_user_permission_on_target},
+    terminal_session::{TerminalSessionGuard, open_terminal_session},
+  },
   periphery::PeripheryClient,
   state::periphery_connections,
 };
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use axum::{
   Router,
   extract::ws::{self, WebSocket},
@@ -13,20 +16,23 @@ use axum::{
 use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
 use komodo_client::{
-  api::write::TerminalRecreateMode,
-  entities::{server::Server, user::User},
+  api::{terminal::ContainerAttachInfo, write::TerminalRecreateMode},
+  entities::{
+    ResourceTarget, permission::PermissionLevel, server::Server,
+    user::User,
+  },
   ws::WsLoginMessage,
 };
 use periphery_client::{
-  api::terminal::DisconnectTerminal,
+  api::{container::InspectContainer, terminal::DisconnectTerminal},
   transport::EncodedTransportMessage,
 };
-use tokio_util::sync::CancellationToken;
 use transport::channel::{Receiver, Sender};
 use uuid::Uuid;
 
 mod container;
 mod deployment;
+mod event;
 mod stack;
 mod terminal;
 mod update;
@@ -37,6 +43,7 @@ pub fn router() -> Router {
     .route("/periphery", get(crate::connection::server::handler))
     // User facing
     .route("/update", get(update::handler))
+    .route("/events", get(event::handler))
     .route("/terminal", get(terminal::handler))
     .route("/container/terminal", get(container::exec))
     .route("/container/terminal/attach", get(container::attach))
@@ -135,13 +142,45 @@ async fn check_user_valid(user_id: &str) -> anyhow::Result<User> {
   Ok(user)
 }
 
+async fn user_can_see_target(
+  user: &User,
+  target: &ResourceTarget,
+) -> anyhow::Result<()> {
+  if user.admin {
+    return Ok(());
+  }
+  let permission =
+    get_user_permission_on_target(user, target).await?;
+  if permission.level > PermissionLevel::None {
+    Ok(())
+  } else {
+    Err(anyhow!("user does not have permissions on {target:?}"))
+  }
+}
+
 async fn handle_container_exec_terminal(
   mut client_socket: WebSocket,
+  user: &User,
   server: &Server,
   container: String,
   shell: String,
   recreate: TerminalRecreateMode,
 ) {
+  let session = match open_terminal_session(
+    user,
+    server,
+    format!("exec: {container}"),
+  ) {
+    Ok(session) => session,
+    Err(e) => {
+      let _ = client_socket
+        .send(ws::Message::text(format!("ERROR: {e:#}")))
+        .await;
+      let _ = client_socket.close().await;
+      return;
+    }
+  };
+
   let periphery = match crate::helpers::periphery_client(server).await
   {
     Ok(periphery) => periphery,
@@ -178,6 +217,7 @@ async fn handle_container_exec_terminal(
   trace!("connected to periphery container exec websocket");
 
   forward_ws_channel(
+    session,
     periphery,
     client_socket,
     periphery_connection_id,
@@ -189,10 +229,27 @@ async fn handle_container_exec_terminal(
 
 async fn handle_container_attach_terminal(
   mut client_socket: WebSocket,
+  user: &User,
   server: &Server,
   container: String,
   recreate: TerminalRecreateMode,
+  detach_keys: Option<String>,
 ) {
+  let session = match open_terminal_session(
+    user,
+    server,
+    format!("attach: {container}"),
+  ) {
+    Ok(session) => session,
+    Err(e) => {
+      let _ = client_socket
+        .send(ws::Message::text(format!("ERROR: {e:#}")))
+        .await;
+      let _ = client_socket.close().await;
+      return;
+    }
+  };
+
   let periphery = match crate::helpers::periphery_client(server).await
   {
     Ok(periphery) => periphery,
@@ -206,11 +263,26 @@ async fn handle_container_attach_terminal(
     }
   };
 
-  trace!("connecting to periphery container exec websocket");
+  let attach_info =
+    match container_attach_info(&periphery, &container, &detach_keys)
+      .await
+    {
+      Ok(attach_info) => attach_info,
+      Err(e) => {
+        debug!("couldn't negotiate container attach | {e:#}");
+        let _ = client_socket
+          .send(ws::Message::text(format!("ERROR: {e:#}")))
+          .await;
+        let _ = client_socket.close().await;
+        return;
+      }
+    };
+
+  trace!("connecting to periphery container attach websocket");
 
   let (periphery_connection_id, periphery_sender, periphery_receiver) =
     match periphery
-      .connect_container_attach(container, recreate)
+      .connect_container_attach(container, recreate, detach_keys)
       .await
     {
       Ok(ws) => ws,
@@ -228,7 +300,14 @@ async fn handle_container_attach_terminal(
 
   trace!("connected to periphery container attach websocket");
 
+  // attach_info is plain data, serialization won't fail.
+  let attach_info = serde_json::to_string(&attach_info).unwrap();
+  let _ = client_socket
+    .send(ws::Message::text(format!("ATTACH_INFO:{attach_info}")))
+    .await;
+
   forward_ws_channel(
+    session,
     periphery,
     client_socket,
     periphery_connection_id,
@@ -238,15 +317,47 @@ async fn handle_container_attach_terminal(
   .await
 }
 
+/// Inspects the container to tell the client how its
+/// main process was started, so it can adjust the terminal.
+async fn container_attach_info(
+  periphery: &PeripheryClient,
+  container: &str,
+  detach_keys: &Option<String>,
+) -> anyhow::Result<ContainerAttachInfo> {
+  let container = periphery
+    .request(InspectContainer {
+      name: container.to_string(),
+    })
+    .await
+    .context("Failed to inspect container")?;
+  let running = container
+    .state
+    .and_then(|state| state.running)
+    .unwrap_or_default();
+  if !running {
+    return Err(anyhow!("Cannot attach to a stopped container"));
+  }
+  let config = container.config.unwrap_or_default();
+  Ok(ContainerAttachInfo {
+    tty: config.tty.unwrap_or_default(),
+    stdin: config.open_stdin.unwrap_or_default(),
+    detach_keys: detach_keys
+      .clone()
+      .unwrap_or_else(|| String::from("ctrl-p,ctrl-q")),
+  })
+}
+
 async fn forward_ws_channel(
+  session: TerminalSessionGuard,
   periphery: PeripheryClient,
   client_socket: axum::extract::ws::WebSocket,
   periphery_connection_id: Uuid,
   periphery_sender: Sender<EncodedTransportMessage>,
-  mut periphery_receiver: Receiver<Vec<u8>>,
+  mut periphery_receiver: Receiver<Bytes>,
 ) {
   let (mut client_send, mut client_receive) = client_socket.split();
-  let cancel = CancellationToken::new();
+  // Also cancelled when the session is force disconnected.
+  let cancel = session.cancel.clone();
 
   periphery_receiver.set_cancel(cancel.clone());
 
@@ -308,7 +419,7 @@ async fn forward_ws_channel(
       match periphery_receiver.recv().await {
         Ok(bytes) => {
           if let Err(e) =
-            client_send.send(ws::Message::Binary(bytes.into())).await
+            client_send.send(ws::Message::Binary(bytes)).await
           {
             debug!("{e:?}");
             cancel.cancel();
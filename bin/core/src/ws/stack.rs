@@ -6,7 +6,10 @@ use futures::SinkExt;
 use komodo_client::{
   api::terminal::{ConnectStackAttachQuery, ConnectStackExecQuery},
   entities::{
-    permission::PermissionLevel, server::Server, stack::Stack,
+    permission::{PermissionLevel, PermissionLevelAndSpecifics},
+    server::Server,
+    stack::Stack,
+    user::User,
   },
 };
 
@@ -26,14 +29,21 @@ pub async fn exec(
   ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
   ws.on_upgrade(async move |socket| {
-    let Some((client_socket, server, container)) =
-      login_get_server_container(socket, &stack, &service).await
+    let Some((client_socket, user, server, container)) =
+      login_get_server_container(
+        socket,
+        &stack,
+        &service,
+        PermissionLevel::Read.terminal(),
+      )
+      .await
     else {
       return;
     };
 
     super::handle_container_exec_terminal(
       client_socket,
+      &user,
       &server,
       container,
       shell,
@@ -49,21 +59,30 @@ pub async fn attach(
     stack,
     service,
     recreate,
+    detach_keys,
   }): Query<ConnectStackAttachQuery>,
   ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
   ws.on_upgrade(async move |socket| {
-    let Some((client_socket, server, container)) =
-      login_get_server_container(socket, &stack, &service).await
+    let Some((client_socket, user, server, container)) =
+      login_get_server_container(
+        socket,
+        &stack,
+        &service,
+        PermissionLevel::Read.container_attach(),
+      )
+      .await
     else {
       return;
     };
 
     super::handle_container_attach_terminal(
       client_socket,
+      &user,
       &server,
       container,
       recreate,
+      detach_keys,
     )
     .await
   })
@@ -73,27 +92,25 @@ async fn login_get_server_container(
   socket: axum::extract::ws::WebSocket,
   stack: &str,
   service: &str,
-) -> Option<(axum::extract::ws::WebSocket, Server, String)> {
+  permissions: PermissionLevelAndSpecifics,
+) -> Option<(axum::extract::ws::WebSocket, User, Server, String)> {
   let (mut client_socket, user) =
     super::user_ws_login(socket).await?;
 
-  let stack = match get_check_permissions::<Stack>(
-    stack,
-    &user,
-    PermissionLevel::Read.terminal(),
-  )
-  .await
-  {
-    Ok(stack) => stack,
-    Err(e) => {
-      debug!("could not get stack | {e:#}");
-      let _ = client_socket
-        .send(Message::text(format!("ERROR: {e:#}")))
-        .await;
-      let _ = client_socket.close().await;
-      return None;
-    }
-  };
+  let stack =
+    match get_check_permissions::<Stack>(stack, &user, permissions)
+      .await
+    {
+      Ok(stack) => stack,
+      Err(e) => {
+        debug!("could not get stack | {e:#}");
+        let _ = client_socket
+          .send(Message::text(format!("ERROR: {e:#}")))
+          .await;
+        let _ = client_socket.close().await;
+        return None;
+      }
+    };
 
   let server = match get::<Server>(&stack.config.server_id).await {
     Ok(server) => server,
@@ -146,5 +163,5 @@ async fn login_get_server_container(
     }
   };
 
-  Some((client_socket, server, container))
+  Some((client_socket, user, server, container))
 }
@@ -9,15 +9,20 @@ use komodo_client::{
 };
 
 use crate::{
-  helpers::periphery_client, permission::get_check_permissions,
+  helpers::{
+    periphery_client, terminal_session::open_terminal_session,
+  },
+  permission::get_check_permissions,
   ws::forward_ws_channel,
 };
 
 #[instrument("ConnectTerminal", skip(ws))]
 pub async fn handler(
-  Query(ConnectTerminalQuery { server, terminal }): Query<
-    ConnectTerminalQuery,
-  >,
+  Query(ConnectTerminalQuery {
+    server,
+    terminal,
+    read_only,
+  }): Query<ConnectTerminalQuery>,
   ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
   ws.on_upgrade(|socket| async move {
@@ -27,10 +32,17 @@ pub async fn handler(
       return;
     };
 
+    // Viewing only needs Read, sending input needs Write.
+    let level = if read_only {
+      PermissionLevel::Read
+    } else {
+      PermissionLevel::Write
+    };
+
     let server = match get_check_permissions::<Server>(
       &server,
       &user,
-      PermissionLevel::Read.terminal(),
+      level.terminal(),
     )
     .await
     {
@@ -45,6 +57,23 @@ pub async fn handler(
       }
     };
 
+    let label = if read_only {
+      format!("view: {terminal}")
+    } else {
+      terminal.clone()
+    };
+
+    let session = match open_terminal_session(&user, &server, label) {
+      Ok(session) => session,
+      Err(e) => {
+        let _ = client_socket
+          .send(Message::text(format!("ERROR: {e:#}")))
+          .await;
+        let _ = client_socket.close().await;
+        return;
+      }
+    };
+
     let periphery = match periphery_client(&server).await {
       Ok(periphery) => periphery,
       Err(e) => {
@@ -63,7 +92,7 @@ pub async fn handler(
       periphery_connection_id,
       periphery_sender,
       periphery_receiver,
-    ) = match periphery.connect_terminal(terminal).await {
+    ) = match periphery.connect_terminal(terminal, read_only).await {
       Ok(ws) => ws,
       Err(e) => {
         debug!("Failed connect to periphery terminal | {e:#}");
@@ -78,6 +107,7 @@ pub async fn handler(
     trace!("connected to periphery terminal websocket");
 
     forward_ws_channel(
+      session,
       periphery,
       client_socket,
       periphery_connection_id,
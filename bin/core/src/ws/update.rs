@@ -1,24 +1,29 @@
-use anyhow::anyhow;
 use axum::{
   extract::{WebSocketUpgrade, ws::Message},
   response::IntoResponse,
 };
 use futures::{SinkExt, StreamExt};
-use komodo_client::entities::{
-  ResourceTarget, permission::PermissionLevel, user::User,
+use komodo_client::{
+  entities::{notification::Notification, update::UpdateListItem},
+  ws::WsNotificationMessage,
 };
 use serde_json::json;
 use serror::serialize_error;
 use tokio::select;
 use tokio_util::sync::CancellationToken;
 
-use crate::helpers::{
-  channel::update_channel, query::get_user_permission_on_target,
-};
+use crate::helpers::channel::{notification_channel, update_channel};
+
+enum OutgoingMessage {
+  Update(UpdateListItem),
+  Notification(Notification),
+}
 
 pub async fn handler(ws: WebSocketUpgrade) -> impl IntoResponse {
   // get a reveiver for internal update messages.
   let mut receiver = update_channel().receiver.resubscribe();
+  let mut notification_receiver =
+    notification_channel().receiver.resubscribe();
 
   // handle http -> ws updgrade
   ws.on_upgrade(|socket| async move {
@@ -34,9 +39,20 @@ pub async fn handler(ws: WebSocketUpgrade) -> impl IntoResponse {
     tokio::spawn(async move {
       loop {
         // poll for updates off the receiver / await cancel.
-        let update = select! {
+        let msg = select! {
           _ = cancel_clone.cancelled() => break,
-          update = receiver.recv() => {update.expect("failed to recv update msg")}
+          update = receiver.recv() => {
+            OutgoingMessage::Update(update.expect("failed to recv update msg"))
+          }
+          notification = notification_receiver.recv() => {
+            match notification {
+              Ok(notification) => OutgoingMessage::Notification(notification),
+              Err(e) => {
+                warn!("failed to recv notification msg | {e:?}");
+                continue;
+              }
+            }
+          }
         };
 
         // before sending every update, verify user is still valid.
@@ -53,11 +69,24 @@ pub async fn handler(ws: WebSocketUpgrade) -> impl IntoResponse {
           Ok(user) => user,
         };
 
-        // Only send if user has permission on the target resource.
-        if user_can_see_update(&user, &update.target).await.is_ok() {
-          let _ = ws_sender
-            .send(Message::text(serde_json::to_string(&update).unwrap()))
-            .await;
+        match msg {
+          OutgoingMessage::Update(update) => {
+            // Only send if user has permission on the target resource.
+            if super::user_can_see_target(&user, &update.target).await.is_ok() {
+              let _ = ws_sender
+                .send(Message::text(serde_json::to_string(&update).unwrap()))
+                .await;
+            }
+          }
+          OutgoingMessage::Notification(notification) => {
+            // Only send to the recipient.
+            if notification.user == user.id {
+              let msg = WsNotificationMessage::Notification(notification);
+              let _ = ws_sender
+                .send(Message::text(serde_json::to_string(&msg).unwrap()))
+                .await;
+            }
+          }
         }
       }
     });
@@ -80,21 +109,3 @@ pub async fn handler(ws: WebSocketUpgrade) -> impl IntoResponse {
     }
     })
 }
-
-async fn user_can_see_update(
-  user: &User,
-  update_target: &ResourceTarget,
-) -> anyhow::Result<()> {
-  if user.admin {
-    return Ok(());
-  }
-  let permission =
-    get_user_permission_on_target(user, update_target).await?;
-  if permission.level > PermissionLevel::None {
-    Ok(())
-  } else {
-    Err(anyhow!(
-      "user does not have permissions on {update_target:?}"
-    ))
-  }
-}
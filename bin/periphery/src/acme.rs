@@ -0,0 +1,321 @@
+//! Obtains and renews the server ssl cert using ACME (eg. Let's Encrypt),
+//! when `ssl_acme_email` and `ssl_acme_domain` are configured.
+
+use std::{
+  collections::HashMap,
+  path::Path,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+use anyhow::{Context, anyhow};
+use axum::{Router, extract::Path as UrlPath, routing::get};
+use instant_acme::{
+  Account, AccountCredentials, AuthorizationStatus, ChallengeType,
+  Identifier, LetsEncrypt, NewAccount, NewOrder, OrderStatus,
+  RetryPolicy,
+};
+use komodo_client::entities::config::periphery::AcmeChallenge;
+use tokio::process::Command;
+
+use crate::config::periphery_config;
+
+/// Renew when the cert expires within this many days.
+const RENEW_BEFORE_DAYS: u64 = 30;
+/// How often to check whether the cert needs renewal.
+const RENEWAL_CHECK_INTERVAL: Duration =
+  Duration::from_secs(12 * 60 * 60);
+
+/// Makes sure a valid ACME cert exists at the ssl cert path,
+/// obtaining one if it is missing or close to expiry.
+/// If obtaining fails and there is no existing cert, Periphery can't
+/// serve ssl, so this panics like the self signed generation does.
+#[instrument("EnsureAcmeCert")]
+pub async fn ensure_cert() {
+  match renew_if_needed().await {
+    Ok(_) => {}
+    Err(e) if periphery_config().ssl_cert_file().is_file() => {
+      warn!("Failed to renew ACME SSL Cert, using existing | {e:#}");
+    }
+    Err(e) => panic!("🚨 Failed to obtain ACME SSL Cert | {e:#}"),
+  }
+}
+
+/// Periodically renews the cert, calling `reload`
+/// after a new cert is written so it is served
+/// to new connections without dropping existing ones.
+pub fn spawn_renewal_loop<F>(
+  reload: impl Fn() -> F + Send + Sync + 'static,
+) where
+  F: Future<Output = anyhow::Result<()>> + Send,
+{
+  tokio::spawn(async move {
+    loop {
+      tokio::time::sleep(RENEWAL_CHECK_INTERVAL).await;
+      match renew_if_needed().await {
+        Ok(false) => {}
+        Ok(true) => match reload().await {
+          Ok(_) => info!("✅ Reloaded renewed ACME SSL Cert"),
+          Err(e) => {
+            error!("Failed to reload renewed ACME SSL Cert | {e:#}")
+          }
+        },
+        Err(e) => error!("Failed to renew ACME SSL Cert | {e:#}"),
+      }
+    }
+  });
+}
+
+/// Returns whether a new cert was written.
+async fn renew_if_needed() -> anyhow::Result<bool> {
+  let config = periphery_config();
+  let cert_file = config.ssl_cert_file();
+  if cert_file.is_file() && !expires_soon(&cert_file).await {
+    return Ok(false);
+  }
+  obtain_cert().await?;
+  Ok(true)
+}
+
+/// Uses openssl, the same as the self signed cert generation.
+async fn expires_soon(cert_file: &Path) -> bool {
+  let seconds = RENEW_BEFORE_DAYS * 24 * 60 * 60;
+  let command = format!(
+    "openssl x509 -checkend {seconds} -noout -in {}",
+    cert_file.display()
+  );
+  !run_command::async_run_command(&command).await.success()
+}
+
+async fn obtain_cert() -> anyhow::Result<()> {
+  let config = periphery_config();
+  let (email, domain) = config
+    .ssl_acme()
+    .context("Missing ssl_acme_email / ssl_acme_domain")?;
+
+  info!("Obtaining ACME SSL Cert for {domain}...");
+
+  let account = load_or_create_account(email).await?;
+
+  let identifiers = [Identifier::Dns(domain.to_string())];
+  let mut order = account
+    .new_order(&NewOrder::new(&identifiers))
+    .await
+    .context("Failed to create ACME order")?;
+
+  // Responses for pending http01 challenges, by token.
+  let http_tokens = Arc::new(Mutex::new(HashMap::new()));
+  // The dns01 TXT records to clean up after the order.
+  let mut dns_records = Vec::new();
+  let mut http_server = None;
+
+  let res = async {
+    let mut authorizations = order.authorizations();
+    while let Some(authorization) = authorizations.next().await {
+      let mut authorization =
+        authorization.context("Failed to get ACME authorization")?;
+      match authorization.status {
+        AuthorizationStatus::Pending => {}
+        AuthorizationStatus::Valid => continue,
+        status => {
+          return Err(anyhow!(
+            "ACME authorization has unexpected status {status:?}"
+          ));
+        }
+      }
+      match config.ssl_acme_challenge {
+        AcmeChallenge::Http01 => {
+          let mut challenge = authorization
+            .challenge(ChallengeType::Http01)
+            .context("ACME server did not offer http01 challenge")?;
+          http_tokens.lock().unwrap().insert(
+            challenge.token.clone(),
+            challenge.key_authorization().as_str().to_string(),
+          );
+          if http_server.is_none() {
+            http_server =
+              Some(serve_http_challenges(&http_tokens).await?);
+          }
+          challenge
+            .set_ready()
+            .await
+            .context("Failed to set ACME challenge ready")?;
+        }
+        AcmeChallenge::Dns01 => {
+          let mut challenge = authorization
+            .challenge(ChallengeType::Dns01)
+            .context("ACME server did not offer dns01 challenge")?;
+          let record = format!("_acme-challenge.{domain}");
+          let value = challenge.key_authorization().dns_value();
+          run_dns_command("present", &record, &value).await?;
+          dns_records.push((record, value));
+          challenge
+            .set_ready()
+            .await
+            .context("Failed to set ACME challenge ready")?;
+        }
+      }
+    }
+
+    let status = order
+      .poll_ready(&RetryPolicy::default())
+      .await
+      .context("Failed waiting for ACME order to be ready")?;
+    if status != OrderStatus::Ready {
+      return Err(anyhow!(
+        "ACME order has unexpected status {status:?}"
+      ));
+    }
+
+    let private_key = order
+      .finalize()
+      .await
+      .context("Failed to finalize ACME order")?;
+    let cert_chain = order
+      .poll_certificate(&RetryPolicy::default())
+      .await
+      .context("Failed waiting for ACME certificate")?;
+
+    anyhow::Ok((private_key, cert_chain))
+  }
+  .await;
+
+  if let Some(http_server) = http_server {
+    http_server.abort();
+  }
+  for (record, value) in dns_records {
+    if let Err(e) = run_dns_command("cleanup", &record, &value).await
+    {
+      warn!("Failed to clean up ACME dns01 record | {e:#}");
+    }
+  }
+
+  let (private_key, cert_chain) = res?;
+
+  write_file(&config.ssl_key_file(), &private_key).await?;
+  write_file(&config.ssl_cert_file(), &cert_chain).await?;
+
+  info!("✅ ACME SSL Cert obtained for {domain}");
+
+  Ok(())
+}
+
+/// The account credentials are stored next to the ssl key,
+/// so renewals reuse the same ACME account.
+async fn load_or_create_account(
+  email: &str,
+) -> anyhow::Result<Account> {
+  let config = periphery_config();
+  let credentials_file =
+    config.ssl_key_file().with_file_name("acme_account.json");
+
+  if let Ok(credentials) =
+    tokio::fs::read_to_string(&credentials_file).await
+  {
+    let credentials =
+      serde_json::from_str::<AccountCredentials>(&credentials)
+        .context("Invalid ACME account credentials file")?;
+    return Account::builder()
+      .context("Failed to build ACME client")?
+      .from_credentials(credentials)
+      .await
+      .context("Failed to load ACME account");
+  }
+
+  let directory_url = config
+    .ssl_acme_directory_url
+    .clone()
+    .filter(|url| !url.is_empty())
+    .unwrap_or_else(|| LetsEncrypt::Production.url().to_string());
+  let contact = format!("mailto:{email}");
+  let (account, credentials) = Account::builder()
+    .context("Failed to build ACME client")?
+    .create(
+      &NewAccount {
+        contact: &[&contact],
+        terms_of_service_agreed: true,
+        only_return_existing: false,
+      },
+      directory_url,
+      None,
+    )
+    .await
+    .context("Failed to create ACME account")?;
+
+  let credentials = serde_json::to_string(&credentials)
+    .context("Failed to serialize ACME account credentials")?;
+  write_file(&credentials_file, &credentials).await?;
+
+  Ok(account)
+}
+
+/// Serves pending http01 challenges on port 80 until aborted.
+async fn serve_http_challenges(
+  tokens: &Arc<Mutex<HashMap<String, String>>>,
+) -> anyhow::Result<tokio::task::JoinHandle<()>> {
+  let tokens = tokens.clone();
+  let app = Router::new().route(
+    "/.well-known/acme-challenge/{token}",
+    get(move |UrlPath(token): UrlPath<String>| {
+      let tokens = tokens.clone();
+      async move {
+        tokens
+          .lock()
+          .unwrap()
+          .get(&token)
+          .cloned()
+          .unwrap_or_default()
+      }
+    }),
+  );
+  let listener = tokio::net::TcpListener::bind("[::]:80")
+    .await
+    .context("Failed to bind port 80 for ACME http01 challenge")?;
+  Ok(tokio::spawn(async move {
+    if let Err(e) = axum::serve(listener, app).await {
+      warn!("ACME http01 challenge server failed | {e:#}");
+    }
+  }))
+}
+
+async fn run_dns_command(
+  action: &str,
+  record: &str,
+  value: &str,
+) -> anyhow::Result<()> {
+  let command = periphery_config()
+    .ssl_acme_dns_command
+    .as_deref()
+    .filter(|command| !command.is_empty())
+    .context("dns01 challenge requires 'ssl_acme_dns_command'")?;
+  let output = Command::new("sh")
+    .arg("-c")
+    .arg(command)
+    .env("ACME_DNS_ACTION", action)
+    .env("ACME_DNS_RECORD", record)
+    .env("ACME_DNS_VALUE", value)
+    .output()
+    .await
+    .context("Failed to run ssl_acme_dns_command")?;
+  if output.status.success() {
+    Ok(())
+  } else {
+    Err(anyhow!(
+      "ssl_acme_dns_command '{action}' failed | stdout: {} | stderr: {}",
+      String::from_utf8_lossy(&output.stdout),
+      String::from_utf8_lossy(&output.stderr)
+    ))
+  }
+}
+
+async fn write_file(
+  path: &Path,
+  contents: &str,
+) -> anyhow::Result<()> {
+  if let Some(parent) = path.parent() {
+    let _ = tokio::fs::create_dir_all(parent).await;
+  }
+  tokio::fs::write(path, contents)
+    .await
+    .with_context(|| format!("Failed to write {path:?}"))
+}
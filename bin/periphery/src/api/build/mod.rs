@@ -4,9 +4,7 @@ use std::{
 };
 
 use anyhow::{Context, anyhow};
-use command::{
-  run_komodo_command, run_komodo_command_with_sanitization,
-};
+use command::run_komodo_command;
 use formatting::format_serror;
 use interpolate::Interpolator;
 use komodo_client::entities::{
@@ -18,15 +16,22 @@ use komodo_client::entities::{
 };
 use periphery_client::api::build::{
   self, GetDockerfileContentsOnHost,
-  GetDockerfileContentsOnHostResponse, PruneBuilders, PruneBuildx,
-  WriteDockerfileContentsToHost,
+  GetDockerfileContentsOnHostResponse, PruneBuildCache,
+  PruneBuilders, PruneBuildx, WriteDockerfileContentsToHost,
 };
 use resolver_api::Resolve;
 use tokio::fs;
 
 use crate::{
   config::periphery_config,
-  docker::docker_login,
+  docker::{
+    buildx::{
+      cache_args, ensure_builder, platform_args, platform_digests,
+      prune_build_cache,
+    },
+    docker_login,
+    runtime::docker_cli,
+  },
   helpers::{format_extra_args, format_labels},
 };
 
@@ -123,7 +128,7 @@ impl Resolve<super::Args> for build::Build {
   #[instrument("Build", skip_all, fields(build = self.build.name.to_string()))]
   async fn resolve(
     self,
-    _: &super::Args,
+    args: &super::Args,
   ) -> anyhow::Result<Vec<Log>> {
     let build::Build {
       mut build,
@@ -156,6 +161,9 @@ impl Resolve<super::Args> for build::Build {
           labels,
           extra_args,
           use_buildx,
+          platforms,
+          cache_from,
+          cache_to,
           image_registry,
           repo,
           files_on_host,
@@ -250,14 +258,15 @@ impl Resolve<super::Args> for build::Build {
     // Pre Build
     if !pre_build.is_none() {
       let pre_build_path = build_path.join(&pre_build.path);
-      if let Some(log) = run_komodo_command_with_sanitization(
-        "Pre Build",
-        pre_build_path.as_path(),
-        &pre_build.command,
-        true,
-        &replacers,
-      )
-      .await
+      if let Some(log) = args
+        .run_command(
+          "Pre Build",
+          pre_build_path.as_path(),
+          &pre_build.command,
+          true,
+          &replacers,
+        )
+        .await
       {
         let success = log.success;
         logs.push(log);
@@ -291,7 +300,34 @@ impl Resolve<super::Args> for build::Build {
 
     let extra_args = format_extra_args(extra_args);
 
-    let buildx = if *use_buildx { " buildx" } else { "" };
+    let platforms = platforms
+      .iter()
+      .map(|platform| platform.trim())
+      .filter(|platform| !platform.is_empty())
+      .map(String::from)
+      .collect::<Vec<_>>();
+
+    // Multi platform builds and cache export require
+    // buildx with a docker-container driver builder.
+    let (buildx, platform_args) =
+      if platforms.is_empty() && cache_to.trim().is_empty() {
+        (if *use_buildx { " buildx" } else { "" }, String::new())
+      } else {
+        match ensure_builder(&mut logs).await {
+          Ok(builder) => {
+            (" buildx", platform_args(builder, &platforms))
+          }
+          Err(e) => {
+            logs.push(Log::error(
+              "Buildx Builder",
+              format_serror(&e.into()),
+            ));
+            return Ok(logs);
+          }
+        }
+      };
+
+    let cache_args = cache_args(cache_from.trim(), cache_to.trim());
 
     let image_tags = build
       .get_image_tags_as_arg(commit_hash.as_deref(), &additional_tags)
@@ -300,20 +336,38 @@ impl Resolve<super::Args> for build::Build {
     let maybe_push = if should_push { " --push" } else { "" };
 
     // Construct command
+    let docker = docker_cli();
     let command = format!(
-      "docker{buildx} build{build_args}{command_secret_args}{extra_args}{labels}{image_tags}{maybe_push} -f {dockerfile_path} .",
+      "{docker}{buildx} build{platform_args}{cache_args}{build_args}{command_secret_args}{extra_args}{labels}{image_tags}{maybe_push} -f {dockerfile_path} .",
     );
 
-    if let Some(build_log) = run_komodo_command_with_sanitization(
-      "Docker Build",
-      build_path.as_ref(),
-      command,
-      false,
-      &replacers,
-    )
-    .await
+    if let Some(build_log) = args
+      .run_command(
+        "Docker Build",
+        build_path.as_ref(),
+        command,
+        false,
+        &replacers,
+      )
+      .await
     {
+      let success = build_log.success;
       logs.push(build_log);
+      // Report the digest of each platform image. Multi platform
+      // images are only stored in the registry after push.
+      if success
+        && should_push
+        && !platforms.is_empty()
+        && let Some(image) = build
+          .get_image_tags(
+            &build.get_image_names(),
+            commit_hash.as_deref(),
+            &additional_tags,
+          )
+          .first()
+      {
+        logs.push(platform_digests(image).await);
+      }
     };
 
     Ok(logs)
@@ -325,7 +379,8 @@ impl Resolve<super::Args> for build::Build {
 impl Resolve<super::Args> for PruneBuilders {
   #[instrument("PruneBuilders", skip_all)]
   async fn resolve(self, _: &super::Args) -> anyhow::Result<Log> {
-    let command = String::from("docker builder prune -a -f");
+    let command =
+      format!("{docker} builder prune -a -f", docker = docker_cli());
     Ok(run_komodo_command("Prune Builders", None, command).await)
   }
 }
@@ -335,7 +390,20 @@ impl Resolve<super::Args> for PruneBuilders {
 impl Resolve<super::Args> for PruneBuildx {
   #[instrument("PruneBuildx", skip_all)]
   async fn resolve(self, _: &super::Args) -> anyhow::Result<Log> {
-    let command = String::from("docker buildx prune -a -f");
+    let command =
+      format!("{docker} buildx prune -a -f", docker = docker_cli());
     Ok(run_komodo_command("Prune Buildx", None, command).await)
   }
 }
+
+//
+
+impl Resolve<super::Args> for PruneBuildCache {
+  #[instrument("PruneBuildCache", skip_all, fields(keep_last = self.keep_last))]
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<Vec<Log>> {
+    Ok(prune_build_cache(self.keep_last.as_deref()).await)
+  }
+}
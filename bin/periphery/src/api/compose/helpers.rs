@@ -1,22 +1,30 @@
 use std::{
   fmt::Write,
+  fs::Permissions,
+  os::unix::fs::PermissionsExt,
   path::{Path, PathBuf},
 };
 
 use anyhow::{Context, anyhow};
-use command::run_komodo_command;
+use command::{
+  run_komodo_command, run_komodo_command_with_sanitization,
+};
 use formatting::format_serror;
-use komodo_client::entities::{
-  FileContents, RepoExecutionArgs,
-  repo::Repo,
-  stack::{Stack, StackRemoteFileContents},
-  to_path_compatible_name,
-  update::Log,
+use komodo_client::{
+  entities::{
+    EnvironmentVar, FileContents, RepoExecutionArgs, SystemCommand,
+    repo::Repo,
+    stack::{Stack, StackRemoteFileContents},
+    to_path_compatible_name,
+    update::Log,
+  },
+  parsers::parse_multiline_command,
 };
 use periphery_client::api::{
   compose::ComposeUpResponse, git::PullOrCloneRepo,
 };
 use resolver_api::Resolve;
+use shell_escape::unix::escape;
 use tokio::fs;
 
 use crate::{
@@ -87,6 +95,125 @@ pub fn env_file_args(
   Ok(res)
 }
 
+/// Writes the Stack secret files into the project directory under
+/// `secrets_dir`, along with a compose file defining each as a top level secret.
+/// Returns the path of the compose file to pass with `-f`,
+/// or None if the Stack has no secret files.
+#[instrument(
+  "WriteSecretFiles",
+  skip(stack, logs),
+  fields(stack = stack.name)
+)]
+pub async fn write_secret_files(
+  stack: &Stack,
+  project_name: &str,
+  logs: &mut Vec<Log>,
+) -> anyhow::Result<Option<PathBuf>> {
+  let secrets = stack.config.secret_file_vars()?;
+  if secrets.is_empty() {
+    // Clean up any left over from previous deploys.
+    remove_secret_files(project_name).await?;
+    return Ok(None);
+  }
+
+  let dir = secret_files_dir(project_name);
+  fs::create_dir_all(&dir).await.with_context(|| {
+    format!("Failed to create secrets directory at {dir:?}")
+  })?;
+  // Only the periphery user can list / traverse the directory.
+  // The docker daemon mounts the files directly.
+  fs::set_permissions(&dir, Permissions::from_mode(0o700))
+    .await
+    .context("Failed to set secrets directory permissions")?;
+
+  let mut definitions = serde_json::Map::new();
+  for EnvironmentVar { variable, value } in &secrets {
+    if variable.is_empty()
+      || variable.starts_with('.')
+      || !variable.chars().all(|c| {
+        c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')
+      })
+    {
+      return Err(anyhow!(
+        "Invalid secret file name '{variable}'. Use only alphanumeric, '_', '-', or '.'"
+      ));
+    }
+    let path = dir.join(variable);
+    // Written in place so running containers see the new contents.
+    fs::write(&path, value).await.with_context(|| {
+      format!("Failed to write secret file {variable}")
+    })?;
+    // Readable by non root container users,
+    // the directory keeps them private on the host.
+    fs::set_permissions(&path, Permissions::from_mode(0o644))
+      .await
+      .with_context(|| {
+        format!("Failed to set secret file {variable} permissions")
+      })?;
+    definitions
+      .insert(variable.clone(), serde_json::json!({ "file": path }));
+  }
+
+  // Remove secrets which are no longer configured.
+  let mut entries = fs::read_dir(&dir)
+    .await
+    .context("Failed to read secrets directory")?;
+  while let Some(entry) = entries.next_entry().await? {
+    let name = entry.file_name();
+    let name = name.to_string_lossy();
+    if name != SECRETS_COMPOSE_FILE
+      && !secrets.iter().any(|secret| secret.variable == name)
+    {
+      let _ = fs::remove_file(entry.path()).await;
+    }
+  }
+
+  let compose_file = dir.join(SECRETS_COMPOSE_FILE);
+  let contents = serde_yaml_ng::to_string(
+    &serde_json::json!({ "secrets": definitions }),
+  )
+  .context("Failed to serialize secrets compose file")?;
+  fs::write(&compose_file, contents)
+    .await
+    .context("Failed to write secrets compose file")?;
+
+  logs.push(Log::simple(
+    "Write Secret Files",
+    format!(
+      "Wrote {} secret file(s) to {dir:?}: {}",
+      secrets.len(),
+      secrets
+        .iter()
+        .map(|secret| secret.variable.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+    ),
+  ));
+
+  Ok(Some(compose_file))
+}
+
+/// Removes the project secret files directory, if it exists.
+pub async fn remove_secret_files(
+  project_name: &str,
+) -> anyhow::Result<()> {
+  let dir = secret_files_dir(project_name);
+  if !dir.exists() {
+    return Ok(());
+  }
+  fs::remove_dir_all(&dir).await.with_context(|| {
+    format!("Failed to remove secrets directory at {dir:?}")
+  })
+}
+
+const SECRETS_COMPOSE_FILE: &str = "compose.secrets.yaml";
+
+fn secret_files_dir(project_name: &str) -> PathBuf {
+  periphery_config()
+    .secrets_dir()
+    .join(to_path_compatible_name(project_name))
+}
+
 #[instrument("ComposeDown", skip(res))]
 pub async fn compose_down(
   project: &str,
@@ -258,3 +385,75 @@ pub async fn validate_files(
     });
   }
 }
+
+/// Runs a Stack pre / post deploy command.
+///
+/// - The command path must stay inside the run directory.
+/// - If `timeout_seconds` is positive, the command is killed
+///   if it is still running after the timeout.
+pub async fn run_deploy_command(
+  stage: &str,
+  run_directory: &Path,
+  command: &SystemCommand,
+  timeout_seconds: i64,
+  replacers: &[(String, String)],
+) -> Option<Log> {
+  let path = match sandboxed_path(run_directory, &command.path) {
+    Ok(path) => path,
+    Err(e) => {
+      return Some(Log::error(stage, format_serror(&e.into())));
+    }
+  };
+  if timeout_seconds <= 0 {
+    return run_komodo_command_with_sanitization(
+      stage,
+      path.as_path(),
+      &command.command,
+      true,
+      replacers,
+    )
+    .await;
+  }
+  let parsed = parse_multiline_command(&command.command);
+  if parsed.is_empty() {
+    return None;
+  }
+  let mut log = run_komodo_command_with_sanitization(
+    stage,
+    path.as_path(),
+    format!(
+      "timeout --kill-after=10s {timeout_seconds}s sh -c {}",
+      escape(parsed.into())
+    ),
+    false,
+    replacers,
+  )
+  .await?;
+  if !log.success
+    && log.end_ts - log.start_ts >= timeout_seconds * 1_000
+  {
+    log.stderr.push_str(&format!(
+      "\n{stage} timed out after {timeout_seconds} seconds"
+    ));
+  }
+  Some(log)
+}
+
+/// Joins the relative path onto the run directory,
+/// ensuring the result does not escape it.
+fn sandboxed_path(
+  run_directory: &Path,
+  path: &str,
+) -> anyhow::Result<PathBuf> {
+  let joined =
+    run_directory.join(path).canonicalize().with_context(|| {
+      format!("Command path '{path}' does not exist")
+    })?;
+  if joined.starts_with(run_directory) {
+    Ok(joined)
+  } else {
+    Err(anyhow!(
+      "Command path '{path}' must be inside the Stack run directory"
+    ))
+  }
+}
@@ -1,4 +1,8 @@
-use std::{borrow::Cow, path::PathBuf};
+use std::{
+  borrow::Cow,
+  path::{Path, PathBuf},
+  time::Duration,
+};
 
 use anyhow::{Context, anyhow};
 use command::{
@@ -10,8 +14,9 @@ use interpolate::Interpolator;
 use komodo_client::entities::{
   FileContents, RepoExecutionResponse, all_logs_success,
   stack::{
-    ComposeFile, ComposeProject, ComposeService,
-    ComposeServiceDeploy, StackRemoteFileContents, StackServiceNames,
+    ComposeFile, ComposeProject, ComposeProjectService,
+    ComposeService, ComposeServiceDeploy, StackRemoteFileContents,
+    StackServiceNames,
   },
   to_path_compatible_name,
   update::Log,
@@ -23,20 +28,20 @@ use shell_escape::unix::escape;
 
 use crate::{
   config::periphery_config,
+  docker::{health::wait_for_healthy, runtime::runtime},
   helpers::{format_extra_args, format_log_grep},
+  rollback::stack_rollback_file,
+  state::docker_client,
 };
 
 mod helpers;
+mod swarm;
 mod write;
 
 use helpers::*;
 
 fn docker_compose() -> &'static str {
-  if periphery_config().legacy_compose_cli {
-    "docker-compose"
-  } else {
-    "docker compose"
-  }
+  runtime().compose_cli()
 }
 
 pub async fn list_compose_projects()
@@ -55,17 +60,38 @@ pub async fn list_compose_projects()
     )));
   }
 
-  let res =
+  let items =
     serde_json::from_str::<Vec<DockerComposeLsItem>>(&res.stdout)
       .with_context(|| res.stdout.clone())
       .with_context(|| {
         format!(
           "failed to parse '{docker_compose} ls' response to json"
         )
-      })?
-      .into_iter()
-      .filter(|item| !item.name.is_empty())
-      .map(|item| ComposeProject {
+      })?;
+
+  let replicas = match docker_client().load().iter().next() {
+    Some(client) => client
+      .compose_service_replicas()
+      .await
+      .inspect_err(|e| warn!("{e:#}"))
+      .unwrap_or_default(),
+    None => Default::default(),
+  };
+
+  let res = items
+    .into_iter()
+    .filter(|item| !item.name.is_empty())
+    .map(|item| {
+      let mut services = replicas
+        .iter()
+        .filter(|((project, _), _)| *project == item.name)
+        .map(|((_, service), replicas)| ComposeProjectService {
+          service: service.clone(),
+          replicas: *replicas,
+        })
+        .collect::<Vec<_>>();
+      services.sort_by(|a, b| a.service.cmp(&b.service));
+      ComposeProject {
         name: item.name,
         status: item.status,
         compose_files: item
@@ -73,12 +99,102 @@ pub async fn list_compose_projects()
           .split(',')
           .map(str::to_string)
           .collect(),
-      })
-      .collect();
+        services,
+      }
+    })
+    .collect();
 
   Ok(res)
 }
 
+/// Re-deploys the compose config of the last successful deploy.
+async fn rollback_project(project_name: &str, logs: &mut Vec<Log>) {
+  let file = stack_rollback_file(project_name);
+  if !file.is_file() {
+    logs.push(Log::error(
+      "Compose Rollback",
+      format!(
+        "No previous deploy recorded for project {project_name}"
+      ),
+    ));
+    return;
+  }
+  let docker_compose = docker_compose();
+  logs.push(
+    run_komodo_command(
+      "Compose Rollback",
+      None,
+      format!(
+        "{docker_compose} -p {project_name} -f {} up -d --remove-orphans",
+        file.display()
+      ),
+    )
+    .await,
+  );
+}
+
+/// Saves the resolved compose config after a successful deploy,
+/// to roll back to if a later deploy fails.
+async fn save_project_rollback(
+  project_name: &str,
+  run_directory: &Path,
+  file_args: &str,
+  env_file_args: &str,
+) {
+  let file = stack_rollback_file(project_name);
+  if let Some(parent) = file.parent()
+    && let Err(e) = tokio::fs::create_dir_all(parent).await
+  {
+    warn!("Failed to create rollback directory {parent:?} | {e:?}");
+    return;
+  }
+  let docker_compose = docker_compose();
+  let log = run_komodo_command(
+    "Save Rollback",
+    run_directory,
+    format!(
+      "{docker_compose} -p {project_name} -f {file_args}{env_file_args} config -o {}",
+      file.display()
+    ),
+  )
+  .await;
+  if !log.success {
+    warn!(
+      "Failed to save rollback config for project {project_name} | {}",
+      log.combined()
+    );
+  }
+}
+
+/// Waits for the containers of the project services to become healthy.
+async fn wait_for_project_healthy(
+  args: &super::Args,
+  project_name: &str,
+  service_args: &str,
+  timeout: Duration,
+) -> Log {
+  let docker_compose = docker_compose();
+  let log = run_komodo_command(
+    "Wait For Healthy",
+    None,
+    format!(
+      "{docker_compose} -p {project_name} ps --all --format '{{{{.Name}}}}'{service_args}"
+    ),
+  )
+  .await;
+  if !log.success {
+    return log;
+  }
+  let containers = log
+    .stdout
+    .lines()
+    .map(str::trim)
+    .filter(|name| !name.is_empty())
+    .map(str::to_string)
+    .collect::<Vec<_>>();
+  wait_for_healthy(args, &containers, timeout).await
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerComposeLsItem {
   #[serde(default, alias = "Name")]
@@ -381,7 +497,7 @@ impl Resolve<super::Args> for ComposePull {
       format!(" {}", services.join(" "))
     };
 
-    let file_args = stack.compose_file_paths().join(" -f ");
+    let mut file_args = stack.compose_file_paths().join(" -f ");
 
     let env_file_args = env_file_args(
       env_file_path,
@@ -390,6 +506,23 @@ impl Resolve<super::Args> for ComposePull {
 
     let project_name = stack.project_name(false);
 
+    // Services referencing secrets need them defined to pull.
+    match write_secret_files(&stack, &project_name, &mut res.logs)
+      .await
+    {
+      Ok(Some(path)) => {
+        file_args.push_str(&format!(" -f {}", path.display()))
+      }
+      Ok(None) => {}
+      Err(e) => {
+        res.logs.push(Log::error(
+          "Write Secret Files",
+          format_serror(&e.into()),
+        ));
+        return Ok(res);
+      }
+    }
+
     let log = run_komodo_command(
       "Compose Pull",
       run_directory.as_ref(),
@@ -476,23 +609,20 @@ impl Resolve<super::Args> for ComposeUp {
     }
 
     // Pre deploy
-    if !stack.config.pre_deploy.is_none() {
-      let pre_deploy_path =
-        run_directory.join(&stack.config.pre_deploy.path);
-      if let Some(log) = run_komodo_command_with_sanitization(
+    if !stack.config.pre_deploy.is_none()
+      && let Some(log) = run_deploy_command(
         "Pre Deploy",
-        pre_deploy_path.as_path(),
-        &stack.config.pre_deploy.command,
-        true,
+        &run_directory,
+        &stack.config.pre_deploy,
+        stack.config.deploy_command_timeout_seconds,
         &replacers,
       )
       .await
-      {
-        res.logs.push(log);
-        if !all_logs_success(&res.logs) {
-          return Ok(res);
-        }
-      };
+    {
+      res.logs.push(log);
+      if !all_logs_success(&res.logs) {
+        return Ok(res);
+      }
     }
 
     let docker_compose = docker_compose();
@@ -503,13 +633,29 @@ impl Resolve<super::Args> for ComposeUp {
       format!(" {}", services.join(" "))
     };
 
-    let file_args = stack.compose_file_paths().join(" -f ");
+    let mut file_args = stack.compose_file_paths().join(" -f ");
 
     // This will be the last project name, which is the one that needs to be destroyed.
     // Might be different from the current project name, if user renames stack / changes to custom project name.
     let last_project_name = stack.project_name(false);
     let project_name = stack.project_name(true);
 
+    match write_secret_files(&stack, &project_name, &mut res.logs)
+      .await
+    {
+      Ok(Some(path)) => {
+        file_args.push_str(&format!(" -f {}", path.display()))
+      }
+      Ok(None) => {}
+      Err(e) => {
+        res.logs.push(Log::error(
+          "Write Secret Files",
+          format_serror(&e.into()),
+        ));
+        return Ok(res);
+      }
+    }
+
     let env_file_args = env_file_args(
       env_file_path,
       &stack.config.additional_env_files,
@@ -631,6 +777,11 @@ impl Resolve<super::Args> for ComposeUp {
       helpers::compose_down(&last_project_name, &services, &mut res)
         .await
         .context("failed to destroy existing containers")?;
+      if last_project_name != project_name
+        && let Err(e) = remove_secret_files(&last_project_name).await
+      {
+        warn!("{e:#}");
+      }
     }
 
     // Run compose up
@@ -654,20 +805,48 @@ impl Resolve<super::Args> for ComposeUp {
     res.deployed = log.success;
     res.logs.push(log);
 
-    if res.deployed && !stack.config.post_deploy.is_none() {
-      let post_deploy_path =
-        run_directory.join(&stack.config.post_deploy.path);
-      if let Some(log) = run_komodo_command_with_sanitization(
+    if res.deployed && stack.config.wait_for_healthcheck {
+      let log = wait_for_project_healthy(
+        args,
+        &project_name,
+        &service_args,
+        Duration::from_secs(
+          stack.config.healthcheck_timeout_seconds.max(0) as u64,
+        ),
+      )
+      .await;
+      res.logs.push(log);
+    }
+
+    if !all_logs_success(&res.logs) {
+      if stack.config.rollback_on_failure {
+        rollback_project(&project_name, &mut res.logs).await;
+        // The previous contents are still the deployed ones.
+        res.deployed = false;
+      }
+      return Ok(res);
+    }
+
+    save_project_rollback(
+      &project_name,
+      &run_directory,
+      &file_args,
+      &env_file_args,
+    )
+    .await;
+
+    if res.deployed
+      && !stack.config.post_deploy.is_none()
+      && let Some(log) = run_deploy_command(
         "Post Deploy",
-        post_deploy_path.as_path(),
-        &stack.config.post_deploy.command,
-        true,
+        &run_directory,
+        &stack.config.post_deploy,
+        stack.config.deploy_command_timeout_seconds,
         &replacers,
       )
       .await
-      {
-        res.logs.push(log);
-      };
+    {
+      res.logs.push(log);
     }
 
     Ok(res)
@@ -679,14 +858,80 @@ impl Resolve<super::Args> for ComposeUp {
 impl Resolve<super::Args> for ComposeExecution {
   #[instrument("ComposeExecution", skip(args), fields(core = args.core))]
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
-    let ComposeExecution { project, command } = self;
+    let ComposeExecution {
+      project,
+      command,
+      remove_secret_files,
+    } = self;
     let docker_compose = docker_compose();
-    let log = run_komodo_command(
+    let mut log = run_komodo_command(
       "Compose Command",
       None,
       format!("{docker_compose} -p {project} {command}"),
     )
     .await;
+    if log.success
+      && remove_secret_files
+      && let Err(e) = helpers::remove_secret_files(&project).await
+    {
+      log.stderr.push_str(&format!("\n{e:#}"));
+    }
+    Ok(log)
+  }
+}
+
+//
+
+impl Resolve<super::Args> for ComposeScale {
+  #[instrument(
+    "ComposeScale",
+    skip(args),
+    fields(core = args.core)
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let ComposeScale {
+      project,
+      service,
+      replicas,
+    } = self;
+    if replicas < 0 {
+      return Err(anyhow!("Replicas cannot be negative"));
+    }
+    let compose_files = list_compose_projects()
+      .await?
+      .into_iter()
+      .find(|p| p.name == project)
+      .with_context(|| {
+        format!("Did not find compose project {project} on host")
+      })?
+      .compose_files
+      .into_iter()
+      .filter(|file| !file.is_empty())
+      .collect::<Vec<_>>();
+    // Run from the project directory so relative paths
+    // and the default .env file resolve like the original deploy.
+    let run_directory = compose_files
+      .first()
+      .and_then(|file| {
+        PathBuf::from(file).parent().map(PathBuf::from)
+      })
+      .with_context(|| {
+        format!("Did not find compose files for project {project}")
+      })?;
+    let file_args = compose_files
+      .iter()
+      .map(|file| format!(" -f {}", escape(file.into())))
+      .collect::<String>();
+    let docker_compose = docker_compose();
+    let service = escape(service.into());
+    let log = run_komodo_command(
+      "Compose Scale",
+      run_directory.as_ref(),
+      format!(
+        "{docker_compose} -p {project}{file_args} up -d --no-deps --no-recreate --scale {service}={replicas} {service}"
+      ),
+    )
+    .await;
     Ok(log)
   }
 }
@@ -759,7 +1004,7 @@ impl Resolve<super::Args> for ComposeRun {
 
     let docker_compose = docker_compose();
 
-    let file_args = if stack.config.file_paths.is_empty() {
+    let mut file_args = if stack.config.file_paths.is_empty() {
       String::from("compose.yaml")
     } else {
       stack.config.file_paths.join(" -f ")
@@ -772,6 +1017,21 @@ impl Resolve<super::Args> for ComposeRun {
 
     let project_name = stack.project_name(true);
 
+    match write_secret_files(&stack, &project_name, &mut Vec::new())
+      .await
+    {
+      Ok(Some(path)) => {
+        file_args.push_str(&format!(" -f {}", path.display()))
+      }
+      Ok(None) => {}
+      Err(e) => {
+        return Ok(Log::error(
+          "Write Secret Files",
+          format_serror(&e.into()),
+        ));
+      }
+    }
+
     if pull.unwrap_or_default() {
       let pull_log = run_komodo_command(
         "Compose Pull",
@@ -0,0 +1,192 @@
+use anyhow::Context;
+use command::run_komodo_command_with_sanitization;
+use formatting::format_serror;
+use interpolate::Interpolator;
+use komodo_client::entities::{
+  all_logs_success,
+  stack::{ComposeFile, StackServiceNames},
+  update::Log,
+};
+use periphery_client::api::{
+  compose::ComposeUpResponse, swarm::DeploySwarmStack,
+};
+use resolver_api::Resolve;
+
+use crate::{api::Args, config::periphery_config};
+
+use super::{docker_compose, helpers::*, write};
+
+impl Resolve<Args> for DeploySwarmStack {
+  #[instrument(
+    "DeploySwarmStack",
+    skip_all,
+    fields(
+      stack = self.stack.name,
+      repo = self.repo.as_ref().map(|repo| &repo.name),
+    )
+  )]
+  async fn resolve(
+    self,
+    args: &Args,
+  ) -> anyhow::Result<ComposeUpResponse> {
+    let DeploySwarmStack {
+      mut stack,
+      repo,
+      git_token,
+      registry_token,
+      mut replacers,
+    } = self;
+
+    let mut res = ComposeUpResponse::default();
+
+    let mut interpolator =
+      Interpolator::new(None, &periphery_config().secrets);
+    interpolator
+      .interpolate_stack(&mut stack)?
+      .push_logs(&mut res.logs);
+    replacers.extend(interpolator.secret_replacers);
+
+    let (run_directory, env_file_path) = match write::stack(
+      &stack,
+      repo.as_ref(),
+      git_token,
+      replacers.clone(),
+      &mut res,
+      args,
+    )
+    .await
+    {
+      Ok(res) => res,
+      Err(e) => {
+        res
+          .logs
+          .push(Log::error("Write Stack", format_serror(&e.into())));
+        return Ok(res);
+      }
+    };
+
+    let run_directory = run_directory.canonicalize().context(
+      "Failed to validate run directory on host after stack write (canonicalize error)",
+    )?;
+
+    validate_files(&stack, &run_directory, &mut res).await;
+    if !all_logs_success(&res.logs) {
+      return Ok(res);
+    }
+
+    maybe_login_registry(&stack, registry_token, &mut res.logs).await;
+    if !all_logs_success(&res.logs) {
+      return Ok(res);
+    }
+
+    if !stack.config.pre_deploy.is_none()
+      && let Some(log) = run_deploy_command(
+        "Pre Deploy",
+        &run_directory,
+        &stack.config.pre_deploy,
+        stack.config.deploy_command_timeout_seconds,
+        &replacers,
+      )
+      .await
+    {
+      res.logs.push(log);
+      if !all_logs_success(&res.logs) {
+        return Ok(res);
+      }
+    }
+
+    let docker_compose = docker_compose();
+    let mut file_args = stack.compose_file_paths().join(" -f ");
+    let project_name = stack.project_name(true);
+
+    match write_secret_files(&stack, &project_name, &mut res.logs)
+      .await
+    {
+      Ok(Some(path)) => {
+        file_args.push_str(&format!(" -f {}", path.display()))
+      }
+      Ok(None) => {}
+      Err(e) => {
+        res.logs.push(Log::error(
+          "Write Secret Files",
+          format_serror(&e.into()),
+        ));
+        return Ok(res);
+      }
+    }
+
+    let env_file_args = env_file_args(
+      env_file_path,
+      &stack.config.additional_env_files,
+    )?;
+
+    // 'docker stack deploy' doesn't read env files,
+    // so compose renders the interpolated config to deploy.
+    let config_command = format!(
+      "{docker_compose} -p {project_name} -f {file_args}{env_file_args} config",
+    );
+
+    let Some(config_log) = run_komodo_command_with_sanitization(
+      "Compose Config",
+      run_directory.as_path(),
+      &config_command,
+      false,
+      &replacers,
+    )
+    .await
+    else {
+      unreachable!()
+    };
+    if !config_log.success {
+      res.logs.push(config_log);
+      return Ok(res);
+    }
+    let compose =
+      serde_yaml_ng::from_str::<ComposeFile>(&config_log.stdout)
+        .context("Failed to parse compose contents")?;
+    res.compose_config = Some(config_log.stdout);
+    // Swarm services are named '{project}_{service}',
+    // the task containers add the slot and task id.
+    for (service_name, service) in compose.services {
+      res.services.push(StackServiceNames {
+        container_name: format!("{project_name}_{service_name}"),
+        service_name,
+        image: service.image.unwrap_or_default(),
+      });
+    }
+
+    let command = format!(
+      "{config_command} | docker stack deploy --with-registry-auth -c - {project_name}",
+    );
+    let Some(log) = run_komodo_command_with_sanitization(
+      "Stack Deploy",
+      run_directory.as_path(),
+      command,
+      false,
+      &replacers,
+    )
+    .await
+    else {
+      unreachable!()
+    };
+
+    res.deployed = log.success;
+    res.logs.push(log);
+
+    if res.deployed
+      && !stack.config.post_deploy.is_none()
+      && let Some(log) = run_deploy_command(
+        "Post Deploy",
+        &run_directory,
+        &stack.config.post_deploy,
+        stack.config.deploy_command_timeout_seconds,
+        &replacers,
+      )
+      .await
+    {
+      res.logs.push(log);
+    }
+
+    Ok(res)
+  }
+}
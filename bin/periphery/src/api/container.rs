@@ -1,20 +1,35 @@
-use anyhow::Context;
+use std::{
+  path::{Path, PathBuf},
+  sync::OnceLock,
+  time::Duration,
+};
+
+use anyhow::{Context, anyhow};
 use command::run_komodo_command;
 use futures::future::join_all;
 use komodo_client::entities::{
+  NoData,
+  deployment::{DeploymentHealth, DeploymentHealthProbe},
   docker::{
     container::{Container, ContainerListItem, ContainerStats},
     stats::FullContainerStats,
   },
+  komodo_timestamp,
   update::Log,
 };
-use periphery_client::api::container::*;
+use periphery_client::api::{container::*, file::PulledFile};
 use resolver_api::Resolve;
+use shell_escape::unix::escape;
 
 use crate::{
-  docker::{stats::get_container_stats, stop_container_command},
+  config::periphery_config,
+  docker::{
+    runtime::docker_cli, stats::get_container_stats,
+    stop_container_command,
+  },
+  file::{hash_file, resolve_path, send_file},
   helpers::format_log_grep,
-  state::docker_client,
+  state::{container_stats, docker_client},
 };
 
 // ======
@@ -40,7 +55,7 @@ impl Resolve<super::Args> for InspectContainer {
 //
 
 impl Resolve<super::Args> for GetContainerLog {
-  async fn resolve(self, _: &super::Args) -> anyhow::Result<Log> {
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
     let GetContainerLog {
       name,
       tail,
@@ -51,9 +66,14 @@ impl Resolve<super::Args> for GetContainerLog {
     } else {
       Default::default()
     };
-    let command =
-      format!("docker logs {name} --tail {tail}{timestamps}");
-    Ok(run_komodo_command("Get container log", None, command).await)
+    let command = format!(
+      "{docker} logs {name} --tail {tail}{timestamps}",
+      docker = docker_cli()
+    );
+    args
+      .run_command("Get container log", None, command, false, &[])
+      .await
+      .context("Container log command is empty")
   }
 }
 
@@ -74,8 +94,9 @@ impl Resolve<super::Args> for GetContainerLogSearch {
     } else {
       Default::default()
     };
+    let docker = docker_cli();
     let command = format!(
-      "docker logs {name} --tail 5000{timestamps} 2>&1 | {grep}"
+      "{docker} logs {name} --tail 5000{timestamps} 2>&1 | {grep}"
     );
     Ok(
       run_komodo_command("Get container log grep", None, command)
@@ -91,9 +112,15 @@ impl Resolve<super::Args> for GetContainerStats {
     self,
     _: &super::Args,
   ) -> anyhow::Result<ContainerStats> {
-    let mut stats = get_container_stats(Some(self.name)).await?;
-    let stats =
+    let mut stats =
+      get_container_stats(Some(self.name.clone())).await?;
+    let mut stats =
       stats.pop().context("No stats found for container")?;
+    // Rates are only available from the polling cache
+    stats.io_rates = container_stats()
+      .load()
+      .get(&self.name)
+      .and_then(|stats| stats.io_rates);
     Ok(stats)
   }
 }
@@ -125,6 +152,209 @@ impl Resolve<super::Args> for GetContainerStatsList {
   }
 }
 
+//
+
+impl Resolve<super::Args> for RunHealthProbe {
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<DeploymentHealth> {
+    let timeout = Duration::from_secs(self.timeout.max(1) as u64);
+    let res = tokio::time::timeout(
+      timeout,
+      run_health_probe(&self.name, self.probe),
+    )
+    .await
+    .unwrap_or_else(|_| {
+      Err(anyhow!("Timed out after {}s", timeout.as_secs()))
+    });
+    let (healthy, message) = match res {
+      Ok(message) => (true, message),
+      Err(e) => (false, format!("{e:#}")),
+    };
+    Ok(DeploymentHealth {
+      healthy,
+      message,
+      ts: komodo_timestamp(),
+    })
+  }
+}
+
+//
+
+impl Resolve<super::Args> for SubscribeDockerEvents {
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<NoData> {
+    crate::docker::events::subscribe(&args.core);
+    Ok(NoData {})
+  }
+}
+
+//
+
+impl Resolve<super::Args> for CopyFromContainer {
+  #[instrument(
+    "CopyFromContainer",
+    skip_all,
+    fields(
+      core = args.core,
+      container = self.container,
+      path = self.path,
+      channel = self.channel.to_string(),
+      offset = self.offset,
+      dry_run = self.dry_run,
+    )
+  )]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<ContainerArchive> {
+    let CopyFromContainer {
+      container,
+      path,
+      channel,
+      offset,
+      dry_run,
+    } = self;
+    let directory = container_copy_directory();
+    let archive = directory.join(format!("{channel}.tar"));
+    // A resumed copy reuses the archive from the first attempt,
+    // so the contents don't change underneath the offset.
+    if offset == 0 || !archive.is_file() {
+      tokio::fs::create_dir_all(&directory).await.with_context(
+        || format!("Failed to create directory {directory:?}"),
+      )?;
+      let command = format!(
+        "{docker} cp {} - > {}",
+        escape(format!("{container}:{path}").into()),
+        archive.display(),
+        docker = docker_cli(),
+      );
+      let log =
+        run_komodo_command("Docker Copy", None, command).await;
+      if !log.success {
+        let _ = tokio::fs::remove_file(&archive).await;
+        return Err(anyhow!("{}", log.combined()))
+          .context("Failed to copy path out of container");
+      }
+    }
+    let entries = list_archive(&archive).await?;
+    let res = if dry_run {
+      match tokio::fs::metadata(&archive).await {
+        Ok(metadata) => {
+          hash_file(&archive).await.map(|sha256| PulledFile {
+            size: metadata.len(),
+            sha256,
+          })
+        }
+        Err(e) => Err(e).context("Failed to read archive metadata"),
+      }
+    } else {
+      send_file(&args.core, channel, &archive, offset).await
+    };
+    // Keep the archive if sending fails, so Core can resume.
+    if dry_run || res.is_ok() {
+      let _ = tokio::fs::remove_file(&archive).await;
+    }
+    let PulledFile { size, sha256 } = res?;
+    Ok(ContainerArchive {
+      size,
+      sha256,
+      entries,
+    })
+  }
+}
+
+/// Archives copied to or from containers are staged here.
+fn container_copy_directory() -> PathBuf {
+  periphery_config().root_directory.join("container-copy")
+}
+
+/// Returns the verbose tar listing of the archive.
+async fn list_archive(archive: &Path) -> anyhow::Result<Vec<String>> {
+  let command = format!("tar -tvf {}", archive.display());
+  let log = run_komodo_command("List Archive", None, command).await;
+  if !log.success {
+    return Err(anyhow!("{}", log.combined()))
+      .context("Failed to list archive entries");
+  }
+  Ok(
+    log
+      .stdout
+      .lines()
+      .map(str::trim)
+      .filter(|line| !line.is_empty())
+      .map(str::to_string)
+      .collect(),
+  )
+}
+
+/// Returns the probe output on success.
+async fn run_health_probe(
+  container: &str,
+  probe: DeploymentHealthProbe,
+) -> anyhow::Result<String> {
+  match probe {
+    DeploymentHealthProbe::None => {
+      Err(anyhow!("No health probe configured"))
+    }
+    DeploymentHealthProbe::Http { url } => {
+      let status = health_probe_http_client()
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {url}"))?
+        .status();
+      if status.is_success() || status.is_redirection() {
+        Ok(format!("GET {url} | {status}"))
+      } else {
+        Err(anyhow!("GET {url} | {status}"))
+      }
+    }
+    DeploymentHealthProbe::Tcp { address } => {
+      tokio::net::TcpStream::connect(&address)
+        .await
+        .with_context(|| format!("Failed to connect to {address}"))?;
+      Ok(format!("Connected to {address}"))
+    }
+    DeploymentHealthProbe::Command { command } => {
+      let log = run_komodo_command(
+        "Health Probe",
+        None,
+        format!(
+          "{docker} exec {container} sh -c {}",
+          escape(command.into()),
+          docker = docker_cli(),
+        ),
+      )
+      .await;
+      let output = if log.stderr.is_empty() {
+        log.stdout
+      } else {
+        log.combined()
+      };
+      if log.success {
+        Ok(output)
+      } else {
+        Err(anyhow!("{output}"))
+      }
+    }
+  }
+}
+
+fn health_probe_http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(|| {
+    reqwest::Client::builder()
+      // Only the probed endpoint itself should count.
+      .redirect(reqwest::redirect::Policy::none())
+      .build()
+      .expect("Failed to build health probe http client")
+  })
+}
+
 // =========
 //  ACTIONS
 // =========
@@ -143,7 +373,11 @@ impl Resolve<super::Args> for StartContainer {
       run_komodo_command(
         "Docker Start",
         None,
-        format!("docker start {}", self.name),
+        format!(
+          "{docker} start {}",
+          self.name,
+          docker = docker_cli()
+        ),
       )
       .await,
     )
@@ -166,7 +400,11 @@ impl Resolve<super::Args> for RestartContainer {
       run_komodo_command(
         "Docker Restart",
         None,
-        format!("docker restart {}", self.name),
+        format!(
+          "{docker} restart {}",
+          self.name,
+          docker = docker_cli()
+        ),
       )
       .await,
     )
@@ -189,7 +427,11 @@ impl Resolve<super::Args> for PauseContainer {
       run_komodo_command(
         "Docker Pause",
         None,
-        format!("docker pause {}", self.name),
+        format!(
+          "{docker} pause {}",
+          self.name,
+          docker = docker_cli()
+        ),
       )
       .await,
     )
@@ -210,7 +452,11 @@ impl Resolve<super::Args> for UnpauseContainer {
       run_komodo_command(
         "Docker Unpause",
         None,
-        format!("docker unpause {}", self.name),
+        format!(
+          "{docker} unpause {}",
+          self.name,
+          docker = docker_cli()
+        ),
       )
       .await,
     )
@@ -265,15 +511,19 @@ impl Resolve<super::Args> for RemoveContainer {
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
     let RemoveContainer { name, signal, time } = self;
     let stop_command = stop_container_command(&name, signal, time);
-    let command =
-      format!("{stop_command} && docker container rm {name}");
+    let command = format!(
+      "{stop_command} && {docker} container rm {name}",
+      docker = docker_cli()
+    );
     let log =
       run_komodo_command("Docker Stop and Remove", None, command)
         .await;
     if log.stderr.contains("unknown flag: --signal") {
       let stop_command = stop_container_command(&name, None, time);
-      let command =
-        format!("{stop_command} && docker container rm {name}");
+      let command = format!(
+        "{stop_command} && {docker} container rm {name}",
+        docker = docker_cli()
+      );
       let mut log =
         run_komodo_command("Docker Stop and Remove", None, command)
           .await;
@@ -309,17 +559,323 @@ impl Resolve<super::Args> for RenameContainer {
       curr_name,
       new_name,
     } = self;
-    let command = format!("docker rename {curr_name} {new_name}");
+    let command = format!(
+      "{docker} rename {curr_name} {new_name}",
+      docker = docker_cli()
+    );
     Ok(run_komodo_command("Docker Rename", None, command).await)
   }
 }
 
 //
 
+impl Resolve<super::Args> for CommitContainer {
+  #[instrument(
+    "CommitContainer",
+    skip_all,
+    fields(
+      core = args.core,
+      container = self.name,
+      image = self.image,
+      pause = self.pause,
+    )
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let CommitContainer {
+      name,
+      image,
+      author,
+      message,
+      pause,
+    } = self;
+    let author = author
+      .filter(|author| !author.is_empty())
+      .map(|author| format!(" --author {}", escape(author.into())))
+      .unwrap_or_default();
+    let message = message
+      .filter(|message| !message.is_empty())
+      .map(|message| format!(" --message {}", escape(message.into())))
+      .unwrap_or_default();
+    let command = format!(
+      "{docker} commit --pause={pause}{author}{message} {name} {}",
+      escape(image.into()),
+      docker = docker_cli()
+    );
+    Ok(run_komodo_command("Docker Commit", None, command).await)
+  }
+}
+
+//
+
+impl Resolve<super::Args> for CopyIntoContainer {
+  #[instrument(
+    "CopyIntoContainer",
+    skip_all,
+    fields(
+      core = args.core,
+      container = self.container,
+      archive = format!("{:?}", self.archive),
+      destination = self.destination,
+      dry_run = self.dry_run,
+    )
+  )]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<Vec<Log>> {
+    let CopyIntoContainer {
+      container,
+      archive,
+      destination,
+      owner,
+      mode,
+      dry_run,
+    } = self;
+    let archive = resolve_path(archive);
+    let res = copy_into_container(
+      &container,
+      &archive,
+      &destination,
+      owner,
+      mode,
+      dry_run,
+    )
+    .await;
+    let _ = tokio::fs::remove_file(&archive).await;
+    res
+  }
+}
+
+async fn copy_into_container(
+  container: &str,
+  archive: &Path,
+  destination: &str,
+  owner: Option<String>,
+  mode: Option<String>,
+  dry_run: bool,
+) -> anyhow::Result<Vec<Log>> {
+  if dry_run {
+    let entries = list_archive(archive).await?;
+    return Ok(vec![Log::simple(
+      "Copy Into Container (Dry Run)",
+      entries.join("\n"),
+    )]);
+  }
+
+  let mut logs = Vec::new();
+
+  let command = format!(
+    "{docker} cp - {} < {}",
+    escape(format!("{container}:{destination}").into()),
+    archive.display(),
+    docker = docker_cli(),
+  );
+  let log = run_komodo_command("Docker Copy", None, command).await;
+  let success = log.success;
+  logs.push(log);
+  if !success {
+    return Ok(logs);
+  }
+
+  // Only the top level entries of the archive are changed,
+  // leaving the rest of the destination as is. Entries climbing
+  // out with '..' are skipped, so the recursive chown / chmod
+  // can't reach outside the destination.
+  let mut targets = Vec::new();
+  for entry in tar_entry_names(archive).await? {
+    if entry.split('/').any(|part| part == "..") {
+      continue;
+    }
+    let Some(top) = entry
+      .split('/')
+      .find(|part| !part.is_empty() && *part != ".")
+    else {
+      continue;
+    };
+    let target = escape(
+      format!("{}/{top}", destination.trim_end_matches('/')).into(),
+    )
+    .into_owned();
+    if !targets.contains(&target) {
+      targets.push(target);
+    }
+  }
+  if targets.is_empty() {
+    return Ok(logs);
+  }
+  let targets = targets.join(" ");
+
+  for (stage, program, arg) in [
+    ("Docker Copy Owner", "chown", owner),
+    ("Docker Copy Mode", "chmod", mode),
+  ] {
+    let Some(arg) = arg.filter(|arg| !arg.is_empty()) else {
+      continue;
+    };
+    let command = format!(
+      "{docker} exec -u 0 {} {program} -R {} {targets}",
+      escape(container.into()),
+      escape(arg.into()),
+      docker = docker_cli(),
+    );
+    let log = run_komodo_command(stage, None, command).await;
+    let success = log.success;
+    logs.push(log);
+    if !success {
+      break;
+    }
+  }
+
+  Ok(logs)
+}
+
+/// The entry paths of the archive, without the verbose listing.
+async fn tar_entry_names(
+  archive: &Path,
+) -> anyhow::Result<Vec<String>> {
+  let command = format!("tar -tf {}", archive.display());
+  let log = run_komodo_command("List Archive", None, command).await;
+  if !log.success {
+    return Err(anyhow!("{}", log.combined()))
+      .context("Failed to list archive entries");
+  }
+  Ok(log.stdout.lines().map(str::to_string).collect())
+}
+
+//
+
+impl Resolve<super::Args> for CheckpointContainer {
+  #[instrument(
+    "CheckpointContainer",
+    skip_all,
+    fields(
+      core = args.core,
+      container = self.name,
+      checkpoint = self.checkpoint,
+      leave_running = self.leave_running,
+    )
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let CheckpointContainer {
+      name,
+      checkpoint,
+      leave_running,
+      archive,
+    } = self;
+    let directory = checkpoint_directory(&name, &checkpoint)?;
+    tokio::fs::create_dir_all(&directory).await.with_context(
+      || format!("Failed to create directory {directory:?}"),
+    )?;
+    // An existing checkpoint with the same name fails the create.
+    let _ =
+      tokio::fs::remove_dir_all(directory.join(&checkpoint)).await;
+    let leave_running = if leave_running {
+      " --leave-running"
+    } else {
+      Default::default()
+    };
+    let mut command = format!(
+      "docker checkpoint create{leave_running} --checkpoint-dir {} {name} {checkpoint}",
+      directory.display()
+    );
+    if let Some(archive) = archive {
+      let archive = resolve_path(archive);
+      if let Some(parent) = archive.parent() {
+        tokio::fs::create_dir_all(parent).await.with_context(
+          || format!("Failed to create directory {parent:?}"),
+        )?;
+      }
+      command.push_str(&format!(
+        " && tar cf {} -C {} {checkpoint}",
+        archive.display(),
+        directory.display()
+      ));
+    }
+    Ok(run_komodo_command("Docker Checkpoint", None, command).await)
+  }
+}
+
+//
+
+impl Resolve<super::Args> for RestoreContainer {
+  #[instrument(
+    "RestoreContainer",
+    skip_all,
+    fields(
+      core = args.core,
+      container = self.name,
+      checkpoint = self.checkpoint,
+    )
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let RestoreContainer {
+      name,
+      checkpoint,
+      archive,
+    } = self;
+    let directory = checkpoint_directory(&name, &checkpoint)?;
+    let archive = archive.map(resolve_path);
+    let mut command = String::new();
+    if let Some(archive) = &archive {
+      tokio::fs::create_dir_all(&directory).await.with_context(
+        || format!("Failed to create directory {directory:?}"),
+      )?;
+      let _ =
+        tokio::fs::remove_dir_all(directory.join(&checkpoint)).await;
+      command = format!(
+        "tar xf {} -C {} && ",
+        archive.display(),
+        directory.display()
+      );
+    }
+    command.push_str(&format!(
+      "docker start --checkpoint {checkpoint} --checkpoint-dir {} {name}",
+      directory.display()
+    ));
+    let log =
+      run_komodo_command("Docker Restore", None, command).await;
+    if let Some(archive) = archive {
+      let _ = tokio::fs::remove_file(&archive).await;
+    }
+    Ok(log)
+  }
+}
+
+/// Errors unless `enable_container_checkpoints` is set.
+/// The container and checkpoint names are validated
+/// to stay inside the checkpoint directory.
+fn checkpoint_directory(
+  name: &str,
+  checkpoint: &str,
+) -> anyhow::Result<PathBuf> {
+  let config = periphery_config();
+  if !config.enable_container_checkpoints {
+    return Err(anyhow!(
+      "Container checkpoints are disabled in the periphery config"
+    ));
+  }
+  for name in [name, checkpoint] {
+    if name.is_empty()
+      || name.starts_with(['.', '-'])
+      || !name.chars().all(|c| {
+        c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')
+      })
+    {
+      return Err(anyhow!(
+        "Invalid container or checkpoint name: {name}"
+      ));
+    }
+  }
+  Ok(config.root_directory.join("checkpoints").join(name))
+}
+
+//
+
 impl Resolve<super::Args> for PruneContainers {
   #[instrument("PruneContainers", skip_all, fields(core = args.core))]
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
-    let command = String::from("docker container prune -f");
+    let command =
+      format!("{docker} container prune -f", docker = docker_cli());
     Ok(run_komodo_command("Prune Containers", None, command).await)
   }
 }
@@ -348,7 +904,8 @@ impl Resolve<super::Args> for StartAllContainers {
         {
           return None;
         }
-        let command = format!("docker start {name}");
+        let command =
+          format!("{docker} start {name}", docker = docker_cli());
         Some(async move {
           run_komodo_command(&command.clone(), None, command).await
         })
@@ -382,7 +939,8 @@ impl Resolve<super::Args> for RestartAllContainers {
         {
           return None;
         }
-        let command = format!("docker restart {name}");
+        let command =
+          format!("{docker} restart {name}", docker = docker_cli());
         Some(async move {
           run_komodo_command(&command.clone(), None, command).await
         })
@@ -416,7 +974,8 @@ impl Resolve<super::Args> for PauseAllContainers {
         {
           return None;
         }
-        let command = format!("docker pause {name}");
+        let command =
+          format!("{docker} pause {name}", docker = docker_cli());
         Some(async move {
           run_komodo_command(&command.clone(), None, command).await
         })
@@ -450,7 +1009,8 @@ impl Resolve<super::Args> for UnpauseAllContainers {
         {
           return None;
         }
-        let command = format!("docker unpause {name}");
+        let command =
+          format!("{docker} unpause {name}", docker = docker_cli());
         Some(async move {
           run_komodo_command(&command.clone(), None, command).await
         })
@@ -486,7 +1046,7 @@ impl Resolve<super::Args> for StopAllContainers {
         }
         Some(async move {
           run_komodo_command(
-            &format!("docker stop {name}"),
+            &format!("{docker} stop {name}", docker = docker_cli()),
             None,
             stop_container_command(name, None, None),
           )
@@ -1,10 +1,12 @@
+use std::time::Duration;
+
 use anyhow::Context;
 use command::run_komodo_command_with_sanitization;
 use formatting::format_serror;
 use interpolate::Interpolator;
 use komodo_client::{
   entities::{
-    EnvironmentVar,
+    EnvironmentVar, TerminationSignal,
     deployment::{
       Conversion, Deployment, DeploymentConfig, DeploymentImage,
       RestartMode, conversions_from_str, extract_registry_domain,
@@ -14,13 +16,22 @@ use komodo_client::{
   },
   parsers::QUOTE_PATTERN,
 };
-use periphery_client::api::container::{Deploy, RemoveContainer};
+use periphery_client::api::container::{
+  Deploy, RemoveContainer, RollbackDeploy,
+};
 use resolver_api::Resolve;
 
 use crate::{
   config::periphery_config,
-  docker::{docker_login, pull_image},
+  docker::{
+    docker_login, health::wait_for_healthy, pull_image,
+    runtime::docker_cli,
+  },
   helpers::{format_extra_args, format_labels},
+  rollback::{
+    container_image_id, deployment_rollback_target,
+    finish_deployment_rollback, record_deployment,
+  },
 };
 
 impl Resolve<super::Args> for Deploy {
@@ -82,36 +93,157 @@ impl Resolve<super::Args> for Deploy {
     let _ = pull_image(image).await;
     debug!("image pulled");
 
-    let _ = (RemoveContainer {
-      name: deployment.name.clone(),
-      signal: stop_signal,
-      time: stop_time,
-    })
-    .resolve(args)
-    .await;
-    debug!("container stopped and removed");
+    let log = run_container(
+      args,
+      &deployment,
+      image,
+      stop_signal,
+      stop_time,
+      &replacers,
+    )
+    .await?;
 
-    let command = docker_run_command(&deployment, image)
-      .context("Unable to generate valid docker run command")?;
+    if log.success
+      && let Err(e) = record_deployment(&deployment.name, image).await
+    {
+      warn!(
+        "Failed to record deployment {} for rollback | {e:#}",
+        deployment.name
+      );
+    }
 
-    let Some(log) = run_komodo_command_with_sanitization(
-      "Docker Run",
-      None,
-      command,
-      false,
-      &replacers,
+    Ok(log)
+  }
+}
+
+impl Resolve<super::Args> for RollbackDeploy {
+  #[instrument(
+    "RollbackDeploy",
+    skip_all,
+    fields(
+      deployment = &self.deployment.name,
+      stop_signal = format!("{:?}", self.stop_signal),
+      stop_time = self.stop_time,
+    )
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let RollbackDeploy {
+      mut deployment,
+      stop_signal,
+      stop_time,
+      mut replacers,
+    } = self;
+
+    let mut interpolator =
+      Interpolator::new(None, &periphery_config().secrets);
+    interpolator.interpolate_deployment(&mut deployment)?;
+    replacers.extend(interpolator.secret_replacers);
+
+    let current = container_image_id(&deployment.name).await;
+    let Some((index, target)) = deployment_rollback_target(
+      &deployment.name,
+      current.as_deref(),
     )
     .await
     else {
-      // The none case is only for empty command,
-      // this won't be the case given it is populated above.
-      unreachable!()
+      return Ok(Log::error(
+        "Rollback",
+        format!(
+          "No previous image recorded for deployment {}",
+          deployment.name
+        ),
+      ));
     };
 
+    let mut log = run_container(
+      args,
+      &deployment,
+      &target.image_id,
+      stop_signal,
+      stop_time,
+      &replacers,
+    )
+    .await?;
+    log.stage = String::from("Rollback");
+    log.stdout = format!(
+      "Rolled back to {} ({})\n\n{}",
+      target.image, target.image_id, log.stdout
+    );
+
+    if log.success
+      && let Err(e) =
+        finish_deployment_rollback(&deployment.name, index).await
+    {
+      warn!(
+        "Failed to update rollback history for deployment {} | {e:#}",
+        deployment.name
+      );
+    }
+
     Ok(log)
   }
 }
 
+/// Replaces the container with one running the image,
+/// waiting for the healthcheck if configured.
+async fn run_container(
+  args: &super::Args,
+  deployment: &Deployment,
+  image: &str,
+  stop_signal: Option<TerminationSignal>,
+  stop_time: Option<i32>,
+  replacers: &[(String, String)],
+) -> anyhow::Result<Log> {
+  let _ = (RemoveContainer {
+    name: deployment.name.clone(),
+    signal: stop_signal,
+    time: stop_time,
+  })
+  .resolve(args)
+  .await;
+  debug!("container stopped and removed");
+
+  let command = docker_run_command(deployment, image)
+    .context("Unable to generate valid docker run command")?;
+
+  let Some(mut log) = run_komodo_command_with_sanitization(
+    "Docker Run",
+    None,
+    command,
+    false,
+    replacers,
+  )
+  .await
+  else {
+    // The none case is only for empty command,
+    // this won't be the case given it is populated above.
+    unreachable!()
+  };
+
+  if log.success && deployment.config.wait_for_healthcheck {
+    let health = wait_for_healthy(
+      args,
+      std::slice::from_ref(&deployment.name),
+      Duration::from_secs(
+        deployment.config.healthcheck_timeout.max(0) as u64,
+      ),
+    )
+    .await;
+    // Deploy responds with a single log,
+    // so the health status is added to the run log.
+    log.success = health.success;
+    log
+      .stdout
+      .push_str(&format!("\n\n{}:\n{}", health.stage, health.stdout));
+    if !health.stderr.is_empty() {
+      log.stderr.push_str(&format!("\n{}", health.stderr));
+    }
+    log.end_ts = health.end_ts;
+  }
+
+  Ok(log)
+}
+
 fn docker_run_command(
   Deployment {
     name,
@@ -150,8 +282,9 @@ fn docker_run_command(
   );
   let command = parse_command(command);
   let extra_args = format_extra_args(extra_args);
+  let docker = docker_cli();
   let command = format!(
-    "docker run -d --name {name}{ports}{volumes}{network}{restart}{environment}{labels}{extra_args} {image}{command}"
+    "{docker} run -d --name {name}{ports}{volumes}{network}{restart}{environment}{labels}{extra_args} {image}{command}"
   );
   Ok(command)
 }
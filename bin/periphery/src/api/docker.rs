@@ -1,6 +1,6 @@
-use std::sync::OnceLock;
+use std::{path::PathBuf, sync::OnceLock};
 
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use cache::TimeoutCache;
 use command::run_komodo_command;
 use komodo_client::entities::{
@@ -13,10 +13,16 @@ use komodo_client::entities::{
   komodo_timestamp,
   update::Log,
 };
-use periphery_client::api::docker::*;
+use periphery_client::api::{docker::*, file::PulledFile};
 use resolver_api::Resolve;
+use shell_escape::unix::escape;
 
-use crate::{docker::docker_login, state::docker_client};
+use crate::{
+  config::periphery_config,
+  docker::{docker_login, runtime::docker_cli},
+  file::{resolve_path, send_file},
+  state::docker_client,
+};
 
 // =====
 // IMAGE
@@ -92,7 +98,7 @@ impl Resolve<super::Args> for PullImage {
         run_komodo_command(
           "Docker Pull",
           None,
-          format!("docker pull {name}"),
+          format!("{docker} pull {name}", docker = docker_cli()),
         )
         .await,
       )
@@ -112,7 +118,11 @@ impl Resolve<super::Args> for PullImage {
 impl Resolve<super::Args> for DeleteImage {
   #[instrument("DeleteImage", skip_all, fields(image_name = self.name, core = args.core))]
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
-    let command = format!("docker image rm {}", self.name);
+    let command = format!(
+      "{docker} image rm {}",
+      self.name,
+      docker = docker_cli()
+    );
     Ok(run_komodo_command("Delete Image", None, command).await)
   }
 }
@@ -122,11 +132,91 @@ impl Resolve<super::Args> for DeleteImage {
 impl Resolve<super::Args> for PruneImages {
   #[instrument("PruneImages", skip_all, fields(core = args.core))]
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
-    let command = String::from("docker image prune -a -f");
+    let command =
+      format!("{docker} image prune -a -f", docker = docker_cli());
     Ok(run_komodo_command("Prune Images", None, command).await)
   }
 }
 
+//
+
+impl Resolve<super::Args> for ExportImage {
+  #[instrument(
+    "ExportImage",
+    skip_all,
+    fields(
+      core = args.core,
+      image_name = self.name,
+      channel = self.channel.to_string(),
+      offset = self.offset,
+    )
+  )]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<PulledFile> {
+    let ExportImage {
+      name,
+      channel,
+      offset,
+    } = self;
+    let directory = image_transfer_directory();
+    let archive = directory.join(format!("{channel}.tar"));
+    // A resumed export reuses the archive from the first attempt,
+    // so the contents don't change underneath the offset.
+    if offset == 0 || !archive.is_file() {
+      tokio::fs::create_dir_all(&directory).await.with_context(
+        || format!("Failed to create directory {directory:?}"),
+      )?;
+      let command = format!(
+        "{docker} save -o {} {}",
+        archive.display(),
+        escape(name.into()),
+        docker = docker_cli(),
+      );
+      let log =
+        run_komodo_command("Docker Save", None, command).await;
+      if !log.success {
+        let _ = tokio::fs::remove_file(&archive).await;
+        return Err(anyhow!("{}", log.combined()))
+          .context("Failed to save image");
+      }
+    }
+    let res = send_file(&args.core, channel, &archive, offset).await;
+    // Keep the archive if sending fails, so Core can resume.
+    if res.is_ok() {
+      let _ = tokio::fs::remove_file(&archive).await;
+    }
+    res
+  }
+}
+
+//
+
+impl Resolve<super::Args> for ImportImage {
+  #[instrument(
+    "ImportImage",
+    skip_all,
+    fields(core = args.core, archive = format!("{:?}", self.archive))
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let archive = resolve_path(self.archive);
+    let command = format!(
+      "{docker} load -i {}",
+      archive.display(),
+      docker = docker_cli()
+    );
+    let log = run_komodo_command("Docker Load", None, command).await;
+    let _ = tokio::fs::remove_file(&archive).await;
+    Ok(log)
+  }
+}
+
+/// Archives of exported images are staged here.
+fn image_transfer_directory() -> PathBuf {
+  periphery_config().root_directory.join("image-transfer")
+}
+
 // =======
 // NETWORK
 // =======
@@ -152,7 +242,10 @@ impl Resolve<super::Args> for CreateNetwork {
       Some(driver) => format!(" -d {driver}"),
       None => String::new(),
     };
-    let command = format!("docker network create{driver} {name}");
+    let command = format!(
+      "{docker} network create{driver} {name}",
+      docker = docker_cli()
+    );
     Ok(run_komodo_command("Create Network", None, command).await)
   }
 }
@@ -162,7 +255,11 @@ impl Resolve<super::Args> for CreateNetwork {
 impl Resolve<super::Args> for DeleteNetwork {
   #[instrument("DeleteNetwork", skip_all, fields(network_name = self.name, core = args.core))]
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
-    let command = format!("docker network rm {}", self.name);
+    let command = format!(
+      "{docker} network rm {}",
+      self.name,
+      docker = docker_cli()
+    );
     Ok(run_komodo_command("Delete Network", None, command).await)
   }
 }
@@ -172,7 +269,8 @@ impl Resolve<super::Args> for DeleteNetwork {
 impl Resolve<super::Args> for PruneNetworks {
   #[instrument("PruneNetworks", skip_all, fields(core = args.core))]
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
-    let command = String::from("docker network prune -f");
+    let command =
+      format!("{docker} network prune -f", docker = docker_cli());
     Ok(run_komodo_command("Prune Networks", None, command).await)
   }
 }
@@ -197,7 +295,11 @@ impl Resolve<super::Args> for InspectVolume {
 impl Resolve<super::Args> for DeleteVolume {
   #[instrument("DeleteVolume", skip_all, fields(volume_name = self.name, core = args.core))]
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
-    let command = format!("docker volume rm {}", self.name);
+    let command = format!(
+      "{docker} volume rm {}",
+      self.name,
+      docker = docker_cli()
+    );
     Ok(run_komodo_command("Delete Volume", None, command).await)
   }
 }
@@ -207,7 +309,129 @@ impl Resolve<super::Args> for DeleteVolume {
 impl Resolve<super::Args> for PruneVolumes {
   #[instrument("PruneVolumes", skip_all, fields(core = args.core))]
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
-    let command = String::from("docker volume prune -a -f");
+    let command =
+      format!("{docker} volume prune -a -f", docker = docker_cli());
     Ok(run_komodo_command("Prune Volumes", None, command).await)
   }
 }
+
+//
+
+impl Resolve<super::Args> for ListComposeVolumes {
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<Vec<String>> {
+    let command = format!(
+      "{docker} volume ls --filter label=com.docker.compose.project={} --format '{{{{.Name}}}}'",
+      escape(self.project.into()),
+      docker = docker_cli(),
+    );
+    let log =
+      run_komodo_command("List Compose Volumes", None, command).await;
+    if !log.success {
+      return Err(anyhow!("{}", log.combined()))
+        .context("Failed to list compose project volumes");
+    }
+    Ok(
+      log
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect(),
+    )
+  }
+}
+
+//
+
+/// The image used to read / write the volume contents.
+const VOLUME_ARCHIVE_IMAGE: &str = "alpine";
+
+fn volume_archive_directory() -> PathBuf {
+  periphery_config().root_directory.join("volumes")
+}
+
+/// Volume names are validated to stay inside the archive directory.
+fn volume_archive_path(name: &str) -> anyhow::Result<PathBuf> {
+  if name.is_empty()
+    || name.starts_with(['.', '-'])
+    || !name.chars().all(|c| {
+      c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')
+    })
+  {
+    return Err(anyhow!("Invalid volume name: {name}"));
+  }
+  Ok(volume_archive_directory().join(format!("{name}.tar.gz")))
+}
+
+impl Resolve<super::Args> for ArchiveVolume {
+  #[instrument("ArchiveVolume", skip_all, fields(volume_name = self.name, core = args.core))]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    volume_archive_path(&self.name)?;
+    let directory = volume_archive_directory();
+    tokio::fs::create_dir_all(&directory).await.with_context(
+      || format!("Failed to create directory {directory:?}"),
+    )?;
+    let command = format!(
+      "{docker} run --rm -v {name}:/volume:ro -v {directory}:/archive {VOLUME_ARCHIVE_IMAGE} tar czf /archive/{name}.tar.gz -C /volume .",
+      name = self.name,
+      directory = directory.display(),
+      docker = docker_cli(),
+    );
+    Ok(run_komodo_command("Archive Volume", None, command).await)
+  }
+}
+
+//
+
+impl Resolve<super::Args> for RestoreVolume {
+  #[instrument("RestoreVolume", skip_all, fields(volume_name = self.name, core = args.core))]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let path = volume_archive_path(&self.name)?;
+    if !path.exists() {
+      return Err(anyhow!("No archive found at {path:?}"));
+    }
+    let labels = self
+      .labels
+      .iter()
+      .map(|(key, value)| {
+        format!(
+          " --label {}",
+          escape(format!("{key}={value}").into())
+        )
+      })
+      .collect::<String>();
+    let command = format!(
+      "({docker} volume inspect {name} > /dev/null 2>&1 || {docker} volume create{labels} {name}) && {docker} run --rm -v {name}:/volume -v {directory}:/archive:ro {VOLUME_ARCHIVE_IMAGE} tar xzf /archive/{name}.tar.gz -C /volume",
+      name = self.name,
+      directory = volume_archive_directory().display(),
+      docker = docker_cli(),
+    );
+    Ok(run_komodo_command("Restore Volume", None, command).await)
+  }
+}
+
+//
+
+impl Resolve<super::Args> for DeleteVolumeArchive {
+  #[instrument("DeleteVolumeArchive", skip_all, fields(volume_name = self.name, core = args.core))]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let path = volume_archive_path(&self.name)?;
+    if !path.exists() {
+      return Ok(Log::simple(
+        "Delete Volume Archive",
+        format!("No archive found at {path:?}"),
+      ));
+    }
+    tokio::fs::remove_file(&path)
+      .await
+      .with_context(|| format!("Failed to delete {path:?}"))?;
+    Ok(Log::simple(
+      "Delete Volume Archive",
+      format!("Deleted {path:?}"),
+    ))
+  }
+}
@@ -0,0 +1,95 @@
+use std::{process::Stdio, time::Duration};
+
+use anyhow::Context;
+use komodo_client::entities::{komodo_timestamp, update::Log};
+use periphery_client::api::extension::*;
+use resolver_api::Resolve;
+use serde_json::json;
+use tokio::{io::AsyncWriteExt, process::Command};
+
+use crate::config::periphery_config;
+
+impl Resolve<super::Args> for RunExtension {
+  #[instrument("RunExtension", skip_all, fields(extension = self.extension, command = self.command, core = args.core))]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let extension = periphery_config()
+      .extensions
+      .iter()
+      .find(|extension| extension.name == self.extension)
+      .with_context(|| {
+        format!("No extension named {} is configured", self.extension)
+      })?;
+
+    let stage = format!("{}.{}", extension.name, self.command);
+    let input = serde_json::to_vec(&json!({
+      "command": self.command,
+      "params": self.params,
+    }))
+    .context("Failed to serialize extension input")?;
+
+    let start_ts = komodo_timestamp();
+
+    let mut child = Command::new("sh")
+      .arg("-c")
+      .arg(&extension.command)
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      // Kills the extension if the timeout drops the output future
+      .kill_on_drop(true)
+      .spawn()
+      .with_context(|| {
+        format!("Failed to spawn extension {}", extension.name)
+      })?;
+
+    let mut stdin = child
+      .stdin
+      .take()
+      .context("Failed to open extension stdin")?;
+    // Writing the input is covered by the timeout too,
+    // an extension which never reads it would block the write.
+    let output = async {
+      stdin
+        .write_all(&input)
+        .await
+        .context("Failed to write to extension stdin")?;
+      // Close stdin so the extension knows the input is complete.
+      drop(stdin);
+      child
+        .wait_with_output()
+        .await
+        .context("Failed to run extension")
+    };
+    let output = if extension.timeout_seconds == 0 {
+      output.await
+    } else {
+      match tokio::time::timeout(
+        Duration::from_secs(extension.timeout_seconds),
+        output,
+      )
+      .await
+      {
+        Ok(output) => output,
+        Err(_) => {
+          return Ok(Log::error(
+            &stage,
+            format!(
+              "Extension timed out after {} seconds",
+              extension.timeout_seconds
+            ),
+          ));
+        }
+      }
+    }?;
+
+    Ok(Log {
+      stage,
+      command: extension.command.clone(),
+      stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+      stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+      success: output.status.success(),
+      start_ts,
+      end_ts: komodo_timestamp(),
+    })
+  }
+}
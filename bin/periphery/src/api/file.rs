@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use komodo_client::entities::{NoData, update::Log};
+use periphery_client::api::file::*;
+use resolver_api::Resolve;
+use tokio::sync::Mutex;
+
+use crate::{
+  file::{FileTransfer, resolve_path, send_file},
+  state::file_channels,
+};
+
+impl Resolve<super::Args> for BeginFilePush {
+  #[instrument(
+    "BeginFilePush",
+    skip_all,
+    fields(
+      core = args.core,
+      channel = self.channel.to_string(),
+      path = format!("{:?}", self.path),
+      size = self.size,
+    )
+  )]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<FileTransferState> {
+    let BeginFilePush {
+      channel,
+      path,
+      size,
+    } = self;
+    let path = resolve_path(path);
+    if let Some(transfer) = file_channels().get(&channel).await {
+      let transfer = transfer.lock().await;
+      if transfer.path != path || transfer.size != size {
+        return Err(anyhow!(
+          "File channel {channel} is already in use for another file"
+        ));
+      }
+      return Ok(FileTransferState {
+        offset: transfer.received,
+      });
+    }
+    let transfer = FileTransfer::open(channel, path, size).await?;
+    let offset = transfer.received;
+    file_channels()
+      .insert(channel, Arc::new(Mutex::new(transfer)))
+      .await;
+    Ok(FileTransferState { offset })
+  }
+}
+
+//
+
+impl Resolve<super::Args> for FinishFilePush {
+  #[instrument(
+    "FinishFilePush",
+    skip_all,
+    fields(core = args.core, channel = self.channel.to_string())
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let transfer = file_channels()
+      .get(&self.channel)
+      .await
+      .with_context(|| {
+        format!(
+          "No file push in progress on channel {}",
+          self.channel
+        )
+      })?;
+    let mut transfer = transfer.lock().await;
+    // Keep the transfer around so Core can resume.
+    transfer.check_received()?;
+    let res = transfer.finish(&self.sha256).await;
+    file_channels().remove(&self.channel).await;
+    res?;
+    Ok(Log::simple(
+      "Push File",
+      format!(
+        "Wrote {} bytes to {:?}",
+        transfer.received, transfer.path
+      ),
+    ))
+  }
+}
+
+//
+
+impl Resolve<super::Args> for CancelFilePush {
+  #[instrument(
+    "CancelFilePush",
+    skip_all,
+    fields(core = args.core, channel = self.channel.to_string())
+  )]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<NoData> {
+    let Some(transfer) = file_channels().remove(&self.channel).await
+    else {
+      return Ok(NoData {});
+    };
+    transfer.lock().await.cancel().await?;
+    Ok(NoData {})
+  }
+}
+
+//
+
+impl Resolve<super::Args> for PullFile {
+  #[instrument(
+    "PullFile",
+    skip_all,
+    fields(
+      core = args.core,
+      channel = self.channel.to_string(),
+      path = format!("{:?}", self.path),
+      offset = self.offset,
+    )
+  )]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<PulledFile> {
+    let PullFile {
+      channel,
+      path,
+      offset,
+    } = self;
+    send_file(&args.core, channel, &resolve_path(path), offset).await
+  }
+}
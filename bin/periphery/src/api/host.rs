@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use command::run_komodo_command;
+use komodo_client::entities::{server::HostPowerAction, update::Log};
+use periphery_client::{
+  api::{container::StopAllContainers, host::*},
+  transport::TransportNotice,
+};
+use resolver_api::Resolve;
+
+use crate::{config::periphery_config, state::core_connections};
+
+/// Gives Periphery time to send the response
+/// and flush the drain notices before the host goes down.
+const POWER_ACTION_DELAY: Duration = Duration::from_secs(5);
+
+impl Resolve<super::Args> for RebootHost {
+  #[instrument("RebootHost", skip_all, fields(core = args.core))]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<Vec<Log>> {
+    host_power_action(HostPowerAction::Reboot, args).await
+  }
+}
+
+impl Resolve<super::Args> for ShutdownHost {
+  #[instrument("ShutdownHost", skip_all, fields(core = args.core))]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<Vec<Log>> {
+    host_power_action(HostPowerAction::Shutdown, args).await
+  }
+}
+
+async fn host_power_action(
+  action: HostPowerAction,
+  args: &super::Args,
+) -> anyhow::Result<Vec<Log>> {
+  if !periphery_config().allow_host_power_actions {
+    return Err(anyhow!(
+      "Host power actions are disabled in the periphery config"
+    ));
+  }
+
+  // Stopping the containers lets them shut down cleanly,
+  // rather than being killed when the docker daemon stops.
+  let stop_containers = StopAllContainers {};
+  let mut logs = match stop_containers.resolve(args).await {
+    Ok(logs) => logs,
+    Err(e) => vec![Log::error("Stop Containers", format!("{e:#}"))],
+  };
+
+  let notice = TransportNotice::PeripheryDraining { action };
+  for connection in core_connections().get_values().await {
+    if let Err(e) = connection.sender.send_notice(&notice).await {
+      warn!("Failed to send drain notice to Core | {e:#}");
+    }
+  }
+
+  let (stage, command) = match action {
+    HostPowerAction::Reboot => ("Reboot Host", "systemctl reboot"),
+    HostPowerAction::Shutdown => {
+      ("Shutdown Host", "systemctl poweroff")
+    }
+  };
+  logs.push(Log::simple(
+    stage,
+    format!(
+      "Running '{command}' in {}s",
+      POWER_ACTION_DELAY.as_secs()
+    ),
+  ));
+
+  tokio::spawn(async move {
+    tokio::time::sleep(POWER_ACTION_DELAY).await;
+    let log = run_komodo_command(stage, None, command).await;
+    if !log.success {
+      error!("Failed to run '{command}' | {}", log.stderr);
+    }
+  });
+
+  Ok(logs)
+}
@@ -1,23 +1,41 @@
-use command::run_komodo_command;
-use derive_variants::EnumVariants;
-use encoding::{EncodedJsonMessage, EncodedResponse};
+use std::path::Path;
+
+use anyhow::anyhow;
+use command::{
+  OutputLine, run_komodo_command, run_komodo_command_streamed,
+  run_komodo_command_with_sanitization,
+};
+use derive_variants::{EnumVariants, ExtractVariant};
+use encoding::{
+  BodyEncoding, BodyMessage, Encode as _, ResponseBody,
+};
 use futures::FutureExt;
 use komodo_client::entities::{
   config::{DockerRegistry, GitProvider},
-  server::PeripheryInformation,
-  stats::SystemProcess,
+  server::{PeripheryInformation, periphery_request_allowed},
+  stats::{
+    DiskHealth, ListeningSocket, PendingOsUpdates, SystemProcess,
+    SystemStats,
+  },
   update::Log,
 };
 use periphery_client::api::{
-  build::*, compose::*, container::*, docker::*, git::*, keys::*,
-  stats::*, terminal::*, *,
+  build::*, compose::*, container::*, docker::*, extension::*,
+  file::*, git::*, host::*, keys::*, schedule::*, stats::*, swarm::*,
+  systemd::*, terminal::*, *,
 };
+use periphery_client::transport::EncodedTransportMessage;
 use resolver_api::Resolve;
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use transport::channel::Sender;
+use uuid::Uuid;
 
 use crate::{
   api::compose::list_compose_projects,
   config::periphery_config,
+  connection::auth_limit::auth_limiter,
+  docker::{buildx::buildx_platforms, runtime::docker_cli},
   state::{docker_client, periphery_keys, stats_client},
 };
 
@@ -28,19 +46,107 @@ mod compose;
 mod container;
 mod deploy;
 mod docker;
+mod extension;
+mod file;
 mod git;
+mod host;
 mod keys;
+mod schedule;
+mod swarm;
+mod systemd;
 
 #[derive(Debug)]
 pub struct Args {
   pub core: String,
+  /// Set for requests Core made with `stream: true`.
+  pub stream: Option<OutputStream>,
+}
+
+impl Args {
+  /// Runs the command with sanitization, sending the output
+  /// to Core as it is written if the request is streamed.
+  pub async fn run_command(
+    &self,
+    stage: &str,
+    path: impl Into<Option<&Path>>,
+    command: impl AsRef<str>,
+    parse_multiline: bool,
+    replacers: &[(String, String)],
+  ) -> Option<Log> {
+    let Some(stream) = &self.stream else {
+      return run_komodo_command_with_sanitization(
+        stage,
+        path,
+        command,
+        parse_multiline,
+        replacers,
+      )
+      .await;
+    };
+    let (lines, mut receiver) = mpsc::unbounded_channel();
+    let forward = async {
+      while let Some(line) = receiver.recv().await {
+        stream.send(stage, line).await;
+      }
+    };
+    let (log, _) = tokio::join!(
+      run_komodo_command_streamed(
+        stage,
+        path,
+        command,
+        parse_multiline,
+        replacers,
+        lines,
+      ),
+      forward
+    );
+    log
+  }
+
+  /// Sends a status line to Core if the request is streamed.
+  pub async fn send_status(&self, stage: &str, status: String) {
+    if let Some(stream) = &self.stream {
+      stream.send(stage, OutputLine::Stdout(status)).await;
+    }
+  }
+}
+
+/// Sends [OutputChunk]s on the channel of a streamed request.
+#[derive(Debug, Clone)]
+pub struct OutputStream {
+  pub channel: Uuid,
+  pub sender: Sender<EncodedTransportMessage>,
+  pub encoding: BodyEncoding,
+}
+
+impl OutputStream {
+  async fn send(&self, stage: &str, line: OutputLine) {
+    let (stdout, stderr) = match line {
+      OutputLine::Stdout(line) => (line, String::new()),
+      OutputLine::Stderr(line) => (String::new(), line),
+    };
+    let chunk = OutputChunk {
+      stage: stage.to_string(),
+      stdout,
+      stderr,
+    };
+    let res = match BodyMessage(&chunk, self.encoding).encode() {
+      Ok(body) => {
+        self.sender.send_stream_chunk(self.channel, body).await
+      }
+      Err(e) => Err(e),
+    };
+    if let Err(e) = res {
+      warn!("Failed to send output chunk | {e:#}");
+    }
+  }
 }
 
 #[derive(
   Serialize, Deserialize, Debug, Clone, Resolve, EnumVariants,
 )]
 #[args(Args)]
-#[response(EncodedResponse<EncodedJsonMessage>)]
+#[response(ResponseBody)]
 #[error(anyhow::Error)]
 #[variant_derive(Debug)]
 #[serde(tag = "type", content = "params")]
@@ -49,8 +155,13 @@ pub enum PeripheryRequest {
   // Stats / Info (Read)
   PollStatus(PollStatus),
   GetHealth(GetHealth),
+  GetAuthStats(GetAuthStats),
   GetVersion(GetVersion),
   GetSystemProcesses(GetSystemProcesses),
+  GetListeningSockets(GetListeningSockets),
+  GetDiskHealth(GetDiskHealth),
+  GetPendingOsUpdates(GetPendingOsUpdates),
+  GetStatsHistory(GetStatsHistory),
   GetLatestCommit(GetLatestCommit),
 
   // Config (Read)
@@ -71,6 +182,7 @@ pub enum PeripheryRequest {
   Build(Build),
   PruneBuilders(PruneBuilders),
   PruneBuildx(PruneBuildx),
+  PruneBuildCache(PruneBuildCache),
 
   // Compose (Read)
   GetComposeContentsOnHost(GetComposeContentsOnHost),
@@ -84,6 +196,7 @@ pub enum PeripheryRequest {
   ComposeUp(ComposeUp),
   ComposeExecution(ComposeExecution),
   ComposeRun(ComposeRun),
+  ComposeScale(ComposeScale),
 
   // Container (Read)
   InspectContainer(InspectContainer),
@@ -92,9 +205,13 @@ pub enum PeripheryRequest {
   GetContainerStats(GetContainerStats),
   GetContainerStatsList(GetContainerStatsList),
   GetFullContainerStats(GetFullContainerStats),
+  RunHealthProbe(RunHealthProbe),
+  SubscribeDockerEvents(SubscribeDockerEvents),
+  CopyFromContainer(CopyFromContainer),
 
   // Container (Write)
   Deploy(Deploy),
+  RollbackDeploy(RollbackDeploy),
   StartContainer(StartContainer),
   RestartContainer(RestartContainer),
   PauseContainer(PauseContainer),
@@ -107,6 +224,10 @@ pub enum PeripheryRequest {
   StopAllContainers(StopAllContainers),
   RemoveContainer(RemoveContainer),
   RenameContainer(RenameContainer),
+  CommitContainer(CommitContainer),
+  CopyIntoContainer(CopyIntoContainer),
+  CheckpointContainer(CheckpointContainer),
+  RestoreContainer(RestoreContainer),
   PruneContainers(PruneContainers),
 
   // Networks (Read)
@@ -120,37 +241,139 @@ pub enum PeripheryRequest {
   // Image (Read)
   InspectImage(InspectImage),
   ImageHistory(ImageHistory),
+  ExportImage(ExportImage),
 
   // Image (Write)
   PullImage(PullImage),
   DeleteImage(DeleteImage),
   PruneImages(PruneImages),
+  ImportImage(ImportImage),
 
   // Volume (Read)
   InspectVolume(InspectVolume),
+  ListComposeVolumes(ListComposeVolumes),
 
   // Volume (Write)
   DeleteVolume(DeleteVolume),
   PruneVolumes(PruneVolumes),
+  ArchiveVolume(ArchiveVolume),
+  RestoreVolume(RestoreVolume),
+  DeleteVolumeArchive(DeleteVolumeArchive),
 
   // All in one (Write)
   PruneSystem(PruneSystem),
 
+  // Swarm (Read)
+  ListSwarmServices(ListSwarmServices),
+  ListSwarmNodes(ListSwarmNodes),
+  InspectService(InspectService),
+
+  // Swarm (Write)
+  UpdateService(UpdateService),
+  DeploySwarmStack(DeploySwarmStack),
+  RemoveSwarmStack(RemoveSwarmStack),
+
   // Terminal
   ListTerminals(ListTerminals),
   CreateTerminal(CreateTerminal),
   DeleteTerminal(DeleteTerminal),
   DeleteAllTerminals(DeleteAllTerminals),
+  ClearTerminalHistory(ClearTerminalHistory),
+  ListTerminalRecordings(ListTerminalRecordings),
+  GetTerminalRecording(GetTerminalRecording),
   ConnectTerminal(ConnectTerminal),
   ConnectContainerExec(ConnectContainerExec),
   ConnectContainerAttach(ConnectContainerAttach),
   DisconnectTerminal(DisconnectTerminal),
   ExecuteTerminal(ExecuteTerminal),
   ExecuteContainerExec(ExecuteContainerExec),
+  ResumeExecute(ResumeExecute),
+  AckExecute(AckExecute),
+  TailHostFile(TailHostFile),
+
+  // Files
+  BeginFilePush(BeginFilePush),
+  FinishFilePush(FinishFilePush),
+  CancelFilePush(CancelFilePush),
+  PullFile(PullFile),
 
   // Keys
   RotatePrivateKey(RotatePrivateKey),
   RotateCorePublicKey(RotateCorePublicKey),
+
+  // Processes
+  KillSystemProcess(KillSystemProcess),
+  SetProcessPriority(SetProcessPriority),
+
+  // Systemd
+  ListSystemdUnits(ListSystemdUnits),
+  GetUnitStatus(GetUnitStatus),
+  GetUnitJournal(GetUnitJournal),
+  StartUnit(StartUnit),
+  StopUnit(StopUnit),
+  RestartUnit(RestartUnit),
+
+  // Schedules
+  ListSchedules(ListSchedules),
+  UpsertSchedule(UpsertSchedule),
+  DeleteSchedule(DeleteSchedule),
+
+  // Host
+  RebootHost(RebootHost),
+  ShutdownHost(ShutdownHost),
+
+  // Extensions
+  RunExtension(RunExtension),
+}
+
+/// Core relies on these to manage the Server,
+/// they can't be denied.
+const ALWAYS_ALLOWED_REQUESTS: [&str; 3] =
+  ["PollStatus", "GetHealth", "GetVersion"];
+
+impl PeripheryRequest {
+  /// Resolves the request if allowed by the
+  /// `allowed_requests` / `denied_requests` config.
+  pub async fn resolve_allowed(
+    self,
+    args: &Args,
+  ) -> anyhow::Result<ResponseBody> {
+    let request = format!("{:?}", self.extract_variant());
+    let config = periphery_config();
+    if !ALWAYS_ALLOWED_REQUESTS.contains(&request.as_str())
+      && !periphery_request_allowed(
+        &config.allowed_requests.0,
+        &config.denied_requests.0,
+        &request,
+      )
+    {
+      return Err(anyhow!(
+        "Periphery is configured to not allow {request} requests"
+      ));
+    }
+    self.resolve(args).await
+  }
+
+  /// Stats responses are sent in the lowest priority lane.
+  /// PollStatus is left out, Core uses it as the health check.
+  pub fn is_stats(&self) -> bool {
+    matches!(
+      self,
+      PeripheryRequest::GetSystemProcesses(_)
+        | PeripheryRequest::GetListeningSockets(_)
+        | PeripheryRequest::GetDiskHealth(_)
+        | PeripheryRequest::GetPendingOsUpdates(_)
+        | PeripheryRequest::GetStatsHistory(_)
+        | PeripheryRequest::GetContainerStats(_)
+        | PeripheryRequest::GetContainerStatsList(_)
+        | PeripheryRequest::GetFullContainerStats(_)
+    )
+  }
+
+  /// See [periphery_client::api::SENSITIVE_REQUESTS].
+  pub fn is_sensitive(&self) -> bool {
+    is_sensitive_request(&format!("{:?}", self.extract_variant()))
+  }
 }
 
 //
@@ -166,6 +389,17 @@ impl Resolve<Args> for GetHealth {
 
 //
 
+impl Resolve<Args> for GetAuthStats {
+  async fn resolve(
+    self,
+    _: &Args,
+  ) -> anyhow::Result<GetAuthStatsResponse> {
+    Ok(auth_limiter().stats())
+  }
+}
+
+//
+
 impl Resolve<Args> for GetVersion {
   async fn resolve(
     self,
@@ -236,6 +470,14 @@ impl Resolve<Args> for PollStatus {
           .disable_container_terminals,
         stats_polling_rate: config.stats_polling_rate,
         docker_connected: docker_client().load().is_some(),
+        allowed_requests: config.allowed_requests.0.clone(),
+        denied_requests: config.denied_requests.0.clone(),
+        extensions: config
+          .extensions
+          .iter()
+          .map(|extension| extension.name.clone())
+          .collect(),
+        buildx_platforms: buildx_platforms().to_vec(),
       },
       system_info: stats_client.info.clone(),
       system_stats,
@@ -261,6 +503,107 @@ impl Resolve<Args> for GetSystemProcesses {
 
 //
 
+impl Resolve<Args> for KillSystemProcess {
+  #[instrument("KillSystemProcess", skip_all, fields(core = args.core, pid = self.pid))]
+  async fn resolve(self, args: &Args) -> anyhow::Result<Log> {
+    check_process_managed(self.pid)?;
+    // The shell `kill` takes the signal name without the prefix.
+    let signal = self.signal.to_string();
+    let signal = signal.trim_start_matches("SIG");
+    Ok(
+      run_komodo_command(
+        "Kill Process",
+        None,
+        format!("kill -s {signal} {}", self.pid),
+      )
+      .await,
+    )
+  }
+}
+
+//
+
+impl Resolve<Args> for SetProcessPriority {
+  #[instrument("SetProcessPriority", skip_all, fields(core = args.core, pid = self.pid))]
+  async fn resolve(self, args: &Args) -> anyhow::Result<Log> {
+    check_process_managed(self.pid)?;
+    if !(-20..=19).contains(&self.nice) {
+      return Err(anyhow!(
+        "Niceness must be between -20 and 19, got {}",
+        self.nice
+      ));
+    }
+    Ok(
+      run_komodo_command(
+        "Set Process Priority",
+        None,
+        format!("renice -n {} -p {}", self.nice, self.pid),
+      )
+      .await,
+    )
+  }
+}
+
+/// Checks process management is enabled, and the pid
+/// isn't init or Periphery itself.
+fn check_process_managed(pid: u32) -> anyhow::Result<()> {
+  if !periphery_config().enable_process_management {
+    return Err(anyhow!(
+      "Process management is disabled in the periphery config"
+    ));
+  }
+  if pid <= 1 || pid == std::process::id() {
+    return Err(anyhow!("Process {pid} can't be managed"));
+  }
+  Ok(())
+}
+
+//
+
+impl Resolve<Args> for GetListeningSockets {
+  async fn resolve(
+    self,
+    _: &Args,
+  ) -> anyhow::Result<Vec<ListeningSocket>> {
+    crate::stats::sockets::get_listening_sockets().await
+  }
+}
+
+//
+
+impl Resolve<Args> for GetDiskHealth {
+  async fn resolve(
+    self,
+    _: &Args,
+  ) -> anyhow::Result<Vec<DiskHealth>> {
+    crate::stats::smart::get_disk_health().await
+  }
+}
+
+//
+
+impl Resolve<Args> for GetPendingOsUpdates {
+  async fn resolve(
+    self,
+    _: &Args,
+  ) -> anyhow::Result<PendingOsUpdates> {
+    Ok(crate::stats::os_updates::get_pending_os_updates().await)
+  }
+}
+
+//
+
+impl Resolve<Args> for GetStatsHistory {
+  async fn resolve(
+    self,
+    _: &Args,
+  ) -> anyhow::Result<Vec<SystemStats>> {
+    Ok(stats_client().read().await.get_history(self.since))
+  }
+}
+
+//
+
 impl Resolve<Args> for ListGitProviders {
   async fn resolve(
     self,
@@ -296,7 +639,10 @@ impl Resolve<Args> for ListSecrets {
 impl Resolve<Args> for PruneSystem {
   #[instrument("PruneSystem", skip_all, fields(core = args.core))]
   async fn resolve(self, args: &Args) -> anyhow::Result<Log> {
-    let command = String::from("docker system prune -a -f --volumes");
+    let command = format!(
+      "{docker} system prune -a -f --volumes",
+      docker = docker_cli()
+    );
     Ok(run_komodo_command("Prune System", None, command).await)
   }
 }
@@ -0,0 +1,45 @@
+use anyhow::anyhow;
+use komodo_client::entities::{
+  NoData, server_schedule::ServerScheduleStatus,
+};
+use periphery_client::api::schedule::*;
+use resolver_api::Resolve;
+
+use crate::{config::periphery_config, schedule};
+
+impl Resolve<super::Args> for ListSchedules {
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<Vec<ServerScheduleStatus>> {
+    Ok(schedule::list_schedules().await)
+  }
+}
+
+impl Resolve<super::Args> for UpsertSchedule {
+  #[instrument("UpsertSchedule", skip_all, fields(core = args.core))]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<ServerScheduleStatus> {
+    // Schedules run arbitrary commands, like terminals.
+    // When terminals are disabled, they can only be set in the config.
+    if periphery_config().disable_terminals {
+      return Err(anyhow!(
+        "Terminals are disabled in the periphery config, schedules can only be set in the config file"
+      ));
+    }
+    schedule::upsert_schedule(self.schedule).await
+  }
+}
+
+impl Resolve<super::Args> for DeleteSchedule {
+  #[instrument("DeleteSchedule", skip_all, fields(core = args.core))]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<NoData> {
+    schedule::delete_schedule(&self.name).await?;
+    Ok(NoData {})
+  }
+}
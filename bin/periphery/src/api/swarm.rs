@@ -0,0 +1,107 @@
+use command::run_komodo_command;
+use komodo_client::entities::{
+  docker::swarm::{
+    SwarmNodeListItem, SwarmService, SwarmServiceListItem,
+  },
+  update::Log,
+};
+use periphery_client::api::swarm::*;
+use resolver_api::Resolve;
+use shell_escape::unix::escape;
+
+use crate::{docker::swarm, helpers::format_extra_args};
+
+// ======
+//  READ
+// ======
+
+impl Resolve<super::Args> for ListSwarmServices {
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<Vec<SwarmServiceListItem>> {
+    swarm::list_swarm_services().await
+  }
+}
+
+//
+
+impl Resolve<super::Args> for ListSwarmNodes {
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<Vec<SwarmNodeListItem>> {
+    swarm::list_swarm_nodes().await
+  }
+}
+
+//
+
+impl Resolve<super::Args> for InspectService {
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<SwarmService> {
+    swarm::inspect_service(&self.name).await
+  }
+}
+
+// =========
+//  ACTIONS
+// =========
+
+impl Resolve<super::Args> for UpdateService {
+  #[instrument(
+    "UpdateService",
+    skip_all,
+    fields(
+      core = args.core,
+      service = self.name,
+      image = self.image.as_deref(),
+      replicas = self.replicas,
+      force = self.force,
+    )
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let UpdateService {
+      name,
+      image,
+      replicas,
+      force,
+      extra_args,
+    } = self;
+    let image = image
+      .map(|image| {
+        format!(
+          " --image {} --with-registry-auth",
+          escape(image.into())
+        )
+      })
+      .unwrap_or_default();
+    let replicas = replicas
+      .map(|replicas| format!(" --replicas {replicas}"))
+      .unwrap_or_default();
+    let force = if force { " --force" } else { "" };
+    let extra_args = format_extra_args(&extra_args);
+    let command = format!(
+      "docker service update --detach{image}{replicas}{force}{extra_args} {}",
+      escape(name.into())
+    );
+    Ok(run_komodo_command("Update Service", None, command).await)
+  }
+}
+
+//
+
+impl Resolve<super::Args> for RemoveSwarmStack {
+  #[instrument(
+    "RemoveSwarmStack",
+    skip_all,
+    fields(core = args.core, project = self.project)
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let command =
+      format!("docker stack rm {}", escape(self.project.into()));
+    Ok(run_komodo_command("Stack Remove", None, command).await)
+  }
+}
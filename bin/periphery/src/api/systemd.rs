@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, anyhow};
+use command::run_komodo_command;
+use komodo_client::entities::{
+  systemd::{SystemdUnit, SystemdUnitStatus},
+  update::Log,
+};
+use periphery_client::api::systemd::*;
+use resolver_api::Resolve;
+use run_command::async_run_command;
+use shell_escape::unix::escape;
+
+use crate::config::periphery_config;
+
+/// The unit types listed. Devices, slices and scopes are left out,
+/// they aren't managed directly.
+const LISTED_UNIT_TYPES: &str = "service,timer,socket,path,mount";
+
+impl Resolve<super::Args> for ListSystemdUnits {
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<Vec<SystemdUnit>> {
+    check_enabled()?;
+    let output = async_run_command(&format!(
+      "systemctl list-units --all --plain --full --no-legend --no-pager --type={LISTED_UNIT_TYPES}"
+    ))
+    .await;
+    if !output.success() {
+      return Err(anyhow!("{}", output.stderr.trim()))
+        .context("Failed to list systemd units");
+    }
+    Ok(output.stdout.lines().filter_map(parse_unit).collect())
+  }
+}
+
+/// Parses a line like
+/// `nfs-server.service loaded active exited NFS server and services`.
+fn parse_unit(line: &str) -> Option<SystemdUnit> {
+  let mut fields = line.split_whitespace();
+  let name = fields.next()?.to_string();
+  let load_state = fields.next()?.to_string();
+  let active_state = fields.next()?.to_string();
+  let sub_state = fields.next()?.to_string();
+  let description = fields.collect::<Vec<_>>().join(" ");
+  Some(SystemdUnit {
+    name,
+    load_state,
+    active_state,
+    sub_state,
+    description,
+  })
+}
+
+//
+
+impl Resolve<super::Args> for GetUnitStatus {
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<SystemdUnitStatus> {
+    let unit = systemd_unit(&self.unit)?;
+    let output = async_run_command(&format!(
+      "systemctl show {unit} --no-pager --property=Id,Description,LoadState,ActiveState,SubState,UnitFileState,FragmentPath,MainPID,ActiveEnterTimestamp,MemoryCurrent"
+    ))
+    .await;
+    if !output.success() {
+      return Err(anyhow!("{}", output.stderr.trim())).with_context(
+        || format!("Failed to get status of unit {unit}"),
+      );
+    }
+    let mut properties = output
+      .stdout
+      .lines()
+      .filter_map(|line| line.split_once('='))
+      .collect::<HashMap<_, _>>();
+    let mut property = |name: &str| {
+      properties
+        .remove(name)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+    };
+    Ok(SystemdUnitStatus {
+      name: property("Id").unwrap_or(self.unit),
+      description: property("Description").unwrap_or_default(),
+      load_state: property("LoadState").unwrap_or_default(),
+      active_state: property("ActiveState").unwrap_or_default(),
+      sub_state: property("SubState").unwrap_or_default(),
+      unit_file_state: property("UnitFileState").unwrap_or_default(),
+      fragment_path: property("FragmentPath"),
+      // 0 when not running
+      main_pid: property("MainPID")
+        .and_then(|pid| pid.parse().ok())
+        .filter(|pid| *pid > 0),
+      active_since: property("ActiveEnterTimestamp"),
+      // `[not set]` when memory isn't accounted
+      memory_bytes: property("MemoryCurrent")
+        .and_then(|bytes| bytes.parse().ok()),
+    })
+  }
+}
+
+//
+
+impl Resolve<super::Args> for StartUnit {
+  #[instrument(
+    "StartUnit",
+    skip_all,
+    fields(unit = self.unit, core = args.core)
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let unit = systemd_unit(&self.unit)?;
+    Ok(
+      run_komodo_command(
+        "Systemctl Start",
+        None,
+        format!("systemctl start {unit}"),
+      )
+      .await,
+    )
+  }
+}
+
+//
+
+impl Resolve<super::Args> for StopUnit {
+  #[instrument(
+    "StopUnit",
+    skip_all,
+    fields(unit = self.unit, core = args.core)
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let unit = systemd_unit(&self.unit)?;
+    Ok(
+      run_komodo_command(
+        "Systemctl Stop",
+        None,
+        format!("systemctl stop {unit}"),
+      )
+      .await,
+    )
+  }
+}
+
+//
+
+impl Resolve<super::Args> for RestartUnit {
+  #[instrument(
+    "RestartUnit",
+    skip_all,
+    fields(unit = self.unit, core = args.core)
+  )]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Log> {
+    let unit = systemd_unit(&self.unit)?;
+    Ok(
+      run_komodo_command(
+        "Systemctl Restart",
+        None,
+        format!("systemctl restart {unit}"),
+      )
+      .await,
+    )
+  }
+}
+
+//
+
+impl Resolve<super::Args> for GetUnitJournal {
+  async fn resolve(self, _: &super::Args) -> anyhow::Result<Log> {
+    let GetUnitJournal {
+      unit,
+      tail,
+      timestamps,
+    } = self;
+    let unit = systemd_unit(&unit)?;
+    let output = if timestamps {
+      " --output=short-precise"
+    } else {
+      Default::default()
+    };
+    Ok(
+      run_komodo_command(
+        "Get unit journal",
+        None,
+        format!(
+          "journalctl --unit {unit} --lines {tail} --no-pager{output}"
+        ),
+      )
+      .await,
+    )
+  }
+}
+
+/// Errors unless `enable_systemd_management` is set.
+fn check_enabled() -> anyhow::Result<()> {
+  if periphery_config().enable_systemd_management {
+    Ok(())
+  } else {
+    Err(anyhow!(
+      "Systemd management is disabled in the periphery config"
+    ))
+  }
+}
+
+/// Checks systemd management is enabled and the unit name
+/// only uses the characters systemd allows,
+/// returning it escaped to pass to the commands.
+fn systemd_unit(unit: &str) -> anyhow::Result<String> {
+  check_enabled()?;
+  if unit.is_empty()
+    || unit.starts_with('-')
+    || !unit.chars().all(|c| {
+      c.is_ascii_alphanumeric()
+        || matches!(c, ':' | '-' | '_' | '.' | '@' | '\\')
+    })
+  {
+    return Err(anyhow!("Invalid systemd unit name: {unit}"));
+  }
+  Ok(escape(unit.into()).to_string())
+}
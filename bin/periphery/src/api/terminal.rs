@@ -1,28 +1,34 @@
-use std::{sync::Arc, time::Duration};
+use std::{path::PathBuf, process::Stdio, sync::Arc, time::Duration};
 
 use anyhow::{Context, anyhow};
 use futures::{Stream, StreamExt, TryStreamExt};
 use komodo_client::entities::{
   ContainerTerminalMode, KOMODO_EXIT_CODE, NoData,
-  server::TerminalInfo,
+  server::{TerminalInfo, TerminalRecording},
 };
 use periphery_client::{
   api::terminal::*, transport::EncodedTransportMessage,
 };
 use resolver_api::Resolve;
+use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio_util::{codec::LinesCodecError, sync::CancellationToken};
 use transport::channel::{BufferedChannel, Sender};
 use uuid::Uuid;
 
 use crate::{
   config::periphery_config,
+  docker::runtime::docker_cli,
   state::{
-    TerminalChannel, core_connections, terminal_channels,
-    terminal_triggers,
+    TerminalChannel, core_connections, execute_buffers,
+    terminal_channels, terminal_triggers,
   },
   terminal::*,
 };
 
+/// How long to keep the output of completed executions
+/// which were never acknowledged by Core.
+const EXECUTE_BUFFER_TTL: Duration = Duration::from_secs(10 * 60);
+
 //
 
 impl Resolve<super::Args> for ListTerminals {
@@ -48,9 +54,16 @@ impl Resolve<super::Args> for CreateTerminal {
         "Terminals are disabled in the periphery config"
       ));
     }
-    create_terminal(self.name, self.command, self.recreate, None)
-      .await
-      .map(|_| NoData {})
+    create_terminal(
+      self.name,
+      self.command,
+      self.recreate,
+      None,
+      self.record,
+      self.history_kb,
+    )
+    .await
+    .map(|_| NoData {})
   }
 }
 
@@ -82,6 +95,38 @@ impl Resolve<super::Args> for DeleteAllTerminals {
 
 //
 
+impl Resolve<super::Args> for ClearTerminalHistory {
+  #[instrument("ClearTerminalHistory", skip(args), fields(core = args.core))]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<NoData> {
+    get_terminal(&self.terminal).await?.history.clear();
+    Ok(NoData {})
+  }
+}
+
+//
+
+impl Resolve<super::Args> for ListTerminalRecordings {
+  async fn resolve(
+    self,
+    _: &super::Args,
+  ) -> anyhow::Result<Vec<TerminalRecording>> {
+    recording::list_recordings().await
+  }
+}
+
+//
+
+impl Resolve<super::Args> for GetTerminalRecording {
+  async fn resolve(self, _: &super::Args) -> anyhow::Result<String> {
+    recording::get_recording(&self.name).await
+  }
+}
+
+//
+
 impl Resolve<super::Args> for ConnectTerminal {
   #[instrument("ConnectTerminal", skip(args), fields(core = args.core))]
   async fn resolve(self, args: &super::Args) -> anyhow::Result<Uuid> {
@@ -100,8 +145,11 @@ impl Resolve<super::Args> for ConnectTerminal {
 
     let terminal = get_terminal(&self.terminal).await?;
 
+    check_terminal_session_limit().await?;
+
     let channel =
-      spawn_terminal_forwarding(connection, terminal).await;
+      spawn_terminal_forwarding(connection, terminal, self.read_only)
+        .await;
 
     Ok(channel)
   }
@@ -138,15 +186,22 @@ impl Resolve<super::Args> for ConnectContainerExec {
     // Create (recreate if shell changed)
     let terminal = create_terminal(
       container.clone(),
-      format!("docker exec -it {container} {shell}"),
+      format!(
+        "{docker} exec -it {container} {shell}",
+        docker = docker_cli()
+      ),
       recreate,
       Some((container, ContainerTerminalMode::Exec)),
+      None,
+      None,
     )
     .await
     .context("Failed to create terminal for container exec")?;
 
+    check_terminal_session_limit().await?;
+
     let channel =
-      spawn_terminal_forwarding(connection, terminal).await;
+      spawn_terminal_forwarding(connection, terminal, false).await;
 
     Ok(channel)
   }
@@ -171,6 +226,7 @@ impl Resolve<super::Args> for ConnectContainerAttach {
     let ConnectContainerAttach {
       container,
       recreate,
+      detach_keys,
     } = self;
 
     if container.contains("&&") {
@@ -179,23 +235,62 @@ impl Resolve<super::Args> for ConnectContainerAttach {
       ));
     }
 
-    // Create (recreate if shell changed)
+    let mut command = format!(
+      "{docker} attach {container} --sig-proxy=false",
+      docker = docker_cli()
+    );
+    if let Some(detach_keys) = detach_keys {
+      validate_detach_keys(&detach_keys)?;
+      command.push_str(&format!(" --detach-keys={detach_keys}"));
+    }
+
+    // Create (recreate if detach keys changed)
     let terminal = create_terminal(
       container.clone(),
-      format!("docker attach {container} --sig-proxy=false"),
+      command,
       recreate,
       Some((container, ContainerTerminalMode::Attach)),
+      None,
+      None,
     )
     .await
     .context("Failed to create terminal for container attach")?;
 
+    check_terminal_session_limit().await?;
+
     let channel =
-      spawn_terminal_forwarding(connection, terminal).await;
+      spawn_terminal_forwarding(connection, terminal, false).await;
 
     Ok(channel)
   }
 }
 
+/// Docker detach keys are comma separated,
+/// each either a single letter or `ctrl-<value>`.
+fn validate_detach_keys(detach_keys: &str) -> anyhow::Result<()> {
+  let valid = !detach_keys.is_empty()
+    && detach_keys.split(',').all(|key| {
+      match key.strip_prefix("ctrl-") {
+        Some(value) => {
+          value.len() == 1
+            && value.chars().all(|c| {
+              c.is_ascii_lowercase() || "@[\\]^_".contains(c)
+            })
+        }
+        None => {
+          key.len() == 1 && key.chars().all(|c| c.is_ascii_graphic())
+        }
+      }
+    });
+  if valid {
+    Ok(())
+  } else {
+    Err(anyhow!(
+      "Invalid detach keys '{detach_keys}'. Expected eg. 'ctrl-p,ctrl-q'"
+    ))
+  }
+}
+
 //
 
 impl Resolve<super::Args> for DisconnectTerminal {
@@ -239,14 +334,9 @@ impl Resolve<super::Args> for ExecuteTerminal {
     )
     .await?;
 
-    tokio::spawn(async move {
-      forward_execute_command_on_terminal_response(
-        &channel.sender,
-        channel_id,
-        stdout,
-      )
-      .await
-    });
+    let buffer = spawn_execute_buffering(channel_id, stdout).await;
+
+    spawn_execute_forwarding(channel, channel_id, buffer, 0);
 
     Ok(channel_id)
   }
@@ -283,9 +373,14 @@ impl Resolve<super::Args> for ExecuteContainerExec {
 
     let terminal = create_terminal(
       container.clone(),
-      format!("docker exec -it {container} {shell}"),
+      format!(
+        "{docker} exec -it {container} {shell}",
+        docker = docker_cli()
+      ),
       recreate,
       Some((container, ContainerTerminalMode::Exec)),
+      None,
+      None,
     )
     .await
     .context("Failed to create terminal for container exec")?;
@@ -300,23 +395,227 @@ impl Resolve<super::Args> for ExecuteContainerExec {
     )
     .await?;
 
+    let buffer = spawn_execute_buffering(channel_id, stdout).await;
+
+    spawn_execute_forwarding(channel, channel_id, buffer, 0);
+
+    Ok(channel_id)
+  }
+}
+
+//
+
+impl Resolve<super::Args> for ResumeExecute {
+  #[instrument("ResumeExecute", skip(args), fields(core = args.core))]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Uuid> {
+    let connection =
+      core_connections().get(&args.core).await.with_context(
+        || format!("Failed to find channel for {}", args.core),
+      )?;
+
+    let buffer = execute_buffers()
+      .get(&self.channel)
+      .await
+      .with_context(|| {
+        format!(
+          "No buffered output for execution {}. It may have expired.",
+          self.channel
+        )
+      })?;
+
+    // Forward on a new channel, so any output still in flight
+    // on the original channel can't be received twice.
+    let channel_id = Uuid::new_v4();
+    terminal_triggers().insert(channel_id).await;
+
+    spawn_execute_forwarding(
+      connection,
+      channel_id,
+      buffer,
+      self.from_line,
+    );
+
+    Ok(channel_id)
+  }
+}
+
+//
+
+impl Resolve<super::Args> for AckExecute {
+  #[instrument("AckExecute", skip(args), fields(core = args.core))]
+  async fn resolve(
+    self,
+    args: &super::Args,
+  ) -> anyhow::Result<NoData> {
+    if let Some(buffer) =
+      execute_buffers().remove(&self.channel).await
+    {
+      buffer.replace_forwarder().cancel();
+    }
+    Ok(NoData {})
+  }
+}
+
+//
+
+impl Resolve<super::Args> for TailHostFile {
+  #[instrument("TailHostFile", skip(args), fields(core = args.core))]
+  async fn resolve(self, args: &super::Args) -> anyhow::Result<Uuid> {
+    let connection =
+      core_connections().get(&args.core).await.with_context(
+        || format!("Failed to find channel for {}", args.core),
+      )?;
+
+    let path = check_log_path_allowed(&self.path).await?;
+
+    check_terminal_session_limit().await?;
+
+    let mut command = tokio::process::Command::new("tail");
+    command.arg("-n").arg(self.lines.to_string());
+    // Follows the open file rather than the name (-F), so
+    // swapping in a symlink can't escape the allowed paths.
+    if self.follow {
+      command.arg("-f");
+    }
+    let mut child = command
+      .arg("--")
+      .arg(&path)
+      .stdin(Stdio::null())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::null())
+      .kill_on_drop(true)
+      .spawn()
+      .with_context(|| {
+        format!("Failed to spawn tail for {path:?}")
+      })?;
+    let stdout =
+      child.stdout.take().context("Failed to take tail stdout")?;
+
+    let channel = Uuid::new_v4();
+    let cancel = CancellationToken::new();
+    // Registered so DisconnectTerminal stops the tail.
+    let (stdin, _) = tokio::sync::mpsc::channel(1);
+
+    tokio::join!(
+      terminal_channels().insert(
+        channel,
+        Arc::new(TerminalChannel {
+          sender: stdin,
+          cancel: cancel.clone(),
+          read_only: true,
+        }),
+      ),
+      terminal_triggers().insert(channel),
+    );
+
     tokio::spawn(async move {
-      forward_execute_command_on_terminal_response(
-        &channel.sender,
-        channel_id,
+      handle_tail_forwarding(
+        &connection.sender,
+        channel,
+        child,
         stdout,
+        cancel,
       )
       .await
     });
 
-    Ok(channel_id)
+    Ok(channel)
+  }
+}
+
+/// Canonicalizes the path, so `..` and symlinks can't
+/// escape the `allowed_log_paths`.
+async fn check_log_path_allowed(
+  path: &str,
+) -> anyhow::Result<PathBuf> {
+  let allowed_log_paths = &periphery_config().allowed_log_paths;
+  if allowed_log_paths.is_empty() {
+    return Err(anyhow!(
+      "Host file tailing is disabled. Add paths to 'allowed_log_paths' in the Periphery config."
+    ));
+  }
+  let path = tokio::fs::canonicalize(path)
+    .await
+    .with_context(|| format!("Failed to resolve path {path}"))?;
+  for allowed in allowed_log_paths.iter() {
+    // Allowed paths which don't exist are skipped
+    if let Ok(allowed) = tokio::fs::canonicalize(allowed).await
+      && path.starts_with(&allowed)
+    {
+      if !path.is_file() {
+        return Err(anyhow!("{path:?} is not a file"));
+      }
+      return Ok(path);
+    }
+  }
+  Err(anyhow!(
+    "{path:?} is not inside the 'allowed_log_paths' in the Periphery config"
+  ))
+}
+
+async fn handle_tail_forwarding(
+  sender: &Sender<EncodedTransportMessage>,
+  channel: Uuid,
+  mut child: tokio::process::Child,
+  stdout: tokio::process::ChildStdout,
+  cancel: CancellationToken,
+) {
+  if let Err(e) = terminal_triggers().recv(&channel).await {
+    warn!(
+      "Failed to tail host file | Failed to receive begin trigger | {e:#}"
+    );
+    terminal_channels().remove(&channel).await;
+    return;
+  }
+
+  let mut lines = BufReader::new(stdout).lines();
+  loop {
+    let line = tokio::select! {
+      line = lines.next_line() => line,
+      _ = cancel.cancelled() => break,
+    };
+    let line = match line {
+      Ok(Some(line)) => line,
+      // tail exited, ie the file was removed without follow
+      res => {
+        if let Err(e) = res {
+          debug!("Failed to read tail output | {e:?}");
+        }
+        let _ = sender.send_terminal(channel, END_OF_OUTPUT).await;
+        break;
+      }
+    };
+    if let Err(e) = sender.send_terminal(channel, line + "\n").await {
+      debug!("Failed to send tail output | {e:?}");
+      break;
+    }
   }
+
+  let _ = child.kill().await;
+  terminal_channels().remove(&channel).await;
 }
 
 #[instrument("SpawnTerminalForwarding", skip_all)]
+/// Enforces the `max_terminal_sessions` Periphery config
+/// across all connected terminal channels.
+async fn check_terminal_session_limit() -> anyhow::Result<()> {
+  let max = periphery_config().max_terminal_sessions;
+  if max == 0 {
+    return Ok(());
+  }
+  let active = terminal_channels().get_keys().await.len();
+  if active >= max {
+    return Err(anyhow!(
+      "Reached the maximum of {max} concurrent terminal sessions on this Periphery"
+    ));
+  }
+  Ok(())
+}
+
 async fn spawn_terminal_forwarding(
   connection: Arc<BufferedChannel<EncodedTransportMessage>>,
   terminal: Arc<Terminal>,
+  read_only: bool,
 ) -> Uuid {
   let channel = Uuid::new_v4();
   let cancel = CancellationToken::new();
@@ -327,6 +626,7 @@ async fn spawn_terminal_forwarding(
       Arc::new(TerminalChannel {
         sender: terminal.stdin.clone(),
         cancel: cancel.clone(),
+        read_only,
       }),
     ),
     terminal_triggers().insert(channel),
@@ -338,6 +638,7 @@ async fn spawn_terminal_forwarding(
       channel,
       terminal,
       cancel,
+      read_only,
     )
     .await
   });
@@ -350,6 +651,7 @@ async fn handle_terminal_forwarding(
   channel: Uuid,
   terminal: Arc<Terminal>,
   cancel: CancellationToken,
+  read_only: bool,
 ) {
   // This waits to begin forwarding until Core sends the None byte start trigger.
   // This ensures no messages are lost before channels on both sides are set up.
@@ -361,6 +663,9 @@ async fn handle_terminal_forwarding(
     return;
   }
 
+  // Counted in ListTerminals until forwarding ends
+  let _connection = terminal.connect(read_only);
+
   let init_res = async {
     let (a, b) = terminal.history.bytes_parts();
     if !a.is_empty() {
@@ -487,10 +792,70 @@ async fn setup_execute_command_on_terminal(
   Ok(stdout)
 }
 
+/// Reads the execution output into a buffer, independently of
+/// the forwarding to Core. This way output is not lost if
+/// the connection drops mid execution.
+async fn spawn_execute_buffering(
+  channel: Uuid,
+  mut stdout: impl Stream<Item = Result<String, LinesCodecError>>
+  + Unpin
+  + Send
+  + 'static,
+) -> Arc<ExecuteBuffer> {
+  let buffer = Arc::new(ExecuteBuffer::default());
+  execute_buffers().insert(channel, buffer.clone()).await;
+
+  let _buffer = buffer.clone();
+  tokio::spawn(async move {
+    loop {
+      match stdout.next().await {
+        Some(Ok(line)) if line.as_str() == END_OF_OUTPUT => break,
+        Some(Ok(line)) => _buffer.push(line),
+        Some(Err(e)) => {
+          warn!("Got stdout stream error | {e:?}");
+          break;
+        }
+        None => {
+          clean_up_terminals().await;
+          break;
+        }
+      }
+    }
+    _buffer.complete();
+    // Release the output if Core never acknowledges it.
+    tokio::time::sleep(EXECUTE_BUFFER_TTL).await;
+    execute_buffers().remove(&channel).await;
+  });
+
+  buffer
+}
+
+/// Forwards the buffered execution output to Core, starting from `from_line`.
+fn spawn_execute_forwarding(
+  connection: Arc<BufferedChannel<EncodedTransportMessage>>,
+  channel: Uuid,
+  buffer: Arc<ExecuteBuffer>,
+  from_line: usize,
+) {
+  let cancel = buffer.replace_forwarder();
+  tokio::spawn(async move {
+    forward_execute_command_on_terminal_response(
+      &connection.sender,
+      channel,
+      &buffer,
+      from_line,
+      cancel,
+    )
+    .await
+  });
+}
+
 async fn forward_execute_command_on_terminal_response(
   sender: &Sender<EncodedTransportMessage>,
   channel: Uuid,
-  mut stdout: impl Stream<Item = Result<String, LinesCodecError>> + Unpin,
+  buffer: &ExecuteBuffer,
+  mut next_line: usize,
+  cancel: CancellationToken,
 ) {
   // This waits to begin forwarding until Core sends the None byte start trigger.
   // This ensures no messages are lost before channels on both sides are set up.
@@ -499,30 +864,62 @@ async fn forward_execute_command_on_terminal_response(
     return;
   }
 
+  let mut progress = buffer.subscribe();
+
   loop {
-    match stdout.next().await {
-      Some(Ok(line)) if line.as_str() == END_OF_OUTPUT => {
-        if let Err(e) = sender.send_terminal(channel, line).await {
-          warn!("Got ws_sender send error on END_OF_OUTPUT | {e:?}");
-        }
-        break;
+    let (len, complete) = *progress.borrow_and_update();
+
+    let (dropped, lines) = buffer.lines(next_line, len);
+    if dropped > 0
+      && let Err(e) = sender
+        .send_terminal(
+          channel,
+          format!("{OUTPUT_DROPPED}{dropped}\n"),
+        )
+        .await
+    {
+      warn!("Got ws_sender send error | {e:?}");
+      return;
+    }
+    for line in lines {
+      if cancel.is_cancelled() {
+        return;
       }
-      Some(Ok(line)) => {
-        if let Err(e) =
-          sender.send_terminal(channel, line + "\n").await
-        {
-          warn!("Got ws_sender send error | {e:?}");
-          break;
-        }
+      if let Err(e) = sender.send_terminal(channel, line + "\n").await
+      {
+        warn!("Got ws_sender send error | {e:?}");
+        return;
       }
-      Some(Err(e)) => {
-        warn!("Got stdout stream error | {e:?}");
-        break;
+    }
+    next_line = next_line.max(len);
+
+    if complete {
+      if let Some(exit_code) = buffer.exit_code()
+        && let Err(e) = sender
+          .send_terminal(
+            channel,
+            format!("{KOMODO_EXIT_CODE}{exit_code}\n"),
+          )
+          .await
+      {
+        warn!("Got ws_sender send error on exit code | {e:?}");
+        return;
       }
-      None => {
-        clean_up_terminals().await;
-        break;
+      if let Err(e) =
+        sender.send_terminal(channel, END_OF_OUTPUT).await
+      {
+        warn!("Got ws_sender send error on END_OF_OUTPUT | {e:?}");
+      }
+      return;
+    }
+
+    tokio::select! {
+      res = progress.changed() => {
+        if res.is_err() {
+          return;
+        }
       }
+      _ = cancel.cancelled() => return,
     }
   }
 }
@@ -88,18 +88,57 @@ pub fn periphery_config() -> &'static PeripheryConfig {
         env.periphery_passkeys,
       )
       .or(config.passkeys),
+      transport_psk: maybe_read_item_from_file(
+        env.periphery_transport_psk_file,
+        env.periphery_transport_psk,
+      )
+      .or(config.transport_psk),
       core_addresses: env
         .periphery_core_addresses
         .unwrap_or(config.core_addresses),
       core_tls_insecure_skip_verify: env
         .periphery_core_tls_insecure_skip_verify
         .unwrap_or(config.core_tls_insecure_skip_verify),
+      core_proxy_url: env
+        .periphery_core_proxy_url
+        .or(config.core_proxy_url),
       connect_as: env
         .periphery_connect_as
         .unwrap_or(config.connect_as),
+      auth_timeout_ms: env
+        .periphery_auth_timeout_ms
+        .unwrap_or(config.auth_timeout_ms),
+      transport_compression: env
+        .periphery_transport_compression
+        .unwrap_or(config.transport_compression),
+      transport_encoding: env
+        .periphery_transport_encoding
+        .unwrap_or(config.transport_encoding),
+      transport_max_chunk_bytes: env
+        .periphery_transport_max_chunk_bytes
+        .unwrap_or(config.transport_max_chunk_bytes),
+      transport_max_message_bytes: env
+        .periphery_transport_max_message_bytes
+        .unwrap_or(config.transport_max_message_bytes),
+      transport_max_body_bytes: env
+        .periphery_transport_max_body_bytes
+        .unwrap_or(config.transport_max_body_bytes),
+      transport_max_body_depth: env
+        .periphery_transport_max_body_depth
+        .unwrap_or(config.transport_max_body_depth),
+      transport_max_bytes_per_sec: env
+        .periphery_transport_max_bytes_per_sec
+        .unwrap_or(config.transport_max_bytes_per_sec),
+      transport_heartbeat_timeout_ms: env
+        .periphery_transport_heartbeat_timeout_ms
+        .unwrap_or(config.transport_heartbeat_timeout_ms),
+      connection_retry_seconds: env
+        .periphery_connection_retry_seconds
+        .unwrap_or(config.connection_retry_seconds),
       server_enabled: env
         .periphery_server_enabled
         .or(config.server_enabled),
+      transport: env.periphery_transport.unwrap_or(config.transport),
       port: env.periphery_port.unwrap_or(config.port),
       bind_ip: env.periphery_bind_ip.unwrap_or(config.bind_ip),
       root_directory: env
@@ -108,21 +147,76 @@ pub fn periphery_config() -> &'static PeripheryConfig {
       repo_dir: env.periphery_repo_dir.or(config.repo_dir),
       stack_dir: env.periphery_stack_dir.or(config.stack_dir),
       build_dir: env.periphery_build_dir.or(config.build_dir),
+      secrets_dir: env.periphery_secrets_dir.or(config.secrets_dir),
       disable_terminals: env
         .periphery_disable_terminals
         .unwrap_or(config.disable_terminals),
       disable_container_terminals: env
         .periphery_disable_container_terminals
         .unwrap_or(config.disable_container_terminals),
+      max_terminal_sessions: env
+        .periphery_max_terminal_sessions
+        .unwrap_or(config.max_terminal_sessions),
+      record_terminals: env
+        .periphery_record_terminals
+        .unwrap_or(config.record_terminals),
+      max_terminal_recordings: env
+        .periphery_max_terminal_recordings
+        .unwrap_or(config.max_terminal_recordings),
+      terminal_idle_timeout_seconds: env
+        .periphery_terminal_idle_timeout_seconds
+        .unwrap_or(config.terminal_idle_timeout_seconds),
+      terminal_max_lifetime_seconds: env
+        .periphery_terminal_max_lifetime_seconds
+        .unwrap_or(config.terminal_max_lifetime_seconds),
+      terminal_history_kb: env
+        .periphery_terminal_history_kb
+        .unwrap_or(config.terminal_history_kb),
+      allowed_log_paths: env
+        .periphery_allowed_log_paths
+        .unwrap_or(config.allowed_log_paths),
+      allowed_requests: env
+        .periphery_allowed_requests
+        .unwrap_or(config.allowed_requests),
+      denied_requests: env
+        .periphery_denied_requests
+        .unwrap_or(config.denied_requests),
       stats_polling_rate: env
         .periphery_stats_polling_rate
         .unwrap_or(config.stats_polling_rate),
       container_stats_polling_rate: env
         .periphery_container_stats_polling_rate
         .unwrap_or(config.container_stats_polling_rate),
+      stats_history_hours: env
+        .periphery_stats_history_hours
+        .unwrap_or(config.stats_history_hours),
+      os_updates_refresh_interval: env
+        .periphery_os_updates_refresh_interval
+        .unwrap_or(config.os_updates_refresh_interval),
       legacy_compose_cli: env
         .periphery_legacy_compose_cli
         .unwrap_or(config.legacy_compose_cli),
+      container_runtime: env
+        .periphery_container_runtime
+        .unwrap_or(config.container_runtime),
+      container_runtime_socket: env
+        .periphery_container_runtime_socket
+        .or(config.container_runtime_socket),
+      buildx_builder: env
+        .periphery_buildx_builder
+        .unwrap_or(config.buildx_builder),
+      enable_container_checkpoints: env
+        .periphery_enable_container_checkpoints
+        .unwrap_or(config.enable_container_checkpoints),
+      enable_systemd_management: env
+        .periphery_enable_systemd_management
+        .unwrap_or(config.enable_systemd_management),
+      allow_host_power_actions: env
+        .periphery_allow_host_power_actions
+        .unwrap_or(config.allow_host_power_actions),
+      enable_process_management: env
+        .periphery_enable_process_management
+        .unwrap_or(config.enable_process_management),
       logging: LogConfig {
         level: args
           .log_level
@@ -163,6 +257,12 @@ pub fn periphery_config() -> &'static PeripheryConfig {
       exclude_disk_mounts: env
         .periphery_exclude_disk_mounts
         .unwrap_or(config.exclude_disk_mounts),
+      include_network_interfaces: env
+        .periphery_include_network_interfaces
+        .unwrap_or(config.include_network_interfaces),
+      exclude_network_interfaces: env
+        .periphery_exclude_network_interfaces
+        .unwrap_or(config.exclude_network_interfaces),
       ssl_enabled: env
         .periphery_ssl_enabled
         .unwrap_or(config.ssl_enabled),
@@ -172,9 +272,26 @@ pub fn periphery_config() -> &'static PeripheryConfig {
       ssl_cert_file: env
         .periphery_ssl_cert_file
         .or(config.ssl_cert_file),
+      ssl_acme_email: env
+        .periphery_ssl_acme_email
+        .or(config.ssl_acme_email),
+      ssl_acme_domain: env
+        .periphery_ssl_acme_domain
+        .or(config.ssl_acme_domain),
+      ssl_acme_challenge: env
+        .periphery_ssl_acme_challenge
+        .unwrap_or(config.ssl_acme_challenge),
+      ssl_acme_directory_url: env
+        .periphery_ssl_acme_directory_url
+        .or(config.ssl_acme_directory_url),
+      ssl_acme_dns_command: env
+        .periphery_ssl_acme_dns_command
+        .or(config.ssl_acme_dns_command),
       secrets: config.secrets,
       git_providers: config.git_providers,
       docker_registries: config.docker_registries,
+      extensions: config.extensions,
+      schedules: config.schedules,
     }
   })
 }
@@ -0,0 +1,189 @@
+//! Per-IP rate limiting of the failed inbound connection logins,
+//! with exponential lockout after repeated failures.
+//! Successful logins reset the count for the IP.
+
+use std::{
+  collections::HashMap,
+  net::IpAddr,
+  sync::{
+    Mutex, OnceLock,
+    atomic::{AtomicU64, Ordering},
+  },
+  time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use periphery_client::api::{AuthLockout, GetAuthStatsResponse};
+
+/// Max failed logins from an IP per [RATE_LIMIT_WINDOW].
+const RATE_LIMIT_FAILURES: u32 = 10;
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+/// Consecutive failures allowed before the lockout starts.
+const FAILURES_BEFORE_LOCKOUT: u32 = 3;
+/// The first lockout, doubled with each further failure.
+const BASE_LOCKOUT: Duration = Duration::from_secs(2);
+const MAX_LOCKOUT: Duration = Duration::from_secs(10 * 60);
+/// Entries without activity for this long are dropped.
+const ENTRY_TTL: Duration = Duration::from_secs(60 * 60);
+
+pub fn auth_limiter() -> &'static AuthLimiter {
+  static AUTH_LIMITER: OnceLock<AuthLimiter> = OnceLock::new();
+  AUTH_LIMITER.get_or_init(Default::default)
+}
+
+#[derive(Default)]
+pub struct AuthLimiter {
+  entries: Mutex<HashMap<IpAddr, Entry>>,
+  attempts: AtomicU64,
+  successes: AtomicU64,
+  failures: AtomicU64,
+  rejected: AtomicU64,
+}
+
+struct Entry {
+  window_start: Instant,
+  window_failures: u32,
+  failures: u32,
+  locked_until: Option<Instant>,
+  public_key: Option<String>,
+  last_seen: Instant,
+}
+
+impl Entry {
+  fn new(now: Instant) -> Entry {
+    Entry {
+      window_start: now,
+      window_failures: 0,
+      failures: 0,
+      locked_until: None,
+      public_key: None,
+      last_seen: now,
+    }
+  }
+}
+
+impl AuthLimiter {
+  /// Call before the login. Errors if the IP is
+  /// locked out or over the rate limit.
+  pub fn check(&self, ip: IpAddr) -> anyhow::Result<()> {
+    let now = Instant::now();
+    let mut entries = self.entries.lock().unwrap();
+    entries.retain(|_, entry| {
+      now.duration_since(entry.last_seen) < ENTRY_TTL
+        || entry.locked_until.is_some_and(|until| until > now)
+    });
+    let entry = entries.entry(ip).or_insert_with(|| Entry::new(now));
+    entry.last_seen = now;
+
+    if let Some(until) = entry.locked_until
+      && until > now
+    {
+      self.rejected.fetch_add(1, Ordering::Relaxed);
+      return Err(anyhow!(
+        "{ip} is locked out for {}s after {} failed logins",
+        (until - now).as_secs().max(1),
+        entry.failures
+      ));
+    }
+
+    if now.duration_since(entry.window_start) < RATE_LIMIT_WINDOW
+      && entry.window_failures >= RATE_LIMIT_FAILURES
+    {
+      self.rejected.fetch_add(1, Ordering::Relaxed);
+      return Err(anyhow!(
+        "{ip} exceeded {RATE_LIMIT_FAILURES} failed logins per {}s",
+        RATE_LIMIT_WINDOW.as_secs()
+      ));
+    }
+    self.attempts.fetch_add(1, Ordering::Relaxed);
+    Ok(())
+  }
+
+  /// Resets the failures for the IP.
+  pub fn success(&self, ip: IpAddr) {
+    self.successes.fetch_add(1, Ordering::Relaxed);
+    let mut entries = self.entries.lock().unwrap();
+    if let Some(entry) = entries.get_mut(&ip) {
+      entry.window_failures = 0;
+      entry.failures = 0;
+      entry.locked_until = None;
+    }
+  }
+
+  /// Records the failed login, locking out the IP
+  /// once it passes [FAILURES_BEFORE_LOCKOUT].
+  /// Only logged with `log`, so repeated failures
+  /// of the same Core don't flood the logs.
+  pub fn failure(
+    &self,
+    ip: IpAddr,
+    public_key: Option<&str>,
+    e: &anyhow::Error,
+    log: bool,
+  ) {
+    self.failures.fetch_add(1, Ordering::Relaxed);
+    let now = Instant::now();
+    let mut entries = self.entries.lock().unwrap();
+    let entry = entries.entry(ip).or_insert_with(|| Entry::new(now));
+    if now.duration_since(entry.window_start) >= RATE_LIMIT_WINDOW {
+      entry.window_start = now;
+      entry.window_failures = 0;
+    }
+    entry.window_failures += 1;
+    entry.failures += 1;
+    entry.last_seen = now;
+    if let Some(public_key) = public_key {
+      entry.public_key = Some(public_key.to_string());
+    }
+    let lockout = lockout_duration(entry.failures);
+    if let Some(lockout) = lockout {
+      entry.locked_until = Some(now + lockout);
+    }
+    if !log {
+      return;
+    }
+    warn!(
+      %ip,
+      public_key = entry.public_key.as_deref().unwrap_or_default(),
+      failures = entry.failures,
+      lockout_secs = lockout.map(|l| l.as_secs()).unwrap_or_default(),
+      "Core failed to login to connection | {e:#}"
+    );
+  }
+
+  pub fn stats(&self) -> GetAuthStatsResponse {
+    let now = Instant::now();
+    let lockouts = self
+      .entries
+      .lock()
+      .unwrap()
+      .iter()
+      .filter_map(|(ip, entry)| {
+        let until =
+          entry.locked_until.filter(|until| *until > now)?;
+        Some(AuthLockout {
+          ip: ip.to_string(),
+          failures: entry.failures,
+          public_key: entry.public_key.clone(),
+          remaining_ms: (until - now).as_millis() as u64,
+        })
+      })
+      .collect();
+    GetAuthStatsResponse {
+      attempts: self.attempts.load(Ordering::Relaxed),
+      successes: self.successes.load(Ordering::Relaxed),
+      failures: self.failures.load(Ordering::Relaxed),
+      rejected: self.rejected.load(Ordering::Relaxed),
+      lockouts,
+    }
+  }
+}
+
+fn lockout_duration(failures: u32) -> Option<Duration> {
+  let exponent = failures.checked_sub(FAILURES_BEFORE_LOCKOUT)?;
+  Some(
+    BASE_LOCKOUT
+      .saturating_mul(2u32.saturating_pow(exponent))
+      .min(MAX_LOCKOUT),
+  )
+}
@@ -2,15 +2,15 @@ use std::{sync::Arc, time::Duration};
 
 use anyhow::{Context, anyhow};
 use axum::http::{HeaderValue, StatusCode};
-use periphery_client::{
-  CONNECTION_RETRY_SECONDS, transport::LoginMessage,
-};
+use periphery_client::transport::{LoginMessage, TransportOptions};
 use tracing::Instrument;
 use transport::{
   auth::{
     AddressConnectionIdentifiers, ClientLoginFlow,
     ConnectionIdentifiers, LoginFlow, LoginFlowArgs,
+    advertise_options_query,
   },
+  backoff::{Backoff, DEFAULT_MAX_RETRY_DELAY},
   fix_ws_address,
   websocket::{
     WebsocketExt, login::LoginWebsocketExt,
@@ -30,12 +30,23 @@ pub async fn handler(
   address: &str,
 ) -> anyhow::Result<tokio::task::JoinHandle<anyhow::Result<()>>> {
   let address = fix_ws_address(address);
+  if address.starts_with("quic://") {
+    return Err(anyhow!(
+      "QUIC transport is only supported for Core -> Periphery connections, use a websocket Core address."
+    ));
+  }
   let identifiers = AddressConnectionIdentifiers::extract(&address)?;
   let query = format!(
     "server={}",
     urlencoding::encode(&periphery_config().connect_as)
   );
-  let endpoint = format!("{address}/ws/periphery?{query}");
+  let endpoint = format!(
+    "{address}/ws/periphery?{query}&{}",
+    advertise_options_query(
+      periphery_config().transport_compression,
+      periphery_config().transport_encoding
+    )
+  );
 
   info!("Initiating outbound connection to {endpoint}");
 
@@ -45,6 +56,7 @@ pub async fn handler(
 
   let args = Arc::new(Args {
     core: identifiers.host().to_string(),
+    stream: None,
   });
 
   let channel =
@@ -52,6 +64,12 @@ pub async fn handler(
 
   let handle = tokio::spawn(async move {
     let mut receiver = channel.receiver()?;
+    let mut backoff = Backoff::new(
+      Duration::from_secs(
+        periphery_config().connection_retry_seconds,
+      ),
+      DEFAULT_MAX_RETRY_DELAY,
+    );
     loop {
       let (mut socket, accept) =
         match connect_websocket(&endpoint).await {
@@ -65,10 +83,7 @@ pub async fn handler(
               already_logged_login_error = false;
               already_logged_onboarding_error = false;
             }
-            tokio::time::sleep(Duration::from_secs(
-              CONNECTION_RETRY_SECONDS,
-            ))
-            .await;
+            backoff.wait().await;
             continue;
           }
         };
@@ -90,10 +105,7 @@ pub async fn handler(
             already_logged_login_error = false;
             already_logged_onboarding_error = false;
           }
-          tokio::time::sleep(Duration::from_secs(
-            CONNECTION_RETRY_SECONDS,
-          ))
-          .await;
+          backoff.wait().await;
           continue;
         }
       };
@@ -109,10 +121,7 @@ pub async fn handler(
             error!("{e:#}");
             already_logged_onboarding_error = true;
           }
-          tokio::time::sleep(Duration::from_secs(
-            CONNECTION_RETRY_SECONDS,
-          ))
-          .await;
+          backoff.wait().await;
           continue;
         };
       } else {
@@ -125,32 +134,43 @@ pub async fn handler(
           super::handle_login::<_, ClientLoginFlow>(
             &mut socket,
             identifiers,
+            // Core decides the options on client side.
+            TransportOptions::default(),
           )
           .await
         }
         .instrument(span)
         .await;
-        if let Err(e) = login {
-          if !already_logged_login_error {
-            warn!("Failed to login | {e:#}");
-            already_logged_login_error = true;
+        let (options, session_keys) = match login {
+          Ok(res) => res,
+          Err(e) => {
+            if !already_logged_login_error {
+              warn!("Failed to login | {e:#}");
+              already_logged_login_error = true;
+            }
+            backoff.wait().await;
+            continue;
           }
-          tokio::time::sleep(Duration::from_secs(
-            CONNECTION_RETRY_SECONDS,
-          ))
-          .await;
-          continue;
-        }
+        };
 
         already_logged_login_error = false;
+        backoff.reset();
 
-        super::handle_socket(
+        if let Some(retry_after) = super::handle_socket(
           socket,
           &args,
+          options,
+          session_keys,
           &channel.sender,
           &mut receiver,
         )
         .await
+        {
+          // Core announced the restart, so the connection
+          // errors while it is down are expected.
+          already_logged_connection_error = true;
+          tokio::time::sleep(retry_after).await;
+        }
       }
     }
   });
@@ -174,6 +194,7 @@ async fn handle_onboarding(
     identifiers,
     public_key_validator: core_public_keys(),
     socket: &mut socket,
+    options: TransportOptions::default(),
   })
   .await?;
 
@@ -202,8 +223,24 @@ async fn connect_websocket(
   url: &str,
 ) -> anyhow::Result<(TungsteniteWebsocket, HeaderValue)> {
   let config = periphery_config();
-  TungsteniteWebsocket::connect_maybe_tls_insecure(url, config.core_tls_insecure_skip_verify)
-    .await
+  let res = match &config.core_proxy_url {
+    Some(proxy_url) => {
+      TungsteniteWebsocket::connect_via_proxy(
+        url,
+        proxy_url,
+        config.core_tls_insecure_skip_verify,
+      )
+      .await
+    }
+    None => {
+      TungsteniteWebsocket::connect_maybe_tls_insecure(
+        url,
+        config.core_tls_insecure_skip_verify,
+      )
+      .await
+    }
+  };
+  res
     .map_err(|e| match e.status {
       StatusCode::NOT_FOUND => anyhow!("404 Not Found: Server '{}' does not exist.", config.connect_as),
       StatusCode::BAD_REQUEST => anyhow!("400 Bad Request: Server '{}' is disabled or configured to make Core → Periphery connection", config.connect_as),
@@ -0,0 +1,79 @@
+use std::{
+  collections::HashMap,
+  sync::OnceLock,
+  time::{Duration, Instant},
+};
+
+use encoding::{EncodedBodyMessage, EncodedResponse};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// How long completed results are kept for retransmissions.
+const RESULT_TTL: Duration = Duration::from_secs(300);
+
+pub fn idempotent_requests() -> &'static IdempotentRequests {
+  static IDEMPOTENT_REQUESTS: OnceLock<IdempotentRequests> =
+    OnceLock::new();
+  IDEMPOTENT_REQUESTS.get_or_init(Default::default)
+}
+
+/// Requests sent with an idempotency key, so Core can retransmit
+/// them after a reconnect without running them twice.
+#[derive(Default)]
+pub struct IdempotentRequests(
+  Mutex<HashMap<Uuid, IdempotentRequest>>,
+);
+
+enum IdempotentRequest {
+  Running,
+  Done {
+    response: EncodedResponse<EncodedBodyMessage>,
+    completed_at: Instant,
+  },
+}
+
+pub enum IdempotentStart {
+  /// The request hasn't been seen, run it.
+  New,
+  /// The first request is still running,
+  /// and will respond on the same channel.
+  Running,
+  /// The response of the first request.
+  Done(EncodedResponse<EncodedBodyMessage>),
+}
+
+impl IdempotentRequests {
+  pub async fn start(&self, key: Uuid) -> IdempotentStart {
+    let mut requests = self.0.lock().await;
+    requests.retain(|_, request| match request {
+      IdempotentRequest::Running => true,
+      IdempotentRequest::Done { completed_at, .. } => {
+        completed_at.elapsed() < RESULT_TTL
+      }
+    });
+    match requests.get(&key) {
+      Some(IdempotentRequest::Running) => IdempotentStart::Running,
+      Some(IdempotentRequest::Done { response, .. }) => {
+        IdempotentStart::Done(response.clone())
+      }
+      None => {
+        requests.insert(key, IdempotentRequest::Running);
+        IdempotentStart::New
+      }
+    }
+  }
+
+  pub async fn finish(
+    &self,
+    key: Uuid,
+    response: EncodedResponse<EncodedBodyMessage>,
+  ) {
+    self.0.lock().await.insert(
+      key,
+      IdempotentRequest::Done {
+        response,
+        completed_at: Instant::now(),
+      },
+    );
+  }
+}
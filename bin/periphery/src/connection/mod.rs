@@ -2,11 +2,16 @@ use std::{sync::Arc, time::Duration};
 
 use anyhow::anyhow;
 use encoding::{
-  CastBytes as _, Decode as _, Encode as _, WithChannel,
+  BodyEncoding, CastBytes as _, Decode as _, Encode as _,
+  EncodedBodyMessage, EncodedResponse, WithChannel,
 };
-use periphery_client::transport::{
-  EncodedRequestMessage, EncodedTransportMessage, RequestMessage,
-  TransportMessage,
+use noise::SessionKeys;
+use periphery_client::{
+  api::StreamFlag,
+  transport::{
+    EncodedRequestMessage, EncodedTransportMessage, RequestMessage,
+    TransportMessage, TransportNotice, TransportOptions,
+  },
 };
 use resolver_api::Resolve;
 use transport::{
@@ -15,20 +20,28 @@ use transport::{
     PublicKeyValidator,
   },
   channel::{BufferedReceiver, Sender},
+  chunk::ChunkAssembler,
+  rate_limit::RateLimiter,
   websocket::{
     Websocket, WebsocketReceiverExt as _, WebsocketSender as _,
+    WebsocketSenderExt as _,
   },
 };
+use uuid::Uuid;
 
 use crate::{
-  api::{Args, PeripheryRequest},
+  api::{Args, OutputStream, PeripheryRequest},
   config::periphery_config,
+  connection::idempotency::{IdempotentStart, idempotent_requests},
   state::{CorePublicKeys, core_public_keys, periphery_keys},
 };
 
+pub mod auth_limit;
 pub mod client;
 pub mod server;
 
+mod idempotency;
+
 impl PublicKeyValidator for &CorePublicKeys {
   type ValidationResult = ();
   #[instrument("ValidateCorePublicKey", skip(self))]
@@ -49,22 +62,29 @@ impl PublicKeyValidator for &CorePublicKeys {
 async fn handle_login<W: Websocket, L: LoginFlow>(
   socket: &mut W,
   identifiers: ConnectionIdentifiers<'_>,
-) -> anyhow::Result<()> {
+  options: TransportOptions,
+) -> anyhow::Result<(TransportOptions, Option<SessionKeys>)> {
   L::login(LoginFlowArgs {
     socket,
     identifiers,
     private_key: periphery_keys().load().private.as_str(),
     public_key_validator: core_public_keys(),
+    options,
   })
   .await
+  .map(|success| (success.options, success.payload_keys()))
 }
 
+/// Returns how long to wait before reconnecting
+/// if Core sent a shutdown notice.
 async fn handle_socket<W: Websocket>(
   socket: W,
   args: &Arc<Args>,
+  options: TransportOptions,
+  session_keys: Option<SessionKeys>,
   sender: &Sender<EncodedTransportMessage>,
   receiver: &mut BufferedReceiver<EncodedTransportMessage>,
-) {
+) -> Option<Duration> {
   let config = periphery_config();
   info!(
     "Logged in to Komodo Core {} websocket{}",
@@ -80,6 +100,15 @@ async fn handle_socket<W: Websocket>(
 
   let (mut ws_write, mut ws_read) = socket.split();
 
+  let max_chunk_bytes = if options.chunking {
+    config.transport_max_chunk_bytes
+  } else {
+    0
+  };
+
+  let send_limit =
+    RateLimiter::new(config.transport_max_bytes_per_sec);
+
   let forward_writes = async {
     loop {
       let message = match receiver.recv().await {
@@ -89,7 +118,11 @@ async fn handle_socket<W: Websocket>(
           break;
         }
       };
-      match ws_write.send(message.into_bytes()).await {
+      send_limit.acquire(message.len()).await;
+      match ws_write
+        .send_framed(message, options, max_chunk_bytes)
+        .await
+      {
         // Clears the stored message from receiver buffer.
         Ok(_) => receiver.clear_buffer(),
         Err(e) => {
@@ -101,59 +134,203 @@ async fn handle_socket<W: Websocket>(
     }
   };
 
+  // Core Pings connections with keepalive, so a silent connection
+  // was most likely dropped without being closed, eg. by NAT.
+  let heartbeat_timeout = (options.keepalive
+    && config.transport_heartbeat_timeout_ms > 0)
+    .then(|| {
+      Duration::from_millis(config.transport_heartbeat_timeout_ms)
+    });
+
   let handle_reads = async {
+    let mut chunks = ChunkAssembler::new(options);
     loop {
-      let message = match ws_read.recv_message().await {
+      let res = match heartbeat_timeout {
+        Some(timeout) => {
+          match tokio::time::timeout(
+            timeout,
+            ws_read.recv_message(options.header),
+          )
+          .await
+          {
+            Ok(res) => res,
+            Err(_) => {
+              warn!(
+                "No message from Komodo Core {} for over {}ms, reconnecting",
+                args.core, config.transport_heartbeat_timeout_ms
+              );
+              break;
+            }
+          }
+        }
+        None => ws_read.recv_message(options.header).await,
+      };
+      let message = match res {
         Ok(res) => res,
         Err(e) => {
           warn!("{e:#}");
           break;
         }
       };
-      match message {
-        TransportMessage::Request(message) => {
-          handle_request(args.clone(), sender.clone(), message)
+      let message = match chunks.reassemble(message) {
+        Ok(Some(message)) => message,
+        // Waiting on more chunks
+        Ok(None) => continue,
+        Err(e) => {
+          warn!("Failed to reassemble chunked message | {e:#}");
+          continue;
         }
+      };
+      match message {
+        TransportMessage::Request(message) => handle_request(
+          args.clone(),
+          sender.clone(),
+          options.body_encoding(),
+          session_keys,
+          message,
+        ),
         TransportMessage::Terminal(message) => {
           crate::terminal::handle_message(message).await
         }
+        // Handled inline so the contents are written
+        // before any following FinishFilePush request.
+        TransportMessage::File(message) => {
+          crate::file::handle_message(message).await
+        }
+        TransportMessage::Notice(message) => match message.decode() {
+          Ok(TransportNotice::CoreShutdown {
+            retry_after_seconds,
+          }) => {
+            info!(
+              "Komodo Core {} is restarting, retrying connection in {retry_after_seconds}s",
+              args.core
+            );
+            return Some(Duration::from_secs(retry_after_seconds));
+          }
+          Ok(notice) => {
+            warn!("Received unexpected Notice message | {notice:?}")
+          }
+          Err(e) => warn!("Failed to read Notice message | {e:#}"),
+        },
+        TransportMessage::Ping(message) => {
+          if let Err(e) = sender.send(message.into_pong()).await {
+            warn!("Failed to send Pong message | {e:#}");
+          }
+        }
         // Rest shouldn't be received by Periphery
         _ => {}
       }
     }
+    None
   };
 
   tokio::select! {
-    _ = forward_writes => {},
-    _ = handle_reads => {},
+    _ = forward_writes => None,
+    retry_after = handle_reads => retry_after,
   }
 }
 
+/// Responses are encoded with the `encoding`
+/// negotiated for the connection.
+/// The `session_keys` are passed when encryption was negotiated.
 fn handle_request(
   args: Arc<Args>,
   sender: Sender<EncodedTransportMessage>,
+  encoding: BodyEncoding,
+  session_keys: Option<SessionKeys>,
   message: EncodedRequestMessage,
 ) {
   tokio::spawn(async move {
+    let message: RequestMessage = match message.decode() {
+      Ok(res) => res,
+      Err(e) => {
+        // TODO: handle:
+        warn!("Failed to parse Request bytes | {e:#}");
+        return;
+      }
+    };
+    // Core sends a freshly encrypted copy after a reconnect,
+    // so requests encrypted with old keys are just dropped.
+    let message = match session_keys {
+      Some(keys) => message.decrypt(&keys.receive),
+      None => Ok(message),
+    };
+    let message = match message {
+      Ok(res) => res,
+      Err(e) => {
+        warn!("Failed to decrypt Request | {e:#}");
+        return;
+      }
+    };
+    let idempotency_key = message.idempotency_key();
     let WithChannel {
       channel,
-      data: request,
-    }: WithChannel<PeripheryRequest> =
-      match message.decode().and_then(RequestMessage::map_decode) {
-        Ok(res) => res,
-        Err(e) => {
-          // TODO: handle:
-          warn!("Failed to parse Request bytes | {e:#}");
+      data: json,
+    } = message.into_inner();
+
+    let StreamFlag { stream } =
+      json.clone().decode().unwrap_or_default();
+
+    let request: PeripheryRequest = match json.decode() {
+      Ok(res) => res,
+      Err(e) => {
+        // TODO: handle:
+        warn!("Failed to parse Request bytes | {e:#}");
+        return;
+      }
+    };
+
+    let seal_keys = session_keys.filter(|_| request.is_sensitive());
+
+    // Core retransmits requests after a reconnect.
+    if let Some(key) = idempotency_key {
+      match idempotent_requests().start(key).await {
+        IdempotentStart::New => {}
+        // Responds on the same channel once finished.
+        IdempotentStart::Running => return,
+        IdempotentStart::Done(response) => {
+          let response = seal_response(response, channel, seal_keys);
+          if let Err(e) =
+            sender.send_response(channel, response).await
+          {
+            error!("Failed to send response over channel | {e:?}");
+          }
           return;
         }
-      };
+      }
+    }
+
+    let stats = request.is_stats();
+
+    // Streamed requests get their own args to send output on.
+    let args = if stream {
+      Arc::new(Args {
+        core: args.core.clone(),
+        stream: Some(OutputStream {
+          channel,
+          sender: sender.clone(),
+          encoding,
+        }),
+      })
+    } else {
+      args
+    };
 
     let resolve_response = async {
-      let response = match request.resolve(&args).await {
-        Ok(res) => res,
+      let response = match request.resolve_allowed(&args).await {
+        Ok(res) => res.encode_with(encoding),
         Err(e) => (&e).encode(),
       };
-      if let Err(e) = sender.send_response(channel, response).await {
+      if let Some(key) = idempotency_key {
+        idempotent_requests().finish(key, response.clone()).await;
+      }
+      let response = seal_response(response, channel, seal_keys);
+      let res = if stats {
+        sender.send_stats_response(channel, response).await
+      } else {
+        sender.send_response(channel, response).await
+      };
+      if let Err(e) = res {
         error!("Failed to send response over channel | {e:?}");
       }
     };
@@ -173,3 +350,19 @@ fn handle_request(
     }
   });
 }
+
+/// Sensitive responses are encrypted when sent, so the response
+/// cached for the idempotency key can be sent again after
+/// a reconnect with the new session keys.
+fn seal_response(
+  response: EncodedResponse<EncodedBodyMessage>,
+  channel: Uuid,
+  keys: Option<SessionKeys>,
+) -> EncodedResponse<EncodedBodyMessage> {
+  let Some(keys) = keys else {
+    return response;
+  };
+  response
+    .encrypt_body(&keys.send, channel)
+    .unwrap_or_else(|e| (&e).encode())
+}
@@ -12,29 +12,41 @@ use axum::{
   Router,
   body::Body,
   extract::{ConnectInfo, Query, WebSocketUpgrade},
-  http::{HeaderMap, Request, StatusCode},
+  http::{HeaderMap, Request, StatusCode, Uri},
   middleware::{self, Next},
   response::Response,
   routing::get,
 };
 use axum_server::tls_rustls::RustlsConfig;
+use komodo_client::entities::config::periphery::PeripheryTransport;
+use noise::SessionKeys;
 use periphery_client::{
-  api::CoreConnectionQuery, transport::LoginMessage,
+  api::CoreConnectionQuery,
+  transport::{LoginMessage, TransportOptions},
 };
 use serror::{AddStatusCode, AddStatusCodeError};
 use transport::{
   auth::{
-    ConnectionIdentifiers, HeaderConnectionIdentifiers,
-    ServerLoginFlow,
+    ConnectionIdentifiers, HeaderConnectionIdentifiers, LoginFlow,
+    LoginFlowArgs, PublicKeyValidator, ServerLoginFlow,
+    negotiate_options,
   },
   websocket::{
-    Websocket, WebsocketExt, axum::AxumWebsocket,
+    Websocket, WebsocketExt,
+    axum::AxumWebsocket,
     login::LoginWebsocketExt,
+    quic::{QuicRequest, QuicServer},
   },
 };
 
 use crate::{
-  api::Args, config::periphery_config, state::core_connections,
+  api::Args,
+  config::periphery_config,
+  connection::auth_limit::auth_limiter,
+  state::{
+    CoreConnection, core_connections, core_public_keys,
+    periphery_keys,
+  },
 };
 
 #[instrument("RunCoreConnectionServer")]
@@ -47,6 +59,10 @@ pub async fn run()
   let socket_addr = SocketAddr::from_str(&addr)
     .context("failed to parse listen address")?;
 
+  if config.transport == PeripheryTransport::Quic {
+    return run_quic(socket_addr).await;
+  }
+
   let app = Router::new()
     .route("/version", get(|| async { env!("CARGO_PKG_VERSION") }))
     .route("/", get(crate::connection::server::handler))
@@ -63,6 +79,22 @@ pub async fn run()
     )
     .await
     .context("Invalid ssl cert / key")?;
+    if config.ssl_acme().is_some() {
+      let ssl_config = ssl_config.clone();
+      crate::acme::spawn_renewal_loop(move || {
+        let ssl_config = ssl_config.clone();
+        async move {
+          let config = periphery_config();
+          ssl_config
+            .reload_from_pem_file(
+              config.ssl_cert_file(),
+              config.ssl_key_file(),
+            )
+            .await
+            .context("Invalid ssl cert / key")
+        }
+      });
+    }
     tokio::spawn(async move {
       axum_server::bind_rustls(socket_addr, ssl_config)
         .serve(app)
@@ -83,21 +115,71 @@ pub async fn run()
   Ok(handle)
 }
 
+async fn run_quic(
+  socket_addr: SocketAddr,
+) -> anyhow::Result<tokio::task::JoinHandle<anyhow::Result<()>>> {
+  let config = periphery_config();
+  if !config.ssl_enabled {
+    warn!("QUIC transport always uses SSL, ignoring ssl_enabled");
+  }
+  crate::helpers::ensure_ssl_certs().await;
+  let server = QuicServer::bind(
+    socket_addr,
+    &config.ssl_cert_file(),
+    &config.ssl_key_file(),
+  )?;
+  if config.ssl_acme().is_some() {
+    let server = server.clone();
+    crate::acme::spawn_renewal_loop(move || {
+      let res = server
+        .reload(&config.ssl_cert_file(), &config.ssl_key_file());
+      async { res }
+    });
+  }
+  info!("Komodo Periphery starting on quic://{}", socket_addr);
+  let handle = tokio::spawn(async move {
+    while let Some(incoming) = server.accept().await {
+      tokio::spawn(async move {
+        if let Err(e) = quic_handler(incoming).await {
+          warn!("{e:#}");
+        }
+      });
+    }
+    Err(anyhow!("QUIC endpoint closed"))
+  });
+  Ok(handle)
+}
+
 fn already_logged_login_error() -> &'static AtomicBool {
   static ALREADY_LOGGED: OnceLock<AtomicBool> = OnceLock::new();
   ALREADY_LOGGED.get_or_init(|| AtomicBool::new(false))
 }
 
 async fn handler(
-  Query(CoreConnectionQuery { core }): Query<CoreConnectionQuery>,
+  Query(CoreConnectionQuery {
+    core,
+    compression,
+    chunking,
+    keepalive,
+    encoding,
+    encryption,
+    header,
+  }): Query<CoreConnectionQuery>,
+  ConnectInfo(socket_addr): ConnectInfo<SocketAddr>,
   mut headers: HeaderMap,
   ws: WebSocketUpgrade,
 ) -> serror::Result<Response> {
+  let ip = socket_addr.ip();
+  auth_limiter()
+    .check(ip)
+    .inspect_err(|e| warn!("{e:#}"))
+    .status_code(StatusCode::TOO_MANY_REQUESTS)?;
+
   let identifiers =
     HeaderConnectionIdentifiers::extract(&mut headers)
       .status_code(StatusCode::UNAUTHORIZED)?;
 
-  let args = Arc::new(Args { core });
+  let args = Arc::new(Args { core, stream: None });
 
   let channel =
     core_connections().get_or_insert_default(&args.core).await;
@@ -115,56 +197,180 @@ async fn handler(
   );
 
   Ok(ws.on_upgrade(|socket| async move {
-    let mut socket = AxumWebsocket(socket);
-
-    // Make sure receiver locked over the login.
-    let mut receiver = match channel.receiver() {
-      Ok(receiver) => receiver,
-      Err(e) => {
-        warn!("Failed to forward connection | {e:#}");
-
-        if let Err(e) = socket
-          .send_login_error(&e)
-          .await
-          .context("Failed to send forward failed to client")
-        {
-          // Log additional error
-          warn!("{e:#}");
-        }
+    let query = format!("core={}", urlencoding::encode(&args.core));
+    handle_connection(
+      AxumWebsocket(socket),
+      ip,
+      identifiers.build(query.as_bytes()),
+      &args,
+      &channel,
+      negotiate_options(
+        periphery_config().transport_compression,
+        periphery_config().transport_encoding,
+        compression,
+        encoding,
+        chunking,
+        keepalive,
+        encryption,
+        header,
+      ),
+    )
+    .await
+  }))
+}
 
-        // Close socket
-        let _ = socket.close().await;
+async fn quic_handler(
+  incoming: quinn::Incoming,
+) -> anyhow::Result<()> {
+  let ip = incoming.remote_address().ip();
+  if !ip_allowed(ip) {
+    incoming.refuse();
+    return Err(anyhow!("requesting ip {ip} not allowed"));
+  }
+  if let Err(e) = auth_limiter().check(ip) {
+    incoming.refuse();
+    return Err(e);
+  }
 
-        return;
-      }
-    };
+  let request = QuicRequest::read(incoming).await?;
 
-    let query = format!("core={}", urlencoding::encode(&args.core));
+  let CoreConnectionQuery {
+    core,
+    compression,
+    chunking,
+    keepalive,
+    encoding,
+    encryption,
+    header,
+  } = match request
+    .target
+    .parse::<Uri>()
+    .context("Invalid request target")
+    .and_then(|uri| {
+      Query::<CoreConnectionQuery>::try_from_uri(&uri)
+        .context("Invalid request query")
+    }) {
+    Ok(Query(query)) => query,
+    Err(e) => {
+      request
+        .reject(StatusCode::BAD_REQUEST, &format!("{e:#}"))
+        .await;
+      return Err(e);
+    }
+  };
+
+  let args = Arc::new(Args { core, stream: None });
+
+  let channel =
+    core_connections().get_or_insert_default(&args.core).await;
 
-    if let Err(e) =
-      handle_login(&mut socket, identifiers.build(query.as_bytes()))
+  // Same as the websocket handler, ensure the receiver
+  // is free before responding to the request.
+  if let Err(e) = channel.receiver() {
+    let e = e.context(format!(
+      "Connection for {} is already connected",
+      args.core
+    ));
+    request
+      .reject(StatusCode::CONFLICT, &format!("{e:#}"))
+      .await;
+    return Err(e);
+  }
+
+  let (socket, identifiers) = request.respond().await?;
+
+  let query = format!("core={}", urlencoding::encode(&args.core));
+  handle_connection(
+    socket,
+    ip,
+    identifiers.build(query.as_bytes()),
+    &args,
+    &channel,
+    negotiate_options(
+      periphery_config().transport_compression,
+      periphery_config().transport_encoding,
+      compression,
+      encoding,
+      chunking,
+      keepalive,
+      encryption,
+      header,
+    ),
+  )
+  .await;
+
+  Ok(())
+}
+
+async fn handle_connection<W: Websocket>(
+  mut socket: W,
+  ip: IpAddr,
+  identifiers: ConnectionIdentifiers<'_>,
+  args: &Arc<Args>,
+  channel: &CoreConnection,
+  options: TransportOptions,
+) {
+  // Make sure receiver locked over the login.
+  let mut receiver = match channel.receiver() {
+    Ok(receiver) => receiver,
+    Err(e) => {
+      warn!("Failed to forward connection | {e:#}");
+
+      if let Err(e) = socket
+        .send_login_error(&e)
         .await
-    {
-      let already_logged = already_logged_login_error();
-      if !already_logged.load(atomic::Ordering::Relaxed) {
-        warn!("Core failed to login to connection | {e:#}");
-        already_logged.store(true, atomic::Ordering::Relaxed);
+        .context("Failed to send forward failed to client")
+      {
+        // Log additional error
+        warn!("{e:#}");
       }
+
+      // Close socket
+      let _ = socket.close().await;
+
+      return;
+    }
+  };
+
+  let attempted_public_key = OnceLock::new();
+  let session_keys = match handle_login(
+    &mut socket,
+    identifiers,
+    options,
+    &attempted_public_key,
+  )
+  .await
+  {
+    Ok(session_keys) => session_keys,
+    Err(e) => {
+      let already_logged = already_logged_login_error()
+        .swap(true, atomic::Ordering::Relaxed);
+      auth_limiter().failure(
+        ip,
+        attempted_public_key.get().map(String::as_str),
+        &e,
+        !already_logged,
+      );
       // End the connection
       return;
     }
+  };
 
-    already_logged_login_error()
-      .store(false, atomic::Ordering::Relaxed);
+  auth_limiter().success(ip);
+  already_logged_login_error()
+    .store(false, atomic::Ordering::Relaxed);
 
-    super::handle_socket(
-      socket,
-      &args,
-      &channel.sender,
-      &mut receiver,
-    )
-    .await
-  }))
+  // Core reconnects to Periphery in this direction,
+  // so the shutdown notice is only logged.
+  super::handle_socket(
+    socket,
+    args,
+    options,
+    session_keys,
+    &channel.sender,
+    &mut receiver,
+  )
+  .await;
 }
 
 /// Custom Core -> Periphery side only login wrapper
@@ -174,10 +380,12 @@ async fn handler(
   skip(socket, identifiers),
   fields(direction = "CoreToPeriphery")
 )]
-async fn handle_login(
-  socket: &mut AxumWebsocket,
+async fn handle_login<W: Websocket>(
+  socket: &mut W,
   identifiers: ConnectionIdentifiers<'_>,
-) -> anyhow::Result<()> {
+  options: TransportOptions,
+  attempted_public_key: &OnceLock<String>,
+) -> anyhow::Result<Option<SessionKeys>> {
   let config = periphery_config();
   match (&config.core_public_keys, &config.passkeys) {
     (Some(_), _) | (_, None) => {
@@ -185,19 +393,37 @@ async fn handle_login(
         .send_message(LoginMessage::V1PasskeyFlow(false))
         .await
         .context("Failed to send Login V1PasskeyFlow message")?;
-      super::handle_login::<_, ServerLoginFlow>(socket, identifiers)
-        .await
-    }
-    (None, Some(passkeys)) => {
-      handle_passkey_login(socket, passkeys).await
+      ServerLoginFlow::login(LoginFlowArgs {
+        socket,
+        identifiers,
+        private_key: periphery_keys().load().private.as_str(),
+        public_key_validator: RecordAttemptedPublicKey {
+          attempted: attempted_public_key,
+        },
+        options,
+      })
+      .await
+      .map(|success| success.payload_keys())
     }
+    // The passkey flow has no handshake to get session keys from.
+    (None, Some(passkeys)) => handle_passkey_login(
+      socket,
+      passkeys,
+      TransportOptions {
+        encryption: false,
+        ..options
+      },
+    )
+    .await
+    .map(|_| None),
   }
 }
 
 #[instrument("V1PasskeyCoreLoginFlow", skip(socket, passkeys))]
-async fn handle_passkey_login(
-  socket: &mut AxumWebsocket,
+async fn handle_passkey_login<W: Websocket>(
+  socket: &mut W,
   passkeys: &[String],
+  options: TransportOptions,
 ) -> anyhow::Result<()> {
   if !already_logged_login_error().load(atomic::Ordering::Relaxed) {
     warn!(
@@ -221,7 +447,7 @@ async fn handle_passkey_login(
       .any(|expected_passkey| expected_passkey.as_bytes() == passkey)
     {
       socket
-        .send_message(LoginMessage::Success)
+        .send_message(LoginMessage::Success(options))
         .await
         .context("Failed to send login type indicator")?;
       Ok(())
@@ -274,17 +500,7 @@ async fn guard_request_by_ip(
     .status_code(StatusCode::UNAUTHORIZED)?;
   let ip = socket_addr.ip();
 
-  let ip_match = periphery_config().allowed_ips.iter().any(|net| {
-    net.contains(ip)
-      || match ip {
-        IpAddr::V4(ipv4) => {
-          net.contains(IpAddr::V6(ipv4.to_ipv6_mapped()))
-        }
-        IpAddr::V6(_) => net.contains(ip.to_canonical()),
-      }
-  });
-
-  if ip_match {
+  if ip_allowed(ip) {
     Ok(next.run(req).await)
   } else {
     Err(
@@ -293,3 +509,30 @@ async fn guard_request_by_ip(
     )
   }
 }
+
+/// Validates against the core public keys,
+/// keeping the key for the failed login log.
+struct RecordAttemptedPublicKey<'a> {
+  attempted: &'a OnceLock<String>,
+}
+
+impl PublicKeyValidator for RecordAttemptedPublicKey<'_> {
+  type ValidationResult = ();
+  async fn validate(&self, public_key: String) -> anyhow::Result<()> {
+    let _ = self.attempted.set(public_key.clone());
+    core_public_keys().validate(public_key).await
+  }
+}
+
+fn ip_allowed(ip: IpAddr) -> bool {
+  periphery_config().allowed_ips.is_empty()
+    || periphery_config().allowed_ips.iter().any(|net| {
+      net.contains(ip)
+        || match ip {
+          IpAddr::V4(ipv4) => {
+            net.contains(IpAddr::V6(ipv4.to_ipv6_mapped()))
+          }
+          IpAddr::V6(_) => net.contains(ip.to_canonical()),
+        }
+    })
+}
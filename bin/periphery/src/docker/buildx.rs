@@ -0,0 +1,297 @@
+//! Manages the buildx builder instance used for multi platform builds,
+//! and the platforms it supports.
+
+use std::{
+  fmt::Write,
+  sync::{Arc, OnceLock},
+};
+
+use anyhow::{Context, anyhow};
+use arc_swap::ArcSwap;
+use command::run_komodo_command;
+use komodo_client::entities::update::Log;
+use run_command::async_run_command;
+use serde::Deserialize;
+use shell_escape::unix::escape;
+
+use crate::config::periphery_config;
+
+use super::runtime::docker_cli;
+
+/// The platforms of the buildx builder,
+/// advertised in the Periphery information.
+pub fn buildx_platforms() -> Arc<Vec<String>> {
+  platforms().load_full()
+}
+
+fn platforms() -> &'static ArcSwap<Vec<String>> {
+  static BUILDX_PLATFORMS: OnceLock<ArcSwap<Vec<String>>> =
+    OnceLock::new();
+  BUILDX_PLATFORMS.get_or_init(Default::default)
+}
+
+/// Load the builder platforms in the background on startup.
+pub fn spawn_platforms_refresh() {
+  tokio::spawn(refresh_platforms());
+}
+
+/// Reads the platforms of the configured builder, or the
+/// default builder if it hasn't been created yet.
+async fn refresh_platforms() {
+  let builder = &periphery_config().buildx_builder;
+  let docker = docker_cli();
+  let command = format!(
+    "{docker} buildx inspect {builder} 2>/dev/null || {docker} buildx inspect"
+  );
+  let output = async_run_command(&command).await;
+  if !output.success() {
+    debug!(
+      "Failed to inspect buildx builder platforms | {}",
+      output.stderr
+    );
+    return;
+  }
+  platforms().store(Arc::new(parse_platforms(&output.stdout)));
+}
+
+/// Parses the `Platforms:` lines of `docker buildx inspect`.
+/// Builders with multiple nodes list platforms for each.
+fn parse_platforms(inspect: &str) -> Vec<String> {
+  let mut platforms = Vec::new();
+  for line in inspect.lines() {
+    let Some(list) = line.trim().strip_prefix("Platforms:") else {
+      continue;
+    };
+    for platform in list.split(',') {
+      // Platforms set with --platform are marked with '*'
+      let platform = platform.trim().trim_end_matches('*');
+      if !platform.is_empty()
+        && !platforms.iter().any(|p| p == platform)
+      {
+        platforms.push(platform.to_string());
+      }
+    }
+  }
+  platforms
+}
+
+/// Creates the configured builder with the `docker-container` driver
+/// if it doesn't exist, which is required to build multi platform
+/// images. Returns the builder name.
+pub async fn ensure_builder(
+  logs: &mut Vec<Log>,
+) -> anyhow::Result<&'static str> {
+  let builder = periphery_config().buildx_builder.as_str();
+  if builder.is_empty() {
+    return Err(anyhow!("'buildx_builder' must not be empty"));
+  }
+  if builder_exists(builder).await {
+    return Ok(builder);
+  }
+  let docker = docker_cli();
+  let log = run_komodo_command(
+    "Create Buildx Builder",
+    None,
+    format!(
+      "{docker} buildx create --name {builder} --driver docker-container --bootstrap"
+    ),
+  )
+  .await;
+  let success = log.success;
+  logs.push(log);
+  if !success {
+    return Err(anyhow!("Failed to create buildx builder {builder}"));
+  }
+  refresh_platforms().await;
+  Ok(builder)
+}
+
+async fn builder_exists(builder: &str) -> bool {
+  async_run_command(&format!(
+    "{docker} buildx inspect {builder}",
+    docker = docker_cli()
+  ))
+  .await
+  .success()
+}
+
+/// The ` --builder ... --platform ...` build args.
+pub fn platform_args(builder: &str, platforms: &[String]) -> String {
+  let mut args = format!(" --builder {builder}");
+  if !platforms.is_empty() {
+    let _ = write!(
+      &mut args,
+      " --platform {}",
+      escape(platforms.join(",").into())
+    );
+  }
+  args
+}
+
+/// The ` --cache-from ... --cache-to ...` build args.
+/// Plain image references use the registry cache backend.
+pub fn cache_args(cache_from: &str, cache_to: &str) -> String {
+  let mut args = String::new();
+  if !cache_from.is_empty() {
+    let spec = if cache_from.contains('=') {
+      cache_from.to_string()
+    } else {
+      format!("type=registry,ref={cache_from}")
+    };
+    let _ =
+      write!(&mut args, " --cache-from {}", escape(spec.into()));
+  }
+  if !cache_to.is_empty() {
+    let spec = if cache_to.contains('=') {
+      cache_to.to_string()
+    } else {
+      format!("type=registry,ref={cache_to},mode=max")
+    };
+    let _ = write!(&mut args, " --cache-to {}", escape(spec.into()));
+  }
+  args
+}
+
+/// Prunes the build cache of the default builder, and the configured
+/// builder if it exists, keeping cache used within `keep_last`.
+pub async fn prune_build_cache(keep_last: Option<&str>) -> Vec<Log> {
+  let docker = docker_cli();
+  let filter = match keep_last.filter(|keep| !keep.is_empty()) {
+    Some(keep_last) => format!(
+      " --filter {}",
+      escape(format!("until={keep_last}").into())
+    ),
+    None => String::new(),
+  };
+  let configured = periphery_config().buildx_builder.as_str();
+  let mut builders = vec![None];
+  if !configured.is_empty() && builder_exists(configured).await {
+    builders.push(Some(configured));
+  }
+  let mut logs = Vec::new();
+  for builder in builders {
+    let (stage, builder_arg) = match builder {
+      Some(builder) => (
+        format!("Prune Build Cache ({builder})"),
+        format!(" --builder {builder}"),
+      ),
+      None => (String::from("Prune Build Cache"), String::new()),
+    };
+    let mut log = run_komodo_command(
+      &stage,
+      None,
+      format!("{docker} buildx prune -a -f{builder_arg}{filter}"),
+    )
+    .await;
+    if log.success {
+      let remaining = cache_size(&builder_arg)
+        .await
+        .unwrap_or_else(|e| format!("unknown ({e:#})"));
+      let reclaimed = log
+        .stdout
+        .lines()
+        .rev()
+        .find_map(|line| line.trim().strip_prefix("Total:"))
+        .map(str::trim)
+        .unwrap_or("0B")
+        .to_string();
+      let _ = write!(
+        &mut log.stdout,
+        "\nReclaimed: {reclaimed} | Remaining: {remaining}"
+      );
+    }
+    let success = log.success;
+    logs.push(log);
+    if !success {
+      break;
+    }
+  }
+  logs
+}
+
+/// The total size from `docker buildx du`.
+async fn cache_size(builder_arg: &str) -> anyhow::Result<String> {
+  let output = async_run_command(&format!(
+    "{docker} buildx du{builder_arg}",
+    docker = docker_cli()
+  ))
+  .await;
+  if !output.success() {
+    return Err(anyhow!("{}", output.stderr))
+      .context("Failed to get build cache size");
+  }
+  Ok(
+    output
+      .stdout
+      .lines()
+      .rev()
+      .find_map(|line| line.trim().strip_prefix("Total:"))
+      .map(|total| total.trim().to_string())
+      .unwrap_or_else(|| String::from("0B")),
+  )
+}
+
+#[derive(Deserialize)]
+struct ImageIndex {
+  #[serde(default)]
+  manifests: Vec<ImageManifest>,
+}
+
+#[derive(Deserialize)]
+struct ImageManifest {
+  digest: String,
+  platform: Option<ManifestPlatform>,
+}
+
+#[derive(Deserialize)]
+struct ManifestPlatform {
+  os: String,
+  architecture: String,
+  variant: Option<String>,
+}
+
+/// Reads the digest of each platform image in the pushed
+/// multi platform image, ie `linux/arm64: sha256:...`.
+pub async fn platform_digests(image: &str) -> Log {
+  let docker = docker_cli();
+  let command = format!(
+    "{docker} buildx imagetools inspect --raw {}",
+    escape(image.into())
+  );
+  let mut log =
+    run_komodo_command("Platform Digests", None, command).await;
+  if !log.success {
+    return log;
+  }
+  match serde_json::from_str::<ImageIndex>(&log.stdout)
+    .context("Failed to parse image index")
+  {
+    Ok(index) => {
+      log.stdout = index
+        .manifests
+        .into_iter()
+        .filter_map(|manifest| {
+          let platform = manifest.platform?;
+          // Attestation manifests are on the 'unknown' platform
+          if platform.os == "unknown" {
+            return None;
+          }
+          let variant = platform
+            .variant
+            .map(|variant| format!("/{variant}"))
+            .unwrap_or_default();
+          Some(format!(
+            "{}/{}{variant}: {}",
+            platform.os, platform.architecture, manifest.digest
+          ))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    }
+    Err(e) => {
+      log.success = false;
+      log.stderr = format!("{e:#}");
+    }
+  }
+  log
+}
@@ -13,6 +13,9 @@ use crate::state::container_stats;
 
 use super::DockerClient;
 
+const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
+
 impl DockerClient {
   pub async fn list_containers(
     &self,
@@ -103,6 +106,45 @@ impl DockerClient {
     Ok(containers)
   }
 
+  /// Counts the running containers of each compose service,
+  /// keyed by (project, service). Services with only stopped
+  /// containers are included with 0 replicas.
+  pub async fn compose_service_replicas(
+    &self,
+  ) -> anyhow::Result<HashMap<(String, String), i64>> {
+    let filters = HashMap::from([(
+      String::from("label"),
+      vec![String::from(COMPOSE_PROJECT_LABEL)],
+    )]);
+    let containers = self
+      .docker
+      .list_containers(Some(ListContainersOptions {
+        all: true,
+        filters: Some(filters),
+        ..Default::default()
+      }))
+      .await
+      .context("Failed to list compose containers")?;
+    let mut replicas = HashMap::<_, i64>::new();
+    for container in containers {
+      let Some(labels) = container.labels else {
+        continue;
+      };
+      let (Some(project), Some(service)) = (
+        labels.get(COMPOSE_PROJECT_LABEL),
+        labels.get(COMPOSE_SERVICE_LABEL),
+      ) else {
+        continue;
+      };
+      let running = container.state
+        == Some(bollard::secret::ContainerSummaryStateEnum::RUNNING);
+      *replicas
+        .entry((project.clone(), service.clone()))
+        .or_default() += running as i64;
+    }
+    Ok(replicas)
+  }
+
   pub async fn inspect_container(
     &self,
     container_name: &str,
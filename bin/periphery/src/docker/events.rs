@@ -0,0 +1,94 @@
+use std::{
+  collections::{HashMap, HashSet},
+  sync::{Mutex, OnceLock},
+  time::Duration,
+};
+
+use anyhow::Context;
+use bollard::{
+  models::EventMessage, query_parameters::EventsOptions,
+};
+use futures::StreamExt;
+use komodo_client::entities::docker::container::ContainerStateStatusEnum;
+use periphery_client::transport::PeripheryEvent;
+
+use crate::{
+  docker::DockerClient,
+  state::{core_connections, docker_client},
+};
+
+/// Wait before resubscribing once the docker event stream ends,
+/// eg. when the docker daemon restarts.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// The Cores which called SubscribeDockerEvents, by address / host.
+fn subscribers() -> &'static Mutex<HashSet<String>> {
+  static SUBSCRIBERS: OnceLock<Mutex<HashSet<String>>> =
+    OnceLock::new();
+  SUBSCRIBERS.get_or_init(Default::default)
+}
+
+pub fn subscribe(core: &str) {
+  subscribers().lock().unwrap().insert(core.to_string());
+}
+
+/// Pushes container state changes and OOM kills to the
+/// subscribed Cores as they happen, so they don't need to poll.
+pub fn spawn_event_watcher() {
+  tokio::spawn(async move {
+    loop {
+      let client =
+        arc_swap::Guard::into_inner(docker_client().load());
+      if let Some(client) = client.as_ref()
+        && let Err(e) = watch_events(client).await
+      {
+        warn!("Docker event stream failed | {e:#}");
+      }
+      tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+    }
+  });
+}
+
+async fn watch_events(client: &DockerClient) -> anyhow::Result<()> {
+  let filters = HashMap::from([(
+    String::from("type"),
+    vec![String::from("container")],
+  )]);
+  let mut events = client.docker.events(Some(EventsOptions {
+    filters: Some(filters),
+    ..Default::default()
+  }));
+  while let Some(event) = events.next().await {
+    let event = event.context("Failed to read docker event")?;
+    let Some(event) = periphery_event(event) else {
+      continue;
+    };
+    let subscribers = subscribers().lock().unwrap().clone();
+    for core in subscribers {
+      let Some(connection) = core_connections().get(&core).await
+      else {
+        continue;
+      };
+      if let Err(e) = connection.sender.send_event(&event).await {
+        debug!("Failed to push event to Core | {e:#}");
+      }
+    }
+  }
+  Ok(())
+}
+
+fn periphery_event(event: EventMessage) -> Option<PeripheryEvent> {
+  let container = event.actor?.attributes?.remove("name")?;
+  use ContainerStateStatusEnum::*;
+  let state = match event.action?.as_str() {
+    "oom" => {
+      return Some(PeripheryEvent::ContainerOomKilled { container });
+    }
+    "create" => Created,
+    "start" | "unpause" => Running,
+    "pause" => Paused,
+    "die" | "stop" => Exited,
+    _ => return None,
+  };
+  Some(PeripheryEvent::ContainerStateChanged { container, state })
+}
@@ -0,0 +1,174 @@
+//! Waits for deployed containers to report healthy,
+//! so deploys fail when the containers crash loop.
+
+use std::{
+  collections::HashMap,
+  time::{Duration, Instant},
+};
+
+use komodo_client::entities::{
+  docker::container::{
+    Container, ContainerStateStatusEnum, HealthStatusEnum,
+  },
+  komodo_timestamp,
+  update::Log,
+};
+
+use crate::{api::Args, state::docker_client};
+
+use super::DockerClient;
+
+const STAGE: &str = "Wait For Healthy";
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+enum HealthCheck {
+  Healthy,
+  Waiting(String),
+  Failed(String),
+}
+
+/// Polls the containers until they are all healthy, sending status
+/// changes to Core if the request is streamed. Fails early if a container
+/// is unhealthy, exits or restarts, and once `timeout` elapses.
+pub async fn wait_for_healthy(
+  args: &Args,
+  containers: &[String],
+  timeout: Duration,
+) -> Log {
+  let start_ts = komodo_timestamp();
+  let client = docker_client().load();
+  let Some(client) = client.iter().next() else {
+    return Log::error(
+      STAGE,
+      String::from("Cannot check health, docker is not connected"),
+    );
+  };
+  let deadline = Instant::now() + timeout;
+  let mut restart_counts = HashMap::<&str, i64>::new();
+  let mut statuses = HashMap::<&str, String>::new();
+  let mut stdout = Vec::new();
+  let (success, stderr) = loop {
+    // Containers without a healthcheck need to
+    // stay running for at least one interval.
+    tokio::time::sleep(POLL_INTERVAL).await;
+    let mut waiting = Vec::new();
+    let mut failed = Vec::new();
+    for name in containers {
+      let check =
+        check_container(client, name, &mut restart_counts).await;
+      let status = match &check {
+        HealthCheck::Healthy => String::from("healthy"),
+        HealthCheck::Waiting(status) => {
+          waiting.push(name.as_str());
+          status.clone()
+        }
+        HealthCheck::Failed(status) => {
+          failed.push(name.as_str());
+          status.clone()
+        }
+      };
+      if statuses.get(name.as_str()) != Some(&status) {
+        let line = format!("{name}: {status}");
+        args.send_status(STAGE, line.clone()).await;
+        stdout.push(line);
+        statuses.insert(name, status);
+      }
+    }
+    if !failed.is_empty() {
+      break (
+        false,
+        format!(
+          "Containers failed to become healthy: {}",
+          failed.join(", ")
+        ),
+      );
+    }
+    if waiting.is_empty() {
+      break (true, String::new());
+    }
+    if Instant::now() >= deadline {
+      break (
+        false,
+        format!(
+          "Timed out after {}s waiting for containers to become healthy: {}",
+          timeout.as_secs(),
+          waiting.join(", ")
+        ),
+      );
+    }
+  };
+  Log {
+    stage: STAGE.to_string(),
+    stdout: stdout.join("\n"),
+    stderr,
+    success,
+    start_ts,
+    end_ts: komodo_timestamp(),
+    ..Default::default()
+  }
+}
+
+async fn check_container<'a>(
+  client: &DockerClient,
+  name: &'a str,
+  restart_counts: &mut HashMap<&'a str, i64>,
+) -> HealthCheck {
+  let Container {
+    state,
+    restart_count,
+    ..
+  } = match client.inspect_container(name).await {
+    Ok(container) => container,
+    Err(e) => {
+      return HealthCheck::Failed(format!(
+        "failed to inspect | {e:#}"
+      ));
+    }
+  };
+  let Some(state) = state else {
+    return HealthCheck::Waiting(String::from("no state"));
+  };
+  let restart_count = restart_count.unwrap_or_default();
+  let initial = *restart_counts.entry(name).or_insert(restart_count);
+  if restart_count > initial {
+    return HealthCheck::Failed(format!(
+      "restarted {} times",
+      restart_count - initial
+    ));
+  }
+  match state.status {
+    ContainerStateStatusEnum::Running => {}
+    ContainerStateStatusEnum::Exited
+    | ContainerStateStatusEnum::Dead => {
+      return HealthCheck::Failed(format!(
+        "{} with code {}",
+        state.status,
+        state.exit_code.unwrap_or_default()
+      ));
+    }
+    ContainerStateStatusEnum::Restarting => {
+      return HealthCheck::Failed(String::from("restarting"));
+    }
+    status => return HealthCheck::Waiting(status.to_string()),
+  }
+  let Some(health) = state.health else {
+    return HealthCheck::Healthy;
+  };
+  match health.status {
+    HealthStatusEnum::Healthy
+    | HealthStatusEnum::None
+    | HealthStatusEnum::Empty => HealthCheck::Healthy,
+    HealthStatusEnum::Starting => {
+      HealthCheck::Waiting(String::from("starting"))
+    }
+    HealthStatusEnum::Unhealthy => {
+      let output = health
+        .log
+        .last()
+        .and_then(|result| result.output.as_deref())
+        .map(str::trim)
+        .unwrap_or_default();
+      HealthCheck::Failed(format!("unhealthy | {output}"))
+    }
+  }
+}
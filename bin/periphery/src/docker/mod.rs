@@ -1,10 +1,17 @@
-use anyhow::{Context, anyhow};
+use anyhow::anyhow;
 use bollard::Docker;
 use command::run_komodo_command;
 use komodo_client::entities::{TerminationSignal, update::Log};
 use run_command::async_run_command;
 
+use self::runtime::docker_cli;
+
+pub mod buildx;
+pub mod events;
+pub mod health;
+pub mod runtime;
 pub mod stats;
+pub mod swarm;
 
 mod containers;
 mod images;
@@ -16,9 +23,9 @@ pub struct DockerClient {
 }
 
 impl DockerClient {
+  /// Connects to the api of the configured container runtime.
   pub fn connect() -> anyhow::Result<DockerClient> {
-    let docker = Docker::connect_with_defaults()
-      .context("Failed to connect to docker api. Docker monitoring won't work and will return empty results.")?;
+    let docker = runtime::runtime().connect()?;
     Ok(DockerClient { docker })
   }
 }
@@ -38,8 +45,9 @@ pub async fn docker_login(
     Some(token) => token,
     None => crate::helpers::registry_token(domain, account)?,
   };
+  let docker = docker_cli();
   let log = async_run_command(&format!(
-    "echo {registry_token} | docker login {domain} --username '{account}' --password-stdin",
+    "echo {registry_token} | {docker} login {domain} --username '{account}' --password-stdin",
   ))
   .await;
   if log.success() {
@@ -62,7 +70,8 @@ pub async fn docker_login(
 
 #[instrument("PullImage")]
 pub async fn pull_image(image: &str) -> Log {
-  let command = format!("docker pull {image}");
+  let command =
+    format!("{docker} pull {image}", docker = docker_cli());
   run_komodo_command("Docker Pull", None, command).await
 }
 
@@ -77,5 +86,8 @@ pub fn stop_container_command(
   let time = time
     .map(|time| format!(" --time {time}"))
     .unwrap_or_default();
-  format!("docker stop{signal}{time} {container_name}")
+  format!(
+    "{docker} stop{signal}{time} {container_name}",
+    docker = docker_cli()
+  )
 }
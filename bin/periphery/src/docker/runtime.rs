@@ -0,0 +1,111 @@
+//! The container runtimes Periphery can manage.
+//! Both serve the docker compatible api used for the
+//! typed reads, and differ in the socket and cli.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use bollard::{API_DEFAULT_VERSION, Docker};
+use komodo_client::entities::config::periphery::ContainerRuntime;
+
+use crate::config::periphery_config;
+
+/// Seconds before api requests time out,
+/// the same as the bollard default.
+const SOCKET_TIMEOUT: u64 = 120;
+
+pub trait RuntimeBackend: Send + Sync {
+  /// Connects to the runtime api socket.
+  fn connect(&self) -> anyhow::Result<Docker>;
+  /// The cli used for runtime commands, ie `docker`.
+  fn cli(&self) -> &'static str;
+  /// The compose cli, ie `docker compose`.
+  fn compose_cli(&self) -> &'static str;
+}
+
+/// The backend selected with `container_runtime`.
+pub fn runtime() -> &'static dyn RuntimeBackend {
+  match periphery_config().container_runtime {
+    ContainerRuntime::Docker => &DockerBackend,
+    ContainerRuntime::Podman => &PodmanBackend,
+  }
+}
+
+/// The cli of the configured runtime, for use in commands.
+pub fn docker_cli() -> &'static str {
+  runtime().cli()
+}
+
+pub struct DockerBackend;
+
+impl RuntimeBackend for DockerBackend {
+  fn connect(&self) -> anyhow::Result<Docker> {
+    match configured_socket() {
+      Some(socket) => connect_socket(socket),
+      None => Docker::connect_with_defaults().context(
+        "Failed to connect to docker api. Docker monitoring won't work and will return empty results.",
+      ),
+    }
+  }
+
+  fn cli(&self) -> &'static str {
+    "docker"
+  }
+
+  fn compose_cli(&self) -> &'static str {
+    if periphery_config().legacy_compose_cli {
+      "docker-compose"
+    } else {
+      "docker compose"
+    }
+  }
+}
+
+pub struct PodmanBackend;
+
+impl RuntimeBackend for PodmanBackend {
+  fn connect(&self) -> anyhow::Result<Docker> {
+    if let Some(socket) = configured_socket() {
+      return connect_socket(socket);
+    }
+    // Rootless podman serves the socket in the user runtime dir.
+    let rootless = std::env::var_os("XDG_RUNTIME_DIR")
+      .map(|dir| PathBuf::from(dir).join("podman/podman.sock"))
+      .filter(|socket| socket.exists());
+    match rootless {
+      Some(socket) => connect_socket(&socket.to_string_lossy()),
+      None => connect_socket("/run/podman/podman.sock"),
+    }
+  }
+
+  fn cli(&self) -> &'static str {
+    "podman"
+  }
+
+  fn compose_cli(&self) -> &'static str {
+    if periphery_config().legacy_compose_cli {
+      "podman-compose"
+    } else {
+      "podman compose"
+    }
+  }
+}
+
+fn configured_socket() -> Option<&'static str> {
+  periphery_config()
+    .container_runtime_socket
+    .as_deref()
+    .filter(|socket| !socket.is_empty())
+}
+
+fn connect_socket(socket: &str) -> anyhow::Result<Docker> {
+  let socket = socket.trim_start_matches("unix://");
+  Docker::connect_with_socket(
+    socket,
+    SOCKET_TIMEOUT,
+    API_DEFAULT_VERSION,
+  )
+  .with_context(|| {
+    format!("Failed to connect to container runtime api at {socket}")
+  })
+}
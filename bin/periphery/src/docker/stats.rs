@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Instant};
 
 use anyhow::{Context, anyhow};
 use async_timing_util::wait_until_timelength;
@@ -8,15 +8,16 @@ use komodo_client::entities::docker::{
   container::ContainerStats,
   stats::{
     ContainerBlkioStatEntry, ContainerBlkioStats, ContainerCpuStats,
-    ContainerCpuUsage, ContainerMemoryStats, ContainerNetworkStats,
-    ContainerPidsStats, ContainerStorageStats,
+    ContainerCpuUsage, ContainerIoRates, ContainerMemoryStats,
+    ContainerNetworkStats, ContainerPidsStats, ContainerStorageStats,
     ContainerThrottlingData, FullContainerStats,
   },
 };
 use run_command::async_run_command;
 
 use crate::{
-  config::periphery_config, docker::DockerClient,
+  config::periphery_config,
+  docker::{DockerClient, runtime::docker_cli},
   state::container_stats,
 };
 
@@ -27,17 +28,29 @@ pub fn spawn_polling_thread() {
       .to_string()
       .parse()
       .expect("invalid stats polling rate");
-    update_container_stats().await;
+    let mut last_sample = None;
+    update_container_stats(&mut last_sample).await;
     loop {
       let _ts = wait_until_timelength(polling_rate, 200).await;
-      update_container_stats().await;
+      update_container_stats(&mut last_sample).await;
     }
   });
 }
 
-async fn update_container_stats() {
+async fn update_container_stats(last_sample: &mut Option<Instant>) {
   match get_container_stats(None).await {
-    Ok(stats) => {
+    Ok(mut stats) => {
+      let now = Instant::now();
+      // Rates need a previous sample to diff against
+      if let Some(last_sample) = last_sample.replace(now) {
+        let elapsed = now.duration_since(last_sample).as_secs_f64();
+        let prev = container_stats().load();
+        for stats in &mut stats {
+          stats.io_rates = prev
+            .get(&stats.name)
+            .and_then(|prev| compute_io_rates(prev, stats, elapsed));
+        }
+      }
       container_stats().store(Arc::new(
         stats.into_iter().map(|s| (s.name.clone(), s)).collect(),
       ));
@@ -63,8 +76,10 @@ pub async fn get_container_stats(
     Some(name) => format!(" {name}"),
     None => "".to_string(),
   };
-  let command =
-    format!("docker stats{container_name} --no-stream {format}");
+  let command = format!(
+    "{docker} stats{container_name} --no-stream {format}",
+    docker = docker_cli()
+  );
   let output = async_run_command(&command).await;
   if output.success() {
     output
@@ -82,6 +97,60 @@ pub async fn get_container_stats(
   }
 }
 
+/// Computes the network / block IO rates between two samples.
+/// The docker cli reports the cumulative totals rounded to
+/// a few significant digits, so these are approximate.
+fn compute_io_rates(
+  prev: &ContainerStats,
+  curr: &ContainerStats,
+  elapsed_secs: f64,
+) -> Option<ContainerIoRates> {
+  if elapsed_secs <= 0.0 {
+    return None;
+  }
+  let (prev_rx, prev_tx) = parse_io_pair(&prev.net_io)?;
+  let (rx, tx) = parse_io_pair(&curr.net_io)?;
+  let (prev_read, prev_write) = parse_io_pair(&prev.block_io)?;
+  let (read, write) = parse_io_pair(&curr.block_io)?;
+  // Totals reset when the container restarts, don't report negative rates.
+  let rate =
+    |prev: f64, curr: f64| (curr - prev).max(0.0) / elapsed_secs;
+  Some(ContainerIoRates {
+    net_rx: rate(prev_rx, rx),
+    net_tx: rate(prev_tx, tx),
+    block_read: rate(prev_read, read),
+    block_write: rate(prev_write, write),
+  })
+}
+
+/// Parses docker cli io pairs, eg. '1.45kB / 648B'
+fn parse_io_pair(io: &str) -> Option<(f64, f64)> {
+  let (first, second) = io.split_once('/')?;
+  Some((parse_size(first.trim())?, parse_size(second.trim())?))
+}
+
+/// Parses docker cli human readable sizes, eg. '1.45kB' or '3.2MiB'
+fn parse_size(size: &str) -> Option<f64> {
+  let split =
+    size.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+  let (num, unit) = size.split_at(split);
+  let num = num.parse::<f64>().ok()?;
+  let multiplier = match unit.trim() {
+    "B" => 1.0,
+    "kB" | "KB" => 1e3,
+    "MB" => 1e6,
+    "GB" => 1e9,
+    "TB" => 1e12,
+    "PB" => 1e15,
+    "KiB" => 1024.0,
+    "MiB" => 1024.0 * 1024.0,
+    "GiB" => 1024.0 * 1024.0 * 1024.0,
+    "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+    _ => return None,
+  };
+  Some(num * multiplier)
+}
+
 impl DockerClient {
   /// Calls for stats once, similar to --no-stream on the cli
   pub async fn full_container_stats(
@@ -110,6 +179,10 @@ impl DockerClient {
       precpu_stats: stats.precpu_stats.map(convert_cpu_stats),
       memory_stats: stats.memory_stats.map(convert_memory_stats),
       networks: stats.networks.map(convert_network_stats),
+      io_rates: container_stats()
+        .load()
+        .get(container_name)
+        .and_then(|stats| stats.io_rates),
     })
   }
 }
@@ -0,0 +1,64 @@
+use anyhow::{Context, anyhow};
+use command::run_komodo_command;
+use komodo_client::entities::docker::swarm::{
+  SwarmNodeListItem, SwarmService, SwarmServiceListItem,
+};
+use serde::de::DeserializeOwned;
+use shell_escape::unix::escape;
+
+/// Uses the docker cli, which reports the replicas
+/// the same way users see them in `docker service ls`.
+pub async fn list_swarm_services()
+-> anyhow::Result<Vec<SwarmServiceListItem>> {
+  list_json_lines(
+    "List Swarm Services",
+    "docker service ls --format json",
+  )
+  .await
+}
+
+pub async fn list_swarm_nodes()
+-> anyhow::Result<Vec<SwarmNodeListItem>> {
+  list_json_lines("List Swarm Nodes", "docker node ls --format json")
+    .await
+}
+
+pub async fn inspect_service(
+  name: &str,
+) -> anyhow::Result<SwarmService> {
+  let command =
+    format!("docker service inspect {}", escape(name.into()));
+  let log =
+    run_komodo_command("Inspect Service", None, command).await;
+  if !log.success {
+    return Err(anyhow!("{}", log.combined()))
+      .with_context(|| format!("Failed to inspect service {name}"));
+  }
+  serde_json::from_str::<Vec<SwarmService>>(&log.stdout)
+    .context("Failed to parse 'docker service inspect' response")?
+    .pop()
+    .with_context(|| format!("No service found matching {name}"))
+}
+
+/// The docker cli `--format json` outputs one object per line.
+async fn list_json_lines<T: DeserializeOwned>(
+  stage: &str,
+  command: &str,
+) -> anyhow::Result<Vec<T>> {
+  let log = run_komodo_command(stage, None, command).await;
+  if !log.success {
+    return Err(anyhow!("{}", log.combined()))
+      .with_context(|| format!("Failed to run '{command}'"));
+  }
+  log
+    .stdout
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty())
+    .map(|line| {
+      serde_json::from_str(line).with_context(|| {
+        format!("Failed to parse '{command}' output")
+      })
+    })
+    .collect()
+}
@@ -0,0 +1,302 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, anyhow};
+use encoding::{ChunkedStream, Decode as _, EncodeStream as _};
+use periphery_client::{
+  api::file::PulledFile,
+  transport::{EncodedFileMessage, FILE_MESSAGE_BYTES, FileMessage},
+};
+use sha2::{Digest, Sha256};
+use tokio::{
+  fs::File,
+  io::{AsyncReadExt as _, AsyncSeekExt as _, AsyncWriteExt as _},
+};
+use tokio_stream::StreamExt as _;
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+use crate::{
+  config::periphery_config,
+  state::{core_connections, file_channels},
+};
+
+/// State of a file being pushed from Core.
+/// Contents are written to the partial path,
+/// and moved into place once the checksum is verified.
+#[derive(Debug)]
+pub struct FileTransfer {
+  /// Where the file is moved once finished.
+  pub path: PathBuf,
+  pub partial: PathBuf,
+  /// The total size of the file, if known up front.
+  pub size: Option<u64>,
+  /// The bytes written to the partial file.
+  pub received: u64,
+  file: File,
+}
+
+impl FileTransfer {
+  /// Opens the partial file for the channel.
+  /// The partial file is left in place if the transfer is interrupted,
+  /// so it can also resume after Periphery restarts.
+  pub async fn open(
+    channel: Uuid,
+    path: PathBuf,
+    size: Option<u64>,
+  ) -> anyhow::Result<FileTransfer> {
+    let file_name = path
+      .file_name()
+      .context("File path has no file name")?
+      .to_string_lossy();
+    let partial =
+      path.with_file_name(format!(".{file_name}.{channel}.partial"));
+    if let Some(parent) = path.parent() {
+      tokio::fs::create_dir_all(parent).await.with_context(|| {
+        format!("Failed to create directory {parent:?}")
+      })?;
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+      .create(true)
+      .write(true)
+      .truncate(false)
+      .open(&partial)
+      .await
+      .with_context(|| format!("Failed to open {partial:?}"))?;
+    let mut received = file
+      .metadata()
+      .await
+      .context("Failed to read partial file metadata")?
+      .len();
+    if size.is_some_and(|size| received > size) {
+      // Not from this transfer, start over.
+      file
+        .set_len(0)
+        .await
+        .context("Failed to truncate partial file")?;
+      received = 0;
+    }
+    file
+      .seek(std::io::SeekFrom::Start(received))
+      .await
+      .context("Failed to seek partial file")?;
+    Ok(FileTransfer {
+      path,
+      partial,
+      size,
+      received,
+      file,
+    })
+  }
+
+  async fn write(
+    &mut self,
+    offset: u64,
+    data: &[u8],
+  ) -> anyhow::Result<()> {
+    if offset != self.received {
+      return Err(anyhow!(
+        "Expected data at offset {}, got offset {offset}",
+        self.received
+      ));
+    }
+    let received = self.received + data.len() as u64;
+    if let Some(size) = self.size
+      && received > size
+    {
+      return Err(anyhow!(
+        "Received more than the expected {size} bytes"
+      ));
+    }
+    self
+      .file
+      .write_all(data)
+      .await
+      .context("Failed to write to partial file")?;
+    self.received = received;
+    Ok(())
+  }
+
+  /// Verifies the checksum and moves the file into place.
+  /// The partial file is removed if the checksum doesn't match.
+  pub async fn finish(&mut self, sha256: &str) -> anyhow::Result<()> {
+    self.check_received()?;
+    self
+      .file
+      .sync_all()
+      .await
+      .context("Failed to sync partial file")?;
+    let actual = hash_file(&self.partial).await?;
+    if !actual.eq_ignore_ascii_case(sha256) {
+      let _ = self.cancel().await;
+      return Err(anyhow!(
+        "Checksum mismatch | expected: {sha256} | got: {actual}"
+      ));
+    }
+    tokio::fs::rename(&self.partial, &self.path)
+      .await
+      .with_context(|| {
+        format!("Failed to move file into place at {:?}", self.path)
+      })
+  }
+
+  /// Errors if fewer bytes than the expected size were received.
+  pub fn check_received(&self) -> anyhow::Result<()> {
+    match self.size {
+      Some(size) if self.received != size => Err(anyhow!(
+        "Only received {} of {size} bytes",
+        self.received
+      )),
+      _ => Ok(()),
+    }
+  }
+
+  /// Removes the partial contents.
+  pub async fn cancel(&self) -> anyhow::Result<()> {
+    tokio::fs::remove_file(&self.partial)
+      .await
+      .with_context(|| format!("Failed to remove {:?}", self.partial))
+  }
+}
+
+pub async fn handle_message(message: EncodedFileMessage) {
+  let FileMessage {
+    channel,
+    offset,
+    data,
+  } = match message.decode() {
+    Ok(res) => res,
+    Err(e) => {
+      warn!("Received invalid File bytes | {e:#}");
+      return;
+    }
+  };
+  let Some(transfer) = file_channels().get(&channel).await else {
+    warn!("Received File message for unknown transfer {channel}");
+    return;
+  };
+  if let Err(e) = transfer.lock().await.write(offset, &data).await {
+    warn!("Failed to write File message for {channel} | {e:#}");
+  }
+}
+
+/// Sends the file contents after `offset` to the Core
+/// as File messages on the `channel`.
+/// The returned sha256 covers the full file.
+pub async fn send_file(
+  core: &str,
+  channel: Uuid,
+  path: &Path,
+  offset: u64,
+) -> anyhow::Result<PulledFile> {
+  let connection = core_connections()
+    .get(core)
+    .await
+    .with_context(|| format!("Failed to find channel for {core}"))?;
+  let mut file = File::open(path)
+    .await
+    .with_context(|| format!("Failed to open {path:?}"))?;
+  let size = file
+    .metadata()
+    .await
+    .context("Failed to read file metadata")?
+    .len();
+  if offset > size {
+    return Err(anyhow!(
+      "Offset {offset} is past the end of the file ({size} bytes)"
+    ));
+  }
+
+  let mut hasher = Sha256::new();
+
+  // Hash the contents Core already has.
+  let mut buf = vec![0u8; FILE_MESSAGE_BYTES];
+  let mut position = 0u64;
+  while position < offset {
+    let max = buf.len().min((offset - position) as usize);
+    let n = file
+      .read(&mut buf[..max])
+      .await
+      .with_context(|| format!("Failed to read {path:?}"))?;
+    if n == 0 {
+      return Err(anyhow!("File shrank while reading {path:?}"));
+    }
+    hasher.update(&buf[..n]);
+    position += n as u64;
+  }
+  file
+    .seek(std::io::SeekFrom::Start(offset))
+    .await
+    .context("Failed to seek file")?;
+
+  // Send the rest, re-chunked into File messages.
+  // Only up to the size, in case the file grows while sending.
+  let mut read_error = None;
+  {
+    let contents = ReaderStream::with_capacity(
+      file.take(size - offset),
+      FILE_MESSAGE_BYTES,
+    )
+    .map_while(|res| res.map_err(|e| read_error = Some(e)).ok());
+    let chunks = ChunkedStream {
+      stream: contents,
+      chunk_bytes: FILE_MESSAGE_BYTES,
+    }
+    .encode_stream();
+    tokio::pin!(chunks);
+    while let Some(data) = chunks.next().await {
+      hasher.update(&data);
+      let len = data.len() as u64;
+      connection
+        .sender
+        .send_message(FileMessage {
+          channel,
+          offset: position,
+          data,
+        })
+        .await
+        .context("Failed to send file contents to Core")?;
+      position += len;
+    }
+  }
+  if let Some(e) = read_error {
+    return Err(e)
+      .with_context(|| format!("Failed to read {path:?}"));
+  }
+  if position != size {
+    return Err(anyhow!("File shrank while reading {path:?}"));
+  }
+
+  Ok(PulledFile {
+    size,
+    sha256: hex::encode(hasher.finalize()),
+  })
+}
+
+/// Relative paths are relative to the Periphery root directory.
+pub fn resolve_path(path: PathBuf) -> PathBuf {
+  if path.is_absolute() {
+    path
+  } else {
+    periphery_config().root_directory.join(path)
+  }
+}
+
+/// Returns the hex encoded sha256 of the file contents.
+pub async fn hash_file(path: &Path) -> anyhow::Result<String> {
+  let mut file = File::open(path)
+    .await
+    .with_context(|| format!("Failed to open {path:?}"))?;
+  let mut hasher = Sha256::new();
+  let mut buf = vec![0u8; 64 * 1024];
+  loop {
+    let n = file
+      .read(&mut buf)
+      .await
+      .with_context(|| format!("Failed to read {path:?}"))?;
+    if n == 0 {
+      break;
+    }
+    hasher.update(&buf[..n]);
+  }
+  Ok(hex::encode(hasher.finalize()))
+}
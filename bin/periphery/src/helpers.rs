@@ -210,6 +210,9 @@ pub fn registry_token(
 
 pub async fn ensure_ssl_certs() {
   let config = periphery_config();
+  if config.ssl_acme().is_some() {
+    return crate::acme::ensure_cert().await;
+  }
   if !config.ssl_cert_file().is_file()
     || !config.ssl_key_file().is_file()
   {
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use futures::{StreamExt, stream::FuturesUnordered};
 use komodo_client::entities::config::periphery::Command;
 use tracing::Instrument;
@@ -10,11 +12,15 @@ use crate::{
 #[macro_use]
 extern crate tracing;
 
+mod acme;
 mod api;
 mod config;
 mod connection;
 mod docker;
+mod file;
 mod helpers;
+mod rollback;
+mod schedule;
 mod state;
 mod stats;
 mod terminal;
@@ -44,8 +50,24 @@ async fn app() -> anyhow::Result<()> {
       .install_default()
       .expect("Failed to install default crypto provider");
 
+    transport::auth::set_auth_timeout(Duration::from_millis(
+      config.auth_timeout_ms,
+    ));
+    if let Some(psk) = config.transport_psk.as_deref() {
+      transport::auth::set_transport_psk(psk);
+    }
+    encoding::set_decode_limits(encoding::DecodeLimits {
+      max_message_bytes: config.transport_max_message_bytes,
+      max_body_bytes: config.transport_max_body_bytes,
+      max_body_depth: config.transport_max_body_depth,
+    });
+
     stats::spawn_polling_thread();
     docker::stats::spawn_polling_thread();
+    docker::events::spawn_event_watcher();
+    docker::buildx::spawn_platforms_refresh();
+    schedule::init_schedules().await;
+    schedule::spawn_schedule_executor();
 
     let handles = FuturesUnordered::new();
 
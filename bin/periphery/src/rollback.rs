@@ -0,0 +1,132 @@
+//! Tracks the previously deployed versions of Deployments and Stacks,
+//! so failed deploys can be rolled back.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::periphery_config, state::docker_client};
+
+/// The max number of images kept in each Deployment history.
+const MAX_DEPLOYMENT_HISTORY: usize = 10;
+
+fn rollback_directory() -> PathBuf {
+  periphery_config().root_directory.join("rollback")
+}
+
+/// An image a Deployment was successfully deployed with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployedImage {
+  /// The configured image, ie `nginx:latest`.
+  pub image: String,
+  /// The id of the image the container ran,
+  /// which still points to it if the tag moves.
+  pub image_id: String,
+}
+
+fn deployment_history_path(name: &str) -> PathBuf {
+  rollback_directory()
+    .join("deployments")
+    .join(format!("{name}.json"))
+}
+
+/// The images of the successful deploys, oldest first.
+async fn deployment_history(name: &str) -> Vec<DeployedImage> {
+  let Ok(contents) =
+    tokio::fs::read_to_string(deployment_history_path(name)).await
+  else {
+    return Vec::new();
+  };
+  serde_json::from_str(&contents)
+    .inspect_err(|e| {
+      warn!("Invalid rollback history for deployment {name} | {e:?}")
+    })
+    .unwrap_or_default()
+}
+
+async fn write_deployment_history(
+  name: &str,
+  history: &[DeployedImage],
+) -> anyhow::Result<()> {
+  let path = deployment_history_path(name);
+  if let Some(parent) = path.parent() {
+    tokio::fs::create_dir_all(parent).await.with_context(|| {
+      format!("Failed to create rollback directory {parent:?}")
+    })?;
+  }
+  let contents = serde_json::to_string_pretty(history)
+    .context("Failed to serialize rollback history")?;
+  tokio::fs::write(&path, contents)
+    .await
+    .with_context(|| format!("Failed to write {path:?}"))
+}
+
+/// The id of the image the container is running, if it exists.
+pub async fn container_image_id(name: &str) -> Option<String> {
+  docker_client()
+    .load()
+    .iter()
+    .next()?
+    .inspect_container(name)
+    .await
+    .ok()?
+    .image
+}
+
+/// Records the image of the container after a successful deploy.
+pub async fn record_deployment(
+  name: &str,
+  image: &str,
+) -> anyhow::Result<()> {
+  let image_id = container_image_id(name)
+    .await
+    .context("Failed to get the deployed container image id")?;
+  let mut history = deployment_history(name).await;
+  if history.last().map(|last| &last.image_id) == Some(&image_id) {
+    return Ok(());
+  }
+  history.push(DeployedImage {
+    image: image.to_string(),
+    image_id,
+  });
+  if history.len() > MAX_DEPLOYMENT_HISTORY {
+    history.drain(..history.len() - MAX_DEPLOYMENT_HISTORY);
+  }
+  write_deployment_history(name, &history).await
+}
+
+/// The latest deployed image which differs from the current one,
+/// and its index in the history.
+pub async fn deployment_rollback_target(
+  name: &str,
+  current_image_id: Option<&str>,
+) -> Option<(usize, DeployedImage)> {
+  deployment_history(name)
+    .await
+    .into_iter()
+    .enumerate()
+    .rev()
+    .find(|(_, deployed)| {
+      Some(deployed.image_id.as_str()) != current_image_id
+    })
+}
+
+/// Drops the history after the image rolled back to,
+/// so rolling back again goes to the one before it.
+pub async fn finish_deployment_rollback(
+  name: &str,
+  index: usize,
+) -> anyhow::Result<()> {
+  let mut history = deployment_history(name).await;
+  history.truncate(index + 1);
+  write_deployment_history(name, &history).await
+}
+
+/// The resolved compose config of the last successful deploy of the project.
+/// It uses absolute paths, so it can be deployed from any directory.
+pub fn stack_rollback_file(project: &str) -> PathBuf {
+  rollback_directory()
+    .join("stacks")
+    .join(format!("{project}.compose.yaml"))
+}
@@ -0,0 +1,309 @@
+//! Runs the commands in `PeripheryConfig.schedules`, and the schedules
+//! added with `UpsertSchedule`, on their cron expressions whether or not
+//! Core is connected. The run logs are queued and pushed to the
+//! connected Cores as [PeripheryEvent::ScheduleRun].
+
+use std::{
+  collections::{HashMap, HashSet, VecDeque},
+  path::PathBuf,
+  sync::OnceLock,
+};
+
+use anyhow::{Context, anyhow};
+use async_timing_util::{Timelength, wait_until_timelength};
+use command::run_komodo_command;
+use croner::parser::{CronParser, Seconds};
+use komodo_client::entities::server_schedule::{
+  ServerSchedule, ServerScheduleStatus,
+};
+use periphery_client::transport::PeripheryEvent;
+use tokio::sync::Mutex;
+
+use crate::{config::periphery_config, state::core_connections};
+
+/// The oldest runs are dropped past this many,
+/// if no Core connects for a long time.
+const MAX_PENDING_RUNS: usize = 100;
+
+/// Schedule name -> The schedule and its run status.
+fn schedules() -> &'static Mutex<HashMap<String, ServerScheduleStatus>>
+{
+  static SCHEDULES: OnceLock<
+    Mutex<HashMap<String, ServerScheduleStatus>>,
+  > = OnceLock::new();
+  SCHEDULES.get_or_init(Default::default)
+}
+
+/// The names of the schedules currently running,
+/// so slow commands don't pile up.
+fn running() -> &'static std::sync::Mutex<HashSet<String>> {
+  static RUNNING: OnceLock<std::sync::Mutex<HashSet<String>>> =
+    OnceLock::new();
+  RUNNING.get_or_init(Default::default)
+}
+
+/// The runs not yet pushed to a Core, oldest first.
+fn pending_runs() -> &'static Mutex<VecDeque<PeripheryEvent>> {
+  static PENDING_RUNS: OnceLock<Mutex<VecDeque<PeripheryEvent>>> =
+    OnceLock::new();
+  PENDING_RUNS.get_or_init(Default::default)
+}
+
+fn api_schedules_path() -> PathBuf {
+  periphery_config().root_directory.join("schedules.json")
+}
+
+fn cron_parser() -> &'static CronParser {
+  static CRON_PARSER: OnceLock<CronParser> = OnceLock::new();
+  CRON_PARSER.get_or_init(|| {
+    CronParser::builder()
+      .seconds(Seconds::Optional)
+      .dom_and_dow(true)
+      .build()
+  })
+}
+
+/// Finds the next run in UTC ms, or None if the schedule is disabled.
+fn find_next_run(
+  schedule: &ServerSchedule,
+) -> anyhow::Result<Option<i64>> {
+  if !schedule.enabled {
+    return Ok(None);
+  }
+  let cron = cron_parser()
+    .parse(&schedule.schedule)
+    .context("Invalid cron schedule")?;
+  let next = cron
+    .find_next_occurrence(&chrono::Local::now(), false)
+    .context("Failed to find next run time")?;
+  Ok(Some(next.timestamp_millis()))
+}
+
+fn schedule_status(
+  schedule: ServerSchedule,
+  from_config: bool,
+) -> ServerScheduleStatus {
+  let (next_run_ts, error) = match find_next_run(&schedule) {
+    Ok(next_run_ts) => (next_run_ts, None),
+    Err(e) => (None, Some(format!("{e:#}"))),
+  };
+  ServerScheduleStatus {
+    schedule,
+    from_config,
+    next_run_ts,
+    error,
+    last_run_ts: None,
+    last_run_success: None,
+  }
+}
+
+/// Loads the schedules from the config and `schedules.json`.
+/// Config schedules take precedence over stored ones with the same name.
+pub async fn init_schedules() {
+  let mut schedules = schedules().lock().await;
+  let stored = read_api_schedules().await;
+  let config = periphery_config().schedules.iter().cloned();
+  let all = config
+    .map(|schedule| (schedule, true))
+    .chain(stored.into_iter().map(|schedule| (schedule, false)));
+  for (schedule, from_config) in all {
+    if schedules.contains_key(&schedule.name) {
+      warn!(
+        "Duplicate schedule '{}', only the first is used",
+        schedule.name
+      );
+      continue;
+    }
+    let status = schedule_status(schedule, from_config);
+    if let Some(e) = &status.error {
+      warn!("Schedule '{}' can't run | {e}", status.schedule.name);
+    }
+    schedules.insert(status.schedule.name.clone(), status);
+  }
+}
+
+pub fn spawn_schedule_executor() {
+  tokio::spawn(async move {
+    loop {
+      let current_time =
+        wait_until_timelength(Timelength::OneSecond, 0).await as i64;
+      let due = {
+        let mut schedules = schedules().lock().await;
+        schedules
+          .values_mut()
+          .filter(|status| {
+            status.next_run_ts.is_some_and(|ts| ts <= current_time)
+          })
+          .map(|status| {
+            status.last_run_ts = Some(current_time);
+            status.next_run_ts =
+              find_next_run(&status.schedule).ok().flatten();
+            status.schedule.clone()
+          })
+          .collect::<Vec<_>>()
+      };
+      for schedule in due {
+        tokio::spawn(run_schedule(schedule));
+      }
+      push_pending_runs().await;
+    }
+  });
+}
+
+async fn run_schedule(schedule: ServerSchedule) {
+  if !running().lock().unwrap().insert(schedule.name.clone()) {
+    warn!(
+      "Schedule '{}' is still running, skipping this run",
+      schedule.name
+    );
+    return;
+  }
+  let log = run_komodo_command(
+    &format!("Schedule: {}", schedule.name),
+    None,
+    &schedule.command,
+  )
+  .await;
+  running().lock().unwrap().remove(&schedule.name);
+  if !log.success {
+    warn!("Schedule '{}' failed | {}", schedule.name, log.stderr);
+  }
+  if let Some(status) =
+    schedules().lock().await.get_mut(&schedule.name)
+  {
+    status.last_run_success = Some(log.success);
+  }
+  {
+    let mut pending = pending_runs().lock().await;
+    if pending.len() >= MAX_PENDING_RUNS {
+      pending.pop_front();
+    }
+    pending.push_back(PeripheryEvent::ScheduleRun {
+      name: schedule.name,
+      log,
+    });
+  }
+  push_pending_runs().await;
+}
+
+/// Sends the pending runs in order, stopping at the first one
+/// no Core accepts. They are retried on the next tick.
+async fn push_pending_runs() {
+  // Held through the sends, so runs aren't sent twice.
+  let mut pending = pending_runs().lock().await;
+  if pending.is_empty() {
+    return;
+  }
+  let connections = core_connections().get_values().await;
+  while let Some(event) = pending.front() {
+    let mut sent = false;
+    for connection in &connections {
+      match connection.sender.send_event(event).await {
+        Ok(()) => sent = true,
+        Err(e) => {
+          debug!("Failed to push schedule run to Core | {e:#}")
+        }
+      }
+    }
+    if !sent {
+      return;
+    }
+    pending.pop_front();
+  }
+}
+
+pub async fn list_schedules() -> Vec<ServerScheduleStatus> {
+  let mut schedules = schedules()
+    .lock()
+    .await
+    .values()
+    .cloned()
+    .collect::<Vec<_>>();
+  schedules.sort_by(|a, b| a.schedule.name.cmp(&b.schedule.name));
+  schedules
+}
+
+pub async fn upsert_schedule(
+  mut schedule: ServerSchedule,
+) -> anyhow::Result<ServerScheduleStatus> {
+  schedule.name = schedule.name.trim().to_string();
+  if schedule.name.is_empty() {
+    return Err(anyhow!("Schedule name can't be empty"));
+  }
+  if schedule.command.trim().is_empty() {
+    return Err(anyhow!("Schedule command can't be empty"));
+  }
+  let mut schedules = schedules().lock().await;
+  let existing = schedules.get(&schedule.name);
+  if existing.is_some_and(|status| status.from_config) {
+    return Err(anyhow!(
+      "Schedule '{}' is set in the periphery config, and can't be changed through the API",
+      schedule.name
+    ));
+  }
+  let mut status = schedule_status(schedule, false);
+  if let Some(e) = &status.error {
+    return Err(anyhow!("{e}"));
+  }
+  if let Some(existing) = existing {
+    status.last_run_ts = existing.last_run_ts;
+    status.last_run_success = existing.last_run_success;
+  }
+  let mut updated = schedules.clone();
+  updated.insert(status.schedule.name.clone(), status.clone());
+  write_api_schedules(&updated).await?;
+  *schedules = updated;
+  Ok(status)
+}
+
+pub async fn delete_schedule(name: &str) -> anyhow::Result<()> {
+  let mut schedules = schedules().lock().await;
+  match schedules.get(name) {
+    Some(status) if status.from_config => {
+      return Err(anyhow!(
+        "Schedule '{name}' is set in the periphery config, and can't be deleted through the API"
+      ));
+    }
+    Some(_) => {}
+    None => return Err(anyhow!("No schedule named '{name}'")),
+  }
+  let mut updated = schedules.clone();
+  updated.remove(name);
+  write_api_schedules(&updated).await?;
+  *schedules = updated;
+  Ok(())
+}
+
+async fn read_api_schedules() -> Vec<ServerSchedule> {
+  let Ok(contents) =
+    tokio::fs::read_to_string(api_schedules_path()).await
+  else {
+    return Vec::new();
+  };
+  serde_json::from_str(&contents)
+    .inspect_err(|e| warn!("Invalid schedules.json | {e:?}"))
+    .unwrap_or_default()
+}
+
+/// Writes the schedules added through the API, sorted by name.
+async fn write_api_schedules(
+  schedules: &HashMap<String, ServerScheduleStatus>,
+) -> anyhow::Result<()> {
+  let mut stored = schedules
+    .values()
+    .filter(|status| !status.from_config)
+    .map(|status| &status.schedule)
+    .collect::<Vec<_>>();
+  stored.sort_by(|a, b| a.name.cmp(&b.name));
+  let path = api_schedules_path();
+  if let Some(parent) = path.parent() {
+    tokio::fs::create_dir_all(parent).await.with_context(|| {
+      format!("Failed to create schedules directory {parent:?}")
+    })?;
+  }
+  let contents = serde_json::to_string_pretty(&stored)
+    .context("Failed to serialize schedules")?;
+  tokio::fs::write(&path, contents)
+    .await
+    .with_context(|| format!("Failed to write {path:?}"))
+}
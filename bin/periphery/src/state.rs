@@ -18,8 +18,9 @@ use uuid::Uuid;
 use crate::{
   config::periphery_config,
   docker::DockerClient,
+  file::FileTransfer,
   stats::StatsClient,
-  terminal::{StdinMsg, Terminal},
+  terminal::{ExecuteBuffer, StdinMsg, Terminal},
 };
 
 /// Should call in startup to ensure Periphery errors without valid private key.
@@ -188,6 +189,24 @@ pub fn terminal_channels() -> &'static TerminalChannels {
 pub struct TerminalChannel {
   pub sender: mpsc::Sender<StdinMsg>,
   pub cancel: CancellationToken,
+  /// Stdin and resizes received on the channel are dropped.
+  pub read_only: bool,
+}
+
+/// File channel id -> In progress file push from Core
+pub type FileChannels = CloneCache<Uuid, Arc<Mutex<FileTransfer>>>;
+
+pub fn file_channels() -> &'static FileChannels {
+  static FILE_CHANNELS: OnceLock<FileChannels> = OnceLock::new();
+  FILE_CHANNELS.get_or_init(Default::default)
+}
+
+/// Execution channel id -> Buffered execution output
+pub type ExecuteBuffers = CloneCache<Uuid, Arc<ExecuteBuffer>>;
+
+pub fn execute_buffers() -> &'static ExecuteBuffers {
+  static EXECUTE_BUFFERS: OnceLock<ExecuteBuffers> = OnceLock::new();
+  EXECUTE_BUFFERS.get_or_init(Default::default)
 }
 
 pub fn terminal_triggers() -> &'static TerminalTriggers {
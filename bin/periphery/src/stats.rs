@@ -1,14 +1,23 @@
-use std::cmp::Ordering;
+use std::{
+  cmp::Ordering, collections::VecDeque, path::Path, time::Instant,
+};
 
 use async_timing_util::wait_until_timelength;
 use komodo_client::entities::stats::{
-  SingleDiskUsage, SystemInformation, SystemLoadAverage,
-  SystemProcess, SystemStats,
+  SingleDiskUsage, SingleNetworkInterfaceUsage, SystemInformation,
+  SystemLoadAverage, SystemProcess, SystemStats,
 };
 use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
 
 use crate::{config::periphery_config, state::stats_client};
 
+pub mod os_updates;
+pub mod smart;
+pub mod sockets;
+
+mod gpu;
+mod zfs;
+
 /// This should be called before starting the server in main.rs.
 /// Keeps the cached stats up to date
 pub fn spawn_polling_thread() {
@@ -21,10 +30,16 @@ pub fn spawn_polling_thread() {
     let client = stats_client();
     loop {
       let ts = wait_until_timelength(polling_rate, 1).await;
+      // Collected before taking the lock, as they run commands.
+      let (gpus, zfs_pools) =
+        tokio::join!(gpu::get_gpus(), zfs::get_zfs_pools());
       let mut client = client.write().await;
       client.refresh();
       client.stats = client.get_system_stats();
+      client.stats.gpus = gpus;
+      client.stats.zfs_pools = zfs_pools;
       client.stats.refresh_ts = ts as i64;
+      client.push_history();
     }
   });
 }
@@ -34,11 +49,22 @@ pub struct StatsClient {
   pub stats: SystemStats,
   /// Cached system information
   pub info: SystemInformation,
+  /// The recent stats samples, oldest first
+  history: VecDeque<SystemStats>,
 
   // the handles used to get the stats
   system: sysinfo::System,
   disks: sysinfo::Disks,
   networks: sysinfo::Networks,
+  /// When the networks were last refreshed,
+  /// to turn the bytes since then into rates.
+  networks_refreshed: Instant,
+  /// The seconds between the last two network refreshes
+  networks_elapsed_secs: f64,
+  /// The cumulative (steal, total) cpu time at the last refresh
+  cpu_times: Option<(u64, u64)>,
+  /// The steal percentage between the last two refreshes
+  cpu_steal_perc: Option<f32>,
 }
 
 const BYTES_PER_GB: f64 = 1073741824.0;
@@ -56,9 +82,14 @@ impl Default for StatsClient {
     };
     StatsClient {
       info: get_system_information(&system),
+      history: VecDeque::new(),
       system,
       disks,
       networks,
+      networks_refreshed: Instant::now(),
+      networks_elapsed_secs: 0.0,
+      cpu_times: platform::cpu_times(),
+      cpu_steal_perc: None,
       stats,
     }
   }
@@ -75,6 +106,52 @@ impl StatsClient {
     );
     self.disks.refresh(true);
     self.networks.refresh(true);
+    let now = Instant::now();
+    self.networks_elapsed_secs =
+      now.duration_since(self.networks_refreshed).as_secs_f64();
+    self.networks_refreshed = now;
+    let cpu_times = platform::cpu_times();
+    self.cpu_steal_perc = match (self.cpu_times, cpu_times) {
+      (Some((prev_steal, prev_total)), Some((steal, total)))
+        if total > prev_total =>
+      {
+        Some(
+          steal.saturating_sub(prev_steal) as f32
+            / (total - prev_total) as f32
+            * 100.0,
+        )
+      }
+      _ => None,
+    };
+    self.cpu_times = cpu_times;
+  }
+
+  /// Adds the current stats to the history,
+  /// and drops the samples older than `stats_history_hours`.
+  fn push_history(&mut self) {
+    let hours = periphery_config().stats_history_hours;
+    if hours == 0 {
+      return;
+    }
+    let cutoff = self.stats.refresh_ts - hours as i64 * 3_600_000;
+    while self
+      .history
+      .front()
+      .is_some_and(|stats| stats.refresh_ts < cutoff)
+    {
+      self.history.pop_front();
+    }
+    self.history.push_back(self.stats.clone());
+  }
+
+  /// The stats samples polled after `since`, oldest first.
+  pub fn get_history(&self, since: i64) -> Vec<SystemStats> {
+    self
+      .history
+      .iter()
+      .filter(|stats| stats.refresh_ts > since)
+      .cloned()
+      .collect()
   }
 
   pub fn get_system_stats(&self) -> SystemStats {
@@ -89,10 +166,17 @@ impl StatsClient {
       network_egress_bytes += network.transmitted();
     }
 
-    let load_avg = System::load_average();
+    let load_avg = platform::load_average(&self.system);
 
     SystemStats {
       cpu_perc: self.system.global_cpu_usage(),
+      cpu_cores: self
+        .system
+        .cpus()
+        .iter()
+        .map(|cpu| cpu.cpu_usage())
+        .collect(),
+      cpu_steal_perc: self.cpu_steal_perc,
       load_average: SystemLoadAverage {
         one: load_avg.one,
         five: load_avg.five,
@@ -103,7 +187,10 @@ impl StatsClient {
       mem_total_gb: total_mem as f64 / BYTES_PER_GB,
       network_ingress_bytes: network_ingress_bytes as f64,
       network_egress_bytes: network_egress_bytes as f64,
+      network_interfaces: self.get_network_interfaces(),
       disks: self.get_disks(),
+      gpus: self.stats.gpus.clone(),
+      zfs_pools: self.stats.zfs_pools.clone(),
       polling_rate: self.stats.polling_rate,
       refresh_ts: self.stats.refresh_ts,
       refresh_list_ts: self.stats.refresh_list_ts,
@@ -117,12 +204,12 @@ impl StatsClient {
       .list()
       .iter()
       .filter(|d| {
-        if d.file_system() == "overlay" {
+        if platform::ignore_disk(d) {
           return false;
         }
         let path = d.mount_point();
         for mount in config.exclude_disk_mounts.iter() {
-          if path == mount {
+          if platform::mount_matches(path, mount) {
             return false;
           }
         }
@@ -130,7 +217,7 @@ impl StatsClient {
           return true;
         }
         for mount in config.include_disk_mounts.iter() {
-          if path == mount {
+          if platform::mount_matches(path, mount) {
             return true;
           }
         }
@@ -151,6 +238,52 @@ impl StatsClient {
       .collect()
   }
 
+  fn get_network_interfaces(
+    &self,
+  ) -> Vec<SingleNetworkInterfaceUsage> {
+    let config = periphery_config();
+    let rate = |bytes: u64| {
+      if self.networks_elapsed_secs > 0.0 {
+        bytes as f64 / self.networks_elapsed_secs
+      } else {
+        0.0
+      }
+    };
+    let mut interfaces = self
+      .networks
+      .iter()
+      .filter(|(name, _)| {
+        if config
+          .exclude_network_interfaces
+          .iter()
+          .any(|exclude| exclude == *name)
+        {
+          return false;
+        }
+        config.include_network_interfaces.is_empty()
+          || config
+            .include_network_interfaces
+            .iter()
+            .any(|include| include == *name)
+      })
+      .map(|(name, network)| {
+        let (rx_dropped, tx_dropped) =
+          platform::interface_drops(name);
+        SingleNetworkInterfaceUsage {
+          name: name.clone(),
+          rx: rate(network.received()),
+          tx: rate(network.transmitted()),
+          rx_errors: network.total_errors_on_received() as i64,
+          tx_errors: network.total_errors_on_transmitted() as i64,
+          rx_dropped,
+          tx_dropped,
+        }
+      })
+      .collect::<Vec<_>>();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+  }
+
   pub fn get_processes(&self) -> Vec<SystemProcess> {
     let mut procs: Vec<_> = self
       .system
@@ -208,3 +341,161 @@ fn get_system_information(
       .unwrap_or_default(),
   }
 }
+
+/// The disk, load and network reporting differs per OS.
+/// sysinfo handles the collection itself (procfs on Linux,
+/// PDH / the Win32 APIs on Windows, and host / IOKit APIs on macOS),
+/// this smooths over how the results should be reported.
+#[cfg(target_os = "linux")]
+mod platform {
+  use super::*;
+
+  pub fn ignore_disk(disk: &sysinfo::Disk) -> bool {
+    disk.file_system() == "overlay"
+  }
+
+  pub fn mount_matches(path: &Path, mount: &Path) -> bool {
+    path == mount
+  }
+
+  pub fn load_average(_: &System) -> sysinfo::LoadAvg {
+    System::load_average()
+  }
+
+  /// sysinfo doesn't report drops, they are read from
+  /// the interface statistics in sysfs.
+  pub fn interface_drops(name: &str) -> (Option<i64>, Option<i64>) {
+    let statistics =
+      Path::new("/sys/class/net").join(name).join("statistics");
+    let read = |file: &str| {
+      std::fs::read_to_string(statistics.join(file))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+    };
+    (read("rx_dropped"), read("tx_dropped"))
+  }
+
+  /// The cumulative (steal, total) cpu time in jiffies,
+  /// from the aggregate `cpu` line of `/proc/stat`.
+  pub fn cpu_times() -> Option<(u64, u64)> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let times = stat
+      .lines()
+      .next()?
+      .strip_prefix("cpu ")?
+      .split_whitespace()
+      .map(|time| time.parse::<u64>().ok())
+      .collect::<Option<Vec<_>>>()?;
+    // user nice system idle iowait irq softirq steal guest guest_nice.
+    // guest time is already counted in user.
+    let steal = *times.get(7)?;
+    let total = times.iter().take(8).sum();
+    Some((steal, total))
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::*;
+
+  /// APFS splits the boot disk into many volumes (Preboot, VM, Data...)
+  /// sharing the one container. Their total and available space are
+  /// the container's, so only `/` is reported to not count it many times.
+  pub fn ignore_disk(disk: &sysinfo::Disk) -> bool {
+    let fs = disk.file_system();
+    let mount = disk.mount_point();
+    fs == "devfs"
+      || fs == "autofs"
+      || fs == "nullfs"
+      || mount.starts_with("/System/Volumes")
+      || mount.starts_with("/private/var/vm")
+  }
+
+  pub fn mount_matches(path: &Path, mount: &Path) -> bool {
+    path == mount
+  }
+
+  pub fn load_average(_: &System) -> sysinfo::LoadAvg {
+    System::load_average()
+  }
+
+  pub fn interface_drops(_: &str) -> (Option<i64>, Option<i64>) {
+    (None, None)
+  }
+
+  pub fn cpu_times() -> Option<(u64, u64)> {
+    None
+  }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+  use super::*;
+
+  /// Skips removable drives and empty drives (ie. a CD drive with no disk).
+  pub fn ignore_disk(disk: &sysinfo::Disk) -> bool {
+    disk.is_removable() || disk.total_space() == 0
+  }
+
+  /// Drive letters are case insensitive, and may be given
+  /// with or without the trailing separator, ie. `C:` or `c:\`.
+  pub fn mount_matches(path: &Path, mount: &Path) -> bool {
+    let path = path.to_string_lossy();
+    let mount = mount.to_string_lossy();
+    path
+      .trim_end_matches('\\')
+      .eq_ignore_ascii_case(mount.trim_end_matches('\\'))
+  }
+
+  /// Windows has no load average. Report the cpu usage
+  /// scaled to the core count for all three, which
+  /// approximates the number of busy cores.
+  pub fn load_average(system: &System) -> sysinfo::LoadAvg {
+    let load = system.global_cpu_usage() as f64 / 100.0
+      * system.cpus().len() as f64;
+    sysinfo::LoadAvg {
+      one: load,
+      five: load,
+      fifteen: load,
+    }
+  }
+
+  pub fn interface_drops(_: &str) -> (Option<i64>, Option<i64>) {
+    (None, None)
+  }
+
+  pub fn cpu_times() -> Option<(u64, u64)> {
+    None
+  }
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "macos",
+  target_os = "windows"
+)))]
+mod platform {
+  use super::*;
+
+  pub fn ignore_disk(_: &sysinfo::Disk) -> bool {
+    false
+  }
+
+  pub fn mount_matches(path: &Path, mount: &Path) -> bool {
+    path == mount
+  }
+
+  pub fn load_average(_: &System) -> sysinfo::LoadAvg {
+    System::load_average()
+  }
+
+  pub fn interface_drops(_: &str) -> (Option<i64>, Option<i64>) {
+    (None, None)
+  }
+
+  pub fn cpu_times() -> Option<(u64, u64)> {
+    None
+  }
+}
@@ -0,0 +1,145 @@
+//! Collects the GPU usage. NVIDIA GPUs are read through NVML
+//! using `nvidia-smi`, and AMD GPUs through the amdgpu sysfs files.
+//! Servers without either report no GPUs.
+
+use std::{
+  path::Path,
+  sync::atomic::{AtomicBool, Ordering},
+};
+
+use komodo_client::entities::stats::{GpuVendor, SingleGpuUsage};
+use run_command::async_run_command;
+
+use super::{BYTES_PER_GB, BYTES_PER_MB};
+
+const AMD_VENDOR_ID: &str = "0x1002";
+
+/// Cleared the first time `nvidia-smi` fails,
+/// so servers without it don't spawn it on every poll.
+static NVIDIA_SMI_AVAILABLE: AtomicBool = AtomicBool::new(true);
+
+pub async fn get_gpus() -> Vec<SingleGpuUsage> {
+  let mut gpus = nvidia_gpus().await;
+  gpus.extend(amd_gpus().await);
+  gpus
+}
+
+async fn nvidia_gpus() -> Vec<SingleGpuUsage> {
+  if !NVIDIA_SMI_AVAILABLE.load(Ordering::Relaxed) {
+    return Vec::new();
+  }
+  let output = async_run_command(
+    "nvidia-smi --query-gpu=index,name,utilization.gpu,memory.used,memory.total,temperature.gpu --format=csv,noheader,nounits",
+  )
+  .await;
+  if !output.success() {
+    debug!(
+      "nvidia-smi unavailable, not reporting NVIDIA GPUs | {}",
+      output.stderr.trim()
+    );
+    NVIDIA_SMI_AVAILABLE.store(false, Ordering::Relaxed);
+    return Vec::new();
+  }
+  output.stdout.lines().filter_map(parse_nvidia_gpu).collect()
+}
+
+/// Parses a line like `0, NVIDIA A100, 35, 1024, 40960, 41`,
+/// with memory in MiB. Unsupported values are reported as `[N/A]`.
+fn parse_nvidia_gpu(line: &str) -> Option<SingleGpuUsage> {
+  let mut fields = line.split(',').map(str::trim);
+  let index = fields.next()?.parse().ok()?;
+  let name = fields.next()?.to_string();
+  let mut value =
+    || fields.next().and_then(|v| v.parse::<f64>().ok());
+  let utilization_perc = value().unwrap_or_default();
+  let mem_used_mb = value().unwrap_or_default();
+  let mem_total_mb = value().unwrap_or_default();
+  let temperature = value();
+  Some(SingleGpuUsage {
+    index,
+    vendor: GpuVendor::Nvidia,
+    name,
+    utilization_perc,
+    mem_used_gb: mem_used_mb * BYTES_PER_MB / BYTES_PER_GB,
+    mem_total_gb: mem_total_mb * BYTES_PER_MB / BYTES_PER_GB,
+    temperature,
+  })
+}
+
+async fn amd_gpus() -> Vec<SingleGpuUsage> {
+  let Ok(mut cards) = tokio::fs::read_dir("/sys/class/drm").await
+  else {
+    return Vec::new();
+  };
+  let mut gpus = Vec::new();
+  while let Ok(Some(card)) = cards.next_entry().await {
+    let file_name = card.file_name();
+    // Skip the connectors, ie `card0-DP-1`
+    let Some(index) = file_name
+      .to_str()
+      .and_then(|name| name.strip_prefix("card"))
+      .and_then(|index| index.parse().ok())
+    else {
+      continue;
+    };
+    let device = card.path().join("device");
+    if read_trimmed(&device.join("vendor")).await.as_deref()
+      != Some(AMD_VENDOR_ID)
+    {
+      continue;
+    }
+    // Only amdgpu exposes the usage files.
+    let Some(utilization_perc) =
+      read_number(&device.join("gpu_busy_percent")).await
+    else {
+      continue;
+    };
+    let name = read_trimmed(&device.join("product_name"))
+      .await
+      .filter(|name| !name.is_empty())
+      .unwrap_or_else(|| String::from("AMD GPU"));
+    let mem_used = read_number(&device.join("mem_info_vram_used"))
+      .await
+      .unwrap_or_default();
+    let mem_total = read_number(&device.join("mem_info_vram_total"))
+      .await
+      .unwrap_or_default();
+    gpus.push(SingleGpuUsage {
+      index,
+      vendor: GpuVendor::Amd,
+      name,
+      utilization_perc,
+      mem_used_gb: mem_used / BYTES_PER_GB,
+      mem_total_gb: mem_total / BYTES_PER_GB,
+      temperature: amd_temperature(&device).await,
+    });
+  }
+  gpus.sort_by_key(|gpu| gpu.index);
+  gpus
+}
+
+/// The edge temperature from the hwmon of the device,
+/// reported in millidegrees.
+async fn amd_temperature(device: &Path) -> Option<f64> {
+  let mut hwmons =
+    tokio::fs::read_dir(device.join("hwmon")).await.ok()?;
+  while let Ok(Some(hwmon)) = hwmons.next_entry().await {
+    if let Some(temp) =
+      read_number(&hwmon.path().join("temp1_input")).await
+    {
+      return Some(temp / 1000.0);
+    }
+  }
+  None
+}
+
+async fn read_trimmed(path: &Path) -> Option<String> {
+  tokio::fs::read_to_string(path)
+    .await
+    .ok()
+    .map(|contents| contents.trim().to_string())
+}
+
+async fn read_number(path: &Path) -> Option<f64> {
+  read_trimmed(path).await?.parse().ok()
+}
@@ -0,0 +1,224 @@
+//! Lists the pending OS package updates using the detected
+//! package manager (apt, dnf or apk). Querying the package
+//! manager is slow, so the result is cached for
+//! `os_updates_refresh_interval`.
+
+use std::{collections::HashSet, sync::OnceLock};
+
+use async_timing_util::{Timelength, get_timelength_in_ms};
+use komodo_client::entities::{
+  komodo_timestamp,
+  stats::{OsPackageUpdate, PendingOsUpdates},
+};
+use run_command::async_run_command;
+use tokio::sync::Mutex;
+
+use crate::config::periphery_config;
+
+fn os_updates_cache() -> &'static Mutex<Option<PendingOsUpdates>> {
+  static CACHE: OnceLock<Mutex<Option<PendingOsUpdates>>> =
+    OnceLock::new();
+  CACHE.get_or_init(Default::default)
+}
+
+pub async fn get_pending_os_updates() -> PendingOsUpdates {
+  // Held through the refresh, so simultaneous calls
+  // wait for the first to finish and use its result.
+  let mut cached = os_updates_cache().lock().await;
+  let refresh_interval = get_timelength_in_ms(
+    periphery_config()
+      .os_updates_refresh_interval
+      .try_into()
+      .unwrap_or(Timelength::OneHour),
+  ) as i64;
+  if let Some(updates) = cached.as_ref()
+    && updates.checked_ts + refresh_interval > komodo_timestamp()
+  {
+    return updates.clone();
+  }
+  let updates = check_pending_os_updates().await;
+  *cached = Some(updates.clone());
+  updates
+}
+
+async fn check_pending_os_updates() -> PendingOsUpdates {
+  let Some(package_manager) = detect_package_manager().await else {
+    return PendingOsUpdates {
+      checked_ts: komodo_timestamp(),
+      error: Some(String::from(
+        "No supported package manager found (apt, dnf, apk)",
+      )),
+      ..Default::default()
+    };
+  };
+  let res = match package_manager {
+    "apt" => apt_updates().await,
+    "dnf" => dnf_updates().await,
+    _ => apk_updates().await,
+  };
+  let (updates, error) = match res {
+    Ok(updates) => (updates, None),
+    Err(e) => (Vec::new(), Some(e)),
+  };
+  PendingOsUpdates {
+    package_manager: package_manager.to_string(),
+    updates,
+    checked_ts: komodo_timestamp(),
+    error,
+  }
+}
+
+async fn detect_package_manager() -> Option<&'static str> {
+  for package_manager in ["apt", "dnf", "apk"] {
+    if async_run_command(&format!("command -v {package_manager}"))
+      .await
+      .success()
+    {
+      return Some(package_manager);
+    }
+  }
+  None
+}
+
+/// Parses lines of `apt list --upgradable` like
+/// `openssl/jammy-updates,jammy-security 3.0.2-0ubuntu1.15 amd64 [upgradable from: 3.0.2-0ubuntu1.14]`.
+/// Updates from a `-security` suite are flagged as security updates.
+async fn apt_updates() -> Result<Vec<OsPackageUpdate>, String> {
+  let output =
+    async_run_command("apt list --upgradable 2>/dev/null").await;
+  if !output.success() {
+    return Err(format!(
+      "Failed to list apt updates | {}",
+      output.stderr.trim()
+    ));
+  }
+  let updates = output
+    .stdout
+    .lines()
+    .filter_map(|line| {
+      let mut fields = line.split_whitespace();
+      let (name, suites) = fields.next()?.split_once('/')?;
+      let new_version = fields.next()?.to_string();
+      let current_version =
+        line.split_once("[upgradable from: ").map(|(_, current)| {
+          current.trim_end_matches(']').to_string()
+        });
+      Some(OsPackageUpdate {
+        name: name.to_string(),
+        current_version,
+        new_version,
+        security: suites
+          .split(',')
+          .any(|suite| suite.ends_with("-security")),
+      })
+    })
+    .collect();
+  Ok(updates)
+}
+
+/// Parses lines of `dnf check-update` like
+/// `openssl.x86_64  1:3.0.9-2.fc38  updates`, flagging the packages
+/// with a security advisory in `dnf updateinfo list --security`.
+/// dnf doesn't report the installed version.
+async fn dnf_updates() -> Result<Vec<OsPackageUpdate>, String> {
+  // Exits with 100 when updates are available.
+  let output =
+    async_run_command("dnf -q check-update || [ $? -eq 100 ]").await;
+  if !output.success() {
+    return Err(format!(
+      "Failed to check dnf updates | {}",
+      output.stderr.trim()
+    ));
+  }
+  if output.stdout.trim().is_empty() {
+    return Ok(Vec::new());
+  }
+  let security = dnf_security_packages().await;
+  let updates = output
+    .stdout
+    .lines()
+    .skip_while(|line| line.trim().is_empty())
+    // The obsoleted packages are listed after an empty line
+    .take_while(|line| !line.trim().is_empty())
+    .filter_map(|line| {
+      let mut fields = line.split_whitespace();
+      let (name, _arch) = fields.next()?.rsplit_once('.')?;
+      let new_version = fields.next()?.to_string();
+      fields.next()?;
+      Some(OsPackageUpdate {
+        security: security.contains(name),
+        name: name.to_string(),
+        current_version: None,
+        new_version,
+      })
+    })
+    .collect();
+  Ok(updates)
+}
+
+/// The names of the packages with a pending security advisory,
+/// from lines like `FEDORA-2024-1234  Moderate/Sec.  openssl-1:3.0.9-2.fc38.x86_64`.
+async fn dnf_security_packages() -> HashSet<String> {
+  let output =
+    async_run_command("dnf -q updateinfo list --security --updates")
+      .await;
+  if !output.success() {
+    warn!(
+      "Failed to list dnf security advisories | {}",
+      output.stderr.trim()
+    );
+    return HashSet::new();
+  }
+  output
+    .stdout
+    .lines()
+    .filter_map(|line| {
+      let nevra = line.split_whitespace().nth(2)?;
+      // Strip the `-[epoch:]version-release.arch`
+      let (name_version, _release) = nevra.rsplit_once('-')?;
+      let (name, _version) = name_version.rsplit_once('-')?;
+      Some(name.to_string())
+    })
+    .collect()
+}
+
+/// Parses lines of `apk list -u` like
+/// `musl-1.2.4-r3 x86_64 {musl} (MIT) [upgradable from: musl-1.2.4-r2]`.
+/// apk doesn't report which updates are security fixes.
+async fn apk_updates() -> Result<Vec<OsPackageUpdate>, String> {
+  let output = async_run_command("apk list -u").await;
+  if !output.success() {
+    return Err(format!(
+      "Failed to list apk updates | {}",
+      output.stderr.trim()
+    ));
+  }
+  let updates = output
+    .stdout
+    .lines()
+    .filter_map(|line| {
+      let (name, new_version) =
+        split_apk_package(line.split_whitespace().next()?)?;
+      let current_version = line
+        .split_once("[upgradable from: ")
+        .and_then(|(_, current)| {
+          split_apk_package(current.trim_end_matches(']'))
+        })
+        .map(|(_, version)| version.to_string());
+      Some(OsPackageUpdate {
+        name: name.to_string(),
+        current_version,
+        new_version: new_version.to_string(),
+        security: false,
+      })
+    })
+    .collect();
+  Ok(updates)
+}
+
+/// Splits `musl-1.2.4-r3` into `musl` and `1.2.4-r3`.
+fn split_apk_package(package: &str) -> Option<(&str, &str)> {
+  let (name_version, _release) = package.rsplit_once('-')?;
+  let (name, _version) = name_version.rsplit_once('-')?;
+  Some((name, &package[name.len() + 1..]))
+}
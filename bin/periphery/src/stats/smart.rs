@@ -0,0 +1,177 @@
+//! Reads the SMART health of the physical disks using `smartctl --json`.
+
+use anyhow::{Context, anyhow};
+use komodo_client::entities::stats::DiskHealth;
+use run_command::async_run_command;
+use serde::Deserialize;
+use shell_escape::unix::escape;
+
+/// Bits 0 and 1 of the smartctl exit status mean the command
+/// or the device open failed. The other bits report disk problems.
+const SMARTCTL_FAILED_MASK: i64 = 0b11;
+
+pub async fn get_disk_health() -> anyhow::Result<Vec<DiskHealth>> {
+  let output = async_run_command("smartctl --scan --json").await;
+  if !output.success() {
+    return Err(anyhow!("{}", output.stderr.trim())).context(
+      "Failed to scan disks with smartctl. Is it installed?",
+    );
+  }
+  let scan = serde_json::from_str::<SmartScan>(&output.stdout)
+    .context("Failed to parse smartctl scan")?;
+  let mut disks = Vec::with_capacity(scan.devices.len());
+  for device in scan.devices {
+    disks.push(read_disk_health(device).await);
+  }
+  Ok(disks)
+}
+
+async fn read_disk_health(device: ScanDevice) -> DiskHealth {
+  let command = format!(
+    "smartctl --json -a -d {} {}",
+    escape(device.r#type.as_str().into()),
+    escape(device.name.as_str().into())
+  );
+  // smartctl exits non zero when the disk reports problems,
+  // so the exit status is read from the output instead.
+  let output = async_run_command(&command).await;
+  let info = match serde_json::from_str::<SmartInfo>(&output.stdout) {
+    Ok(info) => info,
+    Err(e) => {
+      return DiskHealth {
+        device: device.name,
+        error: Some(format!(
+          "Failed to parse smartctl output | {e} | {}",
+          output.stderr.trim()
+        )),
+        ..Default::default()
+      };
+    }
+  };
+  if info.smartctl.exit_status & SMARTCTL_FAILED_MASK != 0 {
+    let error = info
+      .smartctl
+      .messages
+      .into_iter()
+      .map(|message| message.string)
+      .collect::<Vec<_>>()
+      .join(" | ");
+    return DiskHealth {
+      device: device.name,
+      model: info.model_name,
+      serial: info.serial_number,
+      error: Some(error),
+      ..Default::default()
+    };
+  }
+  let ata_attribute = |id: i64| {
+    info
+      .ata_smart_attributes
+      .as_ref()?
+      .table
+      .iter()
+      .find(|attribute| attribute.id == id)
+  };
+  let nvme = info.nvme_smart_health_information_log.as_ref();
+  // Wear leveling counts (177, 233) are normalized down
+  // from 100, so the used portion is the remainder.
+  let wear_perc =
+    nvme.and_then(|log| log.percentage_used).or_else(|| {
+      ata_attribute(177)
+        .or_else(|| ata_attribute(233))
+        .map(|attribute| (100 - attribute.value).max(0) as f64)
+    });
+  DiskHealth {
+    device: device.name,
+    model: info.model_name,
+    serial: info.serial_number,
+    passed: info.smart_status.map(|s| s.passed).unwrap_or(true),
+    temperature: info.temperature.and_then(|t| t.current),
+    reallocated_sectors: ata_attribute(5)
+      .map(|attribute| attribute.raw.value),
+    pending_sectors: ata_attribute(197)
+      .map(|attribute| attribute.raw.value),
+    media_errors: nvme.and_then(|log| log.media_errors),
+    wear_perc,
+    power_on_hours: info.power_on_time.and_then(|t| t.hours),
+    error: None,
+  }
+}
+
+#[derive(Deserialize)]
+struct SmartScan {
+  #[serde(default)]
+  devices: Vec<ScanDevice>,
+}
+
+#[derive(Deserialize)]
+struct ScanDevice {
+  name: String,
+  r#type: String,
+}
+
+#[derive(Deserialize)]
+struct SmartInfo {
+  smartctl: SmartctlInfo,
+  #[serde(default)]
+  model_name: String,
+  #[serde(default)]
+  serial_number: String,
+  smart_status: Option<SmartStatus>,
+  temperature: Option<SmartTemperature>,
+  power_on_time: Option<SmartPowerOnTime>,
+  ata_smart_attributes: Option<AtaSmartAttributes>,
+  nvme_smart_health_information_log: Option<NvmeHealthLog>,
+}
+
+#[derive(Deserialize)]
+struct SmartctlInfo {
+  #[serde(default)]
+  exit_status: i64,
+  #[serde(default)]
+  messages: Vec<SmartctlMessage>,
+}
+
+#[derive(Deserialize)]
+struct SmartctlMessage {
+  string: String,
+}
+
+#[derive(Deserialize)]
+struct SmartStatus {
+  passed: bool,
+}
+
+#[derive(Deserialize)]
+struct SmartTemperature {
+  current: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct SmartPowerOnTime {
+  hours: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct AtaSmartAttributes {
+  #[serde(default)]
+  table: Vec<AtaSmartAttribute>,
+}
+
+#[derive(Deserialize)]
+struct AtaSmartAttribute {
+  id: i64,
+  value: i64,
+  raw: AtaSmartRawValue,
+}
+
+#[derive(Deserialize)]
+struct AtaSmartRawValue {
+  value: i64,
+}
+
+#[derive(Deserialize)]
+struct NvmeHealthLog {
+  percentage_used: Option<f64>,
+  media_errors: Option<i64>,
+}
@@ -0,0 +1,245 @@
+//! Lists the listening TCP / UDP sockets from `/proc/net`,
+//! finding the owning process through the `/proc/<pid>/fd` socket links,
+//! and the owning container through the published ports
+//! or the process cgroup.
+
+use std::{
+  collections::HashMap,
+  net::{Ipv4Addr, Ipv6Addr},
+};
+
+use anyhow::Context;
+use komodo_client::entities::{
+  docker::container::{ContainerListItem, PortTypeEnum},
+  stats::ListeningSocket,
+};
+
+use crate::state::docker_client;
+
+/// The `st` of listening TCP sockets.
+const TCP_LISTEN: &str = "0A";
+/// The `st` of bound UDP sockets without a peer.
+const UDP_UNCONNECTED: &str = "07";
+
+/// A socket read from `/proc/net`, before finding its owner.
+struct ProcSocket {
+  protocol: PortTypeEnum,
+  address: String,
+  port: u16,
+  inode: u64,
+}
+
+pub async fn get_listening_sockets()
+-> anyhow::Result<Vec<ListeningSocket>> {
+  let client = docker_client().load();
+  let containers = match client.iter().next() {
+    Some(client) => {
+      client.list_containers().await.unwrap_or_default()
+    }
+    None => Vec::new(),
+  };
+  tokio::task::spawn_blocking(move || {
+    read_listening_sockets(&containers)
+  })
+  .await
+  .context("Failed to join socket listing task")?
+}
+
+fn read_listening_sockets(
+  containers: &[ContainerListItem],
+) -> anyhow::Result<Vec<ListeningSocket>> {
+  let mut sockets = Vec::new();
+  for (file, protocol, state) in [
+    ("tcp", PortTypeEnum::TCP, TCP_LISTEN),
+    ("tcp6", PortTypeEnum::TCP, TCP_LISTEN),
+    ("udp", PortTypeEnum::UDP, UDP_UNCONNECTED),
+    ("udp6", PortTypeEnum::UDP, UDP_UNCONNECTED),
+  ] {
+    // tcp6 / udp6 are missing when IPv6 is disabled.
+    let Ok(contents) =
+      std::fs::read_to_string(format!("/proc/net/{file}"))
+    else {
+      continue;
+    };
+    sockets.extend(
+      contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| parse_proc_socket(line, protocol, state)),
+    );
+  }
+  if sockets.is_empty() {
+    // Every host has some listener, so this means /proc isn't readable.
+    std::fs::metadata("/proc/net/tcp")
+      .context("Failed to read /proc/net/tcp")?;
+  }
+
+  let owners = socket_owners();
+  let container_names = containers
+    .iter()
+    .filter_map(|container| {
+      Some((container.id.as_deref()?, container.name.as_str()))
+    })
+    .collect::<HashMap<_, _>>();
+  let mut process_containers = HashMap::<u32, Option<String>>::new();
+
+  let mut listening = sockets
+    .into_iter()
+    .map(|socket| {
+      let owner = owners.get(&socket.inode);
+      let pid = owner.map(|(pid, _)| *pid);
+      let container = published_by(containers, &socket)
+        .map(str::to_string)
+        .or_else(|| {
+          let pid = pid?;
+          process_containers
+            .entry(pid)
+            .or_insert_with(|| {
+              process_container_id(pid)
+                .and_then(|id| container_names.get(id.as_str()))
+                .map(|name| name.to_string())
+            })
+            .clone()
+        });
+      ListeningSocket {
+        protocol: socket.protocol,
+        address: socket.address,
+        port: socket.port,
+        pid,
+        process: owner.map(|(_, name)| name.clone()),
+        container,
+      }
+    })
+    .collect::<Vec<_>>();
+  listening.sort_by(|a, b| {
+    a.port
+      .cmp(&b.port)
+      .then(a.protocol.cmp(&b.protocol))
+      .then(a.address.cmp(&b.address))
+  });
+  Ok(listening)
+}
+
+/// Parses a line of `/proc/net/{tcp,udp}[6]` like
+/// `0: 00000000:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000 0 0 23456 ...`,
+/// keeping only the sockets in the given state.
+fn parse_proc_socket(
+  line: &str,
+  protocol: PortTypeEnum,
+  state: &str,
+) -> Option<ProcSocket> {
+  let fields = line.split_whitespace().collect::<Vec<_>>();
+  if fields.get(3)? != &state {
+    return None;
+  }
+  // UDP sockets with a peer are outgoing, not listening.
+  if protocol == PortTypeEnum::UDP
+    && !fields.get(2)?.split_once(':')?.0.chars().all(|c| c == '0')
+  {
+    return None;
+  }
+  let (address, port) = fields.get(1)?.split_once(':')?;
+  Some(ProcSocket {
+    protocol,
+    address: parse_proc_address(address)?,
+    port: u16::from_str_radix(port, 16).ok()?,
+    inode: fields.get(9)?.parse().ok()?,
+  })
+}
+
+/// The addresses are written as 32 bit words in host byte order.
+fn parse_proc_address(hex: &str) -> Option<String> {
+  let mut bytes = Vec::with_capacity(16);
+  for i in (0..hex.len()).step_by(8) {
+    let word = u32::from_str_radix(hex.get(i..i + 8)?, 16).ok()?;
+    bytes.extend(word.to_ne_bytes());
+  }
+  match bytes.len() {
+    4 => {
+      let bytes: [u8; 4] = bytes.try_into().ok()?;
+      Some(Ipv4Addr::from(bytes).to_string())
+    }
+    16 => {
+      let bytes: [u8; 16] = bytes.try_into().ok()?;
+      Some(Ipv6Addr::from(bytes).to_string())
+    }
+    _ => None,
+  }
+}
+
+/// Socket inode -> The pid and name of a process holding it,
+/// from the `socket:[<inode>]` links in `/proc/<pid>/fd`.
+/// Processes Periphery isn't allowed to inspect are skipped.
+fn socket_owners() -> HashMap<u64, (u32, String)> {
+  let mut owners = HashMap::new();
+  let Ok(processes) = std::fs::read_dir("/proc") else {
+    return owners;
+  };
+  for process in processes.flatten() {
+    let Some(pid) = process
+      .file_name()
+      .to_str()
+      .and_then(|pid| pid.parse::<u32>().ok())
+    else {
+      continue;
+    };
+    let Ok(fds) = std::fs::read_dir(process.path().join("fd")) else {
+      continue;
+    };
+    let mut name = None;
+    for fd in fds.flatten() {
+      let Some(inode) =
+        std::fs::read_link(fd.path()).ok().and_then(|link| {
+          link
+            .to_str()?
+            .strip_prefix("socket:[")?
+            .strip_suffix(']')?
+            .parse::<u64>()
+            .ok()
+        })
+      else {
+        continue;
+      };
+      let name = name
+        .get_or_insert_with(|| {
+          std::fs::read_to_string(process.path().join("comm"))
+            .map(|comm| comm.trim().to_string())
+            .unwrap_or_default()
+        })
+        .clone();
+      owners.entry(inode).or_insert((pid, name));
+    }
+  }
+  owners
+}
+
+/// The container publishing the socket port on the host,
+/// ie through `docker-proxy`.
+fn published_by<'a>(
+  containers: &'a [ContainerListItem],
+  socket: &ProcSocket,
+) -> Option<&'a str> {
+  containers
+    .iter()
+    .find(|container| {
+      container.ports.iter().any(|port| {
+        port.public_port == Some(socket.port)
+          && port.typ == socket.protocol
+      })
+    })
+    .map(|container| container.name.as_str())
+}
+
+/// Reads the container id from the process cgroup, like
+/// `0::/system.slice/docker-<id>.scope` or `0::/docker/<id>`.
+/// Finds the processes of host network containers.
+fn process_container_id(pid: u32) -> Option<String> {
+  let cgroup =
+    std::fs::read_to_string(format!("/proc/{pid}/cgroup")).ok()?;
+  cgroup
+    .split(['/', '-', '.', '\n'])
+    .find(|part| {
+      part.len() == 64 && part.chars().all(|c| c.is_ascii_hexdigit())
+    })
+    .map(str::to_string)
+}
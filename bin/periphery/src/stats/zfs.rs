@@ -0,0 +1,170 @@
+//! Collects the status of the ZFS pools and datasets using
+//! `zpool list`, `zpool status -j` and `zfs list`.
+//! Servers without ZFS report no pools.
+
+use std::{
+  collections::HashMap,
+  sync::atomic::{AtomicBool, Ordering},
+};
+
+use komodo_client::entities::stats::{ZfsDataset, ZfsPool};
+use run_command::async_run_command;
+use serde::Deserialize;
+
+use super::BYTES_PER_GB;
+
+/// Cleared the first time `zpool` fails,
+/// so servers without ZFS don't spawn it on every poll.
+static ZPOOL_AVAILABLE: AtomicBool = AtomicBool::new(true);
+
+pub async fn get_zfs_pools() -> Vec<ZfsPool> {
+  if !ZPOOL_AVAILABLE.load(Ordering::Relaxed) {
+    return Vec::new();
+  }
+  let output = async_run_command(
+    "zpool list -Hp -o name,size,alloc,free,frag,health",
+  )
+  .await;
+  if !output.success() {
+    debug!(
+      "zpool unavailable, not reporting ZFS pools | {}",
+      output.stderr.trim()
+    );
+    ZPOOL_AVAILABLE.store(false, Ordering::Relaxed);
+    return Vec::new();
+  }
+  let mut pools = output
+    .stdout
+    .lines()
+    .filter_map(parse_pool)
+    .collect::<Vec<_>>();
+  if pools.is_empty() {
+    return pools;
+  }
+  let (scans, datasets) = tokio::join!(pool_scans(), datasets());
+  for pool in &mut pools {
+    pool.scan = scans.get(&pool.name).cloned();
+    pool.datasets = datasets
+      .iter()
+      .filter(|dataset| {
+        dataset.name == pool.name
+          || dataset
+            .name
+            .strip_prefix(&pool.name)
+            .is_some_and(|rest| rest.starts_with('/'))
+      })
+      .cloned()
+      .collect();
+  }
+  pools
+}
+
+/// Parses a tab separated line of `zpool list -Hp`.
+/// Fragmentation is `-` when not supported.
+fn parse_pool(line: &str) -> Option<ZfsPool> {
+  let mut fields = line.split('\t');
+  let name = fields.next()?.to_string();
+  let mut gb = || {
+    fields
+      .next()?
+      .parse::<f64>()
+      .ok()
+      .map(|bytes| bytes / BYTES_PER_GB)
+  };
+  let size_gb = gb()?;
+  let allocated_gb = gb()?;
+  let free_gb = gb()?;
+  let fragmentation_perc =
+    fields.next()?.trim_end_matches('%').parse::<f64>().ok();
+  let health = fields.next()?.to_string();
+  Some(ZfsPool {
+    name,
+    health,
+    size_gb,
+    allocated_gb,
+    free_gb,
+    fragmentation_perc,
+    scan: None,
+    datasets: Vec::new(),
+  })
+}
+
+async fn datasets() -> Vec<ZfsDataset> {
+  let output =
+    async_run_command("zfs list -Hp -o name,used,avail,mountpoint")
+      .await;
+  if !output.success() {
+    warn!("Failed to list ZFS datasets | {}", output.stderr.trim());
+    return Vec::new();
+  }
+  output
+    .stdout
+    .lines()
+    .filter_map(|line| {
+      let mut fields = line.split('\t');
+      let name = fields.next()?.to_string();
+      let used_gb =
+        fields.next()?.parse::<f64>().ok()? / BYTES_PER_GB;
+      let available_gb =
+        fields.next()?.parse::<f64>().ok()? / BYTES_PER_GB;
+      let mountpoint = fields
+        .next()
+        .filter(|mount| !matches!(*mount, "-" | "none" | "legacy"))
+        .map(str::to_string);
+      Some(ZfsDataset {
+        name,
+        used_gb,
+        available_gb,
+        mountpoint,
+      })
+    })
+    .collect()
+}
+
+#[derive(Deserialize)]
+struct ZpoolStatus {
+  #[serde(default)]
+  pools: HashMap<String, ZpoolStatusPool>,
+}
+
+#[derive(Deserialize)]
+struct ZpoolStatusPool {
+  scan_stats: Option<ZpoolScanStats>,
+}
+
+#[derive(Deserialize)]
+struct ZpoolScanStats {
+  function: String,
+  state: String,
+  errors: Option<String>,
+}
+
+/// Pool name -> The status of the last or running scrub / resilver.
+/// `zpool status -j` requires OpenZFS 2.3+, older versions
+/// report no scan status.
+async fn pool_scans() -> HashMap<String, String> {
+  let output = async_run_command("zpool status -j").await;
+  if !output.success() {
+    return HashMap::new();
+  }
+  let Ok(status) =
+    serde_json::from_str::<ZpoolStatus>(&output.stdout)
+  else {
+    return HashMap::new();
+  };
+  status
+    .pools
+    .into_iter()
+    .filter_map(|(name, pool)| {
+      let scan = pool.scan_stats?;
+      let errors = scan
+        .errors
+        .map(|errors| format!(" ({errors} errors)"))
+        .unwrap_or_default();
+      Some((
+        name,
+        format!("{} {}{errors}", scan.function, scan.state),
+      ))
+    })
+    .collect()
+}
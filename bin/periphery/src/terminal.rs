@@ -1,23 +1,40 @@
-use std::{collections::VecDeque, sync::Arc, time::Duration};
+use std::{
+  collections::VecDeque,
+  sync::{
+    Arc,
+    atomic::{AtomicI64, AtomicUsize, Ordering},
+  },
+  time::Duration,
+};
 
 use anyhow::{Context, anyhow};
 use bytes::Bytes;
 use encoding::{Decode as _, WithChannel};
 use komodo_client::{
   api::write::TerminalRecreateMode,
-  entities::{ContainerTerminalMode, server::TerminalInfo},
+  entities::{
+    ContainerTerminalMode, KOMODO_EXIT_CODE, komodo_timestamp,
+    server::TerminalInfo,
+  },
 };
 use periphery_client::transport::EncodedTerminalMessage;
 use portable_pty::{CommandBuilder, PtySize, native_pty_system};
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, watch};
 use tokio_util::sync::CancellationToken;
 
-use crate::state::{terminal_channels, terminal_triggers, terminals};
+use crate::{
+  config::periphery_config,
+  state::{terminal_channels, terminal_triggers, terminals},
+};
+
+use self::recording::Recorder;
+
+pub mod recording;
 
 pub async fn handle_message(message: EncodedTerminalMessage) {
   let WithChannel {
     channel: channel_id,
-    mut data,
+    data,
   } = match message.decode() {
     Ok(res) => res,
     Err(e) => {
@@ -26,7 +43,7 @@ pub async fn handle_message(message: EncodedTerminalMessage) {
     }
   };
   let msg = match data.first() {
-    Some(&0x00) => StdinMsg::Bytes(data.drain(1..).collect()),
+    Some(&0x00) => StdinMsg::Bytes(data[1..].to_vec()),
     Some(&0xFF) => {
       if let Ok(dimensions) =
         serde_json::from_slice::<ResizeDimensions>(&data[1..])
@@ -36,7 +53,7 @@ pub async fn handle_message(message: EncodedTerminalMessage) {
         return;
       }
     }
-    Some(_) => StdinMsg::Bytes(data),
+    Some(_) => StdinMsg::Bytes(data.into()),
     // Empty bytes are the "begin" trigger for Terminal Executions
     None => {
       if let Err(e) = terminal_triggers().send(&channel_id).await {
@@ -50,6 +67,10 @@ pub async fn handle_message(message: EncodedTerminalMessage) {
     warn!("No terminal channel for {channel_id}");
     return;
   };
+  if channel.read_only {
+    trace!("Dropping input on read only channel {channel_id}");
+    return;
+  }
   if let Err(e) = channel.sender.send(msg).await {
     warn!("No receiver for {channel_id} | {e:?}");
   };
@@ -61,9 +82,11 @@ pub async fn create_terminal(
   command: String,
   recreate: TerminalRecreateMode,
   container: Option<(String, ContainerTerminalMode)>,
+  record: Option<bool>,
+  history_kb: Option<u64>,
 ) -> anyhow::Result<Arc<Terminal>> {
   trace!(
-    "CreateTerminal: {name} | command: {command} | recreate: {recreate:?}"
+    "CreateTerminal: {name} | command: {command} | recreate: {recreate:?} | record: {record:?} | history kb: {history_kb:?}"
   );
   let mut terminals = terminals().write().await;
   use TerminalRecreateMode::*;
@@ -80,9 +103,17 @@ pub async fn create_terminal(
     }
   }
   let terminal = Arc::new(
-    Terminal::new(command, container)
-      .await
-      .context("Failed to init terminal")?,
+    Terminal::new(
+      &name,
+      command,
+      container,
+      // The server config can't be opted out of.
+      periphery_config().record_terminals
+        || record.unwrap_or_default(),
+      history_kb.unwrap_or(periphery_config().terminal_history_kb),
+    )
+    .await
+    .context("Failed to init terminal")?,
   );
   if let Some(prev) = terminals.insert(name, terminal.clone()) {
     prev.cancel();
@@ -120,6 +151,16 @@ pub async fn list_terminals(
       name: name.to_string(),
       command: terminal.command.clone(),
       stored_size_kb: terminal.history.size_kb(),
+      recording: terminal
+        .recorder
+        .as_ref()
+        .map(|recorder| recorder.name.clone()),
+      ttl_ms: terminal.activity.ttl_ms().map(|ttl| ttl.max(0)),
+      connections: terminal.connections.load(Ordering::Relaxed)
+        as u64,
+      read_only_connections: terminal
+        .read_only_connections
+        .load(Ordering::Relaxed) as u64,
     })
     .collect::<Vec<_>>();
   terminals.sort_by(|a, b| a.name.cmp(&b.name));
@@ -181,14 +222,27 @@ pub struct Terminal {
 
   pub history: Arc<History>,
 
+  /// If the terminal output is being recorded.
+  pub recorder: Option<Arc<Recorder>>,
+
+  pub activity: Arc<TerminalActivity>,
+
+  /// The number of connected clients, including read only.
+  connections: AtomicUsize,
+  /// The number of read only clients connected.
+  read_only_connections: AtomicUsize,
+
   /// If terminal is for a container.
   pub container: Option<(String, ContainerTerminalMode)>,
 }
 
 impl Terminal {
   async fn new(
+    name: &str,
     command: String,
     container: Option<(String, ContainerTerminalMode)>,
+    record: bool,
+    history_kb: u64,
   ) -> anyhow::Result<Terminal> {
     trace!("Creating terminal with command: {command}");
 
@@ -226,6 +280,16 @@ impl Terminal {
       ));
     }
 
+    let recorder = if record {
+      let recorder =
+        Recorder::new(name, &command, PtySize::default())
+          .await
+          .context("Failed to start terminal recording")?;
+      Some(Arc::new(recorder))
+    } else {
+      None
+    };
+
     let mut terminal_write = terminal
       .master
       .take_writer()
@@ -266,11 +330,37 @@ impl Terminal {
       }
     });
 
+    let activity = Arc::new(TerminalActivity::new());
+
+    // IDLE TIMEOUT / MAX LIFETIME TASK
+    if activity.ttl_ms().is_some() {
+      let _cancel = cancel.clone();
+      let _activity = activity.clone();
+      let name = name.to_string();
+      tokio::spawn(async move {
+        loop {
+          tokio::select! {
+            _ = _cancel.cancelled() => break,
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+          }
+          if _activity.ttl_ms().is_some_and(|ttl| ttl <= 0) {
+            info!(
+              "Closing terminal {name} | Reached idle timeout or max lifetime"
+            );
+            _cancel.cancel();
+            break;
+          }
+        }
+      });
+    }
+
     // WS (channel) -> STDIN TASK
     // Theres only one consumer here, so use mpsc
     let (stdin, mut channel_read) =
       tokio::sync::mpsc::channel::<StdinMsg>(8192);
     let _cancel = cancel.clone();
+    let _recorder = recorder.clone();
+    let _activity = activity.clone();
     tokio::task::spawn_blocking(move || {
       loop {
         if _cancel.is_cancelled() {
@@ -279,6 +369,7 @@ impl Terminal {
         }
         match channel_read.blocking_recv() {
           Some(StdinMsg::Bytes(bytes)) => {
+            _activity.touch();
             if let Err(e) = terminal_write.write_all(&bytes) {
               debug!("Failed to write to PTY: {e:?}");
               _cancel.cancel();
@@ -296,6 +387,9 @@ impl Terminal {
               _cancel.cancel();
               break;
             };
+            if let Some(recorder) = &_recorder {
+              recorder.resize(dimensions.cols, dimensions.rows);
+            }
           }
           None => {
             debug!("WS -> PTY channel read error: Disconnected");
@@ -306,7 +400,7 @@ impl Terminal {
       }
     });
 
-    let history = Arc::new(History::default());
+    let history = Arc::new(History::new(history_kb as usize * 1024));
 
     // PTY -> WS (channel) TASK
     // Uses broadcast to output to multiple client simultaneously
@@ -314,6 +408,8 @@ impl Terminal {
       tokio::sync::broadcast::channel::<Bytes>(8192);
     let _cancel = cancel.clone();
     let _history = history.clone();
+    let _recorder = recorder.clone();
+    let _activity = activity.clone();
     tokio::task::spawn_blocking(move || {
       let mut buf = [0u8; 8192];
       loop {
@@ -329,7 +425,11 @@ impl Terminal {
             break;
           }
           Ok(n) => {
+            _activity.touch();
             _history.push(&buf[..n]);
+            if let Some(recorder) = &_recorder {
+              recorder.output(&buf[..n]);
+            }
             if let Err(e) =
               write.send(Bytes::copy_from_slice(&buf[..n]))
             {
@@ -355,6 +455,10 @@ impl Terminal {
       stdin,
       stdout,
       history,
+      recorder,
+      activity,
+      connections: AtomicUsize::new(0),
+      read_only_connections: AtomicUsize::new(0),
       container,
     })
   }
@@ -363,35 +467,219 @@ impl Terminal {
     trace!("Cancel called");
     self.cancel.cancel();
   }
+
+  /// Counts a connected client until the guard is dropped.
+  pub fn connect(&self, read_only: bool) -> TerminalConnection<'_> {
+    self.connections.fetch_add(1, Ordering::Relaxed);
+    if read_only {
+      self.read_only_connections.fetch_add(1, Ordering::Relaxed);
+    }
+    TerminalConnection {
+      terminal: self,
+      read_only,
+    }
+  }
 }
 
-/// 1 MiB rolling max history size per terminal
-const MAX_BYTES: usize = 1024 * 1024;
+pub struct TerminalConnection<'a> {
+  terminal: &'a Terminal,
+  read_only: bool,
+}
 
-pub struct History {
-  buf: std::sync::RwLock<VecDeque<u8>>,
+impl Drop for TerminalConnection<'_> {
+  fn drop(&mut self) {
+    self.terminal.connections.fetch_sub(1, Ordering::Relaxed);
+    if self.read_only {
+      self
+        .terminal
+        .read_only_connections
+        .fetch_sub(1, Ordering::Relaxed);
+    }
+  }
+}
+
+/// Tracks the last stdin / stdout activity on a terminal,
+/// for the `terminal_idle_timeout_seconds` and
+/// `terminal_max_lifetime_seconds` Periphery config.
+pub struct TerminalActivity {
+  created_at: i64,
+  /// Unix timestamp in milliseconds of the last stdin / stdout.
+  last_activity: AtomicI64,
+  /// 0 means no idle timeout
+  idle_timeout_ms: i64,
+  /// 0 means no max lifetime
+  max_lifetime_ms: i64,
+}
+
+impl TerminalActivity {
+  fn new() -> TerminalActivity {
+    let config = periphery_config();
+    let now = komodo_timestamp();
+    TerminalActivity {
+      created_at: now,
+      last_activity: AtomicI64::new(now),
+      idle_timeout_ms: config.terminal_idle_timeout_seconds as i64
+        * 1000,
+      max_lifetime_ms: config.terminal_max_lifetime_seconds as i64
+        * 1000,
+    }
+  }
+
+  fn touch(&self) {
+    self
+      .last_activity
+      .store(komodo_timestamp(), Ordering::Relaxed);
+  }
+
+  /// The milliseconds until the terminal should be closed,
+  /// or None if neither limit is set.
+  pub fn ttl_ms(&self) -> Option<i64> {
+    let now = komodo_timestamp();
+    let idle = (self.idle_timeout_ms > 0).then(|| {
+      self.last_activity.load(Ordering::Relaxed)
+        + self.idle_timeout_ms
+        - now
+    });
+    let lifetime = (self.max_lifetime_ms > 0)
+      .then(|| self.created_at + self.max_lifetime_ms - now);
+    match (idle, lifetime) {
+      (Some(idle), Some(lifetime)) => Some(idle.min(lifetime)),
+      (idle, lifetime) => idle.or(lifetime),
+    }
+  }
+}
+
+/// Max bytes of execution output kept for resuming,
+/// the oldest lines are dropped past this.
+const EXECUTE_BUFFER_MAX_BYTES: usize = 4 * 1024 * 1024;
+
+/// Buffers the output lines of a terminal execution, so Core
+/// can resume forwarding after reconnecting mid execution.
+pub struct ExecuteBuffer {
+  output: std::sync::RwLock<ExecuteOutput>,
+  /// Broadcasts the line count, and whether the execution is complete.
+  progress: watch::Sender<(usize, bool)>,
+  /// Cancels the active forwarder, only one forwards at a time.
+  forwarder: std::sync::Mutex<CancellationToken>,
+}
+
+#[derive(Default)]
+struct ExecuteOutput {
+  lines: VecDeque<String>,
+  /// The number of lines dropped from the front.
+  dropped: usize,
+  bytes: usize,
+  exit_code: Option<i32>,
 }
 
-impl Default for History {
+impl Default for ExecuteBuffer {
   fn default() -> Self {
-    History {
-      buf: VecDeque::with_capacity(MAX_BYTES).into(),
+    ExecuteBuffer {
+      output: Default::default(),
+      progress: watch::Sender::new((0, false)),
+      forwarder: Default::default(),
     }
   }
 }
 
+impl ExecuteBuffer {
+  /// The exit code line is stored separately,
+  /// so it is still sent if the earlier output was dropped.
+  pub fn push(&self, line: String) {
+    let mut output = self.output.write().unwrap();
+    if let Some(code) = line.strip_prefix(KOMODO_EXIT_CODE) {
+      output.exit_code = code.trim().parse().ok();
+      return;
+    }
+    output.bytes += line.len();
+    output.lines.push_back(line);
+    while output.bytes > EXECUTE_BUFFER_MAX_BYTES
+      && output.lines.len() > 1
+      && let Some(line) = output.lines.pop_front()
+    {
+      output.bytes -= line.len();
+      output.dropped += 1;
+    }
+    let len = output.dropped + output.lines.len();
+    self.progress.send_modify(|progress| progress.0 = len);
+  }
+
+  pub fn complete(&self) {
+    self.progress.send_modify(|progress| progress.1 = true);
+  }
+
+  /// Get the lines in range `start..end`, along with the
+  /// number of lines in the range which were already dropped.
+  pub fn lines(
+    &self,
+    start: usize,
+    end: usize,
+  ) -> (usize, Vec<String>) {
+    let output = self.output.read().unwrap();
+    let end = end.min(output.dropped + output.lines.len());
+    if start >= end {
+      return (0, Vec::new());
+    }
+    let dropped =
+      output.dropped.saturating_sub(start).min(end - start);
+    let start = start.max(output.dropped) - output.dropped;
+    let end = end.saturating_sub(output.dropped);
+    let lines = output.lines.range(start..end).cloned().collect();
+    (dropped, lines)
+  }
+
+  /// The exit code of the command, once complete.
+  pub fn exit_code(&self) -> Option<i32> {
+    self.output.read().unwrap().exit_code
+  }
+
+  pub fn subscribe(&self) -> watch::Receiver<(usize, bool)> {
+    self.progress.subscribe()
+  }
+
+  /// Cancels any active forwarder, and returns
+  /// the cancel token for the new forwarder.
+  pub fn replace_forwarder(&self) -> CancellationToken {
+    let mut forwarder = self.forwarder.lock().unwrap();
+    forwarder.cancel();
+    *forwarder = CancellationToken::new();
+    forwarder.clone()
+  }
+}
+
+/// Rolling history of the terminal output,
+/// sized by `terminal_history_kb`.
+pub struct History {
+  buf: std::sync::RwLock<VecDeque<u8>>,
+  max_bytes: usize,
+}
+
 impl History {
+  fn new(max_bytes: usize) -> History {
+    History {
+      buf: VecDeque::with_capacity(max_bytes).into(),
+      max_bytes,
+    }
+  }
+
   /// Push some bytes, evicting the oldest when full.
   fn push(&self, bytes: &[u8]) {
+    if self.max_bytes == 0 {
+      return;
+    }
     let mut buf = self.buf.write().unwrap();
     for byte in bytes {
-      if buf.len() == MAX_BYTES {
+      if buf.len() == self.max_bytes {
         buf.pop_front();
       }
       buf.push_back(*byte);
     }
   }
 
+  pub fn clear(&self) {
+    self.buf.write().unwrap().clear();
+  }
+
   pub fn bytes_parts(&self) -> (Bytes, Bytes) {
     let buf = self.buf.read().unwrap();
     let (a, b) = buf.as_slices();
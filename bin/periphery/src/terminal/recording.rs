@@ -0,0 +1,258 @@
+//! Records the terminal output to asciicast v2 files
+//! in `<root_directory>/recordings`, named `<terminal>-<started_at>.cast`.
+//! See https://docs.asciinema.org/manual/asciicast/v2/.
+//! Recordings stop at [MAX_RECORDING_BYTES], and only the newest
+//! `max_terminal_recordings` are kept.
+
+use std::{path::PathBuf, sync::Mutex, time::Instant};
+
+use anyhow::{Context, anyhow};
+use komodo_client::entities::{
+  komodo_timestamp, server::TerminalRecording,
+};
+use portable_pty::PtySize;
+use serde_json::json;
+use tokio::{
+  fs::File,
+  io::AsyncWriteExt as _,
+  sync::mpsc::{self, error::TrySendError},
+};
+
+use crate::config::periphery_config;
+
+/// Recordings stop growing at this size,
+/// so they can always be fetched through Core.
+const MAX_RECORDING_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Events are dropped if writing falls this far behind,
+/// rather than blocking the terminal on the disk.
+const RECORDING_EVENT_BUFFER: usize = 1024;
+
+fn recordings_dir() -> PathBuf {
+  periphery_config().root_directory.join("recordings")
+}
+
+pub struct Recorder {
+  /// The file name of the recording
+  pub name: String,
+  start: Instant,
+  /// The start of a utf8 char split across PTY reads.
+  pending: Mutex<Vec<u8>>,
+  /// Lines for the writer task, see [write_events].
+  events: mpsc::Sender<String>,
+}
+
+impl Recorder {
+  /// Creates the recording file and writes the asciicast header.
+  /// The oldest recordings past `max_terminal_recordings` are removed.
+  pub async fn new(
+    terminal: &str,
+    command: &str,
+    size: PtySize,
+  ) -> anyhow::Result<Recorder> {
+    let dir = recordings_dir();
+    tokio::fs::create_dir_all(&dir).await.with_context(|| {
+      format!("Failed to create recordings directory {dir:?}")
+    })?;
+    let max_recordings = periphery_config().max_terminal_recordings;
+    if max_recordings > 0
+      && let Err(e) = prune_recordings(max_recordings - 1).await
+    {
+      warn!("Failed to remove old terminal recordings | {e:#}");
+    }
+    let started_at = komodo_timestamp();
+    // Container terminals are named after the container,
+    // which can't contain '/', but terminal names can.
+    let terminal_file_name = terminal
+      .chars()
+      .map(|c| {
+        if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+          c
+        } else {
+          '_'
+        }
+      })
+      .collect::<String>();
+    let name = format!("{terminal_file_name}-{started_at}.cast");
+    let path = dir.join(&name);
+    let mut file = File::create(&path)
+      .await
+      .with_context(|| format!("Failed to create {path:?}"))?;
+    let header = json!({
+      "version": 2,
+      "width": size.cols,
+      "height": size.rows,
+      "timestamp": started_at / 1000,
+      "command": command,
+      "title": terminal,
+      "env": { "TERM": "xterm-256color" },
+    });
+    let header = format!("{header}\n");
+    file
+      .write_all(header.as_bytes())
+      .await
+      .with_context(|| format!("Failed to write to {path:?}"))?;
+    let (events, receiver) = mpsc::channel(RECORDING_EVENT_BUFFER);
+    tokio::spawn(write_events(
+      name.clone(),
+      file,
+      header.len() as u64,
+      receiver,
+    ));
+    Ok(Recorder {
+      name,
+      start: Instant::now(),
+      pending: Default::default(),
+      events,
+    })
+  }
+
+  pub fn output(&self, bytes: &[u8]) {
+    let output = {
+      let mut pending = self.pending.lock().unwrap();
+      pending.extend_from_slice(bytes);
+      let complete = match std::str::from_utf8(&pending) {
+        Ok(_) => pending.len(),
+        // Keep the incomplete char for the next read
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        // Invalid bytes are replaced
+        Err(_) => pending.len(),
+      };
+      let rest = pending.split_off(complete);
+      std::mem::replace(&mut *pending, rest)
+    };
+    if output.is_empty() {
+      return;
+    }
+    self.write_event("o", &String::from_utf8_lossy(&output));
+  }
+
+  pub fn resize(&self, cols: u16, rows: u16) {
+    self.write_event("r", &format!("{cols}x{rows}"));
+  }
+
+  /// Queues the event for the writer task without blocking.
+  fn write_event(&self, code: &str, data: &str) {
+    let event =
+      json!([self.start.elapsed().as_secs_f64(), code, data]);
+    match self.events.try_send(format!("{event}\n")) {
+      Ok(()) => {}
+      Err(TrySendError::Full(_)) => {
+        debug!(
+          "Dropped terminal recording event for {} | writer is behind",
+          self.name
+        );
+      }
+      // The recording stopped.
+      Err(TrySendError::Closed(_)) => {}
+    }
+  }
+}
+
+/// Appends the events to the recording until the terminal
+/// is closed, or the recording reaches [MAX_RECORDING_BYTES].
+async fn write_events(
+  name: String,
+  mut file: File,
+  mut written: u64,
+  mut events: mpsc::Receiver<String>,
+) {
+  while let Some(event) = events.recv().await {
+    written += event.len() as u64;
+    if written > MAX_RECORDING_BYTES {
+      info!(
+        "Terminal recording {name} reached {} MiB, recording stopped",
+        MAX_RECORDING_BYTES / 1024 / 1024
+      );
+      break;
+    }
+    if let Err(e) = file.write_all(event.as_bytes()).await {
+      debug!("Failed to write terminal recording {name} | {e:?}");
+      break;
+    }
+  }
+  if let Err(e) = file.flush().await {
+    debug!("Failed to flush terminal recording {name} | {e:?}");
+  }
+}
+
+/// Removes the oldest recordings, keeping the newest `keep`.
+async fn prune_recordings(keep: usize) -> anyhow::Result<()> {
+  let dir = recordings_dir();
+  for recording in list_recordings().await?.into_iter().skip(keep) {
+    let path = dir.join(&recording.name);
+    tokio::fs::remove_file(&path)
+      .await
+      .with_context(|| format!("Failed to remove {path:?}"))?;
+  }
+  Ok(())
+}
+
+/// Lists the recordings, newest first.
+pub async fn list_recordings()
+-> anyhow::Result<Vec<TerminalRecording>> {
+  let dir = recordings_dir();
+  let mut entries = match tokio::fs::read_dir(&dir).await {
+    Ok(entries) => entries,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+      return Ok(Vec::new());
+    }
+    Err(e) => {
+      return Err(e).with_context(|| {
+        format!("Failed to read recordings directory {dir:?}")
+      });
+    }
+  };
+  let mut recordings = Vec::new();
+  while let Some(entry) = entries
+    .next_entry()
+    .await
+    .context("Failed to read recordings directory entry")?
+  {
+    let name = entry.file_name().to_string_lossy().to_string();
+    let Some((terminal, started_at)) = name
+      .strip_suffix(".cast")
+      .and_then(|name| name.rsplit_once('-'))
+    else {
+      continue;
+    };
+    let Ok(started_at) = started_at.parse() else {
+      continue;
+    };
+    let size_kb = entry
+      .metadata()
+      .await
+      .map(|metadata| metadata.len() as f64 / 1024.0)
+      .unwrap_or_default();
+    recordings.push(TerminalRecording {
+      terminal: terminal.to_string(),
+      name,
+      size_kb,
+      started_at,
+    });
+  }
+  recordings.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+  Ok(recordings)
+}
+
+pub async fn get_recording(name: &str) -> anyhow::Result<String> {
+  if name.contains(['/', '\\'])
+    || name.starts_with('.')
+    || !name.ends_with(".cast")
+  {
+    return Err(anyhow!("Invalid recording name {name}"));
+  }
+  let path = recordings_dir().join(name);
+  let metadata = tokio::fs::metadata(&path)
+    .await
+    .with_context(|| format!("No recording named {name}"))?;
+  if metadata.len() > MAX_RECORDING_BYTES {
+    return Err(anyhow!(
+      "Recording {name} is larger than {} MiB, copy it from {path:?} on the host",
+      MAX_RECORDING_BYTES / 1024 / 1024
+    ));
+  }
+  tokio::fs::read_to_string(&path)
+    .await
+    .with_context(|| format!("Failed to read {path:?}"))
+}
@@ -250,6 +250,39 @@ pub struct DestroyDeployment {
 
 //
 
+/// Rolls the deployment back to the image of the previous successful deploy.
+/// Response: [Update].
+///
+/// 1. Periphery finds the latest deployed image which differs from the current container image.
+/// 2. The container is replaced with one running that image,
+/// using the current deployment configuration.
+///
+/// Rolling back again goes to the deploy before that one.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct RollbackDeployment {
+  /// Name or id
+  pub deployment: String,
+  /// Override the default termination signal specified in the deployment.
+  pub stop_signal: Option<TerminationSignal>,
+  /// Override the default termination max time.
+  pub stop_time: Option<i32>,
+}
+
+//
+
 /// Destroys multiple Deployments in parallel that match pattern. Response: [BatchExecutionResponse].
 #[typeshare]
 #[derive(
@@ -90,6 +90,7 @@ pub enum Execution {
   UnpauseDeployment(UnpauseDeployment),
   StopDeployment(StopDeployment),
   DestroyDeployment(DestroyDeployment),
+  RollbackDeployment(RollbackDeployment),
   BatchDestroyDeployment(BatchDestroyDeployment),
 
   // REPO
@@ -110,6 +111,7 @@ pub enum Execution {
   UnpauseContainer(UnpauseContainer),
   StopContainer(StopContainer),
   DestroyContainer(DestroyContainer),
+  CommitContainer(CommitContainer),
   StartAllContainers(StartAllContainers),
   RestartAllContainers(RestartAllContainers),
   PauseAllContainers(PauseAllContainers),
@@ -122,11 +124,27 @@ pub enum Execution {
   PruneNetworks(PruneNetworks),
   DeleteImage(DeleteImage),
   PruneImages(PruneImages),
+  TransferImage(TransferImage),
   DeleteVolume(DeleteVolume),
   PruneVolumes(PruneVolumes),
   PruneDockerBuilders(PruneDockerBuilders),
   PruneBuildx(PruneBuildx),
+  PruneBuildCache(PruneBuildCache),
   PruneSystem(PruneSystem),
+  RunServerExtension(RunServerExtension),
+
+  // SERVER (Processes)
+  KillSystemProcess(KillSystemProcess),
+  SetSystemProcessPriority(SetSystemProcessPriority),
+
+  // SERVER (Systemd)
+  StartSystemdUnit(StartSystemdUnit),
+  StopSystemdUnit(StopSystemdUnit),
+  RestartSystemdUnit(RestartSystemdUnit),
+
+  // SERVER (Host)
+  RebootServer(RebootServer),
+  ShutdownServer(ShutdownServer),
 
   // SYNC
   /// Execute a Resource Sync. (alias: `sync`)
@@ -153,6 +171,9 @@ pub enum Execution {
   DestroyStack(DestroyStack),
   BatchDestroyStack(BatchDestroyStack),
   RunStackService(RunStackService),
+  ScaleStack(ScaleStack),
+  PromoteStack(PromoteStack),
+  MigrateStack(MigrateStack),
 
   // ALERTER
   TestAlerter(TestAlerter),
@@ -1,10 +1,13 @@
+use anyhow::Context;
 use clap::Parser;
 use derive_empty_traits::EmptyTraits;
 use resolver_api::Resolve;
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::entities::{TerminationSignal, update::Update};
+use crate::entities::{
+  JsonObject, TerminationSignal, stats::ProcessSignal, update::Update,
+};
 
 use super::KomodoExecuteRequest;
 
@@ -179,6 +182,49 @@ pub struct DestroyContainer {
 
 //
 
+/// Commits the container on the target server into a new image,
+/// so it can be redeployed later. Response: [Update].
+///
+/// 1. Runs `docker commit ${container_name} ${image}`.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct CommitContainer {
+  /// Name or id
+  pub server: String,
+  /// The container name
+  pub container: String,
+  /// The image to create, ie `repository:tag`.
+  pub image: String,
+  /// Sets the author of the image.
+  #[clap(long)]
+  pub author: Option<String>,
+  /// Sets the commit message of the image.
+  #[clap(long)]
+  pub message: Option<String>,
+  /// Pause the container during the commit. Default: true
+  #[serde(default = "default_pause")]
+  #[clap(long, default_value_t = default_pause())]
+  pub pause: bool,
+}
+
+fn default_pause() -> bool {
+  true
+}
+
+//
+
 /// Starts all containers on the target server. Response: [Update]
 #[typeshare]
 #[derive(
@@ -413,6 +459,37 @@ pub struct PruneImages {
 
 //
 
+/// Copies the image from one server to another,
+/// without going through a registry. Response: [Update].
+///
+/// 1. Runs `docker save` on the `from_server`.
+/// 2. Transfers the archive to the `to_server`.
+/// 3. Runs `docker load` on the `to_server`.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct TransferImage {
+  /// The server with the image. Id or name
+  pub from_server: String,
+  /// The server to load the image on. Id or name
+  pub to_server: String,
+  /// The image name, ie `repository:tag`.
+  pub image: String,
+}
+
+//
+
 /// Delete a docker volume.
 /// Response: [Update]
 #[typeshare]
@@ -510,6 +587,36 @@ pub struct PruneBuildx {
 
 //
 
+/// Prunes the build cache on the target server,
+/// reporting the reclaimed and remaining size. Response: [Update].
+///
+/// 1. Runs `docker buildx prune -a -f` for the default builder
+///    and the Periphery `buildx_builder`.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct PruneBuildCache {
+  /// Id or name
+  pub server: String,
+  /// Keep cache used within this duration, ie `24h`.
+  /// By default, all the cache is pruned.
+  #[clap(long)]
+  pub keep_last: Option<String>,
+}
+
+//
+
 /// Prunes the docker system on the target server, including volumes. Response: [Update].
 ///
 /// 1. Runs `docker system prune -a -f --volumes`.
@@ -531,3 +638,257 @@ pub struct PruneSystem {
   /// Id or name
   pub server: String,
 }
+
+// =============
+// = PROCESSES =
+// =============
+
+/// Sends a signal to a process on the target server. Response: [Update]
+///
+/// 1. Runs `kill -s ${signal} ${pid}`.
+///
+/// Requires `enable_process_management` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct KillSystemProcess {
+  /// Id or name
+  pub server: String,
+  /// The process PID, see [ListSystemProcesses][crate::api::read::ListSystemProcesses]
+  pub pid: u32,
+  /// The signal to send. Default: `SIGTERM`
+  #[arg(long, short = 's')]
+  pub signal: Option<ProcessSignal>,
+}
+
+//
+
+/// Sets the priority of a process on the target server. Response: [Update]
+///
+/// 1. Runs `renice -n ${nice} -p ${pid}`.
+///
+/// Requires `enable_process_management` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct SetSystemProcessPriority {
+  /// Id or name
+  pub server: String,
+  /// The process PID, see [ListSystemProcesses][crate::api::read::ListSystemProcesses]
+  pub pid: u32,
+  /// The niceness, from -20 (highest priority) to 19 (lowest priority)
+  #[arg(allow_hyphen_values = true)]
+  pub nice: i32,
+}
+
+// ===========
+// = SYSTEMD =
+// ===========
+
+/// Starts the systemd unit on the target server. Response: [Update]
+///
+/// 1. Runs `systemctl start ${unit}`.
+///
+/// Requires `enable_systemd_management` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct StartSystemdUnit {
+  /// Id or name
+  pub server: String,
+  /// The unit name, ie `nfs-server.service`
+  pub unit: String,
+}
+
+//
+
+/// Stops the systemd unit on the target server. Response: [Update]
+///
+/// 1. Runs `systemctl stop ${unit}`.
+///
+/// Requires `enable_systemd_management` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct StopSystemdUnit {
+  /// Id or name
+  pub server: String,
+  /// The unit name, ie `nfs-server.service`
+  pub unit: String,
+}
+
+//
+
+/// Restarts the systemd unit on the target server. Response: [Update]
+///
+/// 1. Runs `systemctl restart ${unit}`.
+///
+/// Requires `enable_systemd_management` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct RestartSystemdUnit {
+  /// Id or name
+  pub server: String,
+  /// The unit name, ie `nfs-server.service`
+  pub unit: String,
+}
+
+// ========
+// = HOST =
+// ========
+
+/// Reboots the host of the target server. Response: [Update]
+///
+/// 1. Stops all containers on the host.
+/// 2. Tells Core the host is going down.
+/// 3. Runs `systemctl reboot`.
+///
+/// Requires `allow_host_power_actions` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct RebootServer {
+  /// Id or name
+  pub server: String,
+  /// Must be the server name, to confirm the reboot.
+  #[arg(long)]
+  pub confirm: String,
+}
+
+//
+
+/// Powers off the host of the target server. Response: [Update]
+///
+/// 1. Stops all containers on the host.
+/// 2. Tells Core the host is going down.
+/// 3. Runs `systemctl poweroff`.
+///
+/// The server will stay unreachable until the host is powered back on.
+/// Requires `allow_host_power_actions` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct ShutdownServer {
+  /// Id or name
+  pub server: String,
+  /// Must be the server name, to confirm the shutdown.
+  #[arg(long)]
+  pub confirm: String,
+}
+
+// =============
+// = EXTENSION =
+// =============
+
+/// Runs a command of one of the extensions configured on the target server. Response: [Update].
+///
+/// The extension receives `{ "command": string, "params": object }`
+/// as JSON on stdin, and its stdout / stderr are returned in the log.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  PartialEq,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct RunServerExtension {
+  /// Id or name
+  pub server: String,
+  /// The extension name, as configured on the Periphery.
+  pub extension: String,
+  /// The command passed to the extension.
+  pub command: String,
+  /// Custom params passed to the extension.
+  /// CLI Format: `'{"key":"value"}'`
+  #[clap(value_parser = params_parser)]
+  pub params: Option<JsonObject>,
+}
+
+fn params_parser(params: &str) -> anyhow::Result<JsonObject> {
+  serde_json::from_str(params).context("Failed to parse params")
+}
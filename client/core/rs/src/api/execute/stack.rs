@@ -1,4 +1,4 @@
-use crate::entities::update::Update;
+use crate::entities::{I64, update::Update};
 use anyhow::Context;
 use clap::ArgAction::SetTrue;
 use clap::Parser;
@@ -408,6 +408,36 @@ fn env_parser(args: &str) -> anyhow::Result<HashMap<String, String>> {
 
 //
 
+/// Scales a service of the target stack to the given number of replicas,
+/// using `docker compose up --scale`. Response: [Update]
+///
+/// The replica count is not written to the compose file,
+/// so the next deploy returns the service to its configured replicas.
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  PartialEq,
+  Serialize,
+  Deserialize,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct ScaleStack {
+  /// Id or name
+  pub stack: String,
+  /// The service to scale
+  pub service: String,
+  /// The target number of replicas
+  pub replicas: I64,
+}
+
+//
+
 /// Destroys multiple Stacks in parallel that match pattern. Response: [BatchExecutionResponse].
 #[typeshare]
 #[derive(
@@ -436,3 +466,70 @@ pub struct BatchDestroyStack {
   /// ```
   pub pattern: String,
 }
+
+//
+
+/// Promotes a Stack to another Stack, ie staging to production. Response: [Update]
+///
+/// Copies the pinned commit, the compose files and the other release config
+/// from the `from` Stack onto the `to` Stack. The `to` Stack keeps its own Server,
+/// environment, tags and deploy settings.
+///
+/// The config changes are logged on the Update. Use `dry_run` to only preview them.
+/// The `to` Stack is not redeployed, chain with [DeployStack] to release it.
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  PartialEq,
+  Serialize,
+  Deserialize,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct PromoteStack {
+  /// The Stack to promote from (ie staging). Id or name.
+  pub from: String,
+  /// The Stack to promote to (ie production). Id or name.
+  pub to: String,
+  /// Only preview the config changes on the Update, without applying them.
+  #[serde(default)]
+  #[clap(long, default_value_t = false)]
+  pub dry_run: bool,
+}
+
+//
+
+/// Migrates a Stack to another Server, including its named volumes. Response: [Update]
+///
+/// 1. Destroys the Stack on the current Server, keeping the volumes.
+/// 2. Archives the named volumes and transfers them to the target Server.
+/// 3. Restores the volumes, points the Stack at the target Server and deploys it.
+/// 4. Waits for the Stack to be running, then removes the volumes from the old Server.
+///
+/// Each step is logged on the Update. If a step fails, the completed steps
+/// are kept on the Stack info, and running MigrateStack again resumes after them.
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  PartialEq,
+  Serialize,
+  Deserialize,
+  Resolve,
+  EmptyTraits,
+  Parser,
+)]
+#[empty_traits(KomodoExecuteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct MigrateStack {
+  /// Id or name
+  pub stack: String,
+  /// The Server to migrate the Stack to. Id or name.
+  pub to_server: String,
+}
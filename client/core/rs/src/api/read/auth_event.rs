@@ -0,0 +1,48 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{
+  I64, U64,
+  auth_event::{AuthEvent, AuthEventDirection},
+};
+
+use super::KomodoReadRequest;
+
+/// **Admin only.** Get a paginated list of Core <-> Periphery
+/// login attempts, sorted by timestamp descending.
+/// Response: [ListAuthEventsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListAuthEventsResponse)]
+#[error(serror::Error)]
+pub struct ListAuthEvents {
+  /// Filter by Server id or name.
+  pub server: Option<String>,
+  /// Filter by the public key presented.
+  pub public_key: Option<String>,
+  /// Filter by successful / failed logins.
+  pub success: Option<bool>,
+  /// Filter by connection direction.
+  pub direction: Option<AuthEventDirection>,
+  /// Only include events at or after this unix timestamp in milliseconds.
+  pub since: Option<I64>,
+  /// Retrieve older results by incrementing the page.
+  /// `page: 0` is default, and returns the most recent results.
+  #[serde(default)]
+  pub page: U64,
+}
+
+/// Response for [ListAuthEvents].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ListAuthEventsResponse {
+  pub events: Vec<AuthEvent>,
+  /// If more events exist, the next page will be given here.
+  /// Otherwise it will be `null`
+  pub next_page: Option<I64>,
+}
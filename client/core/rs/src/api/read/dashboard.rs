@@ -0,0 +1,44 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::dashboard::Dashboard;
+
+use super::KomodoReadRequest;
+
+/// Get a specific dashboard. Response: [Dashboard].
+///
+/// Non admin users can only get dashboards
+/// they own or which are shared.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetDashboardResponse)]
+#[error(serror::Error)]
+pub struct GetDashboard {
+  /// The id of the dashboard.
+  pub id: String,
+}
+
+#[typeshare]
+pub type GetDashboardResponse = Dashboard;
+
+//
+
+/// List the dashboards the calling user owns,
+/// as well as all shared dashboards.
+/// Response: [ListDashboardsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListDashboardsResponse)]
+#[error(serror::Error)]
+pub struct ListDashboards {}
+
+#[typeshare]
+pub type ListDashboardsResponse = Vec<Dashboard>;
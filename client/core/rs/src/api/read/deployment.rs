@@ -10,6 +10,7 @@ use crate::entities::{
     DeploymentQuery, DeploymentState,
   },
   docker::container::{Container, ContainerListItem, ContainerStats},
+  resource::ConfigInheritance,
   update::Log,
 };
 
@@ -69,6 +70,10 @@ pub struct ListFullDeployments {
   /// optional structured query to filter deployments.
   #[serde(default)]
   pub query: DeploymentQuery,
+  /// Only include these fields of each deployment in the response,
+  /// as dot separated paths, eg. `config.image`. Includes all fields if empty.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub fields: Vec<String>,
 }
 
 #[typeshare]
@@ -125,6 +130,26 @@ pub type InspectDeploymentContainerResponse = Container;
 
 //
 
+/// Get the config the Deployment inherits from its template,
+/// with the source of each value. Response: [ConfigInheritance].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetDeploymentInheritanceResponse)]
+#[error(serror::Error)]
+pub struct GetDeploymentInheritance {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub deployment: String,
+}
+
+#[typeshare]
+pub type GetDeploymentInheritanceResponse = ConfigInheritance;
+
+//
+
 /// Get the deployment log's tail, split by stdout/stderr.
 /// Response: [Log].
 ///
@@ -102,6 +102,10 @@ pub struct InspectDockerContainer {
   pub server: String,
   /// The container name
   pub container: String,
+  /// Only include these fields in the response,
+  /// as dot separated paths, eg. `State.Status`. Includes all fields if empty.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub fields: Vec<String>,
 }
 
 #[typeshare]
@@ -302,6 +306,9 @@ pub struct InspectDockerImage {
   pub server: String,
   /// The image name
   pub image: String,
+  /// Bypass the Core read cache and get the latest from Periphery.
+  #[serde(default)]
+  pub skip_cache: bool,
 }
 
 #[typeshare]
@@ -323,6 +330,9 @@ pub struct ListDockerImageHistory {
   pub server: String,
   /// The image name
   pub image: String,
+  /// Bypass the Core read cache and get the latest from Periphery.
+  #[serde(default)]
+  pub skip_cache: bool,
 }
 
 #[typeshare]
@@ -6,20 +6,28 @@ use typeshare::typeshare;
 mod action;
 mod alert;
 mod alerter;
+mod auth_event;
 mod build;
 mod builder;
+mod dashboard;
 mod deployment;
 mod docker;
+mod notification;
 mod onboarding_key;
+mod organization;
+mod pending_server;
 mod permission;
 mod procedure;
 mod provider;
 mod repo;
+mod revoked_key;
 mod schedule;
 mod server;
 mod stack;
 mod sync;
+mod systemd;
 mod tag;
+mod template;
 mod toml;
 mod update;
 mod user;
@@ -29,20 +37,28 @@ mod variable;
 pub use action::*;
 pub use alert::*;
 pub use alerter::*;
+pub use auth_event::*;
 pub use build::*;
 pub use builder::*;
+pub use dashboard::*;
 pub use deployment::*;
 pub use docker::*;
+pub use notification::*;
 pub use onboarding_key::*;
+pub use organization::*;
+pub use pending_server::*;
 pub use permission::*;
 pub use procedure::*;
 pub use provider::*;
 pub use repo::*;
+pub use revoked_key::*;
 pub use schedule::*;
 pub use server::*;
 pub use stack::*;
 pub use sync::*;
+pub use systemd::*;
 pub use tag::*;
+pub use template::*;
 pub use toml::*;
 pub use update::*;
 pub use user::*;
@@ -50,7 +66,7 @@ pub use user_group::*;
 pub use variable::*;
 
 use crate::entities::{
-  ResourceTarget, Timelength,
+  ReadOnlyMode, ResourceTarget, Timelength,
   config::{DockerRegistry, GitProvider},
 };
 
@@ -116,6 +132,10 @@ pub struct GetCoreInfoResponse {
   pub timezone: String,
   /// Default public key allowing this Core to authenticate to Periphery agents.
   pub public_key: String,
+  /// The current read-only mode state.
+  /// While enabled, write and execute requests are rejected.
+  #[serde(default)]
+  pub read_only: ReadOnlyMode,
 }
 
 //
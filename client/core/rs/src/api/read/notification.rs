@@ -0,0 +1,39 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{I64, U64, notification::Notification};
+
+use super::KomodoReadRequest;
+
+/// Get a paginated list of the calling user's notifications,
+/// sorted by timestamp descending. Response: [ListNotificationsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListNotificationsResponse)]
+#[error(serror::Error)]
+pub struct ListNotifications {
+  /// Only include notifications which haven't been read.
+  #[serde(default)]
+  pub unread_only: bool,
+  /// Retrieve older results by incrementing the page.
+  /// `page: 0` is default, and returns the most recent results.
+  #[serde(default)]
+  pub page: U64,
+}
+
+/// Response for [ListNotifications].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ListNotificationsResponse {
+  pub notifications: Vec<Notification>,
+  /// The total number of unread notifications for the user.
+  pub unread: U64,
+  /// If more notifications exist, the next page will be given here.
+  /// Otherwise it will be `null`
+  pub next_page: Option<I64>,
+}
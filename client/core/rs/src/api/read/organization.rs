@@ -0,0 +1,45 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::organization::Organization;
+
+use super::KomodoReadRequest;
+
+/// Get a specific Organization by name or id.
+/// Response: [Organization].
+///
+/// Non admin users can only get the Organization they belong to.
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetOrganizationResponse)]
+#[error(serror::Error)]
+pub struct GetOrganization {
+  /// Name or Id
+  pub organization: String,
+}
+
+#[typeshare]
+pub type GetOrganizationResponse = Organization;
+
+//
+
+/// List all Organizations which user can see. Response: [ListOrganizationsResponse].
+///
+/// Admins can see all Organizations,
+/// and users can see the Organization to which they belong.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListOrganizationsResponse)]
+#[error(serror::Error)]
+pub struct ListOrganizations {}
+
+#[typeshare]
+pub type ListOrganizationsResponse = Vec<Organization>;
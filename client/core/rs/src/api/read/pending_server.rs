@@ -0,0 +1,23 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::pending_server::PendingServer;
+
+use super::KomodoReadRequest;
+
+/// **Admin only.** Gets list of Servers waiting for onboarding
+/// approval, most recently requested first.
+/// Response: [ListPendingServersResponse]
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListPendingServersResponse)]
+#[error(serror::Error)]
+pub struct ListPendingServers {}
+
+#[typeshare]
+pub type ListPendingServersResponse = Vec<PendingServer>;
@@ -0,0 +1,23 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::revoked_key::RevokedKey;
+
+use super::KomodoReadRequest;
+
+/// **Admin only.** Gets list of revoked Periphery public keys,
+/// most recently revoked first.
+/// Response: [ListRevokedKeysResponse]
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListRevokedKeysResponse)]
+#[error(serror::Error)]
+pub struct ListRevokedKeys {}
+
+#[typeshare]
+pub type ListRevokedKeysResponse = Vec<RevokedKey>;
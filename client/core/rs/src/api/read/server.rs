@@ -6,11 +6,16 @@ use typeshare::typeshare;
 use crate::entities::{
   I64, Timelength,
   server::{
-    PeripheryInformation, Server, ServerActionState, ServerListItem,
-    ServerQuery, ServerState, TerminalInfo,
+    ActiveTerminalSession, ConnectionShardStats,
+    PeripheryInformation, Server, ServerActionState,
+    ServerConnectionMetrics, ServerListItem, ServerQuery,
+    ServerState, TerminalInfo, TerminalRecording,
+    TransportTraceEntry,
   },
+  server_schedule::ServerScheduleStatus,
   stats::{
-    SystemInformation, SystemProcess, SystemStats, SystemStatsRecord,
+    ListeningSocket, SystemInformation, SystemProcess, SystemStats,
+    SystemStatsRecord,
   },
 };
 
@@ -132,6 +137,10 @@ pub struct GetPeripheryInformation {
   /// Id or name
   #[serde(alias = "id", alias = "name")]
   pub server: String,
+  /// Only include these fields in the response,
+  /// as dot separated paths, eg. `version`. Includes all fields if empty.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub fields: Vec<String>,
 }
 
 #[typeshare]
@@ -139,6 +148,75 @@ pub type GetPeripheryInformationResponse = PeripheryInformation;
 
 //
 
+/// **Admin only.** List the load metrics of the Core
+/// Periphery connection shards.
+/// Response: [ListConnectionShardsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListConnectionShardsResponse)]
+#[error(serror::Error)]
+pub struct ListConnectionShards {}
+
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ListConnectionShardsResponse {
+  /// The total inbound connections rejected by
+  /// `connection_accept_rate` since Core started.
+  pub rate_limited: u64,
+  /// The load metrics of each shard.
+  pub shards: Vec<ConnectionShardStats>,
+}
+
+//
+
+/// Get the transport metrics of the target server's Periphery connection,
+/// eg. to find which server is saturating Core's uplink.
+/// Response: [ServerConnectionMetrics].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetServerConnectionMetricsResponse)]
+#[error(serror::Error)]
+pub struct GetServerConnectionMetrics {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub server: String,
+}
+
+#[typeshare]
+pub type GetServerConnectionMetricsResponse = ServerConnectionMetrics;
+
+//
+
+/// **Admin only.** List the recorded transport message headers,
+/// newest first. Requires Core `transport_trace` to be enabled.
+/// Response: [ListTransportTraceResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListTransportTraceResponse)]
+#[error(serror::Error)]
+pub struct ListTransportTrace {
+  /// Only include messages on this Server's connection. Id or name.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub server: Option<String>,
+  /// Max entries to return. Default: 1000
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub limit: Option<u64>,
+}
+
+#[typeshare]
+pub type ListTransportTraceResponse = Vec<TransportTraceEntry>;
+
+//
+
 /// Get the system information of the target server.
 /// Response: [SystemInformation].
 #[typeshare]
@@ -152,6 +230,10 @@ pub struct GetSystemInformation {
   /// Id or name
   #[serde(alias = "id", alias = "name")]
   pub server: String,
+  /// Only include these fields in the response,
+  /// as dot separated paths, eg. `host_name`. Includes all fields if empty.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub fields: Vec<String>,
 }
 
 #[typeshare]
@@ -175,6 +257,10 @@ pub struct GetSystemStats {
   /// Id or name
   #[serde(alias = "id", alias = "name")]
   pub server: String,
+  /// Only include these fields in the response,
+  /// as dot separated paths, eg. `cpu_perc`. Includes all fields if empty.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub fields: Vec<String>,
 }
 
 #[typeshare]
@@ -199,6 +285,9 @@ pub struct ListSystemProcesses {
   /// Id or name
   #[serde(alias = "id", alias = "name")]
   pub server: String,
+  /// Bypass the Core read cache and get the latest from Periphery.
+  #[serde(default)]
+  pub skip_cache: bool,
 }
 
 #[typeshare]
@@ -206,6 +295,107 @@ pub type ListSystemProcessesResponse = Vec<SystemProcess>;
 
 //
 
+/// List the TCP / UDP sockets listening on the target server,
+/// with the owning process and container. Use it to find
+/// the port conflicts before a deploy fails with `address already in use`.
+/// Response: [ListListeningSocketsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListListeningSocketsResponse)]
+#[error(serror::Error)]
+pub struct ListListeningSockets {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub server: String,
+}
+
+#[typeshare]
+pub type ListListeningSocketsResponse = Vec<ListeningSocket>;
+
+//
+
+/// Suggest the best Servers to place a new Deployment on,
+/// based on their current stats and configured capacity.
+/// Response: [SuggestServerPlacementResponse].
+///
+/// Servers are ordered with the best fit first.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(SuggestServerPlacementResponse)]
+#[error(serror::Error)]
+pub struct SuggestServerPlacement {
+  /// Only consider these Servers (id or name).
+  /// If empty, considers all Servers the user has access to.
+  #[serde(default)]
+  pub servers: Vec<String>,
+  /// The expected CPU usage (percent of one Server's total) of the Deployment.
+  #[serde(default)]
+  pub cpu: f32,
+  /// The expected memory usage (GB) of the Deployment.
+  #[serde(default)]
+  pub mem_gb: f64,
+}
+
+#[typeshare]
+pub type SuggestServerPlacementResponse = Vec<ServerPlacement>;
+
+//
+
+/// Check whether a new Deployment fits on the target Server,
+/// based on its current stats and configured capacity.
+/// Response: [ServerPlacement].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(CheckServerPlacementResponse)]
+#[error(serror::Error)]
+pub struct CheckServerPlacement {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub server: String,
+  /// The expected CPU usage (percent of the Server's total) of the Deployment.
+  #[serde(default)]
+  pub cpu: f32,
+  /// The expected memory usage (GB) of the Deployment.
+  #[serde(default)]
+  pub mem_gb: f64,
+}
+
+#[typeshare]
+pub type CheckServerPlacementResponse = ServerPlacement;
+
+/// The result of checking a Server's capacity for a new Deployment.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ServerPlacement {
+  /// The Server id
+  pub server_id: String,
+  /// The Server name
+  pub server_name: String,
+  /// Whether the Deployment fits on the Server without overcommitting it.
+  pub fits: bool,
+  /// The reasons the Deployment does not fit, if any.
+  pub reasons: Vec<String>,
+  /// The CPU percentage available after the current usage and reservation.
+  pub available_cpu: f32,
+  /// The memory (GB) available after the current usage and reservation.
+  pub available_mem_gb: f64,
+  /// The number of containers currently on the Server.
+  pub containers: I64,
+  /// The maximum number of containers configured for the Server. 0 means no limit.
+  pub max_containers: I64,
+}
+
+//
+
 /// Paginated endpoint serving historical (timeseries) server stats for graphing.
 /// Response: [GetHistoricalServerStatsResponse].
 #[typeshare]
@@ -289,3 +479,86 @@ pub struct ListTerminals {
 
 #[typeshare]
 pub type ListTerminalsResponse = Vec<TerminalInfo>;
+
+//
+
+/// List the terminal recordings stored on the server, newest first.
+/// Response: [ListTerminalRecordingsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListTerminalRecordingsResponse)]
+#[error(serror::Error)]
+pub struct ListTerminalRecordings {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub server: String,
+}
+
+#[typeshare]
+pub type ListTerminalRecordingsResponse = Vec<TerminalRecording>;
+
+//
+
+/// Get the contents of a terminal recording on the server,
+/// in asciicast v2 format for playback with `asciinema play`.
+/// Response: [GetTerminalRecordingResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetTerminalRecordingResponse)]
+#[error(serror::Error)]
+pub struct GetTerminalRecording {
+  /// Id or name
+  #[serde(alias = "id")]
+  pub server: String,
+  /// The file name of the recording,
+  /// from [ListTerminalRecordings].
+  pub recording: String,
+}
+
+#[typeshare]
+pub type GetTerminalRecordingResponse = String;
+
+//
+
+/// List the users currently connected to terminals through Core.
+/// Admin only.
+/// Response: [ListActiveTerminalSessionsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListActiveTerminalSessionsResponse)]
+#[error(serror::Error)]
+pub struct ListActiveTerminalSessions {}
+
+#[typeshare]
+pub type ListActiveTerminalSessionsResponse =
+  Vec<ActiveTerminalSession>;
+
+//
+
+/// List the commands the server runs on cron schedules,
+/// from the Periphery config file and the API, with their run status.
+/// Response: [ListServerSchedulesResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListServerSchedulesResponse)]
+#[error(serror::Error)]
+pub struct ListServerSchedules {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub server: String,
+}
+
+#[typeshare]
+pub type ListServerSchedulesResponse = Vec<ServerScheduleStatus>;
@@ -6,6 +6,7 @@ use typeshare::typeshare;
 use crate::entities::{
   SearchCombinator, U64,
   docker::container::Container,
+  resource::ConfigInheritance,
   stack::{
     Stack, StackActionState, StackListItem, StackQuery, StackService,
   },
@@ -35,6 +36,26 @@ pub type GetStackResponse = Stack;
 
 //
 
+/// Get the config the Stack inherits from its template,
+/// with the source of each value. Response: [ConfigInheritance].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetStackInheritanceResponse)]
+#[error(serror::Error)]
+pub struct GetStackInheritance {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub stack: String,
+}
+
+#[typeshare]
+pub type GetStackInheritanceResponse = ConfigInheritance;
+
+//
+
 /// Lists a specific stacks services (the containers). Response: [ListStackServicesResponse].
 #[typeshare]
 #[derive(
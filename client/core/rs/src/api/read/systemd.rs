@@ -0,0 +1,95 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{
+  U64,
+  systemd::{SystemdUnit, SystemdUnitStatus},
+  update::Log,
+};
+
+use super::KomodoReadRequest;
+
+//
+
+/// List the systemd services, timers, sockets, paths and mounts
+/// loaded on the target server.
+/// Response: [ListSystemdUnitsResponse].
+///
+/// Requires `enable_systemd_management` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListSystemdUnitsResponse)]
+#[error(serror::Error)]
+pub struct ListSystemdUnits {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub server: String,
+}
+
+#[typeshare]
+pub type ListSystemdUnitsResponse = Vec<SystemdUnit>;
+
+//
+
+/// Get the detailed status of a systemd unit on the target server.
+/// Response: [SystemdUnitStatus].
+///
+/// Requires `enable_systemd_management` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetSystemdUnitStatusResponse)]
+#[error(serror::Error)]
+pub struct GetSystemdUnitStatus {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub server: String,
+  /// The unit name, ie `nfs-server.service`
+  pub unit: String,
+}
+
+#[typeshare]
+pub type GetSystemdUnitStatusResponse = SystemdUnitStatus;
+
+//
+
+/// Get the tail of a systemd unit journal on the target server.
+/// Response: [Log].
+///
+/// Requires `enable_systemd_management` in the Periphery config.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetSystemdUnitJournalResponse)]
+#[error(serror::Error)]
+pub struct GetSystemdUnitJournal {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub server: String,
+  /// The unit name, ie `nfs-server.service`
+  pub unit: String,
+  /// The number of lines of the journal tail to include.
+  /// Default: 100.
+  /// Max: 5000.
+  #[serde(default = "default_tail")]
+  pub tail: U64,
+  /// Show the entries with microsecond timestamps.
+  #[serde(default)]
+  pub timestamps: bool,
+}
+
+fn default_tail() -> u64 {
+  100
+}
+
+#[typeshare]
+pub type GetSystemdUnitJournalResponse = Log;
@@ -3,7 +3,10 @@ use resolver_api::Resolve;
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::entities::{MongoDocument, tag::Tag};
+use crate::entities::{
+  I64, MongoDocument,
+  tag::{Tag, TagUsageReport},
+};
 
 use super::KomodoReadRequest;
 
@@ -43,3 +46,50 @@ pub struct ListTags {
 
 #[typeshare]
 pub type ListTagsResponse = Vec<Tag>;
+
+//
+
+/// Get the resource usage report for a tag over a time window.
+/// Only includes the resources the user has access to.
+/// Response: [TagUsageReport].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetTagUsageReportResponse)]
+#[error(serror::Error)]
+pub struct GetTagUsageReport {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub tag: String,
+  /// Start of the window (unix ms). Default: 7 days before `until`.
+  pub since: Option<I64>,
+  /// End of the window (unix ms). Default: now.
+  pub until: Option<I64>,
+}
+
+#[typeshare]
+pub type GetTagUsageReportResponse = TagUsageReport;
+
+//
+
+/// Get the resource usage reports for all tags over a time window.
+/// Only includes the resources the user has access to.
+/// Response: [ListTagUsageReportsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListTagUsageReportsResponse)]
+#[error(serror::Error)]
+pub struct ListTagUsageReports {
+  /// Start of the window (unix ms). Default: 7 days before `until`.
+  pub since: Option<I64>,
+  /// End of the window (unix ms). Default: now.
+  pub until: Option<I64>,
+}
+
+#[typeshare]
+pub type ListTagUsageReportsResponse = Vec<TagUsageReport>;
@@ -0,0 +1,40 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::template::Template;
+
+use super::KomodoReadRequest;
+
+/// Get a specific template by name. Response: [Template].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetTemplateResponse)]
+#[error(serror::Error)]
+pub struct GetTemplate {
+  /// The name of the template to get.
+  pub name: String,
+}
+
+#[typeshare]
+pub type GetTemplateResponse = Template;
+
+//
+
+/// List all templates in the catalog.
+/// Response: [ListTemplatesResponse]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListTemplatesResponse)]
+#[error(serror::Error)]
+pub struct ListTemplates {}
+
+#[typeshare]
+pub type ListTemplatesResponse = Vec<Template>;
@@ -44,6 +44,10 @@ pub struct ExportAllResourcesToToml {
   /// Default: false
   #[serde(default)]
   pub include_user_groups: bool,
+  /// Whether to include onboarding key metadata in the exported contents.
+  /// Admin only. Default: false
+  #[serde(default)]
+  pub include_onboarding_keys: bool,
 }
 
 fn default_include_resources() -> bool {
@@ -74,6 +78,9 @@ pub struct ExportResourcesToToml {
   /// Whether to include variables
   #[serde(default)]
   pub include_variables: bool,
+  /// Whether to include onboarding key metadata. Admin only.
+  #[serde(default)]
+  pub include_onboarding_keys: bool,
 }
 
 #[typeshare]
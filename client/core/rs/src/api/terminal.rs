@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::api::write::TerminalRecreateMode;
+use crate::{api::write::TerminalRecreateMode, entities::U64};
 
 /// Query to connect to a terminal (interactive shell over websocket) on the given server.
 #[typeshare]
@@ -14,6 +14,12 @@ pub struct ConnectTerminalQuery {
   /// the call will fail.
   /// Create a terminal using [CreateTerminal][super::write::server::CreateTerminal]
   pub terminal: String,
+  /// Only view the terminal output.
+  /// Any input sent is dropped by Periphery.
+  /// Read only connections require Read + Terminal permission
+  /// on the Server, others require Write + Terminal.
+  #[serde(default)]
+  pub read_only: bool,
 }
 
 /// Execute a terminal command on the given server.
@@ -45,6 +51,7 @@ pub struct ConnectContainerExecQuery {
 }
 
 /// Query to connect to a container attach session (interactive shell over websocket) on the given server.
+/// This call requires the ContainerAttach specific permission on the Server.
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ConnectContainerAttachQuery {
@@ -56,6 +63,24 @@ pub struct ConnectContainerAttachQuery {
   /// Default is 'DifferentCommand'
   #[serde(default = "default_container_recreate_mode")]
   pub recreate: TerminalRecreateMode,
+  /// Override the key sequence for detaching from the container,
+  /// eg. `ctrl-x,x`. Default is docker's `ctrl-p,ctrl-q`.
+  pub detach_keys: Option<String>,
+}
+
+/// Sent to the client as a text message, prefixed with `ATTACH_INFO:`,
+/// once the container attach session is established.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContainerAttachInfo {
+  /// Whether the container main process was started with a TTY (`-t`).
+  /// Without one, output is line buffered and there is no terminal control.
+  pub tty: bool,
+  /// Whether the container main process stdin is open (`-i`).
+  /// If not, the session is read only.
+  pub stdin: bool,
+  /// The key sequence which detaches from the container.
+  pub detach_keys: String,
 }
 
 /// Execute a command in the given containers shell.
@@ -77,7 +102,7 @@ pub struct ExecuteContainerExecBody {
 }
 
 /// Query to connect to a container exec session (interactive shell over websocket) on the given Deployment.
-/// This call will use access to the Deployment Terminal to permission the call.
+/// This call requires the ContainerAttach specific permission on the Deployment.
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ConnectDeploymentExecQuery {
@@ -92,7 +117,7 @@ pub struct ConnectDeploymentExecQuery {
 }
 
 /// Query to connect to a container attach session (interactive shell over websocket) on the given Deployment.
-/// This call will use access to the Deployment Terminal to permission the call.
+/// This call requires the ContainerAttach specific permission on the Deployment.
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ConnectDeploymentAttachQuery {
@@ -102,6 +127,9 @@ pub struct ConnectDeploymentAttachQuery {
   /// Default is 'DifferentCommand'
   #[serde(default = "default_container_recreate_mode")]
   pub recreate: TerminalRecreateMode,
+  /// Override the key sequence for detaching from the container,
+  /// eg. `ctrl-x,x`. Default is docker's `ctrl-p,ctrl-q`.
+  pub detach_keys: Option<String>,
 }
 
 /// Execute a command in the given containers shell.
@@ -121,7 +149,7 @@ pub struct ExecuteDeploymentExecBody {
 }
 
 /// Query to connect to a container exec session (interactive shell over websocket) on the given Stack / service.
-/// This call will use access to the Stack Terminal to permission the call.
+/// This call requires the ContainerAttach specific permission on the Stack.
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ConnectStackExecQuery {
@@ -138,7 +166,7 @@ pub struct ConnectStackExecQuery {
 }
 
 /// Query to connect to a container attach session (interactive shell over websocket) on the given Stack / service.
-/// This call will use access to the Stack Terminal to permission the call.
+/// This call requires the ContainerAttach specific permission on the Stack.
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ConnectStackAttachQuery {
@@ -150,6 +178,9 @@ pub struct ConnectStackAttachQuery {
   /// Default is 'DifferentCommand'
   #[serde(default = "default_container_recreate_mode")]
   pub recreate: TerminalRecreateMode,
+  /// Override the key sequence for detaching from the container,
+  /// eg. `ctrl-x,x`. Default is docker's `ctrl-p,ctrl-q`.
+  pub detach_keys: Option<String>,
 }
 
 /// Execute a command in the given containers shell.
@@ -170,6 +201,32 @@ pub struct ExecuteStackExecBody {
   pub recreate: TerminalRecreateMode,
 }
 
+/// Stream the lines of a file on the server host, ie `/var/log/syslog`.
+/// The path must be inside the `allowed_log_paths` in the Periphery config.
+/// This call requires the Logs specific permission on the Server.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TailHostFileBody {
+  /// Server Id or name
+  pub server: String,
+  /// The absolute path of the file on the host.
+  pub path: String,
+  /// The number of lines from the end of the file to start with.
+  /// Default: 100.
+  /// Max: 5000.
+  #[serde(default = "default_tail_lines")]
+  pub lines: U64,
+  /// Keep streaming the lines appended to the file,
+  /// until the request is closed. The opened file is followed,
+  /// not the path, so rotated files need a new request.
+  #[serde(default)]
+  pub follow: bool,
+}
+
+fn default_tail_lines() -> U64 {
+  100
+}
+
 fn default_container_recreate_mode() -> TerminalRecreateMode {
   TerminalRecreateMode::DifferentCommand
 }
@@ -0,0 +1,80 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::dashboard::{Dashboard, DashboardWidget};
+
+use super::KomodoWriteRequest;
+
+/// Create a dashboard owned by the calling user. Response: [Dashboard].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(CreateDashboardResponse)]
+#[error(serror::Error)]
+pub struct CreateDashboard {
+  /// The name of the dashboard.
+  pub name: String,
+  /// The description of the dashboard. default: "".
+  #[serde(default)]
+  pub description: String,
+  /// Whether all users can view the dashboard. default: false.
+  #[serde(default)]
+  pub shared: bool,
+  /// The initial widget layout.
+  #[serde(default)]
+  pub widgets: Vec<DashboardWidget>,
+}
+
+#[typeshare]
+pub type CreateDashboardResponse = Dashboard;
+
+//
+
+/// Update a dashboard. Only the owner or an admin can update.
+/// Only the fields which are passed will be updated.
+/// Response: [Dashboard].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(UpdateDashboardResponse)]
+#[error(serror::Error)]
+pub struct UpdateDashboard {
+  /// The id of the dashboard to update.
+  pub id: String,
+  /// Update the name.
+  pub name: Option<String>,
+  /// Update the description.
+  pub description: Option<String>,
+  /// Update whether the dashboard is shared.
+  pub shared: Option<bool>,
+  /// Replace the widget layout.
+  pub widgets: Option<Vec<DashboardWidget>>,
+}
+
+#[typeshare]
+pub type UpdateDashboardResponse = Dashboard;
+
+//
+
+/// Delete a dashboard. Only the owner or an admin can delete.
+/// Response: [Dashboard].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(DeleteDashboardResponse)]
+#[error(serror::Error)]
+pub struct DeleteDashboard {
+  /// The id of the dashboard to delete.
+  pub id: String,
+}
+
+#[typeshare]
+pub type DeleteDashboardResponse = Dashboard;
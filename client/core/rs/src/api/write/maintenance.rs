@@ -0,0 +1,35 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::ReadOnlyMode;
+
+use super::KomodoWriteRequest;
+
+/// **Admin only.** Enable or disable Core global read-only mode.
+/// Response: [ReadOnlyMode].
+///
+/// While enabled, all write and execute requests (other than this one)
+/// are rejected with `503 Service Unavailable` and the given reason.
+/// Reads, monitoring and alerting continue as normal.
+/// Useful during database maintenance or incident freezes.
+///
+/// Note. Read-only mode is not persisted across Core restarts.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(SetReadOnlyModeResponse)]
+#[error(serror::Error)]
+pub struct SetReadOnlyMode {
+  /// Whether to enable read-only mode.
+  pub enabled: bool,
+  /// The reason for read-only mode, shown to users when requests are rejected.
+  #[serde(default)]
+  pub reason: String,
+}
+
+#[typeshare]
+pub type SetReadOnlyModeResponse = ReadOnlyMode;
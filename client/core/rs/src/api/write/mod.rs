@@ -4,17 +4,24 @@ mod alerter;
 mod api_key;
 mod build;
 mod builder;
+mod dashboard;
 mod deployment;
+mod maintenance;
+mod notification;
 mod onboarding_key;
+mod organization;
+mod pending_server;
 mod permissions;
 mod procedure;
 mod provider;
 mod repo;
 mod resource;
+mod revoked_key;
 mod server;
 mod stack;
 mod sync;
 mod tags;
+mod template;
 mod user;
 mod user_group;
 mod variable;
@@ -25,17 +32,24 @@ pub use alerter::*;
 pub use api_key::*;
 pub use build::*;
 pub use builder::*;
+pub use dashboard::*;
 pub use deployment::*;
+pub use maintenance::*;
+pub use notification::*;
 pub use onboarding_key::*;
+pub use organization::*;
+pub use pending_server::*;
 pub use permissions::*;
 pub use procedure::*;
 pub use provider::*;
 pub use repo::*;
 pub use resource::*;
+pub use revoked_key::*;
 pub use server::*;
 pub use stack::*;
 pub use sync::*;
 pub use tags::*;
+pub use template::*;
 pub use user::*;
 pub use user_group::*;
 pub use variable::*;
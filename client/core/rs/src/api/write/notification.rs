@@ -0,0 +1,51 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{NoData, ResourceTarget};
+
+use super::KomodoWriteRequest;
+
+/// Mark the calling user's notifications as read. Response: [NoData].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(AckNotificationsResponse)]
+#[error(serror::Error)]
+pub struct AckNotifications {
+  /// The notification ids to mark as read.
+  /// If empty, all of the user's notifications are marked as read.
+  #[serde(default)]
+  pub ids: Vec<String>,
+}
+
+#[typeshare]
+pub type AckNotificationsResponse = NoData;
+
+//
+
+/// Mention other users, sending a notification to their inbox.
+/// Response: [NoData].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(SendNotificationResponse)]
+#[error(serror::Error)]
+pub struct SendNotification {
+  /// The ids or usernames of the users to notify.
+  pub users: Vec<String>,
+  /// The message to send.
+  pub message: String,
+  /// Optionally attach a target the message is about.
+  /// The receiving users must have at least read permissions on it.
+  #[serde(default)]
+  pub target: Option<ResourceTarget>,
+}
+
+#[typeshare]
+pub type SendNotificationResponse = NoData;
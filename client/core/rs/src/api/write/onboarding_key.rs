@@ -41,6 +41,13 @@ pub struct CreateOnboardingKey {
   /// Optional. Whether to also create a Builder for the Server.
   #[serde(default)]
   pub create_builder: bool,
+  /// Optional. The max number of Servers the key can onboard.
+  /// Default is 0, which means no limit.
+  #[serde(default)]
+  pub max_uses: I64,
+  /// Optional. The key can only onboard a single Server.
+  #[serde(default)]
+  pub single_use: bool,
 }
 
 /// The response for [CreateServerOnboardingKey]
@@ -79,6 +86,10 @@ pub struct UpdateOnboardingKey {
   pub copy_server: Option<String>,
   /// Update whether to create Builder
   pub create_builder: Option<bool>,
+  /// Update the max uses, 0 for no limit.
+  pub max_uses: Option<I64>,
+  /// Update whether the key is single use.
+  pub single_use: Option<bool>,
 }
 
 impl UpdateOnboardingKey {
@@ -89,6 +100,8 @@ impl UpdateOnboardingKey {
       && self.tags.is_none()
       && self.copy_server.is_none()
       && self.create_builder.is_none()
+      && self.max_uses.is_none()
+      && self.single_use.is_none()
   }
 }
 
@@ -0,0 +1,108 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{
+  NoData, ResourceTarget, organization::Organization,
+};
+
+use super::KomodoWriteRequest;
+
+/// **Admin only.** Create an Organization. Response: [Organization]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(Organization)]
+#[error(serror::Error)]
+pub struct CreateOrganization {
+  /// The name to assign to the new Organization
+  pub name: String,
+  /// A description for the Organization
+  #[serde(default)]
+  pub description: String,
+}
+
+//
+
+/// **Admin only.** Update an Organization's name and description.
+/// Response: [Organization]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(Organization)]
+#[error(serror::Error)]
+pub struct UpdateOrganization {
+  /// The id of the Organization
+  pub id: String,
+  /// The new name for the Organization
+  pub name: String,
+  /// The new description for the Organization
+  #[serde(default)]
+  pub description: String,
+}
+
+//
+
+/// **Admin only.** Delete an Organization.
+/// Fails if any users, resources, tags, or variables
+/// still belong to the Organization. Response: [Organization]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(Organization)]
+#[error(serror::Error)]
+pub struct DeleteOrganization {
+  /// The id of the Organization
+  pub id: String,
+}
+
+//
+
+/// **Admin only.** Assign a user to an Organization. Response: [NoData]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(SetUserOrganizationResponse)]
+#[error(serror::Error)]
+pub struct SetUserOrganization {
+  /// The id or username of the user
+  pub user: String,
+  /// The name or id of the Organization.
+  /// Pass empty string to remove the user from their Organization.
+  #[serde(default)]
+  pub organization: String,
+}
+
+#[typeshare]
+pub type SetUserOrganizationResponse = NoData;
+
+//
+
+/// **Admin only.** Assign a resource to an Organization. Response: [NoData]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(SetResourceOrganizationResponse)]
+#[error(serror::Error)]
+pub struct SetResourceOrganization {
+  /// The resource to assign
+  pub target: ResourceTarget,
+  /// The name or id of the Organization.
+  /// Pass empty string to remove the resource from its Organization.
+  #[serde(default)]
+  pub organization: String,
+}
+
+#[typeshare]
+pub type SetResourceOrganizationResponse = NoData;
@@ -0,0 +1,56 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{
+  pending_server::PendingServer, server::Server,
+};
+
+use super::KomodoWriteRequest;
+
+//
+
+/// **Admin only.** Approve a Server waiting for onboarding approval.
+/// Creates the Server using the settings of the onboarding key
+/// it used, and the Periphery agent can connect on its next attempt.
+/// Response: [Server].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(ApprovePendingServerResponse)]
+#[error(serror::Error)]
+pub struct ApprovePendingServer {
+  /// The id of the PendingServer.
+  pub id: String,
+  /// Optional. Create the Server with a different name
+  /// than the one Periphery requested.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+}
+
+#[typeshare]
+pub type ApprovePendingServerResponse = Server;
+
+//
+
+/// **Admin only.** Reject a Server waiting for onboarding approval.
+/// The Periphery agent will be added back to the queue
+/// if it retries onboarding.
+/// Response: [PendingServer].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(DeletePendingServerResponse)]
+#[error(serror::Error)]
+pub struct DeletePendingServer {
+  /// The id of the PendingServer.
+  pub id: String,
+}
+
+#[typeshare]
+pub type DeletePendingServerResponse = PendingServer;
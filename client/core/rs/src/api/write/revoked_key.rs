@@ -0,0 +1,55 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::revoked_key::RevokedKey;
+
+use super::KomodoWriteRequest;
+
+//
+
+/// **Admin only.** Revoke a Periphery public key.
+/// Core will refuse connections authenticating with the key,
+/// even if a Server still expects it, and Servers currently
+/// connected with the key are disconnected.
+/// Response: [RevokedKey].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(RevokePublicKeyResponse)]
+#[error(serror::Error)]
+pub struct RevokePublicKey {
+  /// The Periphery public key to revoke.
+  /// Accepts the base64 der or pem form.
+  pub public_key: String,
+  /// Optional. Why the key is revoked.
+  #[serde(default)]
+  pub reason: String,
+}
+
+#[typeshare]
+pub type RevokePublicKeyResponse = RevokedKey;
+
+//
+
+/// **Admin only.** Remove a public key from the revoked keys,
+/// allowing Periphery to authenticate with it again.
+/// Response: The removed [RevokedKey].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(UnrevokePublicKeyResponse)]
+#[error(serror::Error)]
+pub struct UnrevokePublicKey {
+  /// The revoked Periphery public key.
+  /// Accepts the base64 der or pem form.
+  pub public_key: String,
+}
+
+#[typeshare]
+pub type UnrevokePublicKeyResponse = RevokedKey;
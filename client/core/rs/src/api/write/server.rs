@@ -4,8 +4,9 @@ use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
 use crate::entities::{
-  NoData,
+  NoData, U64,
   server::{_PartialServerConfig, Server},
+  server_schedule::{ServerSchedule, ServerScheduleStatus},
   update::Update,
 };
 
@@ -171,6 +172,17 @@ pub struct CreateTerminal {
   /// Default: `Never`
   #[serde(default)]
   pub recreate: TerminalRecreateMode,
+  /// Whether to record the terminal output,
+  /// see [ListTerminalRecordings][crate::api::read::server::ListTerminalRecordings].
+  /// Terminals are always recorded when the
+  /// `record_terminals` Periphery config is enabled.
+  /// Default: The `record_terminals` Periphery config
+  #[serde(default)]
+  pub record: Option<bool>,
+  /// The scrollback history size in KiB.
+  /// Default: The `terminal_history_kb` Periphery config
+  #[serde(default)]
+  pub history_kb: Option<U64>,
 }
 
 fn default_command() -> String {
@@ -213,6 +225,42 @@ pub struct DeleteAllTerminals {
 
 //
 
+/// Clear the scrollback history of a terminal on the server,
+/// so it isn't replayed when connecting.
+/// Response: [NoData]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(NoData)]
+#[error(serror::Error)]
+pub struct ClearTerminalHistory {
+  /// Server Id or name
+  pub server: String,
+  /// The name of the terminal on the server.
+  pub terminal: String,
+}
+
+//
+
+/// Force disconnect an active terminal session. Admin only.
+/// The terminal itself is kept on the server.
+/// Response: [NoData]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(NoData)]
+#[error(serror::Error)]
+pub struct DisconnectTerminalSession {
+  /// The session id, from [ListActiveTerminalSessions][crate::api::read::ListActiveTerminalSessions].
+  pub id: String,
+}
+
+//
+
 /// Updates the Server with an explicit Public Key.
 /// Response: [Update]
 #[typeshare]
@@ -231,6 +279,23 @@ pub struct UpdateServerPublicKey {
 
 //
 
+/// Accepts the Server `attempted_tls_pin`, adding it to the
+/// `tls_pins` used with `Pinned` tls verification.
+/// Response: [Update]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(Update)]
+#[error(serror::Error)]
+pub struct AcceptServerTlsPin {
+  /// Server Id or name
+  pub server: String,
+}
+
+//
+
 /// Rotates the private / public keys for the server.
 /// Response: [Update]
 #[typeshare]
@@ -244,3 +309,43 @@ pub struct RotateServerKeys {
   /// Server Id or name
   pub server: String,
 }
+
+//
+
+/// Create or replace a command the server runs on a cron schedule,
+/// whether or not Core is connected. The runs are recorded
+/// as Updates on the server.
+/// Schedules set in the Periphery config file can't be replaced.
+/// Response: [ServerScheduleStatus]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(ServerScheduleStatus)]
+#[error(serror::Error)]
+pub struct UpsertServerSchedule {
+  /// Server Id or name
+  pub server: String,
+  /// The schedule, replacing any with the same name.
+  pub schedule: ServerSchedule,
+}
+
+//
+
+/// Delete a schedule added with [UpsertServerSchedule].
+/// Schedules set in the Periphery config file can't be deleted.
+/// Response: [NoData]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(NoData)]
+#[error(serror::Error)]
+pub struct DeleteServerSchedule {
+  /// Server Id or name
+  pub server: String,
+  /// The schedule name
+  pub name: String,
+}
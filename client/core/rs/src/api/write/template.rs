@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use derive_empty_traits::EmptyTraits;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{
+  stack::Stack,
+  template::{Template, TemplateParameter},
+};
+
+use super::KomodoWriteRequest;
+
+/// **Admin only.** Create a template. Response: [Template].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(CreateTemplateResponse)]
+#[error(serror::Error)]
+pub struct CreateTemplate {
+  /// The name of the template to create.
+  pub name: String,
+  /// The description of the template. default: "".
+  #[serde(default)]
+  pub description: String,
+  /// The compose file contents. default: "".
+  #[serde(default)]
+  pub file_contents: String,
+  /// The environment (.env) contents. default: "".
+  #[serde(default)]
+  pub environment: String,
+  /// The parameters to prompt for at instantiation.
+  #[serde(default)]
+  pub parameters: Vec<TemplateParameter>,
+}
+
+#[typeshare]
+pub type CreateTemplateResponse = Template;
+
+//
+
+/// **Admin only.** Update a template.
+/// Only the fields which are passed will be updated.
+/// Response: [Template].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(UpdateTemplateResponse)]
+#[error(serror::Error)]
+pub struct UpdateTemplate {
+  /// The name of the template to update.
+  pub name: String,
+  /// Update the description.
+  pub description: Option<String>,
+  /// Update the compose file contents.
+  pub file_contents: Option<String>,
+  /// Update the environment (.env) contents.
+  pub environment: Option<String>,
+  /// Update the parameters.
+  pub parameters: Option<Vec<TemplateParameter>>,
+}
+
+#[typeshare]
+pub type UpdateTemplateResponse = Template;
+
+//
+
+/// **Admin only.** Delete a template. Response: [Template].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(DeleteTemplateResponse)]
+#[error(serror::Error)]
+pub struct DeleteTemplate {
+  pub name: String,
+}
+
+#[typeshare]
+pub type DeleteTemplateResponse = Template;
+
+//
+
+/// Create a new Stack on the given Server from a template,
+/// replacing the template placeholders with the given parameters.
+/// Requires the same permissions as [CreateStack](super::CreateStack),
+/// as well as attach permission on the Server.
+/// Response: [Stack].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(InstantiateTemplateResponse)]
+#[error(serror::Error)]
+pub struct InstantiateTemplate {
+  /// The name of the template to instantiate.
+  pub template: String,
+  /// The name given to the newly created Stack.
+  pub name: String,
+  /// Id or name of the Server to create the Stack on.
+  pub server: String,
+  /// The parameter values, keyed by parameter name.
+  /// Parameters not given fall back to their default.
+  #[serde(default)]
+  pub parameters: HashMap<String, String>,
+}
+
+#[typeshare]
+pub type InstantiateTemplateResponse = Stack;
@@ -3,7 +3,7 @@ use resolver_api::Resolve;
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::entities::user_group::UserGroup;
+use crate::entities::user_group::{UserGroup, UserGroupQuotas};
 
 use super::KomodoWriteRequest;
 
@@ -121,3 +121,21 @@ pub struct SetEveryoneUserGroup {
   /// Whether this user group applies to everyone.
   pub everyone: bool,
 }
+
+//
+
+/// **Admin only.** Set the quotas applied to members of a user group.
+/// Response: [UserGroup]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Resolve, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(UserGroup)]
+#[error(serror::Error)]
+pub struct UpdateUserGroupQuotas {
+  /// Id or name.
+  pub user_group: String,
+  /// The quotas to set. Use 0 for no limit.
+  pub quotas: UserGroupQuotas,
+}
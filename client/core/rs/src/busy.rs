@@ -20,6 +20,8 @@ impl Busy for ServerActionState {
       || self.pausing_containers
       || self.unpausing_containers
       || self.stopping_containers
+      || self.committing_container
+      || self.transferring_image
   }
 }
 
@@ -33,6 +35,7 @@ impl Busy for DeploymentActionState {
       || self.stopping
       || self.destroying
       || self.renaming
+      || self.rolling_back
   }
 }
 
@@ -45,6 +48,7 @@ impl Busy for StackActionState {
       || self.unpausing
       || self.stopping
       || self.destroying
+      || self.scaling
   }
 }
 
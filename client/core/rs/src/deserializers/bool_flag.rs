@@ -0,0 +1,74 @@
+use serde::{Deserializer, de::Visitor};
+
+/// Accepts `true` / `false` as well as `1` / `0`,
+/// eg. for flags set as `KOMODO_TRANSPORT_TRACE=1`.
+pub fn option_bool_flag_deserializer<'de, D>(
+  deserializer: D,
+) -> Result<Option<bool>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  deserializer.deserialize_any(OptionBoolFlagVisitor)
+}
+
+struct OptionBoolFlagVisitor;
+
+impl Visitor<'_> for OptionBoolFlagVisitor {
+  type Value = Option<bool>;
+
+  fn expecting(
+    &self,
+    formatter: &mut std::fmt::Formatter,
+  ) -> std::fmt::Result {
+    write!(formatter, "null or bool or 1 / 0")
+  }
+
+  fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+  where
+    E: serde::de::Error,
+  {
+    match v.trim().to_lowercase().as_str() {
+      "" => Ok(None),
+      "true" | "1" => Ok(Some(true)),
+      "false" | "0" => Ok(Some(false)),
+      other => Err(E::custom(format!(
+        "Invalid bool flag '{other}', expected true / false or 1 / 0"
+      ))),
+    }
+  }
+
+  fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+  where
+    E: serde::de::Error,
+  {
+    Ok(Some(v))
+  }
+
+  fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+  where
+    E: serde::de::Error,
+  {
+    Ok(Some(v != 0))
+  }
+
+  fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+  where
+    E: serde::de::Error,
+  {
+    Ok(Some(v != 0))
+  }
+
+  fn visit_none<E>(self) -> Result<Self::Value, E>
+  where
+    E: serde::de::Error,
+  {
+    Ok(None)
+  }
+
+  fn visit_unit<E>(self) -> Result<Self::Value, E>
+  where
+    E: serde::de::Error,
+  {
+    Ok(None)
+  }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use derive_variants::EnumVariants;
 use serde::{Deserialize, Serialize};
@@ -9,7 +9,7 @@ use crate::entities::{I64, MongoId};
 
 use super::{
   _Serror, ResourceTarget, ResourceTargetVariant, Version,
-  deployment::DeploymentState, stack::StackState,
+  deployment::DeploymentState, stack::StackState, stats::GpuVendor,
 };
 
 /// Representation of an alert in the system.
@@ -137,6 +137,76 @@ pub enum AlertData {
     total_gb: f64,
   },
 
+  /// A server disk reports SMART health problems.
+  ServerDiskHealth {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The device path of the disk, ie `/dev/sda`
+    device: String,
+    /// The disk model name
+    model: String,
+    /// The disk serial number
+    serial: String,
+    /// The problems reported by the disk
+    problems: Vec<String>,
+  },
+
+  /// A ZFS pool on a server is not ONLINE.
+  ServerZfsPool {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The pool name
+    pool: String,
+    /// The pool health, ie `DEGRADED`
+    health: String,
+    /// The status of the last or running scrub / resilver
+    scan: Option<String>,
+  },
+
+  /// A server has high GPU memory usage or temperature.
+  ServerGpu {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The GPU vendor
+    vendor: GpuVendor,
+    /// The index of the GPU
+    index: u32,
+    /// The GPU model name
+    gpu: String,
+    /// The used GPU memory in GB
+    mem_used_gb: f64,
+    /// The total GPU memory in GB
+    mem_total_gb: f64,
+    /// The GPU temperature in degrees Celsius, if reported
+    temperature: Option<f64>,
+  },
+
+  /// A server has pending OS security updates.
+  ServerOsSecurityUpdates {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The package manager reporting the updates, ie `apt`
+    package_manager: String,
+    /// The packages with a pending security update
+    packages: Vec<String>,
+  },
+
   /// A server has a version mismatch with the core.
   ServerVersionMismatch {
     /// The id of the server
@@ -195,6 +265,20 @@ pub enum AlertData {
     image: String,
   },
 
+  /// A Deployment custom health probe is failing.
+  DeploymentUnhealthy {
+    /// The id of the deployment
+    id: String,
+    /// The name of the deployment
+    name: String,
+    /// The server id of server that the deployment is on
+    server_id: String,
+    /// The server name
+    server_name: String,
+    /// The reason the probe failed
+    message: String,
+  },
+
   /// A stack's state has changed unexpectedly.
   StackStateChange {
     /// The id of the stack
@@ -310,6 +394,24 @@ pub enum AlertData {
     #[serde(default)]
     details: String,
   },
+
+  /// An external alert received from Prometheus Alertmanager.
+  /// Produced by the `/listener/alertmanager` webhook receiver.
+  Alertmanager {
+    /// The Alertmanager fingerprint, used to resolve the alert.
+    fingerprint: String,
+    /// The `alertname` label.
+    name: String,
+    /// The `summary` or `description` annotation. May be empty string.
+    #[serde(default)]
+    summary: String,
+    /// The Prometheus expression URL. May be empty string.
+    #[serde(default)]
+    url: String,
+    /// All the labels on the alert.
+    #[serde(default)]
+    labels: HashMap<String, String>,
+  },
 }
 
 impl Default for AlertData {
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+use typeshare::typeshare;
+
+use super::{I64, MongoId};
+
+/// A record of a Core <-> Periphery connection login attempt,
+/// kept for auditing accepted and rejected keys.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+pub struct AuthEvent {
+  /// The Mongo ID of the AuthEvent.
+  /// This field is de/serialized from/to JSON as
+  /// `{ "_id": { "$oid": "..." }, ...(rest of serialized AuthEvent) }`
+  #[serde(
+    default,
+    rename = "_id",
+    skip_serializing_if = "String::is_empty",
+    with = "bson::serde_helpers::hex_string_as_object_id"
+  )]
+  pub id: MongoId,
+
+  /// Unix timestamp in milliseconds of the login attempt.
+  #[cfg_attr(feature = "mongo", index)]
+  pub ts: I64,
+
+  /// The id of the Server, or empty if no Server matched,
+  /// eg. during onboarding.
+  #[serde(default)]
+  #[cfg_attr(feature = "mongo", index)]
+  pub server_id: String,
+
+  /// The Server id or name Periphery connected with.
+  /// Empty for Core -> Periphery connections.
+  #[serde(default)]
+  pub server_query: String,
+
+  /// The connection direction.
+  #[serde(default)]
+  pub direction: AuthEventDirection,
+
+  /// The login flow used.
+  #[serde(default)]
+  pub flow: AuthEventFlow,
+
+  /// The public key Periphery presented in the handshake,
+  /// if the handshake got that far.
+  #[serde(default)]
+  #[cfg_attr(feature = "mongo", index)]
+  pub public_key: String,
+
+  /// Whether the login succeeded.
+  #[serde(default)]
+  #[cfg_attr(feature = "mongo", index)]
+  pub success: bool,
+
+  /// The reason the login failed.
+  #[serde(default)]
+  pub error: String,
+
+  /// The remote IP for Periphery -> Core connections,
+  /// or the Periphery address for Core -> Periphery connections.
+  #[serde(default)]
+  pub remote: String,
+}
+
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Display,
+  EnumString,
+)]
+pub enum AuthEventDirection {
+  /// Periphery connected to Core, Core runs the `ServerLoginFlow`.
+  #[default]
+  PeripheryToCore,
+  /// Core connected to Periphery, Core runs the `ClientLoginFlow`.
+  CoreToPeriphery,
+}
+
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Display,
+  EnumString,
+)]
+pub enum AuthEventFlow {
+  /// The public key handshake.
+  #[default]
+  Standard,
+  /// A new Server logging in with an onboarding key.
+  Onboarding,
+  /// The legacy v1 passkey login.
+  V1Passkey,
+}
@@ -414,6 +414,33 @@ pub struct BuildConfig {
   #[builder(default)]
   pub use_buildx: bool,
 
+  /// Build a multi platform image for these platforms,
+  /// ie `linux/amd64`, `linux/arm64`. Always uses buildx.
+  /// Empty builds for the platform of the builder host.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub platforms: Vec<String>,
+
+  /// Import the build cache from this registry image,
+  /// ie `ghcr.io/org/app:buildcache`, so builds on any server
+  /// reuse the cached layers. Full buildx `--cache-from`
+  /// specs (containing `=`) are passed through as is.
+  #[serde(default)]
+  #[builder(default)]
+  pub cache_from: String,
+
+  /// Export the build cache to this registry image, with all
+  /// intermediate layers (`mode=max`). Always uses buildx.
+  /// Full buildx `--cache-to` specs (containing `=`)
+  /// are passed through as is.
+  #[serde(default)]
+  #[builder(default)]
+  pub cache_to: String,
+
   /// Any extra docker cli arguments to be included in the build command
   #[serde(default, deserialize_with = "string_list_deserializer")]
   #[partial_attr(serde(
@@ -539,6 +566,9 @@ impl Default for BuildConfig {
       labels: Default::default(),
       extra_args: Default::default(),
       use_buildx: Default::default(),
+      platforms: Default::default(),
+      cache_from: Default::default(),
+      cache_to: Default::default(),
       image_registry: Default::default(),
       webhook_enabled: default_webhook_enabled(),
       webhook_secret: Default::default(),
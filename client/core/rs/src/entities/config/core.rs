@@ -14,7 +14,9 @@ use std::{collections::HashMap, path::PathBuf};
 use serde::Deserialize;
 
 use crate::{
-  deserializers::option_string_list_deserializer,
+  deserializers::{
+    option_bool_flag_deserializer, option_string_list_deserializer,
+  },
   entities::{
     Timelength,
     config::DatabaseConfig,
@@ -22,7 +24,10 @@ use crate::{
   },
 };
 
-use super::{DockerRegistry, GitProvider, empty_or_redacted};
+use super::{
+  DockerRegistry, GitProvider, TransportCompression,
+  TransportEncoding, empty_or_redacted,
+};
 
 /// # Komodo Core Environment Variables
 ///
@@ -81,10 +86,78 @@ pub struct Env {
   /// Override `periphery_public_keys`
   #[serde(alias = "komodo_periphery_public_key")]
   pub komodo_periphery_public_keys: Option<Vec<String>>,
+  /// Override `key_rotation_interval_days`
+  pub komodo_key_rotation_interval_days: Option<u64>,
   /// Override `passkey`
   pub komodo_passkey: Option<String>,
   /// Override `passkey` from file
   pub komodo_passkey_file: Option<PathBuf>,
+  /// Override `transport_psk`
+  pub komodo_transport_psk: Option<String>,
+  /// Override `transport_psk` from file
+  pub komodo_transport_psk_file: Option<PathBuf>,
+  /// Override `auth_timeout_ms`
+  pub komodo_auth_timeout_ms: Option<u64>,
+  /// Override `connection_retry_seconds`
+  pub komodo_connection_retry_seconds: Option<u64>,
+  /// Override `connection_poll_times`
+  pub komodo_connection_poll_times: Option<usize>,
+  /// Override `connection_poll_interval_ms`
+  pub komodo_connection_poll_interval_ms: Option<u64>,
+  /// Override `shutdown_retry_after_seconds`
+  pub komodo_shutdown_retry_after_seconds: Option<u64>,
+  /// Override `connection_shards`
+  pub komodo_connection_shards: Option<usize>,
+  /// Override `connection_shard_max_logins`
+  pub komodo_connection_shard_max_logins: Option<usize>,
+  /// Override `connection_accept_rate`
+  pub komodo_connection_accept_rate: Option<u32>,
+  /// Override `connection_accept_burst`
+  pub komodo_connection_accept_burst: Option<u32>,
+  /// Override `transport_compression`
+  pub komodo_transport_compression: Option<TransportCompression>,
+  /// Override `transport_encoding`
+  pub komodo_transport_encoding: Option<TransportEncoding>,
+  /// Override `transport_max_chunk_bytes`
+  pub komodo_transport_max_chunk_bytes: Option<usize>,
+  /// Override `transport_max_message_bytes`
+  pub komodo_transport_max_message_bytes: Option<usize>,
+  /// Override `transport_max_body_bytes`
+  pub komodo_transport_max_body_bytes: Option<usize>,
+  /// Override `transport_max_body_depth`
+  pub komodo_transport_max_body_depth: Option<usize>,
+  /// Override `transport_max_bytes_per_sec`
+  pub komodo_transport_max_bytes_per_sec: Option<u64>,
+  /// Override `transport_heartbeat_interval_ms`
+  pub komodo_transport_heartbeat_interval_ms: Option<u64>,
+  /// Override `transport_heartbeat_max_missed`
+  pub komodo_transport_heartbeat_max_missed: Option<u64>,
+  /// Override `transport_trace`. Also accepts 1 / 0.
+  #[serde(
+    default,
+    deserialize_with = "option_bool_flag_deserializer"
+  )]
+  pub komodo_transport_trace: Option<bool>,
+  /// Override `transport_spillover_directory`
+  pub komodo_transport_spillover_directory: Option<PathBuf>,
+  /// Override `transport_spillover_max_bytes`
+  pub komodo_transport_spillover_max_bytes: Option<u64>,
+  /// Override `inspect_image_cache_ms`
+  pub komodo_inspect_image_cache_ms: Option<i64>,
+  /// Override `image_history_cache_ms`
+  pub komodo_image_history_cache_ms: Option<i64>,
+  /// Override `system_processes_cache_ms`
+  pub komodo_system_processes_cache_ms: Option<i64>,
+  /// Override `onboarding_validator_url`
+  pub komodo_onboarding_validator_url: Option<String>,
+  /// Override `onboarding_validator_secret`
+  pub komodo_onboarding_validator_secret: Option<String>,
+  /// Override `onboarding_validator_secret` with file
+  pub komodo_onboarding_validator_secret_file: Option<PathBuf>,
+  /// Override `onboarding_validator_procedure`
+  pub komodo_onboarding_validator_procedure: Option<String>,
+  /// Override `onboarding_require_approval`
+  pub komodo_onboarding_require_approval: Option<bool>,
   /// Override `timezone`
   #[serde(alias = "tz")]
   pub komodo_timezone: Option<String>,
@@ -115,6 +188,10 @@ pub struct Env {
   pub komodo_keep_stats_for_days: Option<u64>,
   /// Override `keep_alerts_for_days`
   pub komodo_keep_alerts_for_days: Option<u64>,
+  /// Override `tag_usage_report_interval`
+  pub komodo_tag_usage_report_interval: Option<Timelength>,
+  /// Override `tag_usage_report_alerters`
+  pub komodo_tag_usage_report_alerters: Option<Vec<String>>,
   /// Override `webhook_secret`
   pub komodo_webhook_secret: Option<String>,
   /// Override `webhook_secret` with file
@@ -159,6 +236,10 @@ pub struct Env {
   pub komodo_disable_non_admin_create: Option<bool>,
   /// Override `disable_websocket_reconnect`
   pub komodo_disable_websocket_reconnect: Option<bool>,
+  /// Override `max_terminal_sessions`
+  pub komodo_max_terminal_sessions: Option<usize>,
+  /// Override `max_terminal_sessions_per_user`
+  pub komodo_max_terminal_sessions_per_user: Option<usize>,
   /// Override `disable_init_resources`
   pub komodo_disable_init_resources: Option<bool>,
   /// Override `enable_fancy_toml`
@@ -321,6 +402,10 @@ pub struct CoreConfig {
   /// If a file is specified and does not exist, will try to generate one at the path
   /// and use it going forward.
   ///
+  /// To use a Curve25519 key held in a PKCS#11 module, use a PKCS#11 URI:
+  /// `private_key = "pkcs11:token=komodo;object=core?module-path=/path/to/module.so&pin-source=/path/to/pin"`.
+  /// Hardware keys are not rotated.
+  ///
   /// Note. The private key used can be overridden for individual Servers / Builders.
   ///
   /// Default: file:/config/keys/core.key
@@ -346,11 +431,191 @@ pub struct CoreConfig {
   )]
   pub periphery_public_keys: Option<Vec<String>>,
 
+  /// Automatically rotate keys every this number of days,
+  /// or 0 to disable. Servers with `auto_rotate_keys` have their
+  /// Periphery keys rotated once their last rotation is older than
+  /// the interval, and the Core keys are rotated on the same interval
+  /// if `private_key` points to a file. Unreachable Servers are
+  /// skipped and alerted on.
+  /// Default: 0
+  #[serde(default)]
+  pub key_rotation_interval_days: u64,
+
   /// Deprecated. Legacy v1 compatibility.
   /// Users should upgrade to private / public key authentication.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub passkey: Option<String>,
 
+  /// Optional pre-shared key mixed into the Noise handshake
+  /// (`Noise_XXpsk3`), so connecting requires both a trusted key pair
+  /// and this shared secret. Must match the Periphery `transport_psk`,
+  /// mismatches fail the login.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub transport_psk: Option<String>,
+
+  /// Timeout in milliseconds waiting for each message
+  /// of the Periphery connection login handshake.
+  /// Increase for high latency links, eg. satellite.
+  /// Default: 2000
+  #[serde(default = "default_auth_timeout_ms")]
+  pub auth_timeout_ms: u64,
+
+  /// Seconds to wait before retrying a failed
+  /// Core -> Periphery connection or login.
+  /// The delay doubles (with jitter) on repeated failures, up to 60s.
+  /// Default: 5
+  #[serde(default = "default_connection_retry_seconds")]
+  pub connection_retry_seconds: u64,
+
+  /// The number of times to check whether a Server is connected
+  /// before failing a request to it. Default: 3
+  #[serde(default = "default_connection_poll_times")]
+  pub connection_poll_times: usize,
+
+  /// Milliseconds between the Server connected checks. Default: 500
+  #[serde(default = "default_connection_poll_interval_ms")]
+  pub connection_poll_interval_ms: u64,
+
+  /// On shutdown, Core tells connected Peripheries to wait
+  /// this many seconds before reconnecting. Default: 10
+  #[serde(default = "default_shutdown_retry_after_seconds")]
+  pub shutdown_retry_after_seconds: u64,
+
+  /// The number of shards Periphery connections are split
+  /// between by Server id. Each shard has its own login pool
+  /// and load metrics. Default: 8
+  #[serde(default = "default_connection_shards")]
+  pub connection_shards: usize,
+
+  /// The maximum concurrent Periphery logins per shard.
+  /// Inbound connections over the limit are rejected
+  /// with 503, and Periphery retries. Default: 32
+  #[serde(default = "default_connection_shard_max_logins")]
+  pub connection_shard_max_logins: usize,
+
+  /// The maximum inbound Periphery connections accepted
+  /// per second, to smooth out reconnect storms after Core restarts.
+  /// Connections over the limit are rejected with 429,
+  /// and Periphery retries. 0 disables the limit. Default: 0
+  #[serde(default)]
+  pub connection_accept_rate: u32,
+
+  /// The number of inbound connections which can be accepted
+  /// at once before `connection_accept_rate` applies.
+  /// Default: 50
+  #[serde(default = "default_connection_accept_burst")]
+  pub connection_accept_burst: u32,
+
+  /// Compress large Periphery responses on the connection.
+  /// Only used with Peripheries which also enable it,
+  /// older Peripheries connect uncompressed. Default: none
+  #[serde(default)]
+  pub transport_compression: TransportCompression,
+
+  /// Encoding of the Periphery request / response bodies.
+  /// Only used with Peripheries which also enable it,
+  /// older Peripheries keep using JSON. Default: json
+  #[serde(default)]
+  pub transport_encoding: TransportEncoding,
+
+  /// Messages to Periphery larger than this are split
+  /// into multiple websocket frames, to stay under reverse proxy
+  /// buffer limits. 0 disables chunking. Default: 1048576 (1 MiB)
+  #[serde(default = "default_transport_max_chunk_bytes")]
+  pub transport_max_chunk_bytes: usize,
+
+  /// Received messages larger than this, after reassembling
+  /// chunks and decompressing, are rejected, so a misbehaving
+  /// peer can't exhaust memory. Default: 536870912 (512 MiB)
+  #[serde(default = "default_transport_max_message_bytes")]
+  pub transport_max_message_bytes: usize,
+
+  /// Received JSON / CBOR bodies larger than this are rejected
+  /// before they are parsed. Default: 67108864 (64 MiB)
+  #[serde(default = "default_transport_max_body_bytes")]
+  pub transport_max_body_bytes: usize,
+
+  /// Received JSON / CBOR bodies nested deeper than this
+  /// are rejected before they are parsed. Default: 128
+  #[serde(default = "default_transport_max_body_depth")]
+  pub transport_max_body_depth: usize,
+
+  /// The default limit on bytes per second sent and received
+  /// on each Periphery connection, so one Server streaming huge logs
+  /// can't starve the others. Servers can override this with
+  /// `transport_max_bytes_per_sec`. 0 disables the limit. Default: 0
+  #[serde(default)]
+  pub transport_max_bytes_per_sec: u64,
+
+  /// How often to Ping Periphery on connections with keepalive,
+  /// in milliseconds. Default: 10000
+  #[serde(default = "default_transport_heartbeat_interval_ms")]
+  pub transport_heartbeat_interval_ms: u64,
+
+  /// Connections which miss this many heartbeats in a row
+  /// are marked disconnected and reconnected, failing their
+  /// in flight requests, even if the socket was never closed.
+  /// Default: 3
+  #[serde(default = "default_transport_heartbeat_max_missed")]
+  pub transport_heartbeat_max_missed: u64,
+
+  /// Record the header of every transport message sent and received
+  /// on Periphery connections to an in memory ring buffer,
+  /// see ListTransportTrace. For debugging only. Default: false
+  #[serde(default)]
+  pub transport_trace: bool,
+
+  /// Persist the requests queued for a Server to this directory
+  /// when its connection goes down, and replay them once it reconnects,
+  /// including after a Core restart. Sensitive requests and requests
+  /// no longer waiting on a response aren't persisted. Disabled if not set.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub transport_spillover_directory: Option<PathBuf>,
+
+  /// The max bytes of requests persisted per Server.
+  /// Requests past the limit are dropped. Default: 67108864 (64 MiB)
+  #[serde(default = "default_transport_spillover_max_bytes")]
+  pub transport_spillover_max_bytes: u64,
+
+  /// Milliseconds to cache InspectDockerImage results
+  /// per Server and image. 0 disables the cache. Default: 30000
+  #[serde(default = "default_inspect_image_cache_ms")]
+  pub inspect_image_cache_ms: i64,
+
+  /// Milliseconds to cache ListDockerImageHistory results
+  /// per Server and image. 0 disables the cache. Default: 60000
+  #[serde(default = "default_image_history_cache_ms")]
+  pub image_history_cache_ms: i64,
+
+  /// Milliseconds to cache ListSystemProcesses results
+  /// per Server. 0 disables the cache. Default: 15000
+  #[serde(default = "default_system_processes_cache_ms")]
+  pub system_processes_cache_ms: i64,
+
+  /// Optional external endpoint to approve Servers during onboarding,
+  /// eg. to check the Server against a device identity / CMDB system.
+  /// Core will POST the Server name, public key, host,
+  /// and onboarding key name as JSON, and only accept
+  /// the Server if the endpoint responds with a 2xx status.
+  #[serde(default)]
+  pub onboarding_validator_url: String,
+
+  /// Sent to the `onboarding_validator_url` as bearer token
+  /// in the 'Authorization' header, if provided.
+  #[serde(default)]
+  pub onboarding_validator_secret: String,
+
+  /// Optional Procedure (name or id) to run during onboarding.
+  /// The Server is only accepted if the Procedure runs successfully.
+  #[serde(default)]
+  pub onboarding_validator_procedure: String,
+
+  /// Queue onboarding Servers as [PendingServer][crate::entities::pending_server::PendingServer]s
+  /// instead of creating them automatically. An admin must
+  /// approve each one before the Server is created and can connect.
+  #[serde(default)]
+  pub onboarding_require_approval: bool,
+
   /// A TZ Identifier. If not provided, will use Core local timezone.
   /// https://en.wikipedia.org/wiki/List_of_tz_database_time_zones.
   /// This will be populated by TZ env variable in addition to KOMODO_TIMEZONE.
@@ -369,6 +634,16 @@ pub struct CoreConfig {
   #[serde(default)]
   pub disable_websocket_reconnect: bool,
 
+  /// The maximum number of concurrent terminal sessions
+  /// across all users. 0 means unlimited.
+  #[serde(default)]
+  pub max_terminal_sessions: usize,
+
+  /// The maximum number of concurrent terminal sessions
+  /// for a single user. 0 means unlimited.
+  #[serde(default)]
+  pub max_terminal_sessions_per_user: usize,
+
   /// Disable init system resource creation on fresh Komodo launch.
   /// These include the Backup Core Database and Global Auto Update procedures.
   #[serde(default)]
@@ -576,6 +851,21 @@ pub struct CoreConfig {
   #[serde(default = "default_monitoring_interval")]
   pub monitoring_interval: Timelength,
 
+  // =====================
+  // = Tag Usage Reports =
+  // =====================
+  /// Interval at which to send the tag usage reports,
+  /// which cover the usage over the previous interval.
+  /// Default: `1-wk`
+  #[serde(default = "default_tag_usage_report_interval")]
+  pub tag_usage_report_interval: Timelength,
+
+  /// Alerters (name or id) to send the periodic tag usage reports to,
+  /// as a `Custom` alert. If empty, no reports are sent.
+  /// Default: empty
+  #[serde(default)]
+  pub tag_usage_report_alerters: Vec<String>,
+
   // ===================
   // = Cloud Providers =
   // ===================
@@ -677,6 +967,78 @@ fn default_frontend_path() -> String {
   "/app/frontend".to_string()
 }
 
+fn default_auth_timeout_ms() -> u64 {
+  2_000
+}
+
+fn default_connection_retry_seconds() -> u64 {
+  5
+}
+
+fn default_connection_poll_times() -> usize {
+  3
+}
+
+fn default_connection_poll_interval_ms() -> u64 {
+  500
+}
+
+fn default_shutdown_retry_after_seconds() -> u64 {
+  10
+}
+
+fn default_connection_shards() -> usize {
+  8
+}
+
+fn default_connection_shard_max_logins() -> usize {
+  32
+}
+
+fn default_connection_accept_burst() -> u32 {
+  50
+}
+
+fn default_transport_max_chunk_bytes() -> usize {
+  1024 * 1024
+}
+
+fn default_transport_max_message_bytes() -> usize {
+  512 * 1024 * 1024
+}
+
+fn default_transport_max_body_bytes() -> usize {
+  64 * 1024 * 1024
+}
+
+fn default_transport_max_body_depth() -> usize {
+  128
+}
+
+fn default_transport_heartbeat_interval_ms() -> u64 {
+  10_000
+}
+
+fn default_transport_heartbeat_max_missed() -> u64 {
+  3
+}
+
+fn default_transport_spillover_max_bytes() -> u64 {
+  64 * 1024 * 1024
+}
+
+fn default_inspect_image_cache_ms() -> i64 {
+  30_000
+}
+
+fn default_image_history_cache_ms() -> i64 {
+  60_000
+}
+
+fn default_system_processes_cache_ms() -> i64 {
+  15_000
+}
+
 fn default_jwt_ttl() -> Timelength {
   Timelength::OneDay
 }
@@ -709,6 +1071,10 @@ fn default_monitoring_interval() -> Timelength {
   Timelength::FifteenSeconds
 }
 
+fn default_tag_usage_report_interval() -> Timelength {
+  Timelength::OneWeek
+}
+
 fn default_ssl_key_file() -> PathBuf {
   "/config/ssl/key.pem".parse().unwrap()
 }
@@ -727,11 +1093,50 @@ impl Default for CoreConfig {
       internet_interface: Default::default(),
       private_key: Default::default(),
       periphery_public_keys: Default::default(),
+      key_rotation_interval_days: Default::default(),
       passkey: Default::default(),
+      transport_psk: Default::default(),
+      auth_timeout_ms: default_auth_timeout_ms(),
+      connection_retry_seconds: default_connection_retry_seconds(),
+      connection_poll_times: default_connection_poll_times(),
+      connection_poll_interval_ms:
+        default_connection_poll_interval_ms(),
+      shutdown_retry_after_seconds:
+        default_shutdown_retry_after_seconds(),
+      connection_shards: default_connection_shards(),
+      connection_shard_max_logins:
+        default_connection_shard_max_logins(),
+      connection_accept_rate: Default::default(),
+      connection_accept_burst: default_connection_accept_burst(),
+      transport_compression: Default::default(),
+      transport_encoding: Default::default(),
+      transport_max_chunk_bytes: default_transport_max_chunk_bytes(),
+      transport_max_message_bytes:
+        default_transport_max_message_bytes(),
+      transport_max_body_bytes: default_transport_max_body_bytes(),
+      transport_max_body_depth: default_transport_max_body_depth(),
+      transport_max_bytes_per_sec: Default::default(),
+      transport_heartbeat_interval_ms:
+        default_transport_heartbeat_interval_ms(),
+      transport_heartbeat_max_missed:
+        default_transport_heartbeat_max_missed(),
+      transport_trace: Default::default(),
+      transport_spillover_directory: Default::default(),
+      transport_spillover_max_bytes:
+        default_transport_spillover_max_bytes(),
+      inspect_image_cache_ms: default_inspect_image_cache_ms(),
+      image_history_cache_ms: default_image_history_cache_ms(),
+      system_processes_cache_ms: default_system_processes_cache_ms(),
+      onboarding_validator_url: Default::default(),
+      onboarding_validator_secret: Default::default(),
+      onboarding_validator_procedure: Default::default(),
+      onboarding_require_approval: Default::default(),
       timezone: Default::default(),
       ui_write_disabled: Default::default(),
       disable_confirm_dialog: Default::default(),
       disable_websocket_reconnect: Default::default(),
+      max_terminal_sessions: Default::default(),
+      max_terminal_sessions_per_user: Default::default(),
       disable_init_resources: Default::default(),
       enable_fancy_toml: Default::default(),
       first_server_address: Default::default(),
@@ -766,6 +1171,8 @@ impl Default for CoreConfig {
       keep_alerts_for_days: default_prune_days(),
       resource_poll_interval: default_poll_interval(),
       monitoring_interval: default_monitoring_interval(),
+      tag_usage_report_interval: default_tag_usage_report_interval(),
+      tag_usage_report_alerters: Default::default(),
       aws: Default::default(),
       git_providers: Default::default(),
       docker_registries: Default::default(),
@@ -794,7 +1201,48 @@ impl CoreConfig {
         empty_or_redacted(&self.private_key)
       },
       periphery_public_keys: config.periphery_public_keys,
+      key_rotation_interval_days: config.key_rotation_interval_days,
       passkey: config.passkey.as_deref().map(empty_or_redacted),
+      transport_psk: config
+        .transport_psk
+        .as_deref()
+        .map(empty_or_redacted),
+      auth_timeout_ms: config.auth_timeout_ms,
+      connection_retry_seconds: config.connection_retry_seconds,
+      connection_poll_times: config.connection_poll_times,
+      connection_poll_interval_ms: config.connection_poll_interval_ms,
+      shutdown_retry_after_seconds: config
+        .shutdown_retry_after_seconds,
+      connection_shards: config.connection_shards,
+      connection_shard_max_logins: config.connection_shard_max_logins,
+      connection_accept_rate: config.connection_accept_rate,
+      connection_accept_burst: config.connection_accept_burst,
+      transport_compression: config.transport_compression,
+      transport_encoding: config.transport_encoding,
+      transport_max_chunk_bytes: config.transport_max_chunk_bytes,
+      transport_max_message_bytes: config.transport_max_message_bytes,
+      transport_max_body_bytes: config.transport_max_body_bytes,
+      transport_max_body_depth: config.transport_max_body_depth,
+      transport_max_bytes_per_sec: config.transport_max_bytes_per_sec,
+      transport_heartbeat_interval_ms: config
+        .transport_heartbeat_interval_ms,
+      transport_heartbeat_max_missed: config
+        .transport_heartbeat_max_missed,
+      transport_trace: config.transport_trace,
+      transport_spillover_directory: config
+        .transport_spillover_directory,
+      transport_spillover_max_bytes: config
+        .transport_spillover_max_bytes,
+      inspect_image_cache_ms: config.inspect_image_cache_ms,
+      image_history_cache_ms: config.image_history_cache_ms,
+      system_processes_cache_ms: config.system_processes_cache_ms,
+      onboarding_validator_url: config.onboarding_validator_url,
+      onboarding_validator_secret: empty_or_redacted(
+        &config.onboarding_validator_secret,
+      ),
+      onboarding_validator_procedure: config
+        .onboarding_validator_procedure,
+      onboarding_require_approval: config.onboarding_require_approval,
       timezone: config.timezone,
       first_server_address: config.first_server_address,
       first_server_name: config.first_server_name,
@@ -807,6 +1255,8 @@ impl CoreConfig {
       internet_interface: config.internet_interface,
       resource_poll_interval: config.resource_poll_interval,
       monitoring_interval: config.monitoring_interval,
+      tag_usage_report_interval: config.tag_usage_report_interval,
+      tag_usage_report_alerters: config.tag_usage_report_alerters,
       keep_stats_for_days: config.keep_stats_for_days,
       keep_alerts_for_days: config.keep_alerts_for_days,
       logging: config.logging,
@@ -817,6 +1267,9 @@ impl CoreConfig {
       ui_write_disabled: config.ui_write_disabled,
       disable_confirm_dialog: config.disable_confirm_dialog,
       disable_websocket_reconnect: config.disable_websocket_reconnect,
+      max_terminal_sessions: config.max_terminal_sessions,
+      max_terminal_sessions_per_user: config
+        .max_terminal_sessions_per_user,
       disable_init_resources: config.disable_init_resources,
       enable_fancy_toml: config.enable_fancy_toml,
       enable_new_users: config.enable_new_users,
@@ -224,6 +224,71 @@ pub enum KeyOutputFormat {
   JsonPretty,
 }
 
+/// Compression applied to large messages
+/// on the Core <-> Periphery connection.
+/// Only used if both sides of the connection enable it.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Hash,
+  Serialize,
+  Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportCompression {
+  /// Send messages uncompressed.
+  #[default]
+  None,
+  /// Compress large messages with zstd.
+  Zstd,
+}
+
+impl TransportCompression {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      TransportCompression::None => "none",
+      TransportCompression::Zstd => "zstd",
+    }
+  }
+}
+
+/// Encoding of the Request / Response bodies
+/// on the Core <-> Periphery connection.
+/// Only used if both sides of the connection enable it.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Hash,
+  Serialize,
+  Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportEncoding {
+  /// Encode bodies as JSON.
+  #[default]
+  Json,
+  /// Encode bodies as CBOR, which is smaller
+  /// and faster to serialize than JSON.
+  Cbor,
+}
+
+impl TransportEncoding {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      TransportEncoding::Json => "json",
+      TransportEncoding::Cbor => "cbor",
+    }
+  }
+}
+
 #[derive(Serialize)]
 pub struct KeyPair<'a> {
   pub private_key: &'a str,
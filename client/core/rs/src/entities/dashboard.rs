@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::{I64, MongoId, ResourceTarget};
+
+/// A custom operational view made up of widgets,
+/// persisted on Core so it follows the user between browsers.
+///
+/// Dashboards are owned by the user who created them.
+/// If marked as shared, all users are able to view (but not edit) the Dashboard.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+pub struct Dashboard {
+  /// The Mongo ID of the Dashboard.
+  /// This field is de/serialized from/to JSON as
+  /// `{ "_id": { "$oid": "..." }, ...(rest of serialized Dashboard) }`
+  #[serde(
+    default,
+    rename = "_id",
+    skip_serializing_if = "String::is_empty",
+    with = "bson::serde_helpers::hex_string_as_object_id"
+  )]
+  pub id: MongoId,
+
+  /// The id of the User who owns the Dashboard.
+  #[cfg_attr(feature = "mongo", index)]
+  pub owner: String,
+
+  /// A name for the Dashboard.
+  pub name: String,
+
+  /// A description for the Dashboard.
+  #[serde(default)]
+  pub description: String,
+
+  /// Whether all users can view the Dashboard.
+  #[cfg_attr(feature = "mongo", index)]
+  #[serde(default)]
+  pub shared: bool,
+
+  /// The widgets in the Dashboard layout.
+  #[serde(default)]
+  pub widgets: Vec<DashboardWidget>,
+
+  /// Unix time (ms) when the Dashboard was created.
+  #[serde(default)]
+  pub created_at: I64,
+
+  /// Unix time (ms) when the Dashboard was last updated.
+  #[serde(default)]
+  pub updated_at: I64,
+}
+
+/// A single widget placed in the [Dashboard] grid layout.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DashboardWidget {
+  /// Identifies the widget within the Dashboard layout.
+  pub id: String,
+  /// An optional title to display above the widget.
+  #[serde(default)]
+  pub title: String,
+  /// The grid column of the widget's top left corner.
+  #[serde(default)]
+  pub x: I64,
+  /// The grid row of the widget's top left corner.
+  #[serde(default)]
+  pub y: I64,
+  /// The width of the widget in grid columns.
+  #[serde(default = "default_widget_size")]
+  pub w: I64,
+  /// The height of the widget in grid rows.
+  #[serde(default = "default_widget_size")]
+  pub h: I64,
+  /// What the widget displays.
+  pub config: DashboardWidgetConfig,
+}
+
+fn default_widget_size() -> I64 {
+  1
+}
+
+/// What a [DashboardWidget] displays.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "params")]
+pub enum DashboardWidgetConfig {
+  /// Current / historical system stats of a Server.
+  ServerStats {
+    /// Id or name of the Server.
+    server: String,
+  },
+  /// The states of the Stacks matching the tag filter.
+  StackStates {
+    /// Only include Stacks with all of these tags. Empty includes all Stacks.
+    #[serde(default)]
+    tags: Vec<String>,
+  },
+  /// The most recent Updates, optionally for a specific target.
+  RecentUpdates {
+    /// Only include Updates on this target.
+    #[serde(default)]
+    target: Option<ResourceTarget>,
+  },
+  /// The open Alerts, optionally for a specific target.
+  AlertFeed {
+    /// Only include Alerts on this target.
+    #[serde(default)]
+    target: Option<ResourceTarget>,
+  },
+}
+
+impl Default for DashboardWidgetConfig {
+  fn default() -> Self {
+    Self::RecentUpdates { target: None }
+  }
+}
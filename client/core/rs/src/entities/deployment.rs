@@ -21,7 +21,7 @@ use crate::{
 };
 
 use super::{
-  TerminationSignal, Version,
+  I64, TerminationSignal, Version,
   docker::container::ContainerStateStatusEnum,
   resource::{Resource, ResourceListItem, ResourceQuery},
 };
@@ -48,6 +48,8 @@ pub struct DeploymentListItemInfo {
   pub server_id: String,
   /// An attached Komodo Build, if it exists.
   pub build_id: Option<String>,
+  /// The latest result of the custom health probe, if configured.
+  pub health: Option<DeploymentHealth>,
 }
 
 #[typeshare(serialized_as = "Partial<DeploymentConfig>")]
@@ -64,6 +66,14 @@ pub struct DeploymentConfig {
   #[builder(default)]
   pub server_id: String,
 
+  /// Inherit from this template Deployment (id or name).
+  /// At deploy time, the template environment and labels are merged
+  /// underneath this Deployment's, which override by key,
+  /// and the template restart mode is used if this Deployment's is `no`.
+  #[serde(default)]
+  #[builder(default)]
+  pub inherit: String,
+
   /// The image which the deployment deploys.
   /// Can either be a user inputted image, or a Komodo Build.
   #[serde(default)]
@@ -203,6 +213,46 @@ pub struct DeploymentConfig {
   ))]
   #[builder(default)]
   pub labels: String,
+
+  /// An optional health probe evaluated by Periphery after deploy
+  /// and on every monitoring interval, independent of any
+  /// Docker HEALTHCHECK on the image.
+  #[serde(default)]
+  #[builder(default)]
+  pub health_probe: DeploymentHealthProbe,
+
+  /// Seconds before a single probe attempt counts as failed.
+  #[serde(default = "default_health_probe_timeout")]
+  #[builder(default = "default_health_probe_timeout()")]
+  #[partial_default(default_health_probe_timeout())]
+  pub health_probe_timeout: i32,
+
+  /// Seconds after deploy to retry the probe before
+  /// the deploy reports the Deployment as unhealthy.
+  #[serde(default = "default_health_probe_start_period")]
+  #[builder(default = "default_health_probe_start_period()")]
+  #[partial_default(default_health_probe_start_period())]
+  pub health_probe_start_period: i32,
+
+  /// Wait for the container HEALTHCHECK to report healthy after deploy.
+  /// The deploy fails if the container is unhealthy, exits,
+  /// or isn't healthy within `healthcheck_timeout`.
+  /// Containers without a HEALTHCHECK only need to keep running.
+  #[serde(default)]
+  #[builder(default)]
+  pub wait_for_healthcheck: bool,
+
+  /// Seconds to wait for the container to become healthy.
+  #[serde(default = "default_healthcheck_timeout")]
+  #[builder(default = "default_healthcheck_timeout()")]
+  #[partial_default(default_healthcheck_timeout())]
+  pub healthcheck_timeout: i32,
+
+  /// If the deploy fails, or the container fails its healthcheck,
+  /// re-deploy the image of the previous successful deploy.
+  #[serde(default)]
+  #[builder(default)]
+  pub rollback_on_failure: bool,
 }
 
 impl DeploymentConfig {
@@ -228,10 +278,23 @@ fn default_network() -> String {
   String::from("host")
 }
 
+fn default_health_probe_timeout() -> i32 {
+  5
+}
+
+fn default_health_probe_start_period() -> i32 {
+  30
+}
+
+fn default_healthcheck_timeout() -> i32 {
+  120
+}
+
 impl Default for DeploymentConfig {
   fn default() -> Self {
     Self {
       server_id: Default::default(),
+      inherit: Default::default(),
       send_alerts: default_send_alerts(),
       links: Default::default(),
       image: Default::default(),
@@ -251,10 +314,67 @@ impl Default for DeploymentConfig {
       restart: Default::default(),
       command: Default::default(),
       extra_args: Default::default(),
+      health_probe: Default::default(),
+      health_probe_timeout: default_health_probe_timeout(),
+      health_probe_start_period: default_health_probe_start_period(),
+      wait_for_healthcheck: Default::default(),
+      healthcheck_timeout: default_healthcheck_timeout(),
+      rollback_on_failure: Default::default(),
     }
   }
 }
 
+/// A custom health probe for a Deployment.
+/// Http and Tcp probes are made from the Periphery host.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+#[serde(tag = "type", content = "params")]
+pub enum DeploymentHealthProbe {
+  /// No custom health probe.
+  #[default]
+  None,
+  /// GET the url. Healthy on any 2xx or 3xx status.
+  Http {
+    /// Eg. `http://localhost:8080/health`
+    #[serde(default)]
+    url: String,
+  },
+  /// Healthy if a TCP connection to the address can be opened.
+  Tcp {
+    /// Eg. `localhost:5432`
+    #[serde(default)]
+    address: String,
+  },
+  /// Run the command in the container with `docker exec`.
+  /// Healthy on exit code 0.
+  Command {
+    #[serde(default)]
+    command: String,
+  },
+}
+
+impl DeploymentHealthProbe {
+  pub fn is_none(&self) -> bool {
+    matches!(self, DeploymentHealthProbe::None)
+  }
+}
+
+/// The result of a Deployment health probe.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct DeploymentHealth {
+  /// Whether the probe succeeded.
+  pub healthy: bool,
+  /// The probe output, or the reason it failed.
+  pub message: String,
+  /// Unix timestamp in milliseconds of the probe.
+  pub ts: I64,
+}
+
 #[typeshare]
 #[derive(
   Serialize, Deserialize, Debug, Clone, PartialEq, EnumVariants,
@@ -462,6 +582,7 @@ pub struct DeploymentActionState {
   pub stopping: bool,
   pub destroying: bool,
   pub renaming: bool,
+  pub rolling_back: bool,
 }
 
 #[typeshare]
@@ -7,7 +7,10 @@ use typeshare::typeshare;
 
 use crate::entities::{I64, Usize};
 
-use super::{ContainerConfig, GraphDriverData, PortBinding};
+use super::{
+  ContainerConfig, GraphDriverData, PortBinding,
+  stats::ContainerIoRates,
+};
 
 /// Container summary returned by container list apis.
 #[typeshare]
@@ -1183,4 +1186,7 @@ pub struct ContainerStats {
   pub block_io: String,
   #[serde(alias = "PIDs")]
   pub pids: String,
+  /// Network and block IO rates, computed by the Periphery container stats poller.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub io_rates: Option<ContainerIoRates>,
 }
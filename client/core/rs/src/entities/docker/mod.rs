@@ -9,6 +9,7 @@ pub mod container;
 pub mod image;
 pub mod network;
 pub mod stats;
+pub mod swarm;
 pub mod volume;
 
 /// PortBinding represents a binding between a host IP address and a host port.
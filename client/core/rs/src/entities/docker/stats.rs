@@ -62,6 +62,29 @@ pub struct FullContainerStats {
   #[serde(rename = "networks")]
   #[serde(skip_serializing_if = "Option::is_none")]
   pub networks: Option<HashMap<String, ContainerNetworkStats>>,
+
+  /// Network and block IO rates, computed by the Periphery container stats poller.
+  /// This field is omitted if the poller has not yet taken two samples of the container.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub io_rates: Option<ContainerIoRates>,
+}
+
+/// Network and block IO rates of a container, in bytes per second.
+/// Computed by the Periphery container stats poller
+/// from the difference between consecutive samples.
+#[typeshare]
+#[derive(
+  Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct ContainerIoRates {
+  /// Network receive rate in bytes per second
+  pub net_rx: f64,
+  /// Network transmit rate in bytes per second
+  pub net_tx: f64,
+  /// Block device read rate in bytes per second
+  pub block_read: f64,
+  /// Block device write rate in bytes per second
+  pub block_write: f64,
 }
 
 /// PidsStats contains Linux-specific stats of a container's process-IDs (PIDs).  This type is Linux-specific and omitted for Windows containers.
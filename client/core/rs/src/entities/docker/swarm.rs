@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::JsonValue;
+
+/// A service in the swarm, from `docker service ls`.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct SwarmServiceListItem {
+  #[serde(default, alias = "ID")]
+  pub id: String,
+  /// The service name, `{stack}_{service}` for stack services.
+  #[serde(default, alias = "Name")]
+  pub name: String,
+  /// `replicated` or `global`
+  #[serde(default, alias = "Mode")]
+  pub mode: String,
+  /// The running / desired replicas, ie `2/3`.
+  #[serde(default, alias = "Replicas")]
+  pub replicas: String,
+  #[serde(default, alias = "Image")]
+  pub image: String,
+  /// The published ports, ie `*:8080->80/tcp`.
+  #[serde(default, alias = "Ports")]
+  pub ports: String,
+}
+
+/// A node in the swarm, from `docker node ls`.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct SwarmNodeListItem {
+  #[serde(default, alias = "ID")]
+  pub id: String,
+  #[serde(default, alias = "Hostname")]
+  pub hostname: String,
+  /// `Ready` or `Down`
+  #[serde(default, alias = "Status")]
+  pub status: String,
+  /// `Active`, `Pause` or `Drain`
+  #[serde(default, alias = "Availability")]
+  pub availability: String,
+  /// `Leader` or `Reachable` for managers, empty for workers.
+  #[serde(default, alias = "ManagerStatus")]
+  pub manager_status: String,
+  #[serde(default, alias = "EngineVersion")]
+  pub engine_version: String,
+  /// Whether this is the node Periphery is connected to.
+  #[serde(default, alias = "Self")]
+  pub is_self: bool,
+}
+
+/// The output of `docker service inspect`.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct SwarmService {
+  #[serde(rename = "ID")]
+  pub id: String,
+
+  #[serde(rename = "CreatedAt")]
+  pub created_at: Option<String>,
+
+  #[serde(rename = "UpdatedAt")]
+  pub updated_at: Option<String>,
+
+  /// The service spec, ie the image, replicas,
+  /// networks and update config.
+  #[serde(rename = "Spec")]
+  pub spec: JsonValue,
+
+  /// The published ports and virtual ips.
+  #[serde(rename = "Endpoint")]
+  pub endpoint: Option<JsonValue>,
+
+  /// The state of the latest rolling update, if any.
+  #[serde(rename = "UpdateStatus")]
+  pub update_status: Option<JsonValue>,
+}
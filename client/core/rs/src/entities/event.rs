@@ -0,0 +1,100 @@
+use derive_variants::EnumVariants;
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display};
+use typeshare::typeshare;
+
+use super::{
+  I64, ResourceTarget, docker::container::ContainerStateStatusEnum,
+  server::HostPowerAction, update::UpdateListItem,
+};
+
+/// An event emitted on the Core event bus.
+///
+/// Core subsystems subscribe to these internally, and they can
+/// be received externally over the `/ws/events` websocket.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CoreEvent {
+  /// Unix timestamp in milliseconds the event was emitted.
+  pub ts: I64,
+  /// The target the event is about.
+  pub target: ResourceTarget,
+  /// The kind of event, with any kind specific data.
+  pub data: CoreEventData,
+}
+
+/// The variants of event data.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, EnumVariants)]
+#[variant_derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Hash,
+  Display,
+  AsRefStr
+)]
+#[serde(tag = "type", content = "data")]
+pub enum CoreEventData {
+  /// A resource was created.
+  ResourceCreated {
+    /// The name of the resource
+    name: String,
+  },
+
+  /// A resource config was updated.
+  ResourceUpdated {
+    /// The name of the resource
+    name: String,
+  },
+
+  /// A resource was renamed.
+  ResourceRenamed {
+    /// The previous name of the resource
+    from: String,
+    /// The new name of the resource
+    to: String,
+  },
+
+  /// A resource was deleted.
+  ResourceDeleted {
+    /// The name of the resource
+    name: String,
+  },
+
+  /// A Server's Periphery connection was established.
+  ServerConnected {},
+
+  /// A Server's Periphery connection was lost.
+  ServerDisconnected {},
+
+  /// A Server's Periphery is about to reboot or power off the host,
+  /// after stopping its containers.
+  ServerDraining {
+    /// The power action being run
+    action: HostPowerAction,
+  },
+
+  /// A container on a Server changed state,
+  /// as pushed by Periphery.
+  ContainerStateChanged {
+    /// The name of the container
+    container: String,
+    /// The new state of the container
+    state: ContainerStateStatusEnum,
+  },
+
+  /// A container on a Server was killed for running out of memory,
+  /// as pushed by Periphery.
+  ContainerOomKilled {
+    /// The name of the container
+    container: String,
+  },
+
+  /// An Update was finalized.
+  UpdateFinalized(UpdateListItem),
+}
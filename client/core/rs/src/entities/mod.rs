@@ -30,20 +30,32 @@ pub mod alert;
 pub mod alerter;
 /// Subtypes of [ApiKey][api_key::ApiKey].
 pub mod api_key;
+/// Subtypes of [AuthEvent][auth_event::AuthEvent].
+pub mod auth_event;
 /// Subtypes of [Build][build::Build].
 pub mod build;
 /// Subtypes of [Builder][builder::Builder].
 pub mod builder;
 /// [core config][config::core] and [periphery config][config::periphery]
 pub mod config;
+/// Subtypes of [Dashboard][dashboard::Dashboard].
+pub mod dashboard;
 /// Subtypes of [Deployment][deployment::Deployment].
 pub mod deployment;
 /// Networks, Images, Containers.
 pub mod docker;
+/// Subtypes of [CoreEvent][event::CoreEvent].
+pub mod event;
 /// Subtypes of [LogConfig][logger::LogConfig].
 pub mod logger;
+/// Subtypes of [Notification][notification::Notification].
+pub mod notification;
 /// Subtypes of [CreationKey][creation_key::CreationKey]
 pub mod onboarding_key;
+/// Subtypes of [Organization][organization::Organization].
+pub mod organization;
+/// Subtypes of [PendingServer][pending_server::PendingServer].
+pub mod pending_server;
 /// Subtypes of [Permission][permission::Permission].
 pub mod permission;
 /// Subtypes of [Procedure][procedure::Procedure].
@@ -54,18 +66,26 @@ pub mod provider;
 pub mod repo;
 /// Subtypes of [Resource][resource::Resource].
 pub mod resource;
+/// Subtypes of [RevokedKey][revoked_key::RevokedKey].
+pub mod revoked_key;
 /// Subtypes of [Schedule][schedule::Schedule]
 pub mod schedule;
 /// Subtypes of [Server][server::Server].
 pub mod server;
+/// Subtypes of [ServerSchedule][server_schedule::ServerSchedule].
+pub mod server_schedule;
 /// Subtypes of [Stack][stack::Stack]
 pub mod stack;
 /// Subtypes for server stats reporting.
 pub mod stats;
 /// Subtypes of [ResourceSync][sync::ResourceSync]
 pub mod sync;
+/// Subtypes for managing the systemd units on a server.
+pub mod systemd;
 /// Subtypes of [Tag][tag::Tag].
 pub mod tag;
+/// Subtypes of [Template][template::Template].
+pub mod template;
 /// Subtypes of [ResourcesToml][toml::ResourcesToml].
 pub mod toml;
 /// Subtypes of [Update][update::Update].
@@ -419,6 +439,27 @@ fn default_enabled() -> bool {
   true
 }
 
+/// Global read-only mode of Core. While enabled,
+/// all write and execute requests are rejected with 503,
+/// while reads and monitoring continue as normal.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, PartialEq,
+)]
+pub struct ReadOnlyMode {
+  /// Whether Core is in read-only mode.
+  pub enabled: bool,
+  /// The reason for read-only mode, shown to users when requests are rejected.
+  #[serde(default)]
+  pub reason: String,
+  /// Unix timestamp in milliseconds when the mode was last changed.
+  #[serde(default)]
+  pub since: I64,
+  /// The username of the admin who last changed the mode.
+  #[serde(default)]
+  pub set_by: String,
+}
+
 #[typeshare]
 #[derive(
   Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
@@ -1078,6 +1119,7 @@ pub enum Operation {
   UnpauseContainer,
   StopContainer,
   DestroyContainer,
+  CommitContainer,
   StartAllContainers,
   RestartAllContainers,
   PauseAllContainers,
@@ -1089,11 +1131,22 @@ pub enum Operation {
   PruneNetworks,
   DeleteImage,
   PruneImages,
+  TransferImage,
   DeleteVolume,
   PruneVolumes,
   PruneDockerBuilders,
   PruneBuildx,
+  PruneBuildCache,
   PruneSystem,
+  RunServerExtension,
+  KillSystemProcess,
+  SetSystemProcessPriority,
+  StartSystemdUnit,
+  StopSystemdUnit,
+  RestartSystemdUnit,
+  RebootServer,
+  ShutdownServer,
+  RunServerSchedule,
 
   // stack
   CreateStack,
@@ -1111,6 +1164,9 @@ pub enum Operation {
   StopStack,
   DestroyStack,
   RunStackService,
+  PromoteStack,
+  ScaleStack,
+  MigrateStack,
 
   // stack (service)
   DeployStackService,
@@ -1135,6 +1191,7 @@ pub enum Operation {
   UnpauseDeployment,
   StopDeployment,
   DestroyDeployment,
+  RollbackDeployment,
 
   // build
   CreateBuild,
@@ -1204,6 +1261,12 @@ pub enum Operation {
   UpdateVariableValue,
   DeleteVariable,
 
+  // template
+  CreateTemplate,
+  UpdateTemplate,
+  DeleteTemplate,
+  InstantiateTemplate,
+
   // git provider
   CreateGitProviderAccount,
   UpdateGitProviderAccount,
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+use typeshare::typeshare;
+
+use super::{I64, MongoId, ResourceTarget};
+
+/// A message in a user's notification inbox.
+///
+/// Notifications are delivered to the UI over the update websocket
+/// as they are created, and are kept until acknowledged (and beyond)
+/// so important events aren't missed while away.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+#[cfg_attr(feature = "mongo", doc_index({ "user": 1, "read": 1 }))]
+pub struct Notification {
+  /// The Mongo ID of the Notification.
+  /// This field is de/serialized from/to JSON as
+  /// `{ "_id": { "$oid": "..." }, ...(rest of serialized Notification) }`
+  #[serde(
+    default,
+    rename = "_id",
+    skip_serializing_if = "String::is_empty",
+    with = "bson::serde_helpers::hex_string_as_object_id"
+  )]
+  pub id: MongoId,
+
+  /// The id of the User receiving the Notification.
+  #[cfg_attr(feature = "mongo", index)]
+  pub user: String,
+
+  /// Unix timestamp in milliseconds the Notification was created.
+  #[cfg_attr(feature = "mongo", index)]
+  pub ts: I64,
+
+  /// The kind of Notification.
+  #[serde(default)]
+  pub kind: NotificationKind,
+
+  /// A short title for the Notification.
+  pub title: String,
+
+  /// The Notification message.
+  #[serde(default)]
+  pub body: String,
+
+  /// The target the Notification is about.
+  #[serde(default)]
+  pub target: ResourceTarget,
+
+  /// The id of the Alert / User / etc. which produced the Notification.
+  #[serde(default)]
+  pub source: String,
+
+  /// Whether the Notification has been read (acknowledged).
+  #[serde(default)]
+  pub read: bool,
+
+  /// Unix timestamp in milliseconds the Notification was read.
+  #[serde(default)]
+  pub read_ts: I64,
+}
+
+/// The kind of [Notification].
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Display,
+  EnumString,
+)]
+pub enum NotificationKind {
+  /// An Alert was opened or resolved on a target the user can see.
+  #[default]
+  Alert,
+  /// Another user mentioned the user.
+  Mention,
+  /// Something is waiting on the user's approval.
+  ApprovalRequest,
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use super::I64;
+use super::{I64, komodo_timestamp};
 
 /// An public key used to authenticate new Periphery -> Core connections
 /// to join Komodo as a newly created Server.
@@ -54,4 +54,50 @@ pub struct OnboardingKey {
   /// Also create a Builder for the Server.
   #[serde(default)]
   pub create_builder: bool,
+
+  /// The max number of Servers the key can onboard, or 0 for no limit.
+  #[serde(default)]
+  pub max_uses: I64,
+
+  /// The key can only onboard a single Server.
+  #[serde(default)]
+  pub single_use: bool,
+
+  /// The number of Servers onboarded (or currently onboarding) with the key.
+  #[serde(default)]
+  pub uses: I64,
+
+  /// The remaining uses if the key is limited. Only set in API responses.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub remaining_uses: Option<I64>,
+}
+
+impl OnboardingKey {
+  /// Whether the key is enabled, not expired, and has uses remaining.
+  pub fn is_valid(&self) -> bool {
+    self.enabled
+      && (self.expires == 0 || self.expires > komodo_timestamp())
+      && self.compute_remaining_uses() != Some(0)
+  }
+
+  /// The max number of uses, if limited.
+  pub fn use_limit(&self) -> Option<I64> {
+    if self.single_use {
+      Some(1)
+    } else if self.max_uses > 0 {
+      Some(self.max_uses)
+    } else {
+      None
+    }
+  }
+
+  pub fn compute_remaining_uses(&self) -> Option<I64> {
+    self.use_limit().map(|limit| (limit - self.uses).max(0))
+  }
+
+  /// Sets `remaining_uses` for API responses.
+  pub fn with_remaining_uses(mut self) -> OnboardingKey {
+    self.remaining_uses = self.compute_remaining_uses();
+    self
+  }
 }
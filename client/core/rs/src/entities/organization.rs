@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::{I64, MongoId};
+
+/// An isolated tenant on Core.
+///
+/// Resources, users, tags, and variables may belong to an Organization.
+/// Non admin users assigned to an Organization are only able to see
+/// and interact with the resources, tags, and variables in the same Organization.
+/// Users without an Organization keep the unscoped behavior.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+pub struct Organization {
+  /// The Mongo ID of the Organization.
+  /// This field is de/serialized from/to JSON as
+  /// `{ "_id": { "$oid": "..." }, ...(rest of serialized Organization) }`
+  #[serde(
+    default,
+    rename = "_id",
+    skip_serializing_if = "String::is_empty",
+    with = "bson::serde_helpers::hex_string_as_object_id"
+  )]
+  pub id: MongoId,
+
+  /// A name for the Organization
+  #[cfg_attr(feature = "mongo", unique_index)]
+  pub name: String,
+
+  /// A description for the Organization
+  #[serde(default)]
+  pub description: String,
+
+  /// Unix time (ms) when the Organization was created
+  #[serde(default)]
+  pub created_at: I64,
+
+  /// Unix time (ms) when the Organization was last updated
+  #[serde(default)]
+  pub updated_at: I64,
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::{I64, MongoId};
+
+/// A Server which passed the onboarding login while
+/// `onboarding_require_approval` is enabled, and is
+/// waiting for an admin to approve its creation.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+pub struct PendingServer {
+  /// The Mongo ID of the PendingServer.
+  /// This field is de/serialized from/to JSON as
+  /// `{ "_id": { "$oid": "..." }, ...(rest of serialized PendingServer) }`
+  #[serde(
+    default,
+    rename = "_id",
+    skip_serializing_if = "String::is_empty",
+    with = "bson::serde_helpers::hex_string_as_object_id"
+  )]
+  pub id: MongoId,
+
+  /// The name the Server will be created with.
+  #[serde(default)]
+  pub name: String,
+
+  /// The Periphery public key the Server will expect.
+  #[cfg_attr(feature = "mongo", unique_index)]
+  pub public_key: String,
+
+  /// The source IP of the latest onboarding attempt.
+  #[serde(default)]
+  pub remote: String,
+
+  /// The host Periphery reported, if any.
+  #[serde(default)]
+  pub host: String,
+
+  /// The public key of the onboarding key used.
+  /// Its settings (template, tags, builder) are
+  /// applied when the Server is approved.
+  #[serde(default)]
+  pub onboarding_key: String,
+
+  /// The name of the onboarding key used.
+  #[serde(default)]
+  pub onboarding_key_name: String,
+
+  /// Timestamp of the first onboarding attempt.
+  #[serde(default)]
+  #[cfg_attr(feature = "mongo", index)]
+  pub requested_at: I64,
+
+  /// Timestamp of the latest onboarding attempt.
+  #[serde(default)]
+  pub last_attempt_at: I64,
+}
@@ -157,6 +157,11 @@ pub enum SpecificPermission {
   /// On **Server**
   ///   - Read all the processes on the host
   Processes,
+  /// On **Server**
+  ///   - Attach to the main process of any container on the server
+  /// On **Stack / Deployment**
+  ///   - Attach to the main process of the associated containers
+  ContainerAttach,
 }
 
 impl SpecificPermission {
@@ -243,6 +248,11 @@ impl PermissionLevel {
   pub fn processes(self) -> PermissionLevelAndSpecifics {
     self.specific(SpecificPermission::Processes)
   }
+
+  /// Operation requires ContainerAttach permission
+  pub fn container_attach(self) -> PermissionLevelAndSpecifics {
+    self.specific(SpecificPermission::ContainerAttach)
+  }
 }
 
 impl PermissionLevelAndSpecifics {
@@ -331,4 +341,9 @@ impl PermissionLevelAndSpecifics {
   pub fn processes(self) -> PermissionLevelAndSpecifics {
     self.specific(SpecificPermission::Processes)
   }
+
+  /// Operation requires ContainerAttach permission
+  pub fn container_attach(self) -> PermissionLevelAndSpecifics {
+    self.specific(SpecificPermission::ContainerAttach)
+  }
 }
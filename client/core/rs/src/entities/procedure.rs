@@ -6,7 +6,12 @@ use serde::{Deserialize, Serialize};
 use strum::Display;
 use typeshare::typeshare;
 
-use crate::api::execute::Execution;
+use crate::{
+  api::execute::Execution,
+  deserializers::{
+    option_string_list_deserializer, string_list_deserializer,
+  },
+};
 
 use super::{
   I64, ScheduleFormat,
@@ -117,6 +122,23 @@ pub struct ProcedureConfig {
   #[builder(default)]
   pub schedule_timezone: String,
 
+  /// Dates on which scheduled runs are skipped, eg. holidays,
+  /// in the schedule timezone. Either `YYYY-MM-DD` for a single date,
+  /// or `MM-DD` to skip the date every year.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub schedule_exceptions: Vec<String>,
+
+  /// What to do when a scheduled run comes up
+  /// while the procedure is still running.
+  #[serde(default)]
+  #[builder(default)]
+  pub schedule_overlap: ScheduleOverlapPolicy,
+
   /// Whether to send alerts when the schedule was run.
   #[serde(default = "default_schedule_alert")]
   #[builder(default = "default_schedule_alert()")]
@@ -172,6 +194,8 @@ impl Default for ProcedureConfig {
       schedule: Default::default(),
       schedule_enabled: default_schedule_enabled(),
       schedule_timezone: Default::default(),
+      schedule_exceptions: Default::default(),
+      schedule_overlap: Default::default(),
       schedule_alert: default_schedule_alert(),
       failure_alert: default_failure_alert(),
       webhook_enabled: default_webhook_enabled(),
@@ -180,6 +204,23 @@ impl Default for ProcedureConfig {
   }
 }
 
+/// What to do when a scheduled run comes up
+/// while the previous run is still in progress.
+#[typeshare]
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+pub enum ScheduleOverlapPolicy {
+  /// Skip the scheduled run.
+  #[default]
+  Skip,
+  /// Run once the previous run finishes.
+  /// At most one run is queued at a time.
+  Queue,
+  /// Cancel the previous run, then run.
+  CancelPrevious,
+}
+
 /// A single stage of a procedure. Runs a list of executions in parallel.
 #[typeshare]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -49,6 +49,12 @@ pub struct Resource<Config: Default, Info: Default = ()> {
   #[builder(default)]
   pub tags: Vec<String>,
 
+  /// The id of the Organization the resource belongs to.
+  /// Empty means the resource is not scoped to an Organization.
+  #[serde(default)]
+  #[builder(default)]
+  pub organization: String,
+
   /// Resource-specific information (not user configurable).
   #[serde(default)]
   #[builder(setter(skip))]
@@ -79,6 +85,7 @@ impl<C: Default, I: Default> Default for Resource<C, I> {
       description: String::new(),
       template: Default::default(),
       tags: Vec::new(),
+      organization: String::new(),
       info: I::default(),
       config: C::default(),
       base_permission: Default::default(),
@@ -198,3 +205,47 @@ impl<T: AddFilters + Default> AddFilters for ResourceQuery<T> {
     self.specific.add_filters(filters);
   }
 }
+
+/// How a Deployment / Stack config is combined with the
+/// template it inherits from at deploy time.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ConfigInheritance {
+  /// The id of the inherited template,
+  /// or empty if nothing is inherited.
+  pub template: String,
+  /// The effective environment variables.
+  pub environment: Vec<InheritedValue>,
+  /// The effective labels. Deployment only.
+  pub labels: Vec<InheritedValue>,
+  /// The effective restart mode. Deployment only.
+  pub restart: Option<InheritedValue>,
+}
+
+/// A single effective value after inheritance.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InheritedValue {
+  /// The variable / label name.
+  pub key: String,
+  /// The effective value.
+  pub value: String,
+  /// The template value, if the template defines the key.
+  pub template_value: Option<String>,
+  /// Where the effective value comes from.
+  pub source: InheritedSource,
+}
+
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default,
+)]
+pub enum InheritedSource {
+  /// Only defined on the resource.
+  #[default]
+  Resource,
+  /// Only defined on the template.
+  Template,
+  /// Defined on both, the resource value is used.
+  Override,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::I64;
+
+/// A Periphery public key which Core refuses to accept
+/// in the connection handshake, even if a Server still
+/// references it as the expected public key.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+pub struct RevokedKey {
+  /// The revoked public key.
+  #[cfg_attr(feature = "mongo", unique_index)]
+  pub public_key: String,
+
+  /// Why the key was revoked, eg. "Leaked in CI logs".
+  #[serde(default)]
+  pub reason: String,
+
+  /// The id of the admin who revoked the key.
+  #[serde(default)]
+  pub revoked_by: String,
+
+  /// Timestamp of the revocation.
+  #[serde(default)]
+  #[cfg_attr(feature = "mongo", index)]
+  pub revoked_at: I64,
+}
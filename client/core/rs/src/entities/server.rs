@@ -10,7 +10,7 @@ use crate::{
   deserializers::{
     option_string_list_deserializer, string_list_deserializer,
   },
-  entities::{MaintenanceWindow, Timelength},
+  entities::{_Serror, I64, MaintenanceWindow, Timelength, U64},
 };
 
 use super::{
@@ -44,20 +44,46 @@ pub struct ServerListItemInfo {
   pub send_mem_alerts: bool,
   /// Whether server is configured to send disk alerts.
   pub send_disk_alerts: bool,
+  /// Whether server is configured to send gpu alerts.
+  #[serde(default)]
+  pub send_gpu_alerts: bool,
+  /// Whether server is configured to send disk health alerts.
+  #[serde(default)]
+  pub send_disk_health_alerts: bool,
+  /// Whether server is configured to send zfs pool alerts.
+  #[serde(default)]
+  pub send_zfs_alerts: bool,
+  /// Whether server is configured to send os security update alerts.
+  #[serde(default)]
+  pub send_os_update_alerts: bool,
   /// Whether server is configured to send version mismatch alerts.
   pub send_version_mismatch_alerts: bool,
   /// The Komodo Periphery version.
   pub version: Option<String>,
+  /// Round trip latency to Periphery in ms, if known.
+  #[serde(default)]
+  pub latency_ms: Option<u64>,
   /// The public key of Periphery
   pub public_key: Option<String>,
   /// If a Periphery fails to authenticate to Core with invalid Periphery public key,
   /// it will be stored here to accept the connection later on.
   pub attempted_public_key: Option<String>,
+  /// If Periphery presents a tls certificate not matching the pins,
+  /// its pin will be stored here to accept the connection later on.
+  #[serde(default)]
+  pub attempted_tls_pin: Option<String>,
   /// Whether server is configured to send unreachable alerts.
   /// Whether terminals are disabled for this Server.
   pub terminals_disabled: bool,
   /// Whether container terminals are disabled for this Server.
   pub container_terminals_disabled: bool,
+  /// The Periphery request types allowed on this Server.
+  /// Empty means all are allowed.
+  #[serde(default)]
+  pub allowed_requests: Vec<String>,
+  /// The Periphery request types denied on this Server.
+  #[serde(default)]
+  pub denied_requests: Vec<String>,
 }
 
 #[typeshare]
@@ -72,6 +98,14 @@ pub struct ServerInfo {
   /// private key of the periphery agent.
   #[serde(default)]
   pub public_key: String,
+  /// When the Periphery keys were last rotated by Core,
+  /// or 0 if never. Used to schedule `key_rotation_interval_days`.
+  #[serde(default)]
+  pub keys_rotated_at: I64,
+  /// If Periphery presents a tls certificate not matching `tls_pins`,
+  /// its pin will be stored here to accept the connection later on.
+  #[serde(default)]
+  pub attempted_tls_pin: String,
 }
 
 #[typeshare(serialized_as = "Partial<ServerConfig>")]
@@ -84,20 +118,41 @@ pub type _PartialServerConfig = PartialServerConfig;
 #[partial(skip_serializing_none, from, diff)]
 pub struct ServerConfig {
   /// The ws/s address of the periphery client.
+  /// Use `quic://<HOST>:<PORT>` for Peripheries using the QUIC transport.
   /// If unset, Server expects Periphery -> Core connection.
   #[serde(default)]
   #[builder(default)]
   pub address: String,
 
-  /// Only relevant for Core -> Periphery connections.
-  /// Whether to skip Periphery tls certificate validation.
-  /// This defaults to true because Periphery generates self-signed certificates by default,
-  /// but if you use valid certs you can switch this to false.
+  /// Deprecated. Use `tls_verification`.
+  /// Existing Servers are migrated on startup,
+  /// `false` becoming `System` verification.
   #[serde(default = "default_insecure_tls")]
   #[builder(default = "default_insecure_tls()")]
   #[partial_default(default_insecure_tls())]
   pub insecure_tls: bool,
 
+  /// Only relevant for Core -> Periphery connections.
+  /// How to verify the Periphery tls certificate.
+  /// This defaults to `Insecure` because Periphery generates self-signed certificates by default.
+  /// Use `Pinned` to trust only the certificate public keys in `tls_pins`,
+  /// or `System` if you use valid certs.
+  #[serde(default)]
+  #[builder(default)]
+  pub tls_verification: TlsVerification,
+
+  /// The accepted Periphery certificate pins,
+  /// `sha256/<base64 hash of the certificate public key>`.
+  /// Used with `Pinned` tls verification. If empty, the pin seen on connect
+  /// is stored in `attempted_tls_pin` to accept later on.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub tls_pins: Vec<String>,
+
   /// The address to use with links for containers on the server.
   /// If empty, will use the 'address' for links.
   #[serde(default)]
@@ -191,6 +246,31 @@ pub struct ServerConfig {
   #[partial_default(default_send_alerts())]
   pub send_disk_alerts: bool,
 
+  /// Whether to send alerts about the servers GPU status
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_gpu_alerts: bool,
+
+  /// Whether to send alerts about the SMART health of the servers disks.
+  /// Requires `smartctl` on the Periphery host.
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_disk_health_alerts: bool,
+
+  /// Whether to send alerts when a ZFS pool on the server is not ONLINE.
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_zfs_alerts: bool,
+
+  /// Whether to send alerts when the server has pending OS security updates.
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_os_update_alerts: bool,
+
   /// Whether to send alerts about the servers version mismatch with core
   #[serde(default = "default_send_alerts")]
   #[builder(default = "default_send_alerts()")]
@@ -233,10 +313,61 @@ pub struct ServerConfig {
   #[partial_default(default_disk_critical())]
   pub disk_critical: f64,
 
+  /// The percentage threshhold of a GPU's memory
+  /// which triggers WARNING state for the GPU.
+  #[serde(default = "default_gpu_mem_warning")]
+  #[builder(default = "default_gpu_mem_warning()")]
+  #[partial_default(default_gpu_mem_warning())]
+  pub gpu_mem_warning: f64,
+
+  /// The percentage threshhold of a GPU's memory
+  /// which triggers CRITICAL state for the GPU.
+  #[serde(default = "default_gpu_mem_critical")]
+  #[builder(default = "default_gpu_mem_critical()")]
+  #[partial_default(default_gpu_mem_critical())]
+  pub gpu_mem_critical: f64,
+
+  /// The temperature (°C) of a GPU which triggers WARNING state for the GPU.
+  #[serde(default = "default_gpu_temp_warning")]
+  #[builder(default = "default_gpu_temp_warning()")]
+  #[partial_default(default_gpu_temp_warning())]
+  pub gpu_temp_warning: f64,
+
+  /// The temperature (°C) of a GPU which triggers CRITICAL state for the GPU.
+  #[serde(default = "default_gpu_temp_critical")]
+  #[builder(default = "default_gpu_temp_critical()")]
+  #[partial_default(default_gpu_temp_critical())]
+  pub gpu_temp_critical: f64,
+
   /// Scheduled maintenance windows during which alerts will be suppressed.
   #[serde(default)]
   #[builder(default)]
   pub maintenance_windows: Vec<MaintenanceWindow>,
+
+  /// The percentage of CPU to keep free for the host.
+  /// Placement checks will not suggest using this portion.
+  #[serde(default)]
+  #[builder(default)]
+  pub reserved_cpu: f32,
+
+  /// The memory (in GB) to keep free for the host.
+  /// Placement checks will not suggest using this portion.
+  #[serde(default)]
+  #[builder(default)]
+  pub reserved_mem_gb: f64,
+
+  /// The maximum number of containers which should run on the Server.
+  /// 0 means no limit.
+  #[serde(default)]
+  #[builder(default)]
+  pub max_containers: I64,
+
+  /// Limit the bytes per second sent to and received from Periphery.
+  /// 0 uses the Core `transport_max_bytes_per_sec` default,
+  /// and a negative value disables the limit for this Server.
+  #[serde(default)]
+  #[builder(default)]
+  pub transport_max_bytes_per_sec: I64,
 }
 
 impl ServerConfig {
@@ -294,11 +425,29 @@ fn default_disk_critical() -> f64 {
   95.0
 }
 
+fn default_gpu_mem_warning() -> f64 {
+  90.0
+}
+
+fn default_gpu_mem_critical() -> f64 {
+  99.0
+}
+
+fn default_gpu_temp_warning() -> f64 {
+  80.0
+}
+
+fn default_gpu_temp_critical() -> f64 {
+  90.0
+}
+
 impl Default for ServerConfig {
   fn default() -> Self {
     Self {
       address: Default::default(),
       insecure_tls: default_insecure_tls(),
+      tls_verification: Default::default(),
+      tls_pins: Default::default(),
       external_address: Default::default(),
       enabled: default_enabled(),
       auto_rotate_keys: Default::default(),
@@ -310,6 +459,10 @@ impl Default for ServerConfig {
       send_cpu_alerts: default_send_alerts(),
       send_mem_alerts: default_send_alerts(),
       send_disk_alerts: default_send_alerts(),
+      send_gpu_alerts: default_send_alerts(),
+      send_disk_health_alerts: default_send_alerts(),
+      send_zfs_alerts: default_send_alerts(),
+      send_os_update_alerts: default_send_alerts(),
       send_version_mismatch_alerts: default_send_alerts(),
       region: Default::default(),
       passkey: Default::default(),
@@ -319,7 +472,15 @@ impl Default for ServerConfig {
       mem_critical: default_mem_critical(),
       disk_warning: default_disk_warning(),
       disk_critical: default_disk_critical(),
+      gpu_mem_warning: default_gpu_mem_warning(),
+      gpu_mem_critical: default_gpu_mem_critical(),
+      gpu_temp_warning: default_gpu_temp_warning(),
+      gpu_temp_critical: default_gpu_temp_critical(),
       maintenance_windows: Default::default(),
+      reserved_cpu: Default::default(),
+      reserved_mem_gb: Default::default(),
+      max_containers: Default::default(),
+      transport_max_bytes_per_sec: Default::default(),
     }
   }
 }
@@ -340,6 +501,96 @@ pub struct ServerHealth {
   pub cpu: ServerHealthState,
   pub mem: ServerHealthState,
   pub disks: HashMap<PathBuf, ServerHealthState>,
+  /// The health of each GPU, by [gpu_id](super::stats::gpu_id).
+  #[serde(default)]
+  pub gpus: HashMap<String, ServerHealthState>,
+  /// The health of each ZFS pool, by pool name.
+  #[serde(default)]
+  pub zfs_pools: HashMap<String, ServerHealthState>,
+}
+
+/// Load metrics for a Core Periphery connection shard.
+#[typeshare]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct ConnectionShardStats {
+  /// The shard index.
+  pub index: usize,
+  /// The number of currently open connections.
+  pub active: u64,
+  /// The number of logins currently in progress.
+  pub logging_in: u64,
+  /// The maximum concurrent logins.
+  pub max_logins: u64,
+  /// Total inbound connections accepted since Core started.
+  pub accepted: u64,
+  /// Total inbound connections rejected because
+  /// the shard login pool was full.
+  pub rejected_busy: u64,
+  /// Total messages received from Periphery.
+  pub messages_received: u64,
+  /// Total messages sent to Periphery.
+  pub messages_sent: u64,
+}
+
+/// Transport metrics for the Periphery connection of a Server,
+/// since Core started. Kept across reconnects.
+#[typeshare]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct ServerConnectionMetrics {
+  /// Whether Periphery is currently connected.
+  pub connected: bool,
+  /// Round trip latency of the latest Ping in ms,
+  /// if the connection uses keepalive.
+  pub latency_ms: Option<u64>,
+  /// The times the connection was re-established.
+  pub reconnects: u64,
+  /// Total bytes sent to Periphery.
+  pub bytes_sent: u64,
+  /// Total bytes received from Periphery.
+  pub bytes_received: u64,
+  /// Messages sent to Periphery by transport message variant,
+  /// eg. `Request`, `Terminal`.
+  pub messages_sent: HashMap<String, u64>,
+  /// Messages received from Periphery by transport message variant,
+  /// eg. `Response`, `Terminal`.
+  pub messages_received: HashMap<String, u64>,
+  /// The latest connection error, kept after reconnecting.
+  pub last_error: Option<_Serror>,
+  /// Timestamp of the latest connection error.
+  pub last_error_at: Option<I64>,
+}
+
+/// The header of a transport message on a Periphery connection,
+/// recorded when Core `transport_trace` is enabled.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransportTraceEntry {
+  /// When the message was sent / received.
+  pub ts: I64,
+  /// The Server (or Builder) id of the connection.
+  pub server: String,
+  pub direction: TransportDirection,
+  /// The transport message variant, eg. `Request`.
+  /// Empty if the message header is invalid.
+  pub variant: String,
+  /// The channel of Request, Response, Terminal and File messages,
+  /// or the message id of Chunk messages.
+  pub channel: Option<String>,
+  /// The encoded message size in bytes.
+  pub size: u64,
+  /// Whether the message is compressed.
+  pub compressed: bool,
+}
+
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum TransportDirection {
+  /// Core -> Periphery
+  Sent,
+  /// Periphery -> Core
+  Received,
 }
 
 /// Info about Periphery configuration
@@ -358,6 +609,46 @@ pub struct PeripheryInformation {
   pub stats_polling_rate: Timelength,
   /// Whether Periphery is successfully connected to docker daemon.
   pub docker_connected: bool,
+  /// The request types Periphery allows. Empty means all are allowed.
+  #[serde(default)]
+  pub allowed_requests: Vec<String>,
+  /// The request types Periphery denies.
+  #[serde(default)]
+  pub denied_requests: Vec<String>,
+  /// The names of the configured Periphery extensions.
+  #[serde(default)]
+  pub extensions: Vec<String>,
+  /// The platforms the buildx builder supports,
+  /// ie `linux/amd64`, `linux/arm64`.
+  #[serde(default)]
+  pub buildx_platforms: Vec<String>,
+}
+
+impl PeripheryInformation {
+  pub fn request_allowed(&self, request: &str) -> bool {
+    periphery_request_allowed(
+      &self.allowed_requests,
+      &self.denied_requests,
+      request,
+    )
+  }
+}
+
+/// Matches the Periphery request type against the allowed / denied lists.
+/// Entries ending in `*` match by prefix, ie `Prune*`.
+pub fn periphery_request_allowed(
+  allowed: &[String],
+  denied: &[String],
+  request: &str,
+) -> bool {
+  let matches = |pattern: &String| match pattern.strip_suffix('*') {
+    Some(prefix) => request.starts_with(prefix),
+    None => pattern == request,
+  };
+  if denied.iter().any(matches) {
+    return false;
+  }
+  allowed.is_empty() || allowed.iter().any(matches)
 }
 
 /// Info about an active terminal on a server.
@@ -371,6 +662,57 @@ pub struct TerminalInfo {
   pub command: String,
   /// The size of the terminal history in memory.
   pub stored_size_kb: f64,
+  /// The file name of the terminal recording,
+  /// if the terminal is being recorded.
+  #[serde(default)]
+  pub recording: Option<String>,
+  /// The milliseconds remaining before the terminal is closed
+  /// by the Periphery idle timeout or max lifetime, if either is set.
+  #[serde(default)]
+  pub ttl_ms: Option<I64>,
+  /// The number of clients connected to the terminal,
+  /// including the read only viewers.
+  #[serde(default)]
+  pub connections: U64,
+  /// The number of read only viewers connected to the terminal.
+  #[serde(default)]
+  pub read_only_connections: U64,
+}
+
+/// An asciicast v2 terminal recording stored on a server.
+/// Retrieve with [ListTerminalRecordings][crate::api::read::server::ListTerminalRecordings].
+#[typeshare]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct TerminalRecording {
+  /// The file name of the recording, ie `my-terminal-1700000000000.cast`.
+  pub name: String,
+  /// The name of the recorded terminal.
+  pub terminal: String,
+  /// The size of the recording file.
+  pub size_kb: f64,
+  /// Unix timestamp in milliseconds the recording started.
+  pub started_at: I64,
+}
+
+/// A user connected to a terminal through Core.
+/// Retrieve with [ListActiveTerminalSessions][crate::api::read::server::ListActiveTerminalSessions].
+#[typeshare]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct ActiveTerminalSession {
+  /// The id of the session, used to force disconnect.
+  pub id: String,
+  /// The id of the connected user.
+  pub user_id: String,
+  /// The username of the connected user.
+  pub username: String,
+  /// The id of the server the terminal is on.
+  pub server_id: String,
+  /// The name of the server the terminal is on.
+  pub server_name: String,
+  /// The terminal, eg. the terminal name or `exec: <container>`.
+  pub terminal: String,
+  /// Unix timestamp in milliseconds the session started.
+  pub started_at: I64,
 }
 
 /// Current pending actions on the server.
@@ -401,6 +743,10 @@ pub struct ServerActionState {
   pub unpausing_containers: bool,
   /// Server currently stopping containers.
   pub stopping_containers: bool,
+  /// Server currently committing a container.
+  pub committing_container: bool,
+  /// Server currently loading a transferred image.
+  pub transferring_image: bool,
 }
 
 #[typeshare]
@@ -429,6 +775,43 @@ pub enum ServerState {
   Disabled,
 }
 
+/// A power action run on the Server host.
+#[typeshare]
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq, Display, Serialize, Deserialize,
+)]
+pub enum HostPowerAction {
+  /// Reboot the host
+  Reboot,
+  /// Power off the host
+  Shutdown,
+}
+
+/// How Core verifies the Periphery tls certificate
+/// for Core -> Periphery connections.
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Default,
+  Display,
+  Serialize,
+  Deserialize,
+)]
+pub enum TlsVerification {
+  /// Skip certificate verification.
+  /// Periphery uses self-signed certificates by default.
+  #[default]
+  Insecure,
+  /// Verify the certificate against the system CA roots.
+  System,
+  /// Only accept certificates matching the Server `tls_pins`.
+  Pinned,
+}
+
 /// Server-specific query
 #[typeshare]
 pub type ServerQuery = ResourceQuery<ServerQuerySpecifics>;
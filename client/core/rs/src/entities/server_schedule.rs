@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::I64;
+
+/// A command Periphery runs on a cron schedule,
+/// whether or not Core is connected.
+/// The results are sent to Core as Updates on the Server.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ServerSchedule {
+  /// The unique schedule name, ie `nightly-prune`
+  pub name: String,
+  /// The cron expression, in the host local timezone.
+  /// The seconds field is optional, ie
+  /// `0 3 * * *` and `0 0 3 * * *` both run at 03:00.
+  pub schedule: String,
+  /// The shell command to run, ie `docker system prune -af`
+  pub command: String,
+  /// Disabled schedules are kept, but not run.
+  /// Default: true
+  #[serde(default = "default_enabled")]
+  pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+  true
+}
+
+/// A [ServerSchedule] with its run status on the server.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ServerScheduleStatus {
+  pub schedule: ServerSchedule,
+  /// Whether the schedule is set in the Periphery config file.
+  /// These can't be changed through the API.
+  pub from_config: bool,
+  /// The next run timestamp in ms,
+  /// if the schedule is enabled and valid.
+  pub next_run_ts: Option<I64>,
+  /// The reason the schedule can't run, ie an invalid expression.
+  pub error: Option<String>,
+  /// The start timestamp of the last run in ms.
+  /// Not kept across Periphery restarts.
+  pub last_run_ts: Option<I64>,
+  /// Whether the last run succeeded.
+  pub last_run_success: Option<bool>,
+}
@@ -24,7 +24,7 @@ use crate::{
 };
 
 use super::{
-  FileContents, SystemCommand,
+  FileContents, I64, SystemCommand,
   docker::container::ContainerListItem,
   resource::{Resource, ResourceListItem, ResourceQuery},
 };
@@ -260,6 +260,67 @@ pub struct StackInfo {
   pub latest_hash: Option<String>,
   /// Latest commit message, or null
   pub latest_message: Option<String>,
+
+  /// The progress of an unfinished migration to another Server, or null.
+  #[serde(default)]
+  pub migration: Option<StackMigration>,
+}
+
+/// The progress of a [MigrateStack][crate::api::execute::MigrateStack].
+/// Running MigrateStack again resumes after the completed steps.
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StackMigration {
+  /// The id of the Server the Stack is migrating from.
+  pub from_server: String,
+  /// The id of the Server the Stack is migrating to.
+  pub to_server: String,
+  /// The named volumes of the Stack being migrated.
+  #[serde(default)]
+  pub volumes: Vec<String>,
+  /// The completed steps, in order.
+  #[serde(default)]
+  pub completed: Vec<StackMigrationStep>,
+  /// Unix timestamp in milliseconds the migration started.
+  #[serde(default)]
+  pub started_at: I64,
+}
+
+/// The steps of a [StackMigration], in order.
+#[typeshare]
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display,
+)]
+pub enum StackMigrationStep {
+  /// Destroy the Stack on the source Server, keeping the volumes.
+  Destroy,
+  /// Archive the named volumes on the source Server.
+  Archive,
+  /// Transfer the volume archives to the target Server.
+  Transfer,
+  /// Restore the volume archives into volumes on the target Server.
+  Restore,
+  /// Point the Stack at the target Server.
+  SwitchServer,
+  /// Deploy the Stack on the target Server.
+  Deploy,
+  /// Wait for the Stack to be running on the target Server.
+  Verify,
+  /// Remove the volumes and archives from the source Server.
+  Cleanup,
+}
+
+impl StackMigrationStep {
+  pub const ALL: [StackMigrationStep; 8] = [
+    StackMigrationStep::Destroy,
+    StackMigrationStep::Archive,
+    StackMigrationStep::Transfer,
+    StackMigrationStep::Restore,
+    StackMigrationStep::SwitchServer,
+    StackMigrationStep::Deploy,
+    StackMigrationStep::Verify,
+    StackMigrationStep::Cleanup,
+  ];
 }
 
 #[typeshare(serialized_as = "Partial<StackConfig>")]
@@ -277,6 +338,13 @@ pub struct StackConfig {
   #[builder(default)]
   pub server_id: String,
 
+  /// Inherit from this template Stack (id or name).
+  /// At deploy time, the template environment is merged
+  /// underneath this Stack's, which overrides by variable.
+  #[serde(default)]
+  #[builder(default)]
+  pub inherit: String,
+
   /// Configure quick links that are displayed in the resource header
   #[serde(default, deserialize_with = "string_list_deserializer")]
   #[partial_attr(serde(
@@ -335,6 +403,14 @@ pub struct StackConfig {
   #[builder(default)]
   pub destroy_before_deploy: bool,
 
+  /// Whether to deploy with `docker stack deploy`
+  /// to the swarm the Server is a manager of.
+  /// Swarm stacks don't support `run_build`,
+  /// and are deployed and destroyed as a whole.
+  #[serde(default)]
+  #[builder(default)]
+  pub swarm: bool,
+
   /// Whether to skip secret interpolation into the stack environment variables.
   #[serde(default)]
   #[builder(default)]
@@ -496,6 +572,32 @@ pub struct StackConfig {
   #[builder(default)]
   pub post_deploy: SystemCommand,
 
+  /// Kill the pre / post deploy commands if they run
+  /// longer than this many seconds. 0 means no timeout.
+  #[serde(default)]
+  #[builder(default)]
+  pub deploy_command_timeout_seconds: I64,
+
+  /// Wait for the service container HEALTHCHECKs to report healthy after deploy.
+  /// The deploy fails if any container is unhealthy, exits,
+  /// or isn't healthy within `healthcheck_timeout_seconds`.
+  /// Containers without a HEALTHCHECK only need to keep running.
+  #[serde(default)]
+  #[builder(default)]
+  pub wait_for_healthcheck: bool,
+
+  /// Seconds to wait for the containers to become healthy.
+  #[serde(default = "default_healthcheck_timeout_seconds")]
+  #[builder(default = "default_healthcheck_timeout_seconds()")]
+  #[partial_default(default_healthcheck_timeout_seconds())]
+  pub healthcheck_timeout_seconds: I64,
+
+  /// If the deploy fails, or the containers fail their healthchecks,
+  /// re-deploy the compose config of the previous successful deploy.
+  #[serde(default)]
+  #[builder(default)]
+  pub rollback_on_failure: bool,
+
   /// The extra arguments to pass after `docker compose up -d`.
   /// If empty, no extra arguments will be passed.
   #[serde(default, deserialize_with = "string_list_deserializer")]
@@ -528,6 +630,14 @@ pub struct StackConfig {
   #[builder(default)]
   pub ignore_services: Vec<String>,
 
+  /// Stat-based autoscale policies for the Stack services.
+  /// Core evaluates them against the service container stats,
+  /// and scales the service with `docker compose up --scale`.
+  #[serde(default)]
+  #[partial_attr(serde(default))]
+  #[builder(default)]
+  pub autoscale: Vec<StackServiceAutoscale>,
+
   /// The contents of the file directly, for management in the UI.
   /// If this is empty, it will fall back to checking git config for
   /// repo based compose file.
@@ -552,6 +662,25 @@ pub struct StackConfig {
   ))]
   #[builder(default)]
   pub environment: String,
+
+  /// Secrets to mount into the compose services as files,
+  /// instead of passing them as environment variables.
+  /// Given as `NAME = value`, where the value is usually a
+  /// Variable / Secret like `[[DB_PASSWORD]]`.
+  ///
+  /// Before deploy, each is written to a transient tmpfs backed
+  /// directory on the host, and defined as a top level compose secret
+  /// `NAME`. Services use them with `secrets: [NAME]`, and read them
+  /// at `/run/secrets/NAME`. The files are removed on `compose down`.
+  ///
+  /// Note. These are always interpolated, even with `skip_secret_interp`.
+  #[serde(default, deserialize_with = "env_vars_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_env_vars_deserializer"
+  ))]
+  #[builder(default)]
+  pub secret_files: String,
 }
 
 impl StackConfig {
@@ -563,6 +692,13 @@ impl StackConfig {
     environment_vars_from_str(&self.environment)
       .context("Invalid environment")
   }
+
+  pub fn secret_file_vars(
+    &self,
+  ) -> anyhow::Result<Vec<EnvironmentVar>> {
+    environment_vars_from_str(&self.secret_files)
+      .context("Invalid secret files")
+  }
 }
 
 fn default_env_file_path() -> String {
@@ -573,6 +709,10 @@ fn default_auto_pull() -> bool {
   true
 }
 
+fn default_healthcheck_timeout_seconds() -> I64 {
+  120
+}
+
 fn default_git_provider() -> String {
   String::from("github.com")
 }
@@ -597,6 +737,7 @@ impl Default for StackConfig {
   fn default() -> Self {
     Self {
       server_id: Default::default(),
+      inherit: Default::default(),
       project_name: Default::default(),
       run_directory: Default::default(),
       file_paths: Default::default(),
@@ -609,15 +750,22 @@ impl Default for StackConfig {
       auto_update: Default::default(),
       auto_update_all_services: Default::default(),
       ignore_services: Default::default(),
+      autoscale: Default::default(),
       pre_deploy: Default::default(),
       post_deploy: Default::default(),
+      deploy_command_timeout_seconds: Default::default(),
+      wait_for_healthcheck: Default::default(),
+      healthcheck_timeout_seconds:
+        default_healthcheck_timeout_seconds(),
       extra_args: Default::default(),
       environment: Default::default(),
+      secret_files: Default::default(),
       env_file_path: default_env_file_path(),
       additional_env_files: Default::default(),
       config_files: Default::default(),
       run_build: Default::default(),
       destroy_before_deploy: Default::default(),
+      swarm: Default::default(),
       build_extra_args: Default::default(),
       skip_secret_interp: Default::default(),
       linked_repo: Default::default(),
@@ -647,6 +795,18 @@ pub struct ComposeProject {
   pub status: Option<String>,
   /// The compose files included in the project.
   pub compose_files: Vec<String>,
+  /// The services of the project, with their running replicas.
+  #[serde(default)]
+  pub services: Vec<ComposeProjectService>,
+}
+
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComposeProjectService {
+  /// The service name.
+  pub service: String,
+  /// The number of running containers for the service.
+  pub replicas: I64,
 }
 
 #[typeshare]
@@ -700,6 +860,7 @@ pub struct StackActionState {
   pub unpausing: bool,
   pub stopping: bool,
   pub destroying: bool,
+  pub scaling: bool,
 }
 
 #[typeshare]
@@ -818,6 +979,47 @@ pub enum StackFileRequires {
   None,
 }
 
+/// Stat-based autoscale policy for a Stack service.
+///
+/// The replicas are scaled so the average container cpu
+/// across the running replicas approaches `target_cpu_percent`.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StackServiceAutoscale {
+  /// The compose service to scale.
+  pub service: String,
+  /// The target average container cpu percent across the replicas.
+  /// Default: 70
+  #[serde(default = "default_autoscale_target_cpu_percent")]
+  pub target_cpu_percent: f64,
+  /// The minimum number of replicas. Default: 1
+  #[serde(default = "default_autoscale_min_replicas")]
+  pub min_replicas: I64,
+  /// The maximum number of replicas. Default: 3
+  #[serde(default = "default_autoscale_max_replicas")]
+  pub max_replicas: I64,
+  /// Seconds to wait after scaling the service before it can be scaled again.
+  /// Default: 300
+  #[serde(default = "default_autoscale_cooldown_seconds")]
+  pub cooldown_seconds: I64,
+}
+
+fn default_autoscale_target_cpu_percent() -> f64 {
+  70.0
+}
+
+fn default_autoscale_min_replicas() -> I64 {
+  1
+}
+
+fn default_autoscale_max_replicas() -> I64 {
+  3
+}
+
+fn default_autoscale_cooldown_seconds() -> I64 {
+  300
+}
+
 /// Configure additional file dependencies of the Stack.
 #[typeshare]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
@@ -1,9 +1,12 @@
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
 use typeshare::typeshare;
 
-use crate::entities::{I64, Timelength};
+use crate::entities::{
+  I64, Timelength, docker::container::PortTypeEnum,
+};
 
 /// System information of a server
 #[typeshare]
@@ -41,6 +44,13 @@ pub struct SystemStatsRecord {
   // basic stats
   /// Cpu usage percentage
   pub cpu_perc: f32,
+  /// Usage percentage of each cpu core, in core order
+  #[serde(default)]
+  pub cpu_cores: Vec<f32>,
+  /// Percentage of cpu time stolen by the hypervisor.
+  /// Only reported on Linux.
+  #[serde(default)]
+  pub cpu_steal_perc: Option<f32>,
   /// Load average (1m, 5m, 15m)
   #[serde(default)]
   pub load_average: SystemLoadAverage,
@@ -63,6 +73,28 @@ pub struct SystemStatsRecord {
   // /// Network usage by interface name (ingress, egress in bytes)
   // #[serde(default)]
   // pub network_usage_interface: Vec<SingleNetworkInterfaceUsage>, // interface -> (ingress, egress)
+  /// Network and block IO rates of the containers on the server
+  #[serde(default)]
+  pub containers: Vec<ContainerIoStatsRecord>,
+  /// Usage of the GPUs on the server
+  #[serde(default)]
+  pub gpus: Vec<SingleGpuUsage>,
+}
+
+/// Network and block IO rates of a single container stored on the database.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ContainerIoStatsRecord {
+  /// The container name
+  pub name: String,
+  /// Network receive rate in bytes per second
+  pub net_rx: f64,
+  /// Network transmit rate in bytes per second
+  pub net_tx: f64,
+  /// Block device read rate in bytes per second
+  pub block_read: f64,
+  /// Block device write rate in bytes per second
+  pub block_write: f64,
 }
 
 /// Realtime system stats data.
@@ -71,6 +103,13 @@ pub struct SystemStatsRecord {
 pub struct SystemStats {
   /// Cpu usage percentage
   pub cpu_perc: f32,
+  /// Usage percentage of each cpu core, in core order
+  #[serde(default)]
+  pub cpu_cores: Vec<f32>,
+  /// Percentage of cpu time stolen by the hypervisor
+  /// since the last poll. Only reported on Linux.
+  #[serde(default)]
+  pub cpu_steal_perc: Option<f32>,
   ///  Load average (1m, 5m, 15m)
   #[serde(default)]
   pub load_average: SystemLoadAverage,
@@ -86,15 +125,21 @@ pub struct SystemStats {
   pub mem_total_gb: f64,
   /// Breakdown of individual disks, ie their usages, sizes, and mount points
   pub disks: Vec<SingleDiskUsage>,
+  /// Usage of the NVIDIA and AMD GPUs on the system
+  #[serde(default)]
+  pub gpus: Vec<SingleGpuUsage>,
+  /// The ZFS pools on the system and their datasets
+  #[serde(default)]
+  pub zfs_pools: Vec<ZfsPool>,
   /// Network ingress usage in MB
   #[serde(default)]
   pub network_ingress_bytes: f64,
   /// Network egress usage in MB
   #[serde(default)]
   pub network_egress_bytes: f64,
-  // /// Network usage by interface name (ingress, egress in bytes)
-  // #[serde(default)]
-  // pub network_usage_interface: Vec<SingleNetworkInterfaceUsage>, // interface -> (ingress, egress)
+  /// Throughput and errors of each network interface
+  #[serde(default)]
+  pub network_interfaces: Vec<SingleNetworkInterfaceUsage>,
   // metadata
   /// The rate the system stats are being polled from the system
   pub polling_rate: Timelength,
@@ -118,16 +163,185 @@ pub struct SingleDiskUsage {
   pub total_gb: f64,
 }
 
-/// Info for network interface usage.
+/// Usage of a single GPU on the system.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SingleGpuUsage {
+  /// The index of the GPU, as reported by the vendor tooling
+  pub index: u32,
+  /// The GPU vendor
+  pub vendor: GpuVendor,
+  /// The GPU model name
+  pub name: String,
+  /// GPU utilization percentage
+  pub utilization_perc: f64,
+  /// Used GPU memory in GB
+  pub mem_used_gb: f64,
+  /// Total GPU memory in GB
+  pub mem_total_gb: f64,
+  /// GPU temperature in degrees Celsius, if reported
+  pub temperature: Option<f64>,
+}
+
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Display,
+)]
+pub enum GpuVendor {
+  Nvidia,
+  Amd,
+}
+
+/// Identifies a GPU on a server, as indexes
+/// are only unique for the same vendor. ie `Nvidia-0`.
+pub fn gpu_id(vendor: GpuVendor, index: u32) -> String {
+  format!("{vendor}-{index}")
+}
+
+/// Status of a ZFS pool.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ZfsPool {
+  /// The pool name
+  pub name: String,
+  /// The pool health, ie `ONLINE`, `DEGRADED`, `FAULTED`
+  pub health: String,
+  /// Total size of the pool in GB
+  pub size_gb: f64,
+  /// Allocated portion of the pool in GB
+  pub allocated_gb: f64,
+  /// Free portion of the pool in GB
+  pub free_gb: f64,
+  /// Free space fragmentation percentage, if reported
+  pub fragmentation_perc: Option<f64>,
+  /// The status of the last or running scrub / resilver,
+  /// ie `SCRUB FINISHED (0 errors)`
+  pub scan: Option<String>,
+  /// The datasets in the pool
+  pub datasets: Vec<ZfsDataset>,
+}
+
+/// Usage of a ZFS dataset.
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ZfsDataset {
+  /// The dataset name, ie `tank/media`
+  pub name: String,
+  /// Used space in GB, including descendants and snapshots
+  pub used_gb: f64,
+  /// Available space in GB
+  pub available_gb: f64,
+  /// The mountpoint, if mounted
+  pub mountpoint: Option<String>,
+}
+
+/// The SMART health of a physical disk, read with `smartctl`.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DiskHealth {
+  /// The device path, ie `/dev/sda`
+  pub device: String,
+  /// The disk model name
+  pub model: String,
+  /// The disk serial number
+  pub serial: String,
+  /// Whether the overall SMART self assessment passed
+  pub passed: bool,
+  /// The disk temperature in degrees Celsius, if reported
+  pub temperature: Option<f64>,
+  /// The count of reallocated sectors (ATA attribute 5)
+  pub reallocated_sectors: Option<I64>,
+  /// The count of sectors pending reallocation (ATA attribute 197)
+  pub pending_sectors: Option<I64>,
+  /// The count of media and data integrity errors (NVMe)
+  pub media_errors: Option<I64>,
+  /// The percentage of the rated endurance used,
+  /// from the NVMe 'Percentage Used' or the ATA wear leveling count.
+  pub wear_perc: Option<f64>,
+  /// The hours the disk has been powered on
+  pub power_on_hours: Option<I64>,
+  /// The error if smartctl could not read the disk
+  pub error: Option<String>,
+}
+
+/// The OS package updates pending on a server.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PendingOsUpdates {
+  /// The detected package manager, ie `apt`, `dnf`, `apk`.
+  /// Empty if none was found.
+  pub package_manager: String,
+  /// The packages with a newer version available
+  pub updates: Vec<OsPackageUpdate>,
+  /// Unix timestamp in milliseconds of the check
+  pub checked_ts: I64,
+  /// The error if the check failed
+  pub error: Option<String>,
+}
+
+impl PendingOsUpdates {
+  pub fn security_updates(
+    &self,
+  ) -> impl Iterator<Item = &OsPackageUpdate> {
+    self.updates.iter().filter(|update| update.security)
+  }
+}
+
+/// A package with a newer version available.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct OsPackageUpdate {
+  /// The package name
+  pub name: String,
+  /// The installed version, if reported
+  pub current_version: Option<String>,
+  /// The available version
+  pub new_version: String,
+  /// Whether the update comes from a security repository / advisory.
+  /// Not reported by `apk`.
+  pub security: bool,
+}
+
+/// A TCP / UDP socket listening on the host,
+/// with the process and container owning it.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ListeningSocket {
+  /// `tcp` or `udp`
+  pub protocol: PortTypeEnum,
+  /// The bound address, ie `0.0.0.0`, `::` or `127.0.0.1`
+  pub address: String,
+  /// The bound port
+  pub port: u16,
+  /// The PID of the owning process, if Periphery can see it
+  pub pid: Option<u32>,
+  /// The name of the owning process
+  pub process: Option<String>,
+  /// The name of the container owning the socket,
+  /// either publishing the port or running the process.
+  pub container: Option<String>,
+}
+
+/// Info for network interface usage.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct SingleNetworkInterfaceUsage {
   /// The network interface name
   pub name: String,
-  /// The ingress in bytes
-  pub ingress_bytes: f64,
-  /// The egress in bytes
-  pub egress_bytes: f64,
+  /// Receive rate in bytes per second
+  pub rx: f64,
+  /// Transmit rate in bytes per second
+  pub tx: f64,
+  /// Total receive errors since the interface came up
+  pub rx_errors: I64,
+  /// Total transmit errors since the interface came up
+  pub tx_errors: I64,
+  /// Total dropped received packets since the interface came up.
+  /// Only reported on Linux.
+  pub rx_dropped: Option<I64>,
+  /// Total dropped transmitted packets since the interface came up.
+  /// Only reported on Linux.
+  pub tx_dropped: Option<I64>,
 }
 
 pub fn sum_disk_usage(disks: &[SingleDiskUsage]) -> TotalDiskUsage {
@@ -178,6 +392,44 @@ pub struct SystemProcess {
   pub disk_write_kb: f64,
 }
 
+/// A signal to send to a process on the system.
+#[typeshare]
+#[derive(
+  Serialize,
+  Deserialize,
+  Debug,
+  PartialEq,
+  Eq,
+  Clone,
+  Copy,
+  Default,
+  Display,
+  EnumString,
+)]
+#[serde(rename_all = "UPPERCASE")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum ProcessSignal {
+  #[serde(alias = "1")]
+  SigHup,
+  #[serde(alias = "2")]
+  SigInt,
+  #[serde(alias = "3")]
+  SigQuit,
+  #[serde(alias = "9")]
+  SigKill,
+  #[serde(alias = "10")]
+  SigUsr1,
+  #[serde(alias = "12")]
+  SigUsr2,
+  #[default]
+  #[serde(alias = "15")]
+  SigTerm,
+  #[serde(alias = "18")]
+  SigCont,
+  #[serde(alias = "19")]
+  SigStop,
+}
+
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct SystemLoadAverage {
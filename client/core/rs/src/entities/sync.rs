@@ -103,6 +103,9 @@ pub struct ResourceSyncInfo {
   /// The list of pending updates to user groups
   #[serde(default)]
   pub user_group_updates: Vec<DiffData>,
+  /// The list of pending updates to onboarding keys
+  #[serde(default)]
+  pub onboarding_key_updates: Vec<DiffData>,
   /// The list of pending deploys to resources.
   #[serde(default)]
   pub pending_deploy: SyncDeployUpdate,
@@ -290,6 +293,11 @@ pub struct ResourceSyncConfig {
   #[builder(default)]
   pub include_user_groups: bool,
 
+  /// Whether sync should include onboarding key metadata.
+  #[serde(default)]
+  #[builder(default)]
+  pub include_onboarding_keys: bool,
+
   /// Whether sync should send alert when it enters Pending state.
   /// Default: true
   #[serde(default = "default_pending_alert")]
@@ -366,6 +374,7 @@ impl Default for ResourceSyncConfig {
       match_tags: Default::default(),
       include_variables: Default::default(),
       include_user_groups: Default::default(),
+      include_onboarding_keys: Default::default(),
       delete: Default::default(),
       webhook_enabled: default_webhook_enabled(),
       webhook_secret: Default::default(),
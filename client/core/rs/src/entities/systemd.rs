@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::I64;
+
+/// A systemd unit loaded on the server,
+/// as listed by `systemctl list-units`.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SystemdUnit {
+  /// The unit name, ie `wg-quick@wg0.service`
+  pub name: String,
+  /// Whether the unit definition was loaded, ie `loaded`, `not-found`
+  pub load_state: String,
+  /// The high level activation state, ie `active`, `inactive`, `failed`
+  pub active_state: String,
+  /// The unit type specific state, ie `running`, `exited`, `waiting`
+  pub sub_state: String,
+  /// The unit description
+  pub description: String,
+}
+
+/// The detailed status of a systemd unit,
+/// read with `systemctl show`.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SystemdUnitStatus {
+  /// The unit name, ie `wg-quick@wg0.service`
+  pub name: String,
+  /// The unit description
+  pub description: String,
+  /// Whether the unit definition was loaded, ie `loaded`, `not-found`
+  pub load_state: String,
+  /// The high level activation state, ie `active`, `inactive`, `failed`
+  pub active_state: String,
+  /// The unit type specific state, ie `running`, `exited`, `waiting`
+  pub sub_state: String,
+  /// Whether the unit is enabled, ie `enabled`, `disabled`, `static`
+  pub unit_file_state: String,
+  /// The path of the unit file
+  pub fragment_path: Option<String>,
+  /// The pid of the main process, if running
+  pub main_pid: Option<I64>,
+  /// When the unit last entered the active state
+  pub active_since: Option<String>,
+  /// The current memory usage in bytes, if accounted
+  pub memory_bytes: Option<I64>,
+}
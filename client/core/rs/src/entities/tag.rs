@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use strum::AsRefStr;
 use typeshare::typeshare;
 
-use crate::entities::MongoId;
+use crate::entities::{I64, MongoId};
 
 #[typeshare(serialized_as = "Partial<Tag>")]
 pub type _PartialTag = PartialTag;
@@ -41,6 +41,12 @@ pub struct Tag {
   #[serde(default)]
   #[builder(default)]
   pub color: TagColor,
+
+  /// The id of the Organization the tag belongs to.
+  #[serde(default)]
+  #[builder(default)]
+  #[cfg_attr(feature = "mongo", index)]
+  pub organization: String,
   // /// This field is not stored on database,
   // /// but rather populated at query time based on results from the other resources.
   // #[serde(default, skip_serializing_if = "is_false")]
@@ -134,3 +140,40 @@ pub enum TagColor {
   Rose,
   DarkRose,
 }
+
+/// Resource usage aggregated over the resources with a tag,
+/// for chargeback and capacity planning.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TagUsageReport {
+  /// The tag id
+  pub tag: String,
+  /// The tag name
+  pub name: String,
+  /// Start of the report window (unix ms)
+  pub since: I64,
+  /// End of the report window (unix ms)
+  pub until: I64,
+  /// The number of tagged Servers
+  pub servers: I64,
+  /// Average cpu usage percentage across the tagged Servers
+  pub cpu_perc_avg: f64,
+  /// Average memory used in GB, summed over the tagged Servers
+  pub mem_used_gb_avg: f64,
+  /// Average total memory in GB, summed over the tagged Servers
+  pub mem_total_gb_avg: f64,
+  /// The current number of containers on the tagged Servers
+  pub containers: I64,
+  /// The current number of running containers on the tagged Servers
+  pub running_containers: I64,
+  /// The number of tagged Deployments
+  pub deployments: I64,
+  /// The number of tagged Stacks
+  pub stacks: I64,
+  /// The number of tagged Builds
+  pub builds: I64,
+  /// The number of runs of the tagged Builds in the window
+  pub build_runs: I64,
+  /// Total minutes spent running the tagged Builds in the window
+  pub build_minutes: f64,
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::I64;
+
+/// A parameterized compose file which can be instantiated
+/// into a new [Stack](crate::entities::stack::Stack)
+/// on a chosen Server using [InstantiateTemplate](crate::api::write::InstantiateTemplate).
+///
+/// Instances of '{{PARAMETER}}' in the file contents and environment
+/// are replaced with the parameter value given at instantiation,
+/// or the parameter default if none is given.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+pub struct Template {
+  /// Unique name associated with the template.
+  #[cfg_attr(feature = "mongo", unique_index)]
+  pub name: String,
+  /// A description for the template, shown in the catalog.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub description: String,
+  /// The compose file contents, containing '{{PARAMETER}}' placeholders.
+  #[serde(default)]
+  pub file_contents: String,
+  /// The environment (.env) contents, containing '{{PARAMETER}}' placeholders.
+  #[serde(default)]
+  pub environment: String,
+  /// The parameters users are prompted for when instantiating the template.
+  #[serde(default)]
+  pub parameters: Vec<TemplateParameter>,
+  /// Timestamp of template creation
+  #[serde(default)]
+  pub created_at: I64,
+  /// Timestamp of last template update
+  #[serde(default)]
+  pub updated_at: I64,
+}
+
+/// A parameter users are prompted for when instantiating a [Template].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, PartialEq,
+)]
+pub struct TemplateParameter {
+  /// The parameter name. Matches '{{name}}' placeholders in the template.
+  pub name: String,
+  /// A description of the parameter shown when prompting.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub description: String,
+  /// The value used when none is given at instantiation.
+  #[serde(default)]
+  pub default: String,
+  /// Whether a non-empty value must be given (or defaulted) at instantiation.
+  #[serde(default)]
+  pub required: bool,
+}
+
+impl Template {
+  /// Replace all '{{PARAMETER}}' placeholders in `contents`
+  /// using the given values, falling back to parameter defaults.
+  /// Fails if a required parameter ends up empty.
+  pub fn interpolate(
+    &self,
+    contents: &str,
+    values: &HashMap<String, String>,
+  ) -> anyhow::Result<String> {
+    let mut res = contents.to_string();
+    for parameter in &self.parameters {
+      let value = values
+        .get(&parameter.name)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(&parameter.default);
+      if parameter.required && value.is_empty() {
+        return Err(anyhow!(
+          "Missing value for required parameter '{}'",
+          parameter.name
+        ));
+      }
+      res =
+        res.replace(&format!("{{{{{}}}}}", parameter.name), value);
+    }
+    Ok(res)
+  }
+}
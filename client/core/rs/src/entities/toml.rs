@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
 use super::{
-  ResourceTarget, ResourceTargetVariant,
+  I64, ResourceTarget, ResourceTargetVariant,
   action::_PartialActionConfig,
   alerter::_PartialAlerterConfig,
   build::_PartialBuildConfig,
@@ -107,6 +107,13 @@ pub struct ResourcesToml {
     skip_serializing_if = "Vec::is_empty"
   )]
   pub variables: Vec<Variable>,
+
+  #[serde(
+    default,
+    alias = "onboarding_key",
+    skip_serializing_if = "Vec::is_empty"
+  )]
+  pub onboarding_keys: Vec<OnboardingKeyToml>,
 }
 
 #[typeshare]
@@ -195,3 +202,54 @@ pub struct PermissionToml {
   #[serde(default, skip_serializing_if = "IndexSet::is_empty")]
   pub specific: IndexSet<SpecificPermission>,
 }
+
+/// Manages the metadata of an existing Server onboarding key.
+/// The private key is never part of the sync, so keys must
+/// be created on Core first, and are matched by public key.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OnboardingKeyToml {
+  /// The onboarding public key. Required
+  pub public_key: String,
+
+  /// Name associated with the key for management
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub name: String,
+
+  /// Whether the key is enabled. Default: true
+  #[serde(default = "default_onboarding_key_enabled")]
+  pub enabled: bool,
+
+  /// Expiry of key, or 0 if never expires
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub expires: I64,
+
+  /// Default tags to give to Servers created with this key.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub tags: Vec<String>,
+
+  /// Optional. Copy this Server config (by name) when initializing
+  /// the Server.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub copy_server: String,
+
+  /// Also create a Builder for the Server.
+  #[serde(default, skip_serializing_if = "is_false")]
+  pub create_builder: bool,
+
+  /// The max number of Servers the key can onboard, or 0 for no limit.
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub max_uses: I64,
+
+  /// The key can only onboard a single Server.
+  #[serde(default, skip_serializing_if = "is_false")]
+  pub single_use: bool,
+}
+
+fn default_onboarding_key_enabled() -> bool {
+  true
+}
+
+fn is_zero(n: &I64) -> bool {
+  *n == 0
+}
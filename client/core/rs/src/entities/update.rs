@@ -18,6 +18,7 @@ use super::{ResourceTarget, Version};
 )]
 #[cfg_attr(feature = "mongo", doc_index({ "target.type": 1 }))]
 #[cfg_attr(feature = "mongo", sparse_doc_index({ "target.id": 1 }))]
+#[cfg_attr(feature = "mongo", sparse_doc_index({ "idempotency_key": 1 }))]
 pub struct Update {
   /// The Mongo ID of the update.
   /// This field is de/serialized from/to JSON as
@@ -78,6 +79,11 @@ pub struct Update {
   /// If the update is for resource config update, give the current (at time of Update) toml contents
   #[serde(default, skip_serializing_if = "String::is_empty")]
   pub current_toml: String,
+  /// The idempotency key passed with the execute request which created the update, if any.
+  /// Retrying the request with the same key returns this update
+  /// rather than running the execution again.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub idempotency_key: String,
 }
 
 impl Update {
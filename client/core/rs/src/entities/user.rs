@@ -72,6 +72,13 @@ pub struct User {
   pub all:
     IndexMap<ResourceTargetVariant, PermissionLevelAndSpecifics>,
 
+  /// The id of the Organization the user belongs to.
+  /// Non admin users in an Organization can only access
+  /// resources, tags, and variables in the same Organization.
+  #[cfg_attr(feature = "mongo", index)]
+  #[serde(default)]
+  pub organization: String,
+
   #[serde(default)]
   pub updated_at: I64,
 }
@@ -108,6 +115,8 @@ impl User {
         | "000000000000000000000007"
         | "Repo Manager"
         | "000000000000000000000008"
+        | "Autoscaler"
+        | "000000000000000000000009"
     )
   }
 }
@@ -141,6 +150,9 @@ pub fn admin_service_user(user_id: &str) -> Option<User> {
     "000000000000000000000008" | "Repo Manager" => {
       repo_user().to_owned().into()
     }
+    "000000000000000000000009" | "Autoscaler" => {
+      autoscale_user().to_owned().into()
+    }
     _ => None,
   }
 }
@@ -271,6 +283,20 @@ pub fn repo_user() -> &'static User {
   })
 }
 
+pub fn autoscale_user() -> &'static User {
+  static AUTOSCALE_USER: OnceLock<User> = OnceLock::new();
+  AUTOSCALE_USER.get_or_init(|| {
+    let id_name = String::from("Autoscaler");
+    User {
+      id: "000000000000000000000009".to_string(),
+      username: id_name,
+      enabled: true,
+      admin: true,
+      ..Default::default()
+    }
+  })
+}
+
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
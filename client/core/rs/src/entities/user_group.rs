@@ -52,7 +52,32 @@ pub struct UserGroup {
   pub all:
     IndexMap<ResourceTargetVariant, PermissionLevelAndSpecifics>,
 
+  /// Limits on what members of the group are able to create and run.
+  #[serde(default)]
+  pub quotas: UserGroupQuotas,
+
   /// Unix time (ms) when user group last updated
   #[serde(default)]
   pub updated_at: I64,
 }
+
+/// Limits applied to the members of a [UserGroup], shared by all members.
+/// A value of 0 means no limit.
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq,
+)]
+pub struct UserGroupQuotas {
+  /// The maximum number of Deployments created by group members
+  /// which can exist at once.
+  #[serde(default)]
+  pub max_deployments: I64,
+  /// The maximum number of Servers created by group members
+  /// which can exist at once.
+  #[serde(default)]
+  pub max_servers: I64,
+  /// The maximum number of builds group members can run
+  /// in any 24 hour period.
+  #[serde(default)]
+  pub max_builds_per_day: I64,
+}
@@ -28,4 +28,9 @@ pub struct Variable {
   /// of the database (system level encryption, network isolation, etc.)
   #[serde(default)]
   pub is_secret: bool,
+  /// The id of the Organization the variable belongs to.
+  /// Empty means the variable is shared across all Organizations.
+  #[cfg_attr(feature = "mongo", index)]
+  #[serde(default)]
+  pub organization: String,
 }
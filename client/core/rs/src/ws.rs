@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use anyhow::Context;
+use derive_variants::ExtractVariant as _;
 use futures::{SinkExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serror::serialize_error;
@@ -12,7 +13,15 @@ use tracing::{Instrument, debug, info, info_span, warn};
 use typeshare::typeshare;
 use uuid::Uuid;
 
-use crate::{KomodoClient, entities::update::UpdateListItem};
+use crate::{
+  KomodoClient,
+  entities::{
+    ResourceTarget,
+    event::{CoreEvent, CoreEventDataVariant},
+    notification::Notification,
+    update::UpdateListItem,
+  },
+};
 
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,9 +43,47 @@ impl WsLoginMessage {
   }
 }
 
+/// Sent over the update websocket alongside updates
+/// when a [Notification] is delivered to the logged in user.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum WsNotificationMessage {
+  #[serde(rename = "NOTIFICATION")]
+  Notification(Notification),
+}
+
+/// Sent by the client over the `/ws/events` websocket after login
+/// to filter the [CoreEvent]s it receives.
+/// Can be sent again at any time to replace the filter.
+///
+/// Events are only sent for targets the user has permission to see.
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WsEventSubscription {
+  /// Only receive these kinds of events.
+  /// If empty, receives all kinds.
+  #[serde(default)]
+  pub kinds: Vec<CoreEventDataVariant>,
+  /// Only receive events about these targets.
+  /// If empty, receives events for all targets.
+  #[serde(default)]
+  pub targets: Vec<ResourceTarget>,
+}
+
+impl WsEventSubscription {
+  pub fn matches(&self, event: &CoreEvent) -> bool {
+    (self.kinds.is_empty()
+      || self.kinds.contains(&event.data.extract_variant()))
+      && (self.targets.is_empty()
+        || self.targets.contains(&event.target))
+  }
+}
+
 #[derive(Debug, Clone)]
 pub enum UpdateWsMessage {
   Update(UpdateListItem),
+  Notification(Notification),
   Error(UpdateWsError),
   Disconnected,
   Reconnected,
@@ -232,12 +279,20 @@ impl KomodoClient {
                   .context("failed to recieve message")
                 {
                   Ok(Some(Message::Text(msg))) => {
-                    match serde_json::from_str::<UpdateListItem>(&msg) {
+                    let parsed = serde_json::from_str::<UpdateListItem>(&msg)
+                      .map(UpdateWsMessage::Update)
+                      .or_else(|_| {
+                        serde_json::from_str::<WsNotificationMessage>(&msg)
+                          .map(|WsNotificationMessage::Notification(n)| {
+                            UpdateWsMessage::Notification(n)
+                          })
+                      });
+                    match parsed {
                       Ok(msg) => {
                         debug!(
                           "got recognized message: {msg:?} | inner uuid {inner_uuid} | outer uuid {outer_uuid} | master uuid {master_uuid}"
                         );
-                        let _ = tx.send(UpdateWsMessage::Update(msg));
+                        let _ = tx.send(msg);
                       }
                       Err(_) => {
                         warn!(
@@ -7,6 +7,8 @@ import {
 } from "./responses.js";
 import {
   terminal_methods,
+  AttachCallbacks,
+  ConnectAttachQuery,
   ConnectExecQuery,
   ExecuteExecBody,
   TerminalCallbacks,
@@ -15,20 +17,30 @@ import {
   AuthRequest,
   BatchExecutionResponse,
   ConnectTerminalQuery,
+  CoreEvent,
   ExecuteRequest,
   ExecuteTerminalBody,
+  Notification,
   ReadRequest,
   Update,
   UpdateListItem,
   UpdateStatus,
   UserRequest,
   WriteRequest,
+  WsEventSubscription,
   WsLoginMessage,
+  WsNotificationMessage,
 } from "./types.js";
 
 export * as Types from "./types.js";
 
-export type { ConnectExecQuery, ExecuteExecBody, TerminalCallbacks };
+export type {
+  AttachCallbacks,
+  ConnectAttachQuery,
+  ConnectExecQuery,
+  ExecuteExecBody,
+  TerminalCallbacks,
+};
 
 export type InitOptions =
   | { type: "jwt"; params: { jwt: string } }
@@ -61,7 +73,8 @@ export function KomodoClient(url: string, options: InitOptions) {
   const request = <Params, Res>(
     path: "/auth" | "/user" | "/read" | "/execute" | "/write",
     type: string,
-    params: Params
+    params: Params,
+    headers?: Record<string, string>
   ): Promise<Res> =>
     new Promise(async (res, rej) => {
       try {
@@ -80,6 +93,7 @@ export function KomodoClient(url: string, options: InitOptions) {
                 }
               : {}),
             "content-type": "application/json",
+            ...headers,
           },
         });
         if (response.status === 200) {
@@ -169,12 +183,14 @@ export function KomodoClient(url: string, options: InitOptions) {
     Req extends Extract<ExecuteRequest, { type: T }>
   >(
     type: T,
-    params: Req["params"]
+    params: Req["params"],
+    idempotency_key?: string
   ) =>
     await request<Req["params"], ExecuteResponses[Req["type"]]>(
       "/execute",
       type,
-      params
+      params,
+      idempotency_key ? { "idempotency-key": idempotency_key } : undefined
     );
 
   const execute_and_poll = async <
@@ -222,11 +238,13 @@ export function KomodoClient(url: string, options: InitOptions) {
 
   const get_update_websocket = ({
     on_update,
+    on_notification,
     on_login,
     on_open,
     on_close,
   }: {
     on_update: (update: UpdateListItem) => void;
+    on_notification?: (notification: Notification) => void;
     on_login?: () => void;
     on_open?: () => void;
     on_close?: () => void;
@@ -256,7 +274,64 @@ export function KomodoClient(url: string, options: InitOptions) {
 
     ws.addEventListener("message", ({ data }: MessageEvent) => {
       if (data == "LOGGED_IN") return on_login?.();
-      on_update(JSON.parse(data));
+      const msg = JSON.parse(data);
+      if ((msg as WsNotificationMessage).type === "NOTIFICATION") {
+        return on_notification?.((msg as WsNotificationMessage).data);
+      }
+      on_update(msg);
+    });
+
+    if (on_close) {
+      ws.addEventListener("close", on_close);
+    }
+
+    return ws;
+  };
+
+  const get_event_websocket = ({
+    subscription,
+    on_event,
+    on_login,
+    on_open,
+    on_close,
+  }: {
+    subscription?: WsEventSubscription;
+    on_event: (event: CoreEvent) => void;
+    on_login?: () => void;
+    on_open?: () => void;
+    on_close?: () => void;
+  }) => {
+    const ws = new WebSocket(url.replace("http", "ws") + "/ws/events");
+
+    // Handle login on websocket open
+    ws.addEventListener("open", () => {
+      on_open?.();
+      const login_msg: WsLoginMessage =
+        options.type === "jwt"
+          ? {
+              type: "Jwt",
+              params: {
+                jwt: options.params.jwt,
+              },
+            }
+          : {
+              type: "ApiKeys",
+              params: {
+                key: options.params.key,
+                secret: options.params.secret,
+              },
+            };
+      ws.send(JSON.stringify(login_msg));
+    });
+
+    ws.addEventListener("message", ({ data }: MessageEvent) => {
+      if (data == "LOGGED_IN") {
+        if (subscription) ws.send(JSON.stringify(subscription));
+        return on_login?.();
+      }
+      const msg = JSON.parse(data);
+      if (msg.ts === undefined) return console.error(msg);
+      on_event(msg);
     });
 
     if (on_close) {
@@ -268,6 +343,7 @@ export function KomodoClient(url: string, options: InitOptions) {
 
   const subscribe_to_update_websocket = async ({
     on_update,
+    on_notification,
     on_open,
     on_login,
     on_close,
@@ -277,6 +353,7 @@ export function KomodoClient(url: string, options: InitOptions) {
     on_cancel,
   }: {
     on_update: (update: UpdateListItem) => void;
+    on_notification?: (notification: Notification) => void;
     on_login?: () => void;
     on_open?: () => void;
     on_close?: () => void;
@@ -296,6 +373,7 @@ export function KomodoClient(url: string, options: InitOptions) {
           on_open,
           on_login,
           on_update,
+          on_notification,
           on_close,
         });
 
@@ -345,6 +423,7 @@ export function KomodoClient(url: string, options: InitOptions) {
     connect_stack_attach,
     execute_stack_exec,
     execute_stack_exec_stream,
+    tail_host_file_stream,
   } = terminal_methods(url, state);
 
   return {
@@ -409,6 +488,9 @@ export function KomodoClient(url: string, options: InitOptions) {
      * NOTE. These calls return immediately when the update is created, NOT when the execution task finishes.
      * To have the call only return when the task finishes, use [execute_and_poll_until_complete].
      *
+     * Pass an `idempotency_key` to make retries safe. If an update was already
+     * created with the same key, it is returned instead of executing again.
+     *
      * https://docs.rs/komodo_client/latest/komodo_client/api/execute/index.html
      */
     execute,
@@ -442,6 +524,13 @@ export function KomodoClient(url: string, options: InitOptions) {
      * Note. Awaiting this method will never finish.
      */
     subscribe_to_update_websocket,
+    /**
+     * Connects to the event websocket, performs login,
+     * sends the optional subscription filter and attaches handlers.
+     * Returns the WebSocket handle, on which an updated
+     * `WsEventSubscription` can be sent at any time.
+     */
+    get_event_websocket,
     /**
      * Subscribes to terminal io over websocket message,
      * for use with xtermjs.
@@ -673,5 +762,26 @@ export function KomodoClient(url: string, options: InitOptions) {
      * ```
      */
     execute_stack_exec_stream,
+    /**
+     * Streams the lines of a file on the Server host.
+     * The path must be inside the `allowed_log_paths`
+     * in the Periphery config. Server Logs permission required.
+     *
+     * With `follow`, the stream stays open until aborted.
+     *
+     * ```ts
+     * const stream = await komodo.tail_host_file_stream({
+     *   server: "my-server",
+     *   path: "/var/log/syslog",
+     *   lines: 100,
+     *   follow: false,
+     * });
+     *
+     * for await (const line of stream) {
+     *   console.log(line);
+     * }
+     * ```
+     */
+    tail_host_file_stream,
   };
 }
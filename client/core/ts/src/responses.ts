@@ -36,6 +36,13 @@ export type ReadResponses = {
   GetUserGroup: Types.GetUserGroupResponse;
   ListUserGroups: Types.ListUserGroupsResponse;
 
+  // ==== NOTIFICATION ====
+  ListNotifications: Types.ListNotificationsResponse;
+
+  // ==== ORGANIZATION ====
+  GetOrganization: Types.GetOrganizationResponse;
+  ListOrganizations: Types.ListOrganizationsResponse;
+
   // ==== PROCEDURE ====
   GetProceduresSummary: Types.GetProceduresSummaryResponse;
   GetProcedure: Types.GetProcedureResponse;
@@ -58,11 +65,18 @@ export type ReadResponses = {
   GetServer: Types.GetServerResponse;
   GetServerState: Types.GetServerStateResponse;
   GetPeripheryInformation: Types.GetPeripheryInformationResponse;
+  ListConnectionShards: Types.ListConnectionShardsResponse;
+  GetServerConnectionMetrics: Types.GetServerConnectionMetricsResponse;
+  ListTransportTrace: Types.ListTransportTraceResponse;
   GetServerActionState: Types.GetServerActionStateResponse;
   GetHistoricalServerStats: Types.GetHistoricalServerStatsResponse;
   ListServers: Types.ListServersResponse;
   ListFullServers: Types.ListFullServersResponse;
   ListTerminals: Types.ListTerminalsResponse;
+  ListTerminalRecordings: Types.ListTerminalRecordingsResponse;
+  GetTerminalRecording: Types.GetTerminalRecordingResponse;
+  ListActiveTerminalSessions: Types.ListActiveTerminalSessionsResponse;
+  ListServerSchedules: Types.ListServerSchedulesResponse;
 
   // ==== DOCKER ====
   GetDockerContainersSummary: Types.GetDockerContainersSummaryResponse;
@@ -81,9 +95,15 @@ export type ReadResponses = {
   ListDockerVolumes: Types.ListDockerVolumesResponse;
   InspectDockerVolume: Types.InspectDockerVolumeResponse;
 
+  // ==== SYSTEMD ====
+  ListSystemdUnits: Types.ListSystemdUnitsResponse;
+  GetSystemdUnitStatus: Types.GetSystemdUnitStatusResponse;
+  GetSystemdUnitJournal: Types.GetSystemdUnitJournalResponse;
+
   // ==== STACK ====
   GetStacksSummary: Types.GetStacksSummaryResponse;
   GetStack: Types.GetStackResponse;
+  GetStackInheritance: Types.GetStackInheritanceResponse;
   GetStackActionState: Types.GetStackActionStateResponse;
   GetStackLog: Types.GetStackLogResponse;
   SearchStackLog: Types.SearchStackLogResponse;
@@ -103,6 +123,7 @@ export type ReadResponses = {
   GetDeploymentLog: Types.GetDeploymentLogResponse;
   SearchDeploymentLog: Types.SearchDeploymentLogResponse;
   InspectDeploymentContainer: Types.InspectDeploymentContainerResponse;
+  GetDeploymentInheritance: Types.GetDeploymentInheritanceResponse;
   ListDeployments: Types.ListDeploymentsResponse;
   ListFullDeployments: Types.ListFullDeploymentsResponse;
   ListCommonDeploymentExtraArgs: Types.ListCommonDeploymentExtraArgsResponse;
@@ -150,6 +171,8 @@ export type ReadResponses = {
   // ==== TAG ====
   GetTag: Types.GetTagResponse;
   ListTags: Types.ListTagsResponse;
+  GetTagUsageReport: Types.GetTagUsageReportResponse;
+  ListTagUsageReports: Types.ListTagUsageReportsResponse;
 
   // ==== UPDATE ====
   GetUpdate: Types.GetUpdateResponse;
@@ -163,11 +186,22 @@ export type ReadResponses = {
   GetSystemInformation: Types.GetSystemInformationResponse;
   GetSystemStats: Types.GetSystemStatsResponse;
   ListSystemProcesses: Types.ListSystemProcessesResponse;
+  ListListeningSockets: Types.ListListeningSocketsResponse;
+  SuggestServerPlacement: Types.SuggestServerPlacementResponse;
+  CheckServerPlacement: Types.CheckServerPlacementResponse;
 
   // ==== VARIABLE ====
   GetVariable: Types.GetVariableResponse;
   ListVariables: Types.ListVariablesResponse;
 
+  // ==== TEMPLATE ====
+  GetTemplate: Types.GetTemplateResponse;
+  ListTemplates: Types.ListTemplatesResponse;
+
+  // ==== DASHBOARD ====
+  GetDashboard: Types.GetDashboardResponse;
+  ListDashboards: Types.ListDashboardsResponse;
+
   // ==== PROVIDER ====
   GetGitProviderAccount: Types.GetGitProviderAccountResponse;
   ListGitProviderAccounts: Types.ListGitProviderAccountsResponse;
@@ -176,6 +210,15 @@ export type ReadResponses = {
 
   // ==== ONBOARDING KEY ====
   ListOnboardingKeys: Types.ListOnboardingKeysResponse;
+
+  // ==== PENDING SERVER ====
+  ListPendingServers: Types.ListPendingServersResponse;
+
+  // ==== REVOKED KEY ====
+  ListRevokedKeys: Types.ListRevokedKeysResponse;
+
+  // ==== AUTH EVENT ====
+  ListAuthEvents: Types.ListAuthEventsResponse;
 };
 
 export type WriteResponses = {
@@ -199,6 +242,18 @@ export type WriteResponses = {
   RemoveUserFromUserGroup: Types.UserGroup;
   SetUsersInUserGroup: Types.UserGroup;
   SetEveryoneUserGroup: Types.UserGroup;
+  UpdateUserGroupQuotas: Types.UserGroup;
+
+  // ==== NOTIFICATION ====
+  AckNotifications: Types.AckNotificationsResponse;
+  SendNotification: Types.SendNotificationResponse;
+
+  // ==== ORGANIZATION ====
+  CreateOrganization: Types.Organization;
+  UpdateOrganization: Types.Organization;
+  DeleteOrganization: Types.Organization;
+  SetUserOrganization: Types.SetUserOrganizationResponse;
+  SetResourceOrganization: Types.SetResourceOrganizationResponse;
 
   // ==== PERMISSIONS ====
   UpdateUserAdmin: Types.UpdateUserAdminResponse;
@@ -219,8 +274,13 @@ export type WriteResponses = {
   CreateTerminal: Types.NoData;
   DeleteTerminal: Types.NoData;
   DeleteAllTerminals: Types.NoData;
+  ClearTerminalHistory: Types.NoData;
+  DisconnectTerminalSession: Types.NoData;
   UpdateServerPublicKey: Types.Update;
+  AcceptServerTlsPin: Types.Update;
   RotateServerKeys: Types.Update;
+  UpsertServerSchedule: Types.ServerScheduleStatus;
+  DeleteServerSchedule: Types.NoData;
 
   // ==== STACK ====
   CreateStack: Types.Stack;
@@ -307,6 +367,17 @@ export type WriteResponses = {
   UpdateVariableIsSecret: Types.UpdateVariableIsSecretResponse;
   DeleteVariable: Types.DeleteVariableResponse;
 
+  // ==== TEMPLATE ====
+  CreateTemplate: Types.CreateTemplateResponse;
+  UpdateTemplate: Types.UpdateTemplateResponse;
+  DeleteTemplate: Types.DeleteTemplateResponse;
+  InstantiateTemplate: Types.InstantiateTemplateResponse;
+
+  // ==== DASHBOARD ====
+  CreateDashboard: Types.CreateDashboardResponse;
+  UpdateDashboard: Types.UpdateDashboardResponse;
+  DeleteDashboard: Types.DeleteDashboardResponse;
+
   // ==== PROVIDERS ====
   CreateGitProviderAccount: Types.CreateGitProviderAccountResponse;
   UpdateGitProviderAccount: Types.UpdateGitProviderAccountResponse;
@@ -320,8 +391,19 @@ export type WriteResponses = {
   UpdateOnboardingKey: Types.UpdateOnboardingKeyResponse;
   DeleteOnboardingKey: Types.DeleteOnboardingKeyResponse;
 
+  // ==== PENDING SERVER ====
+  ApprovePendingServer: Types.ApprovePendingServerResponse;
+  DeletePendingServer: Types.DeletePendingServerResponse;
+
+  // ==== REVOKED KEY ====
+  RevokePublicKey: Types.RevokePublicKeyResponse;
+  UnrevokePublicKey: Types.UnrevokePublicKeyResponse;
+
   // ==== ALERT ====
   CloseAlert: Types.NoData;
+
+  // ==== MAINTENANCE ====
+  SetReadOnlyMode: Types.SetReadOnlyModeResponse;
 };
 
 export type ExecuteResponses = {
@@ -332,6 +414,7 @@ export type ExecuteResponses = {
   UnpauseContainer: Types.Update;
   StopContainer: Types.Update;
   DestroyContainer: Types.Update;
+  CommitContainer: Types.Update;
   StartAllContainers: Types.Update;
   RestartAllContainers: Types.Update;
   PauseAllContainers: Types.Update;
@@ -342,11 +425,21 @@ export type ExecuteResponses = {
   PruneNetworks: Types.Update;
   DeleteImage: Types.Update;
   PruneImages: Types.Update;
+  TransferImage: Types.Update;
   DeleteVolume: Types.Update;
   PruneVolumes: Types.Update;
   PruneDockerBuilders: Types.Update;
   PruneBuildx: Types.Update;
+  PruneBuildCache: Types.Update;
   PruneSystem: Types.Update;
+  RunServerExtension: Types.Update;
+  KillSystemProcess: Types.Update;
+  SetSystemProcessPriority: Types.Update;
+  StartSystemdUnit: Types.Update;
+  StopSystemdUnit: Types.Update;
+  RestartSystemdUnit: Types.Update;
+  RebootServer: Types.Update;
+  ShutdownServer: Types.Update;
 
   // ==== STACK ====
   DeployStack: Types.Update;
@@ -373,6 +466,7 @@ export type ExecuteResponses = {
   UnpauseDeployment: Types.Update;
   StopDeployment: Types.Update;
   DestroyDeployment: Types.Update;
+  RollbackDeployment: Types.Update;
   BatchDestroyDeployment: Types.BatchExecutionResponse;
 
   // ==== BUILD ====
@@ -409,6 +503,9 @@ export type ExecuteResponses = {
   UnpauseStackService: Types.Update;
   DestroyStackService: Types.Update;
   RunStackService: Types.Update;
+  ScaleStack: Types.Update;
+  PromoteStack: Types.Update;
+  MigrateStack: Types.Update;
 
   // ==== ALERTER ====
   TestAlerter: Types.Update;
@@ -7,10 +7,12 @@ import {
   ConnectStackAttachQuery,
   ConnectStackExecQuery,
   ConnectTerminalQuery,
+  ContainerAttachInfo,
   ExecuteContainerExecBody,
   ExecuteDeploymentExecBody,
   ExecuteStackExecBody,
   ExecuteTerminalBody,
+  TailHostFileBody,
   WsLoginMessage,
 } from "./types";
 
@@ -21,6 +23,11 @@ export type TerminalCallbacks = {
   on_close?: () => void;
 };
 
+export type AttachCallbacks = TerminalCallbacks & {
+  /** Called with how the container main process was started, once attached. */
+  on_attach_info?: (info: ContainerAttachInfo) => void;
+};
+
 export type ConnectExecQuery =
   | {
       type: "container";
@@ -220,7 +227,7 @@ export const terminal_methods = (url: string, state: ClientState) => {
     ...callbacks
   }: {
     query: ConnectContainerAttachQuery;
-  } & TerminalCallbacks) =>
+  } & AttachCallbacks) =>
     connect_attach({ query: { type: "container", query }, ...callbacks });
 
   const connect_deployment_attach = ({
@@ -228,7 +235,7 @@ export const terminal_methods = (url: string, state: ClientState) => {
     ...callbacks
   }: {
     query: ConnectDeploymentAttachQuery;
-  } & TerminalCallbacks) =>
+  } & AttachCallbacks) =>
     connect_attach({ query: { type: "deployment", query }, ...callbacks });
 
   const connect_stack_attach = ({
@@ -236,7 +243,7 @@ export const terminal_methods = (url: string, state: ClientState) => {
     ...callbacks
   }: {
     query: ConnectStackAttachQuery;
-  } & TerminalCallbacks) =>
+  } & AttachCallbacks) =>
     connect_attach({ query: { type: "stack", query }, ...callbacks });
 
   const connect_attach = ({
@@ -245,11 +252,16 @@ export const terminal_methods = (url: string, state: ClientState) => {
     on_login,
     on_open,
     on_close,
+    on_attach_info,
   }: {
     query: ConnectAttachQuery;
-  } & TerminalCallbacks) => {
+  } & AttachCallbacks) => {
+    // Leave out unset optional params, eg. detach_keys
     const url_query = new URLSearchParams(
-      query as any as Record<string, string>
+      Object.entries(query).filter(([_, value]) => value !== undefined) as [
+        string,
+        string,
+      ][]
     ).toString();
     const ws = new WebSocket(
       url.replace("http", "ws") + `/ws/${type}/terminal/attach?` + url_query
@@ -277,7 +289,16 @@ export const terminal_methods = (url: string, state: ClientState) => {
     ws.onmessage = (e) => {
       if (e.data == "LOGGED_IN") {
         ws.binaryType = "arraybuffer";
-        ws.onmessage = (e) => on_message?.(e);
+        ws.onmessage = (e) => {
+          if (
+            typeof e.data === "string" &&
+            e.data.startsWith("ATTACH_INFO:")
+          ) {
+            on_attach_info?.(JSON.parse(e.data.slice("ATTACH_INFO:".length)));
+          } else {
+            on_message?.(e);
+          }
+        };
         on_login?.();
         return;
       } else {
@@ -334,6 +355,9 @@ export const terminal_methods = (url: string, state: ClientState) => {
   const execute_exec_stream = (request: ExecuteExecBody) =>
     execute_stream(`/terminal/execute/${request.type}`, request.body);
 
+  const tail_host_file_stream = (body: TailHostFileBody) =>
+    execute_stream("/terminal/tail/host_file", body);
+
   const execute_stream = (path: string, request: any) =>
     new Promise<AsyncIterable<string>>(async (res, rej) => {
       try {
@@ -426,5 +450,6 @@ export const terminal_methods = (url: string, state: ClientState) => {
     connect_stack_attach,
     execute_stack_exec,
     execute_stack_exec_stream,
+    tail_host_file_stream,
   };
 };
@@ -45,6 +45,11 @@ export interface Resource<Config, Info> {
 	template?: boolean;
 	/** Tag Ids */
 	tags?: string[];
+	/**
+	 * The id of the Organization the resource belongs to.
+	 * Empty means the resource is not scoped to an Organization.
+	 */
+	organization?: string;
 	/** Resource-specific information (not user configurable). */
 	info?: Info;
 	/** Resource-specific configuration. */
@@ -285,6 +290,22 @@ export interface MaintenanceWindow {
 	enabled: boolean;
 }
 
+/**
+ * Global read-only mode of Core. While enabled,
+ * all write and execute requests are rejected with 503,
+ * while reads and monitoring continue as normal.
+ */
+export interface ReadOnlyMode {
+	/** Whether Core is in read-only mode. */
+	enabled: boolean;
+	/** The reason for read-only mode, shown to users when requests are rejected. */
+	reason?: string;
+	/** Unix timestamp in milliseconds when the mode was last changed. */
+	since?: I64;
+	/** The username of the admin who last changed the mode. */
+	set_by?: string;
+}
+
 export interface AlerterConfig {
 	/** Whether the alerter is enabled */
 	enabled?: boolean;
@@ -357,6 +378,7 @@ export enum Operation {
 	UnpauseContainer = "UnpauseContainer",
 	StopContainer = "StopContainer",
 	DestroyContainer = "DestroyContainer",
+	CommitContainer = "CommitContainer",
 	StartAllContainers = "StartAllContainers",
 	RestartAllContainers = "RestartAllContainers",
 	PauseAllContainers = "PauseAllContainers",
@@ -368,11 +390,22 @@ export enum Operation {
 	PruneNetworks = "PruneNetworks",
 	DeleteImage = "DeleteImage",
 	PruneImages = "PruneImages",
+	TransferImage = "TransferImage",
 	DeleteVolume = "DeleteVolume",
 	PruneVolumes = "PruneVolumes",
 	PruneDockerBuilders = "PruneDockerBuilders",
 	PruneBuildx = "PruneBuildx",
+	PruneBuildCache = "PruneBuildCache",
 	PruneSystem = "PruneSystem",
+	RunServerExtension = "RunServerExtension",
+	KillSystemProcess = "KillSystemProcess",
+	SetSystemProcessPriority = "SetSystemProcessPriority",
+	StartSystemdUnit = "StartSystemdUnit",
+	StopSystemdUnit = "StopSystemdUnit",
+	RestartSystemdUnit = "RestartSystemdUnit",
+	RebootServer = "RebootServer",
+	ShutdownServer = "ShutdownServer",
+	RunServerSchedule = "RunServerSchedule",
 	CreateStack = "CreateStack",
 	UpdateStack = "UpdateStack",
 	RenameStack = "RenameStack",
@@ -388,6 +421,9 @@ export enum Operation {
 	StopStack = "StopStack",
 	DestroyStack = "DestroyStack",
 	RunStackService = "RunStackService",
+	PromoteStack = "PromoteStack",
+	ScaleStack = "ScaleStack",
+	MigrateStack = "MigrateStack",
 	DeployStackService = "DeployStackService",
 	PullStackService = "PullStackService",
 	StartStackService = "StartStackService",
@@ -408,6 +444,7 @@ export enum Operation {
 	UnpauseDeployment = "UnpauseDeployment",
 	StopDeployment = "StopDeployment",
 	DestroyDeployment = "DestroyDeployment",
+	RollbackDeployment = "RollbackDeployment",
 	CreateBuild = "CreateBuild",
 	UpdateBuild = "UpdateBuild",
 	RenameBuild = "RenameBuild",
@@ -458,6 +495,10 @@ export enum Operation {
 	CreateVariable = "CreateVariable",
 	UpdateVariableValue = "UpdateVariableValue",
 	DeleteVariable = "DeleteVariable",
+	CreateTemplate = "CreateTemplate",
+	UpdateTemplate = "UpdateTemplate",
+	DeleteTemplate = "DeleteTemplate",
+	InstantiateTemplate = "InstantiateTemplate",
 	CreateGitProviderAccount = "CreateGitProviderAccount",
 	UpdateGitProviderAccount = "UpdateGitProviderAccount",
 	DeleteGitProviderAccount = "DeleteGitProviderAccount",
@@ -546,6 +587,12 @@ export interface Update {
 	prev_toml?: string;
 	/** If the update is for resource config update, give the current (at time of Update) toml contents */
 	current_toml?: string;
+	/**
+	 * The idempotency key passed with the execute request which created the update, if any.
+	 * Retrying the request with the same key returns this update
+	 * rather than running the execution again.
+	 */
+	idempotency_key?: string;
 }
 
 export type BoxUpdate = Update;
@@ -663,6 +710,26 @@ export interface BuildConfig {
 	skip_secret_interp?: boolean;
 	/** Whether to use buildx to build (eg `docker buildx build ...`) */
 	use_buildx?: boolean;
+	/**
+	 * Build a multi platform image for these platforms,
+	 * ie `linux/amd64`, `linux/arm64`. Always uses buildx.
+	 * Empty builds for the platform of the builder host.
+	 */
+	platforms?: string[];
+	/**
+	 * Import the build cache from this registry image,
+	 * ie `ghcr.io/org/app:buildcache`, so builds on any server
+	 * reuse the cached layers. Full buildx `--cache-from`
+	 * specs (containing `=`) are passed through as is.
+	 */
+	cache_from?: string;
+	/**
+	 * Export the build cache to this registry image, with all
+	 * intermediate layers (`mode=max`). Always uses buildx.
+	 * Full buildx `--cache-to` specs (containing `=`)
+	 * are passed through as is.
+	 */
+	cache_to?: string;
 	/** Any extra docker cli arguments to be included in the build command */
 	extra_args?: string[];
 	/** The optional command run after repo clone and before docker build. */
@@ -833,6 +900,7 @@ export type Execution =
 	| { type: "UnpauseDeployment", params: UnpauseDeployment }
 	| { type: "StopDeployment", params: StopDeployment }
 	| { type: "DestroyDeployment", params: DestroyDeployment }
+	| { type: "RollbackDeployment", params: RollbackDeployment }
 	| { type: "BatchDestroyDeployment", params: BatchDestroyDeployment }
 	/** Clone the target repo */
 	| { type: "CloneRepo", params: CloneRepo }
@@ -848,6 +916,7 @@ export type Execution =
 	| { type: "UnpauseContainer", params: UnpauseContainer }
 	| { type: "StopContainer", params: StopContainer }
 	| { type: "DestroyContainer", params: DestroyContainer }
+	| { type: "CommitContainer", params: CommitContainer }
 	| { type: "StartAllContainers", params: StartAllContainers }
 	| { type: "RestartAllContainers", params: RestartAllContainers }
 	| { type: "PauseAllContainers", params: PauseAllContainers }
@@ -858,11 +927,21 @@ export type Execution =
 	| { type: "PruneNetworks", params: PruneNetworks }
 	| { type: "DeleteImage", params: DeleteImage }
 	| { type: "PruneImages", params: PruneImages }
+	| { type: "TransferImage", params: TransferImage }
 	| { type: "DeleteVolume", params: DeleteVolume }
 	| { type: "PruneVolumes", params: PruneVolumes }
 	| { type: "PruneDockerBuilders", params: PruneDockerBuilders }
 	| { type: "PruneBuildx", params: PruneBuildx }
+	| { type: "PruneBuildCache", params: PruneBuildCache }
 	| { type: "PruneSystem", params: PruneSystem }
+	| { type: "RunServerExtension", params: RunServerExtension }
+	| { type: "KillSystemProcess", params: KillSystemProcess }
+	| { type: "SetSystemProcessPriority", params: SetSystemProcessPriority }
+	| { type: "StartSystemdUnit", params: StartSystemdUnit }
+	| { type: "StopSystemdUnit", params: StopSystemdUnit }
+	| { type: "RestartSystemdUnit", params: RestartSystemdUnit }
+	| { type: "RebootServer", params: RebootServer }
+	| { type: "ShutdownServer", params: ShutdownServer }
 	/** Execute a Resource Sync. (alias: `sync`) */
 	| { type: "RunSync", params: RunSync }
 	/** Commit a Resource Sync. (alias: `commit`) */
@@ -882,6 +961,9 @@ export type Execution =
 	| { type: "DestroyStack", params: DestroyStack }
 	| { type: "BatchDestroyStack", params: BatchDestroyStack }
 	| { type: "RunStackService", params: RunStackService }
+	| { type: "ScaleStack", params: ScaleStack }
+	| { type: "PromoteStack", params: PromoteStack }
+	| { type: "MigrateStack", params: MigrateStack }
 	| { type: "TestAlerter", params: TestAlerter }
 	| { type: "SendAlert", params: SendAlert }
 	| { type: "ClearRepoCache", params: ClearRepoCache }
@@ -909,6 +991,22 @@ export interface ProcedureStage {
 	executions?: EnabledExecution[];
 }
 
+/**
+ * What to do when a scheduled run comes up
+ * while the previous run is still in progress.
+ */
+export enum ScheduleOverlapPolicy {
+	/** Skip the scheduled run. */
+	Skip = "Skip",
+	/**
+	 * Run once the previous run finishes.
+	 * At most one run is queued at a time.
+	 */
+	Queue = "Queue",
+	/** Cancel the previous run, then run. */
+	CancelPrevious = "CancelPrevious",
+}
+
 /** Config for the [Procedure] */
 export interface ProcedureConfig {
 	/** The stages to be run by the procedure. */
@@ -944,6 +1042,17 @@ export interface ProcedureConfig {
 	 * https://en.wikipedia.org/wiki/List_of_tz_database_time_zones.
 	 */
 	schedule_timezone?: string;
+	/**
+	 * Dates on which scheduled runs are skipped, eg. holidays,
+	 * in the schedule timezone. Either `YYYY-MM-DD` for a single date,
+	 * or `MM-DD` to skip the date every year.
+	 */
+	schedule_exceptions?: string[];
+	/**
+	 * What to do when a scheduled run comes up
+	 * while the procedure is still running.
+	 */
+	schedule_overlap?: ScheduleOverlapPolicy;
 	/** Whether to send alerts when the schedule was run. */
 	schedule_alert: boolean;
 	/** Whether to send alerts when this procedure fails. */
@@ -1090,6 +1199,12 @@ export interface User {
 	recents?: Record<ResourceTarget["type"], string[]>;
 	/** Give the user elevated permissions on all resources of a certain type */
 	all?: Record<ResourceTarget["type"], PermissionLevelAndSpecifics | PermissionLevel>;
+	/**
+	 * The id of the Organization the user belongs to.
+	 * Non admin users in an Organization can only access
+	 * resources, tags, and variables in the same Organization.
+	 */
+	organization?: string;
 	updated_at?: I64;
 }
 
@@ -1122,10 +1237,17 @@ export interface Variable {
 	 * of the database (system level encryption, network isolation, etc.)
 	 */
 	is_secret?: boolean;
+	/**
+	 * The id of the Organization the variable belongs to.
+	 * Empty means the variable is shared across all Organizations.
+	 */
+	organization?: string;
 }
 
 export type CreateVariableResponse = Variable;
 
+export type AckNotificationsResponse = NoData;
+
 export type DeleteApiKeyForServiceUserResponse = NoData;
 
 export type DeleteApiKeyResponse = NoData;
@@ -1164,16 +1286,272 @@ export interface OnboardingKey {
 	copy_server?: string;
 	/** Also create a Builder for the Server. */
 	create_builder?: boolean;
+	/** The max number of Servers the key can onboard, or 0 for no limit. */
+	max_uses?: I64;
+	/** The key can only onboard a single Server. */
+	single_use?: boolean;
+	/** The number of Servers onboarded (or currently onboarding) with the key. */
+	uses?: I64;
+	/** The remaining uses if the key is limited. Only set in API responses. */
+	remaining_uses?: I64;
 }
 
 export type DeleteOnboardingKeyResponse = OnboardingKey;
 
+/**
+ * A Periphery public key which Core refuses to accept
+ * in the connection handshake, even if a Server still
+ * references it as the expected public key.
+ */
+export interface RevokedKey {
+	/** The revoked public key. */
+	public_key: string;
+	/** Why the key was revoked, eg. "Leaked in CI logs". */
+	reason?: string;
+	/** The id of the admin who revoked the key. */
+	revoked_by?: string;
+	/** Timestamp of the revocation. */
+	revoked_at?: I64;
+}
+
+export type ListRevokedKeysResponse = RevokedKey[];
+
+/**
+ * A Server which passed the onboarding login while
+ * `onboarding_require_approval` is enabled, and is
+ * waiting for an admin to approve its creation.
+ */
+export interface PendingServer {
+	/**
+	 * The Mongo ID of the PendingServer.
+	 * This field is de/serialized from/to JSON as
+	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized PendingServer) }`
+	 */
+	_id?: MongoId;
+	/** The name the Server will be created with. */
+	name?: string;
+	/** The Periphery public key the Server will expect. */
+	public_key: string;
+	/** The source IP of the latest onboarding attempt. */
+	remote?: string;
+	/** The host Periphery reported, if any. */
+	host?: string;
+	/**
+	 * The public key of the onboarding key used.
+	 * Its settings (template, tags, builder) are
+	 * applied when the Server is approved.
+	 */
+	onboarding_key?: string;
+	/** The name of the onboarding key used. */
+	onboarding_key_name?: string;
+	/** Timestamp of the first onboarding attempt. */
+	requested_at?: I64;
+	/** Timestamp of the latest onboarding attempt. */
+	last_attempt_at?: I64;
+}
+
+export type ListPendingServersResponse = PendingServer[];
+
+export enum AuthEventDirection {
+	/** Periphery connected to Core, Core runs the `ServerLoginFlow`. */
+	PeripheryToCore = "PeripheryToCore",
+	/** Core connected to Periphery, Core runs the `ClientLoginFlow`. */
+	CoreToPeriphery = "CoreToPeriphery",
+}
+
+export enum AuthEventFlow {
+	/** The public key handshake. */
+	Standard = "Standard",
+	/** A new Server logging in with an onboarding key. */
+	Onboarding = "Onboarding",
+	/** The legacy v1 passkey login. */
+	V1Passkey = "V1Passkey",
+}
+
+/**
+ * A record of a Core <-> Periphery connection login attempt,
+ * kept for auditing accepted and rejected keys.
+ */
+export interface AuthEvent {
+	/**
+	 * The Mongo ID of the AuthEvent.
+	 * This field is de/serialized from/to JSON as
+	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized AuthEvent) }`
+	 */
+	_id?: MongoId;
+	/** Unix timestamp in milliseconds of the login attempt. */
+	ts: I64;
+	/**
+	 * The id of the Server, or empty if no Server matched,
+	 * eg. during onboarding.
+	 */
+	server_id?: string;
+	/**
+	 * The Server id or name Periphery connected with.
+	 * Empty for Core -> Periphery connections.
+	 */
+	server_query?: string;
+	/** The connection direction. */
+	direction?: AuthEventDirection;
+	/** The login flow used. */
+	flow?: AuthEventFlow;
+	/**
+	 * The public key Periphery presented in the handshake,
+	 * if the handshake got that far.
+	 */
+	public_key?: string;
+	/** Whether the login succeeded. */
+	success?: boolean;
+	/** The reason the login failed. */
+	error?: string;
+	/**
+	 * The remote IP for Periphery -> Core connections,
+	 * or the Periphery address for Core -> Periphery connections.
+	 */
+	remote?: string;
+}
+
+/** Response for [ListAuthEvents]. */
+export interface ListAuthEventsResponse {
+	events: AuthEvent[];
+	/**
+	 * If more events exist, the next page will be given here.
+	 * Otherwise it will be `null`
+	 */
+	next_page?: I64;
+}
+
+export type ApprovePendingServerResponse = Server;
+
+export type DeletePendingServerResponse = PendingServer;
+
+export type RevokePublicKeyResponse = RevokedKey;
+
+export type UnrevokePublicKeyResponse = RevokedKey;
+
 export type DeleteProcedureResponse = Procedure;
 
 export type DeleteUserResponse = User;
 
 export type DeleteVariableResponse = Variable;
 
+/** A parameter users are prompted for when instantiating a [Template]. */
+export interface TemplateParameter {
+	/** The parameter name. Matches '{{name}}' placeholders in the template. */
+	name: string;
+	/** A description of the parameter shown when prompting. */
+	description?: string;
+	/** The value used when none is given at instantiation. */
+	default?: string;
+	/** Whether a non-empty value must be given (or defaulted) at instantiation. */
+	required?: boolean;
+}
+
+/**
+ * A parameterized compose file which can be instantiated
+ * into a new [Stack](crate::entities::stack::Stack)
+ * on a chosen Server using [InstantiateTemplate](crate::api::write::InstantiateTemplate).
+ * 
+ * Instances of '{{PARAMETER}}' in the file contents and environment
+ * are replaced with the parameter value given at instantiation,
+ * or the parameter default if none is given.
+ */
+export interface Template {
+	/** Unique name associated with the template. */
+	name: string;
+	/** A description for the template, shown in the catalog. */
+	description?: string;
+	/** The compose file contents, containing '{{PARAMETER}}' placeholders. */
+	file_contents?: string;
+	/** The environment (.env) contents, containing '{{PARAMETER}}' placeholders. */
+	environment?: string;
+	/** The parameters users are prompted for when instantiating the template. */
+	parameters?: TemplateParameter[];
+	/** Timestamp of template creation */
+	created_at?: I64;
+	/** Timestamp of last template update */
+	updated_at?: I64;
+}
+
+export type CreateTemplateResponse = Template;
+
+export type DeleteTemplateResponse = Template;
+
+/** What a [DashboardWidget] displays. */
+export type DashboardWidgetConfig = 
+	/** Current / historical system stats of a Server. */
+	| { type: "ServerStats", params: {
+	/** Id or name of the Server. */
+	server: string;
+}}
+	/** The states of the Stacks matching the tag filter. */
+	| { type: "StackStates", params: {
+	/** Only include Stacks with all of these tags. Empty includes all Stacks. */
+	tags?: string[];
+}}
+	/** The most recent Updates, optionally for a specific target. */
+	| { type: "RecentUpdates", params: {
+	/** Only include Updates on this target. */
+	target?: ResourceTarget;
+}}
+	/** The open Alerts, optionally for a specific target. */
+	| { type: "AlertFeed", params: {
+	/** Only include Alerts on this target. */
+	target?: ResourceTarget;
+}};
+
+/** A single widget placed in the [Dashboard] grid layout. */
+export interface DashboardWidget {
+	/** Identifies the widget within the Dashboard layout. */
+	id: string;
+	/** An optional title to display above the widget. */
+	title?: string;
+	/** The grid column of the widget's top left corner. */
+	x?: I64;
+	/** The grid row of the widget's top left corner. */
+	y?: I64;
+	/** The width of the widget in grid columns. */
+	w?: I64;
+	/** The height of the widget in grid rows. */
+	h?: I64;
+	/** What the widget displays. */
+	config: DashboardWidgetConfig;
+}
+
+/**
+ * A custom operational view made up of widgets,
+ * persisted on Core so it follows the user between browsers.
+ * 
+ * Dashboards are owned by the user who created them.
+ * If marked as shared, all users are able to view (but not edit) the Dashboard.
+ */
+export interface Dashboard {
+	/**
+	 * The Mongo ID of the Dashboard.
+	 * This field is de/serialized from/to JSON as
+	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized Dashboard) }`
+	 */
+	_id?: MongoId;
+	/** The id of the User who owns the Dashboard. */
+	owner: string;
+	/** A name for the Dashboard. */
+	name: string;
+	/** A description for the Dashboard. */
+	description?: string;
+	/** Whether all users can view the Dashboard. */
+	shared?: boolean;
+	/** The widgets in the Dashboard layout. */
+	widgets?: DashboardWidget[];
+	/** Unix time (ms) when the Dashboard was created. */
+	created_at?: I64;
+	/** Unix time (ms) when the Dashboard was last updated. */
+	updated_at?: I64;
+}
+
+export type CreateDashboardResponse = Dashboard;
+
+export type DeleteDashboardResponse = Dashboard;
+
 export type DeploymentImage = 
 	/** Deploy any external image. */
 	| { type: "Image", params: {
@@ -1205,9 +1583,41 @@ export enum TerminationSignal {
 	SigTerm = "SIGTERM",
 }
 
+/**
+ * A custom health probe for a Deployment.
+ * Http and Tcp probes are made from the Periphery host.
+ */
+export type DeploymentHealthProbe = 
+	/** No custom health probe. */
+	| { type: "None", params?: undefined }
+	/** GET the url. Healthy on any 2xx or 3xx status. */
+	| { type: "Http", params: {
+	/** Eg. `http://localhost:8080/health` */
+	url?: string;
+}}
+	/** Healthy if a TCP connection to the address can be opened. */
+	| { type: "Tcp", params: {
+	/** Eg. `localhost:5432` */
+	address?: string;
+}}
+	/**
+	 * Run the command in the container with `docker exec`.
+	 * Healthy on exit code 0.
+	 */
+	| { type: "Command", params: {
+	command?: string;
+}};
+
 export interface DeploymentConfig {
 	/** The id of server the deployment is deployed on. */
 	server_id?: string;
+	/**
+	 * Inherit from this template Deployment (id or name).
+	 * At deploy time, the template environment and labels are merged
+	 * underneath this Deployment's, which override by key,
+	 * and the template restart mode is used if this Deployment's is `no`.
+	 */
+	inherit?: string;
 	/**
 	 * The image which the deployment deploys.
 	 * Can either be a user inputted image, or a Komodo Build.
@@ -1282,6 +1692,33 @@ export interface DeploymentConfig {
 	environment?: string;
 	/** The docker labels given to the container. */
 	labels?: string;
+	/**
+	 * An optional health probe evaluated by Periphery after deploy
+	 * and on every monitoring interval, independent of any
+	 * Docker HEALTHCHECK on the image.
+	 */
+	health_probe?: DeploymentHealthProbe;
+	/** Seconds before a single probe attempt counts as failed. */
+	health_probe_timeout?: number;
+	/**
+	 * Seconds after deploy to retry the probe before
+	 * the deploy reports the Deployment as unhealthy.
+	 */
+	health_probe_start_period?: number;
+	/**
+	 * Wait for the container HEALTHCHECK to report healthy after deploy.
+	 * The deploy fails if the container is unhealthy, exits,
+	 * or isn't healthy within `healthcheck_timeout`.
+	 * Containers without a HEALTHCHECK only need to keep running.
+	 */
+	wait_for_healthcheck?: boolean;
+	/** Seconds to wait for the container to become healthy. */
+	healthcheck_timeout?: number;
+	/**
+	 * If the deploy fails, or the container fails its healthcheck,
+	 * re-deploy the image of the previous successful deploy.
+	 */
+	rollback_on_failure?: boolean;
 }
 
 export type Deployment = Resource<DeploymentConfig, undefined>;
@@ -1317,6 +1754,16 @@ export enum DeploymentState {
 	Unknown = "unknown",
 }
 
+/** The result of a Deployment health probe. */
+export interface DeploymentHealth {
+	/** Whether the probe succeeded. */
+	healthy: boolean;
+	/** The probe output, or the reason it failed. */
+	message: string;
+	/** Unix timestamp in milliseconds of the probe. */
+	ts: I64;
+}
+
 export interface DeploymentListItemInfo {
 	/** The state of the deployment / underlying docker container. */
 	state: DeploymentState;
@@ -1330,6 +1777,8 @@ export interface DeploymentListItemInfo {
 	server_id: string;
 	/** An attached Komodo Build, if it exists. */
 	build_id?: string;
+	/** The latest result of the custom health probe, if configured. */
+	health?: DeploymentHealth;
 }
 
 export type DeploymentListItem = ResourceListItem<DeploymentListItemInfo>;
@@ -1406,6 +1855,11 @@ export enum SeverityLevel {
 	Critical = "CRITICAL",
 }
 
+export enum GpuVendor {
+	Nvidia = "Nvidia",
+	Amd = "Amd",
+}
+
 /** The variants of data related to the alert. */
 export type AlertData = 
 	/** A null alert */
@@ -1470,6 +1924,72 @@ export type AlertData =
 	used_gb: number;
 	/** The total size of the disk in GB */
 	total_gb: number;
+}}
+	/** A server disk reports SMART health problems. */
+	| { type: "ServerDiskHealth", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The device path of the disk, ie `/dev/sda` */
+	device: string;
+	/** The disk model name */
+	model: string;
+	/** The disk serial number */
+	serial: string;
+	/** The problems reported by the disk */
+	problems: string[];
+}}
+	/** A ZFS pool on a server is not ONLINE. */
+	| { type: "ServerZfsPool", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The pool name */
+	pool: string;
+	/** The pool health, ie `DEGRADED` */
+	health: string;
+	/** The status of the last or running scrub / resilver */
+	scan?: string;
+}}
+	/** A server has high GPU memory usage or temperature. */
+	| { type: "ServerGpu", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The GPU vendor */
+	vendor: GpuVendor;
+	/** The index of the GPU */
+	index: number;
+	/** The GPU model name */
+	gpu: string;
+	/** The used GPU memory in GB */
+	mem_used_gb: number;
+	/** The total GPU memory in GB */
+	mem_total_gb: number;
+	/** The GPU temperature in degrees Celsius, if reported */
+	temperature?: number;
+}}
+	/** A server has pending OS security updates. */
+	| { type: "ServerOsSecurityUpdates", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The package manager reporting the updates, ie `apt` */
+	package_manager: string;
+	/** The packages with a pending security update */
+	packages: string[];
 }}
 	/** A server has a version mismatch with the core. */
 	| { type: "ServerVersionMismatch", data: {
@@ -1524,6 +2044,19 @@ export type AlertData =
 	server_name: string;
 	/** The updated image */
 	image: string;
+}}
+	/** A Deployment custom health probe is failing. */
+	| { type: "DeploymentUnhealthy", data: {
+	/** The id of the deployment */
+	id: string;
+	/** The name of the deployment */
+	name: string;
+	/** The server id of server that the deployment is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The reason the probe failed */
+	message: string;
 }}
 	/** A stack's state has changed unexpectedly. */
 	| { type: "StackStateChange", data: {
@@ -1630,6 +2163,22 @@ export type AlertData =
 	message: string;
 	/** Message details. May be empty string. */
 	details?: string;
+}}
+	/**
+	 * An external alert received from Prometheus Alertmanager.
+	 * Produced by the `/listener/alertmanager` webhook receiver.
+	 */
+	| { type: "Alertmanager", data: {
+	/** The Alertmanager fingerprint, used to resolve the alert. */
+	fingerprint: string;
+	/** The `alertname` label. */
+	name: string;
+	/** The `summary` or `description` annotation. May be empty string. */
+	summary?: string;
+	/** The Prometheus expression URL. May be empty string. */
+	url?: string;
+	/** All the labels on the alert. */
+	labels?: Record<string, string>;
 }};
 
 /** Representation of an alert in the system. */
@@ -1680,6 +2229,7 @@ export interface DeploymentActionState {
 	stopping: boolean;
 	destroying: boolean;
 	renaming: boolean;
+	rolling_back: boolean;
 }
 
 export type GetDeploymentActionStateResponse = DeploymentActionState;
@@ -1688,6 +2238,22 @@ export type GetDeploymentLogResponse = Log;
 
 export type GetDeploymentResponse = Deployment;
 
+/**
+ * Network and block IO rates of a container, in bytes per second.
+ * Computed by the Periphery container stats poller
+ * from the difference between consecutive samples.
+ */
+export interface ContainerIoRates {
+	/** Network receive rate in bytes per second */
+	net_rx: number;
+	/** Network transmit rate in bytes per second */
+	net_tx: number;
+	/** Block device read rate in bytes per second */
+	block_read: number;
+	/** Block device write rate in bytes per second */
+	block_write: number;
+}
+
 export interface ContainerStats {
 	name: string;
 	cpu_perc: string;
@@ -1696,6 +2262,8 @@ export interface ContainerStats {
 	net_io: string;
 	block_io: string;
 	pids: string;
+	/** Network and block IO rates, computed by the Periphery container stats poller. */
+	io_rates?: ContainerIoRates;
 }
 
 export type GetDeploymentStatsResponse = ContainerStats;
@@ -1759,6 +2327,110 @@ export enum Timelength {
 	ThirtyDays = "30-day",
 }
 
+/** Load metrics for a Core Periphery connection shard. */
+export interface ConnectionShardStats {
+	/** The shard index. */
+	index: number;
+	/** The number of currently open connections. */
+	active: number;
+	/** The number of logins currently in progress. */
+	logging_in: number;
+	/** The maximum concurrent logins. */
+	max_logins: number;
+	/** Total inbound connections accepted since Core started. */
+	accepted: number;
+	/**
+	 * Total inbound connections rejected because
+	 * the shard login pool was full.
+	 */
+	rejected_busy: number;
+	/** Total messages received from Periphery. */
+	messages_received: number;
+	/** Total messages sent to Periphery. */
+	messages_sent: number;
+}
+
+export interface ListConnectionShardsResponse {
+	/**
+	 * The total inbound connections rejected by
+	 * `connection_accept_rate` since Core started.
+	 */
+	rate_limited: number;
+	/** The load metrics of each shard. */
+	shards: ConnectionShardStats[];
+}
+
+/**
+ * Transport metrics for the Periphery connection of a Server,
+ * since Core started. Kept across reconnects.
+ */
+export interface ServerConnectionMetrics {
+	/** Whether Periphery is currently connected. */
+	connected: boolean;
+	/**
+	 * Round trip latency of the latest Ping in ms,
+	 * if the connection uses keepalive.
+	 */
+	latency_ms?: number;
+	/** The times the connection was re-established. */
+	reconnects: number;
+	/** Total bytes sent to Periphery. */
+	bytes_sent: number;
+	/** Total bytes received from Periphery. */
+	bytes_received: number;
+	/**
+	 * Messages sent to Periphery by transport message variant,
+	 * eg. `Request`, `Terminal`.
+	 */
+	messages_sent: Record<string, number>;
+	/**
+	 * Messages received from Periphery by transport message variant,
+	 * eg. `Response`, `Terminal`.
+	 */
+	messages_received: Record<string, number>;
+	/** The latest connection error, kept after reconnecting. */
+	last_error?: _Serror;
+	/** Timestamp of the latest connection error. */
+	last_error_at?: I64;
+}
+
+export type GetServerConnectionMetricsResponse = ServerConnectionMetrics;
+
+export enum TransportDirection {
+	/** Core -> Periphery */
+	Sent = "Sent",
+	/** Periphery -> Core */
+	Received = "Received",
+}
+
+/**
+ * The header of a transport message on a Periphery connection,
+ * recorded when Core `transport_trace` is enabled.
+ */
+export interface TransportTraceEntry {
+	/** When the message was sent / received. */
+	ts: I64;
+	/** The Server (or Builder) id of the connection. */
+	server: string;
+	direction: TransportDirection;
+	/**
+	 * The transport message variant, eg. `Request`.
+	 * Empty if the message header is invalid.
+	 */
+	variant: string;
+	/**
+	 * The channel of Request, Response, Terminal and File messages,
+	 * or the message id of Chunk messages.
+	 */
+	channel?: string;
+	/** The encoded message size in bytes. */
+	size: number;
+	/** Whether the message is compressed. */
+	compressed: boolean;
+}
+
+export type ListTransportTraceResponse = TransportTraceEntry[];
+
 /** Info about Periphery configuration */
 export interface PeripheryInformation {
 	/** The Periphery version. */
@@ -1771,6 +2443,17 @@ export interface PeripheryInformation {
 	container_terminals_disabled: boolean;
 	/** The rate the system stats are being polled from the system */
 	stats_polling_rate: Timelength;
+	/** The request types Periphery allows. Empty means all are allowed. */
+	allowed_requests?: string[];
+	/** The request types Periphery denies. */
+	denied_requests?: string[];
+	/** The names of the configured Periphery extensions. */
+	extensions?: string[];
+	/**
+	 * The platforms the buildx builder supports,
+	 * ie `linux/amd64`, `linux/arm64`.
+	 */
+	buildx_platforms?: string[];
 }
 
 export type GetPeripheryInformationResponse = PeripheryInformation;
@@ -1969,6 +2652,8 @@ export interface ResourceSyncConfig {
 	include_variables?: boolean;
 	/** Whether sync should include user groups. */
 	include_user_groups?: boolean;
+	/** Whether sync should include onboarding key metadata. */
+	include_onboarding_keys?: boolean;
 	/**
 	 * Whether sync should send alert when it enters Pending state.
 	 * Default: true
@@ -2036,6 +2721,8 @@ export interface ResourceSyncInfo {
 	variable_updates?: DiffData[];
 	/** The list of pending updates to user groups */
 	user_group_updates?: DiffData[];
+	/** The list of pending updates to onboarding keys */
+	onboarding_key_updates?: DiffData[];
 	/** The list of pending deploys to resources. */
 	pending_deploy?: SyncDeployUpdate;
 	/** If there is an error, it will be stored here */
@@ -2080,24 +2767,59 @@ export interface ServerActionState {
 	unpausing_containers: boolean;
 	/** Server currently stopping containers. */
 	stopping_containers: boolean;
+	/** Server currently committing a container. */
+	committing_container: boolean;
+	/** Server currently loading a transferred image. */
+	transferring_image: boolean;
 }
 
 export type GetServerActionStateResponse = ServerActionState;
 
+/**
+ * How Core verifies the Periphery tls certificate
+ * for Core -> Periphery connections.
+ */
+export enum TlsVerification {
+	/**
+	 * Skip certificate verification.
+	 * Periphery uses self-signed certificates by default.
+	 */
+	Insecure = "Insecure",
+	/** Verify the certificate against the system CA roots. */
+	System = "System",
+	/** Only accept certificates matching the Server `tls_pins`. */
+	Pinned = "Pinned",
+}
+
 /** Server configuration. */
 export interface ServerConfig {
 	/**
 	 * The ws/s address of the periphery client.
+	 * Use `quic://<HOST>:<PORT>` for Peripheries using the QUIC transport.
 	 * If unset, Server expects Periphery -> Core connection.
 	 */
 	address?: string;
 	/**
-	 * Only relevant for Core -> Periphery connections.
-	 * Whether to skip Periphery tls certificate validation.
-	 * This defaults to true because Periphery generates self-signed certificates by default,
-	 * but if you use valid certs you can switch this to false.
+	 * Deprecated. Use `tls_verification`.
+	 * Existing Servers are migrated on startup,
+	 * `false` becoming `System` verification.
 	 */
 	insecure_tls: boolean;
+	/**
+	 * Only relevant for Core -> Periphery connections.
+	 * How to verify the Periphery tls certificate.
+	 * This defaults to `Insecure` because Periphery generates self-signed certificates by default.
+	 * Use `Pinned` to trust only the certificate public keys in `tls_pins`,
+	 * or `System` if you use valid certs.
+	 */
+	tls_verification?: TlsVerification;
+	/**
+	 * The accepted Periphery certificate pins,
+	 * `sha256/<base64 hash of the certificate public key>`.
+	 * Used with `Pinned` tls verification. If empty, the pin seen on connect
+	 * is stored in `attempted_tls_pin` to accept later on.
+	 */
+	tls_pins?: string[];
 	/**
 	 * The address to use with links for containers on the server.
 	 * If empty, will use the 'address' for links.
@@ -2150,6 +2872,17 @@ export interface ServerConfig {
 	send_mem_alerts: boolean;
 	/** Whether to send alerts about the servers DISK status */
 	send_disk_alerts: boolean;
+	/** Whether to send alerts about the servers GPU status */
+	send_gpu_alerts: boolean;
+	/**
+	 * Whether to send alerts about the SMART health of the servers disks.
+	 * Requires `smartctl` on the Periphery host.
+	 */
+	send_disk_health_alerts: boolean;
+	/** Whether to send alerts when a ZFS pool on the server is not ONLINE. */
+	send_zfs_alerts: boolean;
+	/** Whether to send alerts when the server has pending OS security updates. */
+	send_os_update_alerts: boolean;
 	/** Whether to send alerts about the servers version mismatch with core */
 	send_version_mismatch_alerts: boolean;
 	/** The percentage threshhold which triggers WARNING state for CPU. */
@@ -2164,11 +2897,46 @@ export interface ServerConfig {
 	disk_warning: number;
 	/** The percentage threshhold which triggers CRITICAL state for DISK. */
 	disk_critical: number;
+	/**
+	 * The percentage threshhold of a GPU's memory
+	 * which triggers WARNING state for the GPU.
+	 */
+	gpu_mem_warning: number;
+	/**
+	 * The percentage threshhold of a GPU's memory
+	 * which triggers CRITICAL state for the GPU.
+	 */
+	gpu_mem_critical: number;
+	/** The temperature (°C) of a GPU which triggers WARNING state for the GPU. */
+	gpu_temp_warning: number;
+	/** The temperature (°C) of a GPU which triggers CRITICAL state for the GPU. */
+	gpu_temp_critical: number;
 	/** Scheduled maintenance windows during which alerts will be suppressed. */
 	maintenance_windows?: MaintenanceWindow[];
-}
-
-export interface ServerInfo {
+	/**
+	 * The percentage of CPU to keep free for the host.
+	 * Placement checks will not suggest using this portion.
+	 */
+	reserved_cpu?: number;
+	/**
+	 * The memory (in GB) to keep free for the host.
+	 * Placement checks will not suggest using this portion.
+	 */
+	reserved_mem_gb?: number;
+	/**
+	 * The maximum number of containers which should run on the Server.
+	 * 0 means no limit.
+	 */
+	max_containers?: I64;
+	/**
+	 * Limit the bytes per second sent to and received from Periphery.
+	 * 0 uses the Core `transport_max_bytes_per_sec` default,
+	 * and a negative value disables the limit for this Server.
+	 */
+	transport_max_bytes_per_sec?: I64;
+}
+
+export interface ServerInfo {
 	/**
 	 * If a Periphery fails to authenticate to Core
 	 * for a disconnected server with invalid Periphery public key,
@@ -2180,6 +2948,16 @@ export interface ServerInfo {
 	 * private key of the periphery agent.
 	 */
 	public_key?: string;
+	/**
+	 * When the Periphery keys were last rotated by Core,
+	 * or 0 if never. Used to schedule `key_rotation_interval_days`.
+	 */
+	keys_rotated_at?: I64;
+	/**
+	 * If Periphery presents a tls certificate not matching `tls_pins`,
+	 * its pin will be stored here to accept the connection later on.
+	 */
+	attempted_tls_pin?: string;
 }
 
 export type Server = Resource<ServerConfig, ServerInfo>;
@@ -2195,6 +2973,7 @@ export interface StackActionState {
 	unpausing: boolean;
 	stopping: boolean;
 	destroying: boolean;
+	scaling: boolean;
 }
 
 export type GetStackActionStateResponse = StackActionState;
@@ -2210,6 +2989,31 @@ export enum StackFileRequires {
 	None = "None",
 }
 
+/**
+ * Stat-based autoscale policy for a Stack service.
+ * 
+ * The replicas are scaled so the average container cpu
+ * across the running replicas approaches `target_cpu_percent`.
+ */
+export interface StackServiceAutoscale {
+	/** The compose service to scale. */
+	service: string;
+	/**
+	 * The target average container cpu percent across the replicas.
+	 * Default: 70
+	 */
+	target_cpu_percent?: number;
+	/** The minimum number of replicas. Default: 1 */
+	min_replicas?: I64;
+	/** The maximum number of replicas. Default: 3 */
+	max_replicas?: I64;
+	/**
+	 * Seconds to wait after scaling the service before it can be scaled again.
+	 * Default: 300
+	 */
+	cooldown_seconds?: I64;
+}
+
 /** Configure additional file dependencies of the Stack. */
 export interface StackFileDependency {
 	/** Specify the file */
@@ -2224,6 +3028,12 @@ export interface StackFileDependency {
 export interface StackConfig {
 	/** The server to deploy the stack on. */
 	server_id?: string;
+	/**
+	 * Inherit from this template Stack (id or name).
+	 * At deploy time, the template environment is merged
+	 * underneath this Stack's, which overrides by variable.
+	 */
+	inherit?: string;
 	/** Configure quick links that are displayed in the resource header */
 	links?: string[];
 	/**
@@ -2263,6 +3073,13 @@ export interface StackConfig {
 	auto_update_all_services?: boolean;
 	/** Whether to run `docker compose down` before `compose up`. */
 	destroy_before_deploy?: boolean;
+	/**
+	 * Whether to deploy with `docker stack deploy`
+	 * to the swarm the Server is a manager of.
+	 * Swarm stacks don't support `run_build`,
+	 * and are deployed and destroyed as a whole.
+	 */
+	swarm?: boolean;
 	/** Whether to skip secret interpolation into the stack environment variables. */
 	skip_secret_interp?: boolean;
 	/** Choose a Komodo Repo (Resource) to source the compose files. */
@@ -2359,6 +3176,25 @@ export interface StackConfig {
 	pre_deploy?: SystemCommand;
 	/** The optional command to run after the Stack is deployed. */
 	post_deploy?: SystemCommand;
+	/**
+	 * Kill the pre / post deploy commands if they run
+	 * longer than this many seconds. 0 means no timeout.
+	 */
+	deploy_command_timeout_seconds?: I64;
+	/**
+	 * Wait for the service container HEALTHCHECKs to report healthy after deploy.
+	 * The deploy fails if any container is unhealthy, exits,
+	 * or isn't healthy within `healthcheck_timeout_seconds`.
+	 * Containers without a HEALTHCHECK only need to keep running.
+	 */
+	wait_for_healthcheck?: boolean;
+	/** Seconds to wait for the containers to become healthy. */
+	healthcheck_timeout_seconds?: I64;
+	/**
+	 * If the deploy fails, or the containers fail their healthchecks,
+	 * re-deploy the compose config of the previous successful deploy.
+	 */
+	rollback_on_failure?: boolean;
 	/**
 	 * The extra arguments to pass after `docker compose up -d`.
 	 * If empty, no extra arguments will be passed.
@@ -2376,6 +3212,12 @@ export interface StackConfig {
 	 * stack should be healthy. This init service should be in `ignore_services`
 	 */
 	ignore_services?: string[];
+	/**
+	 * Stat-based autoscale policies for the Stack services.
+	 * Core evaluates them against the service container stats,
+	 * and scales the service with `docker compose up --scale`.
+	 */
+	autoscale?: StackServiceAutoscale[];
 	/**
 	 * The contents of the file directly, for management in the UI.
 	 * If this is empty, it will fall back to checking git config for
@@ -2391,6 +3233,20 @@ export interface StackConfig {
 	 * If it is empty, no file will be written.
 	 */
 	environment?: string;
+	/**
+	 * Secrets to mount into the compose services as files,
+	 * instead of passing them as environment variables.
+	 * Given as `NAME = value`, where the value is usually a
+	 * Variable / Secret like `[[DB_PASSWORD]]`.
+	 * 
+	 * Before deploy, each is written to a transient tmpfs backed
+	 * directory on the host, and defined as a top level compose secret
+	 * `NAME`. Services use them with `secrets: [NAME]`, and read them
+	 * at `/run/secrets/NAME`. The files are removed on `compose down`.
+	 * 
+	 * Note. These are always interpolated, even with `skip_secret_interp`.
+	 */
+	secret_files?: string;
 }
 
 export interface FileContents {
@@ -2444,6 +3300,43 @@ export interface StackRemoteFileContents {
 	requires?: StackFileRequires;
 }
 
+/** The steps of a [StackMigration], in order. */
+export enum StackMigrationStep {
+	/** Destroy the Stack on the source Server, keeping the volumes. */
+	Destroy = "Destroy",
+	/** Archive the named volumes on the source Server. */
+	Archive = "Archive",
+	/** Transfer the volume archives to the target Server. */
+	Transfer = "Transfer",
+	/** Restore the volume archives into volumes on the target Server. */
+	Restore = "Restore",
+	/** Point the Stack at the target Server. */
+	SwitchServer = "SwitchServer",
+	/** Deploy the Stack on the target Server. */
+	Deploy = "Deploy",
+	/** Wait for the Stack to be running on the target Server. */
+	Verify = "Verify",
+	/** Remove the volumes and archives from the source Server. */
+	Cleanup = "Cleanup",
+}
+
+/**
+ * The progress of a [MigrateStack][crate::api::execute::MigrateStack].
+ * Running MigrateStack again resumes after the completed steps.
+ */
+export interface StackMigration {
+	/** The id of the Server the Stack is migrating from. */
+	from_server: string;
+	/** The id of the Server the Stack is migrating to. */
+	to_server: string;
+	/** The named volumes of the Stack being migrated. */
+	volumes?: string[];
+	/** The completed steps, in order. */
+	completed?: StackMigrationStep[];
+	/** Unix timestamp in milliseconds the migration started. */
+	started_at?: I64;
+}
+
 export interface StackInfo {
 	/**
 	 * If any of the expected compose / additional files are missing in the repo,
@@ -2493,12 +3386,16 @@ export interface StackInfo {
 	latest_hash?: string;
 	/** Latest commit message, or null */
 	latest_message?: string;
+	/** The progress of an unfinished migration to another Server, or null. */
+	migration?: StackMigration;
 }
 
 export type Stack = Resource<StackConfig, StackInfo>;
 
 export type GetStackResponse = Stack;
 
+export type GetStackInheritanceResponse = ConfigInheritance;
+
 /** System information of a server */
 export interface SystemInformation {
 	/** The system name */
@@ -2538,10 +3435,70 @@ export interface SingleDiskUsage {
 	total_gb: number;
 }
 
+/** Usage of a single GPU on the system. */
+export interface SingleGpuUsage {
+	/** The index of the GPU, as reported by the vendor tooling */
+	index: number;
+	/** The GPU vendor */
+	vendor: GpuVendor;
+	/** The GPU model name */
+	name: string;
+	/** GPU utilization percentage */
+	utilization_perc: number;
+	/** Used GPU memory in GB */
+	mem_used_gb: number;
+	/** Total GPU memory in GB */
+	mem_total_gb: number;
+	/** GPU temperature in degrees Celsius, if reported */
+	temperature?: number;
+}
+
+/** Usage of a ZFS dataset. */
+export interface ZfsDataset {
+	/** The dataset name, ie `tank/media` */
+	name: string;
+	/** Used space in GB, including descendants and snapshots */
+	used_gb: number;
+	/** Available space in GB */
+	available_gb: number;
+	/** The mountpoint, if mounted */
+	mountpoint?: string;
+}
+
+/** Status of a ZFS pool. */
+export interface ZfsPool {
+	/** The pool name */
+	name: string;
+	/** The pool health, ie `ONLINE`, `DEGRADED`, `FAULTED` */
+	health: string;
+	/** Total size of the pool in GB */
+	size_gb: number;
+	/** Allocated portion of the pool in GB */
+	allocated_gb: number;
+	/** Free portion of the pool in GB */
+	free_gb: number;
+	/** Free space fragmentation percentage, if reported */
+	fragmentation_perc?: number;
+	/**
+	 * The status of the last or running scrub / resilver,
+	 * ie `SCRUB FINISHED (0 errors)`
+	 */
+	scan?: string;
+	/** The datasets in the pool */
+	datasets: ZfsDataset[];
+}
+
 /** Realtime system stats data. */
 export interface SystemStats {
 	/** Cpu usage percentage */
 	cpu_perc: number;
+	/** Usage percentage of each cpu core, in core order */
+	cpu_cores?: number[];
+	/**
+	 * Percentage of cpu time stolen by the hypervisor
+	 * since the last poll. Only reported on Linux.
+	 */
+	cpu_steal_perc?: number;
 	/** Load average (1m, 5m, 15m) */
 	load_average?: SystemLoadAverage;
 	/**
@@ -2557,10 +3514,16 @@ export interface SystemStats {
 	mem_total_gb: number;
 	/** Breakdown of individual disks, ie their usages, sizes, and mount points */
 	disks: SingleDiskUsage[];
+	/** Usage of the NVIDIA and AMD GPUs on the system */
+	gpus?: SingleGpuUsage[];
+	/** The ZFS pools on the system and their datasets */
+	zfs_pools?: ZfsPool[];
 	/** Network ingress usage in MB */
 	network_ingress_bytes?: number;
 	/** Network egress usage in MB */
 	network_egress_bytes?: number;
+	/** Throughput and errors of each network interface */
+	network_interfaces?: SingleNetworkInterfaceUsage[];
 	/** The rate the system stats are being polled from the system */
 	polling_rate: Timelength;
 	/** Unix timestamp in milliseconds when stats were last polled */
@@ -2571,6 +3534,61 @@ export interface SystemStats {
 
 export type GetSystemStatsResponse = SystemStats;
 
+export type GetSystemdUnitJournalResponse = Log;
+
+/**
+ * The detailed status of a systemd unit,
+ * read with `systemctl show`.
+ */
+export interface SystemdUnitStatus {
+	/** The unit name, ie `wg-quick@wg0.service` */
+	name: string;
+	/** The unit description */
+	description: string;
+	/** Whether the unit definition was loaded, ie `loaded`, `not-found` */
+	load_state: string;
+	/** The high level activation state, ie `active`, `inactive`, `failed` */
+	active_state: string;
+	/** The unit type specific state, ie `running`, `exited`, `waiting` */
+	sub_state: string;
+	/** Whether the unit is enabled, ie `enabled`, `disabled`, `static` */
+	unit_file_state: string;
+	/** The path of the unit file */
+	fragment_path?: string;
+	/** The pid of the main process, if running */
+	main_pid?: I64;
+	/** When the unit last entered the active state */
+	active_since?: string;
+	/** The current memory usage in bytes, if accounted */
+	memory_bytes?: I64;
+}
+
+export type GetSystemdUnitStatusResponse = SystemdUnitStatus;
+
+/** The result of checking a Server's capacity for a new Deployment. */
+export interface ServerPlacement {
+	/** The Server id */
+	server_id: string;
+	/** The Server name */
+	server_name: string;
+	/** Whether the Deployment fits on the Server without overcommitting it. */
+	fits: boolean;
+	/** The reasons the Deployment does not fit, if any. */
+	reasons: string[];
+	/** The CPU percentage available after the current usage and reservation. */
+	available_cpu: number;
+	/** The memory (GB) available after the current usage and reservation. */
+	available_mem_gb: number;
+	/** The number of containers currently on the Server. */
+	containers: I64;
+	/** The maximum number of containers configured for the Server. 0 means no limit. */
+	max_containers: I64;
+}
+
+export type SuggestServerPlacementResponse = ServerPlacement[];
+
+export type CheckServerPlacementResponse = ServerPlacement;
+
 export enum TagColor {
 	LightSlate = "LightSlate",
 	Slate = "Slate",
@@ -2639,12 +3657,75 @@ export interface Tag {
 	owner?: string;
 	/** Hex color code with alpha for UI display */
 	color?: TagColor;
+	/** The id of the Organization the tag belongs to. */
+	organization?: string;
 }
 
 export type GetTagResponse = Tag;
 
+/**
+ * Resource usage aggregated over the resources with a tag,
+ * for chargeback and capacity planning.
+ */
+export interface TagUsageReport {
+	/** The tag id */
+	tag: string;
+	/** The tag name */
+	name: string;
+	/** Start of the report window (unix ms) */
+	since: I64;
+	/** End of the report window (unix ms) */
+	until: I64;
+	/** The number of tagged Servers */
+	servers: I64;
+	/** Average cpu usage percentage across the tagged Servers */
+	cpu_perc_avg: number;
+	/** Average memory used in GB, summed over the tagged Servers */
+	mem_used_gb_avg: number;
+	/** Average total memory in GB, summed over the tagged Servers */
+	mem_total_gb_avg: number;
+	/** The current number of containers on the tagged Servers */
+	containers: I64;
+	/** The current number of running containers on the tagged Servers */
+	running_containers: I64;
+	/** The number of tagged Deployments */
+	deployments: I64;
+	/** The number of tagged Stacks */
+	stacks: I64;
+	/** The number of tagged Builds */
+	builds: I64;
+	/** The number of runs of the tagged Builds in the window */
+	build_runs: I64;
+	/** Total minutes spent running the tagged Builds in the window */
+	build_minutes: number;
+}
+
+export type GetTagUsageReportResponse = TagUsageReport;
+
 export type GetUpdateResponse = Update;
 
+/**
+ * Limits applied to the members of a [UserGroup], shared by all members.
+ * A value of 0 means no limit.
+ */
+export interface UserGroupQuotas {
+	/**
+	 * The maximum number of Deployments created by group members
+	 * which can exist at once.
+	 */
+	max_deployments?: I64;
+	/**
+	 * The maximum number of Servers created by group members
+	 * which can exist at once.
+	 */
+	max_servers?: I64;
+	/**
+	 * The maximum number of builds group members can run
+	 * in any 24 hour period.
+	 */
+	max_builds_per_day?: I64;
+}
+
 /**
  * Permission users at the group level.
  * 
@@ -2668,16 +3749,51 @@ export interface UserGroup {
 	users?: string[];
 	/** Give the user group elevated permissions on all resources of a certain type */
 	all?: Record<ResourceTarget["type"], PermissionLevelAndSpecifics | PermissionLevel>;
+	/** Limits on what members of the group are able to create and run. */
+	quotas?: UserGroupQuotas;
 	/** Unix time (ms) when user group last updated */
 	updated_at?: I64;
 }
 
+/**
+ * An isolated tenant on Core.
+ * 
+ * Resources, users, tags, and variables may belong to an Organization.
+ * Non admin users assigned to an Organization are only able to see
+ * and interact with the resources, tags, and variables in the same Organization.
+ * Users without an Organization keep the unscoped behavior.
+ */
+export interface Organization {
+	/**
+	 * The Mongo ID of the Organization.
+	 * This field is de/serialized from/to JSON as
+	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized Organization) }`
+	 */
+	_id?: MongoId;
+	/** A name for the Organization */
+	name: string;
+	/** A description for the Organization */
+	description?: string;
+	/** Unix time (ms) when the Organization was created */
+	created_at?: I64;
+	/** Unix time (ms) when the Organization was last updated */
+	updated_at?: I64;
+}
+
+export type GetOrganizationResponse = Organization;
+
 export type GetUserGroupResponse = UserGroup;
 
 export type GetUserResponse = User;
 
 export type GetVariableResponse = Variable;
 
+export type GetTemplateResponse = Template;
+
+export type GetDashboardResponse = Dashboard;
+
+export type InstantiateTemplateResponse = Stack;
+
 export enum ContainerStateStatusEnum {
 	Running = "running",
 	Created = "created",
@@ -3230,6 +4346,47 @@ export interface Container {
 
 export type InspectDeploymentContainerResponse = Container;
 
+export enum InheritedSource {
+	/** Only defined on the resource. */
+	Resource = "Resource",
+	/** Only defined on the template. */
+	Template = "Template",
+	/** Defined on both, the resource value is used. */
+	Override = "Override",
+}
+
+/** A single effective value after inheritance. */
+export interface InheritedValue {
+	/** The variable / label name. */
+	key: string;
+	/** The effective value. */
+	value: string;
+	/** The template value, if the template defines the key. */
+	template_value?: string;
+	/** Where the effective value comes from. */
+	source: InheritedSource;
+}
+
+/**
+ * How a Deployment / Stack config is combined with the
+ * template it inherits from at deploy time.
+ */
+export interface ConfigInheritance {
+	/**
+	 * The id of the inherited template,
+	 * or empty if nothing is inherited.
+	 */
+	template: string;
+	/** The effective environment variables. */
+	environment: InheritedValue[];
+	/** The effective labels. Deployment only. */
+	labels: InheritedValue[];
+	/** The effective restart mode. Deployment only. */
+	restart?: InheritedValue;
+}
+
+export type GetDeploymentInheritanceResponse = ConfigInheritance;
+
 export type InspectDockerContainerResponse = Container;
 
 /** Information about the image's RootFS, including the layer IDs. */
@@ -3596,6 +4753,15 @@ export interface ComposeProject {
 	status?: string;
 	/** The compose files included in the project. */
 	compose_files: string[];
+	/** The services of the project, with their running replicas. */
+	services?: ComposeProjectService[];
+}
+
+export interface ComposeProjectService {
+	/** The service name. */
+	service: string;
+	/** The number of running containers for the service. */
+	replicas: I64;
 }
 
 export type ListComposeProjectsResponse = ComposeProject[];
@@ -3652,6 +4818,51 @@ export interface NetworkListItem {
 
 export type ListDockerNetworksResponse = NetworkListItem[];
 
+/** A service in the swarm, from `docker service ls`. */
+export interface SwarmServiceListItem {
+	id: string;
+	/** The service name, `{stack}_{service}` for stack services. */
+	name: string;
+	/** `replicated` or `global` */
+	mode: string;
+	/** The running / desired replicas, ie `2/3`. */
+	replicas: string;
+	image: string;
+	/** The published ports, ie `*:8080->80/tcp`. */
+	ports: string;
+}
+
+/** A node in the swarm, from `docker node ls`. */
+export interface SwarmNodeListItem {
+	id: string;
+	hostname: string;
+	/** `Ready` or `Down` */
+	status: string;
+	/** `Active`, `Pause` or `Drain` */
+	availability: string;
+	/** `Leader` or `Reachable` for managers, empty for workers. */
+	manager_status: string;
+	engine_version: string;
+	/** Whether this is the node Periphery is connected to. */
+	is_self: boolean;
+}
+
+/** The output of `docker service inspect`. */
+export interface SwarmService {
+	ID: string;
+	CreatedAt?: string;
+	UpdatedAt?: string;
+	/**
+	 * The service spec, ie the image, replicas,
+	 * networks and update config.
+	 */
+	Spec: JsonValue;
+	/** The published ports and virtual ips. */
+	Endpoint?: JsonValue;
+	/** The state of the latest rolling update, if any. */
+	UpdateStatus?: JsonValue;
+}
+
 export interface ProviderAccount {
 	/** The account username. Required. */
 	username: string;
@@ -3723,6 +4934,8 @@ export interface GitProvider {
 
 export type ListGitProvidersFromConfigResponse = GitProvider[];
 
+export type ListOrganizationsResponse = Organization[];
+
 export type ListOnboardingKeysResponse = OnboardingKey[];
 
 export type UserTarget = 
@@ -3935,10 +5148,20 @@ export interface ServerListItemInfo {
 	send_mem_alerts: boolean;
 	/** Whether server is configured to send disk alerts. */
 	send_disk_alerts: boolean;
+	/** Whether server is configured to send gpu alerts. */
+	send_gpu_alerts?: boolean;
+	/** Whether server is configured to send disk health alerts. */
+	send_disk_health_alerts?: boolean;
+	/** Whether server is configured to send zfs pool alerts. */
+	send_zfs_alerts?: boolean;
+	/** Whether server is configured to send os security update alerts. */
+	send_os_update_alerts?: boolean;
 	/** Whether server is configured to send version mismatch alerts. */
 	send_version_mismatch_alerts: boolean;
 	/** The Komodo Periphery version. */
 	version?: string;
+	/** Round trip latency to Periphery in ms, if known. */
+	latency_ms?: number;
 	/** The public key of Periphery */
 	public_key?: string;
 	/**
@@ -3946,6 +5169,11 @@ export interface ServerListItemInfo {
 	 * it will be stored here to accept the connection later on.
 	 */
 	attempted_public_key?: string;
+	/**
+	 * If Periphery presents a tls certificate not matching the pins,
+	 * its pin will be stored here to accept the connection later on.
+	 */
+	attempted_tls_pin?: string;
 	/**
 	 * Whether server is configured to send unreachable alerts.
 	 * Whether terminals are disabled for this Server.
@@ -3953,6 +5181,13 @@ export interface ServerListItemInfo {
 	terminals_disabled: boolean;
 	/** Whether container terminals are disabled for this Server. */
 	container_terminals_disabled: boolean;
+	/**
+	 * The Periphery request types allowed on this Server.
+	 * Empty means all are allowed.
+	 */
+	allowed_requests?: string[];
+	/** The Periphery request types denied on this Server. */
+	denied_requests?: string[];
 }
 
 export type ServerListItem = ResourceListItem<ServerListItemInfo>;
@@ -4081,8 +5316,53 @@ export interface SystemProcess {
 
 export type ListSystemProcessesResponse = SystemProcess[];
 
+/**
+ * A TCP / UDP socket listening on the host,
+ * with the process and container owning it.
+ */
+export interface ListeningSocket {
+	/** `tcp` or `udp` */
+	protocol: PortTypeEnum;
+	/** The bound address, ie `0.0.0.0`, `::` or `127.0.0.1` */
+	address: string;
+	/** The bound port */
+	port: number;
+	/** The PID of the owning process, if Periphery can see it */
+	pid?: number;
+	/** The name of the owning process */
+	process?: string;
+	/**
+	 * The name of the container owning the socket,
+	 * either publishing the port or running the process.
+	 */
+	container?: string;
+}
+
+export type ListListeningSocketsResponse = ListeningSocket[];
+
+/**
+ * A systemd unit loaded on the server,
+ * as listed by `systemctl list-units`.
+ */
+export interface SystemdUnit {
+	/** The unit name, ie `wg-quick@wg0.service` */
+	name: string;
+	/** Whether the unit definition was loaded, ie `loaded`, `not-found` */
+	load_state: string;
+	/** The high level activation state, ie `active`, `inactive`, `failed` */
+	active_state: string;
+	/** The unit type specific state, ie `running`, `exited`, `waiting` */
+	sub_state: string;
+	/** The unit description */
+	description: string;
+}
+
+export type ListSystemdUnitsResponse = SystemdUnit[];
+
 export type ListTagsResponse = Tag[];
 
+export type ListTagUsageReportsResponse = TagUsageReport[];
+
 /**
  * Info about an active terminal on a server.
  * Retrieve with [ListTerminals][crate::api::read::server::ListTerminals].
@@ -4094,10 +5374,118 @@ export interface TerminalInfo {
 	command: string;
 	/** The size of the terminal history in memory. */
 	stored_size_kb: number;
+	/**
+	 * The file name of the terminal recording,
+	 * if the terminal is being recorded.
+	 */
+	recording?: string;
+	/**
+	 * The milliseconds remaining before the terminal is closed
+	 * by the Periphery idle timeout or max lifetime, if either is set.
+	 */
+	ttl_ms?: I64;
+	/**
+	 * The number of clients connected to the terminal,
+	 * including the read only viewers.
+	 */
+	connections?: U64;
+	/** The number of read only viewers connected to the terminal. */
+	read_only_connections?: U64;
 }
 
 export type ListTerminalsResponse = TerminalInfo[];
 
+/**
+ * An asciicast v2 terminal recording stored on a server.
+ * Retrieve with [ListTerminalRecordings][crate::api::read::server::ListTerminalRecordings].
+ */
+export interface TerminalRecording {
+	/** The file name of the recording, ie `my-terminal-1700000000000.cast`. */
+	name: string;
+	/** The name of the recorded terminal. */
+	terminal: string;
+	/** The size of the recording file. */
+	size_kb: number;
+	/** Unix timestamp in milliseconds the recording started. */
+	started_at: I64;
+}
+
+export type ListTerminalRecordingsResponse = TerminalRecording[];
+
+export type GetTerminalRecordingResponse = string;
+
+/**
+ * A user connected to a terminal through Core.
+ * Retrieve with [ListActiveTerminalSessions][crate::api::read::server::ListActiveTerminalSessions].
+ */
+export interface ActiveTerminalSession {
+	/** The id of the session, used to force disconnect. */
+	id: string;
+	/** The id of the connected user. */
+	user_id: string;
+	/** The username of the connected user. */
+	username: string;
+	/** The id of the server the terminal is on. */
+	server_id: string;
+	/** The name of the server the terminal is on. */
+	server_name: string;
+	/** The terminal, eg. the terminal name or `exec: <container>`. */
+	terminal: string;
+	/** Unix timestamp in milliseconds the session started. */
+	started_at: I64;
+}
+
+export type ListActiveTerminalSessionsResponse = ActiveTerminalSession[];
+
+/**
+ * A command Periphery runs on a cron schedule,
+ * whether or not Core is connected.
+ * The results are sent to Core as Updates on the Server.
+ */
+export interface ServerSchedule {
+	/** The unique schedule name, ie `nightly-prune` */
+	name: string;
+	/**
+	 * The cron expression, in the host local timezone.
+	 * The seconds field is optional, ie
+	 * `0 3 * * *` and `0 0 3 * * *` both run at 03:00.
+	 */
+	schedule: string;
+	/** The shell command to run, ie `docker system prune -af` */
+	command: string;
+	/**
+	 * Disabled schedules are kept, but not run.
+	 * Default: true
+	 */
+	enabled?: boolean;
+}
+
+/** A [ServerSchedule] with its run status on the server. */
+export interface ServerScheduleStatus {
+	schedule: ServerSchedule;
+	/**
+	 * Whether the schedule is set in the Periphery config file.
+	 * These can't be changed through the API.
+	 */
+	from_config: boolean;
+	/**
+	 * The next run timestamp in ms,
+	 * if the schedule is enabled and valid.
+	 */
+	next_run_ts?: I64;
+	/** The reason the schedule can't run, ie an invalid expression. */
+	error?: string;
+	/**
+	 * The start timestamp of the last run in ms.
+	 * Not kept across Periphery restarts.
+	 */
+	last_run_ts?: I64;
+	/** Whether the last run succeeded. */
+	last_run_success?: boolean;
+}
+
+export type ListServerSchedulesResponse = ServerScheduleStatus[];
+
 export type ListUserGroupsResponse = UserGroup[];
 
 export type ListUserTargetPermissionsResponse = Permission[];
@@ -4106,6 +5494,10 @@ export type ListUsersResponse = User[];
 
 export type ListVariablesResponse = Variable[];
 
+export type ListTemplatesResponse = Template[];
+
+export type ListDashboardsResponse = Dashboard[];
+
 /** The response for [LoginLocalUser] */
 export type LoginLocalUserResponse = JwtResponse;
 
@@ -4144,8 +5536,16 @@ export interface ServerQuerySpecifics {
 /** Server-specific query */
 export type ServerQuery = ResourceQuery<ServerQuerySpecifics>;
 
+export type SendNotificationResponse = NoData;
+
+export type SetResourceOrganizationResponse = NoData;
+
 export type SetLastSeenUpdateResponse = NoData;
 
+export type SetReadOnlyModeResponse = ReadOnlyMode;
+
+export type SetUserOrganizationResponse = NoData;
+
 /** Response for [SignUpLocalUser]. */
 export type SignUpLocalUserResponse = JwtResponse;
 
@@ -4199,6 +5599,10 @@ export type UpdateVariableIsSecretResponse = Variable;
 
 export type UpdateVariableValueResponse = Variable;
 
+export type UpdateTemplateResponse = Template;
+
+export type UpdateDashboardResponse = Dashboard;
+
 export type _PartialActionConfig = Partial<ActionConfig>;
 
 export type _PartialAlerterConfig = Partial<AlerterConfig>;
@@ -4238,6 +5642,15 @@ export interface __Serror {
 
 export type _Serror = __Serror;
 
+/** Mark the calling user's notifications as read. Response: [NoData]. */
+export interface AckNotifications {
+	/**
+	 * The notification ids to mark as read.
+	 * If empty, all of the user's notifications are marked as read.
+	 */
+	ids?: string[];
+}
+
 /** **Admin only.** Add a user to a user group. Response: [UserGroup] */
 export interface AddUserToUserGroup {
 	/** The name or id of UserGroup that user should be added to. */
@@ -4600,6 +6013,45 @@ export interface CloseAlert {
 	id: string;
 }
 
+/**
+ * **Admin only.** Enable or disable Core global read-only mode.
+ * Response: [ReadOnlyMode].
+ * 
+ * While enabled, all write and execute requests (other than this one)
+ * are rejected with `503 Service Unavailable` and the given reason.
+ * Reads, monitoring and alerting continue as normal.
+ * Useful during database maintenance or incident freezes.
+ * 
+ * Note. Read-only mode is not persisted across Core restarts.
+ */
+export interface SetReadOnlyMode {
+	/** Whether to enable read-only mode. */
+	enabled: boolean;
+	/** The reason for read-only mode, shown to users when requests are rejected. */
+	reason?: string;
+}
+
+/**
+ * Commits the container on the target server into a new image,
+ * so it can be redeployed later. Response: [Update].
+ * 
+ * 1. Runs `docker commit ${container_name} ${image}`.
+ */
+export interface CommitContainer {
+	/** Name or id */
+	server: string;
+	/** The container name */
+	container: string;
+	/** The image to create, ie `repository:tag`. */
+	image: string;
+	/** Sets the author of the image. */
+	author?: string;
+	/** Sets the commit message of the image. */
+	message?: string;
+	/** Pause the container during the commit. Default: true */
+	pause: boolean;
+}
+
 /**
  * Exports matching resources, and writes to the target sync's resource file. Response: [Update]
  * 
@@ -4626,7 +6078,10 @@ export enum TerminalRecreateMode {
 	DifferentCommand = "DifferentCommand",
 }
 
-/** Query to connect to a container attach session (interactive shell over websocket) on the given server. */
+/**
+ * Query to connect to a container attach session (interactive shell over websocket) on the given server.
+ * This call requires the ContainerAttach specific permission on the Server.
+ */
 export interface ConnectContainerAttachQuery {
 	/** Server Id or name */
 	server: string;
@@ -4637,6 +6092,11 @@ export interface ConnectContainerAttachQuery {
 	 * Default is 'DifferentCommand'
 	 */
 	recreate: TerminalRecreateMode;
+	/**
+	 * Override the key sequence for detaching from the container,
+	 * eg. `ctrl-x,x`. Default is docker's `ctrl-p,ctrl-q`.
+	 */
+	detach_keys?: string;
 }
 
 /** Query to connect to a container exec session (interactive shell over websocket) on the given server. */
@@ -4656,7 +6116,7 @@ export interface ConnectContainerExecQuery {
 
 /**
  * Query to connect to a container attach session (interactive shell over websocket) on the given Deployment.
- * This call will use access to the Deployment Terminal to permission the call.
+ * This call requires the ContainerAttach specific permission on the Deployment.
  */
 export interface ConnectDeploymentAttachQuery {
 	/** Deployment Id or name */
@@ -4666,11 +6126,16 @@ export interface ConnectDeploymentAttachQuery {
 	 * Default is 'DifferentCommand'
 	 */
 	recreate: TerminalRecreateMode;
+	/**
+	 * Override the key sequence for detaching from the container,
+	 * eg. `ctrl-x,x`. Default is docker's `ctrl-p,ctrl-q`.
+	 */
+	detach_keys?: string;
 }
 
 /**
  * Query to connect to a container exec session (interactive shell over websocket) on the given Deployment.
- * This call will use access to the Deployment Terminal to permission the call.
+ * This call requires the ContainerAttach specific permission on the Deployment.
  */
 export interface ConnectDeploymentExecQuery {
 	/** Deployment Id or name */
@@ -4686,7 +6151,7 @@ export interface ConnectDeploymentExecQuery {
 
 /**
  * Query to connect to a container attach session (interactive shell over websocket) on the given Stack / service.
- * This call will use access to the Stack Terminal to permission the call.
+ * This call requires the ContainerAttach specific permission on the Stack.
  */
 export interface ConnectStackAttachQuery {
 	/** Stack Id or name */
@@ -4698,11 +6163,35 @@ export interface ConnectStackAttachQuery {
 	 * Default is 'DifferentCommand'
 	 */
 	recreate: TerminalRecreateMode;
+	/**
+	 * Override the key sequence for detaching from the container,
+	 * eg. `ctrl-x,x`. Default is docker's `ctrl-p,ctrl-q`.
+	 */
+	detach_keys?: string;
+}
+
+/**
+ * Sent to the client as a text message, prefixed with `ATTACH_INFO:`,
+ * once the container attach session is established.
+ */
+export interface ContainerAttachInfo {
+	/**
+	 * Whether the container main process was started with a TTY (`-t`).
+	 * Without one, output is line buffered and there is no terminal control.
+	 */
+	tty: boolean;
+	/**
+	 * Whether the container main process stdin is open (`-i`).
+	 * If not, the session is read only.
+	 */
+	stdin: boolean;
+	/** The key sequence which detaches from the container. */
+	detach_keys: string;
 }
 
 /**
  * Query to connect to a container exec session (interactive shell over websocket) on the given Stack / service.
- * This call will use access to the Stack Terminal to permission the call.
+ * This call requires the ContainerAttach specific permission on the Stack.
  */
 export interface ConnectStackExecQuery {
 	/** Stack Id or name */
@@ -4729,6 +6218,13 @@ export interface ConnectTerminalQuery {
 	 * Create a terminal using [CreateTerminal][super::write::server::CreateTerminal]
 	 */
 	terminal: string;
+	/**
+	 * Only view the terminal output.
+	 * Any input sent is dropped by Periphery.
+	 * Read only connections require Read + Terminal permission
+	 * on the Server, others require Write + Terminal.
+	 */
+	read_only?: boolean;
 }
 
 /** Blkio stats entry.  This type is Linux-specific and omitted for Windows containers. */
@@ -5129,8 +6625,20 @@ export interface CreateBuilder {
 	config?: PartialBuilderConfig;
 }
 
-/** Create a deployment. Response: [Deployment]. */
-export interface CreateDeployment {
+/** Create a dashboard owned by the calling user. Response: [Dashboard]. */
+export interface CreateDashboard {
+	/** The name of the dashboard. */
+	name: string;
+	/** The description of the dashboard. default: "". */
+	description?: string;
+	/** Whether all users can view the dashboard. default: false. */
+	shared?: boolean;
+	/** The initial widget layout. */
+	widgets?: DashboardWidget[];
+}
+
+/** Create a deployment. Response: [Deployment]. */
+export interface CreateDeployment {
 	/** The name given to newly created deployment. */
 	name: string;
 	/** Optional partial config to initialize the deployment with. */
@@ -5220,6 +6728,13 @@ export interface CreateOnboardingKey {
 	copy_server?: string;
 	/** Optional. Whether to also create a Builder for the Server. */
 	create_builder?: boolean;
+	/**
+	 * Optional. The max number of Servers the key can onboard.
+	 * Default is 0, which means no limit.
+	 */
+	max_uses?: I64;
+	/** Optional. The key can only onboard a single Server. */
+	single_use?: boolean;
 }
 
 /** The response for [CreateServerOnboardingKey] */
@@ -5264,6 +6779,14 @@ export interface CreateServer {
 	public_key?: string;
 }
 
+/** **Admin only.** Create an Organization. Response: [Organization] */
+export interface CreateOrganization {
+	/** The name to assign to the new Organization */
+	name: string;
+	/** A description for the Organization */
+	description?: string;
+}
+
 /**
  * **Admin only.** Create a service user.
  * Response: [User].
@@ -5311,6 +6834,19 @@ export interface CreateTerminal {
 	command: string;
 	/** Default: `Never` */
 	recreate?: TerminalRecreateMode;
+	/**
+	 * Whether to record the terminal output,
+	 * see [ListTerminalRecordings][crate::api::read::server::ListTerminalRecordings].
+	 * Terminals are always recorded when the
+	 * `record_terminals` Periphery config is enabled.
+	 * Default: The `record_terminals` Periphery config
+	 */
+	record?: boolean;
+	/**
+	 * The scrollback history size in KiB.
+	 * Default: The `terminal_history_kb` Periphery config
+	 */
+	history_kb?: U64;
 }
 
 /** **Admin only.** Create a user group. Response: [UserGroup] */
@@ -5331,6 +6867,20 @@ export interface CreateVariable {
 	is_secret?: boolean;
 }
 
+/** **Admin only.** Create a template. Response: [Template]. */
+export interface CreateTemplate {
+	/** The name of the template to create. */
+	name: string;
+	/** The description of the template. default: "". */
+	description?: string;
+	/** The compose file contents. default: "". */
+	file_contents?: string;
+	/** The environment (.env) contents. default: "". */
+	environment?: string;
+	/** The parameters to prompt for at instantiation. */
+	parameters?: TemplateParameter[];
+}
+
 /** Configuration for a Custom alerter endpoint. */
 export interface CustomAlerterEndpoint {
 	/** The http/s endpoint to send the POST to */
@@ -5364,6 +6914,16 @@ export interface DeleteAllTerminals {
 	server: string;
 }
 
+/**
+ * Force disconnect an active terminal session. Admin only.
+ * The terminal itself is kept on the server.
+ * Response: [NoData]
+ */
+export interface DisconnectTerminalSession {
+	/** The session id, from [ListActiveTerminalSessions][crate::api::read::ListActiveTerminalSessions]. */
+	id: string;
+}
+
 /**
  * Delete an api key for the calling user.
  * Response: [NoData]
@@ -5399,6 +6959,15 @@ export interface DeleteBuilder {
 	id: string;
 }
 
+/**
+ * Delete a dashboard. Only the owner or an admin can delete.
+ * Response: [Dashboard].
+ */
+export interface DeleteDashboard {
+	/** The id of the dashboard to delete. */
+	id: string;
+}
+
 /**
  * Deletes the deployment at the given id, and returns the deleted deployment.
  * Response: [Deployment].
@@ -5451,6 +7020,16 @@ export interface DeleteNetwork {
 	name: string;
 }
 
+/**
+ * **Admin only.** Delete an Organization.
+ * Fails if any users, resources, tags, or variables
+ * still belong to the Organization. Response: [Organization]
+ */
+export interface DeleteOrganization {
+	/** The id of the Organization */
+	id: string;
+}
+
 /**
  * **Admin only.** Delete an onboarding key.
  * Response: The deleted [OnboardingKey].
@@ -5459,6 +7038,63 @@ export interface DeleteOnboardingKey {
 	public_key: string;
 }
 
+/**
+ * **Admin only.** Approve a Server waiting for onboarding approval.
+ * Creates the Server using the settings of the onboarding key
+ * it used, and the Periphery agent can connect on its next attempt.
+ * Response: [Server].
+ */
+export interface ApprovePendingServer {
+	/** The id of the PendingServer. */
+	id: string;
+	/**
+	 * Optional. Create the Server with a different name
+	 * than the one Periphery requested.
+	 */
+	name?: string;
+}
+
+/**
+ * **Admin only.** Reject a Server waiting for onboarding approval.
+ * The Periphery agent will be added back to the queue
+ * if it retries onboarding.
+ * Response: [PendingServer].
+ */
+export interface DeletePendingServer {
+	/** The id of the PendingServer. */
+	id: string;
+}
+
+/**
+ * **Admin only.** Revoke a Periphery public key.
+ * Core will refuse connections authenticating with the key,
+ * even if a Server still expects it, and Servers currently
+ * connected with the key are disconnected.
+ * Response: [RevokedKey].
+ */
+export interface RevokePublicKey {
+	/**
+	 * The Periphery public key to revoke.
+	 * Accepts the base64 der or pem form.
+	 */
+	public_key: string;
+	/** Optional. Why the key is revoked. */
+	reason?: string;
+}
+
+/**
+ * **Admin only.** Remove a public key from the revoked keys,
+ * allowing Periphery to authenticate with it again.
+ * Response: The removed [RevokedKey].
+ */
+export interface UnrevokePublicKey {
+	/**
+	 * The revoked Periphery public key.
+	 * Accepts the base64 der or pem form.
+	 */
+	public_key: string;
+}
+
 /**
  * Deletes the procedure at the given id, and returns the deleted procedure.
  * Response: [Procedure]
@@ -5499,6 +7135,18 @@ export interface DeleteServer {
  * Deletes the stack at the given id, and returns the deleted stack.
  * Response: [Stack]
  */
+/**
+ * Delete a schedule added with [UpsertServerSchedule].
+ * Schedules set in the Periphery config file can't be deleted.
+ * Response: [NoData]
+ */
+export interface DeleteServerSchedule {
+	/** Server Id or name */
+	server: string;
+	/** The schedule name */
+	name: string;
+}
+
 export interface DeleteStack {
 	/** The id or name of the stack to delete. */
 	id: string;
@@ -5525,6 +7173,18 @@ export interface DeleteTerminal {
 	terminal: string;
 }
 
+/**
+ * Clear the scrollback history of a terminal on the server,
+ * so it isn't replayed when connecting.
+ * Response: [NoData]
+ */
+export interface ClearTerminalHistory {
+	/** Server Id or name */
+	server: string;
+	/** The name of the terminal on the server. */
+	terminal: string;
+}
+
 /**
  * **Admin only**. Delete a user.
  * Admins can delete any non-admin user.
@@ -5549,6 +7209,11 @@ export interface DeleteVariable {
 	name: string;
 }
 
+/** **Admin only.** Delete a template. Response: [Template]. */
+export interface DeleteTemplate {
+	name: string;
+}
+
 /**
  * Delete a docker volume.
  * Response: [Update]
@@ -5742,6 +7407,30 @@ export interface ExecuteTerminalBody {
 	command: string;
 }
 
+/**
+ * Stream the lines of a file on the server host, ie `/var/log/syslog`.
+ * The path must be inside the `allowed_log_paths` in the Periphery config.
+ * This call requires the Logs specific permission on the Server.
+ */
+export interface TailHostFileBody {
+	/** Server Id or name */
+	server: string;
+	/** The absolute path of the file on the host. */
+	path: string;
+	/**
+	 * The number of lines from the end of the file to start with.
+	 * Default: 100.
+	 * Max: 5000.
+	 */
+	lines: U64;
+	/**
+	 * Keep streaming the lines appended to the file,
+	 * until the request is closed. The opened file is followed,
+	 * not the path, so rotated files need a new request.
+	 */
+	follow: boolean;
+}
+
 /**
  * Get pretty formatted monrun sync toml for all resources
  * which the user has permissions to view.
@@ -5769,6 +7458,11 @@ export interface ExportAllResourcesToToml {
 	 * Default: false
 	 */
 	include_user_groups?: boolean;
+	/**
+	 * Whether to include onboarding key metadata in the exported contents.
+	 * Admin only. Default: false
+	 */
+	include_onboarding_keys?: boolean;
 }
 
 /**
@@ -5782,6 +7476,8 @@ export interface ExportResourcesToToml {
 	user_groups?: string[];
 	/** Whether to include variables */
 	include_variables?: boolean;
+	/** Whether to include onboarding key metadata. Admin only. */
+	include_onboarding_keys?: boolean;
 }
 
 /**
@@ -5836,6 +7532,11 @@ export interface FullContainerStats {
 	memory_stats?: ContainerMemoryStats;
 	/** Network statistics for the container per interface.  This field is omitted if the container has no networking enabled. */
 	networks?: Record<string, ContainerNetworkStats>;
+	/**
+	 * Network and block IO rates, computed by the Periphery container stats poller.
+	 * This field is omitted if the poller has not yet taken two samples of the container.
+	 */
+	io_rates?: ContainerIoRates;
 }
 
 /** Get a specific action. Response: [Action]. */
@@ -6020,6 +7721,22 @@ export interface GetCoreInfoResponse {
 	timezone: string;
 	/** Default public key allowing this Core to authenticate to Periphery agents. */
 	public_key: string;
+	/**
+	 * The current read-only mode state.
+	 * While enabled, write and execute requests are rejected.
+	 */
+	read_only?: ReadOnlyMode;
+}
+
+/**
+ * Get a specific dashboard. Response: [Dashboard].
+ * 
+ * Non admin users can only get dashboards
+ * they own or which are shared.
+ */
+export interface GetDashboard {
+	/** The id of the dashboard. */
+	id: string;
 }
 
 /** Get a specific deployment by name or id. Response: [Deployment]. */
@@ -6056,6 +7773,15 @@ export interface GetDeploymentContainerResponse {
 	container?: ContainerListItem;
 }
 
+/**
+ * Get the config the Deployment inherits from its template,
+ * with the source of each value. Response: [ConfigInheritance].
+ */
+export interface GetDeploymentInheritance {
+	/** Id or name */
+	deployment: string;
+}
+
 /**
  * Get the deployment log's tail, split by stdout/stderr.
  * Response: [Log].
@@ -6162,6 +7888,20 @@ export interface GetHistoricalServerStats {
 	page?: number;
 }
 
+/** Network and block IO rates of a single container stored on the database. */
+export interface ContainerIoStatsRecord {
+	/** The container name */
+	name: string;
+	/** Network receive rate in bytes per second */
+	net_rx: number;
+	/** Network transmit rate in bytes per second */
+	net_tx: number;
+	/** Block device read rate in bytes per second */
+	block_read: number;
+	/** Block device write rate in bytes per second */
+	block_write: number;
+}
+
 /** System stats stored on the database. */
 export interface SystemStatsRecord {
 	/** Unix timestamp in milliseconds */
@@ -6170,6 +7910,13 @@ export interface SystemStatsRecord {
 	sid: string;
 	/** Cpu usage percentage */
 	cpu_perc: number;
+	/** Usage percentage of each cpu core, in core order */
+	cpu_cores?: number[];
+	/**
+	 * Percentage of cpu time stolen by the hypervisor.
+	 * Only reported on Linux.
+	 */
+	cpu_steal_perc?: number;
 	/** Load average (1m, 5m, 15m) */
 	load_average?: SystemLoadAverage;
 	/** Memory used in GB */
@@ -6186,6 +7933,10 @@ export interface SystemStatsRecord {
 	network_ingress_bytes?: number;
 	/** Total network egress in bytes */
 	network_egress_bytes?: number;
+	/** Network and block IO rates of the containers on the server */
+	containers?: ContainerIoStatsRecord[];
+	/** Usage of the GPUs on the server */
+	gpus?: SingleGpuUsage[];
 }
 
 /** Response to [GetHistoricalServerStats]. */
@@ -6226,6 +7977,52 @@ export interface GetLoginOptionsResponse {
 export interface GetPeripheryInformation {
 	/** Id or name */
 	server: string;
+	/**
+	 * Only include these fields in the response,
+	 * as dot separated paths, eg. `version`. Includes all fields if empty.
+	 */
+	fields?: string[];
+}
+
+/**
+ * **Admin only.** List the load metrics of the Core
+ * Periphery connection shards.
+ * Response: [ListConnectionShardsResponse].
+ */
+export interface ListConnectionShards {
+}
+
+/**
+ * Get the transport metrics of the target server's Periphery connection,
+ * eg. to find which server is saturating Core's uplink.
+ * Response: [ServerConnectionMetrics].
+ */
+export interface GetServerConnectionMetrics {
+	/** Id or name */
+	server: string;
+}
+
+/**
+ * **Admin only.** List the recorded transport message headers,
+ * newest first. Requires Core `transport_trace` to be enabled.
+ * Response: [ListTransportTraceResponse].
+ */
+export interface ListTransportTrace {
+	/** Only include messages on this Server's connection. Id or name. */
+	server?: string;
+	/** Max entries to return. Default: 1000 */
+	limit?: number;
+}
+
+/**
+ * Get a specific Organization by name or id.
+ * Response: [Organization].
+ * 
+ * Non admin users can only get the Organization they belong to.
+ */
+export interface GetOrganization {
+	/** Name or Id */
+	organization: string;
 }
 
 /**
@@ -6413,6 +8210,15 @@ export interface GetStackActionState {
 	stack: string;
 }
 
+/**
+ * Get the config the Stack inherits from its template,
+ * with the source of each value. Response: [ConfigInheritance].
+ */
+export interface GetStackInheritance {
+	/** Id or name */
+	stack: string;
+}
+
 /**
  * Get a stack's logs. Filter down included services. Response: [GetStackLogResponse].
  * 
@@ -6466,6 +8272,11 @@ export interface GetStacksSummaryResponse {
 export interface GetSystemInformation {
 	/** Id or name */
 	server: string;
+	/**
+	 * Only include these fields in the response,
+	 * as dot separated paths, eg. `host_name`. Includes all fields if empty.
+	 */
+	fields?: string[];
 }
 
 /**
@@ -6478,6 +8289,78 @@ export interface GetSystemInformation {
 export interface GetSystemStats {
 	/** Id or name */
 	server: string;
+	/**
+	 * Only include these fields in the response,
+	 * as dot separated paths, eg. `cpu_perc`. Includes all fields if empty.
+	 */
+	fields?: string[];
+}
+
+/**
+ * Get the tail of a systemd unit journal on the target server.
+ * Response: [Log].
+ * 
+ * Requires `enable_systemd_management` in the Periphery config.
+ */
+export interface GetSystemdUnitJournal {
+	/** Id or name */
+	server: string;
+	/** The unit name, ie `nfs-server.service` */
+	unit: string;
+	/**
+	 * The number of lines of the journal tail to include.
+	 * Default: 100.
+	 * Max: 5000.
+	 */
+	tail: U64;
+	/** Show the entries with microsecond timestamps. */
+	timestamps?: boolean;
+}
+
+/**
+ * Get the detailed status of a systemd unit on the target server.
+ * Response: [SystemdUnitStatus].
+ * 
+ * Requires `enable_systemd_management` in the Periphery config.
+ */
+export interface GetSystemdUnitStatus {
+	/** Id or name */
+	server: string;
+	/** The unit name, ie `nfs-server.service` */
+	unit: string;
+}
+
+/**
+ * Suggest the best Servers to place a new Deployment on,
+ * based on their current stats and configured capacity.
+ * Response: [SuggestServerPlacementResponse].
+ * 
+ * Servers are ordered with the best fit first.
+ */
+export interface SuggestServerPlacement {
+	/**
+	 * Only consider these Servers (id or name).
+	 * If empty, considers all Servers the user has access to.
+	 */
+	servers?: string[];
+	/** The expected CPU usage (percent of one Server's total) of the Deployment. */
+	cpu?: number;
+	/** The expected memory usage (GB) of the Deployment. */
+	mem_gb?: number;
+}
+
+/**
+ * Check whether a new Deployment fits on the target Server,
+ * based on its current stats and configured capacity.
+ * Response: [ServerPlacement].
+ */
+export interface CheckServerPlacement {
+	/** Id or name */
+	server: string;
+	/** The expected CPU usage (percent of the Server's total) of the Deployment. */
+	cpu?: number;
+	/** The expected memory usage (GB) of the Deployment. */
+	mem_gb?: number;
 }
 
 /** Get data for a specific tag. Response [Tag]. */
@@ -6486,6 +8369,20 @@ export interface GetTag {
 	tag: string;
 }
 
+/**
+ * Get the resource usage report for a tag over a time window.
+ * Only includes the resources the user has access to.
+ * Response: [TagUsageReport].
+ */
+export interface GetTagUsageReport {
+	/** Id or name */
+	tag: string;
+	/** Start of the window (unix ms). Default: 7 days before `until`. */
+	since?: I64;
+	/** End of the window (unix ms). Default: now. */
+	until?: I64;
+}
+
 /**
  * Get all data for the target update.
  * Response: [Update].
@@ -6540,6 +8437,12 @@ export interface GetVariable {
 	name: string;
 }
 
+/** Get a specific template by name. Response: [Template]. */
+export interface GetTemplate {
+	/** The name of the template to get. */
+	name: string;
+}
+
 /**
  * Get the version of the Komodo Core api.
  * Response: [GetVersionResponse].
@@ -6564,6 +8467,27 @@ export interface GetVersionResponse {
 export interface GlobalAutoUpdate {
 }
 
+/**
+ * Create a new Stack on the given Server from a template,
+ * replacing the template placeholders with the given parameters.
+ * Requires the same permissions as [CreateStack](super::CreateStack),
+ * as well as attach permission on the Server.
+ * Response: [Stack].
+ */
+export interface InstantiateTemplate {
+	/** The name of the template to instantiate. */
+	template: string;
+	/** The name given to the newly created Stack. */
+	name: string;
+	/** Id or name of the Server to create the Stack on. */
+	server: string;
+	/**
+	 * The parameter values, keyed by parameter name.
+	 * Parameters not given fall back to their default.
+	 */
+	parameters?: Record<string, string>;
+}
+
 /**
  * Inspect the docker container associated with the Deployment.
  * Response: [Container].
@@ -6579,6 +8503,11 @@ export interface InspectDockerContainer {
 	server: string;
 	/** The container name */
 	container: string;
+	/**
+	 * Only include these fields in the response,
+	 * as dot separated paths, eg. `State.Status`. Includes all fields if empty.
+	 */
+	fields?: string[];
 }
 
 /** Inspect a docker image on the server. Response: [Image]. */
@@ -6587,6 +8516,8 @@ export interface InspectDockerImage {
 	server: string;
 	/** The image name */
 	image: string;
+	/** Bypass the Core read cache and get the latest from Periphery. */
+	skip_cache?: boolean;
 }
 
 /** Inspect a docker network on the server. Response: [InspectDockerNetworkResponse]. */
@@ -6616,6 +8547,35 @@ export interface InspectStackContainer {
 	service: string;
 }
 
+/** A signal to send to a process on the system. */
+export enum ProcessSignal {
+	SigHup = "SIGHUP",
+	SigInt = "SIGINT",
+	SigQuit = "SIGQUIT",
+	SigKill = "SIGKILL",
+	SigUsr1 = "SIGUSR1",
+	SigUsr2 = "SIGUSR2",
+	SigTerm = "SIGTERM",
+	SigCont = "SIGCONT",
+	SigStop = "SIGSTOP",
+}
+
+/**
+ * Sends a signal to a process on the target server. Response: [Update]
+ * 
+ * 1. Runs `kill -s ${signal} ${pid}`.
+ * 
+ * Requires `enable_process_management` in the Periphery config.
+ */
+export interface KillSystemProcess {
+	/** Id or name */
+	server: string;
+	/** The process PID, see [ListSystemProcesses] */
+	pid: number;
+	/** The signal to send. Default: `SIGTERM` */
+	signal?: ProcessSignal;
+}
+
 export interface LatestCommit {
 	hash: string;
 	message: string;
@@ -6682,6 +8642,62 @@ export interface ListAlertsResponse {
 	next_page?: I64;
 }
 
+/** The kind of [Notification]. */
+export enum NotificationKind {
+	/** An Alert was opened or resolved on a target the user can see. */
+	Alert = "Alert",
+	/** Another user mentioned the user. */
+	Mention = "Mention",
+	/** Something is waiting on the user's approval. */
+	ApprovalRequest = "ApprovalRequest",
+}
+
+/**
+ * A message in a user's notification inbox.
+ * 
+ * Notifications are delivered to the UI over the update websocket
+ * as they are created, and are kept until acknowledged (and beyond)
+ * so important events aren't missed while away.
+ */
+export interface Notification {
+	/**
+	 * The Mongo ID of the Notification.
+	 * This field is de/serialized from/to JSON as
+	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized Notification) }`
+	 */
+	_id?: MongoId;
+	/** The id of the User receiving the Notification. */
+	user: string;
+	/** Unix timestamp in milliseconds the Notification was created. */
+	ts: I64;
+	/** The kind of Notification. */
+	kind?: NotificationKind;
+	/** A short title for the Notification. */
+	title: string;
+	/** The Notification message. */
+	body?: string;
+	/** The target the Notification is about. */
+	target?: ResourceTarget;
+	/** The id of the Alert / User / etc. which produced the Notification. */
+	source?: string;
+	/** Whether the Notification has been read (acknowledged). */
+	read?: boolean;
+	/** Unix timestamp in milliseconds the Notification was read. */
+	read_ts?: I64;
+}
+
+/** Response for [ListNotifications]. */
+export interface ListNotificationsResponse {
+	notifications: Notification[];
+	/** The total number of unread notifications for the user. */
+	unread: U64;
+	/**
+	 * If more notifications exist, the next page will be given here.
+	 * Otherwise it will be `null`
+	 */
+	next_page?: I64;
+}
+
 /**
  * List all docker containers on the target servers.
  * Response: [ListDockerContainersResponse].
@@ -6783,6 +8799,14 @@ export interface ListComposeProjects {
 	server: string;
 }
 
+/**
+ * List the dashboards the calling user owns,
+ * as well as all shared dashboards.
+ * Response: [ListDashboardsResponse].
+ */
+export interface ListDashboards {
+}
+
 /**
  * List deployments matching optional query.
  * Response: [ListDeploymentsResponse].
@@ -6807,6 +8831,8 @@ export interface ListDockerImageHistory {
 	server: string;
 	/** The image name */
 	image: string;
+	/** Bypass the Core read cache and get the latest from Periphery. */
+	skip_cache?: boolean;
 }
 
 /**
@@ -6891,6 +8917,11 @@ export interface ListFullBuilds {
 export interface ListFullDeployments {
 	/** optional structured query to filter deployments. */
 	query?: DeploymentQuery;
+	/**
+	 * Only include these fields of each deployment in the response,
+	 * as dot separated paths, eg. `config.image`. Includes all fields if empty.
+	 */
+	fields?: string[];
 }
 
 /** List procedures matching optional query. Response: [ListFullProceduresResponse]. */
@@ -6958,6 +8989,68 @@ export interface ListGitProvidersFromConfig {
 export interface ListOnboardingKeys {
 }
 
+/**
+ * **Admin only.** Gets list of Servers waiting for onboarding
+ * approval, most recently requested first.
+ * Response: [ListPendingServersResponse]
+ */
+export interface ListPendingServers {
+}
+
+/**
+ * **Admin only.** Gets list of revoked Periphery public keys,
+ * most recently revoked first.
+ * Response: [ListRevokedKeysResponse]
+ */
+export interface ListRevokedKeys {
+}
+
+/**
+ * **Admin only.** Get a paginated list of Core <-> Periphery
+ * login attempts, sorted by timestamp descending.
+ * Response: [ListAuthEventsResponse].
+ */
+export interface ListAuthEvents {
+	/** Filter by Server id or name. */
+	server?: string;
+	/** Filter by the public key presented. */
+	public_key?: string;
+	/** Filter by successful / failed logins. */
+	success?: boolean;
+	/** Filter by connection direction. */
+	direction?: AuthEventDirection;
+	/** Only include events at or after this unix timestamp in milliseconds. */
+	since?: I64;
+	/**
+	 * Retrieve older results by incrementing the page.
+	 * `page: 0` is default, and returns the most recent results.
+	 */
+	page?: U64;
+}
+
+/**
+ * Get a paginated list of the calling user's notifications,
+ * sorted by timestamp descending. Response: [ListNotificationsResponse].
+ */
+export interface ListNotifications {
+	/** Only include notifications which haven't been read. */
+	unread_only?: boolean;
+	/**
+	 * Retrieve older results by incrementing the page.
+	 * `page: 0` is default, and returns the most recent results.
+	 */
+	page?: U64;
+}
+
+/**
+ * List all Organizations which user can see. Response: [ListOrganizationsResponse].
+ * 
+ * Admins can see all Organizations,
+ * and users can see the Organization to which they belong.
+ */
+export interface ListOrganizations {
+}
+
 /**
  * List permissions for the calling user.
  * Does not include any permissions on UserGroups they may be a part of.
@@ -7036,6 +9129,31 @@ export interface ListStacks {
 export interface ListSystemProcesses {
 	/** Id or name */
 	server: string;
+	/** Bypass the Core read cache and get the latest from Periphery. */
+	skip_cache?: boolean;
+}
+
+/**
+ * List the TCP / UDP sockets listening on the target server,
+ * with the owning process and container. Use it to find
+ * the port conflicts before a deploy fails with `address already in use`.
+ * Response: [ListListeningSocketsResponse].
+ */
+export interface ListListeningSockets {
+	/** Id or name */
+	server: string;
+}
+
+/**
+ * List the systemd services, timers, sockets, paths and mounts
+ * loaded on the target server.
+ * Response: [ListSystemdUnitsResponse].
+ * 
+ * Requires `enable_systemd_management` in the Periphery config.
+ */
+export interface ListSystemdUnits {
+	/** Id or name */
+	server: string;
 }
 
 /**
@@ -7046,6 +9164,18 @@ export interface ListTags {
 	query?: MongoDocument;
 }
 
+/**
+ * Get the resource usage reports for all tags over a time window.
+ * Only includes the resources the user has access to.
+ * Response: [ListTagUsageReportsResponse].
+ */
+export interface ListTagUsageReports {
+	/** Start of the window (unix ms). Default: 7 days before `until`. */
+	since?: I64;
+	/** End of the window (unix ms). Default: now. */
+	until?: I64;
+}
+
 /**
  * List the current terminals on specified server.
  * Response: [ListTerminalsResponse].
@@ -7053,11 +9183,53 @@ export interface ListTags {
 export interface ListTerminals {
 	/** Id or name */
 	server: string;
-	/**
-	 * Force a fresh call to Periphery for the list.
-	 * Otherwise the response will be cached for 30s
-	 */
-	fresh?: boolean;
+	/**
+	 * Force a fresh call to Periphery for the list.
+	 * Otherwise the response will be cached for 30s
+	 */
+	fresh?: boolean;
+}
+
+/**
+ * List the terminal recordings stored on the server, newest first.
+ * Response: [ListTerminalRecordingsResponse].
+ */
+export interface ListTerminalRecordings {
+	/** Id or name */
+	server: string;
+}
+
+/**
+ * Get the contents of a terminal recording on the server,
+ * in asciicast v2 format for playback with `asciinema play`.
+ * Response: [GetTerminalRecordingResponse].
+ */
+export interface GetTerminalRecording {
+	/** Id or name */
+	server: string;
+	/**
+	 * The file name of the recording,
+	 * from [ListTerminalRecordings].
+	 */
+	recording: string;
+}
+
+/**
+ * List the users currently connected to terminals through Core.
+ * Admin only.
+ * Response: [ListActiveTerminalSessionsResponse].
+ */
+export interface ListActiveTerminalSessions {
+}
+
+/**
+ * List the commands the server runs on cron schedules,
+ * from the Periphery config file and the API, with their run status.
+ * Response: [ListServerSchedulesResponse].
+ */
+export interface ListServerSchedules {
+	/** Id or name */
+	server: string;
 }
 
 /**
@@ -7154,6 +9326,13 @@ export interface ListUsers {
 export interface ListVariables {
 }
 
+/**
+ * List all templates in the catalog.
+ * Response: [ListTemplatesResponse]
+ */
+export interface ListTemplates {
+}
+
 /**
  * Login as a local user. Will fail if the users credentials don't match
  * any local user.
@@ -7241,6 +9420,60 @@ export interface PermissionToml {
 	specific?: Array<SpecificPermission>;
 }
 
+/**
+ * Promotes a Stack to another Stack, ie staging to production. Response: [Update]
+ * 
+ * Copies the pinned commit, the compose files and the other release config
+ * from the `from` Stack onto the `to` Stack. The `to` Stack keeps its own Server,
+ * environment, tags and deploy settings.
+ * 
+ * The config changes are logged on the Update. Use `dry_run` to only preview them.
+ * The `to` Stack is not redeployed, chain with [DeployStack] to release it.
+ */
+export interface PromoteStack {
+	/** The Stack to promote from (ie staging). Id or name. */
+	from: string;
+	/** The Stack to promote to (ie production). Id or name. */
+	to: string;
+	/** Only preview the config changes on the Update, without applying them. */
+	dry_run?: boolean;
+}
+
+/**
+ * Migrates a Stack to another Server, including its named volumes. Response: [Update]
+ * 
+ * 1. Destroys the Stack on the current Server, keeping the volumes.
+ * 2. Archives the named volumes and transfers them to the target Server.
+ * 3. Restores the volumes, points the Stack at the target Server and deploys it.
+ * 4. Waits for the Stack to be running, then removes the volumes from the old Server.
+ * 
+ * Each step is logged on the Update. If a step fails, the completed steps
+ * are kept on the Stack info, and running MigrateStack again resumes after them.
+ */
+export interface MigrateStack {
+	/** Id or name */
+	stack: string;
+	/** The Server to migrate the Stack to. Id or name. */
+	to_server: string;
+}
+
+/**
+ * Prunes the build cache on the target server,
+ * reporting the reclaimed and remaining size. Response: [Update].
+ * 
+ * 1. Runs `docker buildx prune -a -f` for the default builder
+ * and the Periphery `buildx_builder`.
+ */
+export interface PruneBuildCache {
+	/** Id or name */
+	server: string;
+	/**
+	 * Keep cache used within this duration, ie `24h`.
+	 * By default, all the cache is pruned.
+	 */
+	keep_last?: string;
+}
+
 /**
  * Prunes the docker buildx cache on the target server. Response: [Update].
  * 
@@ -7356,6 +9589,22 @@ export interface PushoverAlerterEndpoint {
 	url: string;
 }
 
+/**
+ * Reboots the host of the target server. Response: [Update]
+ * 
+ * 1. Stops all containers on the host.
+ * 2. Tells Core the host is going down.
+ * 3. Runs `systemctl reboot`.
+ * 
+ * Requires `allow_host_power_actions` in the Periphery config.
+ */
+export interface RebootServer {
+	/** Id or name */
+	server: string;
+	/** Must be the server name, to confirm the reboot. */
+	confirm: string;
+}
+
 /** Trigger a refresh of the cached latest hash and message. */
 export interface RefreshBuildCache {
 	/** Id or name */
@@ -7612,6 +9861,35 @@ export interface UserGroupToml {
 	permissions?: PermissionToml[];
 }
 
+/**
+ * Manages the metadata of an existing Server onboarding key.
+ * The private key is never part of the sync, so keys must
+ * be created on Core first, and are matched by public key.
+ */
+export interface OnboardingKeyToml {
+	/** The onboarding public key. Required */
+	public_key: string;
+	/** Name associated with the key for management */
+	name?: string;
+	/** Whether the key is enabled. Default: true */
+	enabled?: boolean;
+	/** Expiry of key, or 0 if never expires */
+	expires?: I64;
+	/** Default tags to give to Servers created with this key. */
+	tags?: string[];
+	/**
+	 * Optional. Copy this Server config (by name) when initializing
+	 * the Server.
+	 */
+	copy_server?: string;
+	/** Also create a Builder for the Server. */
+	create_builder?: boolean;
+	/** The max number of Servers the key can onboard, or 0 for no limit. */
+	max_uses?: I64;
+	/** The key can only onboard a single Server. */
+	single_use?: boolean;
+}
+
 /** Specifies resources to sync on Komodo */
 export interface ResourcesToml {
 	servers?: ResourceToml<_PartialServerConfig>[];
@@ -7626,6 +9904,7 @@ export interface ResourcesToml {
 	resource_syncs?: ResourceToml<_PartialResourceSyncConfig>[];
 	user_groups?: UserGroupToml[];
 	variables?: Variable[];
+	onboarding_keys?: OnboardingKeyToml[];
 }
 
 /** Restarts all containers on the target server. Response: [Update] */
@@ -7667,6 +9946,39 @@ export interface RestartStack {
 	services?: string[];
 }
 
+/**
+ * Restarts the systemd unit on the target server. Response: [Update]
+ * 
+ * 1. Runs `systemctl restart ${unit}`.
+ * 
+ * Requires `enable_systemd_management` in the Periphery config.
+ */
+export interface RestartSystemdUnit {
+	/** Id or name */
+	server: string;
+	/** The unit name, ie `nfs-server.service` */
+	unit: string;
+}
+
+/**
+ * Rolls the deployment back to the image of the previous successful deploy.
+ * Response: [Update].
+ * 
+ * 1. Periphery finds the latest deployed image which differs from the current container image.
+ * 2. The container is replaced with one running that image,
+ * using the current deployment configuration.
+ * 
+ * Rolling back again goes to the deploy before that one.
+ */
+export interface RollbackDeployment {
+	/** Name or id */
+	deployment: string;
+	/** Override the default termination signal specified in the deployment. */
+	stop_signal?: TerminationSignal;
+	/** Override the default termination max time. */
+	stop_time?: number;
+}
+
 /**
  * **Admin only.** Rotates all connected Server keys.
  * Response: [Update]. Alias: `rotate-keys`.
@@ -7700,6 +10012,20 @@ export interface RotateServerKeys {
 	server: string;
 }
 
+/**
+ * Create or replace a command the server runs on a cron schedule,
+ * whether or not Core is connected. The runs are recorded
+ * as Updates on the server.
+ * Schedules set in the Periphery config file can't be replaced.
+ * Response: [ServerScheduleStatus]
+ */
+export interface UpsertServerSchedule {
+	/** Server Id or name */
+	server: string;
+	/** The schedule, replacing any with the same name. */
+	schedule: ServerSchedule;
+}
+
 /** Runs the target Action. Response: [Update] */
 export interface RunAction {
 	/** Id or name */
@@ -7739,6 +10065,26 @@ export interface RunProcedure {
 	procedure: string;
 }
 
+/**
+ * Runs a command of one of the extensions configured on the target server. Response: [Update].
+ * 
+ * The extension receives `{ "command": string, "params": object }`
+ * as JSON on stdin, and its stdout / stderr are returned in the log.
+ */
+export interface RunServerExtension {
+	/** Id or name */
+	server: string;
+	/** The extension name, as configured on the Periphery. */
+	extension: string;
+	/** The command passed to the extension. */
+	command: string;
+	/**
+	 * Custom params passed to the extension.
+	 * CLI Format: `'{"key":"value"}'`
+	 */
+	params?: JsonObject;
+}
+
 /** Runs a one-time command against a service using `docker compose run`. Response: [Update] */
 export interface RunStackService {
 	/** Id or name */
@@ -7784,6 +10130,22 @@ export interface RunSync {
 	resources?: string[];
 }
 
+/**
+ * Scales a service of the target stack to the given number of replicas,
+ * using `docker compose up --scale`. Response: [Update]
+ * 
+ * The replica count is not written to the compose file,
+ * so the next deploy returns the service to its configured replicas.
+ */
+export interface ScaleStack {
+	/** Id or name */
+	stack: string;
+	/** The service to scale */
+	service: string;
+	/** The target number of replicas */
+	replicas: I64;
+}
+
 export enum SearchCombinator {
 	Or = "Or",
 	And = "And",
@@ -7868,6 +10230,22 @@ export interface SearchStackLog {
 	timestamps?: boolean;
 }
 
+/**
+ * Mention other users, sending a notification to their inbox.
+ * Response: [NoData].
+ */
+export interface SendNotification {
+	/** The ids or usernames of the users to notify. */
+	users: string[];
+	/** The message to send. */
+	message: string;
+	/**
+	 * Optionally attach a target the message is about.
+	 * The receiving users must have at least read permissions on it.
+	 */
+	target?: ResourceTarget;
+}
+
 /**
  * Send a custom alert message to configured Alerters. Response: [Update].
  * Alias: `alert`
@@ -7905,6 +10283,10 @@ export interface ServerHealth {
 	cpu: ServerHealthState;
 	mem: ServerHealthState;
 	disks: Record<string, ServerHealthState>;
+	/** The health of each GPU, by [gpu_id](super::stats::gpu_id). */
+	gpus?: Record<string, ServerHealthState>;
+	/** The health of each ZFS pool, by pool name. */
+	zfs_pools?: Record<string, ServerHealthState>;
 }
 
 /**
@@ -7918,6 +10300,17 @@ export interface SetEveryoneUserGroup {
 	everyone: boolean;
 }
 
+/** **Admin only.** Assign a resource to an Organization. Response: [NoData] */
+export interface SetResourceOrganization {
+	/** The resource to assign */
+	target: ResourceTarget;
+	/**
+	 * The name or id of the Organization.
+	 * Pass empty string to remove the resource from its Organization.
+	 */
+	organization?: string;
+}
+
 /**
  * Set the time the user last opened the UI updates.
  * Used for unseen notification dot.
@@ -7926,6 +10319,33 @@ export interface SetEveryoneUserGroup {
 export interface SetLastSeenUpdate {
 }
 
+/** **Admin only.** Assign a user to an Organization. Response: [NoData] */
+/**
+ * Sets the priority of a process on the target server. Response: [Update]
+ * 
+ * 1. Runs `renice -n ${nice} -p ${pid}`.
+ * 
+ * Requires `enable_process_management` in the Periphery config.
+ */
+export interface SetSystemProcessPriority {
+	/** Id or name */
+	server: string;
+	/** The process PID, see [ListSystemProcesses] */
+	pid: number;
+	/** The niceness, from -20 (highest priority) to 19 (lowest priority) */
+	nice: number;
+}
+
+export interface SetUserOrganization {
+	/** The id or username of the user */
+	user: string;
+	/**
+	 * The name or id of the Organization.
+	 * Pass empty string to remove the user from their Organization.
+	 */
+	organization?: string;
+}
+
 /**
  * **Admin only.** Completely override the users in the group.
  * Response: [UserGroup]
@@ -7937,6 +10357,23 @@ export interface SetUsersInUserGroup {
 	users: string[];
 }
 
+/**
+ * Powers off the host of the target server. Response: [Update]
+ * 
+ * 1. Stops all containers on the host.
+ * 2. Tells Core the host is going down.
+ * 3. Runs `systemctl poweroff`.
+ * 
+ * The server will stay unreachable until the host is powered back on.
+ * Requires `allow_host_power_actions` in the Periphery config.
+ */
+export interface ShutdownServer {
+	/** Id or name */
+	server: string;
+	/** Must be the server name, to confirm the shutdown. */
+	confirm: string;
+}
+
 /**
  * Sign up a new local user account. Will fail if a user with the
  * given username already exists.
@@ -7955,14 +10392,87 @@ export interface SignUpLocalUser {
 	password: string;
 }
 
+/** The SMART health of a physical disk, read with `smartctl`. */
+export interface DiskHealth {
+	/** The device path, ie `/dev/sda` */
+	device: string;
+	/** The disk model name */
+	model: string;
+	/** The disk serial number */
+	serial: string;
+	/** Whether the overall SMART self assessment passed */
+	passed: boolean;
+	/** The disk temperature in degrees Celsius, if reported */
+	temperature?: number;
+	/** The count of reallocated sectors (ATA attribute 5) */
+	reallocated_sectors?: I64;
+	/** The count of sectors pending reallocation (ATA attribute 197) */
+	pending_sectors?: I64;
+	/** The count of media and data integrity errors (NVMe) */
+	media_errors?: I64;
+	/**
+	 * The percentage of the rated endurance used,
+	 * from the NVMe 'Percentage Used' or the ATA wear leveling count.
+	 */
+	wear_perc?: number;
+	/** The hours the disk has been powered on */
+	power_on_hours?: I64;
+	/** The error if smartctl could not read the disk */
+	error?: string;
+}
+
+/** A package with a newer version available. */
+export interface OsPackageUpdate {
+	/** The package name */
+	name: string;
+	/** The installed version, if reported */
+	current_version?: string;
+	/** The available version */
+	new_version: string;
+	/**
+	 * Whether the update comes from a security repository / advisory.
+	 * Not reported by `apk`.
+	 */
+	security: boolean;
+}
+
+/** The OS package updates pending on a server. */
+export interface PendingOsUpdates {
+	/**
+	 * The detected package manager, ie `apt`, `dnf`, `apk`.
+	 * Empty if none was found.
+	 */
+	package_manager: string;
+	/** The packages with a newer version available */
+	updates: OsPackageUpdate[];
+	/** Unix timestamp in milliseconds of the check */
+	checked_ts: I64;
+	/** The error if the check failed */
+	error?: string;
+}
+
 /** Info for network interface usage. */
 export interface SingleNetworkInterfaceUsage {
 	/** The network interface name */
 	name: string;
-	/** The ingress in bytes */
-	ingress_bytes: number;
-	/** The egress in bytes */
-	egress_bytes: number;
+	/** Receive rate in bytes per second */
+	rx: number;
+	/** Transmit rate in bytes per second */
+	tx: number;
+	/** Total receive errors since the interface came up */
+	rx_errors: I64;
+	/** Total transmit errors since the interface came up */
+	tx_errors: I64;
+	/**
+	 * Total dropped received packets since the interface came up.
+	 * Only reported on Linux.
+	 */
+	rx_dropped?: I64;
+	/**
+	 * Total dropped transmitted packets since the interface came up.
+	 * Only reported on Linux.
+	 */
+	tx_dropped?: I64;
 }
 
 /** Configuration for a Slack alerter. */
@@ -8015,6 +10525,20 @@ export interface StartStack {
 	services?: string[];
 }
 
+/**
+ * Starts the systemd unit on the target server. Response: [Update]
+ * 
+ * 1. Runs `systemctl start ${unit}`.
+ * 
+ * Requires `enable_systemd_management` in the Periphery config.
+ */
+export interface StartSystemdUnit {
+	/** Id or name */
+	server: string;
+	/** The unit name, ie `nfs-server.service` */
+	unit: string;
+}
+
 /** Stops all containers on the target server. Response: [Update] */
 export interface StopAllContainers {
 	/** Name or id */
@@ -8064,6 +10588,20 @@ export interface StopStack {
 	services?: string[];
 }
 
+/**
+ * Stops the systemd unit on the target server. Response: [Update]
+ * 
+ * 1. Runs `systemctl stop ${unit}`.
+ * 
+ * Requires `enable_systemd_management` in the Periphery config.
+ */
+export interface StopSystemdUnit {
+	/** Id or name */
+	server: string;
+	/** The unit name, ie `nfs-server.service` */
+	unit: string;
+}
+
 export interface TerminationSignalLabel {
 	signal: TerminationSignal;
 	label: string;
@@ -8083,6 +10621,23 @@ export interface TotalDiskUsage {
 	total_gb: number;
 }
 
+/**
+ * Copies the image from one server to another,
+ * without going through a registry. Response: [Update].
+ * 
+ * 1. Runs `docker save` on the `from_server`.
+ * 2. Transfers the archive to the `to_server`.
+ * 3. Runs `docker load` on the `to_server`.
+ */
+export interface TransferImage {
+	/** The server with the image. Id or name */
+	from_server: string;
+	/** The server to load the image on. Id or name */
+	to_server: string;
+	/** The image name, ie `repository:tag`. */
+	image: string;
+}
+
 /** Unpauses all containers on the target server. Response: [Update] */
 export interface UnpauseAllContainers {
 	/** Name or id */
@@ -8196,6 +10751,24 @@ export interface UpdateBuilder {
 	config: PartialBuilderConfig;
 }
 
+/**
+ * Update a dashboard. Only the owner or an admin can update.
+ * Only the fields which are passed will be updated.
+ * Response: [Dashboard].
+ */
+export interface UpdateDashboard {
+	/** The id of the dashboard to update. */
+	id: string;
+	/** Update the name. */
+	name?: string;
+	/** Update the description. */
+	description?: string;
+	/** Update whether the dashboard is shared. */
+	shared?: boolean;
+	/** Replace the widget layout. */
+	widgets?: DashboardWidget[];
+}
+
 /**
  * Update the deployment at the given id, and return the updated deployment.
  * Response: [Deployment].
@@ -8257,6 +10830,23 @@ export interface UpdateOnboardingKey {
 	copy_server?: string;
 	/** Update whether to create Builder */
 	create_builder?: boolean;
+	/** Update the max uses, 0 for no limit. */
+	max_uses?: I64;
+	/** Update whether the key is single use. */
+	single_use?: boolean;
+}
+
+/**
+ * **Admin only.** Update an Organization's name and description.
+ * Response: [Organization]
+ */
+export interface UpdateOrganization {
+	/** The id of the Organization */
+	id: string;
+	/** The new name for the Organization */
+	name: string;
+	/** The new description for the Organization */
+	description?: string;
 }
 
 /**
@@ -8394,6 +10984,16 @@ export interface UpdateServerPublicKey {
 	public_key: string;
 }
 
+/**
+ * Accepts the Server `attempted_tls_pin`, adding it to the
+ * `tls_pins` used with `Pinned` tls verification.
+ * Response: [Update]
+ */
+export interface AcceptServerTlsPin {
+	/** Server Id or name */
+	server: string;
+}
+
 /**
  * **Admin only.** Update a service user's description.
  * Response: [User].
@@ -8459,6 +11059,17 @@ export interface UpdateUserBasePermissions {
 	create_builds?: boolean;
 }
 
+/**
+ * **Admin only.** Set the quotas applied to members of a user group.
+ * Response: [UserGroup]
+ */
+export interface UpdateUserGroupQuotas {
+	/** Id or name. */
+	user_group: string;
+	/** The quotas to set. Use 0 for no limit. */
+	quotas: UserGroupQuotas;
+}
+
 /**
  * **Only for local users**. Update the calling users password.
  * Response: [NoData].
@@ -8499,6 +11110,24 @@ export interface UpdateVariableValue {
 	value: string;
 }
 
+/**
+ * **Admin only.** Update a template.
+ * Only the fields which are passed will be updated.
+ * Response: [Template].
+ */
+export interface UpdateTemplate {
+	/** The name of the template to update. */
+	name: string;
+	/** Update the description. */
+	description?: string;
+	/** Update the compose file contents. */
+	file_contents?: string;
+	/** Update the environment (.env) contents. */
+	environment?: string;
+	/** Update the parameters. */
+	parameters?: TemplateParameter[];
+}
+
 /** Configuration for a Komodo Url Builder. */
 export interface UrlBuilderConfig {
 	/** The address of the Periphery agent */
@@ -8585,6 +11214,7 @@ export type ExecuteRequest =
 	| { type: "UnpauseContainer", params: UnpauseContainer }
 	| { type: "StopContainer", params: StopContainer }
 	| { type: "DestroyContainer", params: DestroyContainer }
+	| { type: "CommitContainer", params: CommitContainer }
 	| { type: "StartAllContainers", params: StartAllContainers }
 	| { type: "RestartAllContainers", params: RestartAllContainers }
 	| { type: "PauseAllContainers", params: PauseAllContainers }
@@ -8595,11 +11225,21 @@ export type ExecuteRequest =
 	| { type: "PruneNetworks", params: PruneNetworks }
 	| { type: "DeleteImage", params: DeleteImage }
 	| { type: "PruneImages", params: PruneImages }
+	| { type: "TransferImage", params: TransferImage }
 	| { type: "DeleteVolume", params: DeleteVolume }
 	| { type: "PruneVolumes", params: PruneVolumes }
 	| { type: "PruneDockerBuilders", params: PruneDockerBuilders }
 	| { type: "PruneBuildx", params: PruneBuildx }
+	| { type: "PruneBuildCache", params: PruneBuildCache }
 	| { type: "PruneSystem", params: PruneSystem }
+	| { type: "RunServerExtension", params: RunServerExtension }
+	| { type: "KillSystemProcess", params: KillSystemProcess }
+	| { type: "SetSystemProcessPriority", params: SetSystemProcessPriority }
+	| { type: "StartSystemdUnit", params: StartSystemdUnit }
+	| { type: "StopSystemdUnit", params: StopSystemdUnit }
+	| { type: "RestartSystemdUnit", params: RestartSystemdUnit }
+	| { type: "RebootServer", params: RebootServer }
+	| { type: "ShutdownServer", params: ShutdownServer }
 	| { type: "DeployStack", params: DeployStack }
 	| { type: "BatchDeployStack", params: BatchDeployStack }
 	| { type: "DeployStackIfChanged", params: DeployStackIfChanged }
@@ -8614,6 +11254,9 @@ export type ExecuteRequest =
 	| { type: "DestroyStack", params: DestroyStack }
 	| { type: "BatchDestroyStack", params: BatchDestroyStack }
 	| { type: "RunStackService", params: RunStackService }
+	| { type: "ScaleStack", params: ScaleStack }
+	| { type: "PromoteStack", params: PromoteStack }
+	| { type: "MigrateStack", params: MigrateStack }
 	| { type: "Deploy", params: Deploy }
 	| { type: "BatchDeploy", params: BatchDeploy }
 	| { type: "PullDeployment", params: PullDeployment }
@@ -8623,6 +11266,7 @@ export type ExecuteRequest =
 	| { type: "UnpauseDeployment", params: UnpauseDeployment }
 	| { type: "StopDeployment", params: StopDeployment }
 	| { type: "DestroyDeployment", params: DestroyDeployment }
+	| { type: "RollbackDeployment", params: RollbackDeployment }
 	| { type: "BatchDestroyDeployment", params: BatchDestroyDeployment }
 	| { type: "RunBuild", params: RunBuild }
 	| { type: "BatchRunBuild", params: BatchRunBuild }
@@ -8749,6 +11393,9 @@ export type ReadRequest =
 	| { type: "ListUserTargetPermissions", params: ListUserTargetPermissions }
 	| { type: "GetUserGroup", params: GetUserGroup }
 	| { type: "ListUserGroups", params: ListUserGroups }
+	| { type: "ListNotifications", params: ListNotifications }
+	| { type: "GetOrganization", params: GetOrganization }
+	| { type: "ListOrganizations", params: ListOrganizations }
 	| { type: "GetProceduresSummary", params: GetProceduresSummary }
 	| { type: "GetProcedure", params: GetProcedure }
 	| { type: "GetProcedureActionState", params: GetProcedureActionState }
@@ -8764,11 +11411,18 @@ export type ReadRequest =
 	| { type: "GetServer", params: GetServer }
 	| { type: "GetServerState", params: GetServerState }
 	| { type: "GetPeripheryInformation", params: GetPeripheryInformation }
+	| { type: "ListConnectionShards", params: ListConnectionShards }
+	| { type: "GetServerConnectionMetrics", params: GetServerConnectionMetrics }
+	| { type: "ListTransportTrace", params: ListTransportTrace }
 	| { type: "GetServerActionState", params: GetServerActionState }
 	| { type: "GetHistoricalServerStats", params: GetHistoricalServerStats }
 	| { type: "ListServers", params: ListServers }
 	| { type: "ListFullServers", params: ListFullServers }
 	| { type: "ListTerminals", params: ListTerminals }
+	| { type: "ListTerminalRecordings", params: ListTerminalRecordings }
+	| { type: "GetTerminalRecording", params: GetTerminalRecording }
+	| { type: "ListActiveTerminalSessions", params: ListActiveTerminalSessions }
+	| { type: "ListServerSchedules", params: ListServerSchedules }
 	| { type: "GetDockerContainersSummary", params: GetDockerContainersSummary }
 	| { type: "ListAllDockerContainers", params: ListAllDockerContainers }
 	| { type: "ListDockerContainers", params: ListDockerContainers }
@@ -8784,11 +11438,18 @@ export type ReadRequest =
 	| { type: "ListDockerImageHistory", params: ListDockerImageHistory }
 	| { type: "ListDockerVolumes", params: ListDockerVolumes }
 	| { type: "InspectDockerVolume", params: InspectDockerVolume }
+	| { type: "ListSystemdUnits", params: ListSystemdUnits }
+	| { type: "GetSystemdUnitStatus", params: GetSystemdUnitStatus }
+	| { type: "GetSystemdUnitJournal", params: GetSystemdUnitJournal }
 	| { type: "GetSystemInformation", params: GetSystemInformation }
 	| { type: "GetSystemStats", params: GetSystemStats }
 	| { type: "ListSystemProcesses", params: ListSystemProcesses }
+	| { type: "ListListeningSockets", params: ListListeningSockets }
+	| { type: "SuggestServerPlacement", params: SuggestServerPlacement }
+	| { type: "CheckServerPlacement", params: CheckServerPlacement }
 	| { type: "GetStacksSummary", params: GetStacksSummary }
 	| { type: "GetStack", params: GetStack }
+	| { type: "GetStackInheritance", params: GetStackInheritance }
 	| { type: "GetStackActionState", params: GetStackActionState }
 	| { type: "GetStackLog", params: GetStackLog }
 	| { type: "SearchStackLog", params: SearchStackLog }
@@ -8806,6 +11467,7 @@ export type ReadRequest =
 	| { type: "GetDeploymentLog", params: GetDeploymentLog }
 	| { type: "SearchDeploymentLog", params: SearchDeploymentLog }
 	| { type: "InspectDeploymentContainer", params: InspectDeploymentContainer }
+	| { type: "GetDeploymentInheritance", params: GetDeploymentInheritance }
 	| { type: "ListDeployments", params: ListDeployments }
 	| { type: "ListFullDeployments", params: ListFullDeployments }
 	| { type: "ListCommonDeploymentExtraArgs", params: ListCommonDeploymentExtraArgs }
@@ -8839,17 +11501,26 @@ export type ReadRequest =
 	| { type: "ExportResourcesToToml", params: ExportResourcesToToml }
 	| { type: "GetTag", params: GetTag }
 	| { type: "ListTags", params: ListTags }
+	| { type: "GetTagUsageReport", params: GetTagUsageReport }
+	| { type: "ListTagUsageReports", params: ListTagUsageReports }
 	| { type: "GetUpdate", params: GetUpdate }
 	| { type: "ListUpdates", params: ListUpdates }
 	| { type: "ListAlerts", params: ListAlerts }
 	| { type: "GetAlert", params: GetAlert }
 	| { type: "GetVariable", params: GetVariable }
 	| { type: "ListVariables", params: ListVariables }
+	| { type: "GetTemplate", params: GetTemplate }
+	| { type: "ListTemplates", params: ListTemplates }
+	| { type: "GetDashboard", params: GetDashboard }
+	| { type: "ListDashboards", params: ListDashboards }
 	| { type: "GetGitProviderAccount", params: GetGitProviderAccount }
 	| { type: "ListGitProviderAccounts", params: ListGitProviderAccounts }
 	| { type: "GetDockerRegistryAccount", params: GetDockerRegistryAccount }
 	| { type: "ListDockerRegistryAccounts", params: ListDockerRegistryAccounts }
-	| { type: "ListOnboardingKeys", params: ListOnboardingKeys };
+	| { type: "ListOnboardingKeys", params: ListOnboardingKeys }
+	| { type: "ListPendingServers", params: ListPendingServers }
+	| { type: "ListRevokedKeys", params: ListRevokedKeys }
+	| { type: "ListAuthEvents", params: ListAuthEvents };
 
 export enum RepoWebhookAction {
 	Clone = "Clone",
@@ -8894,6 +11565,13 @@ export enum SpecificPermission {
 	 * - Read all the processes on the host
 	 */
 	Processes = "Processes",
+	/**
+	 * On **Server**
+	 * - Attach to the main process of any container on the server
+	 * On **Stack / Deployment**
+	 * - Attach to the main process of the associated containers
+	 */
+	ContainerAttach = "ContainerAttach",
 }
 
 export enum StackWebhookAction {
@@ -8928,6 +11606,14 @@ export type WriteRequest =
 	| { type: "RemoveUserFromUserGroup", params: RemoveUserFromUserGroup }
 	| { type: "SetUsersInUserGroup", params: SetUsersInUserGroup }
 	| { type: "SetEveryoneUserGroup", params: SetEveryoneUserGroup }
+	| { type: "AckNotifications", params: AckNotifications }
+	| { type: "SendNotification", params: SendNotification }
+	| { type: "UpdateUserGroupQuotas", params: UpdateUserGroupQuotas }
+	| { type: "CreateOrganization", params: CreateOrganization }
+	| { type: "UpdateOrganization", params: UpdateOrganization }
+	| { type: "DeleteOrganization", params: DeleteOrganization }
+	| { type: "SetUserOrganization", params: SetUserOrganization }
+	| { type: "SetResourceOrganization", params: SetResourceOrganization }
 	| { type: "UpdateUserAdmin", params: UpdateUserAdmin }
 	| { type: "UpdateUserBasePermissions", params: UpdateUserBasePermissions }
 	| { type: "UpdatePermissionOnResourceType", params: UpdatePermissionOnResourceType }
@@ -8942,8 +11628,13 @@ export type WriteRequest =
 	| { type: "CreateTerminal", params: CreateTerminal }
 	| { type: "DeleteTerminal", params: DeleteTerminal }
 	| { type: "DeleteAllTerminals", params: DeleteAllTerminals }
+	| { type: "ClearTerminalHistory", params: ClearTerminalHistory }
+	| { type: "DisconnectTerminalSession", params: DisconnectTerminalSession }
 	| { type: "UpdateServerPublicKey", params: UpdateServerPublicKey }
+	| { type: "AcceptServerTlsPin", params: AcceptServerTlsPin }
 	| { type: "RotateServerKeys", params: RotateServerKeys }
+	| { type: "UpsertServerSchedule", params: UpsertServerSchedule }
+	| { type: "DeleteServerSchedule", params: DeleteServerSchedule }
 	| { type: "CreateStack", params: CreateStack }
 	| { type: "CopyStack", params: CopyStack }
 	| { type: "DeleteStack", params: DeleteStack }
@@ -9007,6 +11698,13 @@ export type WriteRequest =
 	| { type: "UpdateVariableDescription", params: UpdateVariableDescription }
 	| { type: "UpdateVariableIsSecret", params: UpdateVariableIsSecret }
 	| { type: "DeleteVariable", params: DeleteVariable }
+	| { type: "CreateTemplate", params: CreateTemplate }
+	| { type: "UpdateTemplate", params: UpdateTemplate }
+	| { type: "DeleteTemplate", params: DeleteTemplate }
+	| { type: "InstantiateTemplate", params: InstantiateTemplate }
+	| { type: "CreateDashboard", params: CreateDashboard }
+	| { type: "UpdateDashboard", params: UpdateDashboard }
+	| { type: "DeleteDashboard", params: DeleteDashboard }
 	| { type: "CreateGitProviderAccount", params: CreateGitProviderAccount }
 	| { type: "UpdateGitProviderAccount", params: UpdateGitProviderAccount }
 	| { type: "DeleteGitProviderAccount", params: DeleteGitProviderAccount }
@@ -9016,7 +11714,114 @@ export type WriteRequest =
 	| { type: "CreateOnboardingKey", params: CreateOnboardingKey }
 	| { type: "UpdateOnboardingKey", params: UpdateOnboardingKey }
 	| { type: "DeleteOnboardingKey", params: DeleteOnboardingKey }
-	| { type: "CloseAlert", params: CloseAlert };
+	| { type: "ApprovePendingServer", params: ApprovePendingServer }
+	| { type: "DeletePendingServer", params: DeletePendingServer }
+	| { type: "RevokePublicKey", params: RevokePublicKey }
+	| { type: "UnrevokePublicKey", params: UnrevokePublicKey }
+	| { type: "CloseAlert", params: CloseAlert }
+	| { type: "SetReadOnlyMode", params: SetReadOnlyMode };
+
+/** A power action run on the Server host. */
+export enum HostPowerAction {
+	/** Reboot the host */
+	Reboot = "Reboot",
+	/** Power off the host */
+	Shutdown = "Shutdown",
+}
+
+/** The variants of event data. */
+export type CoreEventData = 
+	/** A resource was created. */
+	| { type: "ResourceCreated", data: {
+	/** The name of the resource */
+	name: string;
+}}
+	/** A resource config was updated. */
+	| { type: "ResourceUpdated", data: {
+	/** The name of the resource */
+	name: string;
+}}
+	/** A resource was renamed. */
+	| { type: "ResourceRenamed", data: {
+	/** The previous name of the resource */
+	from: string;
+	/** The new name of the resource */
+	to: string;
+}}
+	/** A resource was deleted. */
+	| { type: "ResourceDeleted", data: {
+	/** The name of the resource */
+	name: string;
+}}
+	/** A Server's Periphery connection was established. */
+	| { type: "ServerConnected", data: {
+}}
+	/** A Server's Periphery connection was lost. */
+	| { type: "ServerDisconnected", data: {
+}}
+	/**
+	 * A Server's Periphery is about to reboot or power off the host,
+	 * after stopping its containers.
+	 */
+	| { type: "ServerDraining", data: {
+	/** The power action being run */
+	action: HostPowerAction;
+}}
+	/**
+	 * A container on a Server changed state,
+	 * as pushed by Periphery.
+	 */
+	| { type: "ContainerStateChanged", data: {
+	/** The name of the container */
+	container: string;
+	/** The new state of the container */
+	state: ContainerStateStatusEnum;
+}}
+	/**
+	 * A container on a Server was killed for running out of memory,
+	 * as pushed by Periphery.
+	 */
+	| { type: "ContainerOomKilled", data: {
+	/** The name of the container */
+	container: string;
+}}
+	/** An Update was finalized. */
+	| { type: "UpdateFinalized", data: UpdateListItem };
+
+/**
+ * An event emitted on the Core event bus.
+ * 
+ * Core subsystems subscribe to these internally, and they can
+ * be received externally over the `/ws/events` websocket.
+ */
+export interface CoreEvent {
+	/** Unix timestamp in milliseconds the event was emitted. */
+	ts: number;
+	/** The target the event is about. */
+	target: ResourceTarget;
+	/** The kind of event, with any kind specific data. */
+	data: CoreEventData;
+}
+
+/**
+ * Sent by the client over the `/ws/events` websocket after login
+ * to filter the [CoreEvent]s it receives.
+ * Can be sent again at any time to replace the filter.
+ * 
+ * Events are only sent for targets the user has permission to see.
+ */
+export interface WsEventSubscription {
+	/**
+	 * Only receive these kinds of events.
+	 * If empty, receives all kinds.
+	 */
+	kinds?: CoreEventData["type"][];
+	/**
+	 * Only receive events about these targets.
+	 * If empty, receives events for all targets.
+	 */
+	targets?: ResourceTarget[];
+}
 
 export type WsLoginMessage = 
 	| { type: "Jwt", params: {
@@ -9027,3 +11832,10 @@ export type WsLoginMessage =
 	secret: string;
 }};
 
+/**
+ * Sent over the update websocket alongside updates
+ * when a [Notification] is delivered to the logged in user.
+ */
+export type WsNotificationMessage = 
+	| { type: "NOTIFICATION", data: Notification };
+
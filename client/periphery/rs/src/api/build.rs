@@ -76,3 +76,16 @@ pub struct PruneBuilders {}
 #[response(Log)]
 #[error(anyhow::Error)]
 pub struct PruneBuildx {}
+
+//
+
+/// Prunes the build cache of the default builder,
+/// and the `buildx_builder` if it exists.
+/// The logs report the reclaimed and remaining cache size.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<Log>)]
+#[error(anyhow::Error)]
+pub struct PruneBuildCache {
+  /// Keep cache used within this duration, ie `24h`.
+  pub keep_last: Option<String>,
+}
@@ -230,6 +230,27 @@ pub struct ComposeExecution {
   pub project: String,
   /// The command in `docker compose -p {project} {command}`
   pub command: String,
+  /// Remove the project secret files after the command succeeds.
+  /// Used when taking down the whole project.
+  #[serde(default)]
+  pub remove_secret_files: bool,
+}
+
+//
+
+/// Scale a compose service to the given number of replicas,
+/// using `docker compose up -d --no-deps --no-recreate --scale`.
+/// Uses the compose files of the running project on the host.
+#[derive(Debug, Clone, Serialize, Deserialize, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct ComposeScale {
+  /// The compose project name.
+  pub project: String,
+  /// The service to scale.
+  pub service: String,
+  /// The target number of replicas.
+  pub replicas: i64,
 }
 
 //
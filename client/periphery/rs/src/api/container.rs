@@ -1,6 +1,8 @@
+use std::path::PathBuf;
+
 use komodo_client::entities::{
-  SearchCombinator, TerminationSignal,
-  deployment::Deployment,
+  NoData, SearchCombinator, TerminationSignal,
+  deployment::{Deployment, DeploymentHealth, DeploymentHealthProbe},
   docker::{
     container::{Container, ContainerStats},
     stats::FullContainerStats,
@@ -9,6 +11,7 @@ use komodo_client::entities::{
 };
 use resolver_api::Resolve;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 //
 
@@ -83,6 +86,63 @@ pub struct GetFullContainerStats {
 
 //
 
+/// Evaluate a custom Deployment health probe.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(DeploymentHealth)]
+#[error(anyhow::Error)]
+pub struct RunHealthProbe {
+  /// The container name
+  pub name: String,
+  pub probe: DeploymentHealthProbe,
+  /// Seconds before the probe counts as failed.
+  pub timeout: i32,
+}
+
+//
+
+/// Subscribe to container start / stop / die / oom events,
+/// which Periphery pushes to the calling Core as Event messages.
+/// Lasts until Periphery restarts, so Core subscribes on every connect.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(NoData)]
+#[error(anyhow::Error)]
+pub struct SubscribeDockerEvents {}
+
+//
+
+/// Copy a path out of the container with `docker cp`.
+/// The tar archive after `offset` is sent as File messages
+/// on the `channel` before the response, like [PullFile].
+///
+/// [PullFile]: crate::api::file::PullFile
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(ContainerArchive)]
+#[error(anyhow::Error)]
+pub struct CopyFromContainer {
+  pub container: String,
+  /// The path inside the container.
+  pub path: String,
+  pub channel: Uuid,
+  /// Resume a previous copy from this many bytes.
+  #[serde(default)]
+  pub offset: u64,
+  /// Only list the archive entries, without sending the contents.
+  #[serde(default)]
+  pub dry_run: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContainerArchive {
+  /// The total size of the tar archive in bytes.
+  pub size: u64,
+  /// Hex encoded sha256 of the full archive.
+  pub sha256: String,
+  /// The verbose tar listing of the archive entries.
+  pub entries: Vec<String>,
+}
+
+//
+
 // =======
 // ACTIONS
 // =======
@@ -103,6 +163,23 @@ pub struct Deploy {
 
 //
 
+/// Re-deploys the container using the image of the latest
+/// successful deploy which differs from the current container image.
+/// The Deployment config is used as is, only the image is replaced.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct RollbackDeploy {
+  pub deployment: Deployment,
+  pub stop_signal: Option<TerminationSignal>,
+  pub stop_time: Option<i32>,
+  /// Propogate any secret replacers from core interpolation.
+  #[serde(default)]
+  pub replacers: Vec<(String, String)>,
+}
+
+//
+
 #[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
 #[response(Log)]
 #[error(anyhow::Error)]
@@ -161,6 +238,75 @@ pub struct RemoveContainer {
 
 //
 
+/// Extract a tar archive into the container with `docker cp`.
+/// The archive is first pushed to the host with [BeginFilePush],
+/// and is removed once copied.
+///
+/// [BeginFilePush]: crate::api::file::BeginFilePush
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<Log>)]
+#[error(anyhow::Error)]
+pub struct CopyIntoContainer {
+  pub container: String,
+  /// The path of the pushed archive on the host.
+  pub archive: PathBuf,
+  /// The directory inside the container to extract into.
+  pub destination: String,
+  /// Recursively `chown` the copied entries, eg. `1000:1000`.
+  pub owner: Option<String>,
+  /// Recursively `chmod` the copied entries, eg. `u=rwX,go=rX`.
+  pub mode: Option<String>,
+  /// Only list the archive entries, without copying.
+  #[serde(default)]
+  pub dry_run: bool,
+}
+
+//
+
+/// Checkpoint the running container with `docker checkpoint create`.
+/// Requires `enable_container_checkpoints` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct CheckpointContainer {
+  pub name: String,
+  /// The name of the checkpoint.
+  pub checkpoint: String,
+  /// Keep the container running after the checkpoint,
+  /// otherwise it is stopped.
+  #[serde(default)]
+  pub leave_running: bool,
+  /// Also write the checkpoint as a tar archive to this path
+  /// on the host, to transfer it with [PullFile].
+  ///
+  /// [PullFile]: crate::api::file::PullFile
+  pub archive: Option<PathBuf>,
+}
+
+//
+
+/// Start the stopped container from a checkpoint,
+/// using `docker start --checkpoint`.
+/// Requires `enable_container_checkpoints` in the Periphery config.
+///
+/// To restore on another server, the container must first be
+/// created there (but not started) with the same configuration.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct RestoreContainer {
+  pub name: String,
+  /// The name of the checkpoint.
+  pub checkpoint: String,
+  /// Extract the checkpoint from this tar archive on the host,
+  /// ie one pushed with [BeginFilePush]. It is removed after.
+  ///
+  /// [BeginFilePush]: crate::api::file::BeginFilePush
+  pub archive: Option<PathBuf>,
+}
+
+//
+
 #[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
 #[response(Log)]
 #[error(anyhow::Error)]
@@ -171,6 +317,23 @@ pub struct RenameContainer {
 
 //
 
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct CommitContainer {
+  pub name: String,
+  /// The image to create, ie `repository:tag`.
+  pub image: String,
+  /// Sets the author of the image.
+  pub author: Option<String>,
+  /// Sets the commit message of the image.
+  pub message: Option<String>,
+  /// Pause the container during the commit.
+  pub pause: bool,
+}
+
+//
+
 #[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
 #[response(Log)]
 #[error(anyhow::Error)]
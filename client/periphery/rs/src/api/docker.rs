@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use komodo_client::entities::{
   docker::{
     image::{Image, ImageHistoryResponseItem},
@@ -8,6 +10,9 @@ use komodo_client::entities::{
 };
 use resolver_api::Resolve;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::file::PulledFile;
 
 // =====
 // IMAGE
@@ -60,6 +65,38 @@ pub struct DeleteImage {
 #[error(anyhow::Error)]
 pub struct PruneImages {}
 
+//
+
+/// Saves the image as a tar archive with `docker save`,
+/// and sends it as File messages on the channel,
+/// like [PullFile].
+///
+/// [PullFile]: crate::api::file::PullFile
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(PulledFile)]
+#[error(anyhow::Error)]
+pub struct ExportImage {
+  pub name: String,
+  pub channel: Uuid,
+  /// Resume a previous export from this many bytes.
+  #[serde(default)]
+  pub offset: u64,
+}
+
+//
+
+/// Loads the images in the tar archive with `docker load`.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct ImportImage {
+  /// The tar archive on the host,
+  /// ie one pushed with [BeginFilePush]. It is removed after.
+  ///
+  /// [BeginFilePush]: crate::api::file::BeginFilePush
+  pub archive: PathBuf,
+}
+
 // =======
 // NETWORK
 // =======
@@ -125,3 +162,49 @@ pub struct DeleteVolume {
 #[response(Log)]
 #[error(anyhow::Error)]
 pub struct PruneVolumes {}
+
+//
+
+/// List the named volumes created by the compose project.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<String>)]
+#[error(anyhow::Error)]
+pub struct ListComposeVolumes {
+  pub project: String,
+}
+
+//
+
+/// Archive the volume contents to
+/// `${root_directory}/volumes/{name}.tar.gz`.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct ArchiveVolume {
+  pub name: String,
+}
+
+//
+
+/// Restore the volume archive into the volume,
+/// creating the volume with the labels if it doesn't exist.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct RestoreVolume {
+  pub name: String,
+  /// Labels to create the volume with,
+  /// ie the compose project labels from the source volume.
+  #[serde(default)]
+  pub labels: Vec<(String, String)>,
+}
+
+//
+
+/// Delete the volume archive, if it exists.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct DeleteVolumeArchive {
+  pub name: String,
+}
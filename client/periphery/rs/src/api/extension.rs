@@ -0,0 +1,19 @@
+use komodo_client::entities::{JsonObject, update::Log};
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+
+//
+
+/// Run a command of one of the configured Periphery extensions.
+#[derive(Debug, Clone, Serialize, Deserialize, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct RunExtension {
+  /// The extension name.
+  pub extension: String,
+  /// The extension command.
+  pub command: String,
+  /// Passed to the extension along with the command.
+  #[serde(default)]
+  pub params: JsonObject,
+}
@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use komodo_client::entities::{NoData, update::Log};
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+//
+
+/// Start pushing a file to Periphery over the `channel`.
+/// Core then sends the contents from the returned offset
+/// as File messages, and completes with [FinishFilePush].
+///
+/// Calling again with the same channel resumes the transfer.
+#[derive(Debug, Clone, Serialize, Deserialize, Resolve)]
+#[response(FileTransferState)]
+#[error(anyhow::Error)]
+pub struct BeginFilePush {
+  /// Identifies the transfer, used for the File messages.
+  pub channel: Uuid,
+  /// The path to write the file to on the host.
+  pub path: PathBuf,
+  /// The total size of the file in bytes.
+  /// None when Core relays the file from another server
+  /// and the size isn't known up front. The contents
+  /// are still verified against the checksum.
+  pub size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTransferState {
+  /// The bytes of the file already received.
+  /// Core resumes sending from here.
+  pub offset: u64,
+}
+
+/// Verify the pushed file against the checksum,
+/// and move it into place.
+#[derive(Debug, Clone, Serialize, Deserialize, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct FinishFilePush {
+  pub channel: Uuid,
+  /// Hex encoded sha256 of the full file contents.
+  pub sha256: String,
+}
+
+/// Abandon a pushed file, removing the partial contents.
+#[derive(Debug, Clone, Serialize, Deserialize, Resolve)]
+#[response(NoData)]
+#[error(anyhow::Error)]
+pub struct CancelFilePush {
+  pub channel: Uuid,
+}
+
+//
+
+/// Pull a file from Periphery. The contents after `offset`
+/// are sent as File messages on the `channel` before the response.
+#[derive(Debug, Clone, Serialize, Deserialize, Resolve)]
+#[response(PulledFile)]
+#[error(anyhow::Error)]
+pub struct PullFile {
+  pub channel: Uuid,
+  /// The path of the file on the host.
+  pub path: PathBuf,
+  /// Resume a previous pull from this many bytes.
+  #[serde(default)]
+  pub offset: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PulledFile {
+  /// The total size of the file in bytes.
+  pub size: u64,
+  /// Hex encoded sha256 of the full file contents.
+  pub sha256: String,
+}
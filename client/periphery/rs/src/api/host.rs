@@ -0,0 +1,25 @@
+use komodo_client::entities::update::Log;
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+
+//
+
+/// Stops the containers, tells the connected Cores
+/// the host is going down, then reboots the host.
+/// The reboot is delayed a few seconds so the response is sent.
+/// Requires `allow_host_power_actions` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<Log>)]
+#[error(anyhow::Error)]
+pub struct RebootHost {}
+
+//
+
+/// Stops the containers, tells the connected Cores
+/// the host is going down, then powers off the host.
+/// The shutdown is delayed a few seconds so the response is sent.
+/// Requires `allow_host_power_actions` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<Log>)]
+#[error(anyhow::Error)]
+pub struct ShutdownHost {}
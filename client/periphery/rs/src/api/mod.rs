@@ -1,5 +1,8 @@
 use komodo_client::entities::{
-  config::{DockerRegistry, GitProvider},
+  config::{
+    DockerRegistry, GitProvider, TransportCompression,
+    TransportEncoding,
+  },
   docker::{
     container::ContainerListItem, image::ImageListItem,
     network::NetworkListItem, volume::VolumeListItem,
@@ -16,9 +19,15 @@ pub mod build;
 pub mod compose;
 pub mod container;
 pub mod docker;
+pub mod extension;
+pub mod file;
 pub mod git;
+pub mod host;
 pub mod keys;
+pub mod schedule;
 pub mod stats;
+pub mod swarm;
+pub mod systemd;
 pub mod terminal;
 
 //
@@ -27,12 +36,112 @@ pub mod terminal;
 pub struct CoreConnectionQuery {
   /// Core host (eg demo.komo.do)
   pub core: String,
+  /// The compression Core supports.
+  /// Older Cores don't send this.
+  #[serde(default)]
+  pub compression: TransportCompression,
+  /// Whether Core can reassemble Chunk messages.
+  /// Older Cores don't send this.
+  #[serde(default)]
+  pub chunking: bool,
+  /// Whether Core answers Ping messages with Pong.
+  /// Older Cores don't send this.
+  #[serde(default)]
+  pub keepalive: bool,
+  /// The body encoding Core supports.
+  /// Older Cores don't send this.
+  #[serde(default)]
+  pub encoding: TransportEncoding,
+  /// Whether Core can encrypt sensitive bodies
+  /// with the session keys.
+  /// Older Cores don't send this.
+  #[serde(default)]
+  pub encryption: bool,
+  /// Whether Core can read messages framed
+  /// with the transport header after login.
+  /// Older Cores don't send this.
+  #[serde(default)]
+  pub header: bool,
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct PeripheryConnectionQuery {
   /// Server Id or name
   pub server: String,
+  /// The compression Periphery supports.
+  /// Older Peripheries don't send this.
+  #[serde(default)]
+  pub compression: TransportCompression,
+  /// Whether Periphery can reassemble Chunk messages.
+  /// Older Peripheries don't send this.
+  #[serde(default)]
+  pub chunking: bool,
+  /// Whether Periphery answers Ping messages with Pong.
+  /// Older Peripheries don't send this.
+  #[serde(default)]
+  pub keepalive: bool,
+  /// The body encoding Periphery supports.
+  /// Older Peripheries don't send this.
+  #[serde(default)]
+  pub encoding: TransportEncoding,
+  /// Whether Periphery can encrypt sensitive bodies
+  /// with the session keys.
+  /// Older Peripheries don't send this.
+  #[serde(default)]
+  pub encryption: bool,
+  /// Whether Periphery can read messages framed
+  /// with the transport header after login.
+  /// Older Peripheries don't send this.
+  #[serde(default)]
+  pub header: bool,
+}
+
+//
+
+/// Set on the request json next to `type` and `params`
+/// to have Periphery send [OutputChunk]s as the request runs.
+/// Requests without streamed output just send the final response.
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+pub struct StreamFlag {
+  #[serde(default)]
+  pub stream: bool,
+}
+
+/// Requests with secrets in the params, like registry and git tokens,
+/// or in the response. Both Core and Periphery use this list:
+/// Core encrypts the request bodies, and Periphery the response
+/// bodies, with the session keys when the connection negotiated
+/// encryption. Sensitive requests are also never spilled to disk.
+pub const SENSITIVE_REQUESTS: &[&str] = &[
+  "Deploy",
+  "ComposeUp",
+  "ComposePull",
+  "ComposeRun",
+  "Build",
+  "DeploySwarmStack",
+  "GetLatestCommit",
+  "CloneRepo",
+  "PullOrCloneRepo",
+  "ListSecrets",
+];
+
+/// Whether the request type is in [SENSITIVE_REQUESTS].
+pub fn is_sensitive_request(req_type: &str) -> bool {
+  SENSITIVE_REQUESTS.contains(&req_type)
+}
+
+/// Incremental command output of a streamed request,
+/// sent before the final response.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OutputChunk {
+  /// Matches the stage of the [Log] in the final response.
+  pub stage: String,
+  /// A line of stdout, or empty.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub stdout: String,
+  /// A line of stderr, or empty.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub stderr: String,
 }
 
 //
@@ -59,6 +168,40 @@ pub struct GetVersionResponse {
 
 //
 
+/// Counters of the inbound connection login attempts,
+/// and the IPs currently locked out after failed logins.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(GetAuthStatsResponse)]
+#[error(anyhow::Error)]
+pub struct GetAuthStats {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetAuthStatsResponse {
+  /// Login attempts since Periphery started.
+  pub attempts: u64,
+  /// Successful logins since Periphery started.
+  pub successes: u64,
+  /// Failed logins since Periphery started.
+  pub failures: u64,
+  /// Connections refused due to rate limit or lockout.
+  pub rejected: u64,
+  /// The IPs currently locked out.
+  pub lockouts: Vec<AuthLockout>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuthLockout {
+  pub ip: String,
+  /// Consecutive failed logins from the IP.
+  pub failures: u32,
+  /// The last public key the IP attempted to login with, if any.
+  pub public_key: Option<String>,
+  /// Remaining lockout in milliseconds.
+  pub remaining_ms: u64,
+}
+
+//
+
 /// This is the data Core uses to update all Server-related status caches.
 #[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
 #[response(PollStatusResponse)]
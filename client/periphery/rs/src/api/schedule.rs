@@ -0,0 +1,37 @@
+use komodo_client::entities::{
+  NoData,
+  server_schedule::{ServerSchedule, ServerScheduleStatus},
+};
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+
+//
+
+/// List the schedules from the config file and the API,
+/// with their run status.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<ServerScheduleStatus>)]
+#[error(anyhow::Error)]
+pub struct ListSchedules {}
+
+//
+
+/// Create or replace the schedule with the same name.
+/// Schedules from the config file can't be replaced.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(ServerScheduleStatus)]
+#[error(anyhow::Error)]
+pub struct UpsertSchedule {
+  pub schedule: ServerSchedule,
+}
+
+//
+
+/// Delete a schedule added through the API.
+/// Schedules from the config file can't be deleted.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(NoData)]
+#[error(anyhow::Error)]
+pub struct DeleteSchedule {
+  pub name: String,
+}
@@ -1,4 +1,11 @@
-use komodo_client::entities::stats::SystemProcess;
+use komodo_client::entities::{
+  I64,
+  stats::{
+    DiskHealth, ListeningSocket, PendingOsUpdates, ProcessSignal,
+    SystemProcess, SystemStats,
+  },
+  update::Log,
+};
 use resolver_api::Resolve;
 use serde::{Deserialize, Serialize};
 
@@ -10,3 +17,72 @@ use serde::{Deserialize, Serialize};
 pub struct GetSystemProcesses {}
 
 //
+
+/// Lists the TCP / UDP sockets listening on the host from `/proc/net`,
+/// with the owning process and container.
+/// Periphery needs the host network and pid namespaces
+/// to see the sockets and processes outside its container.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<ListeningSocket>)]
+#[error(anyhow::Error)]
+pub struct GetListeningSockets {}
+
+//
+
+/// Sends the signal to the process with `kill`.
+/// Requires `enable_process_management` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct KillSystemProcess {
+  pub pid: u32,
+  pub signal: ProcessSignal,
+}
+
+//
+
+/// Sets the niceness of the process with `renice`,
+/// from -20 (highest priority) to 19 (lowest priority).
+/// Requires `enable_process_management` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct SetProcessPriority {
+  pub pid: u32,
+  pub nice: i32,
+}
+
+//
+
+/// Reads the SMART health of the physical disks with `smartctl`.
+/// Fails if `smartctl` isn't installed.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<DiskHealth>)]
+#[error(anyhow::Error)]
+pub struct GetDiskHealth {}
+
+//
+
+/// Gets the OS package updates pending on the host, using the
+/// detected package manager (apt, dnf or apk). The result is cached
+/// for `os_updates_refresh_interval`.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(PendingOsUpdates)]
+#[error(anyhow::Error)]
+pub struct GetPendingOsUpdates {}
+
+//
+
+/// Gets the system stats samples Periphery has kept in its history,
+/// so Core can backfill its records after losing the connection.
+/// The history length is set by `stats_history_hours`.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<SystemStats>)]
+#[error(anyhow::Error)]
+pub struct GetStatsHistory {
+  /// Only return samples polled after this
+  /// unix timestamp in milliseconds.
+  pub since: I64,
+}
+
+//
@@ -0,0 +1,99 @@
+use komodo_client::entities::{
+  docker::swarm::{
+    SwarmNodeListItem, SwarmService, SwarmServiceListItem,
+  },
+  repo::Repo,
+  stack::Stack,
+  update::Log,
+};
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+
+use super::compose::ComposeUpResponse;
+
+//
+
+/// List the services in the swarm.
+/// Periphery must be connected to a swarm manager.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<SwarmServiceListItem>)]
+#[error(anyhow::Error)]
+pub struct ListSwarmServices {}
+
+//
+
+/// List the nodes in the swarm.
+/// Periphery must be connected to a swarm manager.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<SwarmNodeListItem>)]
+#[error(anyhow::Error)]
+pub struct ListSwarmNodes {}
+
+//
+
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(SwarmService)]
+#[error(anyhow::Error)]
+pub struct InspectService {
+  /// The service name or id.
+  pub name: String,
+}
+
+//
+
+/// `docker service update`, rolling out the changes
+/// to the service tasks.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct UpdateService {
+  /// The service name or id.
+  pub name: String,
+  /// Update the service image.
+  pub image: Option<String>,
+  /// Scale a replicated service.
+  pub replicas: Option<u32>,
+  /// Redeploy the tasks even if nothing changed.
+  #[serde(default)]
+  pub force: bool,
+  /// Passed to `docker service update`,
+  /// ie `["--update-parallelism 2"]`.
+  #[serde(default)]
+  pub extra_args: Vec<String>,
+}
+
+//
+
+/// `docker stack deploy` the compose files to the swarm.
+/// The stack is written to the host the same way as [ComposeUp],
+/// and the interpolated compose config is deployed.
+///
+/// Services can't be built, and are only deployed as a whole.
+///
+/// [ComposeUp]: super::compose::ComposeUp
+#[derive(Debug, Clone, Serialize, Deserialize, Resolve)]
+#[response(ComposeUpResponse)]
+#[error(anyhow::Error)]
+pub struct DeploySwarmStack {
+  /// The stack to deploy
+  pub stack: Stack,
+  /// The linked repo, if it exists.
+  pub repo: Option<Repo>,
+  /// If provided, use it to login in. Otherwise check periphery local registries.
+  pub git_token: Option<String>,
+  /// If provided, use it to login in. Otherwise check periphery local git providers.
+  pub registry_token: Option<String>,
+  /// Propogate any secret replacers from core interpolation.
+  #[serde(default)]
+  pub replacers: Vec<(String, String)>,
+}
+
+//
+
+/// `docker stack rm` the project from the swarm.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct RemoveSwarmStack {
+  pub project: String,
+}
@@ -0,0 +1,77 @@
+use komodo_client::entities::{
+  systemd::{SystemdUnit, SystemdUnitStatus},
+  update::Log,
+};
+use resolver_api::Resolve;
+use serde::{Deserialize, Serialize};
+
+//
+
+/// List the systemd services, timers, sockets, paths and mounts
+/// loaded on the host.
+/// Requires `enable_systemd_management` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<SystemdUnit>)]
+#[error(anyhow::Error)]
+pub struct ListSystemdUnits {}
+
+//
+
+/// Get the detailed status of the unit with `systemctl show`.
+/// Requires `enable_systemd_management` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(SystemdUnitStatus)]
+#[error(anyhow::Error)]
+pub struct GetUnitStatus {
+  /// The unit name, ie `nfs-server.service`
+  pub unit: String,
+}
+
+//
+
+/// Runs `systemctl start` on the unit.
+/// Requires `enable_systemd_management` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct StartUnit {
+  pub unit: String,
+}
+
+//
+
+/// Runs `systemctl stop` on the unit.
+/// Requires `enable_systemd_management` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct StopUnit {
+  pub unit: String,
+}
+
+//
+
+/// Runs `systemctl restart` on the unit.
+/// Requires `enable_systemd_management` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct RestartUnit {
+  pub unit: String,
+}
+
+//
+
+/// Get the tail of the unit journal with `journalctl`.
+/// Requires `enable_systemd_management` in the Periphery config.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Log)]
+#[error(anyhow::Error)]
+pub struct GetUnitJournal {
+  pub unit: String,
+  /// The number of lines of the journal tail to include.
+  pub tail: u64,
+  /// Show the entries with microsecond timestamps.
+  #[serde(default)]
+  pub timestamps: bool,
+}
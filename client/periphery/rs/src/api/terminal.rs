@@ -1,6 +1,9 @@
 use komodo_client::{
   api::write::TerminalRecreateMode,
-  entities::{NoData, server::TerminalInfo},
+  entities::{
+    NoData,
+    server::{TerminalInfo, TerminalRecording},
+  },
 };
 use resolver_api::Resolve;
 use serde::{Deserialize, Serialize};
@@ -9,6 +12,10 @@ use uuid::Uuid;
 /// Execute Sentinels
 pub const START_OF_OUTPUT: &str = "__KOMODO_START_OF_OUTPUT__";
 pub const END_OF_OUTPUT: &str = "__KOMODO_END_OF_OUTPUT__";
+/// Sent in place of execution output lines which were dropped
+/// from the Periphery buffer, followed by the number of lines.
+/// Counted as that many lines when resuming with [ResumeExecute].
+pub const OUTPUT_DROPPED: &str = "__KOMODO_OUTPUT_DROPPED:";
 
 #[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
 #[response(Vec<TerminalInfo>)]
@@ -34,6 +41,16 @@ pub struct CreateTerminal {
   /// Default: `Never`
   #[serde(default)]
   pub recreate: TerminalRecreateMode,
+  /// Whether to record the terminal output.
+  /// Terminals are always recorded when the
+  /// `record_terminals` Periphery config is enabled.
+  /// Default: The `record_terminals` Periphery config
+  #[serde(default)]
+  pub record: Option<bool>,
+  /// The scrollback history size in KiB.
+  /// Default: The `terminal_history_kb` Periphery config
+  #[serde(default)]
+  pub history_kb: Option<u64>,
 }
 
 fn default_command() -> String {
@@ -48,6 +65,10 @@ fn default_command() -> String {
 pub struct ConnectTerminal {
   /// The name of the terminal to connect to
   pub terminal: String,
+  /// Only receive the terminal output,
+  /// any input sent on the channel is dropped.
+  #[serde(default)]
+  pub read_only: bool,
 }
 
 //
@@ -80,6 +101,9 @@ pub struct ConnectContainerAttach {
   /// Default is 'DifferentCommand'
   #[serde(default = "default_container_recreate_mode")]
   pub recreate: TerminalRecreateMode,
+  /// Override the docker attach `--detach-keys`.
+  #[serde(default)]
+  pub detach_keys: Option<String>,
 }
 
 //
@@ -112,6 +136,36 @@ pub struct DeleteAllTerminals {}
 
 //
 
+/// Clears the scrollback history of the terminal,
+/// so it isn't replayed to clients when they connect.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(NoData)]
+#[error(anyhow::Error)]
+pub struct ClearTerminalHistory {
+  /// The name of the terminal
+  pub terminal: String,
+}
+
+//
+
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Vec<TerminalRecording>)]
+#[error(anyhow::Error)]
+pub struct ListTerminalRecordings {}
+
+//
+
+/// Responds with the asciicast v2 contents of the recording.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(String)]
+#[error(anyhow::Error)]
+pub struct GetTerminalRecording {
+  /// The file name of the recording
+  pub name: String,
+}
+
+//
+
 /// Note: The `terminal` must already exist, created by [CreateTerminal].
 #[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
 #[response(Uuid)]
@@ -150,3 +204,65 @@ fn default_container_shell() -> String {
 fn default_container_recreate_mode() -> TerminalRecreateMode {
   TerminalRecreateMode::DifferentCommand
 }
+
+//
+
+/// Resume forwarding the output of an execution started with
+/// [ExecuteTerminal] or [ExecuteContainerExec], for example after
+/// the connection dropped mid execution. Periphery buffers the execution
+/// output until acknowledged with [AckExecute], or it expires.
+///
+/// Responds with a new channel id to receive the output on,
+/// starting from line `from_line` and ending in [END_OF_OUTPUT].
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Uuid)]
+#[error(anyhow::Error)]
+pub struct ResumeExecute {
+  /// The channel id returned by the original execute request.
+  pub channel: Uuid,
+  /// The number of output lines already received,
+  /// forwarding resumes from the next line.
+  #[serde(default)]
+  pub from_line: usize,
+}
+
+//
+
+/// Acknowledge all the output of an execution was received,
+/// releasing the buffered output on Periphery.
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(NoData)]
+#[error(anyhow::Error)]
+pub struct AckExecute {
+  /// The channel id returned by the original execute request.
+  pub channel: Uuid,
+}
+
+//
+
+/// Stream the lines of a host file, like `tail -n <lines> [-f] <path>`,
+/// over a terminal channel ending in [END_OF_OUTPUT].
+/// The `path` must be inside one of the `allowed_log_paths`
+/// in the Periphery config.
+///
+/// With `follow`, the stream stays open until [DisconnectTerminal].
+#[derive(Serialize, Deserialize, Debug, Clone, Resolve)]
+#[response(Uuid)]
+#[error(anyhow::Error)]
+pub struct TailHostFile {
+  /// The absolute path of the file on the host.
+  pub path: String,
+  /// The number of lines from the end of the file to start with.
+  /// Default: `100`
+  #[serde(default = "default_tail_lines")]
+  pub lines: u64,
+  /// Keep streaming the lines appended to the file.
+  /// The opened file is followed, not the path,
+  /// so the stream doesn't continue into a rotated file.
+  #[serde(default)]
+  pub follow: bool,
+}
+
+fn default_tail_lines() -> u64 {
+  100
+}
@@ -0,0 +1,102 @@
+use anyhow::anyhow;
+use bytes::Bytes;
+use encoding::{Decode, Encode, WithChannel};
+use uuid::Uuid;
+
+use crate::transport::{EncodedTransportMessage, TransportMessage};
+
+/// A piece of a large [EncodedTransportMessage], which is split up
+/// so single websocket frames stay under proxy buffer limits.
+/// The message uuid is carried as the channel in the header.
+///
+/// ```markdown
+/// | -- u32 -- | -- u32 -- | -- u8[] -- |
+/// |   Index   |   Total   | <CONTENTS> |
+/// ```
+#[derive(Debug)]
+pub struct EncodedChunkMessage(pub(super) WithChannel<Bytes>);
+
+/// Index + Total
+const CHUNK_HEADER_LEN: usize = 4 + 4;
+
+pub struct ChunkMessage {
+  /// Identifies the chunks of the same message.
+  pub message: Uuid,
+  /// The position of the chunk in the message, starting at 0.
+  pub index: u32,
+  /// The number of chunks in the message.
+  pub total: u32,
+  pub data: Bytes,
+}
+
+impl Encode<EncodedTransportMessage> for ChunkMessage {
+  fn encode(self) -> EncodedTransportMessage {
+    let mut bytes =
+      Vec::with_capacity(CHUNK_HEADER_LEN + self.data.len());
+    bytes.extend(self.index.to_be_bytes());
+    bytes.extend(self.total.to_be_bytes());
+    bytes.extend_from_slice(&self.data);
+    TransportMessage::Chunk(EncodedChunkMessage(WithChannel {
+      channel: self.message,
+      data: bytes.into(),
+    }))
+    .encode()
+  }
+}
+
+impl Decode<ChunkMessage> for EncodedChunkMessage {
+  fn decode(self) -> anyhow::Result<ChunkMessage> {
+    let WithChannel {
+      channel: message,
+      data: mut bytes,
+    } = self.0;
+    if bytes.len() < CHUNK_HEADER_LEN {
+      return Err(anyhow!(
+        "ChunkMessage bytes too short to include header"
+      ));
+    }
+    let header = bytes.split_to(CHUNK_HEADER_LEN);
+    let index = u32::from_be_bytes(header[0..4].try_into()?);
+    let total = u32::from_be_bytes(header[4..8].try_into()?);
+    Ok(ChunkMessage {
+      message,
+      index,
+      total,
+      data: bytes,
+    })
+  }
+}
+
+impl EncodedTransportMessage {
+  /// Splits messages larger than `max_chunk_bytes`
+  /// into Chunk messages. Smaller messages are returned as is.
+  /// A `max_chunk_bytes` of 0 disables chunking.
+  pub fn into_chunks(
+    self,
+    max_chunk_bytes: usize,
+  ) -> Vec<EncodedTransportMessage> {
+    if max_chunk_bytes == 0 || self.0.len() <= max_chunk_bytes {
+      return vec![self];
+    }
+    let message = Uuid::new_v4();
+    let len = self.0.len();
+    let total = len.div_ceil(max_chunk_bytes) as u32;
+    (0..len)
+      .step_by(max_chunk_bytes)
+      .enumerate()
+      .map(|(index, start)| {
+        ChunkMessage {
+          message,
+          index: index as u32,
+          total,
+          // Slices the message, the contents are only
+          // copied once into each encoded chunk.
+          data: self
+            .0
+            .slice(start..(start + max_chunk_bytes).min(len)),
+        }
+        .encode()
+      })
+      .collect()
+  }
+}
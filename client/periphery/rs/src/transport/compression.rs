@@ -0,0 +1,59 @@
+use anyhow::Context;
+use encoding::decode_limits;
+
+pub use komodo_client::entities::config::TransportCompression;
+
+use crate::transport::{
+  COMPRESSED_FLAG, EncodedTransportMessage, TransportHeader,
+  TransportMessageVariant,
+};
+
+/// Responses smaller than this are sent uncompressed,
+/// the savings aren't worth the overhead.
+pub const COMPRESSION_MIN_BYTES: usize = 4 * 1024;
+
+/// Favors speed, large responses are mostly repetitive JSON.
+pub(super) const ZSTD_LEVEL: i32 = 3;
+
+impl EncodedTransportMessage {
+  /// Compresses large Response messages if compression
+  /// was negotiated for the connection.
+  /// Other messages are returned as is.
+  ///
+  /// Only the contents are compressed, and [COMPRESSED_FLAG]
+  /// is set on the header, so the header can still be read.
+  pub fn compress(
+    self,
+    compression: TransportCompression,
+  ) -> EncodedTransportMessage {
+    if compression == TransportCompression::None
+      || self.0.len() < COMPRESSION_MIN_BYTES
+    {
+      return self;
+    }
+    let Ok((mut header, offset)) = TransportHeader::read(&self.0)
+    else {
+      return self;
+    };
+    if !matches!(header.variant, TransportMessageVariant::Response)
+      || header.is_compressed()
+    {
+      return self;
+    }
+    let contents = &self.0[offset..];
+    match zstd::bulk::compress(contents, ZSTD_LEVEL) {
+      Ok(bytes) if bytes.len() < contents.len() => {
+        header.flags |= COMPRESSED_FLAG;
+        EncodedTransportMessage(header.encode_with(&bytes).into())
+      }
+      // Send uncompressed if compression fails or doesn't help.
+      _ => self,
+    }
+  }
+}
+
+pub(super) fn decompress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+  decode_limits()
+    .decompress_zstd(bytes)
+    .context("Failed to decompress transport message")
+}
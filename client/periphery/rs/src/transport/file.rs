@@ -0,0 +1,90 @@
+use anyhow::{Context, anyhow};
+use bytes::Bytes;
+use encoding::{
+  CastBytes, Checked, Compressed, Decode, Encode, EncodedChecked,
+  EncodedCompressed, WithChannel,
+};
+use uuid::Uuid;
+
+use crate::transport::{EncodedTransportMessage, TransportMessage};
+
+/// A piece of a file transfer. Files are sent as raw bytes
+/// instead of through JSON requests, and the transfer is
+/// checksummed with sha256 once all pieces are sent.
+/// The transfer uuid is carried as the channel in the header.
+/// The contents are [EncodedCompressed], as File messages
+/// aren't compressed at the transport level, and [EncodedChecked]
+/// so a corrupted or truncated piece is dropped on arrival. The
+/// transfer then resumes from the last good offset instead of
+/// failing the sha256 and starting over.
+///
+/// ```markdown
+/// | -- u64 -- | ---------------- u8[] ----------------- |
+/// |  Offset   | <EncodedChecked<EncodedCompressed<..>>> |
+/// ```
+#[derive(Debug)]
+pub struct EncodedFileMessage(pub(super) WithChannel<Bytes>);
+
+impl EncodedFileMessage {
+  /// The transfer channel, readable without decoding the contents.
+  pub fn channel(&self) -> Uuid {
+    self.0.channel
+  }
+}
+
+/// Offset
+const FILE_HEADER_LEN: usize = 8;
+
+/// Max bytes of file contents sent in each message.
+pub const FILE_MESSAGE_BYTES: usize = 512 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct FileMessage {
+  /// Identifies the transfer.
+  pub channel: Uuid,
+  /// The position of the data in the file.
+  pub offset: u64,
+  /// The uncompressed contents.
+  pub data: Bytes,
+}
+
+impl Encode<EncodedTransportMessage> for FileMessage {
+  fn encode(self) -> EncodedTransportMessage {
+    let data = Checked(Compressed(self.data).encode())
+      .encode()
+      .into_bytes();
+    let mut bytes = Vec::with_capacity(FILE_HEADER_LEN + data.len());
+    bytes.extend(self.offset.to_be_bytes());
+    bytes.extend_from_slice(&data);
+    TransportMessage::File(EncodedFileMessage(WithChannel {
+      channel: self.channel,
+      data: bytes.into(),
+    }))
+    .encode()
+  }
+}
+
+impl Decode<FileMessage> for EncodedFileMessage {
+  fn decode(self) -> anyhow::Result<FileMessage> {
+    let WithChannel {
+      channel,
+      data: mut bytes,
+    } = self.0;
+    if bytes.len() < FILE_HEADER_LEN {
+      return Err(anyhow!(
+        "FileMessage bytes too short to include header"
+      ));
+    }
+    let header = bytes.split_to(FILE_HEADER_LEN);
+    let offset = u64::from_be_bytes(header[..].try_into()?);
+    let data =
+      EncodedChecked::<EncodedCompressed<Bytes>>::from_bytes(bytes)
+        .decode_into()
+        .context("Failed to decode FileMessage contents")?;
+    Ok(FileMessage {
+      channel,
+      offset,
+      data,
+    })
+  }
+}
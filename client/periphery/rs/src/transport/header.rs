@@ -0,0 +1,178 @@
+use std::sync::OnceLock;
+
+use anyhow::{Context, anyhow};
+use encoding::VersionedDecoders;
+use uuid::Uuid;
+
+use crate::transport::TransportMessageVariant;
+
+/// The header version written by this build.
+/// Later versions may only append fields to the header,
+/// which readers of earlier versions skip using the header length.
+/// Changes which can't be skipped register a decoder in
+/// [header_decoders] for the new version, and migrate the earlier ones.
+pub const TRANSPORT_HEADER_VERSION: u8 = 1;
+
+/// Version + Variant + Flags + Header length
+const HEADER_PREFIX_LEN: usize = 1 + 1 + 1 + 2;
+
+/// Set when the contents are zstd compressed.
+pub const COMPRESSED_FLAG: u8 = 0b0000_0001;
+
+/// The flags this build can read. Contents with
+/// any other flag set can't be interpreted.
+const KNOWN_FLAGS: u8 = COMPRESSED_FLAG;
+
+/// Prefixes every [EncodedTransportMessage][super::EncodedTransportMessage],
+/// so it can be read without touching the contents.
+/// On the wire it is only used after login, once
+/// [TransportOptions::header][super::TransportOptions::header]
+/// is negotiated. Older peers use the legacy framing.
+///
+/// ```markdown
+/// | -- u8 -- | -- u8 -- | -- u8 -- | ---- u16 ---- | -- u8[] -- | -- u8[] -- |
+/// | Version  | Variant  |  Flags   | Header Length |   Header   | <CONTENTS> |
+/// ```
+///
+/// The version 1 header is the channel uuid for the variants
+/// sent on a channel, and empty for the others.
+/// Request messages may follow it with an idempotency key.
+///
+/// ```markdown
+/// | -- [u8; 16] -- | ------- [u8; 16] ------- |
+/// |  Channel Uuid  | Idempotency Key (Request) |
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TransportHeader {
+  pub variant: TransportMessageVariant,
+  pub flags: u8,
+  /// Set for the variants sent on a channel.
+  /// For Chunk messages this is the id of the chunked message.
+  pub channel: Option<Uuid>,
+  /// Set on Request messages which are safe to retransmit.
+  pub idempotency_key: Option<Uuid>,
+}
+
+impl TransportHeader {
+  pub fn new(
+    variant: TransportMessageVariant,
+    channel: Option<Uuid>,
+  ) -> TransportHeader {
+    TransportHeader {
+      variant,
+      flags: 0,
+      channel,
+      idempotency_key: None,
+    }
+  }
+
+  pub fn is_compressed(&self) -> bool {
+    self.flags & COMPRESSED_FLAG != 0
+  }
+
+  /// Writes the header followed by the contents.
+  pub fn encode_with(&self, contents: &[u8]) -> Vec<u8> {
+    // The idempotency key is only read after a channel.
+    let idempotency_key = self.channel.and(self.idempotency_key);
+    let header_len = self.channel.map(|_| 16).unwrap_or_default()
+      + idempotency_key.map(|_| 16).unwrap_or_default();
+    let mut bytes = Vec::with_capacity(
+      HEADER_PREFIX_LEN + header_len + contents.len(),
+    );
+    bytes.push(TRANSPORT_HEADER_VERSION);
+    bytes.push(self.variant.as_byte());
+    bytes.push(self.flags);
+    bytes.extend((header_len as u16).to_be_bytes());
+    if let Some(channel) = self.channel {
+      bytes.extend(channel.into_bytes());
+    }
+    if let Some(idempotency_key) = idempotency_key {
+      bytes.extend(idempotency_key.into_bytes());
+    }
+    bytes.extend_from_slice(contents);
+    bytes
+  }
+
+  /// Reads the header from the front of the bytes.
+  /// Returns the header and the offset of the contents.
+  pub fn read(
+    bytes: &[u8],
+  ) -> anyhow::Result<(TransportHeader, usize)> {
+    let prefix: [u8; HEADER_PREFIX_LEN] = bytes
+      .get(..HEADER_PREFIX_LEN)
+      .and_then(|prefix| prefix.try_into().ok())
+      .with_context(|| {
+        format!(
+          "Transport message of {} bytes too short to include header",
+          bytes.len()
+        )
+      })?;
+    let [version, variant, flags, len @ ..] = prefix;
+    let variant = TransportMessageVariant::from_byte(variant)?;
+    if flags & !KNOWN_FLAGS != 0 {
+      return Err(anyhow!(
+        "Got unsupported transport header flags {flags:#010b} (version {version})"
+      ));
+    }
+    let header_len = u16::from_be_bytes(len) as usize;
+    let offset = HEADER_PREFIX_LEN + header_len;
+    let header = bytes.get(HEADER_PREFIX_LEN..offset).with_context(|| {
+      format!(
+        "Transport message of {} bytes too short to include {header_len} byte header",
+        bytes.len()
+      )
+    })?;
+    let HeaderFields {
+      channel,
+      idempotency_key,
+    } = header_decoders().decode(version, header)?;
+    if variant.has_channel() && channel.is_none() {
+      return Err(anyhow!(
+        "Transport header too short to include channel"
+      ));
+    }
+    let channel = channel.filter(|_| variant.has_channel());
+    let idempotency_key = idempotency_key.filter(|_| {
+      matches!(variant, TransportMessageVariant::Request)
+    });
+    Ok((
+      TransportHeader {
+        variant,
+        flags,
+        channel,
+        idempotency_key,
+      },
+      offset,
+    ))
+  }
+}
+
+/// The fields read from the header,
+/// before they are checked against the variant.
+struct HeaderFields {
+  channel: Option<Uuid>,
+  idempotency_key: Option<Uuid>,
+}
+
+fn header_decoders() -> &'static VersionedDecoders<HeaderFields> {
+  static HEADER_DECODERS: OnceLock<VersionedDecoders<HeaderFields>> =
+    OnceLock::new();
+  HEADER_DECODERS.get_or_init(|| {
+    VersionedDecoders::new("transport header")
+      .register(1, read_v1_header)
+      .forward_compatible()
+  })
+}
+
+fn read_v1_header(header: &[u8]) -> anyhow::Result<HeaderFields> {
+  let read_uuid = |range: std::ops::Range<usize>| {
+    header
+      .get(range)
+      .and_then(|uuid| uuid.try_into().ok())
+      .map(Uuid::from_bytes)
+  };
+  Ok(HeaderFields {
+    channel: read_uuid(0..16),
+    idempotency_key: read_uuid(16..32),
+  })
+}
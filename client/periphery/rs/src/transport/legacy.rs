@@ -0,0 +1,192 @@
+use anyhow::{Context, anyhow};
+use bytes::Bytes;
+use encoding::decode_limits;
+use uuid::Uuid;
+
+use crate::transport::{
+  COMPRESSION_MIN_BYTES, EncodedTransportMessage,
+  TransportCompression, TransportHeader, TransportMessageVariant,
+  compression,
+};
+
+/// Suffixes compressed messages in the legacy framing, in place of the
+/// [TransportMessageVariant] byte. The compressed bytes are a full
+/// legacy framed message, including its variant byte.
+///
+/// ```markdown
+/// | --------- u8[] ---------- | -- u8 -- |
+/// | <ZSTD TRANSPORT MESSAGE>  |   255    |
+/// ```
+const COMPRESSED_MESSAGE_BYTE: u8 = 255;
+
+/// Index + Total of legacy Chunk and Offset of legacy File messages,
+/// which come after the contents instead of before.
+const CHUNK_TRAILER_LEN: usize = 4 + 4;
+const FILE_TRAILER_LEN: usize = 8;
+
+/// The framing used before the [TransportHeader], where the
+/// variant byte comes last and the channel uuid before it.
+/// Login messages always use it, as the framing is only agreed on
+/// with [TransportOptions::header][crate::transport::TransportOptions::header],
+/// and it is kept for the whole connection with older peers.
+///
+/// Messages are converted at the connection edge,
+/// everything else works with the header framing.
+///
+/// ```markdown
+/// | -- u8[] -- | -- u8[] -- | -- [u8; 16] -- | -- u8 -- |
+/// | <CONTENTS> |  Trailer   | Channel Uuid   | Variant  |
+/// ```
+///
+/// The Channel is only included for the variants sent on a channel.
+/// The Trailer is the Index + Total for Chunk messages,
+/// and the Offset for File messages. Request idempotency keys
+/// aren't carried, older peers don't deduplicate retransmissions.
+impl EncodedTransportMessage {
+  /// Received messages are converted from the legacy framing,
+  /// unless the header framing was negotiated.
+  pub fn normalize_framing(
+    self,
+    header: bool,
+  ) -> anyhow::Result<EncodedTransportMessage> {
+    if header {
+      Ok(self)
+    } else {
+      EncodedTransportMessage::from_legacy(self.0)
+    }
+  }
+
+  /// Converts a message in the legacy framing to the header framing.
+  /// Legacy Chunk messages still contain legacy framed pieces,
+  /// the reassembled message needs to be converted as well.
+  pub fn from_legacy(
+    bytes: Bytes,
+  ) -> anyhow::Result<EncodedTransportMessage> {
+    decode_limits().check_message_bytes(bytes.len())?;
+    let (&last, rest) = bytes
+      .split_last()
+      .context("Failed to decode legacy message | bytes are empty")?;
+    let compressed = last == COMPRESSED_MESSAGE_BYTE;
+    let (variant_byte, mut contents) = if compressed {
+      let mut bytes = compression::decompress(rest)?;
+      // Not checked for compression again,
+      // so compressed messages can't be nested.
+      let variant_byte = bytes
+        .pop()
+        .context("Failed to decode compressed legacy message")?;
+      (variant_byte, Bytes::from(bytes))
+    } else {
+      (last, bytes.slice(..rest.len()))
+    };
+    let variant = TransportMessageVariant::from_byte(variant_byte)?;
+    let channel = if variant.has_channel() {
+      let len =
+        contents.len().checked_sub(16).with_context(|| {
+          format!(
+            "Legacy {variant:?} message too short to include channel"
+          )
+        })?;
+      let channel = Uuid::from_slice(&contents[len..])?;
+      contents.truncate(len);
+      Some(channel)
+    } else {
+      None
+    };
+    let trailer_len = match variant {
+      TransportMessageVariant::Chunk => CHUNK_TRAILER_LEN,
+      TransportMessageVariant::File => FILE_TRAILER_LEN,
+      _ => 0,
+    };
+    let len =
+      contents.len().checked_sub(trailer_len).with_context(|| {
+        format!(
+          "Legacy {variant:?} message too short to include header"
+        )
+      })?;
+    let trailer = contents.split_off(len);
+    let mut bytes = Vec::with_capacity(trailer_len + contents.len());
+    bytes.extend_from_slice(&trailer);
+    bytes.extend_from_slice(&contents);
+    let message =
+      TransportHeader::new(variant, channel).encode_with(&bytes);
+    Ok(EncodedTransportMessage(message.into()))
+  }
+
+  /// Converts the message to the legacy framing.
+  /// Compressed contents are sent decompressed,
+  /// [EncodedTransportMessage::into_legacy_frames] compresses the
+  /// whole message like older peers expect.
+  pub fn into_legacy(self) -> anyhow::Result<Vec<u8>> {
+    let (header, offset) = TransportHeader::read(&self.0)?;
+    let mut contents = self.0.slice(offset..);
+    if header.is_compressed() {
+      contents = compression::decompress(&contents)?.into();
+    }
+    let trailer_len = match header.variant {
+      TransportMessageVariant::Chunk => CHUNK_TRAILER_LEN,
+      TransportMessageVariant::File => FILE_TRAILER_LEN,
+      _ => 0,
+    };
+    if contents.len() < trailer_len {
+      return Err(anyhow!(
+        "{:?} message too short to include header",
+        header.variant
+      ));
+    }
+    let (trailer, contents) = contents.split_at(trailer_len);
+    let mut bytes = Vec::with_capacity(contents.len() + 32);
+    bytes.extend_from_slice(contents);
+    bytes.extend_from_slice(trailer);
+    if let Some(channel) = header.channel {
+      bytes.extend(channel.into_bytes());
+    }
+    bytes.push(header.variant.as_byte());
+    Ok(bytes)
+  }
+
+  /// Frames the message for a connection without the header framing.
+  /// Large Response messages are compressed if negotiated, and
+  /// messages larger than `max_chunk_bytes` are split into
+  /// legacy Chunk messages. 0 sends the message whole.
+  pub fn into_legacy_frames(
+    self,
+    compression: TransportCompression,
+    max_chunk_bytes: usize,
+  ) -> anyhow::Result<Vec<Bytes>> {
+    let variant = self.variant()?;
+    let mut bytes = self.into_legacy()?;
+    if compression != TransportCompression::None
+      && matches!(variant, TransportMessageVariant::Response)
+      && bytes.len() >= COMPRESSION_MIN_BYTES
+    {
+      match zstd::bulk::compress(&bytes, compression::ZSTD_LEVEL) {
+        Ok(mut compressed) if compressed.len() < bytes.len() => {
+          compressed.push(COMPRESSED_MESSAGE_BYTE);
+          bytes = compressed;
+        }
+        // Send uncompressed if compression fails or doesn't help.
+        _ => {}
+      }
+    }
+    if max_chunk_bytes == 0 || bytes.len() <= max_chunk_bytes {
+      return Ok(vec![bytes.into()]);
+    }
+    let message = Uuid::new_v4();
+    let total = bytes.len().div_ceil(max_chunk_bytes) as u32;
+    let frames = bytes
+      .chunks(max_chunk_bytes)
+      .enumerate()
+      .map(|(index, data)| {
+        let mut chunk =
+          Vec::with_capacity(data.len() + CHUNK_TRAILER_LEN + 17);
+        chunk.extend_from_slice(data);
+        chunk.extend((index as u32).to_be_bytes());
+        chunk.extend(total.to_be_bytes());
+        chunk.extend(message.into_bytes());
+        chunk.push(TransportMessageVariant::Chunk.as_byte());
+        chunk.into()
+      })
+      .collect();
+    Ok(frames)
+  }
+}
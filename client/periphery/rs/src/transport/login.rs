@@ -1,12 +1,50 @@
 use anyhow::{Context, anyhow};
+use bytes::Bytes;
 use derive_variants::{EnumVariants, ExtractVariant};
 use encoding::{
-  CastBytes, Decode, Encode, EncodedResponse, impl_cast_bytes_vec,
-  impl_from_for_wrapper,
+  BodyEncoding, CastBytes, Decode, Encode, EncodedResponse,
+  impl_cast_bytes_vec, impl_from_for_wrapper,
 };
+pub use komodo_client::entities::config::TransportEncoding;
 use noise::key::SpkiPublicKey;
 
-use crate::transport::{EncodedTransportMessage, TransportMessage};
+use crate::transport::{
+  EncodedTransportMessage, TransportCompression, TransportMessage,
+};
+
+/// The transport features agreed on for a connection.
+/// The server decides these during login, based on what
+/// the client advertised, and sends them with [LoginMessage::Success].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransportOptions {
+  /// Compression applied to large responses.
+  pub compression: TransportCompression,
+  /// Whether large messages can be split into Chunk messages.
+  pub chunking: bool,
+  /// Whether Ping messages are answered with Pong.
+  pub keepalive: bool,
+  /// Encoding of the Response bodies.
+  pub encoding: TransportEncoding,
+  /// Whether sensitive Request / Response bodies are encrypted
+  /// again with the session keys from the handshake, so they stay
+  /// protected through a reverse proxy terminating TLS.
+  pub encryption: bool,
+  /// Whether messages after login are framed with the [TransportHeader].
+  /// Login messages, and all messages with older peers,
+  /// use the legacy trailing variant framing.
+  ///
+  /// [TransportHeader]: crate::transport::TransportHeader
+  pub header: bool,
+}
+
+impl TransportOptions {
+  pub fn body_encoding(&self) -> BodyEncoding {
+    match self.encoding {
+      TransportEncoding::Json => BodyEncoding::Json,
+      TransportEncoding::Cbor => BodyEncoding::Cbor,
+    }
+  }
+}
 
 #[derive(Debug)]
 pub struct EncodedLoginMessage(
@@ -32,11 +70,15 @@ impl_cast_bytes_vec!(InnerEncodedLoginMessage, Vec);
 #[variant_derive(Debug, Clone, Copy)]
 pub enum LoginMessage {
   /// At the end of every login flow,
-  /// Send a success message
-  Success,
+  /// Send a success message.
+  /// Includes the options to use for the connection,
+  /// which older clients ignore.
+  Success(TransportOptions),
   /// Every handshake includes a random 32 byte nonce
   /// to identify the connection.
-  Nonce([u8; 32]),
+  /// Also tells the client whether the server
+  /// uses a pre-shared key with the handshake.
+  Nonce { nonce: [u8; 32], psk: bool },
   /// Bytes that are part of the noise handshake.
   Handshake(Vec<u8>),
   /// Used during Periphery -> Core connections.
@@ -62,8 +104,37 @@ impl Encode<EncodedTransportMessage> for LoginMessage {
   fn encode(self) -> EncodedTransportMessage {
     let variant_byte = self.extract_variant().as_byte();
     let mut bytes = match self {
-      LoginMessage::Success => Vec::new(),
-      LoginMessage::Nonce(nonce) => nonce.to_vec(),
+      LoginMessage::Success(options) => {
+        let compression = match options.compression {
+          TransportCompression::None => 0,
+          TransportCompression::Zstd => 1,
+        };
+        let chunking = if options.chunking { 1 } else { 0 };
+        let keepalive = if options.keepalive { 1 } else { 0 };
+        let encoding = match options.encoding {
+          TransportEncoding::Json => 0,
+          TransportEncoding::Cbor => 1,
+        };
+        let encryption = if options.encryption { 1 } else { 0 };
+        let header = if options.header { 1 } else { 0 };
+        vec![
+          compression,
+          chunking,
+          keepalive,
+          encoding,
+          encryption,
+          header,
+        ]
+      }
+      LoginMessage::Nonce { nonce, psk } => {
+        let mut bytes = nonce.to_vec();
+        // Only add the psk byte when used,
+        // so older clients can still parse the nonce.
+        if psk {
+          bytes.push(1);
+        }
+        bytes
+      }
       LoginMessage::Handshake(bytes) => bytes,
       LoginMessage::OnboardingFlow(onboarding_flow) => {
         let byte = if onboarding_flow { 1 } else { 0 };
@@ -101,13 +172,92 @@ impl Decode<LoginMessage> for EncodedLoginMessage {
 
     use LoginMessageVariant::*;
     let message = match variant {
-      Success => LoginMessage::Success,
+      // Older servers send fewer (or no) option bytes,
+      // the missing options are disabled.
+      Success => {
+        let compression = match bytes.first() {
+          None | Some(0) => TransportCompression::None,
+          Some(1) => TransportCompression::Zstd,
+          Some(other) => {
+            return Err(anyhow!(
+              "Got unrecognized LoginMessage Success compression byte: {other}"
+            ));
+          }
+        };
+        let chunking = match bytes.get(1) {
+          None | Some(0) => false,
+          Some(1) => true,
+          Some(other) => {
+            return Err(anyhow!(
+              "Got unrecognized LoginMessage Success chunking byte: {other}"
+            ));
+          }
+        };
+        let keepalive = match bytes.get(2) {
+          None | Some(0) => false,
+          Some(1) => true,
+          Some(other) => {
+            return Err(anyhow!(
+              "Got unrecognized LoginMessage Success keepalive byte: {other}"
+            ));
+          }
+        };
+        let encoding = match bytes.get(3) {
+          None | Some(0) => TransportEncoding::Json,
+          Some(1) => TransportEncoding::Cbor,
+          Some(other) => {
+            return Err(anyhow!(
+              "Got unrecognized LoginMessage Success encoding byte: {other}"
+            ));
+          }
+        };
+        let encryption = match bytes.get(4) {
+          None | Some(0) => false,
+          Some(1) => true,
+          Some(other) => {
+            return Err(anyhow!(
+              "Got unrecognized LoginMessage Success encryption byte: {other}"
+            ));
+          }
+        };
+        let header = match bytes.get(5) {
+          None | Some(0) => false,
+          Some(1) => true,
+          Some(other) => {
+            return Err(anyhow!(
+              "Got unrecognized LoginMessage Success header byte: {other}"
+            ));
+          }
+        };
+        LoginMessage::Success(TransportOptions {
+          compression,
+          chunking,
+          keepalive,
+          encoding,
+          encryption,
+          header,
+        })
+      }
 
-      Nonce => LoginMessage::Nonce(
-        bytes
-          .try_into()
-          .map_err(|_| anyhow!("Invalid connection nonce"))?,
-      ),
+      // Older servers don't send the psk byte.
+      Nonce => {
+        let psk = match bytes.get(32) {
+          None | Some(0) => false,
+          Some(1) => true,
+          Some(other) => {
+            return Err(anyhow!(
+              "Got unrecognized LoginMessage Nonce psk byte: {other}"
+            ));
+          }
+        };
+        bytes.truncate(32);
+        LoginMessage::Nonce {
+          nonce: bytes
+            .try_into()
+            .map_err(|_| anyhow!("Invalid connection nonce"))?,
+          psk,
+        }
+      }
 
       Handshake => LoginMessage::Handshake(bytes),
 
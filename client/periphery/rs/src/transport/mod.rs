@@ -1,24 +1,86 @@
 use anyhow::{Context as _, anyhow};
+use bytes::Bytes;
 use derive_variants::{EnumVariants, ExtractVariant as _};
 use encoding::{
-  CastBytes, Decode, Encode, EncodedChannel, EncodedJsonMessage,
-  EncodedResponse, WithChannel, impl_cast_bytes_vec,
-  impl_from_for_wrapper,
+  CastBytes, Decode, Encode, EncodedBodyMessage, EncodedJsonMessage,
+  EncodedResponse, JsonMessage, WithChannel, decode_limits,
+  impl_cast_bytes_vec,
 };
 
+mod chunk;
+mod compression;
+mod file;
+mod header;
+mod legacy;
 mod login;
+mod ping;
+pub use chunk::*;
+pub use compression::*;
+pub use file::*;
+pub use header::*;
+use komodo_client::entities::{
+  docker::container::ContainerStateStatusEnum,
+  server::HostPowerAction, update::Log,
+};
 pub use login::*;
-use serde::de::DeserializeOwned;
+pub use ping::*;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use uuid::Uuid;
 
 // ===================
 //  TRANSPORT MESSAGE
 // ===================
 
+/// A [TransportHeader] followed by the message contents.
+/// Messages sent to or received from peers using the legacy framing
+/// are converted with [EncodedTransportMessage::into_legacy_frames] and
+/// [EncodedTransportMessage::normalize_framing].
 #[derive(Debug, Clone)]
-pub struct EncodedTransportMessage(Vec<u8>);
+pub struct EncodedTransportMessage(Bytes);
+
+impl CastBytes for EncodedTransportMessage {
+  fn from_bytes(bytes: Bytes) -> Self {
+    Self(bytes)
+  }
+  fn into_bytes(self) -> Bytes {
+    self.0
+  }
+}
 
-impl_cast_bytes_vec!(EncodedTransportMessage, Vec);
+impl EncodedTransportMessage {
+  /// Reads the header without decoding the message.
+  pub fn header(&self) -> anyhow::Result<TransportHeader> {
+    TransportHeader::read(&self.0).map(|(header, _)| header)
+  }
+
+  /// Reads the variant without decoding the message.
+  pub fn variant(&self) -> anyhow::Result<TransportMessageVariant> {
+    self.header().map(|header| header.variant)
+  }
+
+  pub fn is_compressed(&self) -> bool {
+    self
+      .header()
+      .map(|header| header.is_compressed())
+      .unwrap_or_default()
+  }
+
+  /// Reads the channel without decoding the message,
+  /// for the variants which are sent on a channel.
+  /// For Chunk messages this is the id of the chunked message.
+  pub fn channel(&self) -> Option<Uuid> {
+    self.header().ok()?.channel
+  }
+
+  /// The encoded length in bytes.
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
 
 /// When an EncodedTransportMessage is received,
 /// it is decoded into this type.
@@ -33,49 +95,108 @@ pub enum TransportMessage {
   Request(EncodedRequestMessage),
   Response(EncodedResponseMessage),
   Terminal(EncodedTerminalMessage),
+  Notice(EncodedNoticeMessage),
+  Chunk(EncodedChunkMessage),
+  Ping(EncodedPingMessage),
+  Pong(EncodedPingMessage),
+  File(EncodedFileMessage),
+  Event(EncodedEventMessage),
 }
 
 impl Encode<EncodedTransportMessage> for TransportMessage {
   fn encode(self) -> EncodedTransportMessage {
-    let variant_byte = self.extract_variant().as_byte();
-    let mut bytes = match self {
-      TransportMessage::Login(data) => data.into_vec(),
-      TransportMessage::Request(data) => data.0.into_vec(),
-      TransportMessage::Response(data) => data.0.into_vec(),
-      TransportMessage::Terminal(data) => data.0.into_vec(),
+    let variant = self.extract_variant();
+    let mut idempotency_key = None;
+    let (channel, contents) = match self {
+      TransportMessage::Login(data) => (None, data.into_bytes()),
+      TransportMessage::Request(data) => {
+        idempotency_key = data.1;
+        (Some(data.0.channel), data.0.data.into_bytes())
+      }
+      TransportMessage::Response(data) => {
+        (Some(data.0.channel), data.0.data.into_bytes())
+      }
+      TransportMessage::Terminal(data) => {
+        (Some(data.0.channel), data.0.data)
+      }
+      TransportMessage::Notice(data) => (None, data.0.into_bytes()),
+      TransportMessage::Chunk(data) => {
+        (Some(data.0.channel), data.0.data)
+      }
+      TransportMessage::Ping(data) => (None, data.into_bytes()),
+      TransportMessage::Pong(data) => (None, data.into_bytes()),
+      TransportMessage::File(data) => {
+        (Some(data.0.channel), data.0.data)
+      }
+      TransportMessage::Event(data) => (None, data.0.into_bytes()),
     };
-    bytes.push(variant_byte);
-    EncodedTransportMessage(bytes)
+    let bytes = TransportHeader {
+      idempotency_key,
+      ..TransportHeader::new(variant, channel)
+    }
+    .encode_with(&contents);
+    EncodedTransportMessage(bytes.into())
   }
 }
 
 impl Decode<TransportMessage> for EncodedTransportMessage {
   fn decode(self) -> anyhow::Result<TransportMessage> {
-    let mut bytes = self.0;
-    let variant_byte = bytes
-      .pop()
-      .context("Failed to decode message | bytes are empty")?;
+    decode_limits().check_message_bytes(self.0.len())?;
+    let (header, offset) = TransportHeader::read(&self.0)
+      .context("Failed to decode message header")?;
+    // Shares the received buffer, the contents aren't copied.
+    let mut contents = self.0.slice(offset..);
+    if header.is_compressed() {
+      contents = compression::decompress(&contents)?.into();
+    }
+    let with_channel = |data| {
+      header
+        .channel
+        .map(|channel| WithChannel { channel, data })
+        .context("Transport header is missing channel")
+    };
     use TransportMessageVariant::*;
-    let message =
-      match TransportMessageVariant::from_byte(variant_byte)? {
-        Login => TransportMessage::Login(
-          EncodedLoginMessage::from_vec(bytes),
-        ),
-        Request => TransportMessage::Request(EncodedRequestMessage(
-          EncodedChannel::from_vec(bytes),
-        )),
-        Response => TransportMessage::Response(
-          EncodedResponseMessage(EncodedChannel::from_vec(bytes)),
-        ),
-        Terminal => TransportMessage::Terminal(
-          EncodedTerminalMessage(EncodedChannel::from_vec(bytes)),
-        ),
-      };
+    let message = match header.variant {
+      Login => TransportMessage::Login(
+        EncodedLoginMessage::from_bytes(contents),
+      ),
+      Request => TransportMessage::Request(EncodedRequestMessage(
+        with_channel(contents)?.map(EncodedJsonMessage::from_bytes),
+        header.idempotency_key,
+      )),
+      Response => TransportMessage::Response(EncodedResponseMessage(
+        with_channel(contents)?.map(EncodedResponse::from_bytes),
+      )),
+      Terminal => TransportMessage::Terminal(EncodedTerminalMessage(
+        with_channel(contents)?,
+      )),
+      Notice => TransportMessage::Notice(EncodedNoticeMessage(
+        EncodedJsonMessage::from_bytes(contents),
+      )),
+      Chunk => TransportMessage::Chunk(EncodedChunkMessage(
+        with_channel(contents)?,
+      )),
+      Ping => TransportMessage::Ping(EncodedPingMessage::from_bytes(
+        contents,
+      )),
+      Pong => TransportMessage::Pong(EncodedPingMessage::from_bytes(
+        contents,
+      )),
+      File => TransportMessage::File(EncodedFileMessage(
+        with_channel(contents)?,
+      )),
+      Event => TransportMessage::Event(EncodedEventMessage(
+        EncodedJsonMessage::from_bytes(contents),
+      )),
+    };
     Ok(message)
   }
 }
 
 impl TransportMessageVariant {
+  /// The number of variants, bytes `0..COUNT` are valid.
+  pub const COUNT: usize = 10;
+
   pub fn from_byte(byte: u8) -> anyhow::Result<Self> {
     use TransportMessageVariant::*;
     let variant = match byte {
@@ -83,6 +204,12 @@ impl TransportMessageVariant {
       1 => Request,
       2 => Response,
       3 => Terminal,
+      4 => Notice,
+      5 => Chunk,
+      6 => Ping,
+      7 => Pong,
+      8 => File,
+      9 => Event,
       other => {
         return Err(anyhow!(
           "Got unrecognized MessageVariant byte: {other}"
@@ -92,6 +219,13 @@ impl TransportMessageVariant {
     Ok(variant)
   }
 
+  /// Whether the variant is sent on a channel,
+  /// which is carried in the [TransportHeader].
+  pub fn has_channel(self) -> bool {
+    use TransportMessageVariant::*;
+    matches!(self, Request | Response | Terminal | Chunk | File)
+  }
+
   pub fn as_byte(self) -> u8 {
     use TransportMessageVariant::*;
     match self {
@@ -99,6 +233,12 @@ impl TransportMessageVariant {
       Request => 1,
       Response => 2,
       Terminal => 3,
+      Notice => 4,
+      Chunk => 5,
+      Ping => 6,
+      Pong => 7,
+      File => 8,
+      Event => 9,
     }
   }
 }
@@ -107,12 +247,18 @@ impl TransportMessageVariant {
 //  REQUEST MESSAGE
 // =================
 
+/// The contents, and the idempotency key from the header.
 #[derive(Debug)]
-pub struct EncodedRequestMessage(EncodedChannel<EncodedJsonMessage>);
-
-impl_cast_bytes_vec!(EncodedRequestMessage, EncodedChannel);
+pub struct EncodedRequestMessage(
+  WithChannel<EncodedJsonMessage>,
+  Option<Uuid>,
+);
 
-pub struct RequestMessage(WithChannel<EncodedJsonMessage>);
+#[derive(Debug, Clone)]
+pub struct RequestMessage(
+  WithChannel<EncodedJsonMessage>,
+  Option<Uuid>,
+);
 
 impl RequestMessage {
   pub fn map_decode<T: DeserializeOwned>(
@@ -124,23 +270,75 @@ impl RequestMessage {
 
 impl RequestMessage {
   pub fn new(channel: Uuid, json: EncodedJsonMessage) -> Self {
-    Self(WithChannel {
-      channel,
-      data: json,
-    })
+    Self(
+      WithChannel {
+        channel,
+        data: json,
+      },
+      None,
+    )
+  }
+
+  /// Periphery runs requests with the same idempotency key once,
+  /// and answers retransmissions with the result of the first.
+  pub fn with_idempotency_key(mut self, key: Uuid) -> Self {
+    self.1 = Some(key);
+    self
+  }
+
+  pub fn idempotency_key(&self) -> Option<Uuid> {
+    self.1
+  }
+
+  pub fn into_inner(self) -> WithChannel<EncodedJsonMessage> {
+    self.0
+  }
+
+  pub fn is_encrypted(&self) -> bool {
+    EncodedBodyMessage::from(self.0.data.clone()).is_encrypted()
+  }
+
+  /// Encrypts the request body with the session key,
+  /// bound to the request channel.
+  pub fn encrypt(self, key: &[u8; 32]) -> anyhow::Result<Self> {
+    let WithChannel { channel, data } = self.0;
+    let data =
+      EncodedBodyMessage::from(data).encrypt(key, channel)?;
+    Ok(Self(
+      WithChannel {
+        channel,
+        data: EncodedJsonMessage::from_bytes(data.into_bytes()),
+      },
+      self.1,
+    ))
+  }
+
+  /// Requests which aren't encrypted are returned unchanged.
+  pub fn decrypt(self, key: &[u8; 32]) -> anyhow::Result<Self> {
+    let WithChannel { channel, data } = self.0;
+    let data = EncodedBodyMessage::from(data)
+      .decrypt(key, channel)
+      .context("Failed to decrypt Request body")?;
+    Ok(Self(
+      WithChannel {
+        channel,
+        data: EncodedJsonMessage::from_bytes(data.into_bytes()),
+      },
+      self.1,
+    ))
   }
 }
 
 impl Encode<EncodedTransportMessage> for RequestMessage {
   fn encode(self) -> EncodedTransportMessage {
-    TransportMessage::Request(EncodedRequestMessage(self.0.encode()))
+    TransportMessage::Request(EncodedRequestMessage(self.0, self.1))
       .encode()
   }
 }
 
 impl Decode<RequestMessage> for EncodedRequestMessage {
   fn decode(self) -> anyhow::Result<RequestMessage> {
-    self.0.decode().map(RequestMessage)
+    Ok(RequestMessage(self.0, self.1))
   }
 }
 
@@ -150,24 +348,17 @@ impl Decode<RequestMessage> for EncodedRequestMessage {
 
 #[derive(Debug)]
 pub struct EncodedResponseMessage(
-  EncodedChannel<EncodedResponse<EncodedJsonMessage>>,
+  WithChannel<EncodedResponse<EncodedBodyMessage>>,
 );
 
-impl_from_for_wrapper!(
-  EncodedResponseMessage,
-  EncodedChannel<EncodedResponse<EncodedJsonMessage>>
-);
-
-impl_cast_bytes_vec!(EncodedResponseMessage, EncodedChannel);
-
 pub struct ResponseMessage(
-  WithChannel<EncodedResponse<EncodedJsonMessage>>,
+  WithChannel<EncodedResponse<EncodedBodyMessage>>,
 );
 
 impl ResponseMessage {
   pub fn new(
     channel: Uuid,
-    response: EncodedResponse<EncodedJsonMessage>,
+    response: EncodedResponse<EncodedBodyMessage>,
   ) -> Self {
     Self(WithChannel {
       channel,
@@ -177,23 +368,21 @@ impl ResponseMessage {
 
   pub fn into_inner(
     self,
-  ) -> WithChannel<EncodedResponse<EncodedJsonMessage>> {
+  ) -> WithChannel<EncodedResponse<EncodedBodyMessage>> {
     self.0
   }
 }
 
 impl Encode<EncodedTransportMessage> for ResponseMessage {
   fn encode(self) -> EncodedTransportMessage {
-    TransportMessage::Response(EncodedResponseMessage(
-      self.0.encode(),
-    ))
-    .encode()
+    TransportMessage::Response(EncodedResponseMessage(self.0))
+      .encode()
   }
 }
 
 impl Decode<ResponseMessage> for EncodedResponseMessage {
   fn decode(self) -> anyhow::Result<ResponseMessage> {
-    self.0.decode().map(ResponseMessage)
+    Ok(ResponseMessage(self.0))
   }
 }
 
@@ -202,10 +391,10 @@ impl Decode<ResponseMessage> for EncodedResponseMessage {
 // ==================
 
 #[derive(Debug)]
-pub struct EncodedTerminalMessage(EncodedChannel<Vec<u8>>);
+pub struct EncodedTerminalMessage(WithChannel<Bytes>);
 
 impl TerminalMessage {
-  pub fn new(channel: Uuid, bytes: Vec<u8>) -> Self {
+  pub fn new(channel: Uuid, bytes: Bytes) -> Self {
     Self(WithChannel {
       channel,
       data: bytes,
@@ -213,21 +402,96 @@ impl TerminalMessage {
   }
 }
 
-impl_cast_bytes_vec!(EncodedTerminalMessage, EncodedChannel);
-
-pub struct TerminalMessage(WithChannel<Vec<u8>>);
+pub struct TerminalMessage(WithChannel<Bytes>);
 
 impl Encode<EncodedTransportMessage> for TerminalMessage {
   fn encode(self) -> EncodedTransportMessage {
-    TransportMessage::Terminal(EncodedTerminalMessage(
-      self.0.encode(),
-    ))
-    .encode()
+    TransportMessage::Terminal(EncodedTerminalMessage(self.0))
+      .encode()
+  }
+}
+
+impl Decode<WithChannel<Bytes>> for EncodedTerminalMessage {
+  fn decode(self) -> anyhow::Result<WithChannel<Bytes>> {
+    Ok(self.0)
+  }
+}
+
+// ================
+//  NOTICE MESSAGE
+// ================
+
+/// Connection level notices which aren't tied to a request channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "params")]
+pub enum TransportNotice {
+  /// Core is shutting down and will close the connection.
+  /// The other side should wait `retry_after_seconds`
+  /// before attempting to reconnect.
+  CoreShutdown { retry_after_seconds: u64 },
+  /// Periphery is about to reboot or power off the host,
+  /// and the connection will be lost until it is back up.
+  PeripheryDraining { action: HostPowerAction },
+}
+
+#[derive(Debug)]
+pub struct EncodedNoticeMessage(EncodedJsonMessage);
+
+impl_cast_bytes_vec!(EncodedNoticeMessage, EncodedJsonMessage);
+
+impl Encode<anyhow::Result<EncodedTransportMessage>>
+  for &TransportNotice
+{
+  fn encode(self) -> anyhow::Result<EncodedTransportMessage> {
+    let json = JsonMessage(self).encode()?;
+    Ok(TransportMessage::Notice(EncodedNoticeMessage(json)).encode())
+  }
+}
+
+impl Decode<TransportNotice> for EncodedNoticeMessage {
+  fn decode(self) -> anyhow::Result<TransportNotice> {
+    self.0.decode()
+  }
+}
+
+// ===============
+//  EVENT MESSAGE
+// ===============
+
+/// Pushed by Periphery as things happen on the host,
+/// without Core requesting them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "params")]
+pub enum PeripheryEvent {
+  /// A container was created, started, stopped, paused or unpaused.
+  ContainerStateChanged {
+    container: String,
+    state: ContainerStateStatusEnum,
+  },
+  /// A container was killed for running out of memory.
+  ContainerOomKilled { container: String },
+  /// A schedule in `PeripheryConfig.schedules` or added through
+  /// `UpsertSchedule` finished running. Runs while no Core
+  /// was connected are sent once one connects.
+  ScheduleRun { name: String, log: Log },
+}
+
+#[derive(Debug)]
+pub struct EncodedEventMessage(EncodedJsonMessage);
+
+impl_cast_bytes_vec!(EncodedEventMessage, EncodedJsonMessage);
+
+impl Encode<anyhow::Result<EncodedTransportMessage>>
+  for &PeripheryEvent
+{
+  fn encode(self) -> anyhow::Result<EncodedTransportMessage> {
+    let json = JsonMessage(self).encode()?;
+    Ok(TransportMessage::Event(EncodedEventMessage(json)).encode())
   }
 }
 
-impl Decode<WithChannel<Vec<u8>>> for EncodedTerminalMessage {
-  fn decode(self) -> anyhow::Result<WithChannel<Vec<u8>>> {
-    self.0.decode_map()
+impl Decode<PeripheryEvent> for EncodedEventMessage {
+  fn decode(self) -> anyhow::Result<PeripheryEvent> {
+    self.0.decode()
   }
 }
@@ -0,0 +1,54 @@
+use anyhow::anyhow;
+use bytes::Bytes;
+use encoding::{CastBytes, Decode, Encode, impl_cast_bytes_vec};
+
+use crate::transport::{EncodedTransportMessage, TransportMessage};
+
+/// Application level keepalive, used by Ping and Pong messages.
+/// The receiver of a Ping replies with a Pong carrying the same bytes,
+/// so the sender measures round trip latency using only its own clock.
+///
+/// ```markdown
+/// | ------ i64 ------ |
+/// | Sent at (unix ms) |
+/// ```
+#[derive(Debug)]
+pub struct EncodedPingMessage(Vec<u8>);
+
+impl_cast_bytes_vec!(EncodedPingMessage, Vec);
+
+pub struct PingMessage {
+  /// Unix timestamp in ms when the Ping was sent.
+  pub sent_at: i64,
+}
+
+impl Encode<EncodedTransportMessage> for PingMessage {
+  fn encode(self) -> EncodedTransportMessage {
+    TransportMessage::Ping(EncodedPingMessage(
+      self.sent_at.to_be_bytes().to_vec(),
+    ))
+    .encode()
+  }
+}
+
+impl Decode<PingMessage> for EncodedPingMessage {
+  fn decode(self) -> anyhow::Result<PingMessage> {
+    let bytes: [u8; 8] =
+      self.0.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow!(
+          "Ping message must be 8 bytes, got {} bytes",
+          bytes.len()
+        )
+      })?;
+    Ok(PingMessage {
+      sent_at: i64::from_be_bytes(bytes),
+    })
+  }
+}
+
+impl EncodedPingMessage {
+  /// The Pong reply to this Ping, echoing the same bytes.
+  pub fn into_pong(self) -> EncodedTransportMessage {
+    TransportMessage::Pong(self).encode()
+  }
+}
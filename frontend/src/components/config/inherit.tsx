@@ -0,0 +1,135 @@
+import { ResourceLink, ResourceSelector } from "@components/resources/common";
+import { ColorIntention, text_color_class_by_intention } from "@lib/color";
+import { useRead } from "@lib/hooks";
+import { DataTable } from "@ui/data-table";
+import { Types } from "komodo_client";
+import { ConfigItem } from "./util";
+
+export const InheritConfig = ({
+  type,
+  id,
+  inherit,
+  set,
+  disabled,
+}: {
+  type: "Deployment" | "Stack";
+  id: string;
+  inherit: string | undefined;
+  set: (update: { inherit: string }) => void;
+  disabled: boolean;
+}) => {
+  return (
+    <ConfigItem
+      label={
+        inherit ? (
+          <div className="flex gap-3 text-lg font-bold">
+            Template:
+            <ResourceLink type={type} id={inherit} />
+          </div>
+        ) : (
+          "Select Template"
+        )
+      }
+      description={`Inherit base config from a template ${type}. Values configured on this ${type} override the template by key.`}
+    >
+      <div className="flex flex-col gap-4">
+        <ResourceSelector
+          type={type}
+          selected={inherit}
+          onSelect={(inherit) => set({ inherit })}
+          templates={Types.TemplatesQueryBehavior.Only}
+          placeholder="Select Template"
+          disabled={disabled}
+          align="start"
+        />
+        {inherit && <InheritanceTable type={type} id={id} />}
+      </div>
+    </ConfigItem>
+  );
+};
+
+/** Shows the effective values after inheritance, using the saved config. */
+const InheritanceTable = ({
+  type,
+  id,
+}: {
+  type: "Deployment" | "Stack";
+  id: string;
+}) => {
+  const deployment = useRead(
+    "GetDeploymentInheritance",
+    { deployment: id },
+    { enabled: type === "Deployment" }
+  ).data;
+  const stack = useRead(
+    "GetStackInheritance",
+    { stack: id },
+    { enabled: type === "Stack" }
+  ).data;
+  const inheritance = type === "Deployment" ? deployment : stack;
+
+  if (!inheritance?.template) return null;
+
+  const values = [
+    ...inheritance.environment.map((value) => ({ kind: "Env", ...value })),
+    ...inheritance.labels.map((value) => ({ kind: "Label", ...value })),
+    ...(inheritance.restart
+      ? [{ kind: "Restart", ...inheritance.restart }]
+      : []),
+  ];
+
+  return (
+    <DataTable
+      tableKey={`${type}-inheritance`}
+      data={values}
+      columns={[
+        { header: "Kind", accessorKey: "kind" },
+        { header: "Key", accessorKey: "key" },
+        {
+          header: "Value",
+          cell: ({ row }) => (
+            <div className="font-mono">{row.original.value}</div>
+          ),
+        },
+        {
+          header: "Template",
+          cell: ({ row }) =>
+            row.original.template_value !== undefined && (
+              <div
+                className={
+                  row.original.source === Types.InheritedSource.Override
+                    ? "font-mono line-through text-muted-foreground"
+                    : "font-mono"
+                }
+              >
+                {row.original.template_value}
+              </div>
+            ),
+        },
+        {
+          header: "Source",
+          cell: ({ row }) => (
+            <div
+              className={text_color_class_by_intention(
+                source_intention(row.original.source)
+              )}
+            >
+              {row.original.source}
+            </div>
+          ),
+        },
+      ]}
+    />
+  );
+};
+
+const source_intention = (source: Types.InheritedSource): ColorIntention => {
+  switch (source) {
+    case Types.InheritedSource.Template:
+      return "Neutral";
+    case Types.InheritedSource.Override:
+      return "Warning";
+    default:
+      return "None";
+  }
+};
@@ -78,6 +78,7 @@ const ExportAllLoader = ({
     include_resources: true,
     include_variables: true,
     include_user_groups: true,
+    include_onboarding_keys: true,
   });
   return <ExportPre loading={isPending} content={data?.toml} />;
 };
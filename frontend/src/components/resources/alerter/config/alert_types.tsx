@@ -11,6 +11,10 @@ const ALERT_TYPES: Types.AlertData["type"][] = [
   "ServerCpu",
   "ServerMem",
   "ServerDisk",
+  "ServerDiskHealth",
+  "ServerZfsPool",
+  "ServerGpu",
+  "ServerOsSecurityUpdates",
   // Stack
   "StackStateChange",
   "StackImageUpdateAvailable",
@@ -19,6 +23,7 @@ const ALERT_TYPES: Types.AlertData["type"][] = [
   "ContainerStateChange",
   "DeploymentImageUpdateAvailable",
   "DeploymentAutoUpdated",
+  "DeploymentUnhealthy",
   // Misc
   "ScheduleRun",
   "BuildFailed",
@@ -28,6 +33,7 @@ const ALERT_TYPES: Types.AlertData["type"][] = [
   "ProcedureFailed",
   "AwsBuilderTerminationFailed",
   "Custom",
+  "Alertmanager",
 ];
 
 export const AlertTypeConfig = ({
@@ -442,6 +442,40 @@ export const BuildConfig = ({
         ),
       },
     },
+    {
+      label: "Platforms",
+      labelHidden: true,
+      components: {
+        platforms: (value, set) => (
+          <ConfigItem
+            label="Platforms"
+            boldLabel
+            description="Build a multi platform image with buildx. Leave empty to build for the builder platform."
+          >
+            <InputList
+              field="platforms"
+              values={value ?? []}
+              set={set}
+              disabled={disabled}
+              placeholder="linux/arm64"
+            />
+          </ConfigItem>
+        ),
+      },
+    },
+    {
+      label: "Build Cache",
+      description:
+        "Share the build cache through a registry image, ie ghcr.io/org/app:buildcache. Full buildx cache specs are passed through.",
+      components: {
+        cache_from: {
+          placeholder: "Import cache from registry image",
+        },
+        cache_to: {
+          placeholder: "Export cache to registry image",
+        },
+      },
+    },
     {
       label: "Labels",
       description: "Attach --labels to image.",
@@ -7,6 +7,7 @@ import {
   RefreshCcw,
   Square,
   Download,
+  Undo2,
 } from "lucide-react";
 import { useExecute, useRead } from "@lib/hooks";
 import { useEffect, useState } from "react";
@@ -92,6 +93,36 @@ export const DeployDeployment = ({ id }: DeploymentId) => {
   }
 };
 
+export const RollbackDeployment = ({ id }: DeploymentId) => {
+  const deployment = useRead("GetDeployment", { deployment: id }).data;
+  const { mutate, isPending } = useExecute("RollbackDeployment");
+
+  const deployments = useRead("ListDeployments", {}).data;
+  const state = deployments?.find((d) => d.id === id)?.info.state;
+
+  const rolling_back = useRead(
+    "GetDeploymentActionState",
+    { deployment: id },
+    { refetchInterval: 5_000 }
+  ).data?.rolling_back;
+
+  const pending = isPending || rolling_back;
+
+  if (!deployment) return null;
+  if (state === Types.DeploymentState.NotDeployed) return null;
+
+  return (
+    <ActionWithDialog
+      name={deployment.name}
+      title="Rollback"
+      icon={<Undo2 className="h-4 w-4" />}
+      onClick={() => mutate({ deployment: id })}
+      disabled={pending}
+      loading={pending}
+    />
+  );
+};
+
 export const DestroyDeployment = ({ id }: DeploymentId) => {
   const deployment = useRead("GetDeployment", { deployment: id }).data;
   const [signal, setSignal] = useState<Types.TerminationSignal>();
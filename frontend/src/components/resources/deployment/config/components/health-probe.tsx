@@ -0,0 +1,135 @@
+import { ConfigItem } from "@components/config/util";
+import { Types } from "komodo_client";
+import { Input } from "@ui/input";
+import {
+  Select,
+  SelectContent,
+  SelectGroup,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@ui/select";
+
+const PROBE_TYPES: Types.DeploymentHealthProbe["type"][] = [
+  "None",
+  "Http",
+  "Tcp",
+  "Command",
+];
+
+const default_probe = (
+  type: Types.DeploymentHealthProbe["type"]
+): Types.DeploymentHealthProbe => {
+  switch (type) {
+    case "Http":
+      return { type, params: { url: "" } };
+    case "Tcp":
+      return { type, params: { address: "" } };
+    case "Command":
+      return { type, params: { command: "" } };
+    default:
+      return { type: "None" };
+  }
+};
+
+export const HealthProbeConfig = ({
+  probe,
+  set,
+  disabled,
+}: {
+  probe?: Types.DeploymentHealthProbe;
+  set: (input: Partial<Types.DeploymentConfig>) => void;
+  disabled: boolean;
+}) => {
+  const type = probe?.type ?? "None";
+  return (
+    <>
+      <ConfigItem
+        label="Probe Type"
+        description="Http and Tcp probes are made from the Periphery host. Commands run inside the container."
+      >
+        <Select
+          value={type}
+          onValueChange={(type) =>
+            set({
+              health_probe: default_probe(
+                type as Types.DeploymentHealthProbe["type"]
+              ),
+            })
+          }
+          disabled={disabled}
+        >
+          <SelectTrigger className="w-[200px]" disabled={disabled}>
+            <SelectValue placeholder="Select Type" />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectGroup>
+              {PROBE_TYPES.map((type) => (
+                <SelectItem key={type} value={type} className="cursor-pointer">
+                  {type}
+                </SelectItem>
+              ))}
+            </SelectGroup>
+          </SelectContent>
+        </Select>
+      </ConfigItem>
+      {probe?.type === "Http" && (
+        <ConfigItem label="Url" description="Healthy on any 2xx or 3xx status.">
+          <Input
+            className="w-[400px] max-w-full"
+            placeholder="http://localhost:8080/health"
+            value={probe.params.url}
+            onChange={(e) =>
+              set({
+                health_probe: { type: "Http", params: { url: e.target.value } },
+              })
+            }
+            disabled={disabled}
+          />
+        </ConfigItem>
+      )}
+      {probe?.type === "Tcp" && (
+        <ConfigItem
+          label="Address"
+          description="Healthy if a TCP connection can be opened."
+        >
+          <Input
+            className="w-[400px] max-w-full"
+            placeholder="localhost:5432"
+            value={probe.params.address}
+            onChange={(e) =>
+              set({
+                health_probe: {
+                  type: "Tcp",
+                  params: { address: e.target.value },
+                },
+              })
+            }
+            disabled={disabled}
+          />
+        </ConfigItem>
+      )}
+      {probe?.type === "Command" && (
+        <ConfigItem
+          label="Command"
+          description="Run with 'docker exec'. Healthy on exit code 0."
+        >
+          <Input
+            className="w-[400px] max-w-full"
+            placeholder="pg_isready -U postgres"
+            value={probe.params.command}
+            onChange={(e) =>
+              set({
+                health_probe: {
+                  type: "Command",
+                  params: { command: e.target.value },
+                },
+              })
+            }
+            disabled={disabled}
+          />
+        </ConfigItem>
+      )}
+    </>
+  );
+};
@@ -16,12 +16,14 @@ import { Config } from "@components/config";
 import { ResourceLink, ResourceSelector } from "@components/resources/common";
 import { Link } from "react-router-dom";
 import { SecretsSearch } from "@components/config/env_vars";
+import { InheritConfig } from "@components/config/inherit";
 import { MonacoEditor } from "@components/monaco";
 import {
   DefaultTerminationSignal,
   TerminationTimeout,
 } from "./components/term-signal";
 import { extract_registry_domain } from "@lib/utils";
+import { HealthProbeConfig } from "./components/health-probe";
 
 export const DeploymentConfig = ({
   id,
@@ -90,6 +92,15 @@ export const DeploymentConfig = ({
                   </ConfigItem>
                 );
               },
+              inherit: (inherit, set) => (
+                <InheritConfig
+                  type="Deployment"
+                  id={id}
+                  inherit={inherit}
+                  set={set}
+                  disabled={disabled}
+                />
+              ),
             },
           },
           {
@@ -342,6 +353,46 @@ export const DeploymentConfig = ({
               ),
             },
           },
+          {
+            label: "Health Probe",
+            description:
+              "Probe the Deployment after deploy and on every monitoring interval, independent of any Docker HEALTHCHECK. Failures open a DeploymentUnhealthy alert.",
+            components: {
+              health_probe: (value, set) => (
+                <HealthProbeConfig
+                  probe={value}
+                  set={set}
+                  disabled={disabled}
+                />
+              ),
+              health_probe_timeout: {
+                description:
+                  "Seconds before a single probe attempt counts as failed.",
+              },
+              health_probe_start_period: {
+                description:
+                  "Seconds after deploy to retry the probe before the deploy reports the Deployment as unhealthy.",
+              },
+            },
+          },
+          {
+            label: "Healthcheck",
+            description:
+              "Wait for the container HEALTHCHECK to report healthy after deploy. The deploy fails if the container is unhealthy, exits, or restarts. Containers without a HEALTHCHECK only need to keep running.",
+            components: {
+              wait_for_healthcheck: {
+                label: "Wait For Healthcheck",
+              },
+              healthcheck_timeout: {
+                description:
+                  "Seconds to wait for the container to become healthy.",
+              },
+              rollback_on_failure: {
+                description:
+                  "If the deploy fails, or the container fails its healthcheck, re-deploy the image of the previous successful deploy.",
+              },
+            },
+          },
           {
             label: "Termination",
             description:
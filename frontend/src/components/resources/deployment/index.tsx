@@ -8,6 +8,7 @@ import {
   DeployDeployment,
   StartStopDeployment,
   DestroyDeployment,
+  RollbackDeployment,
   RestartDeployment,
   PauseUnpauseDeployment,
   PullDeployment,
@@ -354,6 +355,7 @@ export const DeploymentComponents: RequiredResourceComponents = {
     RestartDeployment,
     PauseUnpauseDeployment,
     StartStopDeployment,
+    RollbackDeployment,
     DestroyDeployment,
   },
 
@@ -12,6 +12,7 @@ import { Button } from "@ui/button";
 import {
   ConfigItem,
   ConfigSwitch,
+  InputList,
   WebhookBuilder,
 } from "@components/config/util";
 import { Input } from "@ui/input";
@@ -90,6 +91,7 @@ type MinExecutionType = Exclude<
   | "UnpauseContainer"
   | "StopContainer"
   | "DestroyContainer"
+  | "CommitContainer"
   | "DeleteNetwork"
   | "DeleteImage"
   | "DeleteVolume"
@@ -359,6 +361,54 @@ export const ProcedureConfig = ({ id }: { id: string }) => {
                   </ConfigItem>
                 );
               },
+              schedule_exceptions: (values, set) => (
+                <ConfigItem
+                  label="Exceptions"
+                  description="Skip scheduled runs on these dates, eg. holidays. Use 'YYYY-MM-DD', or 'MM-DD' to skip every year."
+                >
+                  <InputList
+                    field="schedule_exceptions"
+                    values={values ?? []}
+                    set={set}
+                    disabled={disabled}
+                    placeholder="12-25"
+                  />
+                </ConfigItem>
+              ),
+              schedule_overlap: (schedule_overlap, set) => (
+                <ConfigItem
+                  label="Overlap"
+                  description="What to do when a scheduled run comes up while the previous run is still in progress."
+                >
+                  <Select
+                    value={schedule_overlap}
+                    onValueChange={(schedule_overlap) =>
+                      set({
+                        schedule_overlap:
+                          schedule_overlap as Types.ScheduleOverlapPolicy,
+                      })
+                    }
+                    disabled={disabled}
+                  >
+                    <SelectTrigger className="w-[200px]" disabled={disabled}>
+                      <SelectValue placeholder="Select Policy" />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {Object.values(Types.ScheduleOverlapPolicy).map(
+                        (policy) => (
+                          <SelectItem
+                            key={policy}
+                            value={policy}
+                            className="cursor-pointer"
+                          >
+                            {fmt_upper_camelcase(policy)}
+                          </SelectItem>
+                        )
+                      )}
+                    </SelectContent>
+                  </Select>
+                </ConfigItem>
+              ),
               schedule_alert: {
                 description: "Send an alert when the scheduled run occurs",
               },
@@ -946,6 +996,17 @@ const TARGET_COMPONENTS: ExecutionConfigs = {
       />
     ),
   },
+  RollbackDeployment: {
+    params: { deployment: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <ResourceSelector
+        type="Deployment"
+        selected={params.deployment}
+        onSelect={(deployment) => setParams({ deployment })}
+        disabled={disabled}
+      />
+    ),
+  },
   BatchDestroyDeployment: {
     params: { pattern: "" },
     Component: ({ params, setParams, disabled }) => (
@@ -1355,6 +1416,81 @@ const TARGET_COMPONENTS: ExecutionConfigs = {
       );
     },
   },
+  ScaleStack: {
+    params: { stack: "", service: "", replicas: 1 },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Stack"
+          selected={params.stack}
+          onSelect={(stack) => setParams({ ...params, stack })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="service"
+          value={params.service}
+          onChange={(e) => setParams({ ...params, service: e.target.value })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="replicas"
+          type="number"
+          className="w-[100px]"
+          value={params.replicas}
+          onChange={(e) =>
+            setParams({ ...params, replicas: Number(e.target.value) || 0 })
+          }
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
+  PromoteStack: {
+    params: { from: "", to: "", dry_run: false },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Stack"
+          selected={params.from}
+          onSelect={(from) => setParams({ ...params, from })}
+          disabled={disabled}
+        />
+        <div className="text-muted-foreground">to</div>
+        <ResourceSelector
+          type="Stack"
+          selected={params.to}
+          onSelect={(to) => setParams({ ...params, to })}
+          disabled={disabled}
+        />
+        <div className="text-muted-foreground">Dry Run</div>
+        <Switch
+          checked={params.dry_run}
+          onCheckedChange={(dry_run) => setParams({ ...params, dry_run })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
+  MigrateStack: {
+    params: { stack: "", to_server: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Stack"
+          selected={params.stack}
+          onSelect={(stack) => setParams({ ...params, stack })}
+          disabled={disabled}
+        />
+        <div className="text-muted-foreground">to</div>
+        <ResourceSelector
+          type="Server"
+          selected={params.to_server}
+          onSelect={(to_server) => setParams({ ...params, to_server })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
   // Repo
   CloneRepo: {
     params: { repo: "" },
@@ -1603,6 +1739,32 @@ const TARGET_COMPONENTS: ExecutionConfigs = {
       />
     ),
   },
+  TransferImage: {
+    params: { from_server: "", to_server: "", image: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <Input
+          placeholder="image"
+          value={params.image}
+          onChange={(e) => setParams({ ...params, image: e.target.value })}
+          disabled={disabled}
+        />
+        <ResourceSelector
+          type="Server"
+          selected={params.from_server}
+          onSelect={(from_server) => setParams({ ...params, from_server })}
+          disabled={disabled}
+        />
+        <div className="text-muted-foreground">to</div>
+        <ResourceSelector
+          type="Server"
+          selected={params.to_server}
+          onSelect={(to_server) => setParams({ ...params, to_server })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
   PruneVolumes: {
     params: { server: "" },
     Component: ({ params, setParams, disabled }) => (
@@ -1636,6 +1798,27 @@ const TARGET_COMPONENTS: ExecutionConfigs = {
       />
     ),
   },
+  PruneBuildCache: {
+    params: { server: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Server"
+          selected={params.server}
+          onSelect={(server) => setParams({ ...params, server })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="keep last (ie 24h)"
+          value={params.keep_last ?? ""}
+          onChange={(e) =>
+            setParams({ ...params, keep_last: e.target.value || undefined })
+          }
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
   PruneSystem: {
     params: { server: "" },
     Component: ({ params, setParams, disabled }) => (
@@ -1647,6 +1830,173 @@ const TARGET_COMPONENTS: ExecutionConfigs = {
       />
     ),
   },
+  RunServerExtension: {
+    params: { server: "", extension: "", command: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Server"
+          selected={params.server}
+          onSelect={(server) => setParams({ ...params, server })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="extension"
+          value={params.extension}
+          onChange={(e) => setParams({ ...params, extension: e.target.value })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="command"
+          value={params.command}
+          onChange={(e) => setParams({ ...params, command: e.target.value })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
+  KillSystemProcess: {
+    params: { server: "", pid: 0 },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Server"
+          selected={params.server}
+          onSelect={(server) => setParams({ ...params, server })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="pid"
+          type="number"
+          value={params.pid}
+          onChange={(e) => setParams({ ...params, pid: Number(e.target.value) })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
+  SetSystemProcessPriority: {
+    params: { server: "", pid: 0, nice: 0 },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Server"
+          selected={params.server}
+          onSelect={(server) => setParams({ ...params, server })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="pid"
+          type="number"
+          value={params.pid}
+          onChange={(e) => setParams({ ...params, pid: Number(e.target.value) })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="nice"
+          type="number"
+          value={params.nice}
+          onChange={(e) => setParams({ ...params, nice: Number(e.target.value) })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
+  StartSystemdUnit: {
+    params: { server: "", unit: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Server"
+          selected={params.server}
+          onSelect={(server) => setParams({ ...params, server })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="unit"
+          value={params.unit}
+          onChange={(e) => setParams({ ...params, unit: e.target.value })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
+  StopSystemdUnit: {
+    params: { server: "", unit: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Server"
+          selected={params.server}
+          onSelect={(server) => setParams({ ...params, server })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="unit"
+          value={params.unit}
+          onChange={(e) => setParams({ ...params, unit: e.target.value })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
+  RestartSystemdUnit: {
+    params: { server: "", unit: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Server"
+          selected={params.server}
+          onSelect={(server) => setParams({ ...params, server })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="unit"
+          value={params.unit}
+          onChange={(e) => setParams({ ...params, unit: e.target.value })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
+  RebootServer: {
+    params: { server: "", confirm: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Server"
+          selected={params.server}
+          onSelect={(server) => setParams({ ...params, server })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="confirm server name"
+          value={params.confirm}
+          onChange={(e) => setParams({ ...params, confirm: e.target.value })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
+  ShutdownServer: {
+    params: { server: "", confirm: "" },
+    Component: ({ params, setParams, disabled }) => (
+      <div className="flex gap-2 items-center">
+        <ResourceSelector
+          type="Server"
+          selected={params.server}
+          onSelect={(server) => setParams({ ...params, server })}
+          disabled={disabled}
+        />
+        <Input
+          placeholder="confirm server name"
+          value={params.confirm}
+          onChange={(e) => setParams({ ...params, confirm: e.target.value })}
+          disabled={disabled}
+        />
+      </div>
+    ),
+  },
   RunSync: {
     params: { sync: "" },
     Component: ({ params, setParams, disabled }) => (
@@ -194,6 +194,11 @@ export const ResourceSyncConfig = ({
         label: "Sync User Groups",
         description: "Include user groups in the sync.",
       },
+      include_onboarding_keys: {
+        label: "Sync Onboarding Keys",
+        description:
+          "Include Server onboarding key metadata in the sync. Keys are matched by public key, and must be created on Core first.",
+      },
     },
   };
 
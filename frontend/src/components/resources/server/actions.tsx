@@ -1,5 +1,6 @@
 import { ActionWithDialog, ConfirmButton } from "@components/util";
 import { useExecute, usePermissions, useRead } from "@lib/hooks";
+import { periphery_request_allowed } from "@lib/utils";
 import { Scissors } from "lucide-react";
 import { useServer } from ".";
 
@@ -37,6 +38,10 @@ export const Prune = ({
                 : "";
 
   const pending = isPending || action_state?.[pruningKey];
+  const disabled =
+    !canExecute ||
+    pending ||
+    !periphery_request_allowed(server.info, `Prune${type}`);
 
   if (type === "Images" || type === "Networks" || type === "Buildx") {
     return (
@@ -45,7 +50,7 @@ export const Prune = ({
         icon={<Scissors className="w-4 h-4" />}
         onClick={() => mutate({ server: server_id })}
         loading={pending}
-        disabled={!canExecute || pending}
+        disabled={disabled}
       />
     );
   } else {
@@ -56,7 +61,7 @@ export const Prune = ({
         icon={<Scissors className="w-4 h-4" />}
         onClick={() => mutate({ server: server_id })}
         loading={pending}
-        disabled={!canExecute || pending}
+        disabled={disabled}
       />
     );
   }
@@ -1,10 +1,22 @@
 import { Config } from "@components/config";
 import { MaintenanceWindows } from "@components/config/maintenance";
-import { ConfigInput, ConfigList } from "@components/config/util";
+import {
+  ConfigInput,
+  ConfigItem,
+  ConfigList,
+} from "@components/config/util";
 import { ConfirmButton } from "@components/util";
+import { fmt_upper_camelcase } from "@lib/formatting";
 import { useLocalStorage, usePermissions, useRead, useWrite } from "@lib/hooks";
 import { Types } from "komodo_client";
-import { RotateCcwKey, Save } from "lucide-react";
+import { Check, RotateCcwKey, Save } from "lucide-react";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@ui/select";
 import { ReactNode, useEffect, useState } from "react";
 import { useFullServer, useServer } from ".";
 
@@ -36,12 +48,18 @@ export const ServerConfig = ({
     useWrite("UpdateServerPublicKey");
   const { mutate: rotate, isPending: rotatePending } =
     useWrite("RotateServerKeys");
+  const { mutate: accept_tls_pin, isPending: acceptTlsPinPending } =
+    useWrite("AcceptServerTlsPin");
 
   if (!config) return null;
 
   const disabled = global_disabled || !canWrite;
   const address = update.address ?? config.address;
   const tls_address = !!address && !address.startsWith("ws://");
+  const tls_pinned =
+    (update.tls_verification ?? config.tls_verification) ===
+    Types.TlsVerification.Pinned;
+  const attempted_tls_pin = server?.info?.attempted_tls_pin;
 
   return (
     <Config
@@ -118,10 +136,75 @@ export const ServerConfig = ({
                   "For Core -> Periphery connection mode, specify address of periphery in your network.",
                 placeholder: "12.34.56.78:8120",
               },
-              insecure_tls: {
-                hidden: !tls_address,
-                description: "Skip Periphery TLS certificate validation.",
-              },
+              tls_verification: (tls_verification, set) =>
+                tls_address && (
+                  <ConfigItem
+                    label="TLS Verification"
+                    description="How to verify the Periphery TLS certificate. 'Insecure' skips verification, 'System' verifies against the system CA roots, and 'Pinned' only trusts the certificate pins below."
+                  >
+                    <Select
+                      value={tls_verification ?? Types.TlsVerification.Insecure}
+                      onValueChange={(tls_verification) =>
+                        set({
+                          tls_verification:
+                            tls_verification as Types.TlsVerification,
+                        })
+                      }
+                      disabled={disabled}
+                    >
+                      <SelectTrigger className="w-[200px]" disabled={disabled}>
+                        <SelectValue placeholder="Select Verification" />
+                      </SelectTrigger>
+                      <SelectContent>
+                        {Object.values(Types.TlsVerification).map((mode) => (
+                          <SelectItem
+                            key={mode}
+                            value={mode}
+                            className="cursor-pointer"
+                          >
+                            {fmt_upper_camelcase(mode)}
+                          </SelectItem>
+                        ))}
+                      </SelectContent>
+                    </Select>
+                  </ConfigItem>
+                ),
+              tls_pins: (values, set) =>
+                tls_address &&
+                tls_pinned && (
+                  <div className="flex flex-col gap-4">
+                    <ConfigList
+                      label="TLS Pins"
+                      description="The accepted Periphery certificate pins. If the certificate doesn't match, its pin is shown below to accept."
+                      field="tls_pins"
+                      values={values ?? []}
+                      set={set}
+                      disabled={disabled}
+                      placeholder="sha256/..."
+                    />
+                    {attempted_tls_pin && (
+                      <ConfigItem
+                        label="Attempted TLS Pin"
+                        description="Periphery presented a certificate not matching the pins. Accept if you trust it."
+                      >
+                        <div className="flex items-center gap-2">
+                          <div className="font-mono text-sm break-all">
+                            {attempted_tls_pin}
+                          </div>
+                          {!disabled && (
+                            <ConfirmButton
+                              title="Accept"
+                              icon={<Check className="w-4 h-4" />}
+                              className="max-w-[120px]"
+                              onClick={() => accept_tls_pin({ server: id })}
+                              loading={acceptTlsPinPending}
+                            />
+                          )}
+                        </div>
+                      </ConfigItem>
+                    )}
+                  </div>
+                ),
               external_address: {
                 description:
                   "Optional. The address of the server used in container links, if different than the Address.",
@@ -175,6 +258,35 @@ export const ServerConfig = ({
               },
             },
           },
+          {
+            label: "Capacity",
+            components: {
+              reserved_cpu: {
+                label: "Reserved CPU",
+                description:
+                  "The percentage of CPU to keep free for the host. Placement checks will not suggest using this portion.",
+              },
+              reserved_mem_gb: {
+                label: "Reserved Memory",
+                description:
+                  "The memory (in GB) to keep free for the host. Placement checks will not suggest using this portion.",
+              },
+              max_containers: {
+                description:
+                  "The maximum number of containers which should run on the Server. 0 means no limit.",
+              },
+            },
+          },
+          {
+            label: "Bandwidth",
+            components: {
+              transport_max_bytes_per_sec: {
+                label: "Max Bytes Per Second",
+                description:
+                  "Limit the bytes per second sent to and received from Periphery. 0 uses the Core default, negative disables the limit.",
+              },
+            },
+          },
         ],
         alerts: [
           {
@@ -258,6 +370,52 @@ export const ServerConfig = ({
                 description:
                   "Send a 'Critical' alert if the disk usage in % is above these thresholds",
               },
+              send_disk_health_alerts: {
+                label: "Send Disk Health Alerts",
+                description:
+                  "Send an alert if a physical disk reports SMART health problems. Requires 'smartctl' on the Periphery host.",
+              },
+              send_zfs_alerts: {
+                label: "Send ZFS Alerts",
+                description:
+                  "Send an alert if a ZFS pool is not ONLINE. 'Warning' when DEGRADED, 'Critical' when FAULTED or UNAVAIL.",
+              },
+              send_os_update_alerts: {
+                label: "Send OS Security Update Alerts",
+                description:
+                  "Send a 'Warning' alert if the host has pending security updates. Detected with apt or dnf.",
+              },
+            },
+          },
+          {
+            label: "GPU",
+            labelHidden: true,
+            components: {
+              send_gpu_alerts: {
+                label: "Send GPU Alerts",
+                description:
+                  "Send an alert if the memory usage or temperature of any NVIDIA / AMD GPU is above the configured thresholds.",
+              },
+              gpu_mem_warning: {
+                label: "GPU Memory Warning",
+                description:
+                  "Send a 'Warning' alert if the GPU memory usage in % is above these thresholds",
+              },
+              gpu_mem_critical: {
+                label: "GPU Memory Critical",
+                description:
+                  "Send a 'Critical' alert if the GPU memory usage in % is above these thresholds",
+              },
+              gpu_temp_warning: {
+                label: "GPU Temperature Warning",
+                description:
+                  "Send a 'Warning' alert if the GPU temperature in °C is above these thresholds",
+              },
+              gpu_temp_critical: {
+                label: "GPU Temperature Critical",
+                description:
+                  "Send a 'Critical' alert if the GPU temperature in °C is above these thresholds",
+              },
             },
           },
           {
@@ -5,7 +5,7 @@ import {
   useUser,
   useWrite,
 } from "@lib/hooks";
-import { cn } from "@lib/utils";
+import { cn, periphery_request_allowed } from "@lib/utils";
 import { Types } from "komodo_client";
 import { RequiredResourceComponents } from "@types";
 import {
@@ -21,6 +21,8 @@ import {
   CheckCircle2,
   KeyRound,
   Loader2,
+  Power,
+  RotateCcw,
 } from "lucide-react";
 import { Section } from "@components/layouts";
 import { Prune } from "./actions";
@@ -115,9 +117,12 @@ const ConfigTabs = ({ id }: { id: string }) => {
   const is_admin = useUser().data?.admin ?? false;
   const { canWrite } = usePermissions({ type: "Server", id });
   const server_info = useServer(id)?.info;
-  const terminals_disabled = server_info?.terminals_disabled ?? true;
+  const terminals_disabled =
+    (server_info?.terminals_disabled ?? true) ||
+    !periphery_request_allowed(server_info, "CreateTerminal");
   const container_terminals_disabled =
-    server_info?.container_terminals_disabled ?? true;
+    (server_info?.container_terminals_disabled ?? true) ||
+    !periphery_request_allowed(server_info, "ConnectContainerExec");
   const disable_non_admin_create =
     useRead("GetCoreInfo", {}).data?.disable_non_admin_create ?? true;
 
@@ -731,6 +736,50 @@ export const ServerComponents: RequiredResourceComponents = {
     },
     PruneBuildx: ({ id }) => <Prune server_id={id} type="Buildx" />,
     PruneSystem: ({ id }) => <Prune server_id={id} type="System" />,
+    Reboot: ({ id }) => {
+      const server = useServer(id);
+      const { mutate, isPending } = useExecute("RebootServer");
+      const { canExecute } = usePermissions({ type: "Server", id });
+      if (
+        !server ||
+        server.info.state !== Types.ServerState.Ok ||
+        !periphery_request_allowed(server.info, "RebootHost")
+      ) {
+        return null;
+      }
+      return (
+        <ActionWithDialog
+          name={server.name}
+          title="Reboot Host"
+          icon={<RotateCcw className="w-4 h-4" />}
+          onClick={() => mutate({ server: id, confirm: server.name })}
+          disabled={!canExecute || isPending}
+          loading={isPending}
+        />
+      );
+    },
+    Shutdown: ({ id }) => {
+      const server = useServer(id);
+      const { mutate, isPending } = useExecute("ShutdownServer");
+      const { canExecute } = usePermissions({ type: "Server", id });
+      if (
+        !server ||
+        server.info.state !== Types.ServerState.Ok ||
+        !periphery_request_allowed(server.info, "ShutdownHost")
+      ) {
+        return null;
+      }
+      return (
+        <ActionWithDialog
+          name={server.name}
+          title="Shutdown Host"
+          icon={<Power className="w-4 h-4" />}
+          onClick={() => mutate({ server: id, confirm: server.name })}
+          disabled={!canExecute || isPending}
+          loading={isPending}
+        />
+      );
+    },
   },
 
   Page: {},
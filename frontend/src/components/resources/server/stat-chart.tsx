@@ -11,6 +11,7 @@ import { fmt_utc_date } from "@lib/formatting";
 
 type StatType =
   | "Cpu"
+  | "Cpu Cores"
   | "Memory"
   | "Disk"
   | "Network Ingress"
@@ -68,6 +69,19 @@ export const StatChart = ({
         { label: "15m", data: fifteen },
       ];
     }
+    if (type === "Cpu Cores") {
+      const cores = Math.max(
+        0,
+        ...records.map((s) => s.cpu_cores?.length ?? 0)
+      );
+      return [...Array(cores).keys()].map((core) => ({
+        label: `Core ${core}`,
+        data: records.map((s) => ({
+          date: convertTsMsToLocalUnixTsInMs(s.ts),
+          value: s.cpu_cores?.[core] ?? 0,
+        })),
+      }));
+    }
     const single = records.map((stat) => ({
       date: convertTsMsToLocalUnixTsInMs(stat.ts),
       value: getStat(stat, type),
@@ -160,6 +174,9 @@ export const InnerStatChart = ({
         maxUnitValue: maxStatValue === 0 ? 1 : maxStatValue * 1.2,
       };
     }
+    if (type === "Cpu Cores") {
+      return { maxUnitValue: 100 };
+    }
     if (type === "Cpu") {
       return {
         maxUnitValue: Math.min(2 ** (Math.log2(maxStatValue) + 0.3), 100),
@@ -172,8 +189,9 @@ export const InnerStatChart = ({
     (): AxisOptions<StatDatapoint>[] => [
       {
         getValue: (datum) => datum.value,
-        elementType: type === "Load Average" ? "line" : "area",
-        stacked: type !== "Load Average",
+        elementType:
+          type === "Load Average" || type === "Cpu Cores" ? "line" : "area",
+        stacked: type !== "Load Average" && type !== "Cpu Cores",
         min: 0,
         max: maxUnitValue,
         formatters: {
@@ -214,7 +232,9 @@ export const InnerStatChart = ({
                 hex_color_by_intention("Neutral"),
                 hex_color_by_intention("Unknown"),
               ]
-            : [getColor(type)],
+            : type === "Cpu Cores"
+              ? undefined
+              : [getColor(type)],
         dark: currentTheme === "dark",
         padding: {
           left: 10,
@@ -1,8 +1,20 @@
 import { Section } from "@components/layouts";
 import { Card, CardContent, CardHeader, CardTitle } from "@ui/card";
 import { Progress } from "@ui/progress";
-import { Cpu, Database, Loader2, MemoryStick, Search } from "lucide-react";
-import { useLocalStorage, usePermissions, useRead } from "@lib/hooks";
+import {
+  Cpu,
+  Database,
+  Loader2,
+  MemoryStick,
+  Search,
+  XOctagon,
+} from "lucide-react";
+import {
+  useExecute,
+  useLocalStorage,
+  usePermissions,
+  useRead,
+} from "@lib/hooks";
 import { Types } from "komodo_client";
 import { DataTable, SortableHeader } from "@ui/data-table";
 import { ReactNode, useMemo, useState } from "react";
@@ -16,7 +28,12 @@ import {
   SelectTrigger,
   SelectValue,
 } from "@ui/select";
-import { DockerResourceLink, ShowHideButton } from "@components/util";
+import {
+  ConfirmButton,
+  DockerResourceLink,
+  ShowHideButton,
+  StatusBadge,
+} from "@components/util";
 import { filterBySplit } from "@lib/utils";
 import { useIsServerAvailable } from ".";
 
@@ -303,10 +320,158 @@ export const ServerStats = ({
           )}
         </Section>
 
+        {/* Current GPU Breakdown */}
+        {!!stats?.gpus?.length && (
+          <Section title="GPUs">
+            <DataTable
+              tableKey="server-gpus"
+              data={stats.gpus.map((gpu) => ({
+                ...gpu,
+                mem_perc:
+                  gpu.mem_total_gb > 0
+                    ? 100 * (gpu.mem_used_gb / gpu.mem_total_gb)
+                    : 0,
+              }))}
+              columns={[
+                {
+                  header: "GPU",
+                  cell: ({ row }) => (
+                    <div className="overflow-hidden overflow-ellipsis">
+                      {row.original.index}: {row.original.vendor}{" "}
+                      {row.original.name}
+                    </div>
+                  ),
+                },
+                {
+                  header: "Utilization",
+                  cell: ({ row }) => (
+                    <>{row.original.utilization_perc.toFixed(0)}%</>
+                  ),
+                },
+                {
+                  header: "Memory",
+                  cell: ({ row }) => (
+                    <div className="flex items-center gap-2">
+                      {row.original.mem_perc.toFixed(2)}%
+                      <div className="text-muted-foreground text-sm">
+                        ({row.original.mem_used_gb.toFixed(2)} GB /{" "}
+                        {row.original.mem_total_gb.toFixed(2)} GB)
+                      </div>
+                    </div>
+                  ),
+                },
+                {
+                  header: "Temperature",
+                  cell: ({ row }) =>
+                    row.original.temperature !== undefined
+                      ? `${row.original.temperature.toFixed(0)}°C`
+                      : "N/A",
+                },
+              ]}
+            />
+          </Section>
+        )}
+
+        {/* Network Interfaces */}
+        {!!stats?.network_interfaces?.length && (
+          <Section title="Network Interfaces">
+            <DataTable
+              tableKey="server-network-interfaces"
+              data={stats.network_interfaces}
+              columns={[
+                {
+                  header: "Interface",
+                  accessorKey: "name",
+                },
+                {
+                  header: "Receive",
+                  cell: ({ row }) => formatRate(row.original.rx),
+                },
+                {
+                  header: "Transmit",
+                  cell: ({ row }) => formatRate(row.original.tx),
+                },
+                {
+                  header: "Errors (rx / tx)",
+                  cell: ({ row }) =>
+                    `${row.original.rx_errors} / ${row.original.tx_errors}`,
+                },
+                {
+                  header: "Dropped (rx / tx)",
+                  cell: ({ row }) =>
+                    row.original.rx_dropped !== undefined &&
+                    row.original.tx_dropped !== undefined
+                      ? `${row.original.rx_dropped} / ${row.original.tx_dropped}`
+                      : "N/A",
+                },
+              ]}
+            />
+          </Section>
+        )}
+
+        {/* ZFS Pools */}
+        {!!stats?.zfs_pools?.length && (
+          <Section title="ZFS Pools">
+            <DataTable
+              tableKey="server-zfs-pools"
+              data={stats.zfs_pools}
+              columns={[
+                {
+                  header: "Pool",
+                  accessorKey: "name",
+                },
+                {
+                  header: "Health",
+                  cell: ({ row }) => (
+                    <StatusBadge
+                      text={row.original.health}
+                      intent={
+                        row.original.health === "ONLINE"
+                          ? "Good"
+                          : row.original.health === "DEGRADED"
+                            ? "Warning"
+                            : "Critical"
+                      }
+                    />
+                  ),
+                },
+                {
+                  header: "Used",
+                  cell: ({ row }) => (
+                    <>
+                      {row.original.allocated_gb.toFixed(2)} GB /{" "}
+                      {row.original.size_gb.toFixed(2)} GB
+                    </>
+                  ),
+                },
+                {
+                  header: "Fragmentation",
+                  cell: ({ row }) =>
+                    row.original.fragmentation_perc !== undefined
+                      ? `${row.original.fragmentation_perc}%`
+                      : "N/A",
+                },
+                {
+                  header: "Datasets",
+                  cell: ({ row }) => row.original.datasets.length,
+                },
+                {
+                  header: "Scan",
+                  cell: ({ row }) => row.original.scan ?? "None",
+                },
+              ]}
+            />
+          </Section>
+        )}
+
         {specific.includes(Types.SpecificPermission.Processes) && (
           <Processes id={id} />
         )}
 
+        {specific.includes(Types.SpecificPermission.Processes) && (
+          <ListeningSockets id={id} />
+        )}
+
         {/* Historical Charts */}
         <Section
           title="Historical"
@@ -363,6 +528,11 @@ export const ServerStats = ({
                 type="Cpu"
                 className="w-full h-[250px]"
               />
+              <StatChart
+                server_id={id}
+                type="Cpu Cores"
+                className="w-full h-[250px]"
+              />
               <StatChart
                 server_id={id}
                 type="Memory"
@@ -428,6 +598,7 @@ const ProcessesInner = ({
   const { data: processes, isPending } = useRead("ListSystemProcesses", {
     server: id,
   });
+  const { canExecute } = usePermissions({ type: "Server", id });
   const filtered = useMemo(
     () =>
       processes?.filter((process) => {
@@ -483,19 +654,154 @@ const ProcessesInner = ({
             </>
           ),
         },
+        ...(canExecute
+          ? [
+              {
+                header: "Kill",
+                cell: ({ row }: { row: { original: Types.SystemProcess } }) => (
+                  <KillProcess server={id} pid={row.original.pid} />
+                ),
+              },
+            ]
+          : []),
       ]}
     />
   );
 };
 
+const ListeningSockets = ({ id }: { id: string }) => {
+  const [show, setShow] = useState(false);
+  const [search, setSearch] = useState("");
+  const searchSplit = search.toLowerCase().split(" ");
+  return (
+    <Section
+      title="Listening Ports"
+      actions={
+        <div className="flex gap-4 items-center">
+          <div className="relative">
+            <Search className="w-4 absolute top-[50%] left-3 -translate-y-[50%] text-muted-foreground" />
+            <Input
+              value={search}
+              onChange={(e) => setSearch(e.target.value)}
+              placeholder="search..."
+              className="pl-8 w-[200px] lg:w-[300px]"
+            />
+          </div>
+          <ShowHideButton show={show} setShow={setShow} />
+        </div>
+      }
+    >
+      {show && <ListeningSocketsInner id={id} searchSplit={searchSplit} />}
+    </Section>
+  );
+};
+
+const ListeningSocketsInner = ({
+  id,
+  searchSplit,
+}: {
+  id: string;
+  searchSplit: string[];
+}) => {
+  const { data: sockets, isPending } = useRead("ListListeningSockets", {
+    server: id,
+  });
+  const filtered = useMemo(
+    () =>
+      sockets?.filter((socket) => {
+        if (searchSplit.length === 0) return true;
+        const text = [
+          socket.port.toString(),
+          socket.process ?? "",
+          socket.container ?? "",
+        ]
+          .join(" ")
+          .toLowerCase();
+        return searchSplit.every((search) => text.includes(search));
+      }),
+    [sockets, searchSplit]
+  );
+  if (isPending)
+    return (
+      <div className="flex items-center justify-center h-[200px]">
+        <Loader2 className="w-8 h-8 animate-spin" />
+      </div>
+    );
+  if (!sockets) return null;
+  return (
+    <DataTable
+      tableKey="server-listening-sockets"
+      data={filtered ?? []}
+      columns={[
+        {
+          accessorKey: "port",
+          header: ({ column }) => (
+            <SortableHeader column={column} title="Port" />
+          ),
+        },
+        {
+          header: "Protocol",
+          accessorKey: "protocol",
+        },
+        {
+          header: "Address",
+          accessorKey: "address",
+        },
+        {
+          accessorKey: "process",
+          header: ({ column }) => (
+            <SortableHeader column={column} title="Process" />
+          ),
+          cell: ({ row }) =>
+            row.original.process
+              ? `${row.original.process} (${row.original.pid})`
+              : "Unknown",
+        },
+        {
+          accessorKey: "container",
+          header: ({ column }) => (
+            <SortableHeader column={column} title="Container" />
+          ),
+          cell: ({ row }) =>
+            row.original.container ? (
+              <DockerResourceLink
+                type="container"
+                server_id={id}
+                name={row.original.container}
+              />
+            ) : (
+              "None"
+            ),
+        },
+      ]}
+    />
+  );
+};
+
+/** Sends SIGTERM to the process. Requires process management enabled on Periphery. */
+const KillProcess = ({ server, pid }: { server: string; pid: number }) => {
+  const { mutate, isPending } = useExecute("KillSystemProcess");
+  return (
+    <ConfirmButton
+      variant="ghost"
+      title="Kill"
+      icon={<XOctagon className="w-4 h-4" />}
+      onClick={() => mutate({ server, pid })}
+      loading={isPending}
+    />
+  );
+};
+
 const StatBar = ({
   title,
   icon,
   percentage,
+  description,
 }: {
   title: string;
   icon: ReactNode;
   percentage: number | undefined;
+  description?: string;
 }) => {
   return (
     <Card className="w-full">
@@ -506,8 +812,11 @@ const StatBar = ({
           {icon}
         </div>
       </CardHeader>
-      <CardContent>
+      <CardContent className="flex flex-col gap-2">
         <Progress value={percentage} className="h-4" />
+        {description && (
+          <div className="text-sm text-muted-foreground">{description}</div>
+        )}
       </CardContent>
     </Card>
   );
@@ -519,6 +828,11 @@ const CPU = ({ stats }: { stats: Types.SystemStats | undefined }) => {
       title="CPU Usage"
       icon={<Cpu className="w-5 h-5" />}
       percentage={stats?.cpu_perc}
+      description={
+        stats?.cpu_steal_perc !== undefined
+          ? `Steal: ${stats.cpu_steal_perc.toFixed(2)}%`
+          : undefined
+      }
     />
   );
 };
@@ -643,6 +957,11 @@ const formatBytes = (bytes: number) => {
   }
 };
 
+const formatRate = (bytesPerSec: number) => {
+  const { value, unit } = formatBytes(bytesPerSec);
+  return `${value.toFixed(2)} ${unit}/s`;
+};
+
 const NETWORK = ({ stats }: { stats: Types.SystemStats | undefined }) => {
   const ingress = stats?.network_ingress_bytes ?? 0;
   const egress = stats?.network_egress_bytes ?? 0;
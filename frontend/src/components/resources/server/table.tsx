@@ -95,6 +95,17 @@ const StandardTable = ({ servers }: { servers: Types.ServerListItem[] }) => {
           ),
           cell: ({ row }) => <ServerVersion id={row.original.id} />,
         },
+        {
+          size: 120,
+          accessorKey: "info.latency_ms",
+          header: ({ column }) => (
+            <SortableHeader column={column} title="Latency" />
+          ),
+          cell: ({ row }) =>
+            row.original.info.latency_ms !== undefined
+              ? `${row.original.info.latency_ms} ms`
+              : "-",
+        },
         {
           size: 150,
           accessorKey: "info.state",
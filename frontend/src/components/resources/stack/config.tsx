@@ -40,6 +40,7 @@ import {
   X,
 } from "lucide-react";
 import { LinkedRepoConfig } from "@components/config/linked_repo";
+import { InheritConfig } from "@components/config/inherit";
 import { Button } from "@ui/button";
 import { Input } from "@ui/input";
 import { useStack } from ".";
@@ -172,6 +173,15 @@ export const StackConfig = ({
           </ConfigItem>
         );
       },
+      inherit: (inherit, set) => (
+        <InheritConfig
+          type="Stack"
+          id={id}
+          inherit={inherit}
+          set={set}
+          disabled={disabled}
+        />
+      ),
     },
   };
 
@@ -260,6 +270,25 @@ export const StackConfig = ({
     },
   };
 
+  const secret_files: ConfigComponent<Types.StackConfig> = {
+    label: "Secret Files",
+    description:
+      "Mount secrets into services as files instead of environment variables. Use with 'secrets: [NAME]', read at '/run/secrets/NAME'.",
+    components: {
+      secret_files: (secrets, set) => (
+        <div className="flex flex-col gap-4">
+          <SecretsSearch server={update.server_id ?? config.server_id} />
+          <MonacoEditor
+            value={secrets || "  # NAME = [[SECRET]]\n"}
+            onValueChange={(secret_files) => set({ secret_files })}
+            language="key_value"
+            readOnly={disabled}
+          />
+        </div>
+      ),
+    },
+  };
+
   const config_files: ConfigComponent<Types.StackConfig> = {
     label: "Config Files",
     description:
@@ -368,6 +397,36 @@ export const StackConfig = ({
         ),
       },
     },
+    {
+      label: "Deploy Command Timeout",
+      labelHidden: true,
+      components: {
+        deploy_command_timeout_seconds: {
+          label: "Deploy Command Timeout",
+          boldLabel: true,
+          description:
+            "Kill the pre / post deploy commands if they run longer than this many seconds. 0 means no timeout.",
+        },
+      },
+    },
+    {
+      label: "Healthcheck",
+      description:
+        "Wait for the service container HEALTHCHECKs to report healthy after deploy. The deploy fails if any container is unhealthy, exits, or restarts. Containers without a HEALTHCHECK only need to keep running.",
+      components: {
+        wait_for_healthcheck: {
+          label: "Wait For Healthcheck",
+        },
+        healthcheck_timeout_seconds: {
+          label: "Healthcheck Timeout",
+          description: "Seconds to wait for the containers to become healthy.",
+        },
+        rollback_on_failure: {
+          description:
+            "If the deploy fails, or the containers fail their healthchecks, re-deploy the compose config of the previous successful deploy.",
+        },
+      },
+    },
     {
       label: "Extra Args",
       labelHidden: true,
@@ -421,6 +480,20 @@ export const StackConfig = ({
         ),
       },
     },
+    {
+      label: "Autoscale",
+      description:
+        "Scale service replicas based on the average container cpu. The service containers must use the default compose naming (no 'container_name').",
+      components: {
+        autoscale: (value, set) => (
+          <AutoscaleConfig
+            policies={value ?? []}
+            set={(autoscale) => set({ autoscale })}
+            disabled={disabled}
+          />
+        ),
+      },
+    },
     {
       label: "Pull Images",
       labelHidden: true,
@@ -516,6 +589,17 @@ export const StackConfig = ({
         },
       },
     },
+    {
+      label: "Swarm",
+      labelHidden: true,
+      components: {
+        swarm: {
+          label: "Swarm Mode",
+          description:
+            "Deploy with 'docker stack deploy' to the swarm the Server is a manager of.",
+        },
+      },
+    },
   ];
 
   if (mode === undefined) {
@@ -548,6 +632,7 @@ export const StackConfig = ({
           },
         },
         environment,
+        secret_files,
         config_files,
         ...general_common,
       ],
@@ -668,6 +753,7 @@ export const StackConfig = ({
           },
         },
         environment,
+        secret_files,
         config_files,
         ...general_common,
         {
@@ -756,6 +842,7 @@ export const StackConfig = ({
           },
         },
         environment,
+        secret_files,
         ...general_common,
       ],
       advanced,
@@ -1014,3 +1101,78 @@ const RequiresSelector = ({
     </Select>
   );
 };
+
+const AutoscaleConfig = ({
+  policies,
+  set,
+  disabled,
+}: {
+  policies: Types.StackServiceAutoscale[];
+  set: (policies: Types.StackServiceAutoscale[]) => void;
+  disabled: boolean;
+}) => {
+  const update = (
+    index: number,
+    policy: Partial<Types.StackServiceAutoscale>
+  ) =>
+    set(policies.map((p, i) => (i === index ? { ...p, ...policy } : p)));
+  const number = (value: string) => {
+    const parsed = Number(value);
+    return isNaN(parsed) ? undefined : parsed;
+  };
+  return (
+    <div className="flex flex-col gap-4 w-full">
+      {policies.map((policy, index) => (
+        <div key={index} className="flex items-center gap-4 flex-wrap">
+          <Input
+            className="w-[200px]"
+            placeholder="Service"
+            value={policy.service}
+            onChange={(e) => update(index, { service: e.target.value })}
+            disabled={disabled}
+          />
+          {(
+            [
+              ["target_cpu_percent", "Target CPU %", 70],
+              ["min_replicas", "Min", 1],
+              ["max_replicas", "Max", 3],
+              ["cooldown_seconds", "Cooldown (s)", 300],
+            ] as const
+          ).map(([field, label, fallback]) => (
+            <div key={field} className="flex items-center gap-2">
+              <div className="text-xs text-muted-foreground">{label}:</div>
+              <Input
+                className="w-[90px]"
+                value={policy[field] ?? fallback}
+                onChange={(e) =>
+                  update(index, {
+                    [field]: number(e.target.value),
+                  } as Partial<Types.StackServiceAutoscale>)
+                }
+                disabled={disabled}
+              />
+            </div>
+          ))}
+          {!disabled && (
+            <Button
+              variant="secondary"
+              onClick={() => set(policies.filter((_, i) => i !== index))}
+            >
+              <MinusCircle className="w-4 h-4" />
+            </Button>
+          )}
+        </div>
+      ))}
+      {!disabled && (
+        <Button
+          variant="secondary"
+          className="flex items-center gap-2 w-[200px]"
+          onClick={() => set([...policies, { service: "" }])}
+        >
+          <PlusCircle className="w-4 h-4" />
+          Add Policy
+        </Button>
+      )}
+    </div>
+  );
+};
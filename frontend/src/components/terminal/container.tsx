@@ -46,6 +46,12 @@ export const ContainerTerminal = ({
     Types.ContainerTerminalMode.Attach
   );
   const [otherShell, setOtherShell] = useState("");
+  const [detachKeys, setDetachKeys] = useLocalStorage(
+    `${storageKey}-term-detach-keys-v1`,
+    ""
+  );
+  const [detachKeysInput, setDetachKeysInput] = useState(detachKeys);
+  const [attachInfo, setAttachInfo] = useState<Types.ContainerAttachInfo>();
 
   const make_ws = useCallback(
     (callbacks: TerminalCallbacks) => {
@@ -55,13 +61,18 @@ export const ContainerTerminal = ({
           ...callbacks,
         });
       } else if (mode === Types.ContainerTerminalMode.Attach) {
+        setAttachInfo(undefined);
         return komodo_client().connect_attach({
-          query: { type, query: { ...query } } as any,
+          query: {
+            type,
+            query: { ...query, detach_keys: detachKeys || undefined },
+          } as any,
           ...callbacks,
+          on_attach_info: setAttachInfo,
         });
       }
     },
-    [query, shell, mode]
+    [query, shell, mode, detachKeys]
   );
 
   return (
@@ -128,6 +139,25 @@ export const ContainerTerminal = ({
                 </SelectGroup>
               </SelectContent>
             </Select>
+            {mode === Types.ContainerTerminalMode.Attach && (
+              <Input
+                className="w-[150px]"
+                title="Detach keys"
+                placeholder="ctrl-p,ctrl-q"
+                value={detachKeysInput}
+                onChange={(e) => setDetachKeysInput(e.target.value)}
+                onBlur={() => setDetachKeys(detachKeysInput)}
+                onKeyDown={(e) => {
+                  if (e.key === "Enter") setDetachKeys(detachKeysInput);
+                }}
+              />
+            )}
+            {mode === Types.ContainerTerminalMode.Attach && attachInfo && (
+              <div className="text-sm">
+                {attachInfo.tty ? "tty" : "no tty"}
+                {!attachInfo.stdin && " | stdin closed (read only)"}
+              </div>
+            )}
           </CardTitle>
           <Button
             className="flex items-center gap-2"
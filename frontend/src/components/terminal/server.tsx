@@ -4,7 +4,7 @@ import { komodo_client, useLocalStorage, useRead, useWrite } from "@lib/hooks";
 import { Card, CardContent, CardHeader } from "@ui/card";
 import { Badge } from "@ui/badge";
 import { Button } from "@ui/button";
-import { Loader2, Plus, RefreshCcw, X } from "lucide-react";
+import { Circle, Loader2, Plus, RefreshCcw, Users, X } from "lucide-react";
 import { Popover, PopoverContent, PopoverTrigger } from "@ui/popover";
 import {
   Command,
@@ -13,7 +13,7 @@ import {
   CommandItem,
   CommandList,
 } from "@ui/command";
-import { filterBySplit } from "@lib/utils";
+import { filterBySplit, periphery_request_allowed } from "@lib/utils";
 import { useServer } from "@components/resources/server";
 import { Terminal } from ".";
 import { TerminalCallbacks } from "komodo_client";
@@ -41,7 +41,10 @@ export const ServerTerminals = ({
   const [_selected, setSelected] = useLocalStorage<{
     selected: string | undefined;
   }>(`server-${id}-selected-terminal-v1`, { selected: undefined });
-  const terminals_disabled = useServer(id)?.info.terminals_disabled ?? true;
+  const server_info = useServer(id)?.info;
+  const terminals_disabled =
+    (server_info?.terminals_disabled ?? true) ||
+    !periphery_request_allowed(server_info, "CreateTerminal");
 
   const selected = _selected.selected ?? terminals?.[0]?.name;
 
@@ -72,38 +75,66 @@ export const ServerTerminals = ({
       <Card>
         <CardHeader className="flex flex-row gap-4 items-center justify-between flex-wrap">
           <div className="flex gap-4 items-center flex-wrap">
-            {terminals?.map(({ name: terminal, stored_size_kb }) => (
-              <Badge
-                key={terminal}
-                variant={terminal === selected ? "default" : "secondary"}
-                className="w-fit min-w-[150px] px-2 py-1 cursor-pointer flex gap-4 justify-between"
-                onClick={() => setSelected({ selected: terminal })}
-              >
-                <div className="text-sm w-full flex gap-1 items-center justify-between">
-                  {terminal}
-                  {/* <div className="min-w-[20px] max-w-[70px] text-xs text-muted-foreground text-nowrap whitespace-nowrap overflow-hidden overflow-ellipsis">
-                    {command}
-                  </div> */}
-                  <div className="text-muted-foreground text-xs">
-                    {stored_size_kb.toFixed()} KiB
-                  </div>
-                </div>
-                <Button
-                  className="p-1 h-fit"
-                  variant="destructive"
-                  onClick={async (e) => {
-                    e.stopPropagation();
-                    await delete_terminal({ server: id, terminal });
-                    refetchTerminals();
-                    if (selected === terminal) {
-                      setSelected({ selected: undefined });
-                    }
-                  }}
+            {terminals?.map(
+              ({
+                name: terminal,
+                stored_size_kb,
+                recording,
+                ttl_ms,
+                connections,
+                read_only_connections,
+              }) => (
+                <Badge
+                  key={terminal}
+                  variant={terminal === selected ? "default" : "secondary"}
+                  className="w-fit min-w-[150px] px-2 py-1 cursor-pointer flex gap-4 justify-between"
+                  onClick={() => setSelected({ selected: terminal })}
                 >
-                  <X className="w-4 h-4" />
-                </Button>
-              </Badge>
-            ))}
+                  <div className="text-sm w-full flex gap-1 items-center justify-between">
+                    {terminal}
+                    {/* <div className="min-w-[20px] max-w-[70px] text-xs text-muted-foreground text-nowrap whitespace-nowrap overflow-hidden overflow-ellipsis">
+                      {command}
+                    </div> */}
+                    <div className="text-muted-foreground text-xs flex gap-1 items-center">
+                      {recording && (
+                        <span title={`Recording to ${recording}`}>
+                          <Circle className="w-2 h-2 fill-red-500 text-red-500" />
+                        </span>
+                      )}
+                      {!!connections && (
+                        <span
+                          title={`${connections} connected, ${read_only_connections ?? 0} read only`}
+                          className="flex gap-0.5 items-center"
+                        >
+                          <Users className="w-3 h-3" />
+                          {connections}
+                        </span>
+                      )}
+                      {stored_size_kb.toFixed()} KiB
+                      {typeof ttl_ms === "number" && (
+                        <span title="Closes after the idle timeout or max lifetime">
+                          | {Math.ceil(ttl_ms / 60_000)}m left
+                        </span>
+                      )}
+                    </div>
+                  </div>
+                  <Button
+                    className="p-1 h-fit"
+                    variant="destructive"
+                    onClick={async (e) => {
+                      e.stopPropagation();
+                      await delete_terminal({ server: id, terminal });
+                      refetchTerminals();
+                      if (selected === terminal) {
+                        setSelected({ selected: undefined });
+                      }
+                    }}
+                  >
+                    <X className="w-4 h-4" />
+                  </Button>
+                </Badge>
+              )
+            )}
             {terminals && !terminals_disabled && (
               <NewTerminal create={create} pending={create_pending} />
             )}
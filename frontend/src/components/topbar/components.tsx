@@ -1,10 +1,12 @@
 import {
   LOGIN_TOKENS,
+  useInvalidate,
   useManageUser,
   useRead,
   useResourceParamType,
   useUser,
   useUserInvalidate,
+  useWrite,
 } from "@lib/hooks";
 import { ResourceComponents } from "../resources";
 import {
@@ -19,10 +21,12 @@ import {
   Circle,
   FileQuestion,
   FolderTree,
+  Inbox,
   Keyboard,
   KeyRound,
   LayoutDashboard,
   Loader2,
+  Lock,
   LogOut,
   Plus,
   Settings,
@@ -410,6 +414,87 @@ export const TopbarUpdates = () => {
   );
 };
 
+export const TopbarNotifications = () => {
+  const inv = useInvalidate();
+  const res = useRead("ListNotifications", {}).data;
+  const { mutate } = useWrite("AckNotifications", {
+    onSuccess: () => inv(["ListNotifications"]),
+  });
+  const unread = res?.unread ?? 0;
+
+  return (
+    <DropdownMenu>
+      <DropdownMenuTrigger asChild>
+        <Button variant="ghost" size="icon" className="relative">
+          <Inbox className="w-4 h-4" />
+          <Circle
+            className={cn(
+              "absolute top-2 right-2 w-2 h-2 stroke-blue-500 fill-blue-500 transition-opacity",
+              unread > 0 ? "opacity-1" : "opacity-0"
+            )}
+          />
+        </Button>
+      </DropdownMenuTrigger>
+      <DropdownMenuContent
+        className="w-[100vw] md:w-[500px] max-h-[500px] overflow-auto"
+        sideOffset={20}
+      >
+        <div className="flex items-center justify-between px-2 py-2">
+          <div className="text-sm text-muted-foreground">
+            {unread} Unread
+          </div>
+          <Button
+            variant="ghost"
+            size="sm"
+            disabled={unread === 0}
+            onClick={() => mutate({})}
+          >
+            Mark all read
+          </Button>
+        </div>
+        <DropdownMenuSeparator />
+        <DropdownMenuGroup>
+          {res?.notifications.length === 0 && (
+            <div className="px-2 py-4 text-sm text-muted-foreground">
+              No notifications
+            </div>
+          )}
+          {res?.notifications.map((notification) => (
+            <div
+              key={notification._id?.$oid}
+              className={cn(
+                "px-2 py-4 hover:bg-muted transition-colors border-b last:border-none cursor-pointer",
+                notification.read && "opacity-60"
+              )}
+              onClick={() =>
+                !notification.read &&
+                mutate({ ids: [notification._id?.$oid!] })
+              }
+            >
+              <div className="flex items-center justify-between gap-2 text-sm">
+                <div className="flex items-center gap-2">
+                  {!notification.read && (
+                    <Circle className="w-2 h-2 stroke-blue-500 fill-blue-500" />
+                  )}
+                  {notification.title}
+                </div>
+                <div className="text-xs text-muted-foreground">
+                  {fmt_date(new Date(notification.ts))}
+                </div>
+              </div>
+              {notification.body && (
+                <div className="text-xs text-muted-foreground whitespace-pre-wrap">
+                  {notification.body}
+                </div>
+              )}
+            </div>
+          ))}
+        </DropdownMenuGroup>
+      </DropdownMenuContent>
+    </DropdownMenu>
+  );
+};
+
 const SingleUpdate = ({ update }: { update: Types.UpdateListItem }) => {
   const Components =
     update.target.type !== "System"
@@ -658,3 +743,47 @@ export const CopyCorePubkey = () => {
     </HoverCard>
   );
 };
+
+export const ReadOnlyIndicator = () => {
+  const user = useUser().data;
+  const inv = useInvalidate();
+  const read_only = useRead("GetCoreInfo", {}).data?.read_only;
+  const { mutate, isPending } = useWrite("SetReadOnlyMode", {
+    onSuccess: () => inv(["GetCoreInfo"]),
+  });
+  if (!read_only?.enabled) return null;
+  return (
+    <HoverCard>
+      <HoverCardTrigger asChild>
+        <Button variant="ghost" className="gap-2 text-yellow-500">
+          <Lock className="w-4 h-4" />
+          <div className="hidden md:block">Read Only</div>
+        </Button>
+      </HoverCardTrigger>
+      <HoverCardContent sideOffset={4} className="w-fit max-w-[300px]">
+        <div className="flex flex-col gap-2 text-sm">
+          <div>
+            Core is in read-only mode. Writes and executions are disabled.
+          </div>
+          {read_only.reason && (
+            <div className="text-muted-foreground">{read_only.reason}</div>
+          )}
+          {read_only.set_by && (
+            <div className="text-muted-foreground">
+              Set by {read_only.set_by}
+            </div>
+          )}
+          {user?.admin && (
+            <Button
+              variant="secondary"
+              onClick={() => mutate({ enabled: false, reason: "" })}
+              disabled={isPending}
+            >
+              Disable read-only mode
+            </Button>
+          )}
+        </div>
+      </HoverCardContent>
+    </HoverCard>
+  );
+};
@@ -9,7 +9,9 @@ import {
   Docs,
   KeyboardShortcuts,
   MobileDropdown,
+  ReadOnlyIndicator,
   TopbarAlerts,
+  TopbarNotifications,
   TopbarUpdates,
   UserDropdown,
   Version,
@@ -44,10 +46,12 @@ export const Topbar = () => {
             <Docs />
             <Version />
           </div>
+          <ReadOnlyIndicator />
           <WsStatusIndicator />
           <CopyCorePubkey />
           <KeyboardShortcuts />
           <TopbarAlerts />
+          <TopbarNotifications />
           <TopbarUpdates />
           <ThemeToggle />
           <UserDropdown />
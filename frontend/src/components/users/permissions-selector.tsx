@@ -66,16 +66,19 @@ const ALL_PERMISSIONS_BY_TYPE: {
     Types.SpecificPermission.Logs,
     Types.SpecificPermission.Terminal,
     Types.SpecificPermission.Processes,
+    Types.SpecificPermission.ContainerAttach,
   ],
   Stack: [
     Types.SpecificPermission.Inspect,
     Types.SpecificPermission.Logs,
     Types.SpecificPermission.Terminal,
+    Types.SpecificPermission.ContainerAttach,
   ],
   Deployment: [
     Types.SpecificPermission.Inspect,
     Types.SpecificPermission.Logs,
     Types.SpecificPermission.Terminal,
+    Types.SpecificPermission.ContainerAttach,
   ],
   Build: [Types.SpecificPermission.Attach],
   Repo: [Types.SpecificPermission.Attach],
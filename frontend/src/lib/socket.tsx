@@ -83,6 +83,10 @@ export const WebsocketProvider = ({ children }: { children: ReactNode }) => {
           setWs((ws) => ({ ...ws, connected: true }));
         },
         on_update: on_update_fn,
+        on_notification: (notification) => {
+          toast({ title: notification.title });
+          invalidate(["ListNotifications"]);
+        },
         on_close: () => {
           console.info(count, "| Update websocket connection closed");
           setWs((ws) => ({ ...ws, connected: false }));
@@ -105,6 +105,24 @@ export function version_is_none(version?: Types.Version) {
   return version.major === 0 && version.minor === 0 && version.patch === 0;
 }
 
+/**
+ * Whether the Server's Periphery allows the request type,
+ * according to its `allowed_requests` / `denied_requests` config.
+ * Entries ending in `*` match by prefix.
+ */
+export function periphery_request_allowed(
+  info: Types.ServerListItemInfo | undefined,
+  request: string
+) {
+  if (!info) return false;
+  const matches = (pattern: string) =>
+    pattern.endsWith("*")
+      ? request.startsWith(pattern.slice(0, -1))
+      : pattern === request;
+  if (info.denied_requests?.some(matches)) return false;
+  return !info.allowed_requests?.length || info.allowed_requests.some(matches);
+}
+
 export function resource_name(type: UsableResource, id: string) {
   const Components = ResourceComponents[type];
   return Components.list_item(id)?.name;
@@ -245,7 +263,8 @@ export const sync_no_changes = (sync: Types.ResourceSync) => {
     (sync.info?.pending_deploy?.to_deploy ?? 0) === 0 &&
     (sync.info?.resource_updates?.length ?? 0) === 0 &&
     (sync.info?.variable_updates?.length ?? 0) === 0 &&
-    (sync.info?.user_group_updates?.length ?? 0) === 0
+    (sync.info?.user_group_updates?.length ?? 0) === 0 &&
+    (sync.info?.onboarding_key_updates?.length ?? 0) === 0
   );
 };
 
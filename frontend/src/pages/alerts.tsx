@@ -26,12 +26,22 @@ import { ResourceComponents } from "@components/resources";
 import { ResourceSelector } from "@components/resources/common";
 
 const ALERT_TYPES_BY_RESOURCE: { [key: string]: Types.AlertData["type"][] } = {
-  Server: ["ServerUnreachable", "ServerCpu", "ServerMem", "ServerDisk"],
+  Server: [
+    "ServerUnreachable",
+    "ServerCpu",
+    "ServerMem",
+    "ServerDisk",
+    "ServerDiskHealth",
+    "ServerZfsPool",
+    "ServerGpu",
+    "ServerOsSecurityUpdates",
+  ],
   Stack: ["StackStateChange", "StackImageUpdateAvailable", "StackAutoUpdated"],
   Deployment: [
     "ContainerStateChange",
     "DeploymentImageUpdateAvailable",
     "DeploymentAutoUpdated",
+    "DeploymentUnhealthy",
   ],
   Build: ["BuildFailed"],
   Repo: ["RepoBuildFailed"],
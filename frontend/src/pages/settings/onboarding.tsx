@@ -11,7 +11,14 @@ import {
 } from "@ui/dialog";
 import { Button } from "@ui/button";
 import { useToast } from "@ui/use-toast";
-import { Trash, PlusCircle, Loader2, Check, KeyRound } from "lucide-react";
+import {
+  Trash,
+  PlusCircle,
+  Loader2,
+  Check,
+  KeyRound,
+  Hourglass,
+} from "lucide-react";
 import { useMemo, useState } from "react";
 import { Input } from "@ui/input";
 import {
@@ -28,6 +35,7 @@ import { Switch } from "@ui/switch";
 import { ResourceSelector, TagSelector } from "@components/resources/common";
 import { Types } from "komodo_client";
 import { ColumnDef } from "@tanstack/react-table";
+import { KOMODO_BASE_URL } from "@main";
 import { Badge } from "@ui/badge";
 
 export const Onboarding = () => {
@@ -132,6 +140,24 @@ export const Onboarding = () => {
           </Badge>
         ),
       },
+      {
+        size: 100,
+        accessorKey: "remaining_uses",
+        header: ({ column }) => (
+          <SortableHeader column={column} title="Remaining Uses" />
+        ),
+        cell: ({
+          row: {
+            original: { remaining_uses },
+          },
+        }) => (
+          <Badge
+            variant={remaining_uses === 0 ? "destructive" : "secondary"}
+          >
+            {remaining_uses ?? "Unlimited"}
+          </Badge>
+        ),
+      },
       {
         size: 100,
         accessorKey: "enabled",
@@ -159,22 +185,125 @@ export const Onboarding = () => {
     ],
     [mutate]
   );
+  return (
+    <div className="flex flex-col gap-12">
+      <Section
+        title="Server Onboarding Keys"
+        icon={<KeyRound className="w-5 h-5" />}
+        actions={<CreateKey />}
+        className="flex flex-col gap-6"
+      >
+        <DataTable
+          tableKey="server-onboarding-keys-v1"
+          data={keys}
+          columns={columns}
+        />
+      </Section>
+      <PendingServers />
+    </div>
+  );
+};
+
+/** Servers waiting for approval when Core has 'onboarding_require_approval' enabled. */
+const PendingServers = () => {
+  const { data } = useRead("ListPendingServers", {});
+  const pending = data ?? [];
+  if (pending.length === 0) return null;
   return (
     <Section
-      title="Server Onboarding Keys"
-      icon={<KeyRound className="w-5 h-5" />}
-      actions={<CreateKey />}
+      title="Pending Servers"
+      icon={<Hourglass className="w-5 h-5" />}
       className="flex flex-col gap-6"
     >
       <DataTable
-        tableKey="server-onboarding-keys-v1"
-        data={keys}
-        columns={columns}
+        tableKey="server-onboarding-pending-v1"
+        data={pending}
+        columns={[
+          {
+            accessorKey: "name",
+            header: ({ column }) => (
+              <SortableHeader column={column} title="Name" />
+            ),
+          },
+          {
+            accessorKey: "remote",
+            header: "Source IP",
+          },
+          {
+            accessorKey: "onboarding_key_name",
+            header: "Onboarding Key",
+          },
+          {
+            accessorKey: "requested_at",
+            header: ({ column }) => (
+              <SortableHeader column={column} title="Requested" />
+            ),
+            cell: ({ row }) =>
+              fmt_date_with_minutes(new Date(row.original.requested_at ?? 0)),
+          },
+          {
+            header: "Approve",
+            cell: ({ row }) => (
+              <ApprovePendingServer id={row.original._id?.$oid!} />
+            ),
+          },
+          {
+            header: "Reject",
+            cell: ({ row }) => (
+              <DeletePendingServer id={row.original._id?.$oid!} />
+            ),
+          },
+        ]}
       />
     </Section>
   );
 };
 
+const ApprovePendingServer = ({ id }: { id: string }) => {
+  const invalidate = useInvalidate();
+  const { toast } = useToast();
+  const { mutate, isPending } = useWrite("ApprovePendingServer", {
+    onSuccess: () => {
+      invalidate(["ListPendingServers"], ["ListServers"]);
+      toast({ title: "Server Approved" });
+    },
+  });
+  return (
+    <ConfirmButton
+      title="Approve"
+      icon={<Check className="w-4 h-4" />}
+      onClick={(e) => {
+        e.stopPropagation();
+        mutate({ id });
+      }}
+      loading={isPending}
+    />
+  );
+};
+
+const DeletePendingServer = ({ id }: { id: string }) => {
+  const invalidate = useInvalidate();
+  const { toast } = useToast();
+  const { mutate, isPending } = useWrite("DeletePendingServer", {
+    onSuccess: () => {
+      invalidate(["ListPendingServers"]);
+      toast({ title: "Server Rejected" });
+    },
+  });
+  return (
+    <ConfirmButton
+      title="Reject"
+      variant="destructive"
+      icon={<Trash className="w-4 h-4" />}
+      onClick={(e) => {
+        e.stopPropagation();
+        mutate({ id });
+      }}
+      loading={isPending}
+    />
+  );
+};
+
 const ONE_DAY_MS = 1000 * 60 * 60 * 24;
 
 type ExpiresOptions = "1 day" | "7 days" | "30 days" | "never";
@@ -228,7 +357,8 @@ const CreateKey = () => {
             <DialogHeader>
               <DialogTitle>Onboarding Key Created</DialogTitle>
               <DialogDescription>
-                Use as the PERIPHERY_ONBOARDING_KEY
+                Use as the PERIPHERY_ONBOARDING_KEY, or run the install
+                command on the new host.
               </DialogDescription>
             </DialogHeader>
             <div className="py-8 flex flex-col gap-4">
@@ -241,6 +371,15 @@ const CreateKey = () => {
                 />
                 <CopyButton content={submitted.private_key} />
               </div>
+              <div className="flex items-center justify-between">
+                Install
+                <Input
+                  className="w-72"
+                  value={install_command(submitted.private_key)}
+                  disabled
+                />
+                <CopyButton content={install_command(submitted.private_key)} />
+              </div>
             </div>
             <DialogFooter className="flex justify-end">
               <Button
@@ -349,3 +488,7 @@ const DeleteKey = ({ public_key }: { public_key: string }) => {
     />
   );
 };
+
+/** One-line Periphery install using the Core generated installer script. */
+const install_command = (private_key: string) =>
+  `curl -fsSL "${KOMODO_BASE_URL}/onboard/install.sh?key=${encodeURIComponent(private_key)}" | sh`;
@@ -24,6 +24,7 @@ import {
   Check,
   Search,
   SearchX,
+  Cpu,
 } from "lucide-react";
 import { useState } from "react";
 import { Input } from "@ui/input";
@@ -42,6 +43,7 @@ import {
 import { cn, filterBySplit } from "@lib/utils";
 import { fmt_upper_camelcase } from "@lib/formatting";
 import { tag_background_class } from "@lib/color";
+import { Section } from "@components/layouts";
 
 export const Tags = () => {
   useSetTitle("Tags");
@@ -107,10 +109,52 @@ export const Tags = () => {
           },
         ]}
       />
+      <TagUsageReports search={search} />
     </div>
   );
 };
 
+/** Usage over the last 7 days, per tag. */
+const TagUsageReports = ({ search }: { search: string }) => {
+  const reports = useRead("ListTagUsageReports", {}).data;
+  return (
+    <Section
+      title="Usage (Last 7 Days)"
+      icon={<Cpu className="w-4 h-4" />}
+    >
+      <DataTable
+        tableKey="tag-usage-reports"
+        data={reports?.filter((report) => report.name.includes(search)) ?? []}
+        columns={[
+          { header: "Tag", size: 200, accessorKey: "name" },
+          { header: "Servers", accessorKey: "servers" },
+          {
+            header: "CPU",
+            accessorFn: (report) => `${report.cpu_perc_avg.toFixed(1)}%`,
+          },
+          {
+            header: "Memory",
+            accessorFn: (report) =>
+              `${report.mem_used_gb_avg.toFixed(1)} / ${report.mem_total_gb_avg.toFixed(1)} GB`,
+          },
+          {
+            header: "Containers",
+            accessorFn: (report) =>
+              `${report.running_containers} / ${report.containers}`,
+          },
+          { header: "Deployments", accessorKey: "deployments" },
+          { header: "Stacks", accessorKey: "stacks" },
+          {
+            header: "Build Minutes",
+            accessorFn: (report) =>
+              `${report.build_minutes.toFixed(1)} (${report.build_runs} runs)`,
+          },
+        ]}
+      />
+    </Section>
+  );
+};
+
 export const TagCards = () => {
   const tags = useRead("ListTags", {}).data;
   const user = useUser().data!;
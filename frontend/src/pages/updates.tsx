@@ -183,6 +183,7 @@ const OPERATIONS_BY_RESOURCE: { [key: string]: Types.Operation[] } = {
     Types.Operation.UnpauseContainer,
     Types.Operation.StopContainer,
     Types.Operation.DestroyContainer,
+    Types.Operation.CommitContainer,
     Types.Operation.StartAllContainers,
     Types.Operation.RestartAllContainers,
     Types.Operation.PauseAllContainers,
@@ -194,11 +195,22 @@ const OPERATIONS_BY_RESOURCE: { [key: string]: Types.Operation[] } = {
     Types.Operation.PruneNetworks,
     Types.Operation.DeleteImage,
     Types.Operation.PruneImages,
+    Types.Operation.TransferImage,
     Types.Operation.DeleteVolume,
     Types.Operation.PruneVolumes,
     Types.Operation.PruneDockerBuilders,
     Types.Operation.PruneBuildx,
+    Types.Operation.PruneBuildCache,
     Types.Operation.PruneSystem,
+    Types.Operation.RunServerExtension,
+    Types.Operation.KillSystemProcess,
+    Types.Operation.SetSystemProcessPriority,
+    Types.Operation.StartSystemdUnit,
+    Types.Operation.StopSystemdUnit,
+    Types.Operation.RestartSystemdUnit,
+    Types.Operation.RebootServer,
+    Types.Operation.ShutdownServer,
+    Types.Operation.RunServerSchedule,
   ],
   Stack: [
     Types.Operation.CreateStack,
@@ -214,6 +226,7 @@ const OPERATIONS_BY_RESOURCE: { [key: string]: Types.Operation[] } = {
     Types.Operation.UnpauseStack,
     Types.Operation.StopStack,
     Types.Operation.DestroyStack,
+    Types.Operation.ScaleStack,
     Types.Operation.StartStackService,
     Types.Operation.RestartStackService,
     Types.Operation.PauseStackService,
@@ -231,6 +244,7 @@ const OPERATIONS_BY_RESOURCE: { [key: string]: Types.Operation[] } = {
     Types.Operation.UnpauseDeployment,
     Types.Operation.StopDeployment,
     Types.Operation.DestroyDeployment,
+    Types.Operation.RollbackDeployment,
     Types.Operation.RenameDeployment,
   ],
   Build: [
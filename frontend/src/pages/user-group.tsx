@@ -16,11 +16,19 @@ import {
 import { Input } from "@ui/input";
 import { Popover, PopoverContent, PopoverTrigger } from "@ui/popover";
 import { useToast } from "@ui/use-toast";
-import { PlusCircle, Save, SearchX, User, Users } from "lucide-react";
+import {
+  Gauge,
+  PlusCircle,
+  Save,
+  SearchX,
+  User,
+  Users,
+} from "lucide-react";
 import { useState } from "react";
 import { useParams } from "react-router-dom";
 import { Switch } from "@ui/switch";
 import { DeleteUserGroup } from "@components/users/delete-user-group";
+import { Types } from "komodo_client";
 
 export default function UserGroupPage() {
   const { toast } = useToast();
@@ -116,6 +124,8 @@ export default function UserGroupPage() {
         )}
       </Section>
 
+      <GroupQuotas group_id={group_id} quotas={group.quotas} />
+
       <PermissionsTableTabs user_target={{ type: "UserGroup", id: group_id }} />
 
       <div className="flex flex-col justify-end w-full gap-4">
@@ -144,6 +154,77 @@ export default function UserGroupPage() {
   );
 }
 
+const QUOTAS: { key: keyof Types.UserGroupQuotas; label: string }[] = [
+  { key: "max_servers", label: "Max Servers" },
+  { key: "max_deployments", label: "Max Deployments" },
+  { key: "max_builds_per_day", label: "Max Builds / Day" },
+];
+
+const GroupQuotas = ({
+  group_id,
+  quotas,
+}: {
+  group_id: string;
+  quotas: Types.UserGroupQuotas | undefined;
+}) => {
+  const inv = useInvalidate();
+  const { toast } = useToast();
+  const [update, setUpdate] = useState<Types.UserGroupQuotas>({});
+  const { mutate, isPending } = useWrite("UpdateUserGroupQuotas", {
+    onSuccess: () => {
+      inv(["ListUserGroups"]);
+      setUpdate({});
+      toast({ title: "Updated User Group quotas" });
+    },
+  });
+  const changed = Object.keys(update).length > 0;
+  return (
+    <Section
+      title="Quotas"
+      icon={<Gauge className="w-4 h-4" />}
+      titleRight={
+        <div className="ml-4 flex gap-4 items-center">
+          <div className="text-muted-foreground">
+            Shared by all group members. Use 0 for no limit.
+          </div>
+          <Button
+            variant="secondary"
+            disabled={!changed || isPending}
+            onClick={() =>
+              mutate({
+                user_group: group_id,
+                quotas: { ...quotas, ...update },
+              })
+            }
+          >
+            <Save className="w-4 h-4" />
+          </Button>
+        </div>
+      }
+    >
+      <div className="flex gap-4 flex-wrap">
+        {QUOTAS.map(({ key, label }) => (
+          <div key={key} className="flex items-center gap-2">
+            <div className="text-muted-foreground">{label}</div>
+            <Input
+              type="number"
+              min={0}
+              className="w-[120px]"
+              value={update[key] ?? quotas?.[key] ?? 0}
+              onChange={(e) =>
+                setUpdate((update) => ({
+                  ...update,
+                  [key]: Number(e.target.value),
+                }))
+              }
+            />
+          </div>
+        ))}
+      </div>
+    </Section>
+  );
+};
+
 const AddUserToGroup = ({ group_id }: { group_id: string }) => {
   const inv = useInvalidate();
   const { toast } = useToast();
@@ -1,10 +1,15 @@
-use std::path::Path;
+use std::{path::Path, process::Stdio};
 
 use komodo_client::{
   entities::{komodo_timestamp, update::Log},
   parsers::parse_multiline_command,
 };
 use run_command::{CommandOutput, async_run_command};
+use tokio::{
+  io::{AsyncBufRead, AsyncBufReadExt, BufReader},
+  process::Command,
+  sync::mpsc,
+};
 
 pub async fn run_komodo_command(
   stage: &str,
@@ -69,6 +74,113 @@ pub async fn run_komodo_command_with_sanitization(
   Some(log)
 }
 
+/// A line of command output, sent as the command runs.
+pub enum OutputLine {
+  Stdout(String),
+  Stderr(String),
+}
+
+/// Like [run_komodo_command_with_sanitization], but also sends
+/// each sanitized line of output to `lines` as it is written.
+/// The full output is still returned in the Log.
+pub async fn run_komodo_command_streamed(
+  stage: &str,
+  path: impl Into<Option<&Path>>,
+  command: impl AsRef<str>,
+  parse_multiline: bool,
+  replacers: &[(String, String)],
+  lines: mpsc::UnboundedSender<OutputLine>,
+) -> Option<Log> {
+  let command = if parse_multiline {
+    parse_multiline_command(command)
+  } else {
+    command.as_ref().to_string()
+  };
+  if command.is_empty() {
+    return None;
+  }
+  let command = if let Some(path) = path.into() {
+    format!("cd {} && {command}", path.display())
+  } else {
+    command
+  };
+  let sanitized_command = svi::replace_in_string(&command, replacers);
+
+  let start_ts = komodo_timestamp();
+
+  let mut child = match Command::new("sh")
+    .arg("-c")
+    .arg(&command)
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .kill_on_drop(true)
+    .spawn()
+  {
+    Ok(child) => child,
+    Err(e) => {
+      let mut log =
+        Log::error(stage, format!("Failed to spawn command | {e:?}"));
+      log.command = sanitized_command;
+      return Some(log);
+    }
+  };
+
+  let (Some(stdout), Some(stderr)) =
+    (child.stdout.take(), child.stderr.take())
+  else {
+    let mut log = Log::error(
+      stage,
+      String::from("Failed to capture command output"),
+    );
+    log.command = sanitized_command;
+    return Some(log);
+  };
+
+  let (stdout, stderr, status) = tokio::join!(
+    read_lines(
+      BufReader::new(stdout),
+      replacers,
+      &lines,
+      OutputLine::Stdout
+    ),
+    read_lines(
+      BufReader::new(stderr),
+      replacers,
+      &lines,
+      OutputLine::Stderr
+    ),
+    child.wait(),
+  );
+
+  Some(Log {
+    stage: stage.to_string(),
+    command: sanitized_command,
+    stdout,
+    stderr,
+    success: status.is_ok_and(|status| status.success()),
+    start_ts,
+    end_ts: komodo_timestamp(),
+  })
+}
+
+async fn read_lines(
+  reader: impl AsyncBufRead + Unpin,
+  replacers: &[(String, String)],
+  lines: &mpsc::UnboundedSender<OutputLine>,
+  output_line: fn(String) -> OutputLine,
+) -> String {
+  let mut output = String::new();
+  let mut reader = reader.lines();
+  while let Ok(Some(line)) = reader.next_line().await {
+    let line = svi::replace_in_string(&line, replacers);
+    // The receiver may stop listening, the output is still collected.
+    let _ = lines.send(output_line(line.clone()));
+    output.push_str(&line);
+    output.push('\n');
+  }
+  output
+}
+
 pub fn output_into_log(
   stage: &str,
   command: String,
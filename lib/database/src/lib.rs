@@ -6,20 +6,27 @@ use komodo_client::entities::{
   alert::Alert,
   alerter::Alerter,
   api_key::ApiKey,
+  auth_event::AuthEvent,
   build::Build,
   builder::Builder,
   config::DatabaseConfig,
+  dashboard::Dashboard,
   deployment::Deployment,
+  notification::Notification,
   onboarding_key::OnboardingKey,
+  organization::Organization,
+  pending_server::PendingServer,
   permission::Permission,
   procedure::Procedure,
   provider::{DockerRegistryAccount, GitProviderAccount},
   repo::Repo,
+  revoked_key::RevokedKey,
   server::Server,
   stack::Stack,
   stats::SystemStatsRecord,
   sync::ResourceSync,
   tag::Tag,
+  template::Template,
   update::Update,
   user::{User, UserConfig},
   user_group::UserGroup,
@@ -44,15 +51,22 @@ pub mod utils;
 pub struct Client {
   pub users: Collection<User>,
   pub user_groups: Collection<UserGroup>,
+  pub organizations: Collection<Organization>,
   pub permissions: Collection<Permission>,
   pub api_keys: Collection<ApiKey>,
   pub onboarding_keys: Collection<OnboardingKey>,
+  pub pending_servers: Collection<PendingServer>,
+  pub revoked_keys: Collection<RevokedKey>,
+  pub auth_events: Collection<AuthEvent>,
   pub tags: Collection<Tag>,
   pub variables: Collection<Variable>,
+  pub templates: Collection<Template>,
+  pub dashboards: Collection<Dashboard>,
   pub git_accounts: Collection<GitProviderAccount>,
   pub registry_accounts: Collection<DockerRegistryAccount>,
   pub updates: Collection<Update>,
   pub alerts: Collection<Alert>,
+  pub notifications: Collection<Notification>,
   pub stats: Collection<SystemStatsRecord>,
   // RESOURCES
   pub servers: Collection<Server>,
@@ -81,15 +95,22 @@ impl Client {
     let client = Client {
       users: mongo_indexed::collection(&db, true).await?,
       user_groups: mongo_indexed::collection(&db, true).await?,
+      organizations: mongo_indexed::collection(&db, true).await?,
       permissions: mongo_indexed::collection(&db, true).await?,
       api_keys: mongo_indexed::collection(&db, true).await?,
       onboarding_keys: mongo_indexed::collection(&db, true).await?,
+      pending_servers: mongo_indexed::collection(&db, true).await?,
+      revoked_keys: mongo_indexed::collection(&db, true).await?,
+      auth_events: mongo_indexed::collection(&db, true).await?,
       tags: mongo_indexed::collection(&db, true).await?,
       variables: mongo_indexed::collection(&db, true).await?,
+      templates: mongo_indexed::collection(&db, true).await?,
+      dashboards: mongo_indexed::collection(&db, true).await?,
       git_accounts: mongo_indexed::collection(&db, true).await?,
       registry_accounts: mongo_indexed::collection(&db, true).await?,
       updates: mongo_indexed::collection(&db, true).await?,
       alerts: mongo_indexed::collection(&db, true).await?,
+      notifications: mongo_indexed::collection(&db, true).await?,
       stats: mongo_indexed::collection(&db, true).await?,
       // RESOURCES
       servers: resource_collection(&db, "Server").await?,
@@ -197,6 +218,8 @@ async fn resource_collection<T: Send + Sync>(
 
   create_index(&coll, "tags").await?;
 
+  create_index(&coll, "organization").await?;
+
   Ok(coll)
 }
 
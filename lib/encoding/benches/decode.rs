@@ -0,0 +1,68 @@
+//! Compares decoding a Response message through the
+//! Bytes backed wrappers against Vec backed contents,
+//! which copy the payload at each nesting level.
+//!
+//! Run with `cargo bench -p encoding --bench decode`.
+
+use std::hint::black_box;
+
+use bytes::Bytes;
+use criterion::{
+  BenchmarkId, Criterion, Throughput, criterion_group, criterion_main,
+};
+use encoding::{
+  CastBytes, Decode, Encode, EncodedBodyMessage, EncodedChannel,
+  EncodedResponse, Response, WithChannel,
+};
+use uuid::Uuid;
+
+const SIZES: [usize; 3] = [1024, 64 * 1024, 1024 * 1024];
+
+/// The contents of a received Response message,
+/// as sliced out of the transport message.
+fn response_message(size: usize) -> Bytes {
+  WithChannel {
+    channel: Uuid::new_v4(),
+    data: Response::Ok(vec![b'a'; size]).encode(),
+  }
+  .encode()
+  .into_bytes()
+}
+
+fn decode<B: CastBytes>(bytes: Bytes) -> B {
+  EncodedChannel::<EncodedResponse<B>>::from_bytes(bytes)
+    .decode()
+    .unwrap()
+    .data
+    .decode()
+    .unwrap()
+    .unwrap()
+}
+
+fn decode_response(c: &mut Criterion) {
+  let mut group = c.benchmark_group("decode_response");
+  for size in SIZES {
+    let message = response_message(size);
+    group.throughput(Throughput::Bytes(size as u64));
+    group.bench_with_input(
+      BenchmarkId::new("bytes", size),
+      &message,
+      |b, message| {
+        b.iter(|| {
+          decode::<EncodedBodyMessage>(black_box(message.clone()))
+        })
+      },
+    );
+    group.bench_with_input(
+      BenchmarkId::new("vec", size),
+      &message,
+      |b, message| {
+        b.iter(|| decode::<Vec<u8>>(black_box(message.clone())))
+      },
+    );
+  }
+  group.finish();
+}
+
+criterion_group!(benches, decode_response);
+criterion_main!(benches);
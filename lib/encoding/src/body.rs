@@ -0,0 +1,146 @@
+use anyhow::Context;
+use bytes::Bytes;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+  CastBytes, Decode, Encode, EncodedCborMessage, EncodedJsonMessage,
+  EncodedResponse, cbor::to_cbor_vec, pop_byte,
+};
+
+/// Appended to CBOR bodies. Serialized JSON never ends
+/// with this byte, so JSON bodies are left unmarked and
+/// bodies from older senders still decode as JSON.
+const CBOR_MARKER: u8 = 0xCB;
+/// Appended to bodies wrapped in [crate::EncodedEncrypted].
+/// Like the CBOR marker, serialized JSON never ends with it.
+pub(crate) const ENCRYPTED_MARKER: u8 = 0xEE;
+
+/// The encoding of Request / Response bodies,
+/// negotiated per connection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyEncoding {
+  #[default]
+  Json,
+  Cbor,
+}
+
+/// A Request / Response body in either [BodyEncoding].
+/// Receivers decode either one, the sender picks.
+/// ```markdown
+/// | --- u8[] --- | -- u8 -- |
+/// | <JSON BYTES> |          |
+/// | <CBOR BYTES> |   0xCB   |
+/// | <ENCRYPTED>  |   0xEE   |
+/// ```
+#[derive(Clone, Debug)]
+pub struct EncodedBodyMessage(Bytes);
+
+impl_identity!(EncodedBodyMessage);
+
+impl CastBytes for EncodedBodyMessage {
+  fn from_bytes(bytes: Bytes) -> Self {
+    Self(bytes)
+  }
+  fn into_bytes(self) -> Bytes {
+    self.0
+  }
+}
+
+impl EncodedBodyMessage {
+  /// Encrypted bodies must be decrypted
+  /// with the session keys before decoding.
+  pub fn is_encrypted(&self) -> bool {
+    self.0.last() == Some(&ENCRYPTED_MARKER)
+  }
+
+  pub fn encoding(&self) -> BodyEncoding {
+    if self.0.last() == Some(&CBOR_MARKER) {
+      BodyEncoding::Cbor
+    } else {
+      BodyEncoding::Json
+    }
+  }
+}
+
+impl From<EncodedJsonMessage> for EncodedBodyMessage {
+  fn from(json: EncodedJsonMessage) -> Self {
+    Self(json.into_bytes())
+  }
+}
+
+impl From<EncodedCborMessage> for EncodedBodyMessage {
+  fn from(cbor: EncodedCborMessage) -> Self {
+    let mut bytes = cbor.into_vec();
+    bytes.push(CBOR_MARKER);
+    Self(bytes.into())
+  }
+}
+
+pub struct BodyMessage<'a, T>(pub &'a T, pub BodyEncoding);
+
+impl<'a, T: Serialize + Send>
+  Encode<anyhow::Result<EncodedBodyMessage>> for BodyMessage<'a, T>
+where
+  &'a T: Send,
+{
+  fn encode(self) -> anyhow::Result<EncodedBodyMessage> {
+    encode_body(self.0, self.1)
+  }
+}
+
+impl<T: DeserializeOwned> Decode<T> for EncodedBodyMessage {
+  fn decode(self) -> anyhow::Result<T> {
+    if self.is_encrypted() {
+      return Err(anyhow::anyhow!(
+        "Body is encrypted, it must be decrypted with the connection session keys"
+      ));
+    }
+    match self.encoding() {
+      BodyEncoding::Json => {
+        EncodedJsonMessage::from_bytes(self.0).decode()
+      }
+      BodyEncoding::Cbor => {
+        let mut bytes = self.0;
+        pop_byte(&mut bytes);
+        EncodedCborMessage::from_bytes(bytes).decode()
+      }
+    }
+  }
+}
+
+fn encode_body<T: Serialize>(
+  value: &T,
+  encoding: BodyEncoding,
+) -> anyhow::Result<EncodedBodyMessage> {
+  match encoding {
+    BodyEncoding::Json => serde_json::to_vec(value)
+      .context("Failed to serialize data to bytes")
+      .map(|json| EncodedJsonMessage::from_vec(json).into()),
+    BodyEncoding::Cbor => to_cbor_vec(value)
+      .map(|cbor| EncodedCborMessage::from_vec(cbor).into()),
+  }
+}
+
+/// A response which is only serialized once the
+/// [BodyEncoding] of the connection is known.
+pub struct ResponseBody(
+  Box<
+    dyn FnOnce(BodyEncoding) -> anyhow::Result<EncodedBodyMessage>
+      + Send,
+  >,
+);
+
+impl<T: Serialize + Send + 'static> From<T> for ResponseBody {
+  fn from(value: T) -> Self {
+    Self(Box::new(move |encoding| encode_body(&value, encoding)))
+  }
+}
+
+impl ResponseBody {
+  pub fn encode_with(
+    self,
+    encoding: BodyEncoding,
+  ) -> EncodedResponse<EncodedBodyMessage> {
+    (self.0)(encoding).encode()
+  }
+}
@@ -0,0 +1,68 @@
+use anyhow::Context;
+use bytes::Bytes;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+  CastBytes, Decode, Encode, EncodedResponse, decode_limits,
+};
+
+/// ```markdown
+/// | --- u8[] --- |
+/// | <CBOR BYTES> |
+/// ```
+#[derive(Clone, Debug)]
+pub struct EncodedCborMessage(Bytes);
+
+impl_identity!(EncodedCborMessage);
+
+impl CastBytes for EncodedCborMessage {
+  fn from_bytes(bytes: Bytes) -> Self {
+    Self(bytes)
+  }
+  fn into_bytes(self) -> Bytes {
+    self.0
+  }
+}
+
+pub struct CborMessage<'a, T>(pub &'a T);
+
+impl<'a, T: Serialize + Send>
+  Encode<anyhow::Result<EncodedCborMessage>> for CborMessage<'a, T>
+where
+  &'a T: Send,
+{
+  fn encode(self) -> anyhow::Result<EncodedCborMessage> {
+    to_cbor_vec(self.0).map(EncodedCborMessage::from_vec)
+  }
+}
+
+impl<T: DeserializeOwned> Decode<T> for EncodedCborMessage {
+  fn decode(self) -> anyhow::Result<T> {
+    let limits = decode_limits();
+    limits.check_body_bytes(self.0.len())?;
+    ciborium::de::from_reader_with_recursion_limit(
+      self.0.as_ref(),
+      limits.max_body_depth,
+    )
+    .context("Failed to parse CBOR bytes")
+  }
+}
+
+impl<T: Serialize + Send> From<T>
+  for EncodedResponse<EncodedCborMessage>
+{
+  fn from(value: T) -> Self {
+    to_cbor_vec(&value)
+      .map(EncodedCborMessage::from_vec)
+      .encode()
+  }
+}
+
+pub(crate) fn to_cbor_vec<T: Serialize>(
+  value: &T,
+) -> anyhow::Result<Vec<u8>> {
+  let mut bytes = Vec::new();
+  ciborium::into_writer(value, &mut bytes)
+    .context("Failed to serialize data to bytes")?;
+  Ok(bytes)
+}
@@ -26,6 +26,7 @@ impl<B: CastBytes> EncodedChannel<B> {
   }
 }
 
+#[derive(Debug, Clone)]
 pub struct WithChannel<T> {
   pub channel: Uuid,
   pub data: T,
@@ -68,20 +69,18 @@ impl<T: CastBytes + Send> Encode<EncodedChannel<T>>
 
 impl<T: CastBytes> Decode<WithChannel<T>> for EncodedChannel<T> {
   fn decode(self) -> anyhow::Result<WithChannel<T>> {
-    let mut bytes = self.0.into_vec();
+    let mut bytes = self.0.into_bytes();
     let len = bytes.len();
-    if bytes.len() < 16 {
+    if len < 16 {
       return Err(anyhow!(
         "ChannelMessage bytes too short to include uuid"
       ));
     }
-    let mut channel = [0u8; 16];
-    for (i, byte) in bytes.drain(len - 16..).enumerate() {
-      channel[i] = byte;
-    }
+    // Shares the buffer, the contents aren't copied.
+    let channel = bytes.split_off(len - 16);
     Ok(WithChannel {
-      channel: Uuid::from_bytes(channel),
-      data: T::from_vec(bytes),
+      channel: Uuid::from_slice(&channel)?,
+      data: T::from_bytes(bytes),
     })
   }
 }
@@ -0,0 +1,73 @@
+use bytes::Bytes;
+
+use crate::{CastBytes, Decode, Encode};
+
+/// Length of the trailing CRC32.
+const CHECKSUM_LEN: usize = 4;
+
+/// Message wrapper to detect corrupted or truncated
+/// contents before they are parsed.
+/// ```markdown
+/// | -- u8[] -- | ------ u32 ------ |
+/// | <CONTENTS> | CRC32 of CONTENTS |
+/// ```
+#[derive(Clone, Debug)]
+pub struct EncodedChecked<T>(T);
+
+impl_wrapper!(EncodedChecked);
+
+/// Appends the CRC32 of the inner bytes.
+pub struct Checked<T>(pub T);
+
+/// Returned when [EncodedChecked] contents don't match the checksum.
+/// Downcast the `anyhow::Error` to tell corruption
+/// apart from other decode failures.
+#[derive(Debug, thiserror::Error)]
+pub enum CorruptedError {
+  #[error(
+    "Checked message of {len} bytes too short to include checksum"
+  )]
+  Truncated { len: usize },
+  #[error(
+    "Checked message contents are corrupted | expected crc32 {expected:#010x}, got {actual:#010x} over {len} bytes"
+  )]
+  Mismatch {
+    expected: u32,
+    actual: u32,
+    len: usize,
+  },
+}
+
+impl<T: CastBytes + Send> Encode<EncodedChecked<T>> for Checked<T> {
+  fn encode(self) -> EncodedChecked<T> {
+    let mut bytes = self.0.into_vec();
+    let checksum = crc32fast::hash(&bytes);
+    bytes.extend(checksum.to_be_bytes());
+    EncodedChecked(T::from_vec(bytes))
+  }
+}
+
+impl<T: CastBytes> Decode<T> for EncodedChecked<T> {
+  fn decode(self) -> anyhow::Result<T> {
+    let mut bytes = self.0.into_bytes();
+    let len = bytes.len();
+    if len < CHECKSUM_LEN {
+      return Err(CorruptedError::Truncated { len }.into());
+    }
+    // Shares the buffer, the contents aren't copied.
+    let checksum = bytes.split_off(len - CHECKSUM_LEN);
+    let expected = u32::from_be_bytes(checksum[..].try_into()?);
+    let actual = crc32fast::hash(&bytes);
+    if actual != expected {
+      return Err(
+        CorruptedError::Mismatch {
+          expected,
+          actual,
+          len: bytes.len(),
+        }
+        .into(),
+      );
+    }
+    Ok(T::from_bytes(bytes))
+  }
+}
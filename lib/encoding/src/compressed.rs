@@ -0,0 +1,64 @@
+use anyhow::{Context, anyhow};
+use bytes::Bytes;
+
+use crate::{CastBytes, Decode, Encode, decode_limits, pop_byte};
+
+/// Contents smaller than this are passed through
+/// uncompressed, the savings aren't worth the overhead.
+pub const COMPRESSED_MIN_BYTES: usize = 1024;
+
+/// Favors speed, payloads are compressed on the request path.
+const ZSTD_LEVEL: i32 = 3;
+
+const UNCOMPRESSED_BYTE: u8 = 0;
+const ZSTD_BYTE: u8 = 1;
+
+/// Message wrapper to compress individual payloads,
+/// independently of the transport level compression.
+/// ```markdown
+/// | -- u8[] -- | ---------- u8 ----------- |
+/// | <CONTENTS> | 0: Passthrough, 1: Zstd |
+/// ```
+#[derive(Clone, Debug)]
+pub struct EncodedCompressed<T>(T);
+
+impl_wrapper!(EncodedCompressed);
+
+/// Compresses the inner bytes with zstd, unless they are
+/// smaller than [COMPRESSED_MIN_BYTES] or compression doesn't help.
+pub struct Compressed<T>(pub T);
+
+impl<T: CastBytes + Send> Encode<EncodedCompressed<T>>
+  for Compressed<T>
+{
+  fn encode(self) -> EncodedCompressed<T> {
+    let mut bytes = self.0.into_vec();
+    if bytes.len() >= COMPRESSED_MIN_BYTES
+      && let Ok(mut compressed) =
+        zstd::bulk::compress(&bytes, ZSTD_LEVEL)
+      && compressed.len() < bytes.len()
+    {
+      compressed.push(ZSTD_BYTE);
+      return EncodedCompressed(T::from_vec(compressed));
+    }
+    bytes.push(UNCOMPRESSED_BYTE);
+    EncodedCompressed(T::from_vec(bytes))
+  }
+}
+
+impl<T: CastBytes> Decode<T> for EncodedCompressed<T> {
+  fn decode(self) -> anyhow::Result<T> {
+    let mut bytes = self.0.into_bytes();
+    let compression_byte = pop_byte(&mut bytes)
+      .context("EncodedCompressed bytes cannot be empty")?;
+    match compression_byte {
+      UNCOMPRESSED_BYTE => Ok(T::from_bytes(bytes)),
+      ZSTD_BYTE => {
+        decode_limits().decompress_zstd(&bytes).map(T::from_vec)
+      }
+      other => Err(anyhow!(
+        "Got unrecognized EncodedCompressed compression byte: {other}"
+      )),
+    }
+  }
+}
@@ -0,0 +1,167 @@
+use anyhow::{Context, anyhow};
+use bytes::Bytes;
+use chacha20poly1305::{
+  KeyInit, XChaCha20Poly1305, XNonce,
+  aead::{Aead, Payload},
+};
+use rand::RngCore;
+use uuid::Uuid;
+
+use crate::{
+  CastBytes, Encode, EncodedBodyMessage, EncodedResponse, Response,
+  body::ENCRYPTED_MARKER, pop_byte,
+};
+
+/// Length of the trailing XChaCha20 nonce.
+const NONCE_LEN: usize = 24;
+/// Length of the Poly1305 tag following the ciphertext.
+const TAG_LEN: usize = 16;
+
+/// Message wrapper adding an AEAD layer on top of the transport,
+/// using a key from the connection handshake.
+/// The channel is authenticated as associated data,
+/// so contents can't be moved to another channel.
+/// ```markdown
+/// | -- u8[] -- | -- u8[16] -- | -- u8[24] -- |
+/// | CIPHERTEXT | Poly1305 TAG | RANDOM NONCE |
+/// ```
+#[derive(Clone, Debug)]
+pub struct EncodedEncrypted<T>(T);
+
+impl_wrapper!(EncodedEncrypted);
+
+/// Encrypts the inner bytes with the key, bound to the channel.
+pub struct Encrypted<'a, T> {
+  pub data: T,
+  pub key: &'a [u8; 32],
+  pub channel: Uuid,
+}
+
+impl<T: CastBytes + Send> Encode<anyhow::Result<EncodedEncrypted<T>>>
+  for Encrypted<'_, T>
+{
+  fn encode(self) -> anyhow::Result<EncodedEncrypted<T>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut nonce);
+    let mut bytes = XChaCha20Poly1305::new(self.key.into())
+      .encrypt(
+        XNonce::from_slice(&nonce),
+        Payload {
+          msg: &self.data.into_vec(),
+          aad: self.channel.as_bytes(),
+        },
+      )
+      .map_err(|_| anyhow!("Failed to encrypt message"))?;
+    bytes.extend(nonce);
+    Ok(EncodedEncrypted(T::from_vec(bytes)))
+  }
+}
+
+impl<T: CastBytes> EncodedEncrypted<T> {
+  /// Fails if the key or channel don't match
+  /// the ones used to encrypt, or the contents were modified.
+  pub fn decrypt(
+    self,
+    key: &[u8; 32],
+    channel: Uuid,
+  ) -> anyhow::Result<T> {
+    let mut bytes = self.0.into_bytes();
+    let len = bytes.len();
+    if len < NONCE_LEN + TAG_LEN {
+      return Err(anyhow!(
+        "Encrypted message of {len} bytes too short to include nonce and tag"
+      ));
+    }
+    let nonce = bytes.split_off(len - NONCE_LEN);
+    let bytes = XChaCha20Poly1305::new(key.into())
+      .decrypt(
+        XNonce::from_slice(&nonce),
+        Payload {
+          msg: &bytes,
+          aad: channel.as_bytes(),
+        },
+      )
+      .map_err(|_| {
+        anyhow!("Failed to decrypt message")
+          .context("The session keys or channel don't match")
+      })?;
+    Ok(T::from_vec(bytes))
+  }
+}
+
+impl EncodedBodyMessage {
+  /// Wraps the body in [EncodedEncrypted].
+  pub fn encrypt(
+    self,
+    key: &[u8; 32],
+    channel: Uuid,
+  ) -> anyhow::Result<EncodedBodyMessage> {
+    let mut bytes = Encrypted {
+      data: self.into_vec(),
+      key,
+      channel,
+    }
+    .encode()?
+    .into_vec();
+    bytes.push(ENCRYPTED_MARKER);
+    Ok(EncodedBodyMessage::from_vec(bytes))
+  }
+
+  /// Bodies which aren't encrypted are returned unchanged.
+  pub fn decrypt(
+    self,
+    key: &[u8; 32],
+    channel: Uuid,
+  ) -> anyhow::Result<EncodedBodyMessage> {
+    if !self.is_encrypted() {
+      return Ok(self);
+    }
+    let mut bytes = self.into_bytes();
+    pop_byte(&mut bytes);
+    EncodedEncrypted::<Bytes>::from_bytes(bytes)
+      .decrypt(key, channel)
+      .map(EncodedBodyMessage::from_bytes)
+  }
+}
+
+impl EncodedResponse<EncodedBodyMessage> {
+  /// Encrypts the body of Ok responses.
+  /// Errors and Pending responses are left as is.
+  pub fn encrypt_body(
+    self,
+    key: &[u8; 32],
+    channel: Uuid,
+  ) -> anyhow::Result<Self> {
+    match self.decode_response()? {
+      Response::Ok(body) => {
+        Ok(Response::Ok(body.encrypt(key, channel)?).encode())
+      }
+      response => Ok(response.encode()),
+    }
+  }
+
+  /// Decrypts the body of Ok responses. Responses which
+  /// aren't encrypted are returned as is, without copying.
+  pub fn decrypt_body(
+    self,
+    key: &[u8; 32],
+    channel: Uuid,
+  ) -> anyhow::Result<Self> {
+    // Shares the buffer, checking the flags doesn't copy.
+    let bytes = self.into_bytes();
+    let encrypted = bytes.len() >= 2
+      && bytes[bytes.len() - 1] == 0
+      && bytes[bytes.len() - 2] == ENCRYPTED_MARKER;
+    let response = EncodedResponse::from_bytes(bytes);
+    if !encrypted {
+      return Ok(response);
+    }
+    let Response::Ok(body) = response.decode_response()? else {
+      unreachable!("Checked for the Ok flag above")
+    };
+    let body = body
+      .decrypt(key, channel)
+      .context("Failed to decrypt Response body")?;
+    Ok(Response::Ok(body).encode())
+  }
+}
@@ -1,22 +1,25 @@
 use anyhow::Context;
+use bytes::Bytes;
 use serde::{Serialize, de::DeserializeOwned};
 
-use crate::{CastBytes, Decode, Encode, EncodedResponse};
+use crate::{
+  CastBytes, Decode, Encode, EncodedResponse, decode_limits,
+};
 
 /// ```markdown
 /// | --- u8[] --- |
 /// | <JSON BYTES> |
 /// ```
 #[derive(Clone, Debug)]
-pub struct EncodedJsonMessage(Vec<u8>);
+pub struct EncodedJsonMessage(Bytes);
 
 impl_identity!(EncodedJsonMessage);
 
 impl CastBytes for EncodedJsonMessage {
-  fn from_vec(vec: Vec<u8>) -> Self {
-    Self(vec)
+  fn from_bytes(bytes: Bytes) -> Self {
+    Self(bytes)
   }
-  fn into_vec(self) -> Vec<u8> {
+  fn into_bytes(self) -> Bytes {
     self.0
   }
 }
@@ -31,12 +34,13 @@ where
   fn encode(self) -> anyhow::Result<EncodedJsonMessage> {
     serde_json::to_vec(self.0)
       .context("Failed to serialize data to bytes")
-      .map(EncodedJsonMessage)
+      .map(EncodedJsonMessage::from_vec)
   }
 }
 
 impl<T: DeserializeOwned> Decode<T> for EncodedJsonMessage {
   fn decode(self) -> anyhow::Result<T> {
+    decode_limits().check_json(&self.0)?;
     serde_json::from_slice(&self.0)
       .context("Failed to parse JSON bytes")
   }
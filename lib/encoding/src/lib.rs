@@ -5,13 +5,29 @@ use bytes::Bytes;
 #[macro_use]
 mod macros;
 
+mod body;
+mod cbor;
 mod channel;
+mod checked;
+mod compressed;
+mod encrypted;
 mod json;
+mod limits;
 mod response;
+mod stream;
+mod versioned;
 
+pub use body::*;
+pub use cbor::*;
 pub use channel::*;
+pub use checked::*;
+pub use compressed::*;
+pub use encrypted::*;
 pub use json::*;
+pub use limits::*;
 pub use response::*;
+pub use stream::*;
+pub use versioned::*;
 
 pub trait Encode<Target>: Sized + Send {
   fn encode(self) -> Target;
@@ -62,6 +78,14 @@ impl CastBytes for Bytes {
   }
 }
 
+/// Removes the trailing flag byte used by the wrappers.
+/// The remaining bytes share the buffer, nothing is copied.
+pub(crate) fn pop_byte(bytes: &mut Bytes) -> Option<u8> {
+  let byte = *bytes.last()?;
+  bytes.truncate(bytes.len() - 1);
+  Some(byte)
+}
+
 impl CastBytes for Vec<u8> {
   fn from_vec(vec: Vec<u8>) -> Self {
     vec
@@ -0,0 +1,125 @@
+use std::{io::Read as _, sync::OnceLock};
+
+use anyhow::{Context, anyhow};
+
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 512 * 1024 * 1024;
+pub const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024 * 1024;
+/// Matches the serde_json recursion limit, which applies regardless.
+pub const DEFAULT_MAX_BODY_DEPTH: usize = 128;
+
+/// Limits on received bytes, checked before they are
+/// decompressed or handed to serde, so a malicious or buggy
+/// peer can't exhaust memory with a huge or deeply nested message.
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeLimits {
+  /// Max bytes of a whole message, after
+  /// reassembling chunks and decompressing.
+  pub max_message_bytes: usize,
+  /// Max bytes of a JSON / CBOR body.
+  pub max_body_bytes: usize,
+  /// Max nesting depth of a JSON / CBOR body.
+  pub max_body_depth: usize,
+}
+
+impl Default for DecodeLimits {
+  fn default() -> Self {
+    DecodeLimits {
+      max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+      max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+      max_body_depth: DEFAULT_MAX_BODY_DEPTH,
+    }
+  }
+}
+
+static DECODE_LIMITS: OnceLock<DecodeLimits> = OnceLock::new();
+
+/// Call once at startup, before any messages are decoded.
+/// Later calls are ignored.
+pub fn set_decode_limits(limits: DecodeLimits) {
+  let _ = DECODE_LIMITS.set(limits);
+}
+
+pub fn decode_limits() -> &'static DecodeLimits {
+  DECODE_LIMITS.get_or_init(Default::default)
+}
+
+impl DecodeLimits {
+  pub fn check_message_bytes(
+    &self,
+    len: usize,
+  ) -> anyhow::Result<()> {
+    if len > self.max_message_bytes {
+      return Err(anyhow!(
+        "Message of {len} bytes exceeds max of {} bytes",
+        self.max_message_bytes
+      ));
+    }
+    Ok(())
+  }
+
+  pub fn check_body_bytes(&self, len: usize) -> anyhow::Result<()> {
+    if len > self.max_body_bytes {
+      return Err(anyhow!(
+        "Body of {len} bytes exceeds max of {} bytes",
+        self.max_body_bytes
+      ));
+    }
+    Ok(())
+  }
+
+  /// Checks the size, and scans the nesting depth
+  /// without parsing, before serde allocates anything.
+  pub fn check_json(&self, json: &[u8]) -> anyhow::Result<()> {
+    self.check_body_bytes(json.len())?;
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for &byte in json {
+      if in_string {
+        match byte {
+          _ if escaped => escaped = false,
+          b'\\' => escaped = true,
+          b'"' => in_string = false,
+          _ => {}
+        }
+        continue;
+      }
+      match byte {
+        b'"' => in_string = true,
+        b'{' | b'[' => {
+          depth += 1;
+          if depth > self.max_body_depth {
+            return Err(anyhow!(
+              "JSON body exceeds max nesting depth of {}",
+              self.max_body_depth
+            ));
+          }
+        }
+        b'}' | b']' => depth = depth.saturating_sub(1),
+        _ => {}
+      }
+    }
+    Ok(())
+  }
+
+  /// Decompresses zstd, stopping once the output
+  /// passes `max_message_bytes`.
+  pub fn decompress_zstd(
+    &self,
+    bytes: &[u8],
+  ) -> anyhow::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    zstd::stream::read::Decoder::new(bytes)
+      .context("Failed to init zstd decoder")?
+      .take(self.max_message_bytes as u64 + 1)
+      .read_to_end(&mut decompressed)
+      .context("Failed to decompress bytes")?;
+    if decompressed.len() > self.max_message_bytes {
+      return Err(anyhow!(
+        "Decompressed message exceeds max of {} bytes",
+        self.max_message_bytes
+      ));
+    }
+    Ok(decompressed)
+  }
+}
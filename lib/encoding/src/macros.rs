@@ -2,6 +2,12 @@
 macro_rules! impl_cast_bytes_vec {
   ($typ:ty, $through:ident) => {
     impl CastBytes for $typ {
+      fn from_bytes(bytes: Bytes) -> Self {
+        Self($through::from_bytes(bytes))
+      }
+      fn into_bytes(self) -> Bytes {
+        self.0.into_bytes()
+      }
       fn from_vec(bytes: Vec<u8>) -> Self {
         Self($through::from_vec(bytes))
       }
@@ -2,13 +2,13 @@ use anyhow::{Context, Result as AnyhowResult};
 use bytes::Bytes;
 use serror::{deserialize_error_bytes, serialize_error_bytes};
 
-use crate::{CastBytes, Decode, Encode};
+use crate::{CastBytes, Decode, Encode, pop_byte};
 
 /// Message wrapper to handle Error unwrapping
 /// anywhere in the en/decoding chain.
 /// ```markdown
-/// | -- u8[] -- | ---------- u8 ----------- |
-/// | <CONTENTS> | 0: Ok, 1: Err, 2: Pending |
+/// | -- u8[] -- | ------------------ u8 ------------------- |
+/// | <CONTENTS> | 0: Ok, 1: Err, 2: Pending, 3: StreamChunk |
 /// ```
 #[derive(Clone, Debug)]
 pub struct EncodedResponse<T>(T);
@@ -19,6 +19,9 @@ pub enum Response<T> {
   Ok(T),
   Err(anyhow::Error),
   Pending,
+  /// Incremental output sent before the final response.
+  /// Older receivers treat it like Pending.
+  StreamChunk(T),
 }
 
 impl<T> Response<T> {
@@ -32,6 +35,7 @@ impl<T> Response<T> {
       Ok(t) => Ok(map(t)),
       Err(e) => Err(e),
       Pending => Pending,
+      StreamChunk(t) => StreamChunk(map(t)),
     }
   }
 
@@ -49,7 +53,7 @@ impl<T> Response<T> {
     match self.map(Decode::decode) {
       Response::Ok(res) => res.map(Some),
       Response::Err(e) => Err(e),
-      Response::Pending => Ok(None),
+      Response::Pending | Response::StreamChunk(_) => Ok(None),
     }
   }
 }
@@ -71,6 +75,11 @@ impl<T: CastBytes + Send> Encode<EncodedResponse<T>> for Response<T> {
       Pending => {
         vec![2]
       }
+      StreamChunk(data) => {
+        let mut bytes = data.into_vec();
+        bytes.push(3);
+        bytes
+      }
     };
     EncodedResponse(T::from_vec(bytes))
   }
@@ -94,17 +103,33 @@ impl<T: CastBytes> Encode<EncodedResponse<T>> for &anyhow::Error {
 
 impl<T: CastBytes> Decode<Option<T>> for EncodedResponse<T> {
   fn decode(self) -> AnyhowResult<Option<T>> {
-    let mut bytes = self.0.into_vec();
-    let result_byte =
-      bytes.pop().context("ResultWrapper bytes cannot be empty")?;
+    let mut bytes = self.0.into_bytes();
+    let result_byte = pop_byte(&mut bytes)
+      .context("ResultWrapper bytes cannot be empty")?;
     match result_byte {
-      0 => Ok(Some(T::from_vec(bytes))),
+      0 => Ok(Some(T::from_bytes(bytes))),
       1 => Err(deserialize_error_bytes(&bytes)),
       _ => Ok(None),
     }
   }
 }
 
+impl<T: CastBytes> EncodedResponse<T> {
+  /// Decodes every response variant, including StreamChunk,
+  /// for receivers of streamed requests.
+  pub fn decode_response(self) -> AnyhowResult<Response<T>> {
+    let mut bytes = self.0.into_bytes();
+    let result_byte = pop_byte(&mut bytes)
+      .context("ResultWrapper bytes cannot be empty")?;
+    match result_byte {
+      0 => Ok(Response::Ok(T::from_bytes(bytes))),
+      1 => Ok(Response::Err(deserialize_error_bytes(&bytes))),
+      3 => Ok(Response::StreamChunk(T::from_bytes(bytes))),
+      _ => Ok(Response::Pending),
+    }
+  }
+}
+
 impl<T> From<AnyhowResult<T>> for Response<T> {
   fn from(value: AnyhowResult<T>) -> Self {
     match value {
@@ -119,7 +144,7 @@ impl<T> From<Response<T>> for AnyhowResult<Option<T>> {
     match value {
       Response::Ok(t) => Ok(Some(t)),
       Response::Err(e) => Err(e),
-      Response::Pending => Ok(None),
+      Response::Pending | Response::StreamChunk(_) => Ok(None),
     }
   }
 }
@@ -0,0 +1,127 @@
+use std::pin::pin;
+
+use bytes::{Bytes, BytesMut};
+use futures_util::{Stream, StreamExt, stream};
+
+use crate::{Compressed, Decode, Encode, EncodedCompressed};
+
+/// Chunk size used by [ChunkedStream] and [CompressedStream]
+/// when not otherwise specified.
+pub const STREAM_CHUNK_BYTES: usize = 512 * 1024;
+
+/// Like [Encode], for values too large to buffer in memory
+/// (image exports, volume backups). The value is encoded
+/// as a stream of independently decodable chunks.
+pub trait EncodeStream<Target>: Sized + Send {
+  fn encode_stream(self) -> impl Stream<Item = Target> + Send;
+}
+
+/// Like [Decode], for streams produced by [EncodeStream].
+/// Each chunk is decoded as it arrives, so the receiver
+/// can write it out without buffering the whole value.
+pub trait DecodeStream<Target>: Sized + Send {
+  fn decode_stream(
+    self,
+  ) -> impl Stream<Item = anyhow::Result<Target>> + Send;
+}
+
+/// Re-chunks a byte stream into chunks of at most `chunk_bytes`.
+/// Only the last chunk may be smaller.
+pub struct ChunkedStream<S> {
+  pub stream: S,
+  pub chunk_bytes: usize,
+}
+
+impl<S> ChunkedStream<S> {
+  pub fn new(stream: S) -> ChunkedStream<S> {
+    ChunkedStream {
+      stream,
+      chunk_bytes: STREAM_CHUNK_BYTES,
+    }
+  }
+}
+
+impl<S: Stream<Item = Bytes> + Send> EncodeStream<Bytes>
+  for ChunkedStream<S>
+{
+  fn encode_stream(self) -> impl Stream<Item = Bytes> + Send {
+    let chunk_bytes = self.chunk_bytes.max(1);
+    let state = (Box::pin(self.stream), BytesMut::new(), false);
+    stream::unfold(
+      state,
+      move |(mut stream, mut buffer, mut finished)| async move {
+        while !finished && buffer.len() < chunk_bytes {
+          match stream.next().await {
+            Some(bytes) => buffer.extend_from_slice(&bytes),
+            None => finished = true,
+          }
+        }
+        if buffer.is_empty() {
+          return None;
+        }
+        let chunk =
+          buffer.split_to(buffer.len().min(chunk_bytes)).freeze();
+        Some((chunk, (stream, buffer, finished)))
+      },
+    )
+  }
+}
+
+/// Re-chunks a byte stream like [ChunkedStream], then compresses
+/// each chunk on its own with [Compressed].
+pub struct CompressedStream<S>(pub ChunkedStream<S>);
+
+impl<S> CompressedStream<S> {
+  pub fn new(stream: S) -> CompressedStream<S> {
+    CompressedStream(ChunkedStream::new(stream))
+  }
+}
+
+impl<S: Stream<Item = Bytes> + Send>
+  EncodeStream<EncodedCompressed<Bytes>> for CompressedStream<S>
+{
+  fn encode_stream(
+    self,
+  ) -> impl Stream<Item = EncodedCompressed<Bytes>> + Send {
+    self
+      .0
+      .encode_stream()
+      .map(|chunk| Compressed(chunk).encode())
+  }
+}
+
+/// A stream of encoded chunks, eg. [EncodedCompressed] chunks
+/// received from the other side of a [CompressedStream].
+pub struct EncodedStream<S>(pub S);
+
+impl<S, T> DecodeStream<T> for EncodedStream<S>
+where
+  S: Stream + Send,
+  S::Item: Decode<T>,
+{
+  fn decode_stream(
+    self,
+  ) -> impl Stream<Item = anyhow::Result<T>> + Send {
+    self.0.map(Decode::decode)
+  }
+}
+
+/// Drives the decoded stream to completion, handing each chunk
+/// to `write`. Returns the total bytes written.
+pub async fn write_decoded_stream<E, T>(
+  encoded: E,
+  mut write: impl AsyncFnMut(T) -> anyhow::Result<()>,
+) -> anyhow::Result<u64>
+where
+  E: DecodeStream<T>,
+  T: AsRef<[u8]>,
+{
+  let mut decoded = pin!(encoded.decode_stream());
+  let mut total = 0;
+  while let Some(chunk) = decoded.next().await {
+    let chunk = chunk?;
+    total += chunk.as_ref().len() as u64;
+    write(chunk).await?;
+  }
+  Ok(total)
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+
+type Decoder<T> =
+  Box<dyn Fn(&[u8]) -> anyhow::Result<T> + Send + Sync>;
+
+/// Decoders for each version of a wire format, so messages
+/// from peers running an older (or newer) version still decode
+/// while a mixed version fleet is upgraded.
+///
+/// Older versions are registered with [VersionedDecoders::migrate],
+/// decoding into their own type which is then converted into the current one.
+pub struct VersionedDecoders<T> {
+  /// Used in errors, eg. "transport header".
+  name: &'static str,
+  decoders: BTreeMap<u8, Decoder<T>>,
+  forward_compatible: bool,
+}
+
+impl<T> VersionedDecoders<T> {
+  pub fn new(name: &'static str) -> VersionedDecoders<T> {
+    VersionedDecoders {
+      name,
+      decoders: Default::default(),
+      forward_compatible: false,
+    }
+  }
+
+  /// Registers the decoder for a version in the current format.
+  pub fn register(
+    mut self,
+    version: u8,
+    decoder: impl Fn(&[u8]) -> anyhow::Result<T> + Send + Sync + 'static,
+  ) -> VersionedDecoders<T> {
+    self.decoders.insert(version, Box::new(decoder));
+    self
+  }
+
+  /// Registers the decoder for an earlier version, which is
+  /// migrated into the current format after decoding.
+  pub fn migrate<Old: Into<T>>(
+    self,
+    version: u8,
+    decoder: impl Fn(&[u8]) -> anyhow::Result<Old> + Send + Sync + 'static,
+  ) -> VersionedDecoders<T> {
+    self
+      .register(version, move |bytes| decoder(bytes).map(Into::into))
+  }
+
+  /// Versions newer than the latest registered one are decoded
+  /// with the latest decoder. Only for formats where later versions
+  /// just append fields, which older readers skip.
+  pub fn forward_compatible(mut self) -> VersionedDecoders<T> {
+    self.forward_compatible = true;
+    self
+  }
+
+  /// The latest registered version.
+  pub fn latest(&self) -> Option<u8> {
+    self.decoders.keys().next_back().copied()
+  }
+
+  pub fn decode(
+    &self,
+    version: u8,
+    bytes: &[u8],
+  ) -> anyhow::Result<T> {
+    if let Some(decoder) = self.decoders.get(&version) {
+      return decoder(bytes);
+    }
+    if self.forward_compatible
+      && let Some((latest, decoder)) = self.decoders.last_key_value()
+      && version > *latest
+    {
+      return decoder(bytes);
+    }
+    let supported = self
+      .decoders
+      .keys()
+      .map(u8::to_string)
+      .collect::<Vec<_>>()
+      .join(", ");
+    Err(anyhow!(
+      "Got unsupported {} version {version} (supports {supported}). The other side may be running an incompatible version of Komodo.",
+      self.name
+    ))
+  }
+}
@@ -30,6 +30,8 @@ impl<'a> Interpolator<'a> {
     &mut self,
     stack: &mut Stack,
   ) -> anyhow::Result<&mut Self> {
+    // Secret files exist to carry secrets, so are always interpolated.
+    self.interpolate_string(&mut stack.config.secret_files)?;
     if stack.config.skip_secret_interp {
       return Ok(self);
     }
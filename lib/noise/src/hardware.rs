@@ -0,0 +1,390 @@
+//! Private keys held by a hardware module, so the
+//! long-lived identity key never touches the filesystem.
+//!
+//! The private key spec is a PKCS#11 URI (RFC 7512), eg.
+//! `pkcs11:token=komodo;object=core?module-path=/usr/lib/softhsm/libsofthsm2.so&pin-source=/run/secrets/pin`.
+//! The module must support `CKM_ECDH1_DERIVE` on a Curve25519
+//! (`CKK_EC_MONTGOMERY`) key, which the Noise handshake
+//! delegates its static key DH operation to.
+
+use std::{
+  collections::HashMap,
+  path::PathBuf,
+  sync::{Arc, Mutex, OnceLock},
+};
+
+use anyhow::{Context, anyhow};
+use cryptoki::{
+  context::{CInitializeArgs, Pkcs11},
+  mechanism::{
+    Mechanism,
+    elliptic_curve::{EcKdf, Ecdh1DeriveParams},
+  },
+  object::{
+    Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle,
+  },
+  session::{Session, UserType},
+  types::AuthPin,
+};
+use snow::{
+  params::{CipherChoice, DHChoice, HashChoice},
+  resolvers::{CryptoResolver, DefaultResolver},
+  types::{Cipher, Dh, Hash, Random},
+};
+
+const PKCS11_PREFIX: &str = "pkcs11:";
+const TPM_PREFIX: &str = "tpm:";
+
+/// Passed to snow as the local private key of hardware keys,
+/// the actual DH is done by the module.
+pub(crate) const PLACEHOLDER_PRIVATE_KEY: [u8; 32] = [0; 32];
+
+/// Whether the private key spec references a hardware module
+/// rather than key material.
+pub fn is_hardware_key_spec(spec: &str) -> bool {
+  spec.starts_with(PKCS11_PREFIX) || spec.starts_with(TPM_PREFIX)
+}
+
+/// Opens the hardware key for the spec once, and reuses it after.
+/// Returns None if the spec is not a hardware key spec.
+pub fn load(spec: &str) -> anyhow::Result<Option<Arc<HardwareKey>>> {
+  if spec.starts_with(TPM_PREFIX) {
+    return Err(anyhow!(
+      "TPM 2.0 does not support the X25519 keys used by the Noise handshake. Use a PKCS#11 module supporting Curve25519 ECDH instead."
+    ));
+  }
+  if !spec.starts_with(PKCS11_PREFIX) {
+    return Ok(None);
+  }
+  static KEYS: OnceLock<Mutex<HashMap<String, Arc<HardwareKey>>>> =
+    OnceLock::new();
+  let mut keys = KEYS
+    .get_or_init(Default::default)
+    .lock()
+    .map_err(|_| anyhow!("Hardware key registry lock poisoned"))?;
+  if let Some(key) = keys.get(spec) {
+    return Ok(Some(key.clone()));
+  }
+  let key = Arc::new(HardwareKey::open(spec)?);
+  keys.insert(spec.to_string(), key.clone());
+  Ok(Some(key))
+}
+
+/// A Curve25519 private key in a PKCS#11 module.
+pub struct HardwareKey {
+  /// Sessions can't be shared between threads.
+  session: Mutex<Session>,
+  private_key: ObjectHandle,
+  public_key: [u8; 32],
+}
+
+impl HardwareKey {
+  fn open(spec: &str) -> anyhow::Result<HardwareKey> {
+    let uri = Pkcs11Uri::parse(spec)?;
+    let pkcs11 =
+      Pkcs11::new(&uri.module_path).with_context(|| {
+        format!("Failed to load PKCS#11 module {:?}", uri.module_path)
+      })?;
+    pkcs11
+      .initialize(CInitializeArgs::OsThreads)
+      .context("Failed to initialize PKCS#11 module")?;
+    let slot = pkcs11
+      .get_slots_with_token()
+      .context("Failed to list PKCS#11 slots")?
+      .into_iter()
+      .find(|slot| {
+        pkcs11
+          .get_token_info(*slot)
+          .map(|info| info.label() == uri.token)
+          .unwrap_or_default()
+      })
+      .with_context(|| {
+        format!("No PKCS#11 token found with label {}", uri.token)
+      })?;
+    let session = pkcs11
+      .open_ro_session(slot)
+      .context("Failed to open PKCS#11 session")?;
+    if let Some(pin) = uri.pin()? {
+      session
+        .login(UserType::User, Some(&AuthPin::new(pin)))
+        .context("Failed to login to PKCS#11 token")?;
+    }
+
+    let find_key = |class: ObjectClass| {
+      session
+        .find_objects(&[
+          Attribute::Class(class),
+          Attribute::KeyType(KeyType::EC_MONTGOMERY),
+          Attribute::Label(uri.object.as_bytes().to_vec()),
+        ])
+        .with_context(|| {
+          format!("Failed to find PKCS#11 object {}", uri.object)
+        })?
+        .into_iter()
+        .next()
+        .with_context(|| {
+          format!(
+            "No Curve25519 {class} found with label {}",
+            uri.object
+          )
+        })
+    };
+
+    let private_key = find_key(ObjectClass::PRIVATE_KEY)?;
+    let public_key = find_key(ObjectClass::PUBLIC_KEY)?;
+    let public_key = session
+      .get_attributes(public_key, &[AttributeType::EcPoint])
+      .context("Failed to read PKCS#11 public key")?
+      .into_iter()
+      .find_map(|attribute| match attribute {
+        Attribute::EcPoint(point) => Some(point),
+        _ => None,
+      })
+      .context("PKCS#11 public key has no EC point")?;
+
+    Ok(HardwareKey {
+      session: Mutex::new(session),
+      private_key,
+      public_key: raw_ec_point(&public_key)?,
+    })
+  }
+
+  /// The raw X25519 public key.
+  pub fn public_key(&self) -> &[u8; 32] {
+    &self.public_key
+  }
+
+  /// Computes the shared secret with the remote public key
+  /// inside the module.
+  pub fn dh(
+    &self,
+    remote_public_key: &[u8],
+  ) -> anyhow::Result<[u8; 32]> {
+    let session = self
+      .session
+      .lock()
+      .map_err(|_| anyhow!("PKCS#11 session lock poisoned"))?;
+    let mechanism = Mechanism::Ecdh1Derive(Ecdh1DeriveParams::new(
+      EcKdf::null(),
+      remote_public_key,
+    ));
+    let shared = session
+      .derive_key(
+        &mechanism,
+        self.private_key,
+        &[
+          Attribute::Class(ObjectClass::SECRET_KEY),
+          Attribute::KeyType(KeyType::GENERIC_SECRET),
+          Attribute::ValueLen(32.into()),
+          Attribute::Token(false),
+          Attribute::Sensitive(false),
+          Attribute::Extractable(true),
+        ],
+      )
+      .context("PKCS#11 ECDH derive failed")?;
+    let value = session
+      .get_attributes(shared, &[AttributeType::Value])
+      .context("Failed to read PKCS#11 shared secret");
+    // Don't leave the shared secret in the session.
+    let _ = session.destroy_object(shared);
+    value?
+      .into_iter()
+      .find_map(|attribute| match attribute {
+        Attribute::Value(value) => value.try_into().ok(),
+        _ => None,
+      })
+      .context("PKCS#11 shared secret is not 32 bytes")
+  }
+}
+
+/// The EC point may be raw, or wrapped in a DER octet string.
+fn raw_ec_point(point: &[u8]) -> anyhow::Result<[u8; 32]> {
+  let raw = match point {
+    [0x04, 0x20, raw @ ..] => raw,
+    raw => raw,
+  };
+  raw.try_into().map_err(|_| {
+    anyhow!("PKCS#11 public key is not a 32 byte X25519 key")
+  })
+}
+
+/// The supported subset of RFC 7512 PKCS#11 URIs.
+struct Pkcs11Uri {
+  token: String,
+  object: String,
+  module_path: PathBuf,
+  pin_value: Option<String>,
+  pin_source: Option<PathBuf>,
+}
+
+impl Pkcs11Uri {
+  fn parse(spec: &str) -> anyhow::Result<Pkcs11Uri> {
+    let spec = spec.strip_prefix(PKCS11_PREFIX).unwrap_or(spec);
+    let (path, query) = spec.split_once('?').unwrap_or((spec, ""));
+    let mut token = None;
+    let mut object = None;
+    let mut module_path = None;
+    let mut pin_value = None;
+    let mut pin_source = None;
+    for attribute in path.split(';').chain(query.split('&')) {
+      let Some((key, value)) = attribute.split_once('=') else {
+        continue;
+      };
+      let value = urlencoding::decode(value)
+        .with_context(|| {
+          format!("Invalid PKCS#11 URI attribute {key}")
+        })?
+        .into_owned();
+      match key {
+        "token" => token = Some(value),
+        "object" => object = Some(value),
+        "module-path" => module_path = Some(PathBuf::from(value)),
+        "pin-value" => pin_value = Some(value),
+        "pin-source" => {
+          let value = value.strip_prefix("file:").unwrap_or(&value);
+          pin_source = Some(PathBuf::from(value))
+        }
+        _ => {}
+      }
+    }
+    Ok(Pkcs11Uri {
+      token: token.context("PKCS#11 URI must include 'token'")?,
+      object: object.context("PKCS#11 URI must include 'object'")?,
+      module_path: module_path
+        .context("PKCS#11 URI must include 'module-path'")?,
+      pin_value,
+      pin_source,
+    })
+  }
+
+  fn pin(&self) -> anyhow::Result<Option<String>> {
+    if let Some(pin) = &self.pin_value {
+      return Ok(Some(pin.clone()));
+    }
+    let Some(path) = &self.pin_source else {
+      return Ok(None);
+    };
+    let pin = std::fs::read_to_string(path).with_context(|| {
+      format!("Failed to read PKCS#11 pin-source {path:?}")
+    })?;
+    Ok(Some(pin.trim().to_string()))
+  }
+}
+
+/// Resolves the DH of the handshake to the hardware key,
+/// and the rest to the default snow implementations.
+pub(crate) struct HardwareResolver {
+  key: Arc<HardwareKey>,
+  default: DefaultResolver,
+}
+
+impl HardwareResolver {
+  pub(crate) fn new(key: Arc<HardwareKey>) -> HardwareResolver {
+    HardwareResolver {
+      key,
+      default: DefaultResolver,
+    }
+  }
+}
+
+impl CryptoResolver for HardwareResolver {
+  fn resolve_rng(&self) -> Option<Box<dyn Random>> {
+    self.default.resolve_rng()
+  }
+
+  fn resolve_dh(&self, choice: &DHChoice) -> Option<Box<dyn Dh>> {
+    Some(Box::new(HardwareDh {
+      key: self.key.clone(),
+      software: self.default.resolve_dh(choice)?,
+      hardware: false,
+    }))
+  }
+
+  fn resolve_hash(
+    &self,
+    choice: &HashChoice,
+  ) -> Option<Box<dyn Hash>> {
+    self.default.resolve_hash(choice)
+  }
+
+  fn resolve_cipher(
+    &self,
+    choice: &CipherChoice,
+  ) -> Option<Box<dyn Cipher>> {
+    self.default.resolve_cipher(choice)
+  }
+}
+
+/// snow resolves the static and ephemeral keys the same way.
+/// The static key is set with the placeholder private key,
+/// and uses the hardware key. The ephemeral key is generated,
+/// and stays in software.
+struct HardwareDh {
+  key: Arc<HardwareKey>,
+  software: Box<dyn Dh>,
+  hardware: bool,
+}
+
+impl Dh for HardwareDh {
+  fn name(&self) -> &'static str {
+    self.software.name()
+  }
+
+  fn pub_len(&self) -> usize {
+    self.software.pub_len()
+  }
+
+  fn priv_len(&self) -> usize {
+    self.software.priv_len()
+  }
+
+  fn set(&mut self, privkey: &[u8]) {
+    self.hardware = privkey == PLACEHOLDER_PRIVATE_KEY;
+    if !self.hardware {
+      self.software.set(privkey);
+    }
+  }
+
+  fn generate(
+    &mut self,
+    rng: &mut dyn Random,
+  ) -> Result<(), snow::Error> {
+    self.hardware = false;
+    self.software.generate(rng)
+  }
+
+  fn pubkey(&self) -> &[u8] {
+    if self.hardware {
+      self.key.public_key()
+    } else {
+      self.software.pubkey()
+    }
+  }
+
+  fn privkey(&self) -> &[u8] {
+    if self.hardware {
+      &PLACEHOLDER_PRIVATE_KEY
+    } else {
+      self.software.privkey()
+    }
+  }
+
+  fn dh(
+    &self,
+    pubkey: &[u8],
+    out: &mut [u8],
+  ) -> Result<(), snow::Error> {
+    if !self.hardware {
+      return self.software.dh(pubkey, out);
+    }
+    let shared = self.key.dh(pubkey).map_err(|e| {
+      tracing::warn!("Hardware key DH failed | {e:#}");
+      snow::Error::Dh
+    })?;
+    out
+      .get_mut(..shared.len())
+      .ok_or(snow::Error::Dh)?
+      .copy_from_slice(&shared);
+    Ok(())
+  }
+}
@@ -0,0 +1,161 @@
+//! Loads private keys from an agent speaking the ssh-agent protocol
+//! (draft-miller-ssh-agent), using `agent:<key-comment>`.
+//!
+//! Agents never hand out private keys with the standard messages,
+//! so the key is exported with the `x25519-private-key@komo.do`
+//! extension. A dedicated komodo-agent socket implementing it can be
+//! given with `KOMODO_AGENT_SOCK`, otherwise `SSH_AUTH_SOCK` is used.
+
+use std::{
+  io::{Read, Write},
+  os::unix::net::UnixStream,
+  path::PathBuf,
+  time::Duration,
+};
+
+use anyhow::{Context, anyhow};
+
+pub const AGENT_PREFIX: &str = "agent:";
+
+const EXPORT_EXTENSION: &str = "x25519-private-key@komo.do";
+
+const SSH_AGENT_FAILURE: u8 = 5;
+const SSH_AGENT_SUCCESS: u8 = 6;
+const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+const SSH_AGENTC_EXTENSION: u8 = 27;
+const SSH_AGENT_EXTENSION_FAILURE: u8 = 28;
+
+/// Guards against misbehaving agents.
+const MAX_MESSAGE_LENGTH: usize = 256 * 1024;
+
+/// Fetches the raw X25519 private key bytes for the
+/// identity with matching comment.
+pub fn load_private_key(comment: &str) -> anyhow::Result<Vec<u8>> {
+  let socket = agent_socket()?;
+  let mut stream =
+    UnixStream::connect(&socket).with_context(|| {
+      format!("Failed to connect to agent socket at {socket:?}")
+    })?;
+  let timeout = Some(Duration::from_secs(10));
+  stream.set_read_timeout(timeout)?;
+  stream.set_write_timeout(timeout)?;
+
+  let (kind, identities) =
+    request(&mut stream, SSH_AGENTC_REQUEST_IDENTITIES, &[])?;
+  if kind != SSH_AGENT_IDENTITIES_ANSWER {
+    return Err(anyhow!(
+      "Agent failed to list identities (message type {kind})"
+    ));
+  }
+  let key_blob =
+    find_identity(&identities, comment)?.with_context(|| {
+      format!("No agent identity found with comment '{comment}'")
+    })?;
+
+  let mut contents = Vec::new();
+  put_string(&mut contents, EXPORT_EXTENSION.as_bytes());
+  put_string(&mut contents, &key_blob);
+  let (kind, response) =
+    request(&mut stream, SSH_AGENTC_EXTENSION, &contents)?;
+  match kind {
+    SSH_AGENT_SUCCESS => {}
+    SSH_AGENT_FAILURE | SSH_AGENT_EXTENSION_FAILURE => {
+      return Err(anyhow!(
+        "Agent refused to export key '{comment}'. Standard ssh-agent does not export private keys, use a komodo-agent socket supporting '{EXPORT_EXTENSION}' with KOMODO_AGENT_SOCK."
+      ));
+    }
+    kind => {
+      return Err(anyhow!(
+        "Unexpected agent response (message type {kind})"
+      ));
+    }
+  }
+  let mut reader = response.as_slice();
+  let private_key = get_string(&mut reader)
+    .context("Invalid agent key export response")?;
+  if private_key.len() != 32 {
+    return Err(anyhow!(
+      "Agent key '{comment}' is not a 32 byte X25519 private key"
+    ));
+  }
+  Ok(private_key.to_vec())
+}
+
+fn agent_socket() -> anyhow::Result<PathBuf> {
+  std::env::var_os("KOMODO_AGENT_SOCK")
+    .or_else(|| std::env::var_os("SSH_AUTH_SOCK"))
+    .map(PathBuf::from)
+    .context(
+      "Using 'agent:' private key, but neither KOMODO_AGENT_SOCK nor SSH_AUTH_SOCK is set",
+    )
+}
+
+/// Returns the key blob of the first identity with matching comment.
+fn find_identity(
+  mut identities: &[u8],
+  comment: &str,
+) -> anyhow::Result<Option<Vec<u8>>> {
+  let count = get_u32(&mut identities)?;
+  for _ in 0..count {
+    let key_blob = get_string(&mut identities)?;
+    let key_comment = get_string(&mut identities)?;
+    if key_comment == comment.as_bytes() {
+      return Ok(Some(key_blob.to_vec()));
+    }
+  }
+  Ok(None)
+}
+
+fn request(
+  stream: &mut UnixStream,
+  kind: u8,
+  contents: &[u8],
+) -> anyhow::Result<(u8, Vec<u8>)> {
+  let mut message = Vec::with_capacity(5 + contents.len());
+  message
+    .extend_from_slice(&(contents.len() as u32 + 1).to_be_bytes());
+  message.push(kind);
+  message.extend_from_slice(contents);
+  stream
+    .write_all(&message)
+    .context("Failed to write to agent socket")?;
+
+  let mut length = [0u8; 4];
+  stream
+    .read_exact(&mut length)
+    .context("Failed to read from agent socket")?;
+  let length = u32::from_be_bytes(length) as usize;
+  if length == 0 || length > MAX_MESSAGE_LENGTH {
+    return Err(anyhow!("Invalid agent message length {length}"));
+  }
+  let mut response = vec![0u8; length];
+  stream
+    .read_exact(&mut response)
+    .context("Failed to read from agent socket")?;
+  let kind = response.remove(0);
+  Ok((kind, response))
+}
+
+fn put_string(buf: &mut Vec<u8>, value: &[u8]) {
+  buf.extend_from_slice(&(value.len() as u32).to_be_bytes());
+  buf.extend_from_slice(value);
+}
+
+fn get_u32(buf: &mut &[u8]) -> anyhow::Result<u32> {
+  let (value, rest) = buf
+    .split_first_chunk::<4>()
+    .context("Agent message truncated")?;
+  *buf = rest;
+  Ok(u32::from_be_bytes(*value))
+}
+
+fn get_string<'a>(buf: &mut &'a [u8]) -> anyhow::Result<&'a [u8]> {
+  let length = get_u32(buf)? as usize;
+  if buf.len() < length {
+    return Err(anyhow!("Agent message truncated"));
+  }
+  let (value, rest) = buf.split_at(length);
+  *buf = rest;
+  Ok(value)
+}
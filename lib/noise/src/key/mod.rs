@@ -7,6 +7,7 @@ use anyhow::Context;
 use arc_swap::ArcSwap;
 use der::AnyRef;
 
+pub mod agent;
 pub mod command;
 
 mod private;
@@ -113,17 +114,35 @@ pub struct RotatableKeyPair {
 impl RotatableKeyPair {
   /// Parses from either direct private key (raw / der / pem),
   /// or from file containing raw / der / pem.
-  /// Use `file:/path/to/private.key` to specify file.
+  /// Use `file:/path/to/private.key` to specify file,
+  /// or `agent:<key-comment>` to load from an agent.
   pub fn from_private_key_spec(
     private_key_spec: &str,
   ) -> anyhow::Result<Self> {
-    let (keys, path) =
-      if let Some(path) = private_key_spec.strip_prefix("file:") {
-        let path = PathBuf::from(path);
-        (EncodedKeyPair::load_maybe_generate(&path)?, Some(path))
-      } else {
-        (EncodedKeyPair::from_private_key(private_key_spec)?, None)
+    let (keys, path) = if let Some(path) =
+      private_key_spec.strip_prefix("file:")
+    {
+      let path = PathBuf::from(path);
+      (EncodedKeyPair::load_maybe_generate(&path)?, Some(path))
+    } else if let Some(comment) =
+      private_key_spec.strip_prefix(agent::AGENT_PREFIX)
+    {
+      // Agent keys are managed by the agent, and are not rotatable.
+      let private_key = agent::load_private_key(comment)?;
+      let private = Pkcs8PrivateKey::from_raw_bytes(&private_key)?;
+      let public = private.compute_public_key()?;
+      (EncodedKeyPair { private, public }, None)
+    } else if let Some(key) = crate::hardware::load(private_key_spec)?
+    {
+      // Hardware keys are managed by the module, and are not rotatable.
+      let keys = EncodedKeyPair {
+        private: Pkcs8PrivateKey::from(private_key_spec.to_string()),
+        public: SpkiPublicKey::from_raw_bytes(key.public_key())?,
       };
+      (keys, None)
+    } else {
+      (EncodedKeyPair::from_private_key(private_key_spec)?, None)
+    };
     Ok(Self {
       keys: ArcSwap::new(Arc::new(keys)),
       path,
@@ -86,8 +86,7 @@ impl SpkiPublicKey {
         let (_label, public_key_der) =
           pem_rfc7468::decode_vec(public_key_maybe_pem.as_bytes())
             .map_err(anyhow::Error::msg)
-            .context("Failed to get der from pem")
-            .unwrap();
+            .context("Failed to get der from pem")?;
         public_key_der
       } else {
         BASE64_STANDARD
@@ -133,7 +132,7 @@ impl SpkiPublicKey {
     // Create mock client handshake. The private key doesn't matter.
     // Trying to get the "server" public key.
     let mut client_handshake =
-      crate::NoiseHandshake::new_initiator("0000", &[])
+      crate::NoiseHandshake::new_initiator("0000", &[], None)
         .context("Failed to create client handshake")?;
     // Create mock server handshake.
     // Use the target private key with server handshake,
@@ -141,6 +140,7 @@ impl SpkiPublicKey {
     let mut server_handshake = crate::NoiseHandshake::new_responder(
       maybe_pkcs8_private_key,
       &[],
+      None,
     )
     .context("Failed to create server handshake")?;
     // write message 1
@@ -1,46 +1,56 @@
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 
+pub mod hardware;
 pub mod key;
 
 const NOISE_XX_PARAMS: &str = "Noise_XX_25519_ChaChaPoly_BLAKE2s";
+/// Mixes the pre-shared key in after the final handshake message.
+const NOISE_XX_PSK_PARAMS: &str =
+  "Noise_XXpsk3_25519_ChaChaPoly_BLAKE2s";
+
+/// The transport keys split from a completed handshake,
+/// unique to the connection. Both sides derive the same
+/// pair, with send / receive swapped.
+#[derive(Clone, Copy)]
+pub struct SessionKeys {
+  /// Encrypts payloads sent to the other side.
+  pub send: [u8; 32],
+  /// Decrypts payloads received from the other side.
+  pub receive: [u8; 32],
+}
+
+impl std::fmt::Debug for SessionKeys {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("SessionKeys").finish_non_exhaustive()
+  }
+}
 
 /// Wrapper around [snow::HandshakeState] to streamline this implementation
 pub struct NoiseHandshake(snow::HandshakeState);
 
 impl NoiseHandshake {
+  /// Accepts pkcs8 / base64 private key, or a hardware key spec
+  /// (see [hardware]).
+  /// Pass 'psk' to use `Noise_XXpsk3`, must match on both sides.
   pub fn new_initiator(
-    maybe_pkcs8_private_key: &str,
+    private_key_spec: &str,
     prologue: &[u8],
+    psk: Option<&[u8; 32]>,
   ) -> anyhow::Result<NoiseHandshake> {
-    let private_key =
-      key::Pkcs8PrivateKey::maybe_raw_bytes(maybe_pkcs8_private_key)?;
-    Ok(NoiseHandshake(
-      snow::Builder::new(NOISE_XX_PARAMS.parse()?)
-        .local_private_key(&private_key)
-        .context("Invalid private key")?
-        .prologue(prologue)
-        .context("Invalid prologue")?
-        .build_initiator()
-        .context("Failed to build initiator")?,
-    ))
+    build_handshake(private_key_spec, prologue, psk, true)
+      .map(NoiseHandshake)
   }
 
-  /// Should pass base64 encoded private key.
+  /// Accepts pkcs8 / base64 private key, or a hardware key spec
+  /// (see [hardware]).
+  /// Pass 'psk' to use `Noise_XXpsk3`, must match on both sides.
   pub fn new_responder(
-    maybe_pkcs8_private_key: &str,
+    private_key_spec: &str,
     prologue: &[u8],
+    psk: Option<&[u8; 32]>,
   ) -> anyhow::Result<NoiseHandshake> {
-    let private_key =
-      key::Pkcs8PrivateKey::maybe_raw_bytes(maybe_pkcs8_private_key)?;
-    Ok(NoiseHandshake(
-      snow::Builder::new(NOISE_XX_PARAMS.parse()?)
-        .local_private_key(&private_key)
-        .context("Invalid private key")?
-        .prologue(prologue)
-        .context("Invalid prologue")?
-        .build_responder()
-        .context("Failed to build responder")?,
-    ))
+    build_handshake(private_key_spec, prologue, psk, false)
+      .map(NoiseHandshake)
   }
 
   /// Reads message from other side of handshake
@@ -67,4 +77,72 @@ impl NoiseHandshake {
       .get_remote_static()
       .context("Failed to get remote public key")
   }
+
+  /// Gets the transport keys split from the handshake.
+  /// Note that this should only be called after m3 is written on client side,
+  /// or m3 is read on server side.
+  pub fn session_keys(&mut self) -> anyhow::Result<SessionKeys> {
+    if !self.0.is_handshake_finished() {
+      return Err(anyhow!(
+        "Cannot get session keys before the handshake is finished"
+      ));
+    }
+    let (initiator, responder) = self.0.dangerously_get_raw_split();
+    let keys = if self.0.is_initiator() {
+      SessionKeys {
+        send: initiator,
+        receive: responder,
+      }
+    } else {
+      SessionKeys {
+        send: responder,
+        receive: initiator,
+      }
+    };
+    Ok(keys)
+  }
+}
+
+fn build_handshake(
+  private_key_spec: &str,
+  prologue: &[u8],
+  psk: Option<&[u8; 32]>,
+  initiator: bool,
+) -> anyhow::Result<snow::HandshakeState> {
+  let params = if psk.is_some() {
+    NOISE_XX_PSK_PARAMS
+  } else {
+    NOISE_XX_PARAMS
+  }
+  .parse()?;
+  let software_private_key;
+  let builder = match hardware::load(private_key_spec)? {
+    Some(key) => snow::Builder::with_resolver(
+      params,
+      Box::new(hardware::HardwareResolver::new(key)),
+    )
+    .local_private_key(&hardware::PLACEHOLDER_PRIVATE_KEY),
+    None => {
+      software_private_key =
+        key::Pkcs8PrivateKey::maybe_raw_bytes(private_key_spec)?;
+      snow::Builder::new(params)
+        .local_private_key(&software_private_key)
+    }
+  }
+  .context("Invalid private key")?
+  .prologue(prologue)
+  .context("Invalid prologue")?;
+  let builder = match psk {
+    Some(psk) => builder.psk(3, psk).context("Invalid psk")?,
+    None => builder,
+  };
+  if initiator {
+    builder
+      .build_initiator()
+      .context("Failed to build initiator")
+  } else {
+    builder
+      .build_responder()
+      .context("Failed to build responder")
+  }
 }
@@ -1,13 +1,16 @@
 //! Implementes both sides of Noise handshake
 //! using asymmetric private-public key authentication.
 
-use std::time::Duration;
+use std::{sync::OnceLock, time::Duration};
 
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use axum::http::{HeaderMap, HeaderValue};
 use base64::{Engine, prelude::BASE64_STANDARD};
-use noise::{NoiseHandshake, key::SpkiPublicKey};
-use periphery_client::transport::LoginMessage;
+use noise::{NoiseHandshake, SessionKeys, key::SpkiPublicKey};
+use periphery_client::transport::{
+  LoginMessage, TransportCompression, TransportEncoding,
+  TransportOptions,
+};
 use rand::RngCore;
 use sha2::{Digest, Sha256};
 use tracing::warn;
@@ -29,15 +32,128 @@ pub struct LoginFlowArgs<'a, 's, V, W> {
   pub private_key: &'a str,
   pub public_key_validator: V,
   pub socket: &'s mut W,
+  /// Server side: The options to use for the connection,
+  /// sent to the client with the login success.
+  /// See [negotiate_options].
+  ///
+  /// Client side: Ignored, the server decides.
+  pub options: TransportOptions,
+}
+
+pub struct LoginSuccess<T> {
+  pub validation: T,
+  /// The options negotiated for the connection.
+  pub options: TransportOptions,
+  /// The transport keys split from the handshake.
+  pub session_keys: SessionKeys,
+}
+
+impl<T> LoginSuccess<T> {
+  /// The keys to encrypt sensitive payloads with,
+  /// if [TransportOptions::encryption] was negotiated.
+  pub fn payload_keys(&self) -> Option<SessionKeys> {
+    self.options.encryption.then_some(self.session_keys)
+  }
 }
 
 pub trait LoginFlow {
   fn login<'a, 's, V: PublicKeyValidator, W: Websocket>(
     args: LoginFlowArgs<'a, 's, V, W>,
-  ) -> impl Future<Output = anyhow::Result<V::ValidationResult>>;
+  ) -> impl Future<
+    Output = anyhow::Result<LoginSuccess<V::ValidationResult>>,
+  >;
 }
 
-pub const AUTH_TIMEOUT: Duration = Duration::from_secs(2);
+/// Query params for the client to advertise the transport options
+/// it supports. These are kept out of the query used for connection
+/// identifiers, so older servers which ignore them still derive
+/// the same handshake prologue.
+pub fn advertise_options_query(
+  compression: TransportCompression,
+  encoding: TransportEncoding,
+) -> String {
+  let mut query = String::from(
+    "chunking=true&keepalive=true&encryption=true&header=true",
+  );
+  if compression != TransportCompression::None {
+    query.push_str("&compression=");
+    query.push_str(compression.as_str());
+  }
+  if encoding != TransportEncoding::Json {
+    query.push_str("&encoding=");
+    query.push_str(encoding.as_str());
+  }
+  query
+}
+
+/// Server side: The options to use for a connection, given the local
+/// compression / encoding config and the options advertised by the client.
+/// Older clients advertise nothing, and get no options.
+pub fn negotiate_options(
+  local_compression: TransportCompression,
+  local_encoding: TransportEncoding,
+  client_compression: TransportCompression,
+  client_encoding: TransportEncoding,
+  client_chunking: bool,
+  client_keepalive: bool,
+  client_encryption: bool,
+  client_header: bool,
+) -> TransportOptions {
+  TransportOptions {
+    compression: if local_compression == client_compression {
+      local_compression
+    } else {
+      TransportCompression::None
+    },
+    chunking: client_chunking,
+    keepalive: client_keepalive,
+    encoding: if local_encoding == client_encoding {
+      local_encoding
+    } else {
+      TransportEncoding::Json
+    },
+    encryption: client_encryption,
+    header: client_header,
+  }
+}
+
+/// The default timeout waiting for each login message.
+pub const DEFAULT_AUTH_TIMEOUT: Duration = Duration::from_secs(2);
+
+static AUTH_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
+/// Configure the timeout waiting for each login message.
+/// Should be called once on startup, before any connections are made.
+pub fn set_auth_timeout(timeout: Duration) {
+  if AUTH_TIMEOUT.set(timeout).is_err() {
+    warn!("Auth timeout already set, ignoring {timeout:?}");
+  }
+}
+
+/// The timeout waiting for each login message.
+/// Defaults to [DEFAULT_AUTH_TIMEOUT] if not configured.
+pub fn auth_timeout() -> Duration {
+  AUTH_TIMEOUT.get().copied().unwrap_or(DEFAULT_AUTH_TIMEOUT)
+}
+
+static TRANSPORT_PSK: OnceLock<[u8; 32]> = OnceLock::new();
+
+/// Configure the pre-shared key mixed into the Noise handshake.
+/// The configured secret is hashed into the 32 byte key.
+/// Should be called once on startup, before any connections are made.
+pub fn set_transport_psk(psk: &str) {
+  if psk.is_empty() {
+    return;
+  }
+  if TRANSPORT_PSK.set(Sha256::digest(psk).into()).is_err() {
+    warn!("Transport psk already set, ignoring");
+  }
+}
+
+/// The pre-shared key used with the handshake, if configured.
+pub fn transport_psk() -> Option<&'static [u8; 32]> {
+  TRANSPORT_PSK.get()
+}
 
 pub struct ServerLoginFlow;
 
@@ -48,14 +164,19 @@ impl LoginFlow for ServerLoginFlow {
       private_key,
       public_key_validator,
       socket,
+      options,
     }: LoginFlowArgs<'a, 's, V, W>,
-  ) -> anyhow::Result<V::ValidationResult> {
+  ) -> anyhow::Result<LoginSuccess<V::ValidationResult>> {
     // Server generates random nonce / uuid and sends to client
     let nonce = nonce();
+    let psk = transport_psk();
 
     let res = async {
       socket
-        .send_message(LoginMessage::Nonce(nonce))
+        .send_message(LoginMessage::Nonce {
+          nonce,
+          psk: psk.is_some(),
+        })
         .await
         .context("Failed to send connection nonce")?;
 
@@ -64,6 +185,7 @@ impl LoginFlow for ServerLoginFlow {
         // Builds the handshake using the connection-unique prologue hash.
         // The prologue must be the same on both sides of connection.
         &identifiers.hash(&nonce),
+        psk,
       )
       .context("Failed to inialize handshake")?;
 
@@ -94,23 +216,31 @@ impl LoginFlow for ServerLoginFlow {
         .read_message(&handshake_m3)
         .context("Failed to read handshake_m3")?;
 
+      let session_keys = handshake.session_keys()?;
+
       // Server now has client public key
       let public_key =
         SpkiPublicKey::from_raw_bytes(handshake.remote_public_key()?)
           .context("Invalid public key")?
           .into_inner();
 
-      public_key_validator.validate(public_key).await
+      let validation =
+        public_key_validator.validate(public_key).await?;
+      anyhow::Ok((validation, session_keys))
     }
     .await;
 
     match res {
-      Ok(res) => {
+      Ok((validation, session_keys)) => {
         socket
-          .send_message(LoginMessage::Success)
+          .send_message(LoginMessage::Success(options))
           .await
           .context("Failed to send login successful to client")?;
-        Ok(res)
+        Ok(LoginSuccess {
+          validation,
+          options,
+          session_keys,
+        })
       }
       Err(e) => {
         if let Err(e) = socket
@@ -139,20 +269,39 @@ impl LoginFlow for ClientLoginFlow {
       private_key,
       public_key_validator,
       socket,
+      options: _,
     }: LoginFlowArgs<'a, 's, V, W>,
-  ) -> anyhow::Result<V::ValidationResult> {
+  ) -> anyhow::Result<LoginSuccess<V::ValidationResult>> {
     let res = async {
       // Receive nonce and channel from server
-      let nonce = socket
+      let (nonce, server_psk) = socket
         .recv_login_nonce()
         .await
         .context("Failed to receive connection nonce")?;
 
+      // Fail clearly on mismatch, rather than with
+      // an opaque handshake decryption error.
+      let psk = transport_psk();
+      match (psk.is_some(), server_psk) {
+        (true, false) => {
+          return Err(anyhow!(
+            "Transport psk mismatch | 'transport_psk' is configured, but the server does not use one"
+          ));
+        }
+        (false, true) => {
+          return Err(anyhow!(
+            "Transport psk mismatch | The server requires a 'transport_psk', but none is configured"
+          ));
+        }
+        _ => {}
+      }
+
       let mut handshake = NoiseHandshake::new_initiator(
         private_key,
         // Builds the handshake using the connection-unique prologue hash.
         // The prologue must be the same on both sides of connection.
         &identifiers.hash(&nonce),
+        psk,
       )
       .context("Failed to inialize handshake")?;
 
@@ -191,14 +340,19 @@ impl LoginFlow for ClientLoginFlow {
         .send_message(LoginMessage::Handshake(handshake_m3))
         .await
         .context("Failed to send handshake_m3")?;
+      let session_keys = handshake.session_keys()?;
 
       // Receive login sucessful
-      socket
+      let options = socket
         .recv_login_success()
         .await
         .context("Failed to receive Login Success message")?;
 
-      anyhow::Ok(validation_result)
+      anyhow::Ok(LoginSuccess {
+        validation: validation_result,
+        options,
+        session_keys,
+      })
     }
     .await;
 
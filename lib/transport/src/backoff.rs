@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use rand::Rng as _;
+
+/// The default cap on the delay between connection retries.
+pub const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Exponential backoff with jitter for connection retries.
+/// Spreads out reconnects when many agents lose their
+/// connection at once, eg. after a Core restart.
+pub struct Backoff {
+  base: Duration,
+  max: Duration,
+  attempt: u32,
+  /// Retry immediately on the next wait,
+  /// set after a healthy connection ends.
+  immediate: bool,
+}
+
+impl Backoff {
+  /// The delay doubles from `base` up to `max` on each failed attempt.
+  /// `max` is raised to `base` if smaller.
+  pub fn new(base: Duration, max: Duration) -> Backoff {
+    Backoff {
+      base,
+      max: max.max(base),
+      attempt: 0,
+      immediate: false,
+    }
+  }
+
+  /// The next delay, randomized between half and
+  /// the full exponential delay.
+  pub fn next_delay(&mut self) -> Duration {
+    if std::mem::take(&mut self.immediate) {
+      return Duration::ZERO;
+    }
+    let delay = self
+      .base
+      .saturating_mul(2u32.saturating_pow(self.attempt))
+      .min(self.max);
+    self.attempt = self.attempt.saturating_add(1);
+    let half = delay / 2;
+    half + half.mul_f64(rand::rng().random::<f64>())
+  }
+
+  /// Sleep for the next delay.
+  pub async fn wait(&mut self) {
+    tokio::time::sleep(self.next_delay()).await
+  }
+
+  /// Call after a connection is established.
+  /// The first retry after it ends is immediate,
+  /// then the delay starts over from `base`.
+  pub fn reset(&mut self) {
+    self.attempt = 0;
+    self.immediate = true;
+  }
+}
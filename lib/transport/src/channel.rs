@@ -1,21 +1,76 @@
+use std::{
+  collections::VecDeque,
+  sync::atomic::{AtomicUsize, Ordering},
+  time::{Duration, Instant},
+};
+
 use anyhow::{Context, anyhow};
+use bytes::Bytes;
 use encoding::{
-  Encode, EncodedJsonMessage, EncodedResponse, JsonMessage,
+  CastBytes, Encode, EncodedBodyMessage, EncodedResponse, JsonMessage,
 };
 use futures_util::FutureExt;
 use periphery_client::transport::{
-  EncodedTransportMessage, RequestMessage, ResponseMessage,
-  TerminalMessage,
+  EncodedTransportMessage, PeripheryEvent, RequestMessage,
+  ResponseMessage, TerminalMessage, TransportMessageVariant,
+  TransportNotice,
 };
 use serde::Serialize;
-use tokio::sync::{Mutex, MutexGuard, mpsc};
+use tokio::sync::{
+  Mutex, MutexGuard,
+  mpsc::{self, error::TrySendError},
+};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
-use crate::timeout::MaybeWithTimeout;
+use crate::{spillover::Spillover, timeout::MaybeWithTimeout};
 
 const RESPONSE_BUFFER_MAX_LEN: usize = 1_024;
 
+/// [BufferedReceiver] lanes take half the capacity, and the channel
+/// feeding them the rest, so buffered channels still hold
+/// [RESPONSE_BUFFER_MAX_LEN] messages in total.
+const PRIORITY_LANES_MAX_LEN: usize = RESPONSE_BUFFER_MAX_LEN / 2;
+
+/// Shed messages are logged at most once per interval.
+const SHED_LOG_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The lanes of a [BufferedReceiver], highest priority first.
+/// Queued messages are sent from the highest priority lane
+/// first, so a flood of terminal bytes can't starve health checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+  Login,
+  /// Requests, responses and other connection messages.
+  Request,
+  Terminal,
+  /// Shed instead of waiting when the channel is full.
+  Stats,
+}
+
+impl Priority {
+  const LANES: usize = 4;
+
+  fn lane(self) -> usize {
+    match self {
+      Priority::Login => 0,
+      Priority::Request => 1,
+      Priority::Terminal => 2,
+      Priority::Stats => 3,
+    }
+  }
+}
+
+impl From<TransportMessageVariant> for Priority {
+  fn from(variant: TransportMessageVariant) -> Self {
+    match variant {
+      TransportMessageVariant::Login => Priority::Login,
+      TransportMessageVariant::Terminal => Priority::Terminal,
+      _ => Priority::Request,
+    }
+  }
+}
+
 #[derive(Debug)]
 pub struct BufferedChannel<T> {
   pub sender: Sender<T>,
@@ -45,7 +100,13 @@ impl<T> BufferedChannel<T> {
 
 /// Create a channel
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
-  let (sender, receiver) = mpsc::channel(RESPONSE_BUFFER_MAX_LEN);
+  channel_with_capacity(RESPONSE_BUFFER_MAX_LEN)
+}
+
+fn channel_with_capacity<T>(
+  capacity: usize,
+) -> (Sender<T>, Receiver<T>) {
+  let (sender, receiver) = mpsc::channel(capacity);
   (
     Sender(sender),
     Receiver {
@@ -58,25 +119,82 @@ pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
 /// Create a buffered channel
 pub fn buffered_channel<T: Send + Clone>()
 -> (Sender<T>, BufferedReceiver<T>) {
-  let (sender, receiver) = channel();
+  let (sender, receiver) = channel_with_capacity(
+    RESPONSE_BUFFER_MAX_LEN - PRIORITY_LANES_MAX_LEN,
+  );
   (sender, BufferedReceiver::new(receiver))
 }
 
 #[derive(Debug, Clone)]
-pub struct Sender<T>(mpsc::Sender<T>);
+pub struct Sender<T>(mpsc::Sender<(Priority, T)>);
 
 impl<T> Sender<T> {
+  /// Whether the receiver has been dropped.
+  pub fn is_closed(&self) -> bool {
+    self.0.is_closed()
+  }
+
   pub async fn send(&self, data: T) -> anyhow::Result<()> {
-    self.0.send(data).await.map_err(|e| anyhow!("{e:?}"))
+    self.send_with_priority(data, Priority::Request).await
+  }
+
+  /// Waits for room in the channel, except for
+  /// [Priority::Stats] messages, which are dropped with
+  /// an error if the channel is full.
+  pub async fn send_with_priority(
+    &self,
+    data: T,
+    priority: Priority,
+  ) -> anyhow::Result<()> {
+    if priority != Priority::Stats {
+      return self
+        .0
+        .send((priority, data))
+        .await
+        .map_err(|e| anyhow!("{e:?}"));
+    }
+    match self.0.try_send((priority, data)) {
+      Ok(()) => Ok(()),
+      Err(TrySendError::Full(_)) => {
+        record_shed();
+        Err(anyhow!("Channel is full, dropped low priority message"))
+      }
+      Err(e) => Err(anyhow!("{e:?}")),
+    }
   }
 }
 
+/// Logs shed messages at most once per [SHED_LOG_INTERVAL],
+/// with the count since, so a busy connection doesn't
+/// flood the logs as well.
+fn record_shed() {
+  static SHED: AtomicUsize = AtomicUsize::new(0);
+  static LOGGED_AT: std::sync::Mutex<Option<Instant>> =
+    std::sync::Mutex::new(None);
+  SHED.fetch_add(1, Ordering::Relaxed);
+  let mut logged_at = LOGGED_AT.lock().unwrap();
+  if logged_at.is_some_and(|at| at.elapsed() < SHED_LOG_INTERVAL) {
+    return;
+  }
+  *logged_at = Some(Instant::now());
+  let shed = SHED.swap(0, Ordering::Relaxed);
+  tracing::debug!(
+    "Channel is full, shed {shed} low priority messages"
+  );
+}
+
 impl Sender<EncodedTransportMessage> {
+  /// Sends in the lane for the message variant.
   pub async fn send_message(
     &self,
     message: impl Encode<EncodedTransportMessage>,
   ) -> anyhow::Result<()> {
-    self.send(message.encode()).await
+    let message = message.encode();
+    let priority = message
+      .variant()
+      .map(Priority::from)
+      .unwrap_or(Priority::Request);
+    self.send_with_priority(message, priority).await
   }
 
   pub async fn send_request<'a, T: Serialize + Send>(
@@ -106,27 +224,84 @@ impl Sender<EncodedTransportMessage> {
   pub async fn send_response(
     &self,
     channel: Uuid,
-    response: EncodedResponse<EncodedJsonMessage>,
+    response: EncodedResponse<EncodedBodyMessage>,
   ) -> anyhow::Result<()> {
     self
       .send_message(ResponseMessage::new(channel, response))
       .await
   }
 
+  /// Sends incremental output for a streamed request,
+  /// ahead of the final response on the same channel.
+  pub async fn send_stream_chunk(
+    &self,
+    channel: Uuid,
+    chunk: EncodedBodyMessage,
+  ) -> anyhow::Result<()> {
+    self
+      .send_message(ResponseMessage::new(
+        channel,
+        encoding::Response::StreamChunk(chunk).encode(),
+      ))
+      .await
+  }
+
+  /// Like [Sender::send_response], in the lowest priority lane.
+  /// Used for stats, which are polled again if dropped. A shed
+  /// response is answered with an error instead, so the request
+  /// fails right away rather than waiting on its timeout.
+  pub async fn send_stats_response(
+    &self,
+    channel: Uuid,
+    response: EncodedResponse<EncodedBodyMessage>,
+  ) -> anyhow::Result<()> {
+    let res = self
+      .send_with_priority(
+        ResponseMessage::new(channel, response).encode(),
+        Priority::Stats,
+      )
+      .await;
+    if res.is_ok() || self.is_closed() {
+      return res;
+    }
+    let e = anyhow!(
+      "Stats response was shed, the connection is busy. Poll again later."
+    );
+    self.send_response(channel, (&e).encode()).await
+  }
+
   pub async fn send_terminal(
     &self,
     channel: Uuid,
-    data: impl Into<Vec<u8>>,
+    data: impl Into<Bytes>,
   ) -> anyhow::Result<()> {
     self
       .send_message(TerminalMessage::new(channel, data.into()))
       .await
   }
+
+  pub async fn send_notice(
+    &self,
+    notice: &TransportNotice,
+  ) -> anyhow::Result<()> {
+    self.send(notice.encode()?).await
+  }
+
+  /// Events are sent in the lowest priority lane,
+  /// and dropped if the channel is full.
+  pub async fn send_event(
+    &self,
+    event: &PeripheryEvent,
+  ) -> anyhow::Result<()> {
+    self
+      .send_with_priority(event.encode()?, Priority::Stats)
+      .await
+  }
 }
 
 #[derive(Debug)]
 pub struct Receiver<T> {
-  receiver: mpsc::Receiver<T>,
+  receiver: mpsc::Receiver<(Priority, T)>,
   cancel: Option<CancellationToken>,
 }
 
@@ -144,7 +319,10 @@ impl<T: Send> Receiver<T> {
     {
       return std::task::Poll::Ready(None);
     }
-    self.receiver.poll_recv(cx)
+    self
+      .receiver
+      .poll_recv(cx)
+      .map(|message| message.map(|(_, data)| data))
   }
 
   pub fn recv(
@@ -152,37 +330,55 @@ impl<T: Send> Receiver<T> {
   ) -> MaybeWithTimeout<impl Future<Output = anyhow::Result<T>> + Send>
   {
     MaybeWithTimeout::new(async {
-      let recv = self
-        .receiver
-        .recv()
-        .map(|res| res.context("Channel is permanently closed"));
-      if let Some(cancel) = &self.cancel {
-        tokio::select! {
-          message = recv => message,
-          _ = cancel.cancelled() => Err(anyhow!("Stream cancelled"))
-        }
-      } else {
-        recv.await
-      }
+      self.recv_with_priority().await.map(|(_, data)| data)
     })
   }
+
+  async fn recv_with_priority(
+    &mut self,
+  ) -> anyhow::Result<(Priority, T)> {
+    let recv = self
+      .receiver
+      .recv()
+      .map(|res| res.context("Channel is permanently closed"));
+    if let Some(cancel) = &self.cancel {
+      tokio::select! {
+        message = recv => message,
+        _ = cancel.cancelled() => Err(anyhow!("Stream cancelled"))
+      }
+    } else {
+      recv.await
+    }
+  }
 }
 
 /// Control when the latest message is dropped, in case it must be re-transmitted.
+///
+/// Queued messages are taken into priority lanes,
+/// and the highest priority message is received first.
 #[derive(Debug)]
 pub struct BufferedReceiver<T> {
   receiver: Receiver<T>,
+  lanes: [VecDeque<T>; Priority::LANES],
   buffer: Option<T>,
+  spillover: Option<Spillover>,
 }
 
 impl<T: Send + Clone> BufferedReceiver<T> {
   pub fn new(receiver: Receiver<T>) -> BufferedReceiver<T> {
     BufferedReceiver {
       receiver,
+      lanes: Default::default(),
       buffer: None,
+      spillover: None,
     }
   }
 
+  /// Persist queued requests to disk with [BufferedReceiver::spill].
+  pub fn set_spillover(&mut self, spillover: Spillover) {
+    self.spillover = Some(spillover);
+  }
+
   pub fn set_cancel(&mut self, cancel: CancellationToken) {
     self.receiver.set_cancel(cancel);
   }
@@ -191,7 +387,8 @@ impl<T: Send + Clone> BufferedReceiver<T> {
   ///   - Immediately returns borrow of buffer.
   /// - Else:
   ///   - Wait for next item.
-  ///   - store in buffer.
+  ///   - Take any other queued items into the lanes.
+  ///   - store highest priority item in buffer.
   ///   - return borrow of buffer.
   pub fn recv(
     &mut self,
@@ -199,12 +396,29 @@ impl<T: Send + Clone> BufferedReceiver<T> {
   {
     MaybeWithTimeout::new(async {
       if let Some(buffer) = self.buffer.clone() {
-        Ok(buffer)
-      } else {
-        let message = self.receiver.recv().await?;
-        self.buffer = Some(message.clone());
-        Ok(message)
+        return Ok(buffer);
       }
+      if self.lanes.iter().all(VecDeque::is_empty) {
+        let (priority, message) =
+          self.receiver.recv_with_priority().await?;
+        self.lanes[priority.lane()].push_back(message);
+      }
+      // Only take up to the lane capacity, past that
+      // senders wait (or shed stats) as usual.
+      while self.lanes.iter().map(VecDeque::len).sum::<usize>()
+        < PRIORITY_LANES_MAX_LEN
+        && let Ok((priority, message)) =
+          self.receiver.receiver.try_recv()
+      {
+        self.lanes[priority.lane()].push_back(message);
+      }
+      let message = self
+        .lanes
+        .iter_mut()
+        .find_map(VecDeque::pop_front)
+        .context("Priority lanes are empty")?;
+      self.buffer = Some(message.clone());
+      Ok(message)
     })
   }
 
@@ -214,3 +428,90 @@ impl<T: Send + Clone> BufferedReceiver<T> {
     self.buffer = None;
   }
 }
+
+impl<T: Send + Clone + CastBytes> BufferedReceiver<T> {
+  /// Moves the unconfirmed buffer and queued [Priority::Request]
+  /// messages passing `keep` to the spillover, if set.
+  /// The others are dropped. Call when the connection goes down.
+  ///
+  /// Login, terminal and stats messages are stale by the time
+  /// the connection comes back, so they are always dropped.
+  pub async fn spill(&mut self, keep: impl Fn(&T) -> bool) {
+    while let Ok((priority, message)) =
+      self.receiver.receiver.try_recv()
+    {
+      self.lanes[priority.lane()].push_back(message);
+    }
+    for priority in
+      [Priority::Login, Priority::Terminal, Priority::Stats]
+    {
+      self.lanes[priority.lane()].clear();
+    }
+    let Some(spillover) = &self.spillover else {
+      return;
+    };
+    let messages = self
+      .buffer
+      .take()
+      .into_iter()
+      .chain(self.lanes[Priority::Request.lane()].drain(..))
+      .filter(keep)
+      .map(CastBytes::into_vec)
+      .collect::<Vec<_>>();
+    if messages.is_empty() {
+      return;
+    }
+    let count = messages.len();
+    match spillover.write(messages).await {
+      Ok(0) => {
+        tracing::debug!("Spilled {count} queued messages to disk")
+      }
+      Ok(dropped) => tracing::warn!(
+        "Spillover is full, dropped {dropped} of {count} queued messages"
+      ),
+      Err(e) => tracing::warn!(
+        "Failed to spill {count} queued messages to disk | {e:#}"
+      ),
+    }
+  }
+
+  /// Queues the spilled messages `key` returns Some for
+  /// to be sent first, the others are dropped. `key` is also
+  /// passed whether the message was spilled before a restart.
+  /// Returns the keys of the replayed messages, so callers
+  /// can skip sending them again.
+  /// Call when the connection is (re)established.
+  pub async fn replay_spillover<K>(
+    &mut self,
+    key: impl Fn(&T, bool) -> Option<K>,
+  ) -> Vec<K> {
+    let Some(spillover) = &self.spillover else {
+      return Vec::new();
+    };
+    let messages = match spillover.take().await {
+      Ok(messages) => messages,
+      Err(e) => {
+        tracing::warn!("Failed to read spilled messages | {e:#}");
+        return Vec::new();
+      }
+    };
+    let mut keys = Vec::new();
+    let messages = messages
+      .into_iter()
+      .filter_map(|spilled| {
+        let message = T::from_vec(spilled.message);
+        keys.push(key(&message, spilled.previous_run)?);
+        Some(message)
+      })
+      .collect::<Vec<_>>();
+    if messages.is_empty() {
+      return keys;
+    }
+    tracing::debug!("Replaying {} spilled messages", messages.len());
+    let lane = &mut self.lanes[Priority::Request.lane()];
+    for message in messages.into_iter().rev() {
+      lane.push_front(message);
+    }
+    keys
+  }
+}
@@ -0,0 +1,105 @@
+use anyhow::anyhow;
+use encoding::{CastBytes as _, Decode as _, decode_limits};
+use periphery_client::transport::{
+  ChunkMessage, EncodedTransportMessage, TransportMessage,
+  TransportOptions,
+};
+use uuid::Uuid;
+
+/// Reassembles messages split into Chunk messages.
+/// Each connection needs its own assembler.
+///
+/// The chunks of a message are sent in order,
+/// and one message at a time, over the connection.
+pub struct ChunkAssembler {
+  pending: Option<PendingMessage>,
+  /// [TransportOptions::header] negotiated for the connection.
+  /// Legacy chunks contain pieces of a legacy framed message.
+  header: bool,
+}
+
+struct PendingMessage {
+  id: Uuid,
+  total: u32,
+  next: u32,
+  bytes: Vec<u8>,
+}
+
+impl ChunkAssembler {
+  pub fn new(options: TransportOptions) -> ChunkAssembler {
+    ChunkAssembler {
+      pending: None,
+      header: options.header,
+    }
+  }
+
+  /// Messages which aren't chunked pass straight through.
+  /// Chunks are buffered until the last one arrives,
+  /// then the full message is returned.
+  pub fn reassemble(
+    &mut self,
+    message: TransportMessage,
+  ) -> anyhow::Result<Option<TransportMessage>> {
+    let TransportMessage::Chunk(chunk) = message else {
+      return Ok(Some(message));
+    };
+    let ChunkMessage {
+      message: id,
+      index,
+      total,
+      data,
+    } = chunk.decode()?;
+
+    let mut pending = match self.pending.take() {
+      Some(pending) if pending.id == id => pending,
+      // A new message starts. Any partial message was
+      // abandoned by the sender.
+      _ if index == 0 && total > 0 => PendingMessage {
+        id,
+        total,
+        next: 0,
+        bytes: Vec::new(),
+      },
+      _ => {
+        return Err(anyhow!(
+          "Received chunk {index} / {total} of unknown message {id}"
+        ));
+      }
+    };
+
+    if index != pending.next || total != pending.total {
+      return Err(anyhow!(
+        "Received out of order chunk {index} / {total} of message {id}"
+      ));
+    }
+
+    // Reassembled messages past the limit are rejected,
+    // so a misbehaving peer can't exhaust memory.
+    let max_bytes = decode_limits().max_message_bytes;
+    if pending.bytes.len() + data.len() > max_bytes {
+      return Err(anyhow!(
+        "Chunked message {id} exceeds max size of {max_bytes} bytes"
+      ));
+    }
+
+    pending.bytes.extend_from_slice(&data);
+    pending.next += 1;
+
+    if pending.next < pending.total {
+      self.pending = Some(pending);
+      return Ok(None);
+    }
+
+    let message = EncodedTransportMessage::from_vec(pending.bytes)
+      .normalize_framing(self.header)?
+      .decode()?;
+
+    if let TransportMessage::Chunk(_) = message {
+      return Err(anyhow!(
+        "Chunked message {id} contains another chunk"
+      ));
+    }
+
+    Ok(Some(message))
+  }
+}
@@ -1,14 +1,24 @@
 pub mod auth;
+pub mod backoff;
 pub mod channel;
+pub mod chunk;
+pub mod proxy;
+pub mod rate_limit;
+pub mod spillover;
 pub mod timeout;
+pub mod tls;
 pub mod websocket;
 
 /// - Fixes ws addresses:
 ///   - `server.domain` => `wss://server.domain`
 ///   - `http://server.domain` => `ws://server.domain`
 ///   - `https://server.domain` => `wss://server.domain`
+///   - `quic://server.domain:8120` is kept for the QUIC transport
 pub fn fix_ws_address(address: &str) -> String {
-  if address.starts_with("ws://") || address.starts_with("wss://") {
+  if address.starts_with("ws://")
+    || address.starts_with("wss://")
+    || address.starts_with("quic://")
+  {
     return address.to_string();
   }
   if address.starts_with("http://") {
@@ -0,0 +1,55 @@
+use std::{
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+/// Token bucket limiting the bytes per second on a connection,
+/// so one busy connection can't starve the others.
+///
+/// Messages larger than the bucket still go through,
+/// the following messages wait until the debt is paid back.
+#[derive(Debug)]
+pub struct RateLimiter {
+  /// Bytes added per second. 0 disables the limit.
+  bytes_per_sec: f64,
+  /// (available bytes, last refill)
+  bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+  /// 0 disables the limit. Bursts of up to
+  /// one second worth of bytes are allowed.
+  pub fn new(bytes_per_sec: u64) -> RateLimiter {
+    let bytes_per_sec = bytes_per_sec as f64;
+    RateLimiter {
+      bytes_per_sec,
+      bucket: Mutex::new((bytes_per_sec, Instant::now())),
+    }
+  }
+
+  /// Waits until the bytes fit in the limit.
+  pub async fn acquire(&self, bytes: usize) {
+    if self.bytes_per_sec == 0.0 {
+      return;
+    }
+    let wait = {
+      let mut bucket = self.bucket.lock().unwrap();
+      let (available, last) = &mut *bucket;
+      let now = Instant::now();
+      *available = (*available
+        + now.duration_since(*last).as_secs_f64()
+          * self.bytes_per_sec)
+        .min(self.bytes_per_sec);
+      *last = now;
+      *available -= bytes as f64;
+      if *available < 0.0 {
+        Duration::from_secs_f64(-*available / self.bytes_per_sec)
+      } else {
+        Duration::ZERO
+      }
+    };
+    if !wait.is_zero() {
+      tokio::time::sleep(wait).await;
+    }
+  }
+}
@@ -0,0 +1,156 @@
+use std::{path::PathBuf, sync::LazyLock};
+
+use anyhow::Context;
+use uuid::Uuid;
+
+/// Spilled messages are stored as files named by sequence number.
+const MESSAGE_EXTENSION: &str = "msg";
+
+/// Written before the message in each file, to tell
+/// the messages spilled by this process from those
+/// spilled before a restart.
+static RUN_ID: LazyLock<Uuid> = LazyLock::new(Uuid::new_v4);
+const RUN_ID_LEN: usize = 16;
+
+/// Persists the messages queued on a connection to disk
+/// when it goes down, so they can be replayed once it reconnects,
+/// including after a restart.
+///
+/// Each message is stored in its own file in the `directory`,
+/// only readable by the owner, so a crash can at most lose
+/// the message being written.
+#[derive(Debug, Clone)]
+pub struct Spillover {
+  directory: PathBuf,
+  /// Messages which don't fit are dropped.
+  max_bytes: u64,
+}
+
+/// A message read back with [Spillover::take].
+#[derive(Debug)]
+pub struct Spilled {
+  pub message: Vec<u8>,
+  /// Spilled by a previous process. Its caller is gone,
+  /// but it may still need to run to finish what was started.
+  pub previous_run: bool,
+}
+
+impl Spillover {
+  pub fn new(directory: PathBuf, max_bytes: u64) -> Spillover {
+    Spillover {
+      directory,
+      max_bytes,
+    }
+  }
+
+  /// Writes the messages after any already spilled.
+  /// Returns the number of messages dropped for exceeding `max_bytes`.
+  pub async fn write(
+    &self,
+    messages: impl IntoIterator<Item = Vec<u8>>,
+  ) -> anyhow::Result<usize> {
+    let run_id = RUN_ID.as_bytes();
+    let existing = self.list().await?;
+    let mut next =
+      existing.last().map(|(seq, _)| seq + 1).unwrap_or(0);
+    let mut size = 0;
+    for (_, path) in &existing {
+      size += tokio::fs::metadata(path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or_default();
+    }
+    let mut dropped = 0;
+    for message in messages {
+      let len = (run_id.len() + message.len()) as u64;
+      if size + len > self.max_bytes {
+        dropped += 1;
+        continue;
+      }
+      let path = self.message_path(next);
+      let contents = [run_id.as_slice(), &message].concat();
+      // Write then rename so partial files are never replayed.
+      let tmp = path.with_extension("tmp");
+      secret_file::write_async(&tmp, &contents)
+        .await
+        .with_context(|| format!("Failed to write {tmp:?}"))?;
+      tokio::fs::rename(&tmp, &path)
+        .await
+        .with_context(|| format!("Failed to move {tmp:?}"))?;
+      size += len;
+      next += 1;
+    }
+    Ok(dropped)
+  }
+
+  /// Reads and removes all spilled messages, oldest first.
+  pub async fn take(&self) -> anyhow::Result<Vec<Spilled>> {
+    let mut messages = Vec::new();
+    for (_, path) in self.list().await? {
+      let mut message = tokio::fs::read(&path)
+        .await
+        .with_context(|| format!("Failed to read {path:?}"))?;
+      tokio::fs::remove_file(&path)
+        .await
+        .with_context(|| format!("Failed to remove {path:?}"))?;
+      let Some(run_id) = message.get(..RUN_ID_LEN) else {
+        tracing::warn!("Dropping truncated spilled message {path:?}");
+        continue;
+      };
+      let previous_run = run_id != RUN_ID.as_bytes();
+      message.drain(..RUN_ID_LEN);
+      messages.push(Spilled {
+        message,
+        previous_run,
+      });
+    }
+    Ok(messages)
+  }
+
+  /// The spilled message files, sorted by sequence.
+  async fn list(&self) -> anyhow::Result<Vec<(u64, PathBuf)>> {
+    let mut dir = match tokio::fs::read_dir(&self.directory).await {
+      Ok(dir) => dir,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+        return Ok(Vec::new());
+      }
+      Err(e) => {
+        return Err(e).with_context(|| {
+          format!(
+            "Failed to read spillover directory {:?}",
+            self.directory
+          )
+        });
+      }
+    };
+    let mut files = Vec::new();
+    while let Some(entry) = dir
+      .next_entry()
+      .await
+      .context("Failed to read spillover directory entry")?
+    {
+      let path = entry.path();
+      if path.extension().and_then(|ext| ext.to_str())
+        != Some(MESSAGE_EXTENSION)
+      {
+        continue;
+      }
+      let Some(seq) = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.parse::<u64>().ok())
+      else {
+        continue;
+      };
+      files.push((seq, path));
+    }
+    files.sort_unstable_by_key(|(seq, _)| *seq);
+    Ok(files)
+  }
+
+  fn message_path(&self, seq: u64) -> PathBuf {
+    self
+      .directory
+      .join(format!("{seq:020}.{MESSAGE_EXTENSION}"))
+  }
+}
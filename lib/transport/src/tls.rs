@@ -0,0 +1,263 @@
+//! Client side verification of the server TLS certificate.
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::Context as _;
+use base64::{Engine as _, prelude::BASE64_STANDARD};
+use der::{Decode as _, Encode as _, SliceReader, asn1::AnyRef};
+use rustls::{
+  ClientConfig, DigitallySignedStruct, RootCertStore,
+  SignatureScheme,
+  client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+  },
+  crypto::{
+    CryptoProvider, verify_tls12_signature, verify_tls13_signature,
+  },
+  pki_types::{CertificateDer, ServerName, UnixTime},
+};
+use sha2::{Digest as _, Sha256};
+
+const PIN_PREFIX: &str = "sha256/";
+
+/// How the client verifies the server TLS certificate.
+#[derive(Debug, Clone)]
+pub enum TlsVerify {
+  /// Verify the certificate chain against the system CA roots.
+  System,
+  /// Only accept certificates matching one of the pins.
+  /// The hostname and chain are not verified, so this
+  /// works with self-signed certificates.
+  Pinned(Arc<PinnedVerifier>),
+  /// Skip verification.
+  Insecure,
+}
+
+impl TlsVerify {
+  pub fn from_insecure(insecure: bool) -> TlsVerify {
+    if insecure {
+      TlsVerify::Insecure
+    } else {
+      TlsVerify::System
+    }
+  }
+
+  pub fn pinned(
+    pins: impl IntoIterator<Item = impl Into<String>>,
+  ) -> TlsVerify {
+    TlsVerify::Pinned(Arc::new(PinnedVerifier::new(pins)))
+  }
+
+  /// The pin of the last certificate rejected with [TlsVerify::Pinned],
+  /// so it can be accepted later on.
+  pub fn take_rejected_pin(&self) -> Option<String> {
+    match self {
+      TlsVerify::Pinned(verifier) => verifier.take_rejected_pin(),
+      _ => None,
+    }
+  }
+
+  pub fn client_config(&self) -> ClientConfig {
+    match self {
+      TlsVerify::System => {
+        let mut roots = RootCertStore::empty();
+        let native = rustls_native_certs::load_native_certs();
+        for e in native.errors {
+          tracing::warn!("Failed to load native cert | {e:?}");
+        }
+        roots.add_parsable_certificates(native.certs);
+        ClientConfig::builder()
+          .with_root_certificates(roots)
+          .with_no_client_auth()
+      }
+      TlsVerify::Pinned(verifier) => ClientConfig::builder()
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth(),
+      TlsVerify::Insecure => ClientConfig::builder()
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(InsecureVerifier))
+        .with_no_client_auth(),
+    }
+  }
+}
+
+/// Computes the pin of a DER certificate,
+/// `sha256/<base64 hash of the SubjectPublicKeyInfo>`.
+/// Pinning the public key keeps the pin valid
+/// when the certificate is renewed with the same key.
+pub fn certificate_pin(
+  certificate: &CertificateDer<'_>,
+) -> anyhow::Result<String> {
+  let spki = subject_public_key_info(certificate)
+    .context("Failed to parse certificate public key")?;
+  Ok(format!(
+    "{PIN_PREFIX}{}",
+    BASE64_STANDARD.encode(Sha256::digest(spki))
+  ))
+}
+
+/// Extracts the DER SubjectPublicKeyInfo from the
+/// DER certificate TBSCertificate (RFC 5280 4.1).
+fn subject_public_key_info(
+  certificate: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+  let certificate =
+    AnyRef::from_der(certificate).map_err(anyhow::Error::msg)?;
+  let mut certificate = SliceReader::new(certificate.value())
+    .map_err(anyhow::Error::msg)?;
+  let tbs =
+    AnyRef::decode(&mut certificate).map_err(anyhow::Error::msg)?;
+  let mut tbs =
+    SliceReader::new(tbs.value()).map_err(anyhow::Error::msg)?;
+  let mut decode_next =
+    || AnyRef::decode(&mut tbs).map_err(anyhow::Error::msg);
+  // The optional version is explicitly tagged [0],
+  // otherwise the first field is the serialNumber.
+  if decode_next()?.tag().is_context_specific() {
+    // serialNumber
+    decode_next()?;
+  }
+  // signature, issuer, validity, subject
+  for _ in 0..4 {
+    decode_next()?;
+  }
+  decode_next()?.to_der().map_err(anyhow::Error::msg)
+}
+
+#[derive(Debug)]
+pub struct PinnedVerifier {
+  pins: Vec<String>,
+  rejected: Mutex<Option<String>>,
+  provider: Arc<CryptoProvider>,
+}
+
+impl PinnedVerifier {
+  pub fn new(
+    pins: impl IntoIterator<Item = impl Into<String>>,
+  ) -> PinnedVerifier {
+    PinnedVerifier {
+      pins: pins.into_iter().map(Into::into).collect(),
+      rejected: Default::default(),
+      provider: Arc::new(
+        rustls::crypto::aws_lc_rs::default_provider(),
+      ),
+    }
+  }
+
+  pub fn take_rejected_pin(&self) -> Option<String> {
+    self.rejected.lock().ok()?.take()
+  }
+}
+
+impl ServerCertVerifier for PinnedVerifier {
+  fn verify_server_cert(
+    &self,
+    end_entity: &CertificateDer<'_>,
+    _intermediates: &[CertificateDer<'_>],
+    _server_name: &ServerName<'_>,
+    _ocsp_response: &[u8],
+    _now: UnixTime,
+  ) -> Result<ServerCertVerified, rustls::Error> {
+    let pin = certificate_pin(end_entity)
+      .map_err(|e| rustls::Error::General(format!("{e:#}")))?;
+    if self.pins.contains(&pin) {
+      return Ok(ServerCertVerified::assertion());
+    }
+    if let Ok(mut rejected) = self.rejected.lock() {
+      *rejected = Some(pin.clone());
+    }
+    Err(rustls::Error::General(if self.pins.is_empty() {
+      format!("No TLS certificate pins configured | got {pin}")
+    } else {
+      format!("TLS certificate does not match pins | got {pin}")
+    }))
+  }
+
+  fn verify_tls12_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    verify_tls12_signature(
+      message,
+      cert,
+      dss,
+      &self.provider.signature_verification_algorithms,
+    )
+  }
+
+  fn verify_tls13_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    verify_tls13_signature(
+      message,
+      cert,
+      dss,
+      &self.provider.signature_verification_algorithms,
+    )
+  }
+
+  fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+    self
+      .provider
+      .signature_verification_algorithms
+      .supported_schemes()
+  }
+}
+
+#[derive(Debug)]
+pub struct InsecureVerifier;
+
+impl ServerCertVerifier for InsecureVerifier {
+  fn verify_server_cert(
+    &self,
+    _end_entity: &CertificateDer<'_>,
+    _intermediates: &[CertificateDer<'_>],
+    _server_name: &ServerName<'_>,
+    _ocsp_response: &[u8],
+    _now: UnixTime,
+  ) -> Result<ServerCertVerified, rustls::Error> {
+    Ok(ServerCertVerified::assertion())
+  }
+
+  fn verify_tls12_signature(
+    &self,
+    _message: &[u8],
+    _cert: &CertificateDer<'_>,
+    _dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    Ok(HandshakeSignatureValid::assertion())
+  }
+
+  fn verify_tls13_signature(
+    &self,
+    _message: &[u8],
+    _cert: &CertificateDer<'_>,
+    _dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    Ok(HandshakeSignatureValid::assertion())
+  }
+
+  fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+    vec![
+      SignatureScheme::RSA_PKCS1_SHA1,
+      SignatureScheme::ECDSA_SHA1_Legacy,
+      SignatureScheme::RSA_PKCS1_SHA256,
+      SignatureScheme::ECDSA_NISTP256_SHA256,
+      SignatureScheme::RSA_PKCS1_SHA384,
+      SignatureScheme::ECDSA_NISTP384_SHA384,
+      SignatureScheme::RSA_PKCS1_SHA512,
+      SignatureScheme::ECDSA_NISTP521_SHA512,
+      SignatureScheme::RSA_PSS_SHA256,
+      SignatureScheme::RSA_PSS_SHA384,
+      SignatureScheme::RSA_PSS_SHA512,
+      SignatureScheme::ED25519,
+      SignatureScheme::ED448,
+    ]
+  }
+}
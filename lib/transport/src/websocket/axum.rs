@@ -86,13 +86,13 @@ where
     match stream.try_next().await? {
       Some(axum::extract::ws::Message::Binary(bytes)) => {
         return Ok(WebsocketMessage::Message(
-          EncodedTransportMessage::from_vec(bytes.into()),
+          EncodedTransportMessage::from_bytes(bytes),
         ));
       }
       Some(axum::extract::ws::Message::Text(text)) => {
         let bytes: Bytes = text.into();
         return Ok(WebsocketMessage::Message(
-          EncodedTransportMessage::from_vec(bytes.into()),
+          EncodedTransportMessage::from_bytes(bytes),
         ));
       }
       Some(axum::extract::ws::Message::Close(frame)) => {
@@ -3,10 +3,11 @@ use encoding::{Decode as _, Encode as _};
 use noise::key::SpkiPublicKey;
 use periphery_client::transport::{
   EncodedLoginMessage, LoginMessage, TransportMessage,
+  TransportOptions,
 };
 
 use crate::{
-  auth::AUTH_TIMEOUT,
+  auth::auth_timeout,
   websocket::{Websocket, WebsocketExt},
 };
 
@@ -25,7 +26,7 @@ pub trait LoginWebsocketExt: WebsocketExt {
   ) -> impl Future<Output = anyhow::Result<LoginMessage>> + Send {
     async {
       let TransportMessage::Login(message) =
-        self.recv_message().with_timeout(AUTH_TIMEOUT).await?
+        self.recv_message().with_timeout(auth_timeout()).await?
       else {
         return Err(anyhow!(
           "Expected Login message, got other message type"
@@ -35,32 +36,37 @@ pub trait LoginWebsocketExt: WebsocketExt {
     }
   }
 
+  /// Returns the options the server chose for the connection.
   fn recv_login_success(
     &mut self,
-  ) -> impl Future<Output = anyhow::Result<()>> + Send {
+  ) -> impl Future<Output = anyhow::Result<TransportOptions>> + Send
+  {
     async {
-      let LoginMessage::Success = self.recv_login_message().await?
+      let LoginMessage::Success(options) =
+        self.recv_login_message().await?
       else {
         return Err(anyhow!(
           "Expected Login Success message, got other message type"
         ));
       };
-      Ok(())
+      Ok(options)
     }
   }
 
+  /// Returns the nonce, and whether the server uses a psk.
   fn recv_login_nonce(
     &mut self,
-  ) -> impl Future<Output = anyhow::Result<[u8; 32]>> + Send {
+  ) -> impl Future<Output = anyhow::Result<([u8; 32], bool)>> + Send
+  {
     async {
-      let LoginMessage::Nonce(nonce) =
+      let LoginMessage::Nonce { nonce, psk } =
         self.recv_login_message().await?
       else {
         return Err(anyhow!(
           "Expected Login Nonce message, got other message type"
         ));
       };
-      Ok(nonce)
+      Ok((nonce, psk))
     }
   }
 
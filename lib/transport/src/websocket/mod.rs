@@ -1,14 +1,22 @@
 //! Wrappers to normalize behavior of websockets between Tungstenite and Axum
+//!
+//! WebSocket permessage-deflate is not negotiated. The axum `ws` upgrade
+//! doesn't support extensions, and tungstenite rejects frames with RSV1
+//! set without one. Large responses are instead compressed at the
+//! transport level when negotiated during login (see
+//! [TransportCompression][periphery_client::transport::TransportCompression]),
+//! which only applies to Response messages, so Terminal messages are
+//! never delayed by compression.
 
 use anyhow::{Context, anyhow};
 use bytes::Bytes;
 use encoding::{
-  CastBytes as _, Decode as _, Encode, EncodedJsonMessage,
+  CastBytes as _, Decode as _, Encode, EncodedBodyMessage,
   EncodedResponse, JsonMessage,
 };
 use periphery_client::transport::{
   EncodedTransportMessage, RequestMessage, ResponseMessage,
-  TerminalMessage, TransportMessage,
+  TerminalMessage, TransportMessage, TransportOptions,
 };
 use serde::Serialize;
 use tokio_util::sync::CancellationToken;
@@ -18,6 +26,7 @@ use crate::timeout::MaybeWithTimeout;
 
 pub mod axum;
 pub mod login;
+pub mod quic;
 pub mod tungstenite;
 
 /// Flattened websocket message possibilites
@@ -59,12 +68,15 @@ pub trait Websocket: Send {
   >;
 }
 
+/// Used during login, before the framing is agreed on,
+/// so messages use the legacy framing older peers expect.
 pub trait WebsocketExt: Websocket {
   fn send_message(
     &mut self,
     message: impl Encode<EncodedTransportMessage>,
   ) -> impl Future<Output = anyhow::Result<()>> + Send {
-    self.send(message.encode().into_bytes())
+    let bytes = message.encode().into_legacy();
+    async move { self.send(bytes?.into()).await }
   }
 
   /// Looping receiver for websocket messages which only returns on TransportMessage.
@@ -75,7 +87,10 @@ pub trait WebsocketExt: Websocket {
   > {
     MaybeWithTimeout::new(async {
       match self.recv_inner().await? {
-        WebsocketMessage::Message(message) => message.decode(),
+        WebsocketMessage::Message(message) => {
+          EncodedTransportMessage::from_legacy(message.into_bytes())?
+            .decode()
+        }
         WebsocketMessage::Close(frame) => {
           Err(anyhow!("Connection closed with framed: {frame:?}"))
         }
@@ -108,7 +123,33 @@ pub trait WebsocketSenderExt: WebsocketSender + Send {
     &mut self,
     message: impl Encode<EncodedTransportMessage>,
   ) -> impl Future<Output = anyhow::Result<()>> + Send {
-    self.send(message.encode().into_vec().into())
+    self.send(message.encode().into_bytes())
+  }
+
+  /// Sends messages with the framing and compression negotiated
+  /// for the connection, and larger than `max_chunk_bytes`
+  /// split into Chunk messages. 0 sends the message whole.
+  fn send_framed(
+    &mut self,
+    message: EncodedTransportMessage,
+    options: TransportOptions,
+    max_chunk_bytes: usize,
+  ) -> impl Future<Output = anyhow::Result<()>> + Send {
+    async move {
+      if options.header {
+        let message = message.compress(options.compression);
+        for chunk in message.into_chunks(max_chunk_bytes) {
+          self.send(chunk.into_bytes()).await?;
+        }
+      } else {
+        for frame in message
+          .into_legacy_frames(options.compression, max_chunk_bytes)?
+        {
+          self.send(frame).await?;
+        }
+      }
+      Ok(())
+    }
   }
 
   fn send_request<'a, T: Serialize + Send>(
@@ -138,7 +179,7 @@ pub trait WebsocketSenderExt: WebsocketSender + Send {
   fn send_response(
     &mut self,
     channel: Uuid,
-    response: EncodedResponse<EncodedJsonMessage>,
+    response: EncodedResponse<EncodedBodyMessage>,
   ) -> impl Future<Output = anyhow::Result<()>> + Send {
     self.send_message(ResponseMessage::new(channel, response))
   }
@@ -146,7 +187,7 @@ pub trait WebsocketSenderExt: WebsocketSender + Send {
   fn send_terminal(
     &mut self,
     channel: Uuid,
-    data: impl Into<Vec<u8>>,
+    data: impl Into<Bytes>,
   ) -> impl Future<Output = anyhow::Result<()>> + Send {
     self.send_message(TerminalMessage::new(channel, data.into()))
   }
@@ -172,18 +213,22 @@ pub trait WebsocketReceiver: Send {
 
 pub trait WebsocketReceiverExt: WebsocketReceiver {
   /// Looping receiver for websocket messages which only returns on TransportMessage.
+  /// Pass [TransportOptions::header] negotiated for the connection.
   fn recv_message(
     &mut self,
+    header: bool,
   ) -> MaybeWithTimeout<
     impl Future<Output = anyhow::Result<TransportMessage>> + Send,
   > {
-    MaybeWithTimeout::new(async {
+    MaybeWithTimeout::new(async move {
       match self
         .recv()
         .await
         .context("Failed to read websocket message")?
       {
-        WebsocketMessage::Message(message) => message.decode(),
+        WebsocketMessage::Message(message) => {
+          message.normalize_framing(header)?.decode()
+        }
         WebsocketMessage::Close(frame) => {
           Err(anyhow!("Connection closed with framed: {frame:?}"))
         }
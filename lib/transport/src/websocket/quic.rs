@@ -0,0 +1,520 @@
+//! QUIC alternative to the websocket transport.
+//!
+//! Transport messages are sent length prefixed over a single
+//! bidirectional stream. Before the login, the client sends the
+//! host and request target (path + query), mirroring the websocket
+//! upgrade request, and the server replies with a status code.
+//!
+//! ```markdown
+//! | --- u32 --- | --- bytes --- |
+//! | Frame len   | Frame         |
+//! ```
+
+use std::{net::SocketAddr, path::Path, sync::Arc, time::Duration};
+
+use anyhow::{Context, anyhow};
+use axum::http::{HeaderValue, StatusCode};
+use base64::{Engine as _, prelude::BASE64_STANDARD};
+use bytes::Bytes;
+use encoding::CastBytes as _;
+use periphery_client::transport::EncodedTransportMessage;
+use quinn::{
+  ApplicationClose, Connection, ConnectionError, Endpoint, ReadError,
+  ReadExactError, RecvStream, SendStream,
+  crypto::rustls::{QuicClientConfig, QuicServerConfig},
+};
+use rustls::{
+  ServerConfig,
+  pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject as _},
+};
+use serror::AddStatusCodeError as _;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+  auth::{ConnectionIdentifiers, auth_timeout},
+  timeout::MaybeWithTimeout,
+  tls::TlsVerify,
+};
+
+use super::{
+  Websocket, WebsocketMessage, WebsocketReceiver, WebsocketSender,
+};
+
+/// ALPN protocol negotiated by both sides of the connection.
+pub const QUIC_ALPN: &[u8] = b"komodo-transport";
+
+/// Matches the default max websocket message size.
+const MAX_FRAME_BYTES: usize = 64 << 20;
+
+/// Keeps the connection from hitting the idle timeout
+/// when the application level keepalive is disabled.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// TLS exporter label used to derive the connection unique
+/// accept value, which takes the place of Sec-Websocket-Accept.
+const ACCEPT_EXPORTER_LABEL: &[u8] = b"EXPORTER-komodo-quic-accept";
+
+pub struct QuicWebsocket {
+  connection: Connection,
+  send: SendStream,
+  recv: RecvStream,
+  /// Client side: Keeps the local endpoint alive
+  /// for the lifetime of the connection.
+  _endpoint: Option<Endpoint>,
+}
+
+impl Websocket for QuicWebsocket {
+  type CloseFrame = ApplicationClose;
+
+  fn split(self) -> (impl WebsocketSender, impl WebsocketReceiver) {
+    (
+      QuicWebsocketSender {
+        connection: self.connection,
+        send: self.send,
+        _endpoint: self._endpoint,
+      },
+      QuicWebsocketReceiver::new(self.recv),
+    )
+  }
+
+  async fn send(&mut self, bytes: Bytes) -> anyhow::Result<()> {
+    write_frame(&mut self.send, bytes).await
+  }
+
+  async fn close(&mut self) -> anyhow::Result<()> {
+    close(&self.connection, &mut self.send);
+    Ok(())
+  }
+
+  fn recv_inner(
+    &mut self,
+  ) -> MaybeWithTimeout<
+    impl Future<
+      Output = anyhow::Result<WebsocketMessage<Self::CloseFrame>>,
+    > + Send,
+  > {
+    MaybeWithTimeout::new(read_message(&mut self.recv))
+  }
+}
+
+pub struct QuicWebsocketSender {
+  connection: Connection,
+  send: SendStream,
+  _endpoint: Option<Endpoint>,
+}
+
+impl WebsocketSender for QuicWebsocketSender {
+  async fn send(&mut self, bytes: Bytes) -> anyhow::Result<()> {
+    write_frame(&mut self.send, bytes).await
+  }
+
+  async fn close(&mut self) -> anyhow::Result<()> {
+    close(&self.connection, &mut self.send);
+    Ok(())
+  }
+}
+
+pub struct QuicWebsocketReceiver {
+  receiver: RecvStream,
+  cancel: Option<CancellationToken>,
+}
+
+impl QuicWebsocketReceiver {
+  pub fn new(receiver: RecvStream) -> Self {
+    Self {
+      receiver,
+      cancel: None,
+    }
+  }
+}
+
+impl WebsocketReceiver for QuicWebsocketReceiver {
+  type CloseFrame = ApplicationClose;
+
+  fn set_cancel(&mut self, cancel: CancellationToken) {
+    self.cancel = Some(cancel);
+  }
+
+  async fn recv(
+    &mut self,
+  ) -> anyhow::Result<WebsocketMessage<Self::CloseFrame>> {
+    let fut = read_message(&mut self.receiver);
+    if let Some(cancel) = &self.cancel {
+      tokio::select! {
+        res = fut => res,
+        _ = cancel.cancelled() => Err(anyhow!("Cancelled before receive"))
+      }
+    } else {
+      fut.await
+    }
+  }
+}
+
+fn close(connection: &Connection, send: &mut SendStream) {
+  // Stream may already be finished, nothing to do.
+  let _ = send.finish();
+  connection.close(0u32.into(), b"");
+}
+
+async fn write_frame(
+  send: &mut SendStream,
+  bytes: Bytes,
+) -> anyhow::Result<()> {
+  let len = u32::try_from(bytes.len())
+    .context("Message too large for QUIC frame")?;
+  send
+    .write_all_chunks(&mut [
+      Bytes::copy_from_slice(&len.to_be_bytes()),
+      bytes,
+    ])
+    .await
+    .context("Failed to send message over QUIC stream")
+}
+
+async fn read_message(
+  recv: &mut RecvStream,
+) -> anyhow::Result<WebsocketMessage<ApplicationClose>> {
+  let mut len = [0u8; 4];
+  match recv.read_exact(&mut len).await {
+    Ok(()) => {}
+    // Stream finished cleanly between frames.
+    Err(ReadExactError::FinishedEarly(0)) => {
+      return Ok(WebsocketMessage::Closed);
+    }
+    Err(ReadExactError::ReadError(ReadError::ConnectionLost(
+      ConnectionError::ApplicationClosed(close),
+    ))) => return Ok(WebsocketMessage::Close(Some(close))),
+    Err(ReadExactError::ReadError(ReadError::ConnectionLost(
+      ConnectionError::LocallyClosed,
+    ))) => return Ok(WebsocketMessage::Closed),
+    Err(e) => {
+      return Err(
+        anyhow::Error::from(e).context("Failed to read QUIC frame"),
+      );
+    }
+  }
+  let len = u32::from_be_bytes(len) as usize;
+  if len > MAX_FRAME_BYTES {
+    return Err(anyhow!(
+      "QUIC frame of {len} bytes exceeds max of {MAX_FRAME_BYTES} bytes"
+    ));
+  }
+  let mut frame = vec![0u8; len];
+  recv
+    .read_exact(&mut frame)
+    .await
+    .context("Failed to read QUIC frame")?;
+  Ok(WebsocketMessage::Message(
+    EncodedTransportMessage::from_vec(frame),
+  ))
+}
+
+/// Reads a connection handshake frame.
+async fn read_handshake(
+  recv: &mut RecvStream,
+) -> anyhow::Result<Vec<u8>> {
+  match read_message(recv).await? {
+    WebsocketMessage::Message(frame) => Ok(frame.into_vec()),
+    WebsocketMessage::Close(frame) => {
+      Err(anyhow!("Connection closed with frame: {frame:?}"))
+    }
+    WebsocketMessage::Closed => Err(anyhow!("Connection closed")),
+  }
+}
+
+/// Both sides derive the same value from the TLS session,
+/// so the login handshake is bound to this QUIC connection.
+fn compute_accept(connection: &Connection) -> anyhow::Result<String> {
+  let mut accept = [0u8; 32];
+  connection
+    .export_keying_material(&mut accept, ACCEPT_EXPORTER_LABEL, b"")
+    .map_err(|e| {
+      anyhow!("Failed to export keying material | {e:?}")
+    })?;
+  Ok(BASE64_STANDARD.encode(accept))
+}
+
+impl QuicWebsocket {
+  /// Connect to a `quic://host:port/path?query` url.
+  /// Returns the socket along with the accept value
+  /// used for the connection identifiers.
+  pub async fn connect_maybe_tls_insecure(
+    url: &str,
+    insecure: bool,
+  ) -> serror::Result<(Self, HeaderValue)> {
+    Self::connect_with_tls(url, &TlsVerify::from_insecure(insecure))
+      .await
+  }
+
+  /// Connect verifying the server certificate with 'tls'.
+  pub async fn connect_with_tls(
+    url: &str,
+    tls: &TlsVerify,
+  ) -> serror::Result<(Self, HeaderValue)> {
+    let res = Self::connect_inner(url, tls).await.map_err(|e| {
+      e.context(format!("Failed to connect over QUIC | url: {url}"))
+    })?;
+    let (socket, status, message) = res;
+    if status != StatusCode::OK {
+      return Err(anyhow!("{message}").status_code(status));
+    }
+    let accept = compute_accept(&socket.connection)?;
+    let accept = HeaderValue::from_str(&accept)
+      .context("Invalid accept value")?;
+    Ok((socket, accept))
+  }
+
+  async fn connect_inner(
+    url: &str,
+    tls: &TlsVerify,
+  ) -> anyhow::Result<(Self, StatusCode, String)> {
+    let url =
+      ::url::Url::parse(url).context("Failed to parse QUIC url")?;
+    let host = url.host_str().context("url has no host")?.to_string();
+    let port = url.port().context("QUIC url must include port")?;
+    let addr = tokio::net::lookup_host((host.as_str(), port))
+      .await
+      .context("Failed to resolve QUIC address")?
+      .next()
+      .context("QUIC address did not resolve")?;
+
+    let mut endpoint = Endpoint::client(if addr.is_ipv6() {
+      SocketAddr::from(([0u16; 8], 0))
+    } else {
+      SocketAddr::from(([0u8; 4], 0))
+    })
+    .context("Failed to bind QUIC client endpoint")?;
+    endpoint.set_default_client_config(client_config(tls)?);
+
+    let connection = endpoint
+      .connect(addr, &host)
+      .context("Failed to start QUIC connection")?
+      .await
+      .context("Failed to establish QUIC connection")?;
+
+    let (mut send, mut recv) = connection
+      .open_bi()
+      .await
+      .context("Failed to open QUIC stream")?;
+
+    // Host header equivalent includes the port,
+    // to match the websocket connection identifiers.
+    let mut target = url.path().to_string();
+    if let Some(query) = url.query() {
+      target.push('?');
+      target.push_str(query);
+    }
+    write_frame(&mut send, format!("{host}:{port}\n{target}").into())
+      .await
+      .context("Failed to send QUIC connection request")?;
+
+    let response = MaybeWithTimeout::new(read_handshake(&mut recv))
+      .with_timeout(auth_timeout())
+      .await
+      .context("Failed to read QUIC connection response")?;
+    let (status, message) = response
+      .split_first_chunk::<2>()
+      .context("Invalid QUIC connection response")?;
+    let status = StatusCode::from_u16(u16::from_be_bytes(*status))
+      .context("Invalid QUIC connection response status")?;
+    let message = String::from_utf8_lossy(message).into_owned();
+
+    Ok((
+      QuicWebsocket {
+        connection,
+        send,
+        recv,
+        _endpoint: Some(endpoint),
+      },
+      status,
+      message,
+    ))
+  }
+}
+
+fn client_config(
+  tls: &TlsVerify,
+) -> anyhow::Result<quinn::ClientConfig> {
+  let mut crypto = tls.client_config();
+  crypto.alpn_protocols = vec![QUIC_ALPN.to_vec()];
+  let mut config = quinn::ClientConfig::new(Arc::new(
+    QuicClientConfig::try_from(crypto)
+      .context("Invalid QUIC client TLS config")?,
+  ));
+  config.transport_config(Arc::new(transport_config()));
+  Ok(config)
+}
+
+fn transport_config() -> quinn::TransportConfig {
+  let mut config = quinn::TransportConfig::default();
+  config.keep_alive_interval(Some(KEEP_ALIVE_INTERVAL));
+  config
+}
+
+fn server_config(
+  cert_file: &Path,
+  key_file: &Path,
+) -> anyhow::Result<quinn::ServerConfig> {
+  let certs = CertificateDer::pem_file_iter(cert_file)
+    .with_context(|| {
+      format!("Failed to read certs at {cert_file:?}")
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Invalid cert file")?;
+  let key = PrivateKeyDer::from_pem_file(key_file)
+    .with_context(|| format!("Failed to read key at {key_file:?}"))?;
+  let mut crypto = ServerConfig::builder()
+    .with_no_client_auth()
+    .with_single_cert(certs, key)
+    .context("Invalid ssl cert / key")?;
+  crypto.alpn_protocols = vec![QUIC_ALPN.to_vec()];
+  let mut config = quinn::ServerConfig::with_crypto(Arc::new(
+    QuicServerConfig::try_from(crypto)
+      .context("Invalid QUIC server TLS config")?,
+  ));
+  config.transport_config(Arc::new(transport_config()));
+  Ok(config)
+}
+
+/// Server side QUIC listener.
+#[derive(Clone)]
+pub struct QuicServer(Endpoint);
+
+impl QuicServer {
+  pub fn bind(
+    addr: SocketAddr,
+    cert_file: &Path,
+    key_file: &Path,
+  ) -> anyhow::Result<QuicServer> {
+    let config = server_config(cert_file, key_file)?;
+    let endpoint = Endpoint::server(config, addr)
+      .context("Failed to bind QUIC server endpoint")?;
+    Ok(QuicServer(endpoint))
+  }
+
+  /// Swaps in a new cert / key. Only new connections
+  /// use it, existing connections are unaffected.
+  pub fn reload(
+    &self,
+    cert_file: &Path,
+    key_file: &Path,
+  ) -> anyhow::Result<()> {
+    let config = server_config(cert_file, key_file)?;
+    self.0.set_server_config(Some(config));
+    Ok(())
+  }
+
+  /// Returns None once the endpoint is closed.
+  pub async fn accept(&self) -> Option<quinn::Incoming> {
+    self.0.accept().await
+  }
+}
+
+/// The QUIC equivalent of a websocket upgrade request,
+/// read by the server before responding.
+pub struct QuicRequest {
+  /// The host (with port) the client connected to.
+  pub host: String,
+  /// The request path and query.
+  pub target: String,
+  accept: String,
+  socket: QuicWebsocket,
+}
+
+impl QuicRequest {
+  pub async fn read(
+    incoming: quinn::Incoming,
+  ) -> anyhow::Result<Self> {
+    let connection = incoming
+      .await
+      .context("Failed to establish QUIC connection")?;
+    let (send, mut recv) = connection
+      .accept_bi()
+      .await
+      .context("Failed to accept QUIC stream")?;
+    let request = MaybeWithTimeout::new(read_handshake(&mut recv))
+      .with_timeout(auth_timeout())
+      .await
+      .context("Failed to read QUIC connection request")?;
+    let request = String::from_utf8(request)
+      .context("QUIC connection request is not utf8")?;
+    let (host, target) = request
+      .split_once('\n')
+      .context("Invalid QUIC connection request")?;
+    let accept = compute_accept(&connection)?;
+    Ok(QuicRequest {
+      host: host.to_string(),
+      target: target.to_string(),
+      accept,
+      socket: QuicWebsocket {
+        connection,
+        send,
+        recv,
+        _endpoint: None,
+      },
+    })
+  }
+
+  /// Accept the request, returning the socket for the login.
+  pub async fn respond(
+    mut self,
+  ) -> anyhow::Result<(QuicWebsocket, QuicConnectionIdentifiers)> {
+    write_response(&mut self.socket.send, StatusCode::OK, "").await?;
+    Ok((
+      self.socket,
+      QuicConnectionIdentifiers {
+        host: self.host,
+        accept: self.accept,
+      },
+    ))
+  }
+
+  /// Reject the request, closing the connection
+  /// once the client receives the response.
+  pub async fn reject(mut self, status: StatusCode, message: &str) {
+    if let Err(e) =
+      write_response(&mut self.socket.send, status, message).await
+    {
+      tracing::warn!("{e:#}");
+    }
+    let _ = self.socket.send.finish();
+    let _ = tokio::time::timeout(
+      Duration::from_secs(2),
+      self.socket.send.stopped(),
+    )
+    .await;
+    self.socket.connection.close(0u32.into(), b"");
+  }
+}
+
+/// Used to extract owned connection identifiers
+/// in server side QUIC connection handler.
+pub struct QuicConnectionIdentifiers {
+  host: String,
+  accept: String,
+}
+
+impl QuicConnectionIdentifiers {
+  pub fn build<'a>(
+    &'a self,
+    query: &'a [u8],
+  ) -> ConnectionIdentifiers<'a> {
+    ConnectionIdentifiers {
+      host: self.host.as_bytes(),
+      query,
+      accept: self.accept.as_bytes(),
+    }
+  }
+}
+
+async fn write_response(
+  send: &mut SendStream,
+  status: StatusCode,
+  message: &str,
+) -> anyhow::Result<()> {
+  let mut response = status.as_u16().to_be_bytes().to_vec();
+  response.extend_from_slice(message.as_bytes());
+  write_frame(send, response.into())
+    .await
+    .context("Failed to send QUIC connection response")
+}
@@ -9,7 +9,6 @@ use futures_util::{
   stream::{SplitSink, SplitStream},
 };
 use periphery_client::transport::EncodedTransportMessage;
-use rustls::{ClientConfig, client::danger::ServerCertVerifier};
 use serror::AddStatusCodeError;
 use tokio::net::TcpStream;
 use tokio_tungstenite::{
@@ -20,7 +19,7 @@ use tokio_tungstenite::{
 };
 use tokio_util::sync::CancellationToken;
 
-use crate::timeout::MaybeWithTimeout;
+use crate::{timeout::MaybeWithTimeout, tls::TlsVerify};
 
 use super::{
   Websocket, WebsocketMessage, WebsocketReceiver, WebsocketSender,
@@ -104,13 +103,13 @@ where
     match stream.try_next().await? {
       Some(tungstenite::Message::Binary(bytes)) => {
         return Ok(WebsocketMessage::Message(
-          EncodedTransportMessage::from_vec(bytes.into()),
+          EncodedTransportMessage::from_bytes(bytes),
         ));
       }
       Some(tungstenite::Message::Text(text)) => {
         let bytes: Bytes = text.into();
         return Ok(WebsocketMessage::Message(
-          EncodedTransportMessage::from_vec(bytes.into()),
+          EncodedTransportMessage::from_bytes(bytes),
         ));
       }
       Some(tungstenite::Message::Close(frame)) => {
@@ -169,10 +168,29 @@ impl TungsteniteWebsocket {
     url: &str,
     insecure: bool,
   ) -> serror::Result<(Self, HeaderValue)> {
-    if insecure {
-      Self::connect_tls_insecure(url).await
-    } else {
-      Self::connect(url).await
+    Self::connect_with_tls(url, &TlsVerify::from_insecure(insecure))
+      .await
+  }
+
+  /// Connect verifying the server certificate with 'tls'.
+  /// Ignored for `ws://` urls.
+  pub async fn connect_with_tls(
+    url: &str,
+    tls: &TlsVerify,
+  ) -> serror::Result<(Self, HeaderValue)> {
+    match tls {
+      TlsVerify::System => Self::connect(url).await,
+      _ if !url.starts_with("wss") => Self::connect(url).await,
+      tls => {
+        let res = tokio_tungstenite::connect_async_tls_with_config(
+          url,
+          None,
+          false,
+          Some(connector(tls)),
+        )
+        .await;
+        Self::handle_connection_result(url, res)
+      }
     }
   }
 
@@ -183,21 +201,29 @@ impl TungsteniteWebsocket {
     Self::handle_connection_result(url, res)
   }
 
-  pub async fn connect_tls_insecure(
+  /// Tunnels the websocket through a SOCKS5 or HTTP CONNECT proxy.
+  /// See [crate::proxy] for the supported proxy urls.
+  pub async fn connect_via_proxy(
     url: &str,
+    proxy_url: &str,
+    insecure: bool,
   ) -> serror::Result<(Self, HeaderValue)> {
-    let res = tokio_tungstenite::connect_async_tls_with_config(
+    let target = ::url::Url::parse(url)
+      .context("Failed to parse websocket url")?;
+    let host = target.host_str().context("url has no host")?;
+    let port =
+      target.port_or_known_default().context("url has no port")?;
+    let stream =
+      crate::proxy::connect_via_proxy(proxy_url, host, port)
+        .await
+        .with_context(|| {
+          format!("Failed to connect to {host}:{port} through proxy")
+        })?;
+    let res = tokio_tungstenite::client_async_tls_with_config(
       url,
+      stream,
       None,
-      false,
-      Some(Connector::Rustls(Arc::new(
-        ClientConfig::builder()
-          .dangerous()
-          .with_custom_certificate_verifier(Arc::new(
-            InsecureVerifier,
-          ))
-          .with_no_client_auth(),
-      ))),
+      insecure.then(|| connector(&TlsVerify::Insecure)),
     )
     .await;
     Self::handle_connection_result(url, res)
@@ -235,61 +261,6 @@ impl TungsteniteWebsocket {
   }
 }
 
-#[derive(Debug)]
-struct InsecureVerifier;
-
-impl ServerCertVerifier for InsecureVerifier {
-  fn verify_server_cert(
-    &self,
-    _end_entity: &rustls::pki_types::CertificateDer<'_>,
-    _intermediates: &[rustls::pki_types::CertificateDer<'_>],
-    _server_name: &rustls::pki_types::ServerName<'_>,
-    _ocsp_response: &[u8],
-    _now: rustls::pki_types::UnixTime,
-  ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error>
-  {
-    Ok(rustls::client::danger::ServerCertVerified::assertion())
-  }
-
-  fn verify_tls12_signature(
-    &self,
-    _message: &[u8],
-    _cert: &rustls::pki_types::CertificateDer<'_>,
-    _dss: &rustls::DigitallySignedStruct,
-  ) -> Result<
-    rustls::client::danger::HandshakeSignatureValid,
-    rustls::Error,
-  > {
-    Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
-  }
-
-  fn verify_tls13_signature(
-    &self,
-    _message: &[u8],
-    _cert: &rustls::pki_types::CertificateDer<'_>,
-    _dss: &rustls::DigitallySignedStruct,
-  ) -> Result<
-    rustls::client::danger::HandshakeSignatureValid,
-    rustls::Error,
-  > {
-    Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
-  }
-
-  fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
-    vec![
-      rustls::SignatureScheme::RSA_PKCS1_SHA1,
-      rustls::SignatureScheme::ECDSA_SHA1_Legacy,
-      rustls::SignatureScheme::RSA_PKCS1_SHA256,
-      rustls::SignatureScheme::ECDSA_NISTP256_SHA256,
-      rustls::SignatureScheme::RSA_PKCS1_SHA384,
-      rustls::SignatureScheme::ECDSA_NISTP384_SHA384,
-      rustls::SignatureScheme::RSA_PKCS1_SHA512,
-      rustls::SignatureScheme::ECDSA_NISTP521_SHA512,
-      rustls::SignatureScheme::RSA_PSS_SHA256,
-      rustls::SignatureScheme::RSA_PSS_SHA384,
-      rustls::SignatureScheme::RSA_PSS_SHA512,
-      rustls::SignatureScheme::ED25519,
-      rustls::SignatureScheme::ED448,
-    ]
-  }
+fn connector(tls: &TlsVerify) -> Connector {
+  Connector::Rustls(Arc::new(tls.client_config()))
 }